@@ -1,4 +1,5 @@
 mod registered_modules;
+mod types_cli;
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
@@ -60,6 +61,11 @@ enum Commands {
     Check,
     /// Run database migrations and exit (for cloud deployments)
     Migrate,
+    /// Export or import GTS entities of a running server as bundles
+    Types {
+        #[command(subcommand)]
+        command: types_cli::TypesCommand,
+    },
 }
 
 #[tokio::main]
@@ -145,6 +151,7 @@ async fn main() -> Result<()> {
         Commands::Run => run_server(config).await,
         Commands::Check => check_config(&config),
         Commands::Migrate => run_migrate(config).await,
+        Commands::Types { command } => types_cli::run(&config, command).await,
     }
 }
 
@@ -0,0 +1,229 @@
+//! `types export|import`: move GTS entities between environments as bundles,
+//! through the types registry REST API of a running server.
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result, bail};
+use clap::{Args, Subcommand, ValueEnum};
+use modkit::bootstrap::AppConfig;
+use serde_json::Value;
+
+const BUNDLE_PATH: &str = "/types-registry/v1/bundle";
+const DEFAULT_URL: &str = "http://127.0.0.1:8087";
+
+#[derive(Args)]
+pub struct ServerArgs {
+    /// Base URL of the running server (default: the `api_gateway` bind address from the configuration)
+    #[arg(long)]
+    url: Option<String>,
+
+    /// Bearer token for the REST API
+    #[arg(long)]
+    token: Option<String>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Kind {
+    Type,
+    Instance,
+}
+
+#[derive(Subcommand)]
+pub enum TypesCommand {
+    /// Export GTS entities to a bundle
+    Export {
+        #[command(flatten)]
+        server: ServerArgs,
+
+        /// Wildcard pattern for GTS ID matching (e.g. gts.acme.*)
+        #[arg(long)]
+        pattern: Option<String>,
+
+        /// Only export types or only instances
+        #[arg(long, value_enum)]
+        kind: Option<Kind>,
+
+        /// Filter by vendor
+        #[arg(long)]
+        vendor: Option<String>,
+
+        /// Filter by package
+        #[arg(long)]
+        package: Option<String>,
+
+        /// Filter by namespace
+        #[arg(long)]
+        namespace: Option<String>,
+
+        /// File to write the bundle to (default: stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Import a bundle, failing if any entity is rejected
+    Import {
+        #[command(flatten)]
+        server: ServerArgs,
+
+        /// Bundle file to import
+        input: PathBuf,
+
+        /// Validate and report per-entity results without applying anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+/// Base URL of the server: `--url`, or the `api_gateway` bind address.
+fn base_url(config: &AppConfig, server: &ServerArgs) -> String {
+    if let Some(url) = &server.url {
+        return url.trim_end_matches('/').to_owned();
+    }
+    config
+        .modules
+        .get("api_gateway")
+        .and_then(|m| m.pointer("/config/bind_addr"))
+        .and_then(Value::as_str)
+        .map_or_else(
+            || DEFAULT_URL.to_owned(),
+            |addr| format!("http://{}", addr.replace("0.0.0.0", "127.0.0.1")),
+        )
+}
+
+fn request(
+    client: &reqwest::Client,
+    method: reqwest::Method,
+    url: &str,
+    server: &ServerArgs,
+) -> reqwest::RequestBuilder {
+    let builder = client.request(method, url);
+    match &server.token {
+        Some(token) => builder.bearer_auth(token),
+        None => builder,
+    }
+}
+
+async fn check_status(response: reqwest::Response) -> Result<reqwest::Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body = response.text().await.unwrap_or_default();
+    let detail = serde_json::from_str::<Value>(&body)
+        .ok()
+        .and_then(|problem| {
+            problem
+                .get("detail")
+                .and_then(Value::as_str)
+                .map(ToOwned::to_owned)
+        })
+        .unwrap_or(body);
+    bail!("server responded {status}: {detail}")
+}
+
+pub async fn run(config: &AppConfig, command: &TypesCommand) -> Result<()> {
+    let client = reqwest::Client::new();
+    match command {
+        TypesCommand::Export {
+            server,
+            pattern,
+            kind,
+            vendor,
+            package,
+            namespace,
+            output,
+        } => {
+            let mut query: Vec<(&str, String)> = Vec::new();
+            let filters = [
+                ("pattern", pattern),
+                ("vendor", vendor),
+                ("package", package),
+                ("namespace", namespace),
+            ];
+            for (name, value) in filters {
+                if let Some(value) = value {
+                    query.push((name, value.clone()));
+                }
+            }
+            if let Some(kind) = kind {
+                query.push(("is_schema", matches!(kind, Kind::Type).to_string()));
+            }
+
+            let url = format!("{}{BUNDLE_PATH}", base_url(config, server));
+            let response = request(&client, reqwest::Method::GET, &url, server)
+                .query(&query)
+                .send()
+                .await
+                .with_context(|| format!("failed to reach {url}"))?;
+            let bundle = check_status(response).await?.text().await?;
+
+            match output {
+                Some(path) => {
+                    std::fs::write(path, &bundle)
+                        .with_context(|| format!("failed to write {}", path.display()))?;
+                    // Header and trailer lines surround the entities
+                    let entities = bundle.lines().count().saturating_sub(2);
+                    println!("Exported {entities} entities to {}", path.display());
+                }
+                None => print!("{bundle}"),
+            }
+            Ok(())
+        }
+        TypesCommand::Import {
+            server,
+            input,
+            dry_run,
+        } => {
+            let bundle = std::fs::read(input)
+                .with_context(|| format!("failed to read {}", input.display()))?;
+
+            let url = format!("{}{BUNDLE_PATH}", base_url(config, server));
+            let response = request(&client, reqwest::Method::POST, &url, server)
+                .query(&[("dry_run", dry_run.to_string())])
+                .header(reqwest::header::CONTENT_TYPE, "application/octet-stream")
+                .body(bundle)
+                .send()
+                .await
+                .with_context(|| format!("failed to reach {url}"))?;
+            let report: Value = check_status(response).await?.json().await?;
+            print_report(&report, *dry_run)
+        }
+    }
+}
+
+fn print_report(report: &Value, dry_run: bool) -> Result<()> {
+    let results = report
+        .get("results")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    for result in results {
+        let gts_id = result
+            .get("gts_id")
+            .or_else(|| result.pointer("/entity/gts_id"))
+            .and_then(Value::as_str)
+            .unwrap_or("<unknown>");
+        match result.get("error") {
+            Some(error) => {
+                let error = error
+                    .as_str()
+                    .map_or_else(|| error.to_string(), ToOwned::to_owned);
+                println!("FAILED {gts_id}: {error}");
+            }
+            None => println!("ok     {gts_id}"),
+        }
+    }
+
+    let count = |field: &str| {
+        report
+            .pointer(&format!("/summary/{field}"))
+            .and_then(Value::as_u64)
+            .unwrap_or_default()
+    };
+    let (succeeded, failed) = (count("succeeded"), count("failed"));
+    let mode = if dry_run { "Dry run" } else { "Import" };
+    println!("{mode}: {succeeded} succeeded, {failed} failed");
+    if failed > 0 {
+        bail!("{failed} entities were rejected");
+    }
+    Ok(())
+}
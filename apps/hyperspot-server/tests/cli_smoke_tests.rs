@@ -742,3 +742,27 @@ fn test_cli_dump_flags_require_config() {
         );
     }
 }
+
+#[test]
+fn test_cli_types_subcommand_help() {
+    let output = run_hyperspot_server(&["types", "--help"]);
+    assert!(output.status.success(), "types --help should succeed");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("export"), "Should list 'export'");
+    assert!(stdout.contains("import"), "Should list 'import'");
+
+    let output = run_hyperspot_server(&["types", "import", "--help"]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("--dry-run"));
+}
+
+#[test]
+fn test_cli_types_import_missing_file() {
+    let output = run_hyperspot_server(&["types", "import", "/nonexistent/bundle.jsonl"]);
+    assert!(
+        !output.status.success(),
+        "Importing a missing file should fail"
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("failed to read"), "stderr: {stderr}");
+}
@@ -11,15 +11,17 @@ use modkit_odata::filter::FilterField as _;
 ///
 /// This struct defines which fields can be used in `OData` $filter queries
 /// for user resources. The field names match the wire format.
+/// Fields marked `searchable` are matched by `$search`; `ops(...)` enables the
+/// listed operators and functions (e.g. `tolower(email) eq 'a@b.c'`).
 #[derive(ODataFilterable)]
 pub struct UserQuery {
     #[odata(filter(kind = "Uuid"))]
     pub id: Uuid,
 
-    #[odata(filter(kind = "String"))]
+    #[odata(filter(kind = "String", searchable, ops(in, tolower)))]
     pub email: String,
 
-    #[odata(filter(kind = "DateTimeUtc"))]
+    #[odata(filter(kind = "DateTimeUtc", ops(year, month, day, now)))]
     pub created_at: OffsetDateTime,
 }
 
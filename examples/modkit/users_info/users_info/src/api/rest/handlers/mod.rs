@@ -23,6 +23,9 @@ mod users;
 
 // ==================== User Handlers ====================
 
+/// Navigation property of the user list that `$expand` can inline
+pub(crate) const EXPAND_ADDRESS: &str = "address";
+
 /// List users with cursor-based pagination and optional field projection via $select
 #[tracing::instrument(
     skip(svc, query, ctx),
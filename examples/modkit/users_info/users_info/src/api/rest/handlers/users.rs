@@ -3,8 +3,9 @@ use axum::response::{IntoResponse, Response};
 use uuid::Uuid;
 
 use super::{
-    ApiResult, Json, JsonBody, JsonPage, SecurityContext, UpdateUserReq, UserDto, UserFullDto,
-    apply_select, created_json, info, no_content, page_to_projected_json,
+    AddressDto, ApiResult, EXPAND_ADDRESS, Json, JsonBody, JsonPage, SecurityContext,
+    UpdateUserReq, UserDto, UserFullDto, apply_select, created_json, info, no_content,
+    page_to_projected_json,
 };
 use crate::module::ConcreteAppServices;
 
@@ -19,9 +20,27 @@ pub(super) async fn list_users(
     );
 
     let page = svc.users.list_users_page(&ctx, &query).await?;
+    let addresses = if query.expands(EXPAND_ADDRESS) {
+        let ids: Vec<Uuid> = page.items.iter().map(|user| user.id).collect();
+        Some(svc.users.user_addresses(&ctx, &ids).await?)
+    } else {
+        None
+    };
     let page = page.map_items(UserDto::from);
 
-    Ok(Json(page_to_projected_json(&page, query.selected_fields())))
+    let mut projected = page_to_projected_json(&page, query.selected_fields());
+    if let Some(mut addresses) = addresses {
+        for (item, user) in projected.items.iter_mut().zip(&page.items) {
+            let address = addresses.remove(&user.id).map(AddressDto::from);
+            if let Some(object) = item.as_object_mut() {
+                object.insert(
+                    EXPAND_ADDRESS.to_owned(),
+                    serde_json::to_value(address).unwrap_or_default(),
+                );
+            }
+        }
+    }
+    Ok(Json(projected))
 }
 
 pub(super) async fn get_user(
@@ -29,6 +29,9 @@ pub(super) fn register_user_routes(mut router: Router, openapi: &dyn OpenApiRegi
         .with_odata_filter::<UserFilterField>()
         .with_odata_select()
         .with_odata_orderby::<UserFilterField>()
+        .with_odata_search::<UserFilterField>()
+        .with_odata_count()
+        .with_odata_expand(&[handlers::EXPAND_ADDRESS])
         .error_400(openapi)
         .error_500(openapi)
         .register(router, openapi);
@@ -1,8 +1,10 @@
 use async_trait::async_trait;
+use std::collections::HashMap;
+
 use modkit_db::secure::DBRunner;
 use modkit_odata::{ODataQuery, Page};
 use modkit_security::AccessScope;
-use user_info_sdk::User;
+use user_info_sdk::{Address, User};
 use uuid::Uuid;
 
 use crate::domain::error::DomainError;
@@ -72,4 +74,14 @@ pub trait UsersRepository: Send + Sync {
         scope: &AccessScope,
         email: &str,
     ) -> Result<u64, DomainError>;
+
+    /// Addresses of the given users, keyed by user ID (`$expand=address`).
+    ///
+    /// Users outside the scope, or whose address is outside it, have no entry.
+    async fn addresses_of<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        user_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, Address>, DomainError>;
 }
@@ -47,6 +47,7 @@ use crate::domain::ports::{AuditPort, EventPublisher};
 use crate::domain::repos::{AddressesRepository, CitiesRepository, UsersRepository};
 use modkit_db::DBProvider;
 use modkit_db::odata::LimitCfg;
+use modkit_db::odata::pager::DEFAULT_MAX_COUNT;
 use modkit_security::{PolicyEngineRef, SecurityContext};
 use tenant_resolver_sdk::{TenantFilter, TenantResolverGatewayClient, TenantStatus};
 use uuid::Uuid;
@@ -105,6 +106,7 @@ impl ServiceConfig {
         LimitCfg {
             default: u64::from(self.default_page_size),
             max: u64::from(self.max_page_size),
+            max_count: DEFAULT_MAX_COUNT,
         }
     }
 }
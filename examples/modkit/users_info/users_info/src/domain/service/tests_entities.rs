@@ -175,3 +175,57 @@ async fn address_crud_and_scope() {
             .is_err()
     );
 }
+
+#[tokio::test]
+async fn user_addresses_are_scoped() {
+    let db = inmem_db().await;
+    let tenant1 = Uuid::new_v4();
+    let tenant2 = Uuid::new_v4();
+    let with_address = Uuid::new_v4();
+    let without_address = Uuid::new_v4();
+    let conn = db.conn().unwrap();
+    seed_user(&conn, with_address, tenant1, "a@example.com", "A").await;
+    seed_user(&conn, without_address, tenant1, "b@example.com", "B").await;
+
+    let services = build_services(db.clone(), ServiceConfig::default());
+    let ctx1 = ctx_allow_tenants(&[tenant1]);
+    let ctx2 = ctx_allow_tenants(&[tenant2]);
+
+    let city = services
+        .cities
+        .create_city(
+            &ctx1,
+            NewCity {
+                id: None,
+                tenant_id: tenant1,
+                name: "C".to_string(),
+                country: "K".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+    let address = services
+        .addresses
+        .create_address(
+            &ctx1,
+            NewAddress {
+                id: None,
+                tenant_id: tenant1,
+                user_id: with_address,
+                city_id: city.id,
+                street: "Main St".to_string(),
+                postal_code: "12345".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+
+    let ids = [with_address, without_address];
+    let found = services.users.user_addresses(&ctx1, &ids).await.unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[&with_address].id, address.id);
+
+    // Other tenants see neither the users nor their addresses
+    let found = services.users.user_addresses(&ctx2, &ids).await.unwrap();
+    assert!(found.is_empty());
+}
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::instrument;
 
@@ -11,7 +12,7 @@ use modkit_odata::{ODataQuery, Page};
 use modkit_security::{PolicyEngineRef, SecurityContext};
 use tenant_resolver_sdk::TenantResolverGatewayClient;
 use time::OffsetDateTime;
-use user_info_sdk::{NewUser, User, UserFull, UserPatch};
+use user_info_sdk::{Address, NewUser, User, UserFull, UserPatch};
 use uuid::Uuid;
 
 /// Users service.
@@ -132,6 +133,27 @@ impl<R: UsersRepository + 'static, CR: CitiesRepository, AR: AddressesRepository
         Ok(page)
     }
 
+    /// Addresses of the given users, keyed by user ID (`$expand=address`).
+    ///
+    /// Both the users and their addresses are filtered by the caller's scope.
+    #[instrument(skip(self, ctx, user_ids), fields(users = user_ids.len()))]
+    pub async fn user_addresses(
+        &self,
+        ctx: &SecurityContext,
+        user_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, Address>, DomainError> {
+        let conn = self.db.conn().map_err(DomainError::from)?;
+
+        let tenant_ids = super::resolve_accessible_tenants(self.resolver.as_ref(), ctx).await?;
+        let scope = ctx
+            .scope(self.policy_engine.clone())
+            .include_accessible_tenants(tenant_ids)
+            .prepare()
+            .await?;
+
+        self.repo.addresses_of(&conn, &scope, user_ids).await
+    }
+
     /// Create a new user.
     #[allow(clippy::cognitive_complexity)]
     #[instrument(
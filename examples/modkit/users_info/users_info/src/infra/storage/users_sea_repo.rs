@@ -1,10 +1,12 @@
 use async_trait::async_trait;
+use std::collections::HashMap;
 
 use crate::infra::storage::db::db_err;
+use crate::infra::storage::entity::address::Entity as AddressEntity;
 use crate::infra::storage::entity::user::{ActiveModel as UserAM, Column, Entity as UserEntity};
 use crate::infra::storage::odata_mapper::UserODataMapper;
 use crate::{domain::error::DomainError, domain::repos::UsersRepository};
-use modkit_db::odata::{LimitCfg, paginate_odata};
+use modkit_db::odata::{LimitCfg, expand_related, paginate_odata};
 use modkit_db::secure::{
     DBRunner, SecureDeleteExt, SecureEntityExt, secure_insert, secure_update_with_scope,
 };
@@ -12,8 +14,8 @@ use modkit_odata::{ODataQuery, Page, SortDir};
 use modkit_security::AccessScope;
 use sea_orm::sea_query::Expr;
 use sea_orm::{EntityTrait, QueryFilter, Set};
-use user_info_sdk::User;
 use user_info_sdk::odata::UserFilterField;
+use user_info_sdk::{Address, User};
 use uuid::Uuid;
 
 /// ORM-based implementation of the `UsersRepository` trait.
@@ -172,4 +174,28 @@ impl UsersRepository for OrmUsersRepository {
             .map_err(db_err)?;
         Ok(count)
     }
+
+    async fn addresses_of<C: DBRunner>(
+        &self,
+        conn: &C,
+        scope: &AccessScope,
+        user_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, Address>, DomainError> {
+        let rows = expand_related::<UserEntity, _, _, _>(
+            conn,
+            scope,
+            Column::Id,
+            user_ids.iter().copied(),
+            AddressEntity,
+        )
+        .await
+        .map_err(db_err)?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|(user, addresses)| {
+                let address = addresses.into_iter().next()?;
+                Some((user.id, address.into()))
+            })
+            .collect())
+    }
 }
@@ -58,6 +58,8 @@ impl TenantResolverPluginClient for Service {
                 next_cursor: None,
                 prev_cursor: None,
                 limit,
+                total_count: None,
+                total_count_capped: false,
             },
         ))
     }
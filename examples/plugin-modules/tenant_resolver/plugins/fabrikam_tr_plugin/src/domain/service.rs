@@ -310,6 +310,8 @@ impl TenantResolverPluginClient for Service {
                 next_cursor,
                 prev_cursor: None,
                 limit,
+                total_count: None,
+                total_count_capped: false,
             },
         ))
     }
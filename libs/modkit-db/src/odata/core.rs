@@ -16,6 +16,7 @@ use thiserror::Error;
 use modkit_odata::filter::FieldKind;
 
 use crate::odata::LimitCfg;
+use crate::odata::sea_orm_filter::count_total;
 use crate::secure::{DBRunner, DBRunnerInternal, SeaOrmRunner};
 
 /// Type alias for cursor extraction function to reduce type complexity
@@ -794,7 +795,7 @@ pub async fn paginate_with_odata<E, D, F, C>(
     q: &ODataQuery,
     fmap: &FieldMap<E>,
     tiebreaker: (&str, SortDir), // e.g. ("id", SortDir::Desc)
-    limit_cfg: LimitCfg,         // e.g. { default: 25, max: 1000 }
+    limit_cfg: LimitCfg,         // e.g. { default: 25, max: 1000, max_count: 10_000 }
     model_to_domain: F,
 ) -> Result<Page<D>, ODataError>
 where
@@ -837,6 +838,20 @@ where
         );
     }
 
+    // FieldMap has no notion of searchable fields; use `paginate_odata` for $search
+    if q.search.is_some() {
+        return Err(ODataError::InvalidSearch(
+            "this resource does not support $search".to_owned(),
+        ));
+    }
+
+    // Count before cursor and limit are applied
+    let total = if q.count {
+        Some(count_total(s.clone(), conn, limit_cfg.max_count).await?)
+    } else {
+        None
+    };
+
     // Check if we're paginating backward
     let is_backward = q.cursor.as_ref().is_some_and(|c| c.d == "bwd");
 
@@ -932,6 +947,8 @@ where
             next_cursor,
             prev_cursor,
             limit,
+            total_count: total.map(|(n, _)| n),
+            total_count_capped: total.is_some_and(|(_, capped)| capped),
         },
     })
 }
@@ -0,0 +1,70 @@
+//! One-level `$expand` on top of Secure ORM relations.
+//!
+//! `$expand` is resolved after the page has been fetched: the primary rows of the
+//! page are selected again under the caller's `AccessScope`, together with their
+//! related rows via `SecureSelect::expand_with_related`, which applies the same
+//! scope to the related entity in the join condition, so that rows without related
+//! rows in scope are still returned. Only one level of navigation is supported; the
+//! HTTP layer rejects nested paths and expand options.
+//!
+//! # Example
+//!
+//! ```ignore
+//! query.validate_expand(&["addresses"])?;
+//!
+//! let page = paginate_odata::<UserFilterField, UserODataMapper, _, _, _, _>(/* ... */).await?;
+//! if query.expands("addresses") {
+//!     let rows = expand_related(
+//!         conn,
+//!         scope,
+//!         user::Column::Id,
+//!         page.items.iter().map(|u| u.id),
+//!         address::Entity,
+//!     )
+//!     .await?;
+//!     // attach `rows` to the page items by key
+//! }
+//! ```
+
+use modkit_odata::Error as ODataError;
+use sea_orm::{ColumnTrait, Condition, EntityTrait, Related, sea_query::Expr};
+
+use crate::secure::{AccessScope, DBRunner, ScopableEntity, SecureEntityExt};
+
+/// Load the rows of `E` whose `key_col` is in `keys`, each with its related `R` rows.
+///
+/// Both `E` and `R` are filtered by `scope`, so an expansion never exposes related
+/// rows the caller could not list directly. Rows of `E` outside the scope are omitted;
+/// rows of `E` in scope are returned even if none of their related rows are.
+///
+/// # Errors
+/// Returns `ODataError::Db` if the database query fails.
+pub async fn expand_related<E, R, C, K>(
+    conn: &C,
+    scope: &AccessScope,
+    key_col: E::Column,
+    keys: impl IntoIterator<Item = K>,
+    related: R,
+) -> Result<Vec<(E::Model, Vec<R::Model>)>, ODataError>
+where
+    E: ScopableEntity + EntityTrait + Related<R>,
+    E::Column: ColumnTrait + Copy,
+    R: ScopableEntity + EntityTrait,
+    R::Column: ColumnTrait + Copy,
+    C: DBRunner,
+    K: Into<sea_orm::Value>,
+{
+    let keys: Vec<sea_orm::Value> = keys.into_iter().map(Into::into).collect();
+    if keys.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    E::find()
+        .secure()
+        .scope_with(scope)
+        .filter(Condition::all().add(Expr::col((E::default(), key_col)).is_in(keys)))
+        .expand_with_related(related)
+        .all(conn)
+        .await
+        .map_err(|e| ODataError::Db(e.to_string()))
+}
@@ -18,6 +18,8 @@
 //! - `core`: Core `OData` to `SeaORM` translation (filters, cursors, ordering) - legacy `FieldMap` based
 //! - `sea_orm_filter`: Type-safe mapping from `FilterNode<F>` to `SeaORM` conditions
 //! - `pager`: Fluent builder for secure + `OData` pagination
+//! - `expand`: Scoped one-level `$expand` via related entities
+//! - `apply`: `$apply` grouping and aggregation over scoped selects
 
 // Core OData functionality (legacy FieldMap-based)
 mod core;
@@ -28,11 +30,20 @@ pub mod sea_orm_filter;
 // Fluent pagination builder
 pub mod pager;
 
+// One-level $expand via SecureSelect::find_with_related
+pub mod expand;
+
+// $apply groupby/aggregate as grouped queries
+pub mod apply;
+
 // Re-export all public items from core (legacy API)
 pub use core::*;
 
 // Re-export SeaORM filter mapping and pagination
 pub use sea_orm_filter::{
     FieldToColumn, LimitCfg, ODataFieldMapping, encode_cursor_value, filter_node_to_condition,
-    paginate_odata, parse_cursor_value,
+    filter_node_to_condition_for, paginate_odata, parse_cursor_value,
 };
+
+pub use apply::aggregate_odata;
+pub use expand::expand_related;
@@ -101,6 +101,7 @@
 //!
 //! - Uses cursor-based pagination for efficient large dataset traversal
 //! - Fetches limit+1 rows to detect "has more" without separate COUNT query
+//! - Runs a capped COUNT query only when the client asks for `$count=true`
 //! - Applies filters at the database level (not in application memory)
 //! - Supports indexed columns via field mappings for optimal query performance
 
@@ -110,6 +111,9 @@ use modkit_odata::{Error as ODataError, ODataQuery, Page, SortDir};
 use modkit_security::AccessScope;
 use sea_orm::{ColumnTrait, EntityTrait};
 
+/// Default cap for `$count=true`, see [`OPager::count_cap`]
+pub const DEFAULT_MAX_COUNT: u64 = 10_000;
+
 /// Minimal fluent builder for Secure + `OData` pagination.
 ///
 /// This builder combines security-scoped queries with `OData` pagination
@@ -136,6 +140,7 @@ use sea_orm::{ColumnTrait, EntityTrait};
 ///
 /// - Tiebreaker: `("id", SortDir::Desc)` - ensures stable pagination
 /// - Limits: `{ default: 25, max: 1000 }` - reasonable defaults for most APIs
+/// - Count cap: [`DEFAULT_MAX_COUNT`] rows for `$count=true`
 #[must_use]
 pub struct OPager<'a, E, C>
 where
@@ -183,6 +188,7 @@ where
             limits: LimitCfg {
                 default: 25,
                 max: 1000,
+                max_count: DEFAULT_MAX_COUNT,
             },
         }
     }
@@ -224,7 +230,25 @@ where
     /// pager.limits(10, 100)  // Smaller pages for this endpoint
     /// ```
     pub fn limits(mut self, default: u64, max: u64) -> Self {
-        self.limits = LimitCfg { default, max };
+        self.limits = LimitCfg {
+            default,
+            max,
+            ..self.limits
+        };
+        self
+    }
+
+    /// Override the maximum number of rows counted for `$count=true` (default: 10 000).
+    ///
+    /// Counting stops at the cap; `PageInfo::total_count_capped` is set when it is reached.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// pager.count_cap(1_000)
+    /// ```
+    pub fn count_cap(mut self, max_count: u64) -> Self {
+        self.limits.max_count = max_count;
         self
     }
 
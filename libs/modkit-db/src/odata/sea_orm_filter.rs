@@ -8,15 +8,17 @@ use crate::secure::{Scoped, SecureSelect};
 use bigdecimal::ToPrimitive;
 use chrono::SecondsFormat;
 use modkit_odata::filter::{
-    FieldKind, FilterField, FilterNode, FilterOp, ODataValue, convert_expr_to_filter_node,
+    ElementPredicate, FieldFunction, FieldKind, FilterField, FilterNode, FilterOp, LambdaOp,
+    ODataValue, convert_expr_to_filter_node,
 };
+use modkit_odata::search::search_to_filter_node;
 use modkit_odata::{CursorV1, Error as ODataError, ODataOrderBy, Page, PageInfo, SortDir};
 use sea_orm::{
-    Condition, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
-    sea_query::{Expr, Order},
+    Condition, DbBackend, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
+    sea_query::{BinOper, Expr, Func, Order, SimpleExpr},
 };
 
-use crate::secure::{DBRunner, DBRunnerInternal, SeaOrmRunner};
+use crate::secure::{DBRunner, DBRunnerInternal, SeaOrmRunner, count_select_up_to};
 
 /// Trait for mapping DTO filter fields to `SeaORM` columns.
 ///
@@ -120,6 +122,10 @@ pub trait ODataFieldMapping<F: FilterField>: FieldToColumn<F> {
 /// all standard `OData` operations. Concrete modules only need to implement
 /// `FieldToColumn` to map their DTO fields to database columns.
 ///
+/// Date-part functions (`year`, `month`, `day`) and `any`/`all` lambdas render
+/// backend-specific SQL; use [`filter_node_to_condition_for`] for filters that
+/// may contain them.
+///
 /// # Type Parameters
 ///
 /// - `F`: The `FilterField` implementation (generated by `#[derive(ODataFilterable)]`)
@@ -144,6 +150,35 @@ pub trait ODataFieldMapping<F: FilterField>: FieldToColumn<F> {
 /// # Errors
 /// Returns an error string if the filter contains unsupported operations or invalid values.
 pub fn filter_node_to_condition<F, M>(filter: &FilterNode<F>) -> Result<Condition, String>
+where
+    F: FilterField,
+    M: FieldToColumn<F>,
+{
+    node_to_condition::<F, M>(filter, None)
+}
+
+/// Convert a `FilterNode`<F> to a `SeaORM` Condition for a specific database backend.
+///
+/// Supports everything [`filter_node_to_condition`] does, plus date-part functions
+/// and `any`/`all` lambdas over JSON array columns.
+///
+/// # Errors
+/// Returns an error string if the filter contains unsupported operations or invalid values.
+pub fn filter_node_to_condition_for<F, M>(
+    filter: &FilterNode<F>,
+    backend: DbBackend,
+) -> Result<Condition, String>
+where
+    F: FilterField,
+    M: FieldToColumn<F>,
+{
+    node_to_condition::<F, M>(filter, Some(backend))
+}
+
+fn node_to_condition<F, M>(
+    filter: &FilterNode<F>,
+    backend: Option<DbBackend>,
+) -> Result<Condition, String>
 where
     F: FilterField,
     M: FieldToColumn<F>,
@@ -163,15 +198,48 @@ where
             };
 
             children.iter().try_fold(base, |acc, child| {
-                let child_cond = filter_node_to_condition::<F, M>(child)?;
+                let child_cond = node_to_condition::<F, M>(child, backend)?;
                 Ok(acc.add(child_cond))
             })
         }
         FilterNode::Not(inner) => {
             // FIXED: Call .not() AFTER adding the inner condition
-            let inner_cond = filter_node_to_condition::<F, M>(inner)?;
+            let inner_cond = node_to_condition::<F, M>(inner, backend)?;
             Ok(Condition::all().add(inner_cond).not())
         }
+        FilterNode::In { field, values } => {
+            let column = M::map_field(*field);
+            if values.is_empty() {
+                // IN () → always false
+                return Ok(Condition::all().add(Expr::value(1).eq(0)));
+            }
+            let values = values
+                .iter()
+                .map(odata_value_to_sea_value)
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Condition::all().add(Expr::col(column).is_in(values)))
+        }
+        FilterNode::Function {
+            func,
+            field,
+            op,
+            value,
+        } => {
+            let column = M::map_field(*field);
+            let lhs = field_function_expr(column, *func, backend)?;
+            Ok(Condition::all().add(compare_expr(Expr::expr(lhs), *op, value)?))
+        }
+        FilterNode::Lambda {
+            field,
+            op,
+            predicate,
+        } => {
+            let backend = backend.ok_or_else(|| {
+                format!("'{op}' requires a database backend; use filter_node_to_condition_for")
+            })?;
+            let column = M::map_field(*field);
+            build_lambda_condition(column, field.kind(), *op, predicate.as_ref(), backend)
+        }
     }
 }
 
@@ -186,9 +254,6 @@ fn build_binary_condition<C>(
 where
     C: sea_orm::Iden + sea_orm::ColumnTrait + sea_orm::IntoSimpleExpr + Clone + 'static,
 {
-    // Convert ODataValue to sea_orm::Value
-    let sea_value = odata_value_to_sea_value(value)?;
-
     // Handle NULL specially
     if matches!(value, ODataValue::Null) {
         return Ok(match op {
@@ -198,32 +263,167 @@ where
         });
     }
 
-    // Build the expression based on the operator
-    let expr = match op {
-        FilterOp::Eq => Expr::col(column).eq(sea_value),
-        FilterOp::Ne => Expr::col(column).ne(sea_value),
-        FilterOp::Gt => Expr::col(column).gt(sea_value),
-        FilterOp::Ge => Expr::col(column).gte(sea_value),
-        FilterOp::Lt => Expr::col(column).lt(sea_value),
-        FilterOp::Le => Expr::col(column).lte(sea_value),
+    Ok(Condition::all().add(compare_expr(Expr::col(column), op, value)?))
+}
+
+/// Apply a comparison, string-match or bit-flag operator to `lhs`.
+fn compare_expr(lhs: Expr, op: FilterOp, value: &ODataValue) -> Result<SimpleExpr, String> {
+    // Convert ODataValue to sea_orm::Value
+    let sea_value = odata_value_to_sea_value(value)?;
+
+    Ok(match op {
+        FilterOp::Eq => lhs.eq(sea_value),
+        FilterOp::Ne => lhs.ne(sea_value),
+        FilterOp::Gt => lhs.gt(sea_value),
+        FilterOp::Ge => lhs.gte(sea_value),
+        FilterOp::Lt => lhs.lt(sea_value),
+        FilterOp::Le => lhs.lte(sea_value),
         FilterOp::Contains => {
             let s = extract_string(value)?;
-            Expr::col(column).like(format!("%{}%", escape_like(&s)))
+            lhs.like(format!("%{}%", escape_like(&s)))
         }
         FilterOp::StartsWith => {
             let s = extract_string(value)?;
-            Expr::col(column).like(format!("{}%", escape_like(&s)))
+            lhs.like(format!("{}%", escape_like(&s)))
         }
         FilterOp::EndsWith => {
             let s = extract_string(value)?;
-            Expr::col(column).like(format!("%{}", escape_like(&s)))
+            lhs.like(format!("%{}", escape_like(&s)))
         }
+        FilterOp::Has => Expr::expr(lhs.binary(BinOper::BitAnd, sea_value.clone())).eq(sea_value),
         FilterOp::And | FilterOp::Or => {
             return Err(format!("Logical operator {op:?} in binary context"));
         }
+    })
+}
+
+/// SQL for `func(column)`.
+pub(super) fn field_function_expr<C>(
+    column: C,
+    func: FieldFunction,
+    backend: Option<DbBackend>,
+) -> Result<SimpleExpr, String>
+where
+    C: sea_orm::Iden + sea_orm::ColumnTrait + sea_orm::IntoSimpleExpr + Clone + 'static,
+{
+    let part = match func {
+        FieldFunction::ToLower => return Ok(Func::lower(Expr::col(column)).into()),
+        FieldFunction::ToUpper => return Ok(Func::upper(Expr::col(column)).into()),
+        // CHAR_LENGTH, or LENGTH on SQLite: characters, not bytes
+        FieldFunction::Length => return Ok(Func::char_length(Expr::col(column)).into()),
+        FieldFunction::Year => ("YEAR", "%Y"),
+        FieldFunction::Month => ("MONTH", "%m"),
+        FieldFunction::Day => ("DAY", "%d"),
+    };
+
+    let backend = backend.ok_or_else(|| {
+        format!("'{func}' requires a database backend; use filter_node_to_condition_for")
+    })?;
+    let col: SimpleExpr = Expr::col(column).into();
+    Ok(match backend {
+        DbBackend::Postgres => Expr::cust_with_exprs(format!("EXTRACT({} FROM $1)", part.0), [col]),
+        DbBackend::MySql => Expr::cust_with_exprs(format!("EXTRACT({} FROM ?)", part.0), [col]),
+        DbBackend::Sqlite => {
+            Expr::cust_with_exprs(format!("CAST(strftime('{}', ?) AS INTEGER)", part.1), [col])
+        }
+    })
+}
+
+/// `EXISTS`/`NOT EXISTS` over the elements of a JSON array column.
+///
+/// `any(x: p)` is true when some element satisfies `p`; `all(x: p)` when no
+/// element violates it (so it is true for empty arrays), as `OData` requires.
+fn build_lambda_condition<C>(
+    column: C,
+    kind: FieldKind,
+    op: LambdaOp,
+    predicate: Option<&ElementPredicate>,
+    backend: DbBackend,
+) -> Result<Condition, String>
+where
+    C: sea_orm::IntoSimpleExpr,
+{
+    let (from, element) = json_elements(backend, kind)?;
+    let array = column.into_simple_expr();
+
+    let Some(predicate) = predicate else {
+        return Ok(Condition::all().add(Expr::cust_with_exprs(
+            format!("EXISTS (SELECT 1 FROM {from})"),
+            [array],
+        )));
     };
 
-    Ok(Condition::all().add(expr))
+    let cond = element_condition(&element, kind, predicate)?;
+    let (prefix, cond) = match op {
+        LambdaOp::Any => ("EXISTS", cond),
+        LambdaOp::All => ("NOT EXISTS", cond.not()),
+    };
+    let second = if backend == DbBackend::Postgres {
+        "$2"
+    } else {
+        "?"
+    };
+    Ok(Condition::all().add(Expr::cust_with_exprs(
+        format!("{prefix} (SELECT 1 FROM {from} WHERE {second})"),
+        [array, cond.into()],
+    )))
+}
+
+/// Table expression expanding the array (bound as the first placeholder) into
+/// rows aliased `_e`, and the typed element expression.
+fn json_elements(backend: DbBackend, kind: FieldKind) -> Result<(String, String), String> {
+    let (pg_type, mysql_type) = match kind {
+        // UUIDs are compared in their canonical text form
+        FieldKind::String | FieldKind::Uuid => (None, "TEXT"),
+        FieldKind::I64 => (Some("bigint"), "BIGINT"),
+        FieldKind::F64 => (Some("double precision"), "DOUBLE"),
+        FieldKind::Bool => (Some("boolean"), "BOOLEAN"),
+        _ => return Err(format!("any/all is not supported on {kind} collections")),
+    };
+
+    Ok(match backend {
+        DbBackend::Postgres => (
+            "jsonb_array_elements_text(CAST($1 AS jsonb)) AS _e(value)".to_owned(),
+            pg_type.map_or_else(
+                || "_e.value".to_owned(),
+                |t| format!("CAST(_e.value AS {t})"),
+            ),
+        ),
+        DbBackend::MySql => (
+            format!("JSON_TABLE(?, '$[*]' COLUMNS (value {mysql_type} PATH '$')) AS _e"),
+            "_e.value".to_owned(),
+        ),
+        DbBackend::Sqlite => ("json_each(?) AS _e".to_owned(), "_e.value".to_owned()),
+    })
+}
+
+fn element_condition(
+    element: &str,
+    kind: FieldKind,
+    predicate: &ElementPredicate,
+) -> Result<Condition, String> {
+    Ok(match predicate {
+        ElementPredicate::Compare { op, value } => {
+            let value = match (kind, value) {
+                (FieldKind::Uuid, ODataValue::Uuid(u)) => ODataValue::String(u.to_string()),
+                _ => value.clone(),
+            };
+            Condition::all().add(compare_expr(Expr::expr(Expr::cust(element)), *op, &value)?)
+        }
+        ElementPredicate::And(children) => {
+            children.iter().try_fold(Condition::all(), |acc, c| {
+                Ok::<_, String>(acc.add(element_condition(element, kind, c)?))
+            })?
+        }
+        ElementPredicate::Or(children) => {
+            children.iter().try_fold(Condition::any(), |acc, c| {
+                Ok::<_, String>(acc.add(element_condition(element, kind, c)?))
+            })?
+        }
+        ElementPredicate::Not(inner) => Condition::all()
+            .add(element_condition(element, kind, inner)?)
+            .not(),
+    })
 }
 
 /// Convert an `ODataValue` to a `sea_orm::Value`.
@@ -390,10 +590,30 @@ pub fn parse_cursor_value(kind: FieldKind, s: &str) -> Result<sea_orm::Value, St
 pub struct LimitCfg {
     pub default: u64,
     pub max: u64,
+    /// Maximum number of rows counted for `$count=true`
+    pub max_count: u64,
+}
+
+/// Run the capped `$count` query for a filtered (but not yet paged) select.
+///
+/// Returns `(total_count, capped)`, where `total_count` never exceeds `cap`.
+pub(crate) async fn count_total<E, C>(
+    select: sea_orm::Select<E>,
+    conn: &C,
+    cap: u64,
+) -> Result<(u64, bool), ODataError>
+where
+    E: EntityTrait,
+    C: DBRunner,
+{
+    let n = count_select_up_to(select, conn, cap)
+        .await
+        .map_err(|e| ODataError::Db(e.to_string()))?;
+    Ok((n.min(cap), n > cap))
 }
 
 /// Clamp the requested limit to configured bounds
-fn clamp_limit(req: Option<u64>, cfg: LimitCfg) -> u64 {
+pub(crate) fn clamp_limit(req: Option<u64>, cfg: LimitCfg) -> u64 {
     let mut l = req.unwrap_or(cfg.default);
     if l == 0 {
         l = 1;
@@ -404,12 +624,16 @@ fn clamp_limit(req: Option<u64>, cfg: LimitCfg) -> u64 {
     l
 }
 
-/// Type-safe `OData` pagination with filters, ordering, and cursors.
+/// Type-safe `OData` pagination with filters, search, ordering, cursors and counts.
 ///
 /// This function provides complete cursor-based pagination using the type-safe
 /// `FilterField` and `ODataFieldMapping` traits. It replaces the legacy FieldMap-based
 /// pagination.
 ///
+/// `$search` matches the fields marked `searchable` in `#[derive(ODataFilterable)]`;
+/// `$count=true` adds `total_count` (capped at `LimitCfg::max_count`) to `PageInfo`.
+/// `$expand` is not handled here; see [`crate::odata::expand_related`].
+///
 /// # Type Parameters
 ///
 /// - `F`: `FilterField` implementation (generated by `#[derive(ODataFilterable)]`)
@@ -440,7 +664,7 @@ fn clamp_limit(req: Option<u64>, cfg: LimitCfg) -> u64 {
 ///     db,
 ///     &odata_query,
 ///     ("id", SortDir::Desc),
-///     LimitCfg { default: 25, max: 1000 },
+///     LimitCfg { default: 25, max: 1000, max_count: 10_000 },
 ///     |model| model.into(),
 /// ).await?;
 /// ```
@@ -484,6 +708,7 @@ where
     }
 
     let mut s = select.inner;
+    let backend = DBRunnerInternal::as_seaorm(conn).backend();
 
     // Apply filter using type-safe FilterNode
     if let Some(ast) = query.filter.as_deref() {
@@ -491,10 +716,27 @@ where
             .map_err(|e| ODataError::InvalidFilter(e.to_string()))?;
 
         s = s.filter(
-            filter_node_to_condition::<F, M>(&filter_node).map_err(ODataError::InvalidFilter)?,
+            filter_node_to_condition_for::<F, M>(&filter_node, backend)
+                .map_err(ODataError::InvalidFilter)?,
+        );
+    }
+
+    // Apply search over the searchable fields
+    if let Some(search) = query.search() {
+        let search_node = search_to_filter_node::<F>(search)?;
+        s = s.filter(
+            filter_node_to_condition_for::<F, M>(&search_node, backend)
+                .map_err(ODataError::InvalidSearch)?,
         );
     }
 
+    // Count before cursor and limit are applied
+    let total = if query.count {
+        Some(count_total(s.clone(), conn, limit_cfg.max_count).await?)
+    } else {
+        None
+    };
+
     let is_backward = query.cursor.as_ref().is_some_and(|c| c.d == "bwd");
 
     // Apply cursor predicate
@@ -587,6 +829,8 @@ where
             next_cursor,
             prev_cursor,
             limit,
+            total_count: total.map(|(n, _)| n),
+            total_count_capped: total.is_some_and(|(_, capped)| capped),
         },
     })
 }
@@ -42,8 +42,8 @@ where
 
     if !scope.resource_ids().is_empty() {
         if let Some(resource_col) = E::resource_col() {
-            let id_filter =
-                Condition::all().add(Expr::col(resource_col).is_in(scope.resource_ids().to_vec()));
+            let id_filter = Condition::all()
+                .add(Expr::col((E::default(), resource_col)).is_in(scope.resource_ids().to_vec()));
             parts.push(id_filter);
         } else {
             // Entity has no resource_col but scope requires resource filtering → deny all
@@ -146,6 +146,7 @@ pub use tx_error::{InfraError, TxError};
 pub use tx_config::{TxAccessMode, TxConfig, TxIsolationLevel};
 
 // Select operations
+pub(crate) use select::count_select_up_to;
 pub use select::{
     Scoped, SecureEntityExt, SecureFindRelatedExt, SecureSelect, SecureSelectTwo,
     SecureSelectTwoMany, Unscoped,
@@ -57,7 +57,10 @@ impl TenantFilterProvider for SimpleTenantFilter {
         };
 
         // Build tenant IN filter
-        Some(Condition::all().add(Expr::col(tcol).is_in(scope.tenant_ids().to_vec())))
+        Some(
+            Condition::all()
+                .add(Expr::col((E::default(), tcol)).is_in(scope.tenant_ids().to_vec())),
+        )
     }
 }
 
@@ -26,6 +26,18 @@ pub enum SeaOrmRunner<'a> {
     Tx(&'a sea_orm::DatabaseTransaction),
 }
 
+impl SeaOrmRunner<'_> {
+    /// Database backend the runner executes against.
+    #[must_use]
+    pub fn backend(&self) -> sea_orm::DbBackend {
+        use sea_orm::ConnectionTrait;
+        match self {
+            Self::Conn(db) => db.get_database_backend(),
+            Self::Tx(tx) => tx.get_database_backend(),
+        }
+    }
+}
+
 /// Internal-only bridge to `SeaORM`'s executor types.
 pub trait DBRunnerInternal: sealed::Sealed + Send + Sync {
     fn as_seaorm(&self) -> SeaOrmRunner<'_>;
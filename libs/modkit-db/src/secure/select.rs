@@ -231,6 +231,18 @@ where
         }
     }
 
+    /// Count matching rows, scanning at most `cap + 1` of them.
+    ///
+    /// Unlike [`Self::count`], the database stops after `cap + 1` rows, which keeps
+    /// `$count` affordable on large tables. A result greater than `cap` means the
+    /// real count exceeds the cap.
+    ///
+    /// # Errors
+    /// Returns `ScopeError::Db` if the database query fails.
+    pub async fn count_up_to(self, runner: &impl DBRunner, cap: u64) -> Result<u64, ScopeError> {
+        Ok(count_select_up_to(self.inner, runner, cap).await?)
+    }
+
     // Note: count() uses SeaORM's `PaginatorTrait::count` internally.
 
     // Note: For pagination, use `into_inner().paginate()` due to complex lifetime bounds
@@ -379,6 +391,49 @@ where
     }
 }
 
+/// Capped `COUNT(*)` over an already-scoped `Select`.
+///
+/// Shared by [`SecureSelect::count_up_to`] and the `OData` paginators, which work on
+/// the inner `Select` after the scope has been applied.
+pub async fn count_select_up_to<E: EntityTrait>(
+    select: sea_orm::Select<E>,
+    runner: &impl DBRunner,
+    cap: u64,
+) -> Result<u64, sea_orm::DbErr> {
+    use sea_orm::sea_query::{Alias, Query};
+    use sea_orm::{ConnectionTrait, DbBackend, QueryTrait};
+
+    let capped = QuerySelect::limit(
+        QuerySelect::expr(QuerySelect::select_only(select), Expr::value(1)),
+        cap.saturating_add(1),
+    )
+    .into_query();
+    let stmt = Query::select()
+        .expr_as(Expr::cust("COUNT(*)"), Alias::new("num_items"))
+        .from_subquery(capped, Alias::new("capped"))
+        .to_owned();
+
+    let (backend, row) = match DBRunnerInternal::as_seaorm(runner) {
+        SeaOrmRunner::Conn(db) => {
+            let backend = db.get_database_backend();
+            (backend, db.query_one(backend.build(&stmt)).await?)
+        }
+        SeaOrmRunner::Tx(tx) => {
+            let backend = tx.get_database_backend();
+            (backend, tx.query_one(backend.build(&stmt)).await?)
+        }
+    };
+    let Some(row) = row else {
+        return Ok(0);
+    };
+    // Same decoding as SeaORM's paginator: COUNT(*) is BIGINT only on Postgres
+    let n = match backend {
+        DbBackend::Postgres => row.try_get::<i64>("", "num_items")?,
+        _ => i64::from(row.try_get::<i32>("", "num_items")?),
+    };
+    Ok(u64::try_from(n).unwrap_or_default())
+}
+
 // =============================================================================
 // Relationship Query Methods on SecureSelect<E, Scoped>
 // =============================================================================
@@ -401,6 +456,26 @@ where
     }
 }
 
+/// LEFT JOIN the related entity `R` with its scope filter in the `ON` clause,
+/// so that primary rows without related rows in scope are kept.
+fn scoped_left_join<E, R>(select: sea_orm::Select<E>, scope: &AccessScope) -> sea_orm::Select<E>
+where
+    E: EntityTrait + Related<R>,
+    R: ScopableEntity + EntityTrait,
+    R::Column: ColumnTrait + Copy,
+{
+    let mut rel = <E as Related<R>>::to();
+    if let Some(cond) = apply_related_scope::<R>(scope) {
+        rel = rel.on_condition(move |_, _| cond.clone());
+    }
+    QuerySelect::join_join(
+        select,
+        sea_orm::JoinType::LeftJoin,
+        rel,
+        <E as Related<R>>::via(),
+    )
+}
+
 impl<E> SecureSelect<E, Scoped>
 where
     E: EntityTrait,
@@ -543,6 +618,40 @@ where
             state: self.state,
         }
     }
+
+    /// Like [`Self::find_with_related`], but scoping the related entity in the
+    /// join condition instead of the `WHERE` clause.
+    ///
+    /// `find_with_related` drops primary rows that have no related rows in
+    /// scope. This variant keeps every primary row matched by the parent
+    /// `SecureSelect`, each with only its related rows in scope, possibly none.
+    /// This is what `OData` `$expand` needs: expanding a navigation property must
+    /// not change which entities are returned.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// // Every order in scope, each with its line items in scope
+    /// let rows: Vec<(order::Model, Vec<line_item::Model>)> = Order::find()
+    ///     .secure()
+    ///     .scope_with(&scope)
+    ///     .expand_with_related(line_item::Entity)
+    ///     .all(db)
+    ///     .await?;
+    /// ```
+    pub fn expand_with_related<R>(self, r: R) -> SecureSelectTwoMany<E, R, Scoped>
+    where
+        R: ScopableEntity + EntityTrait,
+        R::Column: ColumnTrait + Copy,
+        E: Related<R>,
+    {
+        let select_two_many =
+            scoped_left_join::<E, R>(self.inner, &self.state.scope).select_with(r);
+
+        SecureSelectTwoMany {
+            inner: select_two_many,
+            state: self.state,
+        }
+    }
 }
 
 // =============================================================================
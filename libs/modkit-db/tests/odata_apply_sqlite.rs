@@ -0,0 +1,395 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+#![cfg(feature = "sqlite")]
+
+//! `SQLite` integration tests for `$apply` aggregation through `aggregate_odata`.
+
+use anyhow::anyhow;
+use modkit_db::migration_runner::run_migrations_for_testing;
+use modkit_db::odata::sea_orm_filter::FieldToColumn;
+use modkit_db::odata::{LimitCfg, aggregate_odata};
+use modkit_db::secure::{Db, ScopableEntity, SecureEntityExt, secure_insert};
+use modkit_db::{ConnectOpts, connect_db};
+use modkit_odata::apply::{AggregateRow, parse_apply};
+use modkit_odata::filter::{FieldKind, FilterCaps, FilterField};
+use modkit_odata::{Error as ODataError, ODataQuery, Page};
+use modkit_security::AccessScope;
+use sea_orm::Set;
+use sea_orm::entity::prelude::*;
+use sea_orm_migration::prelude as mig;
+use serde_json::json;
+use uuid::Uuid;
+
+mod order {
+    use sea_orm::entity::prelude::*;
+    use uuid::Uuid;
+
+    #[derive(Debug, Clone, PartialEq, DeriveEntityModel)]
+    #[sea_orm(table_name = "odata_apply_order")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i64,
+        pub tenant_id: Uuid,
+        pub status: String,
+        pub region: String,
+        pub amount: i64,
+        pub weight: f64,
+        pub created_at: DateTimeUtc,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+impl ScopableEntity for order::Entity {
+    fn tenant_col() -> Option<<Self as EntityTrait>::Column> {
+        Some(order::Column::TenantId)
+    }
+    fn resource_col() -> Option<<Self as EntityTrait>::Column> {
+        None
+    }
+    fn owner_col() -> Option<<Self as EntityTrait>::Column> {
+        None
+    }
+    fn type_col() -> Option<<Self as EntityTrait>::Column> {
+        None
+    }
+}
+
+/// Typed filter fields, as `#[derive(ODataFilterable)]` with `ops(...)` would generate them
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+enum OrderField {
+    Status,
+    Region,
+    Amount,
+    Weight,
+    CreatedAt,
+}
+
+impl FilterField for OrderField {
+    const FIELDS: &'static [Self] = &[
+        OrderField::Status,
+        OrderField::Region,
+        OrderField::Amount,
+        OrderField::Weight,
+        OrderField::CreatedAt,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            OrderField::Status => "status",
+            OrderField::Region => "region",
+            OrderField::Amount => "amount",
+            OrderField::Weight => "weight",
+            OrderField::CreatedAt => "created_at",
+        }
+    }
+
+    fn kind(&self) -> FieldKind {
+        match self {
+            OrderField::Status | OrderField::Region => FieldKind::String,
+            OrderField::Amount => FieldKind::I64,
+            OrderField::Weight => FieldKind::F64,
+            OrderField::CreatedAt => FieldKind::DateTimeUtc,
+        }
+    }
+
+    fn caps(&self) -> FilterCaps {
+        match self {
+            OrderField::Status => FilterCaps::GROUPBY | FilterCaps::IN,
+            OrderField::Region => FilterCaps::GROUPBY | FilterCaps::COUNTDISTINCT,
+            OrderField::Amount => FilterCaps::SUM | FilterCaps::MIN | FilterCaps::MAX,
+            OrderField::Weight => FilterCaps::AVERAGE,
+            OrderField::CreatedAt => FilterCaps::GROUPBY,
+        }
+    }
+}
+
+struct OrderMapper;
+
+impl FieldToColumn<OrderField> for OrderMapper {
+    type Column = order::Column;
+
+    fn map_field(field: OrderField) -> order::Column {
+        match field {
+            OrderField::Status => order::Column::Status,
+            OrderField::Region => order::Column::Region,
+            OrderField::Amount => order::Column::Amount,
+            OrderField::Weight => order::Column::Weight,
+            OrderField::CreatedAt => order::Column::CreatedAt,
+        }
+    }
+}
+
+struct CreateOdataApplyOrder;
+
+impl mig::MigrationName for CreateOdataApplyOrder {
+    fn name(&self) -> &'static str {
+        "m001_create_odata_apply_order"
+    }
+}
+
+#[async_trait::async_trait]
+impl mig::MigrationTrait for CreateOdataApplyOrder {
+    async fn up(&self, manager: &mig::SchemaManager) -> Result<(), mig::DbErr> {
+        manager
+            .create_table(
+                mig::Table::create()
+                    .table(mig::Alias::new("odata_apply_order"))
+                    .if_not_exists()
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("id"))
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("tenant_id"))
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("status"))
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("region"))
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("amount"))
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("weight"))
+                            .double()
+                            .not_null(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("created_at"))
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &mig::SchemaManager) -> Result<(), mig::DbErr> {
+        manager
+            .drop_table(
+                mig::Table::drop()
+                    .table(mig::Alias::new("odata_apply_order"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+const LIMITS: LimitCfg = LimitCfg {
+    default: 25,
+    max: 1000,
+    max_count: 1000,
+};
+
+struct TestDb {
+    db: Db,
+    scope: AccessScope,
+}
+
+impl TestDb {
+    async fn seeded() -> Self {
+        let db = connect_db("sqlite::memory:", ConnectOpts::default())
+            .await
+            .expect("db connect");
+        run_migrations_for_testing(&db, vec![Box::new(CreateOdataApplyOrder)])
+            .await
+            .map_err(|e| anyhow!(e.to_string()))
+            .expect("migrate");
+
+        let tenant_id = Uuid::new_v4();
+        let other_tenant = Uuid::new_v4();
+        let scope = AccessScope::tenants_only(vec![tenant_id]);
+        let conn = db.conn().expect("conn");
+
+        let rows = [
+            (tenant_id, "open", "eu", 10, 1.0, "2024-01-01T08:00:00Z"),
+            (tenant_id, "open", "us", 20, 2.0, "2024-01-01T23:30:00Z"),
+            (tenant_id, "closed", "eu", 5, 4.0, "2024-01-02T00:15:00Z"),
+            (tenant_id, "open", "eu", 30, 3.0, "2024-02-01T12:00:00Z"),
+            // Must never be aggregated for `scope`
+            (
+                other_tenant,
+                "open",
+                "eu",
+                1000,
+                100.0,
+                "2024-01-01T08:00:00Z",
+            ),
+        ];
+        for (tenant, status, region, amount, weight, created_at) in rows {
+            let am = order::ActiveModel {
+                tenant_id: Set(tenant),
+                status: Set(status.to_owned()),
+                region: Set(region.to_owned()),
+                amount: Set(amount),
+                weight: Set(weight),
+                created_at: Set(created_at.parse().unwrap()),
+                ..Default::default()
+            };
+            let tenant_scope = AccessScope::tenants_only(vec![tenant]);
+            secure_insert::<order::Entity>(am, &tenant_scope, &conn)
+                .await
+                .expect("insert");
+        }
+
+        Self { db, scope }
+    }
+
+    async fn aggregate(&self, query: ODataQuery) -> Result<Page<AggregateRow>, ODataError> {
+        let conn = self.db.conn().expect("conn");
+        let select = order::Entity::find().secure().scope_with(&self.scope);
+        aggregate_odata::<OrderField, OrderMapper, _, _>(select, &conn, &query, LIMITS).await
+    }
+
+    async fn apply(&self, raw: &str) -> Result<Vec<serde_json::Value>, ODataError> {
+        let page = self
+            .aggregate(ODataQuery::new().with_apply(parse_apply(raw)?))
+            .await?;
+        Ok(page
+            .items
+            .into_iter()
+            .map(|row| serde_json::to_value(row).unwrap())
+            .collect())
+    }
+}
+
+#[tokio::test]
+async fn groups_and_aggregates_within_scope() {
+    let db = TestDb::seeded().await;
+
+    let rows = db
+        .apply("groupby((status),aggregate(amount with sum as total,$count as n,amount with max as top))")
+        .await
+        .unwrap();
+    assert_eq!(
+        rows,
+        vec![
+            json!({"status": "closed", "total": 5, "n": 1, "top": 5}),
+            json!({"status": "open", "total": 60, "n": 3, "top": 30}),
+        ]
+    );
+
+    let rows = db.apply("groupby((status, region))").await.unwrap();
+    assert_eq!(rows.len(), 3);
+
+    let rows = db
+        .apply("aggregate(weight with average as avg_weight,region with countdistinct as regions)")
+        .await
+        .unwrap();
+    assert_eq!(rows, vec![json!({"avg_weight": 2.5, "regions": 2})]);
+}
+
+#[tokio::test]
+async fn filter_step_restricts_input_rows() {
+    let db = TestDb::seeded().await;
+
+    let rows = db
+        .apply("filter(status in ('open'))/groupby((region),aggregate(amount with min as low))")
+        .await
+        .unwrap();
+    assert_eq!(
+        rows,
+        vec![
+            json!({"region": "eu", "low": 10}),
+            json!({"region": "us", "low": 20}),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn groups_by_date_functions() {
+    let db = TestDb::seeded().await;
+
+    let rows = db
+        .apply("groupby((date(created_at) as day),aggregate($count as n))")
+        .await
+        .unwrap();
+    assert_eq!(
+        rows,
+        vec![
+            json!({"day": "2024-01-01", "n": 2}),
+            json!({"day": "2024-01-02", "n": 1}),
+            json!({"day": "2024-02-01", "n": 1}),
+        ]
+    );
+
+    let rows = db
+        .apply("groupby((year(created_at) as y, month(created_at) as m),aggregate(amount with sum as total))")
+        .await
+        .unwrap();
+    assert_eq!(
+        rows,
+        vec![
+            json!({"y": 2024, "m": 1, "total": 35}),
+            json!({"y": 2024, "m": 2, "total": 30}),
+        ]
+    );
+
+    // Without an alias the group is keyed by the field
+    let rows = db.apply("groupby((day(created_at)))").await.unwrap();
+    assert_eq!(
+        rows,
+        vec![json!({"created_at": 1}), json!({"created_at": 2})]
+    );
+
+    let res = db.apply("groupby((date(status)))").await;
+    assert!(matches!(res, Err(ODataError::InvalidApply(_))));
+}
+
+#[tokio::test]
+async fn counts_groups_and_enforces_limit() {
+    let db = TestDb::seeded().await;
+    let apply = parse_apply("groupby((status, region))").unwrap();
+
+    let page = db
+        .aggregate(ODataQuery::new().with_apply(apply.clone()).with_count(true))
+        .await
+        .unwrap();
+    assert_eq!(page.page_info.total_count, Some(3));
+
+    let res = db
+        .aggregate(ODataQuery::new().with_apply(apply).with_limit(2))
+        .await;
+    assert!(matches!(res, Err(ODataError::InvalidApply(_))));
+}
+
+#[tokio::test]
+async fn rejects_operations_not_enabled_for_field() {
+    let db = TestDb::seeded().await;
+
+    for raw in [
+        "groupby((amount))",
+        "aggregate(amount with average as a)",
+        "aggregate(status with countdistinct as s)",
+    ] {
+        assert!(
+            matches!(db.apply(raw).await, Err(ODataError::InvalidApply(_))),
+            "{raw} should be rejected"
+        );
+    }
+
+    // $filter must go into the pipeline
+    let query = ODataQuery::new()
+        .with_apply(parse_apply("aggregate($count as n)").unwrap())
+        .with_search("open");
+    assert!(matches!(
+        db.aggregate(query).await,
+        Err(ODataError::InvalidApply(_))
+    ));
+}
@@ -0,0 +1,380 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+#![cfg(feature = "sqlite")]
+
+//! `SQLite` integration tests for `in`, `has`, field functions and `any`/`all`
+//! lambdas in `$filter`, executed through `paginate_odata`.
+
+use anyhow::anyhow;
+use chrono::{TimeZone, Utc};
+use modkit_db::migration_runner::run_migrations_for_testing;
+use modkit_db::odata::sea_orm_filter::{FieldToColumn, ODataFieldMapping};
+use modkit_db::odata::{LimitCfg, filter_node_to_condition_for, paginate_odata};
+use modkit_db::secure::{Db, SecureEntityExt, secure_insert};
+use modkit_db::{ConnectOpts, connect_db};
+use modkit_odata::filter::{FieldKind, FilterCaps, FilterField, parse_odata_filter};
+use modkit_odata::{Error as ODataError, ODataQuery, SortDir, parse_filter_string};
+use modkit_security::AccessScope;
+use sea_orm::entity::prelude::*;
+use sea_orm::{DbBackend, QueryTrait, Set};
+use sea_orm_migration::prelude as mig;
+use uuid::Uuid;
+
+mod item {
+    use sea_orm::entity::prelude::*;
+    use uuid::Uuid;
+
+    #[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "odata_fn_item")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i64,
+        pub tenant_id: Uuid,
+        pub name: String,
+        pub flags: i64,
+        pub created_at: DateTimeUtc,
+        /// JSON array of strings
+        pub tags: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+impl modkit_db::secure::ScopableEntity for item::Entity {
+    fn tenant_col() -> Option<<Self as EntityTrait>::Column> {
+        Some(item::Column::TenantId)
+    }
+    fn resource_col() -> Option<<Self as EntityTrait>::Column> {
+        None
+    }
+    fn owner_col() -> Option<<Self as EntityTrait>::Column> {
+        None
+    }
+    fn type_col() -> Option<<Self as EntityTrait>::Column> {
+        None
+    }
+}
+
+/// Typed filter fields, as `#[derive(ODataFilterable)]` with `ops(...)` would generate them
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+enum ItemField {
+    Id,
+    Name,
+    Flags,
+    CreatedAt,
+    Tags,
+}
+
+impl FilterField for ItemField {
+    const FIELDS: &'static [Self] = &[
+        ItemField::Id,
+        ItemField::Name,
+        ItemField::Flags,
+        ItemField::CreatedAt,
+        ItemField::Tags,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            ItemField::Id => "id",
+            ItemField::Name => "name",
+            ItemField::Flags => "flags",
+            ItemField::CreatedAt => "created_at",
+            ItemField::Tags => "tags",
+        }
+    }
+
+    fn kind(&self) -> FieldKind {
+        match self {
+            ItemField::Id | ItemField::Flags => FieldKind::I64,
+            ItemField::Name | ItemField::Tags => FieldKind::String,
+            ItemField::CreatedAt => FieldKind::DateTimeUtc,
+        }
+    }
+
+    fn caps(&self) -> FilterCaps {
+        match self {
+            ItemField::Id => FilterCaps::IN,
+            ItemField::Name => FilterCaps::TOLOWER | FilterCaps::TOUPPER | FilterCaps::LENGTH,
+            ItemField::Flags => FilterCaps::HAS,
+            ItemField::CreatedAt => FilterCaps::YEAR | FilterCaps::MONTH | FilterCaps::DAY,
+            ItemField::Tags => FilterCaps::ANY | FilterCaps::ALL,
+        }
+    }
+}
+
+struct ItemMapper;
+
+impl FieldToColumn<ItemField> for ItemMapper {
+    type Column = item::Column;
+
+    fn map_field(field: ItemField) -> item::Column {
+        match field {
+            ItemField::Id => item::Column::Id,
+            ItemField::Name => item::Column::Name,
+            ItemField::Flags => item::Column::Flags,
+            ItemField::CreatedAt => item::Column::CreatedAt,
+            ItemField::Tags => item::Column::Tags,
+        }
+    }
+}
+
+impl ODataFieldMapping<ItemField> for ItemMapper {
+    type Entity = item::Entity;
+
+    fn extract_cursor_value(model: &item::Model, field: ItemField) -> sea_orm::Value {
+        match field {
+            ItemField::Id => sea_orm::Value::BigInt(Some(model.id)),
+            ItemField::Name => sea_orm::Value::String(Some(Box::new(model.name.clone()))),
+            ItemField::Flags => sea_orm::Value::BigInt(Some(model.flags)),
+            ItemField::CreatedAt => {
+                sea_orm::Value::ChronoDateTimeUtc(Some(Box::new(model.created_at)))
+            }
+            ItemField::Tags => sea_orm::Value::String(Some(Box::new(model.tags.clone()))),
+        }
+    }
+}
+
+struct CreateOdataFnItem;
+
+impl mig::MigrationName for CreateOdataFnItem {
+    fn name(&self) -> &'static str {
+        "m001_create_odata_fn_item"
+    }
+}
+
+#[async_trait::async_trait]
+impl mig::MigrationTrait for CreateOdataFnItem {
+    async fn up(&self, manager: &mig::SchemaManager) -> Result<(), mig::DbErr> {
+        manager
+            .create_table(
+                mig::Table::create()
+                    .table(mig::Alias::new("odata_fn_item"))
+                    .if_not_exists()
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("id"))
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("tenant_id"))
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("name"))
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("flags"))
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("created_at"))
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("tags"))
+                            .text()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &mig::SchemaManager) -> Result<(), mig::DbErr> {
+        manager
+            .drop_table(
+                mig::Table::drop()
+                    .table(mig::Alias::new("odata_fn_item"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+const LIMITS: LimitCfg = LimitCfg {
+    default: 25,
+    max: 1000,
+    max_count: 1000,
+};
+
+struct TestDb {
+    db: Db,
+    scope: AccessScope,
+}
+
+impl TestDb {
+    async fn seeded() -> Self {
+        let db = connect_db("sqlite::memory:", ConnectOpts::default())
+            .await
+            .expect("db connect");
+        run_migrations_for_testing(&db, vec![Box::new(CreateOdataFnItem)])
+            .await
+            .map_err(|e| anyhow!(e.to_string()))
+            .expect("migrate");
+
+        let tenant_id = Uuid::new_v4();
+        let scope = AccessScope::tenants_only(vec![tenant_id]);
+        let conn = db.conn().expect("conn");
+
+        let rows = [
+            ("Alice", 0b011, (2023, 12, 31), r#"["red","blue"]"#),
+            ("bob", 0b100, (2024, 1, 15), r#"["blue"]"#),
+            ("Charlie", 0b111, (2024, 3, 15), "[]"),
+            ("dave", 0b001, (2024, 3, 1), r#"["green","red"]"#),
+        ];
+        for (name, flags, (y, m, d), tags) in rows {
+            let am = item::ActiveModel {
+                tenant_id: Set(tenant_id),
+                name: Set(name.to_owned()),
+                flags: Set(flags),
+                created_at: Set(Utc.with_ymd_and_hms(y, m, d, 10, 0, 0).unwrap()),
+                tags: Set(tags.to_owned()),
+                ..Default::default()
+            };
+            secure_insert::<item::Entity>(am, &scope, &conn)
+                .await
+                .expect("insert");
+        }
+
+        Self { db, scope }
+    }
+
+    async fn names(&self, filter: &str) -> Result<Vec<String>, ODataError> {
+        let conn = self.db.conn().expect("conn");
+        let q = ODataQuery::new().with_filter(parse_filter_string(filter)?.into_expr());
+        let select = item::Entity::find().secure().scope_with(&self.scope);
+        let page = paginate_odata::<ItemField, ItemMapper, _, _, _, _>(
+            select,
+            &conn,
+            &q,
+            ("id", SortDir::Asc),
+            LIMITS,
+            |m| m.name,
+        )
+        .await?;
+        Ok(page.items)
+    }
+}
+
+#[tokio::test]
+async fn filters_with_in_and_has() {
+    let db = TestDb::seeded().await;
+
+    assert_eq!(
+        db.names("id in (1, 3, 99)").await.unwrap(),
+        ["Alice", "Charlie"]
+    );
+    assert_eq!(db.names("flags has 3").await.unwrap(), ["Alice", "Charlie"]);
+    assert_eq!(
+        db.names("flags has 1 and not (flags has 2)").await.unwrap(),
+        ["dave"]
+    );
+}
+
+#[tokio::test]
+async fn filters_with_string_functions() {
+    let db = TestDb::seeded().await;
+
+    assert_eq!(
+        db.names("tolower(name) eq 'alice'").await.unwrap(),
+        ["Alice"]
+    );
+    assert_eq!(
+        db.names("startswith(toupper(name), 'CH')").await.unwrap(),
+        ["Charlie"]
+    );
+    assert_eq!(
+        db.names("length(name) le 4").await.unwrap(),
+        ["bob", "dave"]
+    );
+}
+
+#[tokio::test]
+async fn filters_with_date_parts() {
+    let db = TestDb::seeded().await;
+
+    assert_eq!(
+        db.names("year(created_at) eq 2024").await.unwrap(),
+        ["bob", "Charlie", "dave"]
+    );
+    assert_eq!(
+        db.names("month(created_at) eq 3 and day(created_at) gt 1")
+            .await
+            .unwrap(),
+        ["Charlie"]
+    );
+}
+
+#[tokio::test]
+async fn filters_with_lambdas() {
+    let db = TestDb::seeded().await;
+
+    assert_eq!(
+        db.names("tags/any(t: t eq 'red')").await.unwrap(),
+        ["Alice", "dave"]
+    );
+    assert_eq!(
+        db.names("tags/any(t: startswith(t, 'gr') or t eq 'blue')")
+            .await
+            .unwrap(),
+        ["Alice", "bob", "dave"]
+    );
+    assert_eq!(
+        db.names("tags/any()").await.unwrap(),
+        ["Alice", "bob", "dave"]
+    );
+    // all() holds vacuously for empty arrays
+    assert_eq!(
+        db.names("tags/all(t: t ne 'red')").await.unwrap(),
+        ["bob", "Charlie"]
+    );
+}
+
+#[tokio::test]
+async fn rejects_operations_not_enabled_for_field() {
+    let db = TestDb::seeded().await;
+
+    assert!(matches!(
+        db.names("name in ('bob')").await,
+        Err(ODataError::InvalidFilter(_))
+    ));
+    assert!(matches!(
+        db.names("year(name) eq 2024").await,
+        Err(ODataError::InvalidFilter(_))
+    ));
+}
+
+#[test]
+fn renders_backend_specific_sql() {
+    let sql = |raw: &str, backend: DbBackend| {
+        let node = parse_odata_filter::<ItemField>(raw).unwrap();
+        let cond = filter_node_to_condition_for::<ItemField, ItemMapper>(&node, backend).unwrap();
+        item::Entity::find().filter(cond).build(backend).to_string()
+    };
+
+    let pg = sql("year(created_at) eq 2024", DbBackend::Postgres);
+    assert!(
+        pg.contains(r#"(EXTRACT(YEAR FROM "created_at")) = 2024"#),
+        "{pg}"
+    );
+    let my = sql("tags/all(t: t ne 'x')", DbBackend::MySql);
+    assert!(
+        my.contains("NOT EXISTS (SELECT 1 FROM JSON_TABLE(`odata_fn_item`.`tags`"),
+        "{my}"
+    );
+    let pg = sql("tags/any(t: t eq 'x')", DbBackend::Postgres);
+    assert!(
+        pg.contains(r#"EXISTS (SELECT 1 FROM jsonb_array_elements_text(CAST("odata_fn_item"."tags" AS jsonb)) AS _e(value) WHERE (_e.value) = 'x')"#),
+        "{pg}"
+    );
+}
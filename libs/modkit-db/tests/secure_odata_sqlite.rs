@@ -9,12 +9,13 @@
 
 use anyhow::anyhow;
 use modkit_db::migration_runner::run_migrations_for_testing;
-use modkit_db::odata::FieldMap;
 use modkit_db::odata::pager::OPager;
-use modkit_db::secure::{Db, DbConn, ScopableEntity, secure_insert};
+use modkit_db::odata::sea_orm_filter::{FieldToColumn, ODataFieldMapping};
+use modkit_db::odata::{FieldMap, LimitCfg, expand_related, paginate_odata};
+use modkit_db::secure::{Db, DbConn, ScopableEntity, SecureEntityExt, secure_insert};
 use modkit_db::{ConnectOpts, connect_db};
-use modkit_odata::ODataQuery;
-use modkit_odata::filter::FieldKind;
+use modkit_odata::filter::{FieldKind, FilterField};
+use modkit_odata::{Error as ODataError, ODataQuery, SortDir};
 use modkit_security::AccessScope;
 use sea_orm::Set;
 use sea_orm::entity::prelude::*;
@@ -36,11 +37,125 @@ mod ent {
     }
 
     #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-    pub enum Relation {}
+    pub enum Relation {
+        #[sea_orm(has_many = "super::child::Entity")]
+        Child,
+    }
+
+    impl Related<super::child::Entity> for Entity {
+        fn to() -> RelationDef {
+            Relation::Child.def()
+        }
+    }
 
     impl ActiveModelBehavior for ActiveModel {}
 }
 
+mod child {
+    use sea_orm::entity::prelude::*;
+    use uuid::Uuid;
+
+    #[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "secure_odata_child")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i64,
+        pub tenant_id: Uuid,
+        pub parent_id: i64,
+        pub label: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {
+        #[sea_orm(
+            belongs_to = "super::ent::Entity",
+            from = "Column::ParentId",
+            to = "super::ent::Column::Id"
+        )]
+        Parent,
+    }
+
+    impl Related<super::ent::Entity> for Entity {
+        fn to() -> RelationDef {
+            Relation::Parent.def()
+        }
+    }
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+impl ScopableEntity for child::Entity {
+    fn tenant_col() -> Option<<Self as EntityTrait>::Column> {
+        Some(child::Column::TenantId)
+    }
+    fn resource_col() -> Option<<Self as EntityTrait>::Column> {
+        None
+    }
+    fn owner_col() -> Option<<Self as EntityTrait>::Column> {
+        None
+    }
+    fn type_col() -> Option<<Self as EntityTrait>::Column> {
+        None
+    }
+}
+
+/// Typed filter fields for `ent`, as `#[derive(ODataFilterable)]` would generate them
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+enum EntField {
+    Id,
+    Name,
+    Score,
+}
+
+impl FilterField for EntField {
+    const FIELDS: &'static [Self] = &[EntField::Id, EntField::Name, EntField::Score];
+
+    fn name(&self) -> &'static str {
+        match self {
+            EntField::Id => "id",
+            EntField::Name => "name",
+            EntField::Score => "score",
+        }
+    }
+
+    fn kind(&self) -> FieldKind {
+        match self {
+            EntField::Id | EntField::Score => FieldKind::I64,
+            EntField::Name => FieldKind::String,
+        }
+    }
+
+    fn searchable(&self) -> bool {
+        matches!(self, EntField::Name)
+    }
+}
+
+struct EntMapper;
+
+impl FieldToColumn<EntField> for EntMapper {
+    type Column = ent::Column;
+
+    fn map_field(field: EntField) -> ent::Column {
+        match field {
+            EntField::Id => ent::Column::Id,
+            EntField::Name => ent::Column::Name,
+            EntField::Score => ent::Column::Score,
+        }
+    }
+}
+
+impl ODataFieldMapping<EntField> for EntMapper {
+    type Entity = ent::Entity;
+
+    fn extract_cursor_value(model: &ent::Model, field: EntField) -> sea_orm::Value {
+        match field {
+            EntField::Id => sea_orm::Value::BigInt(Some(model.id)),
+            EntField::Name => sea_orm::Value::String(Some(Box::new(model.name.clone()))),
+            EntField::Score => sea_orm::Value::BigInt(Some(model.score)),
+        }
+    }
+}
+
 impl ScopableEntity for ent::Entity {
     fn tenant_col() -> Option<<Self as EntityTrait>::Column> {
         Some(ent::Column::TenantId)
@@ -110,6 +225,60 @@ impl mig::MigrationTrait for CreateSecureOdataTest {
     }
 }
 
+struct CreateSecureOdataChild;
+
+impl mig::MigrationName for CreateSecureOdataChild {
+    fn name(&self) -> &'static str {
+        "m002_create_secure_odata_child"
+    }
+}
+
+#[async_trait::async_trait]
+impl mig::MigrationTrait for CreateSecureOdataChild {
+    async fn up(&self, manager: &mig::SchemaManager) -> Result<(), mig::DbErr> {
+        manager
+            .create_table(
+                mig::Table::create()
+                    .table(mig::Alias::new("secure_odata_child"))
+                    .if_not_exists()
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("id"))
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("tenant_id"))
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("parent_id"))
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("label"))
+                            .string()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &mig::SchemaManager) -> Result<(), mig::DbErr> {
+        manager
+            .drop_table(
+                mig::Table::drop()
+                    .table(mig::Alias::new("secure_odata_child"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
 // Helper struct to manage test database lifecycle
 struct TestDb {
     db: Db,
@@ -123,10 +292,16 @@ impl TestDb {
             .await
             .expect("db connect");
 
-        run_migrations_for_testing(&db, vec![Box::new(CreateSecureOdataTest)])
-            .await
-            .map_err(|e| anyhow!(e.to_string()))
-            .expect("migrate");
+        run_migrations_for_testing(
+            &db,
+            vec![
+                Box::new(CreateSecureOdataTest),
+                Box::new(CreateSecureOdataChild),
+            ],
+        )
+        .await
+        .map_err(|e| anyhow!(e.to_string()))
+        .expect("migrate");
 
         let tenant_id = Uuid::new_v4();
         let scope = AccessScope::tenants_only(vec![tenant_id]);
@@ -184,3 +359,273 @@ async fn paginate_odata_works_with_secure_conn() {
 
     assert_eq!(page.items.len(), 2, "page size");
 }
+
+fn ent_field_map() -> FieldMap<ent::Entity> {
+    FieldMap::new()
+        .insert_with_extractor("id", ent::Column::Id, FieldKind::I64, |m: &ent::Model| {
+            m.id.to_string()
+        })
+        .insert("name", ent::Column::Name, FieldKind::String)
+        .insert("score", ent::Column::Score, FieldKind::I64)
+}
+
+const LIMITS: LimitCfg = LimitCfg {
+    default: 25,
+    max: 1000,
+    max_count: 3,
+};
+
+#[tokio::test]
+async fn opager_counts_matching_rows_up_to_cap() {
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    seed(&conn, test_db.tenant_id, &test_db.scope).await;
+    let fmap = ent_field_map();
+
+    let q = ODataQuery::new().with_limit(1).with_count(true);
+    let page = OPager::<ent::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .fetch(&q, |m| m.name)
+        .await
+        .expect("fetch");
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.page_info.total_count, Some(4));
+    assert!(!page.page_info.total_count_capped);
+
+    let page = OPager::<ent::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .count_cap(2)
+        .fetch(&q, |m| m.name)
+        .await
+        .expect("fetch");
+    assert_eq!(page.page_info.total_count, Some(2));
+    assert!(page.page_info.total_count_capped);
+
+    // Without $count no COUNT is reported
+    let page = OPager::<ent::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .fetch(&ODataQuery::new(), |m| m.name)
+        .await
+        .expect("fetch");
+    assert_eq!(page.page_info.total_count, None);
+
+    // Other tenants' rows are never counted
+    let other = AccessScope::tenants_only(vec![Uuid::new_v4()]);
+    let page = OPager::<ent::Entity, _>::new(&other, &conn, &fmap)
+        .fetch(&q, |m| m.name)
+        .await
+        .expect("fetch");
+    assert_eq!(page.page_info.total_count, Some(0));
+}
+
+#[tokio::test]
+async fn opager_rejects_search() {
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    let fmap = ent_field_map();
+
+    let q = ODataQuery::new().with_search("alice");
+    let res = OPager::<ent::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .fetch(&q, |m| m.name)
+        .await;
+    assert!(matches!(res, Err(ODataError::InvalidSearch(_))));
+}
+
+#[tokio::test]
+async fn paginate_odata_searches_and_counts() {
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    seed(&conn, test_db.tenant_id, &test_db.scope).await;
+
+    // "li" matches alice and charlie; count covers all matches, not just the page
+    let q = ODataQuery::new()
+        .with_search("li")
+        .with_count(true)
+        .with_limit(1);
+    let select = ent::Entity::find().secure().scope_with(&test_db.scope);
+    let page = paginate_odata::<EntField, EntMapper, _, _, _, _>(
+        select,
+        &conn,
+        &q,
+        ("id", SortDir::Asc),
+        LIMITS,
+        |m| m.name,
+    )
+    .await
+    .expect("paginate");
+    assert_eq!(page.items, vec!["alice".to_owned()]);
+    assert_eq!(page.page_info.total_count, Some(2));
+    assert!(page.page_info.next_cursor.is_some());
+
+    // Terms are ANDed
+    let q = ODataQuery::new().with_search("li ar");
+    let select = ent::Entity::find().secure().scope_with(&test_db.scope);
+    let page = paginate_odata::<EntField, EntMapper, _, _, _, _>(
+        select,
+        &conn,
+        &q,
+        ("id", SortDir::Asc),
+        LIMITS,
+        |m| m.name,
+    )
+    .await
+    .expect("paginate");
+    assert_eq!(page.items, vec!["charlie".to_owned()]);
+
+    // LIKE wildcards in the search are matched literally
+    let q = ODataQuery::new().with_search("%");
+    let select = ent::Entity::find().secure().scope_with(&test_db.scope);
+    let page = paginate_odata::<EntField, EntMapper, _, _, _, _>(
+        select,
+        &conn,
+        &q,
+        ("id", SortDir::Asc),
+        LIMITS,
+        |m| m.name,
+    )
+    .await
+    .expect("paginate");
+    assert!(page.items.is_empty());
+
+    // Count is capped by LimitCfg::max_count
+    let q = ODataQuery::new().with_count(true);
+    let select = ent::Entity::find().secure().scope_with(&test_db.scope);
+    let page = paginate_odata::<EntField, EntMapper, _, _, _, _>(
+        select,
+        &conn,
+        &q,
+        ("id", SortDir::Asc),
+        LIMITS,
+        |m| m.name,
+    )
+    .await
+    .expect("paginate");
+    assert_eq!(page.page_info.total_count, Some(3));
+    assert!(page.page_info.total_count_capped);
+}
+
+/// Seed `ent` rows and give alice one child in scope and bob one of another tenant
+async fn seed_children(test_db: &TestDb, conn: &DbConn<'_>) -> Vec<ent::Model> {
+    seed(conn, test_db.tenant_id, &test_db.scope).await;
+    let parents = ent::Entity::find()
+        .secure()
+        .scope_with(&test_db.scope)
+        .all(conn)
+        .await
+        .expect("parents");
+
+    let other_tenant = Uuid::new_v4();
+    for (parent, tenant_id, label) in [
+        ("alice", test_db.tenant_id, "mine"),
+        ("bob", other_tenant, "theirs"),
+    ] {
+        let am = child::ActiveModel {
+            tenant_id: Set(tenant_id),
+            parent_id: Set(parents.iter().find(|p| p.name == parent).unwrap().id),
+            label: Set(label.to_owned()),
+            ..Default::default()
+        };
+        secure_insert::<child::Entity>(am, &AccessScope::tenants_only(vec![tenant_id]), conn)
+            .await
+            .expect("insert child");
+    }
+    parents
+}
+
+#[tokio::test]
+async fn find_with_related_scopes_related_rows_in_where() {
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    seed_children(&test_db, &conn).await;
+
+    // Parents without related rows in scope are filtered out
+    let rows = ent::Entity::find()
+        .secure()
+        .scope_with(&test_db.scope)
+        .find_with_related(child::Entity)
+        .all(&conn)
+        .await
+        .expect("find_with_related");
+    let names: Vec<_> = rows.iter().map(|(p, _)| p.name.as_str()).collect();
+    assert_eq!(names, vec!["alice"]);
+    assert_eq!(rows[0].1.len(), 1);
+    assert_eq!(rows[0].1[0].label, "mine");
+}
+
+#[tokio::test]
+async fn expand_with_related_scopes_related_rows_in_join() {
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    let parents = seed_children(&test_db, &conn).await;
+
+    // Every parent in scope is kept, with only its children in scope
+    let rows = ent::Entity::find()
+        .secure()
+        .scope_with(&test_db.scope)
+        .expand_with_related(child::Entity)
+        .all(&conn)
+        .await
+        .expect("expand_with_related");
+    assert_eq!(rows.len(), parents.len());
+    for (parent, children) in &rows {
+        let labels: Vec<_> = children.iter().map(|c| c.label.as_str()).collect();
+        let expected = if parent.name == "alice" {
+            vec!["mine"]
+        } else {
+            vec![]
+        };
+        assert_eq!(labels, expected, "{}", parent.name);
+    }
+}
+
+#[tokio::test]
+async fn expand_related_applies_scope_to_related_rows() {
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    seed(&conn, test_db.tenant_id, &test_db.scope).await;
+
+    let parents = ent::Entity::find()
+        .secure()
+        .scope_with(&test_db.scope)
+        .all(&conn)
+        .await
+        .expect("parents");
+    let alice = parents.iter().find(|p| p.name == "alice").unwrap().id;
+
+    // One child in scope and one belonging to another tenant, both pointing at alice
+    let other_tenant = Uuid::new_v4();
+    for (tenant_id, label) in [(test_db.tenant_id, "mine"), (other_tenant, "theirs")] {
+        let am = child::ActiveModel {
+            tenant_id: Set(tenant_id),
+            parent_id: Set(alice),
+            label: Set(label.to_owned()),
+            ..Default::default()
+        };
+        secure_insert::<child::Entity>(am, &AccessScope::tenants_only(vec![tenant_id]), &conn)
+            .await
+            .expect("insert child");
+    }
+
+    let rows = expand_related::<ent::Entity, _, _, _>(
+        &conn,
+        &test_db.scope,
+        ent::Column::Id,
+        parents.iter().map(|p| p.id),
+        child::Entity,
+    )
+    .await
+    .expect("expand");
+
+    assert_eq!(rows.len(), parents.len());
+    let (_, children) = rows.iter().find(|(p, _)| p.id == alice).unwrap();
+    let labels: Vec<_> = children.iter().map(|c| c.label.as_str()).collect();
+    assert_eq!(labels, vec!["mine"]);
+
+    let none = expand_related::<ent::Entity, _, _, _>(
+        &conn,
+        &test_db.scope,
+        ent::Column::Id,
+        Vec::<i64>::new(),
+        child::Entity,
+    )
+    .await
+    .expect("expand");
+    assert!(none.is_empty());
+}
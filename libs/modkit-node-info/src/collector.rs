@@ -1,5 +1,5 @@
 use crate::error::NodeInfoError;
-use crate::model::{Node, NodeSysCap, NodeSysInfo};
+use crate::model::{Node, NodeSysCap, NodeSysInfo, ResourceUsage};
 use crate::syscap_collector::SysCapCollector;
 use crate::sysinfo_collector::SysInfoCollector;
 use std::sync::Arc;
@@ -75,6 +75,14 @@ impl NodeInfoCollector {
             .map_err(|e| NodeInfoError::SysCapCollectionFailed(e.to_string()))
     }
 
+    /// Sample current CPU, memory and GPU memory usage.
+    ///
+    /// # Errors
+    /// Returns `NodeInfoError::SysInfoCollectionFailed` if the system state cannot be read.
+    pub fn collect_usage(&self) -> Result<ResourceUsage, NodeInfoError> {
+        self.sysinfo_collector.collect_usage()
+    }
+
     /// Collect both sysinfo and syscap.
     ///
     /// # Errors
@@ -0,0 +1,216 @@
+//! Container and cgroup detection.
+//!
+//! Everything here reads plain files below a root directory (`/` in production), so
+//! detection can be exercised against fixture `/sys/fs/cgroup` and `/proc` trees.
+
+use crate::model::{CgroupLimits, CgroupVersion, ContainerInfo, ContainerRuntime, KubernetesInfo};
+use std::path::{Path, PathBuf};
+
+/// cgroup v1 reports "no limit" as a huge page-aligned value rather than a marker
+const CGROUP_V1_UNLIMITED: u64 = 1 << 60;
+
+const K8S_NAMESPACE_FILE: &str = "var/run/secrets/kubernetes.io/serviceaccount/namespace";
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    std::fs::read_to_string(path)
+        .ok()
+        .map(|s| s.trim().to_owned())
+}
+
+/// Path of the process's cgroup for `controller` ("" for the v2 unified hierarchy),
+/// taken from `/proc/self/cgroup`
+fn own_cgroup_path(root: &Path, controller: &str) -> Option<String> {
+    let content = std::fs::read_to_string(root.join("proc/self/cgroup")).ok()?;
+    content.lines().find_map(|line| {
+        let mut parts = line.splitn(3, ':');
+        let _id = parts.next()?;
+        let controllers = parts.next()?;
+        let path = parts.next()?;
+        let matches = if controller.is_empty() {
+            controllers.is_empty()
+        } else {
+            controllers.split(',').any(|c| c == controller)
+        };
+        matches.then(|| path.to_owned())
+    })
+}
+
+/// Directory holding the process's cgroup files below `mount`.
+///
+/// Inside a cgroup namespace the process's cgroup is the mount root; otherwise it is
+/// the path from `/proc/self/cgroup`, if that exists in the tree.
+fn cgroup_dir(root: &Path, mount: &Path, controller: &str) -> PathBuf {
+    own_cgroup_path(root, controller)
+        .map(|path| mount.join(path.trim_start_matches('/')))
+        .filter(|dir| *dir != *mount && dir.is_dir())
+        .unwrap_or_else(|| mount.to_path_buf())
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn quota_cores(quota: u64, period: u64) -> Option<f64> {
+    (period > 0).then(|| quota as f64 / period as f64)
+}
+
+fn read_v2(root: &Path, mount: &Path) -> CgroupLimits {
+    let dir = cgroup_dir(root, mount, "");
+
+    // cpu.max: "<quota|max> <period>"
+    let cpu_quota_cores = read_trimmed(&dir.join("cpu.max")).and_then(|s| {
+        let mut parts = s.split_whitespace();
+        let quota = parts.next()?.parse::<u64>().ok()?;
+        let period = parts.next().map_or(Some(100_000), |p| p.parse().ok())?;
+        quota_cores(quota, period)
+    });
+    let memory_limit_bytes =
+        read_trimmed(&dir.join("memory.max")).and_then(|s| s.parse::<u64>().ok());
+    let memory_usage_bytes =
+        read_trimmed(&dir.join("memory.current")).and_then(|s| s.parse::<u64>().ok());
+
+    CgroupLimits {
+        version: CgroupVersion::V2,
+        cpu_quota_cores,
+        memory_limit_bytes,
+        memory_usage_bytes,
+    }
+}
+
+fn read_v1(root: &Path, mount: &Path) -> CgroupLimits {
+    let cpu_mount = ["cpu", "cpu,cpuacct", "cpuacct,cpu"]
+        .iter()
+        .map(|name| mount.join(name))
+        .find(|dir| dir.is_dir());
+    let cpu_quota_cores = cpu_mount.and_then(|cpu_mount| {
+        let dir = cgroup_dir(root, &cpu_mount, "cpu");
+        // -1 means no quota
+        let quota = read_trimmed(&dir.join("cpu.cfs_quota_us"))?
+            .parse::<i64>()
+            .ok()?;
+        let quota = u64::try_from(quota).ok()?;
+        let period = read_trimmed(&dir.join("cpu.cfs_period_us"))?
+            .parse::<u64>()
+            .ok()?;
+        quota_cores(quota, period)
+    });
+
+    let memory_dir = cgroup_dir(root, &mount.join("memory"), "memory");
+    let memory_limit_bytes = read_trimmed(&memory_dir.join("memory.limit_in_bytes"))
+        .and_then(|s| s.parse::<u64>().ok())
+        .filter(|limit| *limit < CGROUP_V1_UNLIMITED);
+    let memory_usage_bytes =
+        read_trimmed(&memory_dir.join("memory.usage_in_bytes")).and_then(|s| s.parse::<u64>().ok());
+
+    CgroupLimits {
+        version: CgroupVersion::V1,
+        cpu_quota_cores,
+        memory_limit_bytes,
+        memory_usage_bytes,
+    }
+}
+
+/// Read the CPU quota and memory limit of the cgroup this process runs in.
+///
+/// `root` is the filesystem root (`/` outside of tests). Returns `None` if no
+/// cgroup hierarchy is mounted below `root/sys/fs/cgroup`.
+#[must_use]
+pub fn read_cgroup_limits(root: &Path) -> Option<CgroupLimits> {
+    let mount = root.join("sys/fs/cgroup");
+    if mount.join("cgroup.controllers").is_file() {
+        Some(read_v2(root, &mount))
+    } else if mount.join("memory").is_dir() || mount.join("cpu").is_dir() {
+        Some(read_v1(root, &mount))
+    } else {
+        None
+    }
+}
+
+fn runtime_from_cgroup(content: &str) -> Option<ContainerRuntime> {
+    let markers = [
+        ("libpod", ContainerRuntime::Podman),
+        ("crio", ContainerRuntime::CriO),
+        ("docker", ContainerRuntime::Docker),
+        ("containerd", ContainerRuntime::Containerd),
+        ("lxc", ContainerRuntime::Lxc),
+    ];
+    markers
+        .iter()
+        .find(|(marker, _)| content.contains(marker))
+        .map(|(_, runtime)| *runtime)
+}
+
+fn runtime_from_env(value: &str) -> ContainerRuntime {
+    match value {
+        "docker" => ContainerRuntime::Docker,
+        "podman" => ContainerRuntime::Podman,
+        "lxc" | "lxc-libvirt" => ContainerRuntime::Lxc,
+        _ => ContainerRuntime::Unknown,
+    }
+}
+
+/// First 64-hex-digit segment in the cgroup paths, which is how the common runtimes
+/// name container cgroups
+fn container_id(content: &str) -> Option<String> {
+    let re = regex::Regex::new(r"[0-9a-f]{64}").ok()?;
+    re.find(content).map(|m| m.as_str().to_owned())
+}
+
+fn kubernetes_info(
+    root: &Path,
+    env: &dyn Fn(&str) -> Option<String>,
+    in_kubepods: bool,
+) -> Option<KubernetesInfo> {
+    let namespace_file = read_trimmed(&root.join(K8S_NAMESPACE_FILE));
+    let in_cluster = env("KUBERNETES_SERVICE_HOST").is_some() || namespace_file.is_some();
+    if !in_cluster && !in_kubepods {
+        return None;
+    }
+    Some(KubernetesInfo {
+        namespace: env("POD_NAMESPACE").or(namespace_file),
+        pod_name: env("POD_NAME").or_else(|| env("HOSTNAME")),
+        node_name: env("NODE_NAME"),
+    })
+}
+
+/// Detect whether this process runs in a container, and in which runtime/orchestrator.
+///
+/// Looks at runtime marker files (`/.dockerenv`, `/run/.containerenv`), the cgroup
+/// paths of PID 1 and of this process, the `container` env variable set by systemd
+/// compatible runtimes, and Kubernetes service account mounts and downward-API env.
+#[must_use]
+pub fn detect_container(
+    root: &Path,
+    env: &dyn Fn(&str) -> Option<String>,
+) -> Option<ContainerInfo> {
+    let cgroups = ["proc/1/cgroup", "proc/self/cgroup"]
+        .iter()
+        .filter_map(|path| std::fs::read_to_string(root.join(path)).ok())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mountinfo = std::fs::read_to_string(root.join("proc/self/mountinfo")).unwrap_or_default();
+
+    let runtime = if root.join(".dockerenv").exists() {
+        Some(ContainerRuntime::Docker)
+    } else if root.join("run/.containerenv").exists() {
+        Some(ContainerRuntime::Podman)
+    } else {
+        runtime_from_cgroup(&cgroups)
+            .or_else(|| {
+                mountinfo
+                    .contains("/docker/containers/")
+                    .then_some(ContainerRuntime::Docker)
+            })
+            .or_else(|| env("container").map(|value| runtime_from_env(&value)))
+    };
+
+    let kubernetes = kubernetes_info(root, env, cgroups.contains("kubepods"));
+    let runtime = match (runtime, &kubernetes) {
+        (Some(runtime), _) => runtime,
+        (None, Some(_)) => ContainerRuntime::Unknown,
+        (None, None) => return None,
+    };
+
+    Some(ContainerInfo {
+        runtime,
+        container_id: container_id(&cgroups).or_else(|| container_id(&mountinfo)),
+        kubernetes,
+    })
+}
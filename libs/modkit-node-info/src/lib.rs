@@ -5,6 +5,8 @@
 //! where the code is executed. It collects:
 //! - System information (OS, CPU, memory, GPU, battery, host)
 //! - System capabilities (hardware and OS capabilities)
+//! - Container awareness: cgroup v1/v2 CPU quota and memory limits, container runtime
+//!   and Kubernetes detection
 //!
 //! This is a standalone library that can be used by any module to collect
 //! information about the current execution environment.
@@ -25,8 +27,10 @@ pub mod error;
 pub mod model;
 
 mod collector;
+mod container;
 
 pub use collector::NodeInfoCollector;
+pub use container::{detect_container, read_cgroup_limits};
 pub use error::NodeInfoError;
 pub use hardware_uuid::get_hardware_uuid;
 pub use model::*;
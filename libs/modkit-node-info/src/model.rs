@@ -18,6 +18,12 @@ pub struct NodeSysInfo {
     pub host: HostInfo,
     pub gpus: Vec<GpuInfo>,
     pub battery: Option<BatteryInfo>,
+    /// Mounted disk volumes
+    pub disks: Vec<DiskInfo>,
+    /// Network interfaces, sorted by name
+    pub network_interfaces: Vec<NetworkInterfaceInfo>,
+    /// Set when running inside a container
+    pub container: Option<ContainerInfo>,
     pub collected_at: chrono::DateTime<chrono::Utc>,
 }
 
@@ -36,15 +42,21 @@ pub struct CpuInfo {
     pub num_cpus: u32,
     pub cores: u32,
     pub frequency_mhz: f64,
+    /// CPU quota from the cgroup (in cores), if one is set
+    pub quota_cores: Option<f64>,
 }
 
-/// Memory information
+/// Memory information.
+///
+/// Inside a memory-limited cgroup, totals and usage are those of the cgroup.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MemoryInfo {
     pub total_bytes: u64,
     pub available_bytes: u64,
     pub used_bytes: u64,
     pub used_percent: u32,
+    /// Memory limit from the cgroup, if one below the host total is set
+    pub limit_bytes: Option<u64>,
 }
 
 /// Host information
@@ -72,6 +84,105 @@ pub struct BatteryInfo {
     pub percentage: u32,
 }
 
+/// Point-in-time resource usage, for sampling metrics over time.
+///
+/// Memory figures are the cgroup's when a memory limit applies; CPU usage is across
+/// all host CPUs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourceUsage {
+    /// Average CPU usage since the previous sample, 0-100
+    pub cpu_percent: f64,
+    pub memory_used_bytes: u64,
+    pub memory_total_bytes: u64,
+    /// Summed over GPUs that report memory usage
+    pub gpu_memory_used_mb: Option<f64>,
+    pub gpu_memory_total_mb: Option<f64>,
+    pub collected_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Mounted disk volume
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiskInfo {
+    pub name: String,
+    pub mount_point: String,
+    pub file_system: String,
+    pub total_bytes: u64,
+    pub available_bytes: u64,
+    pub is_removable: bool,
+    pub is_read_only: bool,
+}
+
+/// Network interface
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkInterfaceInfo {
+    pub name: String,
+    pub mac_address: Option<String>,
+    pub ip_addresses: Vec<String>,
+    pub mtu: u64,
+    pub received_bytes: u64,
+    pub transmitted_bytes: u64,
+}
+
+/// Resource limits of the cgroup the process runs in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CgroupLimits {
+    pub version: CgroupVersion,
+    /// `quota / period` from `cpu.max` (v2) or `cpu.cfs_quota_us` (v1)
+    pub cpu_quota_cores: Option<f64>,
+    pub memory_limit_bytes: Option<u64>,
+    pub memory_usage_bytes: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CgroupVersion {
+    V1,
+    V2,
+}
+
+/// Container runtime the process runs under
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContainerRuntime {
+    Docker,
+    Podman,
+    Containerd,
+    CriO,
+    Lxc,
+    /// Containerized (e.g. a Kubernetes pod), but the runtime is not identifiable
+    Unknown,
+}
+
+impl ContainerRuntime {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Docker => "docker",
+            Self::Podman => "podman",
+            Self::Containerd => "containerd",
+            Self::CriO => "cri-o",
+            Self::Lxc => "lxc",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+/// Container the process runs in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainerInfo {
+    pub runtime: ContainerRuntime,
+    pub container_id: Option<String>,
+    /// Set when running in a Kubernetes pod
+    pub kubernetes: Option<KubernetesInfo>,
+}
+
+/// Pod metadata from the Kubernetes downward API (`POD_NAME`, `POD_NAMESPACE`,
+/// `NODE_NAME` env) and the service account mount
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KubernetesInfo {
+    pub namespace: Option<String>,
+    pub pod_name: Option<String>,
+    pub node_name: Option<String>,
+}
+
 /// System capability information for a node
 #[derive(Debug, Clone, PartialEq)]
 pub struct NodeSysCap {
@@ -115,6 +115,11 @@ impl SysCapCollector {
         // Collect battery capabilities using sysinfo data
         capabilities.extend(Self::collect_battery_caps(&sysinfo));
 
+        // Collect container, storage and network capabilities using sysinfo data
+        capabilities.extend(Self::collect_container_caps(&sysinfo));
+        capabilities.extend(Self::collect_storage_caps(&sysinfo));
+        capabilities.extend(Self::collect_network_caps(&sysinfo));
+
         // Collect software capabilities
         capabilities.extend(Self::collect_software_caps());
 
@@ -154,8 +159,14 @@ impl SysCapCollector {
             .amount(Some(total_gb))
             .amount_dimension(Some("GB".to_owned()))
             .details(Some(format!(
-                "Total: {:.2} GB, Used: {}%",
-                total_gb, sysinfo.memory.used_percent
+                "Total: {:.2} GB{}, Used: {}%",
+                total_gb,
+                if sysinfo.memory.limit_bytes.is_some() {
+                    " (cgroup limit)"
+                } else {
+                    ""
+                },
+                sysinfo.memory.used_percent
             )))
             .cache_ttl_secs(5) // 5 seconds cache (changes frequently)
             .build(),
@@ -180,6 +191,23 @@ impl SysCapCollector {
             .build(),
         );
 
+        // CPU quota from the cgroup; bounds usable CPU below the core count
+        if let Some(quota) = sysinfo.cpu.quota_cores {
+            caps.push(
+                SysCapBuilder::new(
+                    "hardware:cpu_quota".to_owned(),
+                    "hardware".to_owned(),
+                    "cpu_quota".to_owned(),
+                    "CPU Quota".to_owned(),
+                )
+                .amount(Some(quota))
+                .amount_dimension(Some("cores".to_owned()))
+                .details(Some(format!("cgroup CPU quota: {quota:.2} cores")))
+                .cache_ttl_secs(60) // 1 minute cache (limits can be updated in place)
+                .build(),
+            );
+        }
+
         caps
     }
 
@@ -286,6 +314,109 @@ impl SysCapCollector {
         caps
     }
 
+    fn collect_container_caps(sysinfo: &NodeSysInfo) -> Vec<SysCap> {
+        let mut caps = Vec::new();
+
+        let Some(container) = &sysinfo.container else {
+            return caps;
+        };
+        let runtime = container.runtime.as_str();
+        caps.push(
+            SysCapBuilder::new(
+                "runtime:container".to_owned(),
+                "runtime".to_owned(),
+                runtime.to_owned(),
+                "Container".to_owned(),
+            )
+            .details(Some(match &container.container_id {
+                Some(id) => format!("Runtime: {runtime}, Container: {id}"),
+                None => format!("Runtime: {runtime}"),
+            }))
+            .cache_ttl_secs(3600) // 1 hour cache (never changes)
+            .build(),
+        );
+
+        if let Some(k8s) = &container.kubernetes {
+            let field = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_owned());
+            caps.push(
+                SysCapBuilder::new(
+                    "runtime:kubernetes".to_owned(),
+                    "runtime".to_owned(),
+                    "kubernetes".to_owned(),
+                    "Kubernetes".to_owned(),
+                )
+                .details(Some(format!(
+                    "Namespace: {}, Pod: {}, Node: {}",
+                    field(&k8s.namespace),
+                    field(&k8s.pod_name),
+                    field(&k8s.node_name)
+                )))
+                .cache_ttl_secs(3600) // 1 hour cache (never changes)
+                .build(),
+            );
+        }
+
+        caps
+    }
+
+    fn collect_storage_caps(sysinfo: &NodeSysInfo) -> Vec<SysCap> {
+        sysinfo
+            .disks
+            .iter()
+            .map(|disk| {
+                let available_gb = bytes_to_gb(disk.available_bytes);
+                SysCapBuilder::new(
+                    format!("storage:{}", disk.mount_point),
+                    "storage".to_owned(),
+                    disk.mount_point.clone(),
+                    format!("Volume {}", disk.mount_point),
+                )
+                .amount(Some(available_gb))
+                .amount_dimension(Some("GB".to_owned()))
+                .details(Some(format!(
+                    "{} ({}), Free: {:.2} GB of {:.2} GB{}",
+                    disk.name,
+                    disk.file_system,
+                    available_gb,
+                    bytes_to_gb(disk.total_bytes),
+                    if disk.is_read_only { ", read-only" } else { "" }
+                )))
+                .cache_ttl_secs(30) // 30 seconds cache (free space changes)
+                .build()
+            })
+            .collect()
+    }
+
+    fn collect_network_caps(sysinfo: &NodeSysInfo) -> Vec<SysCap> {
+        sysinfo
+            .network_interfaces
+            .iter()
+            // Loopback interfaces say nothing about the node's connectivity
+            .filter(|iface| {
+                !iface.ip_addresses.is_empty()
+                    && !iface.ip_addresses.iter().all(|ip| {
+                        ip.parse::<std::net::IpAddr>()
+                            .is_ok_and(|ip| ip.is_loopback())
+                    })
+            })
+            .map(|iface| {
+                SysCapBuilder::new(
+                    format!("network:{}", iface.name),
+                    "network".to_owned(),
+                    iface.name.clone(),
+                    format!("Network {}", iface.name),
+                )
+                .details(Some(format!(
+                    "IPs: {}, MTU: {}",
+                    iface.ip_addresses.join(", "),
+                    iface.mtu
+                )))
+                .cache_ttl_secs(60) // 1 minute cache (addresses can change)
+                .build()
+            })
+            .collect()
+    }
+
     fn collect_software_caps() -> Vec<SysCap> {
         // Software capability detection can be extended here
         // For now, return empty list
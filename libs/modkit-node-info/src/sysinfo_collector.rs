@@ -1,6 +1,10 @@
 use crate::error::NodeInfoError;
-use crate::model::{BatteryInfo, CpuInfo, GpuInfo, HostInfo, MemoryInfo, NodeSysInfo, OsInfo};
-use sysinfo::System;
+use crate::model::{
+    BatteryInfo, CgroupLimits, ContainerInfo, CpuInfo, DiskInfo, GpuInfo, HostInfo, MemoryInfo,
+    NetworkInterfaceInfo, NodeSysInfo, OsInfo, ResourceUsage,
+};
+use std::path::Path;
+use sysinfo::{Disks, Networks, System};
 
 /// Calculate percentage as u32 (0-100) from used/total values.
 /// Returns 0 if total is 0 to avoid division by zero.
@@ -39,12 +43,16 @@ impl SysInfoCollector {
         sys.refresh_cpu_all();
         sys.refresh_memory();
 
+        let cgroup = Self::collect_cgroup_limits();
         let os_info = Self::collect_os_info();
-        let cpu_info = Self::collect_cpu_info(&sys);
-        let memory_info = Self::collect_memory_info(&sys);
+        let cpu_info = Self::collect_cpu_info(&sys, cgroup.as_ref());
+        let memory_info = Self::collect_memory_info(&sys, cgroup.as_ref());
         let host_info = Self::collect_host_info();
         let gpus = Self::collect_gpu_info();
         let battery = Self::collect_battery_info();
+        let disks = Self::collect_disks();
+        let network_interfaces = Self::collect_network_interfaces();
+        let container = Self::collect_container_info();
 
         Ok(NodeSysInfo {
             node_id,
@@ -54,6 +62,46 @@ impl SysInfoCollector {
             host: host_info,
             gpus,
             battery,
+            disks,
+            network_interfaces,
+            container,
+            collected_at: chrono::Utc::now(),
+        })
+    }
+
+    /// Sample CPU, memory and GPU memory usage.
+    ///
+    /// CPU usage is measured since the previous refresh of this collector, so the
+    /// first sample after creation may read 0.
+    pub fn collect_usage(&self) -> Result<ResourceUsage, NodeInfoError> {
+        let mut sys = self
+            .system
+            .lock()
+            .map_err(|e| NodeInfoError::SysInfoCollectionFailed(e.to_string()))?;
+        sys.refresh_cpu_usage();
+        sys.refresh_memory();
+
+        let cgroup = Self::collect_cgroup_limits();
+        let memory = Self::collect_memory_info(&sys, cgroup.as_ref());
+        let gpus: Vec<GpuInfo> = Self::collect_gpu_info()
+            .into_iter()
+            .filter(|gpu| gpu.total_memory_mb.is_some() && gpu.used_memory_mb.is_some())
+            .collect();
+        let (gpu_memory_used_mb, gpu_memory_total_mb) = if gpus.is_empty() {
+            (None, None)
+        } else {
+            (
+                Some(gpus.iter().filter_map(|gpu| gpu.used_memory_mb).sum()),
+                Some(gpus.iter().filter_map(|gpu| gpu.total_memory_mb).sum()),
+            )
+        };
+
+        Ok(ResourceUsage {
+            cpu_percent: f64::from(sys.global_cpu_usage()),
+            memory_used_bytes: memory.used_bytes,
+            memory_total_bytes: memory.total_bytes,
+            gpu_memory_used_mb,
+            gpu_memory_total_mb,
             collected_at: chrono::Utc::now(),
         })
     }
@@ -71,7 +119,7 @@ impl SysInfoCollector {
     }
 
     #[allow(clippy::cast_precision_loss)]
-    fn collect_cpu_info(sys: &System) -> CpuInfo {
+    fn collect_cpu_info(sys: &System, cgroup: Option<&CgroupLimits>) -> CpuInfo {
         let cpus = sys.cpus();
         // CPU count is always small, safe to truncate
         let num_cpus = u32::try_from(cpus.len()).unwrap_or(u32::MAX);
@@ -98,13 +146,27 @@ impl SysInfoCollector {
             num_cpus,
             cores,
             frequency_mhz,
+            quota_cores: cgroup.and_then(|c| c.cpu_quota_cores),
         }
     }
 
-    fn collect_memory_info(sys: &System) -> MemoryInfo {
-        let total_bytes = sys.total_memory();
-        let available_bytes = sys.available_memory();
-        let used_bytes = sys.used_memory();
+    fn collect_memory_info(sys: &System, cgroup: Option<&CgroupLimits>) -> MemoryInfo {
+        let host_total = sys.total_memory();
+        // A cgroup limit above the host total is no limit in practice
+        let limit_bytes = cgroup
+            .and_then(|c| c.memory_limit_bytes)
+            .filter(|limit| *limit < host_total);
+
+        let (total_bytes, used_bytes, available_bytes) = match limit_bytes {
+            Some(limit) => {
+                let used = cgroup
+                    .and_then(|c| c.memory_usage_bytes)
+                    .unwrap_or_else(|| sys.used_memory())
+                    .min(limit);
+                (limit, used, limit - used)
+            }
+            None => (host_total, sys.used_memory(), sys.available_memory()),
+        };
         // Calculate percentage (0-100) using integer math to avoid float precision issues
         let used_percent = calculate_percent(used_bytes, total_bytes);
 
@@ -113,9 +175,68 @@ impl SysInfoCollector {
             available_bytes,
             used_bytes,
             used_percent,
+            limit_bytes,
         }
     }
 
+    fn collect_cgroup_limits() -> Option<CgroupLimits> {
+        if cfg!(target_os = "linux") {
+            crate::container::read_cgroup_limits(Path::new("/"))
+        } else {
+            None
+        }
+    }
+
+    fn collect_container_info() -> Option<ContainerInfo> {
+        if cfg!(target_os = "linux") {
+            crate::container::detect_container(Path::new("/"), &|name| std::env::var(name).ok())
+        } else {
+            None
+        }
+    }
+
+    fn collect_disks() -> Vec<DiskInfo> {
+        let disks = Disks::new_with_refreshed_list();
+        disks
+            .list()
+            .iter()
+            .map(|disk| DiskInfo {
+                name: disk.name().to_string_lossy().into_owned(),
+                mount_point: disk.mount_point().to_string_lossy().into_owned(),
+                file_system: disk.file_system().to_string_lossy().into_owned(),
+                total_bytes: disk.total_space(),
+                available_bytes: disk.available_space(),
+                is_removable: disk.is_removable(),
+                is_read_only: disk.is_read_only(),
+            })
+            .collect()
+    }
+
+    fn collect_network_interfaces() -> Vec<NetworkInterfaceInfo> {
+        let networks = Networks::new_with_refreshed_list();
+        let mut interfaces: Vec<NetworkInterfaceInfo> = networks
+            .list()
+            .iter()
+            .map(|(name, data)| {
+                let mac = data.mac_address();
+                NetworkInterfaceInfo {
+                    name: name.clone(),
+                    mac_address: (!mac.is_unspecified()).then(|| mac.to_string()),
+                    ip_addresses: data
+                        .ip_networks()
+                        .iter()
+                        .map(|net| net.addr.to_string())
+                        .collect(),
+                    mtu: data.mtu(),
+                    received_bytes: data.total_received(),
+                    transmitted_bytes: data.total_transmitted(),
+                }
+            })
+            .collect();
+        interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+        interfaces
+    }
+
     fn collect_host_info() -> HostInfo {
         let hostname = hostname::get().map_or_else(
             |_| "unknown".to_owned(),
@@ -0,0 +1,228 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Container and cgroup detection against fixture `/sys/fs/cgroup` and `/proc` trees.
+
+use modkit_node_info::{
+    CgroupVersion, ContainerRuntime, KubernetesInfo, detect_container, read_cgroup_limits,
+};
+use std::collections::HashMap;
+use std::path::Path;
+use tempfile::TempDir;
+
+const CONTAINER_ID: &str = "3f4e1a6c2b9d8e7f0a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f6071";
+
+fn fixture(files: &[(&str, &str)]) -> TempDir {
+    let root = tempfile::tempdir().unwrap();
+    for (path, content) in files {
+        let path = root.path().join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+    root
+}
+
+fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+    let vars: HashMap<String, String> = vars
+        .iter()
+        .map(|(k, v)| ((*k).to_owned(), (*v).to_owned()))
+        .collect();
+    move |name| vars.get(name).cloned()
+}
+
+#[test]
+fn cgroup_v2_limits() {
+    let root = fixture(&[
+        ("sys/fs/cgroup/cgroup.controllers", "cpu memory io"),
+        ("sys/fs/cgroup/cpu.max", "150000 100000\n"),
+        ("sys/fs/cgroup/memory.max", "536870912\n"),
+        ("sys/fs/cgroup/memory.current", "104857600\n"),
+        ("proc/self/cgroup", "0::/\n"),
+    ]);
+
+    let limits = read_cgroup_limits(root.path()).unwrap();
+
+    assert_eq!(limits.version, CgroupVersion::V2);
+    assert_eq!(limits.cpu_quota_cores, Some(1.5));
+    assert_eq!(limits.memory_limit_bytes, Some(536_870_912));
+    assert_eq!(limits.memory_usage_bytes, Some(104_857_600));
+}
+
+#[test]
+fn cgroup_v2_unlimited() {
+    let root = fixture(&[
+        ("sys/fs/cgroup/cgroup.controllers", "cpu memory"),
+        ("sys/fs/cgroup/cpu.max", "max 100000\n"),
+        ("sys/fs/cgroup/memory.max", "max\n"),
+    ]);
+
+    let limits = read_cgroup_limits(root.path()).unwrap();
+
+    assert_eq!(limits.cpu_quota_cores, None);
+    assert_eq!(limits.memory_limit_bytes, None);
+}
+
+#[test]
+fn cgroup_v2_follows_own_cgroup_path() {
+    // Without a cgroup namespace the process sees the whole hierarchy
+    let root = fixture(&[
+        ("sys/fs/cgroup/cgroup.controllers", "cpu memory"),
+        ("sys/fs/cgroup/memory.max", "max\n"),
+        (
+            "sys/fs/cgroup/system.slice/app.service/memory.max",
+            "1073741824\n",
+        ),
+        ("proc/self/cgroup", "0::/system.slice/app.service\n"),
+    ]);
+
+    let limits = read_cgroup_limits(root.path()).unwrap();
+
+    assert_eq!(limits.memory_limit_bytes, Some(1_073_741_824));
+}
+
+#[test]
+fn cgroup_v1_limits() {
+    let root = fixture(&[
+        ("sys/fs/cgroup/cpu,cpuacct/cpu.cfs_quota_us", "200000\n"),
+        ("sys/fs/cgroup/cpu,cpuacct/cpu.cfs_period_us", "100000\n"),
+        (
+            "sys/fs/cgroup/memory/memory.limit_in_bytes",
+            "9223372036854771712\n",
+        ),
+        ("sys/fs/cgroup/memory/memory.usage_in_bytes", "52428800\n"),
+    ]);
+
+    let limits = read_cgroup_limits(root.path()).unwrap();
+
+    assert_eq!(limits.version, CgroupVersion::V1);
+    assert_eq!(limits.cpu_quota_cores, Some(2.0));
+    // The v1 "unlimited" sentinel is not a limit
+    assert_eq!(limits.memory_limit_bytes, None);
+    assert_eq!(limits.memory_usage_bytes, Some(52_428_800));
+}
+
+#[test]
+fn cgroup_v1_no_cpu_quota() {
+    let root = fixture(&[
+        ("sys/fs/cgroup/cpu/cpu.cfs_quota_us", "-1\n"),
+        ("sys/fs/cgroup/cpu/cpu.cfs_period_us", "100000\n"),
+        ("sys/fs/cgroup/memory/memory.limit_in_bytes", "268435456\n"),
+    ]);
+
+    let limits = read_cgroup_limits(root.path()).unwrap();
+
+    assert_eq!(limits.cpu_quota_cores, None);
+    assert_eq!(limits.memory_limit_bytes, Some(268_435_456));
+}
+
+#[test]
+fn no_cgroup_hierarchy() {
+    let root = fixture(&[("proc/self/cgroup", "0::/\n")]);
+
+    assert!(read_cgroup_limits(root.path()).is_none());
+    assert!(read_cgroup_limits(Path::new("/nonexistent-root")).is_none());
+}
+
+#[test]
+fn docker_container() {
+    let root = fixture(&[
+        (".dockerenv", ""),
+        ("proc/self/cgroup", &format!("0::/docker/{CONTAINER_ID}\n")),
+    ]);
+
+    let info = detect_container(root.path(), &env(&[])).unwrap();
+
+    assert_eq!(info.runtime, ContainerRuntime::Docker);
+    assert_eq!(info.container_id.as_deref(), Some(CONTAINER_ID));
+    assert!(info.kubernetes.is_none());
+}
+
+#[test]
+fn docker_container_with_cgroup_namespace() {
+    // cgroup v2 with namespaces hides the id from /proc/self/cgroup
+    let root = fixture(&[
+        ("proc/self/cgroup", "0::/\n"),
+        (
+            "proc/self/mountinfo",
+            &format!(
+                "612 600 8:1 /var/lib/docker/containers/{CONTAINER_ID}/hostname /etc/hostname rw - ext4 /dev/sda1 rw\n"
+            ),
+        ),
+    ]);
+
+    let info = detect_container(root.path(), &env(&[])).unwrap();
+
+    assert_eq!(info.runtime, ContainerRuntime::Docker);
+    assert_eq!(info.container_id.as_deref(), Some(CONTAINER_ID));
+}
+
+#[test]
+fn kubernetes_pod() {
+    let root = fixture(&[
+        (
+            "proc/1/cgroup",
+            &format!("0::/kubepods.slice/kubepods-burstable.slice/crio-{CONTAINER_ID}.scope\n"),
+        ),
+        (
+            "var/run/secrets/kubernetes.io/serviceaccount/namespace",
+            "payments\n",
+        ),
+    ]);
+    let env = env(&[
+        ("KUBERNETES_SERVICE_HOST", "10.96.0.1"),
+        ("POD_NAME", "api-7d9f-x2x"),
+        ("NODE_NAME", "worker-3"),
+    ]);
+
+    let info = detect_container(root.path(), &env).unwrap();
+
+    assert_eq!(info.runtime, ContainerRuntime::CriO);
+    assert_eq!(info.container_id.as_deref(), Some(CONTAINER_ID));
+    assert_eq!(
+        info.kubernetes,
+        Some(KubernetesInfo {
+            namespace: Some("payments".to_owned()),
+            pod_name: Some("api-7d9f-x2x".to_owned()),
+            node_name: Some("worker-3".to_owned()),
+        })
+    );
+}
+
+#[test]
+fn kubernetes_downward_api_namespace_wins() {
+    let root = fixture(&[("proc/self/cgroup", "0::/\n")]);
+    let env = env(&[
+        ("KUBERNETES_SERVICE_HOST", "10.96.0.1"),
+        ("POD_NAMESPACE", "batch"),
+        ("HOSTNAME", "job-abc"),
+    ]);
+
+    let info = detect_container(root.path(), &env).unwrap();
+
+    assert_eq!(info.runtime, ContainerRuntime::Unknown);
+    let k8s = info.kubernetes.unwrap();
+    assert_eq!(k8s.namespace.as_deref(), Some("batch"));
+    assert_eq!(k8s.pod_name.as_deref(), Some("job-abc"));
+    assert_eq!(k8s.node_name, None);
+}
+
+#[test]
+fn podman_from_container_env() {
+    let root = fixture(&[("proc/self/cgroup", "0::/\n")]);
+
+    let info = detect_container(root.path(), &env(&[("container", "podman")])).unwrap();
+
+    assert_eq!(info.runtime, ContainerRuntime::Podman);
+}
+
+#[test]
+fn bare_host() {
+    let root = fixture(&[
+        ("proc/1/cgroup", "0::/init.scope\n"),
+        (
+            "proc/self/cgroup",
+            "0::/user.slice/user-1000.slice/session-2.scope\n",
+        ),
+    ]);
+
+    assert!(detect_container(root.path(), &env(&[])).is_none());
+}
@@ -32,10 +32,41 @@ mod odata_schema;
 /// pub struct UserQuery {
 ///     #[odata(filter(kind = "Uuid"))]
 ///     pub id: uuid::Uuid,
-///     #[odata(filter(kind = "String"))]
+///     #[odata(filter(kind = "String", searchable))]
 ///     pub email: String,
 /// }
 /// ```
+///
+/// `searchable` (allowed on `String` fields only) includes the field in `$search`
+/// free-text matching.
+///
+/// `ops(...)` enables filter operations beyond plain comparisons and
+/// `contains`/`startswith`/`endswith`:
+///
+/// | op | syntax | field kinds |
+/// |----|--------|-------------|
+/// | `in` | `id in (1, 2)` | any |
+/// | `has` | `flags has 4` | `I64` |
+/// | `tolower`, `toupper`, `length` | `tolower(email) eq 'a@b.c'` | `String` |
+/// | `year`, `month`, `day` | `year(created_at) eq 2024` | `DateTimeUtc`, `Date` |
+/// | `now` | `created_at lt now()` | `DateTimeUtc` |
+/// | `any`, `all` | `tags/any(t: t eq 'x')` | `String`, `I64`, `F64`, `Bool`, `Uuid` |
+/// | `groupby`, `countdistinct` | `$apply=groupby((status))` | any |
+/// | `sum`, `average` | `$apply=aggregate(amount with sum as total)` | `I64`, `F64`, `Decimal` |
+/// | `min`, `max` | `$apply=aggregate(amount with max as top)` | all but `Bool`, `Uuid` |
+///
+/// `any`/`all` turn the field into a collection (a JSON array column whose
+/// elements have the declared kind) and cannot be combined with other ops.
+///
+/// ```ignore
+/// #[derive(ODataFilterable)]
+/// pub struct ItemQuery {
+///     #[odata(filter(kind = "String", ops(in, tolower)))]
+///     pub name: String,
+///     #[odata(filter(kind = "String", ops(any, all)))]
+///     pub tags: Vec<String>,
+/// }
+/// ```
 #[proc_macro_derive(ODataFilterable, attributes(odata))]
 #[proc_macro_error]
 pub fn derive_odata_filterable(input: TokenStream) -> TokenStream {
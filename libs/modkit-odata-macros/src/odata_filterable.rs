@@ -12,10 +12,76 @@ struct FilterableField {
     field_name: String,
     /// The `FieldKind` variant name (e.g., "String", "Uuid", "`DateTimeUtc`")
     kind: String,
+    /// Whether the field participates in `$search`
+    searchable: bool,
+    /// `FilterCaps` constant names enabled through `ops(...)`
+    ops: Vec<&'static str>,
     /// Span for error reporting
     span: Span,
 }
 
+/// Operators, functions and `$apply` aggregations that can be enabled with `ops(...)`:
+/// `(name in the attribute, FilterCaps constant, allowed kinds)`
+const OPS: &[(&str, &str, &[&str])] = &[
+    ("in", "IN", &[]),
+    ("has", "HAS", &["I64"]),
+    ("tolower", "TOLOWER", &["String"]),
+    ("toupper", "TOUPPER", &["String"]),
+    ("length", "LENGTH", &["String"]),
+    ("year", "YEAR", &["DateTimeUtc", "Date"]),
+    ("month", "MONTH", &["DateTimeUtc", "Date"]),
+    ("day", "DAY", &["DateTimeUtc", "Date"]),
+    ("now", "NOW", &["DateTimeUtc"]),
+    ("any", "ANY", &["String", "I64", "F64", "Bool", "Uuid"]),
+    ("all", "ALL", &["String", "I64", "F64", "Bool", "Uuid"]),
+    ("groupby", "GROUPBY", &[]),
+    ("sum", "SUM", &["I64", "F64", "Decimal"]),
+    ("average", "AVERAGE", &["I64", "F64", "Decimal"]),
+    ("min", "MIN", ORDERED),
+    ("max", "MAX", ORDERED),
+    ("countdistinct", "COUNTDISTINCT", &[]),
+];
+
+/// Kinds with a meaningful `min`/`max`
+const ORDERED: &[&str] = &[
+    "String",
+    "I64",
+    "F64",
+    "Decimal",
+    "DateTimeUtc",
+    "Date",
+    "Time",
+];
+
+/// Check `ops(...)` entries against the field kind
+fn validate_ops(ops: &[&'static str], kind: Option<&str>, span: Span) {
+    for cap in ops {
+        let Some((name, _, kinds)) = OPS.iter().find(|(_, c, _)| c == cap) else {
+            continue;
+        };
+        if !kinds.is_empty() && !kind.is_some_and(|k| kinds.contains(&k)) {
+            emit_error!(
+                span,
+                "`{}` is only supported on fields with kind {}",
+                name,
+                kinds
+                    .iter()
+                    .map(|k| format!("\"{k}\""))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+    }
+    if ops.iter().any(|c| *c == "ANY" || *c == "ALL")
+        && ops.iter().any(|c| *c != "ANY" && *c != "ALL")
+    {
+        emit_error!(
+            span,
+            "`any`/`all` mark a collection field and cannot be combined with other ops"
+        );
+    }
+}
+
 /// Parse #[odata(filter(kind = "..."))] attributes on struct fields
 fn parse_field_attrs(field: &syn::Field) -> Option<FilterableField> {
     let field_ident = field.ident.as_ref()?.clone();
@@ -23,6 +89,8 @@ fn parse_field_attrs(field: &syn::Field) -> Option<FilterableField> {
     let span = field.span();
 
     let mut found_kind: Option<String> = None;
+    let mut searchable = false;
+    let mut ops = Vec::new();
 
     for attr in &field.attrs {
         // Look for #[odata(...)]
@@ -48,6 +116,25 @@ fn parse_field_attrs(field: &syn::Field) -> Option<FilterableField> {
                                 "kind value must be a string literal"
                             );
                         }
+                    } else if filter_meta.path.is_ident("searchable") {
+                        searchable = true;
+                    } else if filter_meta.path.is_ident("ops") {
+                        filter_meta.parse_nested_meta(|op_meta| {
+                            let entry = OPS.iter().find(|(name, _, _)| op_meta.path.is_ident(name));
+                            match entry {
+                                Some((_, cap, _)) if !ops.contains(cap) => ops.push(*cap),
+                                Some(_) => {}
+                                None => emit_error!(
+                                    op_meta.path.span(),
+                                    "unknown filter op; expected one of: {}",
+                                    OPS.iter()
+                                        .map(|(n, _, _)| *n)
+                                        .collect::<Vec<_>>()
+                                        .join(", ")
+                                ),
+                            }
+                            Ok(())
+                        })?;
                     }
                     Ok(())
                 })?;
@@ -60,10 +147,24 @@ fn parse_field_attrs(field: &syn::Field) -> Option<FilterableField> {
         }
     }
 
+    if searchable && found_kind.as_deref() != Some("String") {
+        emit_error!(
+            span,
+            "`searchable` is only supported on fields with kind = \"String\""
+        );
+    }
+
+    validate_ops(&ops, found_kind.as_deref(), span);
+    if searchable && ops.iter().any(|c| *c == "ANY" || *c == "ALL") {
+        emit_error!(span, "collection fields cannot be `searchable`");
+    }
+
     found_kind.map(|kind| FilterableField {
         field_ident,
         field_name,
         kind,
+        searchable,
+        ops,
         span,
     })
 }
@@ -143,6 +244,52 @@ pub fn expand_derive_odata_filterable(input: DeriveInput) -> TokenStream {
             }
         });
 
+    // Generate searchable() only when at least one field opts in (the trait default is `false`)
+    let searchable_variants: Vec<_> = filterable_fields
+        .iter()
+        .zip(&enum_variants)
+        .filter(|(f, _)| f.searchable)
+        .map(|(_, variant)| quote! { #filter_enum_name::#variant })
+        .collect();
+    let searchable_fn = if searchable_variants.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            fn searchable(&self) -> bool {
+                matches!(self, #(#searchable_variants)|*)
+            }
+        }
+    };
+
+    // Generate caps() only when at least one field enables ops (the trait default is `NONE`)
+    let caps_arms: Vec<_> = filterable_fields
+        .iter()
+        .zip(&enum_variants)
+        .filter(|(f, _)| !f.ops.is_empty())
+        .map(|(f, variant)| {
+            let caps = f.ops.iter().map(|cap| {
+                let cap = Ident::new(cap, f.span);
+                quote! { ::modkit_odata::filter::FilterCaps::#cap }
+            });
+            quote! {
+                #filter_enum_name::#variant => ::modkit_odata::filter::FilterCaps::NONE #(.union(#caps))*
+            }
+        })
+        .collect();
+    let caps_fn = if caps_arms.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            fn caps(&self) -> ::modkit_odata::filter::FilterCaps {
+                #[allow(unreachable_patterns)]
+                match self {
+                    #(#caps_arms,)*
+                    _ => ::modkit_odata::filter::FilterCaps::NONE,
+                }
+            }
+        }
+    };
+
     // Generate the full implementation
     quote! {
         #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
@@ -167,6 +314,10 @@ pub fn expand_derive_odata_filterable(input: DeriveInput) -> TokenStream {
                     #(#kind_match_arms),*
                 }
             }
+
+            #searchable_fn
+
+            #caps_fn
         }
     }
 }
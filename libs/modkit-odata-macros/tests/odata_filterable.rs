@@ -0,0 +1,46 @@
+use modkit_odata::filter::{FieldKind, FilterCaps, FilterField};
+use modkit_odata_macros::ODataFilterable;
+
+#[derive(ODataFilterable)]
+#[allow(dead_code)]
+struct ItemQuery {
+    #[odata(filter(kind = "String", searchable, ops(in, tolower, length)))]
+    name: String,
+    #[odata(filter(kind = "I64", ops(has)))]
+    flags: i64,
+    #[odata(filter(kind = "String", ops(any, all)))]
+    tags: Vec<String>,
+    #[odata(filter(kind = "Uuid"))]
+    id: uuid::Uuid,
+    #[odata(filter(kind = "String", ops(groupby, countdistinct)))]
+    status: String,
+    #[odata(filter(kind = "F64", ops(sum, average, min, max)))]
+    amount: f64,
+}
+
+#[test]
+fn generates_caps_from_ops() {
+    assert_eq!(
+        ItemQueryFilterField::Name.caps(),
+        FilterCaps::IN | FilterCaps::TOLOWER | FilterCaps::LENGTH
+    );
+    assert_eq!(ItemQueryFilterField::Flags.caps(), FilterCaps::HAS);
+    assert_eq!(ItemQueryFilterField::Id.caps(), FilterCaps::NONE);
+    assert_eq!(
+        ItemQueryFilterField::Status.caps(),
+        FilterCaps::GROUPBY | FilterCaps::COUNTDISTINCT
+    );
+    assert!(
+        ItemQueryFilterField::Amount
+            .caps()
+            .contains(FilterCaps::SUM | FilterCaps::AVERAGE | FilterCaps::MIN | FilterCaps::MAX)
+    );
+}
+
+#[test]
+fn any_all_mark_collection_fields() {
+    assert!(ItemQueryFilterField::Tags.is_collection());
+    assert!(!ItemQueryFilterField::Name.is_collection());
+    assert_eq!(ItemQueryFilterField::Tags.kind(), FieldKind::String);
+    assert!(ItemQueryFilterField::Name.searchable());
+}
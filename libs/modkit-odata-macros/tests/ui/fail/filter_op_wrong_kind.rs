@@ -0,0 +1,13 @@
+use modkit_odata_macros::ODataFilterable;
+
+#[derive(ODataFilterable)]
+struct ItemQuery {
+    #[odata(filter(kind = "I64", ops(tolower)))]
+    count: i64,
+    #[odata(filter(kind = "String", ops(between)))]
+    name: String,
+    #[odata(filter(kind = "String", ops(sum)))]
+    label: String,
+}
+
+fn main() {}
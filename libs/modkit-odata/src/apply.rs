@@ -0,0 +1,798 @@
+//! `$apply` data aggregation, a subset of the `OData` Data Aggregation Extension.
+//!
+//! Supported transformations, separated by `/`:
+//!
+//! - `filter(expr)`: restrict the input rows; only allowed before the grouping step
+//! - `groupby((f1, f2))` and `groupby((f1, f2), aggregate(...))`
+//! - `aggregate(...)`: aggregate all input rows into a single result
+//!
+//! A `groupby` property may also be `date(f)`, `year(f)`, `month(f)` or `day(f)` of a
+//! date or timestamp field, optionally named with `as alias`; without an alias the
+//! group is keyed by the field name. `date` truncates timestamps to the (UTC) day.
+//!
+//! Aggregate expressions are `field with <method> as alias`, where method is one of
+//! `sum`, `average`, `min`, `max` and `countdistinct`, or `$count as alias`.
+//!
+//! ```text
+//! $apply=filter(year(created_at) eq 2024)/groupby((status),aggregate(amount with sum as total,$count as n))
+//! $apply=groupby((date(created_at) as day),aggregate($count as n))
+//! ```
+//!
+//! Fields must opt into grouping and each aggregation method through
+//! [`FilterField::caps`](crate::filter::FilterField::caps).
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::Error;
+use crate::ast;
+use crate::filter::{FieldKind, FilterCaps, FilterField, FilterNode, convert_expr_to_filter_node};
+
+/// Maximum number of `groupby` properties
+pub const MAX_GROUP_FIELDS: usize = 8;
+/// Maximum number of aggregate expressions
+pub const MAX_AGGREGATES: usize = 16;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AggregateMethod {
+    Sum,
+    Average,
+    Min,
+    Max,
+    CountDistinct,
+    /// `$count as alias`: number of rows in the group
+    Count,
+}
+
+impl AggregateMethod {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "sum" => Some(Self::Sum),
+            "average" => Some(Self::Average),
+            "min" => Some(Self::Min),
+            "max" => Some(Self::Max),
+            "countdistinct" => Some(Self::CountDistinct),
+            _ => None,
+        }
+    }
+
+    /// Capability a field must enable to be aggregated with this method.
+    #[must_use]
+    pub fn cap(self) -> FilterCaps {
+        match self {
+            Self::Sum => FilterCaps::SUM,
+            Self::Average => FilterCaps::AVERAGE,
+            Self::Min => FilterCaps::MIN,
+            Self::Max => FilterCaps::MAX,
+            Self::CountDistinct => FilterCaps::COUNTDISTINCT,
+            Self::Count => FilterCaps::NONE,
+        }
+    }
+
+    /// Kind of the aggregated value for an input field of `kind`.
+    #[must_use]
+    pub fn result_kind(self, kind: Option<FieldKind>) -> FieldKind {
+        match (self, kind) {
+            (Self::Count | Self::CountDistinct, _) => FieldKind::I64,
+            (Self::Average, _) => FieldKind::F64,
+            (_, Some(kind)) => kind,
+            (_, None) => FieldKind::I64,
+        }
+    }
+}
+
+impl fmt::Display for AggregateMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sum => write!(f, "sum"),
+            Self::Average => write!(f, "average"),
+            Self::Min => write!(f, "min"),
+            Self::Max => write!(f, "max"),
+            Self::CountDistinct => write!(f, "countdistinct"),
+            Self::Count => write!(f, "$count"),
+        }
+    }
+}
+
+/// Date function applied to a `groupby` property
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GroupFunction {
+    /// Calendar day of a date or timestamp
+    Date,
+    Year,
+    Month,
+    Day,
+}
+
+impl GroupFunction {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "date" => Some(Self::Date),
+            "year" => Some(Self::Year),
+            "month" => Some(Self::Month),
+            "day" => Some(Self::Day),
+            _ => None,
+        }
+    }
+
+    /// Kind of the grouped value.
+    #[must_use]
+    pub fn result_kind(self) -> FieldKind {
+        match self {
+            Self::Date => FieldKind::Date,
+            Self::Year | Self::Month | Self::Day => FieldKind::I64,
+        }
+    }
+}
+
+impl fmt::Display for GroupFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Date => write!(f, "date"),
+            Self::Year => write!(f, "year"),
+            Self::Month => write!(f, "month"),
+            Self::Day => write!(f, "day"),
+        }
+    }
+}
+
+/// `groupby` property: `field`, or `function(field) [as alias]`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GroupProperty {
+    pub field: String,
+    pub function: Option<GroupFunction>,
+    /// Key of the group value in result rows; the field name unless aliased
+    pub alias: String,
+}
+
+impl GroupProperty {
+    /// Plain grouping by `field`
+    #[must_use]
+    pub fn field(field: impl Into<String>) -> Self {
+        let field = field.into();
+        Self {
+            alias: field.clone(),
+            field,
+            function: None,
+        }
+    }
+}
+
+/// `field with method as alias`, or `$count as alias` (no field)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Aggregate {
+    pub method: AggregateMethod,
+    pub field: Option<String>,
+    pub alias: String,
+}
+
+/// Parsed `$apply` pipeline
+#[derive(Clone, Debug, Default)]
+pub struct Apply {
+    /// `filter(...)` step applied to the input rows
+    pub filter: Option<ast::Expr>,
+    /// `groupby` properties; empty for a plain `aggregate(...)`
+    pub group_by: Vec<GroupProperty>,
+    pub aggregates: Vec<Aggregate>,
+}
+
+/// [`Aggregate`] with its field resolved against a [`FilterField`] enum
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypedAggregate<F: FilterField> {
+    pub method: AggregateMethod,
+    pub field: Option<F>,
+    pub alias: String,
+}
+
+impl<F: FilterField> TypedAggregate<F> {
+    /// Kind of the aggregated value.
+    #[must_use]
+    pub fn result_kind(&self) -> FieldKind {
+        self.method.result_kind(self.field.map(|f| f.kind()))
+    }
+}
+
+/// [`GroupProperty`] with its field resolved against a [`FilterField`] enum
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypedGroup<F: FilterField> {
+    pub field: F,
+    pub function: Option<GroupFunction>,
+    pub alias: String,
+}
+
+impl<F: FilterField> TypedGroup<F> {
+    /// Kind of the grouped value.
+    #[must_use]
+    pub fn result_kind(&self) -> FieldKind {
+        self.function
+            .map_or_else(|| self.field.kind(), GroupFunction::result_kind)
+    }
+}
+
+/// [`Apply`] validated against a [`FilterField`] enum
+#[derive(Clone, Debug)]
+pub struct TypedApply<F: FilterField> {
+    pub filter: Option<FilterNode<F>>,
+    pub group_by: Vec<TypedGroup<F>>,
+    pub aggregates: Vec<TypedAggregate<F>>,
+}
+
+/// One result row of an aggregation: group values keyed by field name and
+/// aggregate values keyed by alias.
+#[cfg_attr(feature = "with-utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct AggregateRow {
+    #[cfg_attr(feature = "with-utoipa", schema(value_type = Object))]
+    pub values: serde_json::Map<String, serde_json::Value>,
+}
+
+impl AggregateRow {
+    /// Value of a group field or aggregate alias.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&serde_json::Value> {
+        self.values.get(name)
+    }
+
+    pub fn insert(&mut self, name: impl Into<String>, value: serde_json::Value) {
+        self.values.insert(name.into(), value);
+    }
+}
+
+fn invalid(msg: impl Into<String>) -> Error {
+    Error::InvalidApply(msg.into())
+}
+
+/// Split `src` on `sep` outside parentheses and string literals.
+fn split_top_level(src: &str, sep: char) -> Result<Vec<&str>, Error> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut start = 0;
+    let mut chars = src.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\'' if in_string => {
+                // '' is an escaped quote inside a literal
+                if chars.peek().is_some_and(|&(_, n)| n == '\'') {
+                    chars.next();
+                } else {
+                    in_string = false;
+                }
+            }
+            '\'' => in_string = true,
+            _ if in_string => {}
+            '(' => depth += 1,
+            ')' => {
+                depth = depth
+                    .checked_sub(1)
+                    .ok_or_else(|| invalid("unbalanced parentheses"))?;
+            }
+            _ if c == sep && depth == 0 => {
+                parts.push(&src[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    if in_string {
+        return Err(invalid("unterminated string literal"));
+    }
+    if depth != 0 {
+        return Err(invalid("unbalanced parentheses"));
+    }
+    parts.push(&src[start..]);
+    Ok(parts)
+}
+
+/// Split `name(args)` into `name` and `args`.
+fn split_call(step: &str) -> Result<(&str, &str), Error> {
+    let step = step.trim();
+    let open = step
+        .find('(')
+        .ok_or_else(|| invalid(format!("expected a transformation, got '{step}'")))?;
+    if !step.ends_with(')') {
+        return Err(invalid(format!("expected ')' at the end of '{step}'")));
+    }
+    Ok((step[..open].trim(), &step[open + 1..step.len() - 1]))
+}
+
+fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn parse_aggregate(raw: &str) -> Result<Aggregate, Error> {
+    let tokens: Vec<&str> = raw.split_whitespace().collect();
+    let (method, field, alias) = match tokens.as_slice() {
+        [count, as_kw, alias] if *count == "$count" && as_kw.eq_ignore_ascii_case("as") => {
+            (AggregateMethod::Count, None, *alias)
+        }
+        [field, with_kw, method, as_kw, alias]
+            if with_kw.eq_ignore_ascii_case("with") && as_kw.eq_ignore_ascii_case("as") =>
+        {
+            let method = AggregateMethod::from_name(method)
+                .ok_or_else(|| invalid(format!("unsupported aggregation method '{method}'")))?;
+            if !is_identifier(field) {
+                return Err(invalid(format!("invalid property '{field}'")));
+            }
+            (method, Some((*field).to_owned()), *alias)
+        }
+        _ => {
+            return Err(invalid(format!(
+                "expected 'property with method as alias' or '$count as alias', got '{}'",
+                raw.trim()
+            )));
+        }
+    };
+    if !is_identifier(alias) {
+        return Err(invalid(format!("invalid alias '{alias}'")));
+    }
+    Ok(Aggregate {
+        method,
+        field,
+        alias: alias.to_owned(),
+    })
+}
+
+fn parse_aggregates(raw: &str) -> Result<Vec<Aggregate>, Error> {
+    let aggregates = split_top_level(raw, ',')?
+        .into_iter()
+        .map(parse_aggregate)
+        .collect::<Result<Vec<_>, _>>()?;
+    if aggregates.len() > MAX_AGGREGATES {
+        return Err(invalid("too many aggregate expressions"));
+    }
+    Ok(aggregates)
+}
+
+/// `field`, `function(field)` or `function(field) as alias`
+fn parse_group_property(raw: &str) -> Result<GroupProperty, Error> {
+    let raw = raw.trim();
+    let invalid_prop = || invalid(format!("invalid groupby property '{raw}'"));
+
+    let tokens: Vec<&str> = raw.split_whitespace().collect();
+    let (expr, alias) = match tokens.as_slice() {
+        [expr] => (*expr, None),
+        [expr, as_kw, alias] if as_kw.eq_ignore_ascii_case("as") => (*expr, Some(*alias)),
+        _ => return Err(invalid_prop()),
+    };
+
+    let property = if expr.contains('(') {
+        let (name, field) = split_call(expr)?;
+        let function = GroupFunction::from_name(name).ok_or_else(|| {
+            invalid(format!(
+                "unsupported groupby function '{name}'; expected date, year, month or day"
+            ))
+        })?;
+        let field = field.trim();
+        if !is_identifier(field) {
+            return Err(invalid_prop());
+        }
+        GroupProperty {
+            field: field.to_owned(),
+            function: Some(function),
+            alias: alias.unwrap_or(field).to_owned(),
+        }
+    } else if alias.is_some() {
+        return Err(invalid(format!(
+            "only function properties can be aliased in groupby, got '{raw}'"
+        )));
+    } else if is_identifier(expr) {
+        GroupProperty::field(expr)
+    } else {
+        return Err(invalid_prop());
+    };
+
+    if !is_identifier(&property.alias) {
+        return Err(invalid(format!("invalid alias '{}'", property.alias)));
+    }
+    Ok(property)
+}
+
+/// `groupby((a, b))` or `groupby((a, b), aggregate(...))`
+fn parse_groupby(args: &str) -> Result<(Vec<GroupProperty>, Vec<Aggregate>), Error> {
+    let parts = split_top_level(args, ',')?;
+    let props = parts[0].trim();
+    if !props.starts_with('(') || !props.ends_with(')') {
+        return Err(invalid(
+            "groupby properties must be enclosed in parentheses",
+        ));
+    }
+
+    let mut group_by: Vec<GroupProperty> = Vec::new();
+    for prop in split_top_level(&props[1..props.len() - 1], ',')? {
+        let prop = parse_group_property(prop)?;
+        if group_by
+            .iter()
+            .any(|g| g.alias.eq_ignore_ascii_case(&prop.alias))
+        {
+            return Err(invalid(format!(
+                "duplicate groupby property '{}'",
+                prop.alias
+            )));
+        }
+        group_by.push(prop);
+    }
+    if group_by.len() > MAX_GROUP_FIELDS {
+        return Err(invalid("too many groupby properties"));
+    }
+
+    let rest = &parts[1..];
+    let aggregates = if rest.is_empty() {
+        Vec::new()
+    } else {
+        // The aggregate(...) call may itself contain commas; re-join the tail
+        let tail = rest.join(",");
+        let (name, inner) = split_call(&tail)?;
+        if !name.eq_ignore_ascii_case("aggregate") {
+            return Err(invalid(format!(
+                "only aggregate(...) is supported inside groupby, got '{name}'"
+            )));
+        }
+        parse_aggregates(inner)?
+    };
+    Ok((group_by, aggregates))
+}
+
+/// Parse a raw `$apply` string.
+///
+/// # Errors
+/// Returns `Error::InvalidApply` if the pipeline is malformed or uses unsupported
+/// transformations, or the parse error of an embedded `filter(...)` expression.
+pub fn parse_apply(raw: &str) -> Result<Apply, Error> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return Err(invalid("$apply cannot be empty"));
+    }
+
+    let mut apply = Apply::default();
+    let mut grouped = false;
+
+    for step in split_top_level(raw, '/')? {
+        if grouped {
+            return Err(invalid("groupby/aggregate must be the last transformation"));
+        }
+        let (name, args) = split_call(step)?;
+        match name.to_ascii_lowercase().as_str() {
+            "filter" => {
+                if apply.filter.is_some() {
+                    return Err(invalid("only one filter(...) transformation is supported"));
+                }
+                let parsed = crate::parse_filter_string(args).map_err(|e| match e {
+                    Error::InvalidFilter(msg) => invalid(format!("filter: {msg}")),
+                    other => other,
+                })?;
+                apply.filter = Some(parsed.into_expr());
+            }
+            "groupby" => {
+                (apply.group_by, apply.aggregates) = parse_groupby(args)?;
+                grouped = true;
+            }
+            "aggregate" => {
+                apply.aggregates = parse_aggregates(args)?;
+                grouped = true;
+            }
+            other => {
+                return Err(invalid(format!("unsupported transformation '{other}'")));
+            }
+        }
+    }
+
+    if !grouped {
+        return Err(invalid(
+            "$apply must end with groupby(...) or aggregate(...)",
+        ));
+    }
+    Ok(apply)
+}
+
+impl Apply {
+    /// Resolve fields against `F` and check that they enable the requested
+    /// grouping and aggregation methods.
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidApply` for unknown fields, operations a field does not
+    /// enable, numeric aggregations over non-numeric fields, or conflicting aliases.
+    pub fn typed<F: FilterField>(&self) -> Result<TypedApply<F>, Error> {
+        let filter = self
+            .filter
+            .as_ref()
+            .map(|expr| {
+                convert_expr_to_filter_node::<F>(expr).map_err(|e| invalid(format!("filter: {e}")))
+            })
+            .transpose()?;
+
+        let group_by = self
+            .group_by
+            .iter()
+            .map(typed_group::<F>)
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let mut aggregates: Vec<TypedAggregate<F>> = Vec::with_capacity(self.aggregates.len());
+        for agg in &self.aggregates {
+            let field = agg.field.as_deref().map(resolve::<F>).transpose()?;
+            if let Some(field) = field {
+                if !field.caps().contains(agg.method.cap()) {
+                    return Err(invalid(format!(
+                        "'{}' is not supported on '{}'",
+                        agg.method,
+                        field.name()
+                    )));
+                }
+                let numeric = matches!(
+                    field.kind(),
+                    FieldKind::I64 | FieldKind::F64 | FieldKind::Decimal
+                );
+                if matches!(agg.method, AggregateMethod::Sum | AggregateMethod::Average) && !numeric
+                {
+                    return Err(invalid(format!(
+                        "'{}' requires a numeric property, '{}' is {}",
+                        agg.method,
+                        field.name(),
+                        field.kind()
+                    )));
+                }
+            }
+
+            let clashes = aggregates
+                .iter()
+                .any(|a| a.alias.eq_ignore_ascii_case(&agg.alias))
+                || group_by
+                    .iter()
+                    .any(|g| g.alias.eq_ignore_ascii_case(&agg.alias));
+            if clashes {
+                return Err(invalid(format!("duplicate alias '{}'", agg.alias)));
+            }
+
+            aggregates.push(TypedAggregate {
+                method: agg.method,
+                field,
+                alias: agg.alias.clone(),
+            });
+        }
+
+        Ok(TypedApply {
+            filter,
+            group_by,
+            aggregates,
+        })
+    }
+}
+
+fn typed_group<F: FilterField>(group: &GroupProperty) -> Result<TypedGroup<F>, Error> {
+    let field = resolve::<F>(&group.field)?;
+    if !field.caps().contains(FilterCaps::GROUPBY) {
+        return Err(invalid(format!(
+            "grouping by '{}' is not supported",
+            group.field
+        )));
+    }
+    let dated = matches!(field.kind(), FieldKind::DateTimeUtc | FieldKind::Date);
+    if let Some(function) = group.function.filter(|_| !dated) {
+        return Err(invalid(format!(
+            "'{function}' requires a date or timestamp property, '{}' is {}",
+            field.name(),
+            field.kind()
+        )));
+    }
+    Ok(TypedGroup {
+        field,
+        function: group.function,
+        alias: group.alias.clone(),
+    })
+}
+
+fn resolve<F: FilterField>(name: &str) -> Result<F, Error> {
+    F::from_name(name)
+        .filter(|f| !f.is_collection())
+        .ok_or_else(|| invalid(format!("unknown property '{name}'")))
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+    enum Field {
+        Status,
+        Amount,
+        Note,
+        CreatedAt,
+    }
+
+    impl FilterField for Field {
+        const FIELDS: &'static [Self] =
+            &[Field::Status, Field::Amount, Field::Note, Field::CreatedAt];
+
+        fn name(&self) -> &'static str {
+            match self {
+                Field::Status => "status",
+                Field::Amount => "amount",
+                Field::Note => "note",
+                Field::CreatedAt => "created_at",
+            }
+        }
+
+        fn kind(&self) -> FieldKind {
+            match self {
+                Field::Status | Field::Note => FieldKind::String,
+                Field::Amount => FieldKind::I64,
+                Field::CreatedAt => FieldKind::DateTimeUtc,
+            }
+        }
+
+        fn caps(&self) -> FilterCaps {
+            match self {
+                Field::Status | Field::CreatedAt => FilterCaps::GROUPBY | FilterCaps::COUNTDISTINCT,
+                Field::Amount => FilterCaps::SUM | FilterCaps::AVERAGE | FilterCaps::MAX,
+                // Misconfigured on purpose: sum over a string
+                Field::Note => FilterCaps::SUM,
+            }
+        }
+    }
+
+    #[test]
+    fn parses_groupby_with_aggregates() {
+        let apply =
+            parse_apply("groupby((status), aggregate(amount with sum as total, $count as n))")
+                .unwrap();
+        assert_eq!(apply.group_by, vec![GroupProperty::field("status")]);
+        assert_eq!(
+            apply.aggregates,
+            vec![
+                Aggregate {
+                    method: AggregateMethod::Sum,
+                    field: Some("amount".to_owned()),
+                    alias: "total".to_owned(),
+                },
+                Aggregate {
+                    method: AggregateMethod::Count,
+                    field: None,
+                    alias: "n".to_owned(),
+                },
+            ]
+        );
+
+        let apply = parse_apply("aggregate(amount with max as top)").unwrap();
+        assert!(apply.group_by.is_empty());
+        assert_eq!(apply.aggregates.len(), 1);
+
+        let apply = parse_apply("groupby((status,note))").unwrap();
+        assert_eq!(
+            apply.group_by,
+            vec![GroupProperty::field("status"), GroupProperty::field("note")]
+        );
+        assert!(apply.aggregates.is_empty());
+    }
+
+    #[test]
+    fn parses_date_functions_in_groupby() {
+        let apply =
+            parse_apply("groupby((date(created_at) as day, status), aggregate($count as n))")
+                .unwrap();
+        assert_eq!(
+            apply.group_by,
+            vec![
+                GroupProperty {
+                    field: "created_at".to_owned(),
+                    function: Some(GroupFunction::Date),
+                    alias: "day".to_owned(),
+                },
+                GroupProperty::field("status"),
+            ]
+        );
+
+        // Without an alias the group is keyed by the field
+        let apply = parse_apply("groupby((year(created_at), month(created_at) as m))").unwrap();
+        assert_eq!(apply.group_by[0].function, Some(GroupFunction::Year));
+        assert_eq!(apply.group_by[0].alias, "created_at");
+        assert_eq!(apply.group_by[1].alias, "m");
+
+        let typed = apply.typed::<Field>().unwrap();
+        assert_eq!(typed.group_by[0].field, Field::CreatedAt);
+        assert_eq!(typed.group_by[0].result_kind(), FieldKind::I64);
+        let typed = parse_apply("groupby((date(created_at)))")
+            .unwrap()
+            .typed::<Field>()
+            .unwrap();
+        assert_eq!(typed.group_by[0].result_kind(), FieldKind::Date);
+
+        for raw in [
+            "groupby((hour(created_at)))",
+            "groupby((status as s))",
+            "groupby((date(created_at) as 1d))",
+            "groupby((date(created_at) day))",
+            "groupby((date(created_at), day(created_at)))",
+        ] {
+            assert!(
+                matches!(parse_apply(raw), Err(Error::InvalidApply(_))),
+                "{raw:?} should be rejected"
+            );
+        }
+        // Date functions need a date or timestamp property
+        let res = parse_apply("groupby((date(status)))")
+            .unwrap()
+            .typed::<Field>();
+        assert!(matches!(res, Err(Error::InvalidApply(_))));
+    }
+
+    #[cfg(feature = "with-odata-params")]
+    #[test]
+    fn parses_filter_step() {
+        let apply =
+            parse_apply("filter(amount gt 10 and note eq 'a/b')/aggregate($count as n)").unwrap();
+        assert!(apply.filter.is_some());
+        assert_eq!(apply.aggregates[0].method, AggregateMethod::Count);
+    }
+
+    #[test]
+    fn rejects_malformed_pipelines() {
+        for raw in [
+            "",
+            "groupby(status)",
+            "groupby((status))/filter(amount gt 1)",
+            "groupby((status), topcount(2, amount))",
+            "aggregate(amount with median as m)",
+            "aggregate(amount as total)",
+            "aggregate(amount with sum as 1x)",
+            "compute(amount mul 2 as double)",
+            "groupby((status, status))",
+            "groupby((status)",
+        ] {
+            assert!(
+                matches!(parse_apply(raw), Err(Error::InvalidApply(_))),
+                "{raw:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn validates_fields_against_caps() {
+        let typed = parse_apply("groupby((status), aggregate(amount with sum as total))")
+            .unwrap()
+            .typed::<Field>()
+            .unwrap();
+        assert_eq!(typed.group_by[0].field, Field::Status);
+        assert_eq!(typed.group_by[0].alias, "status");
+        assert_eq!(typed.aggregates[0].field, Some(Field::Amount));
+        assert_eq!(typed.aggregates[0].result_kind(), FieldKind::I64);
+
+        for raw in [
+            // amount is not groupable
+            "groupby((amount))",
+            // min is not enabled on amount
+            "aggregate(amount with min as m)",
+            // sum over a string
+            "aggregate(note with sum as s)",
+            // unknown field
+            "groupby((missing))",
+            // alias collides with a group property
+            "groupby((status), aggregate($count as status))",
+            "aggregate($count as n, amount with max as N)",
+        ] {
+            let res = parse_apply(raw).unwrap().typed::<Field>();
+            assert!(
+                matches!(res, Err(Error::InvalidApply(_))),
+                "{raw:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn aggregate_row_serializes_flat() {
+        let mut row = AggregateRow::default();
+        row.insert("status", serde_json::json!("open"));
+        row.insert("n", serde_json::json!(3));
+        assert_eq!(
+            serde_json::to_value(&row).unwrap(),
+            serde_json::json!({"status": "open", "n": 3})
+        );
+    }
+}
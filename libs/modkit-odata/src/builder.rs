@@ -50,9 +50,7 @@
 //! ```
 
 use crate::schema::{AsFieldKey, AsFieldName, FieldRef, Schema};
-use crate::{
-    ODataOrderBy, ODataQuery, OrderKey, SortDir, ast::Expr, pagination::short_filter_hash,
-};
+use crate::{ODataOrderBy, ODataQuery, OrderKey, SortDir, ast::Expr, pagination::short_query_hash};
 use std::marker::PhantomData;
 
 /// Typed query builder for `OData` queries.
@@ -75,6 +73,9 @@ pub struct QueryBuilder<S: Schema> {
     order: Vec<OrderKey>,
     select: Option<Vec<S::Field>>,
     limit: Option<u64>,
+    count: bool,
+    search: Option<String>,
+    expand: Option<Vec<String>>,
     _phantom: PhantomData<S>,
 }
 
@@ -87,6 +88,9 @@ impl<S: Schema> QueryBuilder<S> {
             order: Vec::new(),
             select: None,
             limit: None,
+            count: false,
+            search: None,
+            expand: None,
             _phantom: PhantomData,
         }
     }
@@ -167,12 +171,49 @@ impl<S: Schema> QueryBuilder<S> {
         self
     }
 
+    /// Request the total number of matching items (`$count=true`).
+    #[must_use]
+    pub fn count(mut self, count: bool) -> Self {
+        self.count = count;
+        self
+    }
+
+    /// Set the free-text search expression (`$search`).
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// builder.search("alice \"new york\"")
+    /// ```
+    #[must_use]
+    pub fn search(mut self, search: impl Into<String>) -> Self {
+        self.search = Some(search.into());
+        self
+    }
+
+    /// Set the navigation properties to expand (`$expand`).
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// builder.expand(["addresses"])
+    /// ```
+    #[must_use]
+    pub fn expand<I>(mut self, navs: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.expand = Some(navs.into_iter().map(Into::into).collect());
+        self
+    }
+
     /// Build the final `ODataQuery` with computed filter hash.
     ///
     /// The filter hash is computed using the stable hashing algorithm from
-    /// `pagination::short_filter_hash`.
+    /// `pagination::short_query_hash`, covering both the filter and the search.
     pub fn build(self) -> ODataQuery {
-        let filter_hash = short_filter_hash(self.filter.as_ref());
+        let filter_hash = short_query_hash(self.filter.as_ref(), self.search.as_deref());
 
         let mut query = ODataQuery::new();
 
@@ -200,7 +241,15 @@ impl<S: Schema> QueryBuilder<S> {
             query = query.with_select(names);
         }
 
-        query
+        if let Some(search) = self.search {
+            query = query.with_search(search);
+        }
+
+        if let Some(navs) = self.expand {
+            query = query.with_expand(navs);
+        }
+
+        query.with_count(self.count)
     }
 }
 
@@ -475,6 +524,27 @@ mod tests {
         assert_eq!(query.limit, Some(25));
     }
 
+    #[test]
+    fn test_count_search_expand() {
+        let query = QueryBuilder::<UserSchema>::new()
+            .filter(AGE.gt(18))
+            .search("alice")
+            .expand(["addresses"])
+            .count(true)
+            .build();
+
+        assert!(query.count);
+        assert_eq!(query.search(), Some("alice"));
+        assert!(query.expands("addresses"));
+        assert_ne!(
+            query.filter_hash,
+            QueryBuilder::<UserSchema>::new()
+                .filter(AGE.gt(18))
+                .build()
+                .filter_hash
+        );
+    }
+
     #[test]
     fn test_filter_hash_stability() {
         let user_id = uuid::Uuid::new_v4();
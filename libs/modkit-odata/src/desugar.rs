@@ -0,0 +1,333 @@
+//! Rewrites `OData` filter syntax the underlying parser does not understand
+//! into plain function calls it does:
+//!
+//! - `flags has 4` → `has(flags, 4)`
+//! - `tags/any(t: t eq 'x')` → `any(tags, __it eq 'x')`
+//! - `tags/any()` → `any(tags)`
+//! - `tags/all(t: t ne 'x')` → `all(tags, __it ne 'x')`
+//!
+//! The lambda range variable is renamed to [`crate::ast::LAMBDA_VARIABLE`], so
+//! the parsed AST does not depend on the name the client picked. String literals
+//! are copied verbatim. Only one level of lambdas over primitive elements is
+//! supported; nested lambdas and member access on the range variable are rejected.
+
+use std::fmt::Write as _;
+
+use crate::ast::LAMBDA_VARIABLE;
+
+/// Desugar `has` and `any`/`all` lambdas in a raw `$filter` string.
+///
+/// # Errors
+/// Returns a description of the problem if a lambda is malformed.
+pub fn desugar_filter(raw: &str) -> Result<String, String> {
+    // Fast path: nothing to rewrite
+    if !raw.contains('/') && !raw.contains("has") {
+        return Ok(raw.to_owned());
+    }
+    desugar(raw, true)
+}
+
+fn is_ident_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_'
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// Byte index just past the string literal starting at `start` (which must be `'`).
+///
+/// `OData` escapes a quote inside a literal by doubling it; backslashes are
+/// ordinary characters.
+fn skip_string(src: &str, start: usize) -> Result<usize, String> {
+    let mut i = start + 1;
+    while let Some(offset) = src[i..].find('\'') {
+        let quote = i + offset;
+        if !src[quote + 1..].starts_with('\'') {
+            return Ok(quote + 1);
+        }
+        i = quote + 2;
+    }
+    Err("unterminated string literal".to_owned())
+}
+
+fn skip_ws(src: &str, mut i: usize) -> usize {
+    while let Some(c) = src[i..].chars().next() {
+        if !c.is_whitespace() {
+            break;
+        }
+        i += c.len_utf8();
+    }
+    i
+}
+
+fn read_ident(src: &str, start: usize) -> usize {
+    src[start..]
+        .char_indices()
+        .find(|&(_, c)| !is_ident_char(c))
+        .map_or(src.len(), |(i, _)| start + i)
+}
+
+/// Byte index of the `)` matching the `(` at `open`.
+fn find_closing_paren(src: &str, open: usize) -> Result<usize, String> {
+    let mut depth = 0usize;
+    let mut i = open;
+    while i < src.len() {
+        let c = src[i..].chars().next().unwrap_or(' ');
+        match c {
+            '\'' => {
+                i = skip_string(src, i)?;
+                continue;
+            }
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(i);
+                }
+            }
+            _ => {}
+        }
+        i += c.len_utf8();
+    }
+    Err("unbalanced parentheses".to_owned())
+}
+
+/// Byte index of the first `:` outside string literals.
+fn find_colon(src: &str) -> Result<Option<usize>, String> {
+    let mut i = 0;
+    while i < src.len() {
+        let c = src[i..].chars().next().unwrap_or(' ');
+        match c {
+            '\'' => {
+                i = skip_string(src, i)?;
+                continue;
+            }
+            ':' => return Ok(Some(i)),
+            _ => {}
+        }
+        i += c.len_utf8();
+    }
+    Ok(None)
+}
+
+/// Operand of `has`: a string literal or a run of non-delimiter characters.
+fn read_operand(src: &str, start: usize) -> Result<usize, String> {
+    if src[start..].starts_with('\'') {
+        return skip_string(src, start);
+    }
+    let end = src[start..]
+        .char_indices()
+        .find(|&(_, c)| c.is_whitespace() || c == ')' || c == ',')
+        .map_or(src.len(), |(i, _)| start + i);
+    if end == start {
+        return Err("missing operand after 'has'".to_owned());
+    }
+    Ok(end)
+}
+
+fn desugar(src: &str, allow_lambda: bool) -> Result<String, String> {
+    let mut out = String::with_capacity(src.len() + 16);
+    let mut i = 0;
+    let mut prev: Option<char> = None;
+
+    while i < src.len() {
+        let c = src[i..].chars().next().unwrap_or(' ');
+
+        if c == '\'' {
+            let end = skip_string(src, i)?;
+            out.push_str(&src[i..end]);
+            prev = Some('\'');
+            i = end;
+            continue;
+        }
+
+        if !is_ident_start(c) || prev.is_some_and(is_ident_char) {
+            out.push(c);
+            prev = Some(c);
+            i += c.len_utf8();
+            continue;
+        }
+
+        let word_end = read_ident(src, i);
+        let word = &src[i..word_end];
+
+        if src[word_end..].starts_with('/') {
+            if !allow_lambda {
+                let segment = &src[word_end + 1..read_ident(src, word_end + 1)];
+                if segment.eq_ignore_ascii_case("any") || segment.eq_ignore_ascii_case("all") {
+                    return Err("nested lambda expressions are not supported".to_owned());
+                }
+                // Member access; reported by `rename_variable` or the parser
+                out.push_str(word);
+                prev = word.chars().last();
+                i = word_end;
+                continue;
+            }
+            let (lambda, end) = desugar_lambda(src, word, word_end + 1)?;
+            out.push_str(&lambda);
+            prev = Some(')');
+            i = end;
+            continue;
+        }
+
+        let after_ws = skip_ws(src, word_end);
+        if after_ws > word_end && src[after_ws..].starts_with("has") {
+            let has_end = after_ws + "has".len();
+            let operand_start = skip_ws(src, has_end);
+            if operand_start > has_end {
+                let operand_end = read_operand(src, operand_start)?;
+                let _ = write!(out, "has({word}, {})", &src[operand_start..operand_end]);
+                prev = Some(')');
+                i = operand_end;
+                continue;
+            }
+        }
+
+        out.push_str(word);
+        prev = word.chars().last();
+        i = word_end;
+    }
+
+    Ok(out)
+}
+
+/// Rewrite `field/any(v: body)` starting right after the `/`.
+///
+/// Returns the rewritten call and the byte index just past the closing `)`.
+fn desugar_lambda(src: &str, field: &str, start: usize) -> Result<(String, usize), String> {
+    let op_end = read_ident(src, start);
+    let op = src[start..op_end].to_ascii_lowercase();
+    if op != "any" && op != "all" {
+        return Err(format!(
+            "unsupported path segment '{}' after '{field}'",
+            &src[start..op_end]
+        ));
+    }
+
+    let open = skip_ws(src, op_end);
+    if !src[open..].starts_with('(') {
+        return Err(format!("expected '(' after '{field}/{op}'"));
+    }
+    let close = find_closing_paren(src, open)?;
+    let inner = src[open + 1..close].trim();
+
+    if inner.is_empty() {
+        return Ok((format!("{op}({field})"), close + 1));
+    }
+
+    let colon = find_colon(inner)?.ok_or_else(|| {
+        format!("lambda '{field}/{op}' must declare a range variable, e.g. '{field}/{op}(x: ...)'")
+    })?;
+    let var = inner[..colon].trim();
+    if var.is_empty() || !var.starts_with(is_ident_start) || !var.chars().all(is_ident_char) {
+        return Err(format!("invalid lambda variable '{var}'"));
+    }
+
+    let body = desugar(&inner[colon + 1..], false)?;
+    let body = rename_variable(&body, var)?;
+    Ok((format!("{op}({field}, {})", body.trim()), close + 1))
+}
+
+/// Replace whole-word occurrences of `var` outside string literals with
+/// [`LAMBDA_VARIABLE`].
+fn rename_variable(body: &str, var: &str) -> Result<String, String> {
+    let mut out = String::with_capacity(body.len() + 8);
+    let mut i = 0;
+    let mut prev: Option<char> = None;
+
+    while i < body.len() {
+        let c = body[i..].chars().next().unwrap_or(' ');
+        if c == '\'' {
+            let end = skip_string(body, i)?;
+            out.push_str(&body[i..end]);
+            prev = Some('\'');
+            i = end;
+        } else if is_ident_start(c) && !prev.is_some_and(is_ident_char) {
+            let end = read_ident(body, i);
+            let word = &body[i..end];
+            if word == var {
+                if body[end..].starts_with('/') {
+                    return Err(format!(
+                        "member access on lambda variable '{var}' is not supported"
+                    ));
+                }
+                out.push_str(LAMBDA_VARIABLE);
+            } else {
+                out.push_str(word);
+            }
+            prev = word.chars().last();
+            i = end;
+        } else {
+            out.push(c);
+            prev = Some(c);
+            i += c.len_utf8();
+        }
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leaves_plain_filters_untouched() {
+        let raw = "name eq 'a/b has c' and id gt 3";
+        assert_eq!(desugar_filter(raw).unwrap(), raw);
+    }
+
+    #[test]
+    fn rewrites_has() {
+        assert_eq!(
+            desugar_filter("flags has 4 and name eq 'x'").unwrap(),
+            "has(flags, 4) and name eq 'x'"
+        );
+        assert_eq!(desugar_filter("(flags has 4)").unwrap(), "(has(flags, 4))");
+        // `has` inside strings and as part of identifiers is not an operator
+        assert_eq!(
+            desugar_filter("hash eq 'a has b'").unwrap(),
+            "hash eq 'a has b'"
+        );
+    }
+
+    #[test]
+    fn rewrites_lambdas() {
+        assert_eq!(
+            desugar_filter("tags/any(t: t eq 'x')").unwrap(),
+            "any(tags, __it eq 'x')"
+        );
+        assert_eq!(
+            desugar_filter("tags/all(tag: startswith(tag, 't:') or tag eq 'tag')").unwrap(),
+            "all(tags, startswith(__it, 't:') or __it eq 'tag')"
+        );
+        assert_eq!(
+            desugar_filter("tags/any() and id eq 1").unwrap(),
+            "any(tags) and id eq 1"
+        );
+    }
+
+    #[test]
+    fn string_literals_escape_quotes_by_doubling() {
+        assert_eq!(
+            desugar_filter("contains(path,'C:\\') or flags has 1").unwrap(),
+            "contains(path,'C:\\') or has(flags, 1)"
+        );
+        assert_eq!(
+            desugar_filter("tags/any(t: t eq 'it''s (a) has b')").unwrap(),
+            "any(tags, __it eq 'it''s (a) has b')"
+        );
+        assert!(desugar_filter("flags has 1 and name eq 'it''s").is_err());
+    }
+
+    #[test]
+    fn rejects_malformed_lambdas() {
+        assert!(desugar_filter("tags/count() gt 1").is_err());
+        assert!(desugar_filter("tags/any(t eq 'x')").is_err());
+        assert!(desugar_filter("tags/any(t: t/name eq 'x')").is_err());
+        assert!(desugar_filter("tags/any(t: t/any(u: u eq 1))").is_err());
+        assert!(desugar_filter("tags/any(t: t eq 'x'").is_err());
+    }
+}
@@ -1,4 +1,5 @@
 use std::fmt;
+use std::ops::BitOr;
 
 use thiserror::Error;
 
@@ -35,6 +36,105 @@ impl fmt::Display for FieldKind {
     }
 }
 
+/// Capabilities a field opts into beyond plain comparisons: extra `$filter`
+/// operations and `$apply` grouping/aggregation.
+///
+/// Enabled per field with `#[odata(filter(kind = "...", ops(in, tolower, ...)))]`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct FilterCaps(u32);
+
+impl FilterCaps {
+    pub const NONE: Self = Self(0);
+    /// `field in ('a', 'b')`
+    pub const IN: Self = Self(1);
+    /// `field has 4` on integer bit-flag fields
+    pub const HAS: Self = Self(1 << 1);
+    /// `tolower(field)`, also inside `contains`/`startswith`/`endswith`
+    pub const TOLOWER: Self = Self(1 << 2);
+    /// `toupper(field)`, also inside `contains`/`startswith`/`endswith`
+    pub const TOUPPER: Self = Self(1 << 3);
+    /// `length(field)` in characters
+    pub const LENGTH: Self = Self(1 << 4);
+    /// `year(field)`
+    pub const YEAR: Self = Self(1 << 5);
+    /// `month(field)`
+    pub const MONTH: Self = Self(1 << 6);
+    /// `day(field)`
+    pub const DAY: Self = Self(1 << 7);
+    /// `field lt now()`
+    pub const NOW: Self = Self(1 << 8);
+    /// `field/any(x: ...)` over a JSON array column
+    pub const ANY: Self = Self(1 << 9);
+    /// `field/all(x: ...)` over a JSON array column
+    pub const ALL: Self = Self(1 << 10);
+    /// `$apply=groupby((field))`
+    pub const GROUPBY: Self = Self(1 << 11);
+    /// `$apply=aggregate(field with sum as x)`
+    pub const SUM: Self = Self(1 << 12);
+    /// `$apply=aggregate(field with average as x)`
+    pub const AVERAGE: Self = Self(1 << 13);
+    /// `$apply=aggregate(field with min as x)`
+    pub const MIN: Self = Self(1 << 14);
+    /// `$apply=aggregate(field with max as x)`
+    pub const MAX: Self = Self(1 << 15);
+    /// `$apply=aggregate(field with countdistinct as x)`
+    pub const COUNTDISTINCT: Self = Self(1 << 16);
+
+    const NAMES: &[(&str, Self)] = &[
+        ("in", Self::IN),
+        ("has", Self::HAS),
+        ("tolower", Self::TOLOWER),
+        ("toupper", Self::TOUPPER),
+        ("length", Self::LENGTH),
+        ("year", Self::YEAR),
+        ("month", Self::MONTH),
+        ("day", Self::DAY),
+        ("now", Self::NOW),
+        ("any", Self::ANY),
+        ("all", Self::ALL),
+        ("groupby", Self::GROUPBY),
+        ("sum", Self::SUM),
+        ("average", Self::AVERAGE),
+        ("min", Self::MIN),
+        ("max", Self::MAX),
+        ("countdistinct", Self::COUNTDISTINCT),
+    ];
+
+    #[must_use]
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// Whether all capabilities in `other` are enabled.
+    #[must_use]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Whether any capability in `other` is enabled.
+    #[must_use]
+    pub const fn intersects(self, other: Self) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    /// Look up a capability by its `OData` name (`"in"`, `"tolower"`, ...), case-insensitively.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::NAMES
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, c)| *c)
+    }
+}
+
+impl BitOr for FilterCaps {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        self.union(rhs)
+    }
+}
+
 pub trait FilterField: Copy + Eq + std::hash::Hash + fmt::Debug + 'static {
     const FIELDS: &'static [Self];
 
@@ -42,6 +142,23 @@ pub trait FilterField: Copy + Eq + std::hash::Hash + fmt::Debug + 'static {
 
     fn kind(&self) -> FieldKind;
 
+    /// Whether the field participates in `$search` free-text matching.
+    fn searchable(&self) -> bool {
+        false
+    }
+
+    /// Filter capabilities enabled for the field beyond plain comparisons.
+    fn caps(&self) -> FilterCaps {
+        FilterCaps::NONE
+    }
+
+    /// Whether the field is a JSON array of `kind()` values.
+    ///
+    /// Collection fields can only be filtered with `any`/`all` lambdas.
+    fn is_collection(&self) -> bool {
+        self.caps().intersects(FilterCaps::ANY | FilterCaps::ALL)
+    }
+
     fn from_name(name: &str) -> Option<Self> {
         Self::FIELDS
             .iter()
@@ -61,6 +178,8 @@ pub enum FilterOp {
     Contains,
     StartsWith,
     EndsWith,
+    /// Bit-flag test: `(field & value) = value`
+    Has,
     And,
     Or,
 }
@@ -77,12 +196,93 @@ impl fmt::Display for FilterOp {
             FilterOp::Contains => write!(f, "contains"),
             FilterOp::StartsWith => write!(f, "startswith"),
             FilterOp::EndsWith => write!(f, "endswith"),
+            FilterOp::Has => write!(f, "has"),
             FilterOp::And => write!(f, "and"),
             FilterOp::Or => write!(f, "or"),
         }
     }
 }
 
+/// Function applied to a field on the left-hand side of a comparison.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldFunction {
+    ToLower,
+    ToUpper,
+    Length,
+    Year,
+    Month,
+    Day,
+}
+
+impl FieldFunction {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name.to_ascii_lowercase().as_str() {
+            "tolower" => Self::ToLower,
+            "toupper" => Self::ToUpper,
+            "length" => Self::Length,
+            "year" => Self::Year,
+            "month" => Self::Month,
+            "day" => Self::Day,
+            _ => return None,
+        })
+    }
+
+    /// Capability a field needs for this function.
+    #[must_use]
+    pub fn cap(self) -> FilterCaps {
+        match self {
+            Self::ToLower => FilterCaps::TOLOWER,
+            Self::ToUpper => FilterCaps::TOUPPER,
+            Self::Length => FilterCaps::LENGTH,
+            Self::Year => FilterCaps::YEAR,
+            Self::Month => FilterCaps::MONTH,
+            Self::Day => FilterCaps::DAY,
+        }
+    }
+}
+
+impl fmt::Display for FieldFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ToLower => write!(f, "tolower"),
+            Self::ToUpper => write!(f, "toupper"),
+            Self::Length => write!(f, "length"),
+            Self::Year => write!(f, "year"),
+            Self::Month => write!(f, "month"),
+            Self::Day => write!(f, "day"),
+        }
+    }
+}
+
+/// Lambda operator over a collection field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LambdaOp {
+    Any,
+    All,
+}
+
+impl fmt::Display for LambdaOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Any => write!(f, "any"),
+            Self::All => write!(f, "all"),
+        }
+    }
+}
+
+/// Predicate on the elements of a collection field inside `any`/`all`.
+#[derive(Debug, Clone)]
+pub enum ElementPredicate {
+    /// `x op value`, where `op` is a comparison or `contains`/`startswith`/`endswith`
+    Compare {
+        op: FilterOp,
+        value: ODataValue,
+    },
+    And(Vec<ElementPredicate>),
+    Or(Vec<ElementPredicate>),
+    Not(Box<ElementPredicate>),
+}
+
 #[derive(Debug, Clone)]
 pub enum FilterNode<F: FilterField> {
     Binary {
@@ -95,6 +295,26 @@ pub enum FilterNode<F: FilterField> {
         children: Vec<FilterNode<F>>,
     },
     Not(Box<FilterNode<F>>),
+    /// `field in (v1, v2, ...)`
+    In {
+        field: F,
+        values: Vec<ODataValue>,
+    },
+    /// `func(field) op value`, e.g. `tolower(name) eq 'x'` or `year(created_at) ge 2024`
+    Function {
+        func: FieldFunction,
+        field: F,
+        op: FilterOp,
+        value: ODataValue,
+    },
+    /// `field/any(x: predicate)` or `field/all(x: predicate)`.
+    ///
+    /// `any()` without a predicate matches non-empty collections.
+    Lambda {
+        field: F,
+        op: LambdaOp,
+        predicate: Option<ElementPredicate>,
+    },
 }
 
 impl<F: FilterField> FilterNode<F> {
@@ -139,6 +359,9 @@ pub enum FilterError {
     #[error("Unsupported operation: {0}")]
     UnsupportedOperation(String),
 
+    #[error("Operation '{op}' is not enabled for field {field}")]
+    OperationNotEnabled { field: String, op: String },
+
     #[error("Invalid filter expression: {0}")]
     InvalidExpression(String),
 
@@ -166,7 +389,8 @@ pub fn parse_odata_filter<F: FilterField>(raw: &str) -> FilterResult<FilterNode<
     {
         use odata_params::filters::parse_str;
 
-        let ast = parse_str(raw).map_err(|e| FilterError::InvalidExpression(format!("{e:?}")))?;
+        let raw = crate::desugar::desugar_filter(raw).map_err(FilterError::InvalidExpression)?;
+        let ast = parse_str(&raw).map_err(|e| FilterError::InvalidExpression(format!("{e:?}")))?;
         let ast: odata_ast::Expr = ast.into();
         convert_expr_to_filter_node::<F>(&ast)
     }
@@ -182,10 +406,13 @@ pub fn parse_odata_filter<F: FilterField>(raw: &str) -> FilterResult<FilterNode<
 
 /// Convert a parsed `OData` AST expression into a typed `FilterNode`.
 ///
+/// Operations beyond plain comparisons and `contains`/`startswith`/`endswith`
+/// are only accepted on fields that enable them via [`FilterField::caps`].
+///
 /// # Errors
 ///
 /// Returns `FilterError` if the expression is invalid, references unknown fields, uses unsupported
-/// operations, or contains type mismatches.
+/// or disabled operations, or contains type mismatches.
 pub fn convert_expr_to_filter_node<F: FilterField>(
     expr: &odata_ast::Expr,
 ) -> FilterResult<FilterNode<F>> {
@@ -206,127 +433,372 @@ pub fn convert_expr_to_filter_node<F: FilterField>(
             let inner_node = convert_expr_to_filter_node::<F>(inner)?;
             Ok(FilterNode::not(inner_node))
         }
+        E::Compare(left, op, right) => convert_compare::<F>(left, compare_op(*op), right),
+        E::Function(func_name, args) => convert_function::<F>(func_name, args),
+        E::In(left, list) => convert_in::<F>(left, list),
+        E::Identifier(name) => Err(FilterError::BareIdentifier(name.clone())),
+        E::Value(_) => Err(FilterError::BareLiteral),
+    }
+}
 
-        E::Compare(left, op, right) => {
-            let (field_name, value) = match (&**left, &**right) {
-                (E::Identifier(name), E::Value(val)) => (name.as_str(), val.clone()),
-                (E::Identifier(_), E::Identifier(_)) => {
-                    return Err(FilterError::FieldToFieldComparison);
-                }
-                _ => {
-                    return Err(FilterError::InvalidExpression(
-                        "Comparison must be between field and value".to_owned(),
-                    ));
-                }
-            };
+fn compare_op(op: odata_ast::CompareOperator) -> FilterOp {
+    match op {
+        odata_ast::CompareOperator::Eq => FilterOp::Eq,
+        odata_ast::CompareOperator::Ne => FilterOp::Ne,
+        odata_ast::CompareOperator::Gt => FilterOp::Gt,
+        odata_ast::CompareOperator::Ge => FilterOp::Ge,
+        odata_ast::CompareOperator::Lt => FilterOp::Lt,
+        odata_ast::CompareOperator::Le => FilterOp::Le,
+    }
+}
 
-            let field = F::from_name(field_name)
-                .ok_or_else(|| FilterError::UnknownField(field_name.to_owned()))?;
+/// Resolve a non-collection field by name.
+fn scalar_field<F: FilterField>(name: &str) -> FilterResult<F> {
+    let field = F::from_name(name).ok_or_else(|| FilterError::UnknownField(name.to_owned()))?;
+    if field.is_collection() {
+        return Err(FilterError::UnsupportedOperation(format!(
+            "Field '{}' is a collection; filter it with any/all",
+            field.name()
+        )));
+    }
+    Ok(field)
+}
 
-            validate_value_type(field, &value)?;
+fn require_cap<F: FilterField>(field: F, cap: FilterCaps, op: &str) -> FilterResult<()> {
+    if field.caps().contains(cap) {
+        Ok(())
+    } else {
+        Err(FilterError::OperationNotEnabled {
+            field: field.name().to_owned(),
+            op: op.to_owned(),
+        })
+    }
+}
 
-            let filter_op = match op {
-                odata_ast::CompareOperator::Eq => FilterOp::Eq,
-                odata_ast::CompareOperator::Ne => FilterOp::Ne,
-                odata_ast::CompareOperator::Gt => FilterOp::Gt,
-                odata_ast::CompareOperator::Ge => FilterOp::Ge,
-                odata_ast::CompareOperator::Lt => FilterOp::Lt,
-                odata_ast::CompareOperator::Le => FilterOp::Le,
-            };
+fn require_kind<F: FilterField>(field: F, kinds: &[FieldKind], got: &str) -> FilterResult<()> {
+    if kinds.contains(&field.kind()) {
+        Ok(())
+    } else {
+        Err(FilterError::TypeMismatch {
+            field: field.name().to_owned(),
+            expected: kinds[0],
+            got: got.to_owned(),
+        })
+    }
+}
 
-            Ok(FilterNode::binary(field, filter_op, value))
+fn integer_value(value: &odata_ast::Value) -> Option<i64> {
+    match value {
+        odata_ast::Value::Number(n) if n.is_integer() => bigdecimal::ToPrimitive::to_i64(n),
+        _ => None,
+    }
+}
+
+fn convert_compare<F: FilterField>(
+    left: &odata_ast::Expr,
+    op: FilterOp,
+    right: &odata_ast::Expr,
+) -> FilterResult<FilterNode<F>> {
+    use odata_ast::Expr as E;
+
+    match (left, right) {
+        (E::Identifier(name), E::Value(val)) => {
+            let field = scalar_field::<F>(name)?;
+            validate_value_type(field, val)?;
+            Ok(FilterNode::binary(field, op, val.clone()))
         }
+        (E::Identifier(name), E::Function(func, args))
+            if func.eq_ignore_ascii_case("now") && args.is_empty() =>
+        {
+            let field = scalar_field::<F>(name)?;
+            require_cap(field, FilterCaps::NOW, "now")?;
+            require_kind(field, &[FieldKind::DateTimeUtc], "now()")?;
+            // Resolved once per request so the comparison is portable across backends
+            Ok(FilterNode::binary(
+                field,
+                op,
+                odata_ast::Value::DateTime(chrono::Utc::now()),
+            ))
+        }
+        (E::Identifier(_), E::Identifier(_)) => Err(FilterError::FieldToFieldComparison),
+        (E::Function(func, args), E::Value(val)) => {
+            convert_function_compare::<F>(func, args, op, val)
+        }
+        _ => Err(FilterError::InvalidExpression(
+            "Comparison must be between field and value".to_owned(),
+        )),
+    }
+}
 
-        E::Function(func_name, args) => {
-            let name_lower = func_name.to_ascii_lowercase();
-            match (name_lower.as_str(), args.as_slice()) {
-                (
-                    "contains",
-                    [
-                        E::Identifier(field_name),
-                        E::Value(odata_ast::Value::String(s)),
-                    ],
-                ) => {
-                    let field = F::from_name(field_name)
-                        .ok_or_else(|| FilterError::UnknownField(field_name.clone()))?;
-
-                    if field.kind() != FieldKind::String {
-                        return Err(FilterError::TypeMismatch {
-                            field: field_name.clone(),
-                            expected: FieldKind::String,
-                            got: "non-string".to_owned(),
-                        });
-                    }
-
-                    Ok(FilterNode::binary(
-                        field,
-                        FilterOp::Contains,
-                        odata_ast::Value::String(s.clone()),
-                    ))
-                }
-                (
-                    "startswith",
-                    [
-                        E::Identifier(field_name),
-                        E::Value(odata_ast::Value::String(s)),
-                    ],
-                ) => {
-                    let field = F::from_name(field_name)
-                        .ok_or_else(|| FilterError::UnknownField(field_name.clone()))?;
-
-                    if field.kind() != FieldKind::String {
-                        return Err(FilterError::TypeMismatch {
-                            field: field_name.clone(),
-                            expected: FieldKind::String,
-                            got: "non-string".to_owned(),
-                        });
-                    }
-
-                    Ok(FilterNode::binary(
-                        field,
-                        FilterOp::StartsWith,
-                        odata_ast::Value::String(s.clone()),
-                    ))
-                }
-                (
-                    "endswith",
-                    [
-                        E::Identifier(field_name),
-                        E::Value(odata_ast::Value::String(s)),
-                    ],
-                ) => {
-                    let field = F::from_name(field_name)
-                        .ok_or_else(|| FilterError::UnknownField(field_name.clone()))?;
-
-                    if field.kind() != FieldKind::String {
-                        return Err(FilterError::TypeMismatch {
-                            field: field_name.clone(),
-                            expected: FieldKind::String,
-                            got: "non-string".to_owned(),
-                        });
-                    }
-
-                    Ok(FilterNode::binary(
-                        field,
-                        FilterOp::EndsWith,
-                        odata_ast::Value::String(s.clone()),
-                    ))
-                }
-                _ => Err(FilterError::UnsupportedOperation(format!(
-                    "Function '{func_name}'"
-                ))),
+/// `func(field) op value`
+fn convert_function_compare<F: FilterField>(
+    func_name: &str,
+    args: &[odata_ast::Expr],
+    op: FilterOp,
+    value: &odata_ast::Value,
+) -> FilterResult<FilterNode<F>> {
+    let func = FieldFunction::from_name(func_name)
+        .ok_or_else(|| FilterError::UnsupportedOperation(format!("Function '{func_name}'")))?;
+    let field = function_field::<F>(func, args)?;
+
+    match func {
+        FieldFunction::ToLower | FieldFunction::ToUpper => validate_value_type(field, value)?,
+        FieldFunction::Length | FieldFunction::Year | FieldFunction::Month | FieldFunction::Day => {
+            if integer_value(value).is_none() {
+                return Err(FilterError::InvalidExpression(format!(
+                    "'{func}' must be compared with an integer"
+                )));
             }
         }
+    }
 
-        E::In(_left, _list) => Err(FilterError::UnsupportedOperation(
-            "IN operator not yet supported in typed filters".to_owned(),
-        )),
+    Ok(FilterNode::Function {
+        func,
+        field,
+        op,
+        value: value.clone(),
+    })
+}
 
-        E::Identifier(name) => Err(FilterError::BareIdentifier(name.clone())),
-        E::Value(_) => Err(FilterError::BareLiteral),
+/// Resolve and check the single field argument of a field function.
+fn function_field<F: FilterField>(
+    func: FieldFunction,
+    args: &[odata_ast::Expr],
+) -> FilterResult<F> {
+    let [odata_ast::Expr::Identifier(name)] = args else {
+        return Err(FilterError::InvalidExpression(format!(
+            "'{func}' takes a single field argument"
+        )));
+    };
+    let field = scalar_field::<F>(name)?;
+    require_cap(field, func.cap(), &func.to_string())?;
+    let kinds: &[FieldKind] = match func {
+        FieldFunction::ToLower | FieldFunction::ToUpper | FieldFunction::Length => {
+            &[FieldKind::String]
+        }
+        FieldFunction::Year | FieldFunction::Month | FieldFunction::Day => {
+            &[FieldKind::DateTimeUtc, FieldKind::Date]
+        }
+    };
+    require_kind(field, kinds, &format!("{func}()"))?;
+    Ok(field)
+}
+
+fn convert_function<F: FilterField>(
+    func_name: &str,
+    args: &[odata_ast::Expr],
+) -> FilterResult<FilterNode<F>> {
+    match func_name.to_ascii_lowercase().as_str() {
+        "contains" => convert_string_function::<F>(func_name, FilterOp::Contains, args),
+        "startswith" => convert_string_function::<F>(func_name, FilterOp::StartsWith, args),
+        "endswith" => convert_string_function::<F>(func_name, FilterOp::EndsWith, args),
+        "has" => convert_has::<F>(args),
+        "any" => convert_lambda::<F>(LambdaOp::Any, args),
+        "all" => convert_lambda::<F>(LambdaOp::All, args),
+        _ => Err(FilterError::UnsupportedOperation(format!(
+            "Function '{func_name}'"
+        ))),
+    }
+}
+
+/// `contains`/`startswith`/`endswith` on a field or on `tolower`/`toupper` of a field
+fn convert_string_function<F: FilterField>(
+    func_name: &str,
+    op: FilterOp,
+    args: &[odata_ast::Expr],
+) -> FilterResult<FilterNode<F>> {
+    use odata_ast::Expr as E;
+
+    match args {
+        [
+            E::Identifier(field_name),
+            E::Value(odata_ast::Value::String(s)),
+        ] => {
+            let field = scalar_field::<F>(field_name)?;
+            if field.kind() != FieldKind::String {
+                return Err(FilterError::TypeMismatch {
+                    field: field_name.clone(),
+                    expected: FieldKind::String,
+                    got: "non-string".to_owned(),
+                });
+            }
+            Ok(FilterNode::binary(
+                field,
+                op,
+                odata_ast::Value::String(s.clone()),
+            ))
+        }
+        [
+            E::Function(inner, inner_args),
+            E::Value(value @ odata_ast::Value::String(_)),
+        ] => {
+            let func = FieldFunction::from_name(inner)
+                .filter(|f| matches!(f, FieldFunction::ToLower | FieldFunction::ToUpper))
+                .ok_or_else(|| {
+                    FilterError::UnsupportedOperation(format!("Function '{func_name}'"))
+                })?;
+            let field = function_field::<F>(func, inner_args)?;
+            Ok(FilterNode::Function {
+                func,
+                field,
+                op,
+                value: value.clone(),
+            })
+        }
+        _ => Err(FilterError::UnsupportedOperation(format!(
+            "Function '{func_name}'"
+        ))),
+    }
+}
+
+/// `field has flags` (desugared to `has(field, flags)`)
+fn convert_has<F: FilterField>(args: &[odata_ast::Expr]) -> FilterResult<FilterNode<F>> {
+    use odata_ast::Expr as E;
+
+    let [E::Identifier(name), E::Value(value)] = args else {
+        return Err(FilterError::InvalidExpression(
+            "'has' requires a field and a flag value".to_owned(),
+        ));
+    };
+    let field = scalar_field::<F>(name)?;
+    require_cap(field, FilterCaps::HAS, "has")?;
+    require_kind(field, &[FieldKind::I64], "has")?;
+    if integer_value(value).is_none_or(|v| v < 0) {
+        return Err(FilterError::InvalidExpression(
+            "'has' requires a non-negative integer flag value".to_owned(),
+        ));
+    }
+    Ok(FilterNode::binary(field, FilterOp::Has, value.clone()))
+}
+
+fn convert_in<F: FilterField>(
+    left: &odata_ast::Expr,
+    list: &[odata_ast::Expr],
+) -> FilterResult<FilterNode<F>> {
+    let odata_ast::Expr::Identifier(name) = left else {
+        return Err(FilterError::InvalidExpression(
+            "Left side of 'in' must be a field".to_owned(),
+        ));
+    };
+    let field = scalar_field::<F>(name)?;
+    require_cap(field, FilterCaps::IN, "in")?;
+
+    let values = list
+        .iter()
+        .map(|item| match item {
+            odata_ast::Expr::Value(v) if !matches!(v, odata_ast::Value::Null) => {
+                validate_value_type(field, v)?;
+                Ok(v.clone())
+            }
+            _ => Err(FilterError::InvalidExpression(
+                "'in' list must contain non-null literals".to_owned(),
+            )),
+        })
+        .collect::<FilterResult<Vec<_>>>()?;
+
+    Ok(FilterNode::In { field, values })
+}
+
+/// `any(field)`, `any(field, predicate)` or `all(field, predicate)` (desugared lambdas)
+fn convert_lambda<F: FilterField>(
+    op: LambdaOp,
+    args: &[odata_ast::Expr],
+) -> FilterResult<FilterNode<F>> {
+    let (name, body) = match args {
+        [odata_ast::Expr::Identifier(name)] => (name, None),
+        [odata_ast::Expr::Identifier(name), body] => (name, Some(body)),
+        _ => {
+            return Err(FilterError::InvalidExpression(format!(
+                "'{op}' must be applied to a collection field"
+            )));
+        }
+    };
+
+    let field = F::from_name(name).ok_or_else(|| FilterError::UnknownField(name.clone()))?;
+    let cap = match op {
+        LambdaOp::Any => FilterCaps::ANY,
+        LambdaOp::All => FilterCaps::ALL,
+    };
+    require_cap(field, cap, &op.to_string())?;
+
+    let predicate = match body {
+        Some(body) => Some(convert_element_predicate(field, body)?),
+        None if op == LambdaOp::All => {
+            return Err(FilterError::InvalidExpression(
+                "'all' requires a predicate".to_owned(),
+            ));
+        }
+        None => None,
+    };
+
+    Ok(FilterNode::Lambda {
+        field,
+        op,
+        predicate,
+    })
+}
+
+fn convert_element_predicate<F: FilterField>(
+    field: F,
+    expr: &odata_ast::Expr,
+) -> FilterResult<ElementPredicate> {
+    use odata_ast::Expr as E;
+    use odata_ast::LAMBDA_VARIABLE;
+
+    match expr {
+        E::And(a, b) => Ok(ElementPredicate::And(vec![
+            convert_element_predicate(field, a)?,
+            convert_element_predicate(field, b)?,
+        ])),
+        E::Or(a, b) => Ok(ElementPredicate::Or(vec![
+            convert_element_predicate(field, a)?,
+            convert_element_predicate(field, b)?,
+        ])),
+        E::Not(x) => Ok(ElementPredicate::Not(Box::new(convert_element_predicate(
+            field, x,
+        )?))),
+        E::Compare(l, op, r) => match (&**l, &**r) {
+            (E::Identifier(v), E::Value(value)) if v == LAMBDA_VARIABLE => {
+                validate_value_type(field, value)?;
+                Ok(ElementPredicate::Compare {
+                    op: compare_op(*op),
+                    value: value.clone(),
+                })
+            }
+            _ => Err(lambda_body_error(field)),
+        },
+        E::Function(func, args) => {
+            let op = match func.to_ascii_lowercase().as_str() {
+                "contains" => FilterOp::Contains,
+                "startswith" => FilterOp::StartsWith,
+                "endswith" => FilterOp::EndsWith,
+                _ => return Err(lambda_body_error(field)),
+            };
+            match args.as_slice() {
+                [
+                    E::Identifier(v),
+                    E::Value(value @ odata_ast::Value::String(_)),
+                ] if v == LAMBDA_VARIABLE => {
+                    require_kind(field, &[FieldKind::String], "non-string")?;
+                    Ok(ElementPredicate::Compare {
+                        op,
+                        value: value.clone(),
+                    })
+                }
+                _ => Err(lambda_body_error(field)),
+            }
+        }
+        E::In(..) | E::Identifier(_) | E::Value(_) => Err(lambda_body_error(field)),
     }
 }
 
+fn lambda_body_error<F: FilterField>(field: F) -> FilterError {
+    FilterError::InvalidExpression(format!(
+        "Lambda over '{}' only supports comparisons of the range variable with literals",
+        field.name()
+    ))
+}
+
 fn validate_value_type<F: FilterField>(field: F, value: &odata_ast::Value) -> FilterResult<()> {
     use odata_ast::Value as V;
 
@@ -355,3 +827,178 @@ fn validate_value_type<F: FilterField>(field: F, value: &odata_ast::Value) -> Fi
         })
     }
 }
+
+#[cfg(all(test, feature = "with-odata-params"))]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+    enum Field {
+        Name,
+        Flags,
+        CreatedAt,
+        Tags,
+        Plain,
+    }
+
+    impl FilterField for Field {
+        const FIELDS: &'static [Self] = &[
+            Field::Name,
+            Field::Flags,
+            Field::CreatedAt,
+            Field::Tags,
+            Field::Plain,
+        ];
+
+        fn name(&self) -> &'static str {
+            match self {
+                Field::Name => "name",
+                Field::Flags => "flags",
+                Field::CreatedAt => "created_at",
+                Field::Tags => "tags",
+                Field::Plain => "plain",
+            }
+        }
+
+        fn kind(&self) -> FieldKind {
+            match self {
+                Field::Name | Field::Tags | Field::Plain => FieldKind::String,
+                Field::Flags => FieldKind::I64,
+                Field::CreatedAt => FieldKind::DateTimeUtc,
+            }
+        }
+
+        fn caps(&self) -> FilterCaps {
+            match self {
+                Field::Name => FilterCaps::IN | FilterCaps::TOLOWER | FilterCaps::LENGTH,
+                Field::Flags => FilterCaps::HAS | FilterCaps::IN,
+                Field::CreatedAt => FilterCaps::YEAR | FilterCaps::NOW,
+                Field::Tags => FilterCaps::ANY | FilterCaps::ALL,
+                Field::Plain => FilterCaps::NONE,
+            }
+        }
+    }
+
+    fn parse(raw: &str) -> FilterResult<FilterNode<Field>> {
+        parse_odata_filter::<Field>(raw)
+    }
+
+    #[test]
+    fn converts_in_and_has() {
+        let node = parse("name in ('a', 'b')").unwrap();
+        assert!(matches!(
+            node,
+            FilterNode::In { field: Field::Name, ref values } if values.len() == 2
+        ));
+
+        let node = parse("flags has 4").unwrap();
+        assert!(matches!(
+            node,
+            FilterNode::Binary {
+                field: Field::Flags,
+                op: FilterOp::Has,
+                value: ODataValue::Number(_)
+            }
+        ));
+
+        assert!(matches!(
+            parse("flags in ('x')"),
+            Err(FilterError::TypeMismatch { .. })
+        ));
+        assert!(matches!(
+            parse("flags has -1"),
+            Err(FilterError::InvalidExpression(_))
+        ));
+    }
+
+    #[test]
+    fn converts_field_functions() {
+        let node = parse("tolower(name) eq 'bob'").unwrap();
+        assert!(matches!(
+            node,
+            FilterNode::Function {
+                func: FieldFunction::ToLower,
+                field: Field::Name,
+                op: FilterOp::Eq,
+                ..
+            }
+        ));
+
+        let node = parse("length(name) gt 3").unwrap();
+        assert!(matches!(
+            node,
+            FilterNode::Function {
+                func: FieldFunction::Length,
+                value: ODataValue::Number(_),
+                ..
+            }
+        ));
+
+        let node = parse("year(created_at) eq 2024").unwrap();
+        assert!(matches!(
+            node,
+            FilterNode::Function {
+                func: FieldFunction::Year,
+                field: Field::CreatedAt,
+                ..
+            }
+        ));
+
+        let node = parse("created_at lt now()").unwrap();
+        assert!(matches!(
+            node,
+            FilterNode::Binary {
+                field: Field::CreatedAt,
+                op: FilterOp::Lt,
+                value: ODataValue::DateTime(_)
+            }
+        ));
+    }
+
+    #[test]
+    fn converts_lambdas() {
+        let node = parse("tags/any(t: t eq 'x' or startswith(t, 'y'))").unwrap();
+        let FilterNode::Lambda {
+            field: Field::Tags,
+            op: LambdaOp::Any,
+            predicate: Some(ElementPredicate::Or(children)),
+        } = node
+        else {
+            panic!("expected any() with an or predicate, got {node:?}");
+        };
+        assert_eq!(children.len(), 2);
+
+        assert!(matches!(
+            parse("tags/any()").unwrap(),
+            FilterNode::Lambda {
+                op: LambdaOp::Any,
+                predicate: None,
+                ..
+            }
+        ));
+        assert!(parse("tags/all()").is_err());
+        // Collections only support lambdas
+        assert!(parse("tags eq 'x'").is_err());
+        assert!(parse("name/any(n: n eq 'x')").is_err());
+    }
+
+    #[test]
+    fn rejects_operations_not_enabled_for_field() {
+        for raw in [
+            "plain in ('a')",
+            "tolower(plain) eq 'a'",
+            "toupper(name) eq 'A'",
+            "month(created_at) eq 1",
+            "flags has 1 and plain has 1",
+        ] {
+            assert!(
+                matches!(parse(raw), Err(FilterError::OperationNotEnabled { .. })),
+                "{raw} should be rejected, got {:?}",
+                parse(raw)
+            );
+        }
+        // Pre-existing operations stay available without caps
+        assert!(parse("contains(plain, 'a') and plain ne 'b'").is_ok());
+    }
+}
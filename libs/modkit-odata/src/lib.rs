@@ -1,5 +1,8 @@
 #![cfg_attr(coverage_nightly, feature(coverage_attribute))]
+pub mod apply;
 pub mod builder;
+#[cfg(feature = "with-odata-params")]
+mod desugar;
 pub mod errors;
 pub mod filter;
 pub mod limits;
@@ -7,11 +10,12 @@ pub mod page;
 pub mod pagination;
 pub mod problem_mapping;
 pub mod schema;
+pub mod search;
 
 pub use builder::QueryBuilder;
 pub use limits::ODataLimits;
 pub use page::{Page, PageInfo};
-pub use pagination::{normalize_filter_for_hash, short_filter_hash};
+pub use pagination::{normalize_filter_for_hash, short_filter_hash, short_query_hash};
 pub use schema::{FieldRef, Schema};
 
 pub mod ast {
@@ -19,6 +23,12 @@ pub mod ast {
     use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
     use uuid::Uuid;
 
+    /// Name of the range variable inside `any`/`all` lambda bodies.
+    ///
+    /// `tags/any(t: t eq 'x')` is parsed as `any(tags, __it eq 'x')`, independent of
+    /// the variable name chosen by the client.
+    pub const LAMBDA_VARIABLE: &str = "__it";
+
     #[derive(Clone, Debug)]
     pub enum Expr {
         And(Box<Expr>, Box<Expr>),
@@ -272,6 +282,9 @@ impl std::fmt::Display for ODataOrderBy {
 /// These errors map to RFC 9457 Problem responses via the catalog in `modkit`:
 /// - `InvalidFilter` → 422 `gts...~hx.odata.errors.invalid_filter.v1`
 /// - `InvalidOrderByField` → 422 `gts...~hx.odata.errors.invalid_orderby.v1`
+/// - `InvalidSearch` → 422 `gts...~hx.odata.errors.invalid_search.v1`
+/// - `InvalidExpand` → 422 `gts...~hx.odata.errors.invalid_expand.v1`
+/// - `InvalidApply` → 422 `gts...~hx.odata.errors.invalid_apply.v1`
 /// - Cursor errors → 422 `gts...~hx.odata.errors.invalid_cursor.v1`
 #[derive(thiserror::Error, Debug, Clone)]
 pub enum Error {
@@ -283,6 +296,18 @@ pub enum Error {
     #[error("unsupported $orderby field: {0}")]
     InvalidOrderByField(String),
 
+    // Search parsing and validation errors
+    #[error("invalid $search: {0}")]
+    InvalidSearch(String),
+
+    // Expand parsing and validation errors
+    #[error("unsupported $expand: {0}")]
+    InvalidExpand(String),
+
+    // Apply (aggregation) parsing and validation errors
+    #[error("invalid $apply: {0}")]
+    InvalidApply(String),
+
     // Pagination and cursor errors
     #[error("ORDER_MISMATCH")]
     OrderMismatch,
@@ -466,6 +491,14 @@ pub struct ODataQuery {
     pub cursor: Option<CursorV1>,
     pub filter_hash: Option<String>,
     pub select: Option<Vec<String>>,
+    /// `$count=true`: include the total number of matching items in `PageInfo`
+    pub count: bool,
+    /// `$search`: free-text search over the resource's searchable fields
+    pub search: Option<String>,
+    /// `$expand`: one-level navigation properties to inline
+    pub expand: Option<Vec<String>>,
+    /// `$apply`: grouping and aggregation pipeline
+    pub apply: Option<apply::Apply>,
 }
 
 impl ODataQuery {
@@ -503,6 +536,26 @@ impl ODataQuery {
         self
     }
 
+    pub fn with_count(mut self, count: bool) -> Self {
+        self.count = count;
+        self
+    }
+
+    pub fn with_search(mut self, search: impl Into<String>) -> Self {
+        self.search = Some(search.into());
+        self
+    }
+
+    pub fn with_expand(mut self, navs: Vec<String>) -> Self {
+        self.expand = Some(navs);
+        self
+    }
+
+    pub fn with_apply(mut self, apply: apply::Apply) -> Self {
+        self.apply = Some(apply);
+        self
+    }
+
     /// Get filter as AST
     #[must_use]
     pub fn filter(&self) -> Option<&ast::Expr> {
@@ -532,6 +585,39 @@ impl ODataQuery {
     pub fn selected_fields(&self) -> Option<&[String]> {
         self.select.as_deref()
     }
+
+    /// Get the free-text search expression
+    #[must_use]
+    pub fn search(&self) -> Option<&str> {
+        self.search.as_deref()
+    }
+
+    /// Get the `$apply` aggregation pipeline
+    #[must_use]
+    pub fn apply(&self) -> Option<&apply::Apply> {
+        self.apply.as_ref()
+    }
+
+    /// Check whether the navigation property `nav` was requested via `$expand`
+    #[must_use]
+    pub fn expands(&self, nav: &str) -> bool {
+        self.expand
+            .as_deref()
+            .is_some_and(|navs| navs.iter().any(|n| n.eq_ignore_ascii_case(nav)))
+    }
+
+    /// Validate `$expand` against the navigation properties an endpoint supports.
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidExpand` for the first requested property not in `allowed`.
+    pub fn validate_expand(&self, allowed: &[&str]) -> Result<(), Error> {
+        for nav in self.expand.iter().flatten() {
+            if !allowed.iter().any(|a| a.eq_ignore_ascii_case(nav)) {
+                return Err(Error::InvalidExpand(nav.clone()));
+            }
+        }
+        Ok(())
+    }
 }
 
 impl From<Option<ast::Expr>> for ODataQuery {
@@ -662,7 +748,8 @@ pub fn parse_filter_string(raw: &str) -> Result<ParsedFilter, Error> {
         }
     }
 
-    let ast_src = od::parse_str(raw).map_err(|e| Error::InvalidFilter(format!("{e:?}")))?;
+    let raw = desugar::desugar_filter(raw).map_err(Error::InvalidFilter)?;
+    let ast_src = od::parse_str(&raw).map_err(|e| Error::InvalidFilter(format!("{e:?}")))?;
 
     let node_count = count_ast_nodes(&ast_src);
     let expr: ast::Expr = ast_src.into();
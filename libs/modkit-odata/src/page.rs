@@ -6,6 +6,14 @@ pub struct PageInfo {
     pub next_cursor: Option<String>,
     pub prev_cursor: Option<String>,
     pub limit: u64,
+    /// Number of items matching the filter and search, present only when `$count=true`.
+    ///
+    /// Counting stops at the server-side cap; `total_count_capped` tells when it was reached.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_count: Option<u64>,
+    /// Whether `total_count` was truncated at the server-side cap
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub total_count_capped: bool,
 }
 
 #[cfg_attr(feature = "with-utoipa", derive(utoipa::ToSchema))]
@@ -31,6 +39,8 @@ impl<T> Page<T> {
                 next_cursor: None,
                 prev_cursor: None,
                 limit,
+                total_count: None,
+                total_count_capped: false,
             },
         }
     }
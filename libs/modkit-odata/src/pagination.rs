@@ -90,6 +90,24 @@ pub fn short_filter_hash(expr: Option<&ast::Expr>) -> Option<String> {
     })
 }
 
+/// Generate a short hash over the filter and `$search` expression for cursor consistency checks.
+///
+/// Identical to [`short_filter_hash`] when there is no search, so cursors issued
+/// for filter-only queries stay valid.
+#[must_use]
+pub fn short_query_hash(filter: Option<&ast::Expr>, search: Option<&str>) -> Option<String> {
+    let Some(search) = search else {
+        return short_filter_hash(filter);
+    };
+    let normalized = format!(
+        "{}|SEARCH({})",
+        filter.map(normalize_filter_for_hash).unwrap_or_default(),
+        search.trim()
+    );
+    let bytes = Sha256::digest(normalized.as_bytes());
+    Some(hex::encode(&bytes[..8]))
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
@@ -137,4 +155,21 @@ mod tests {
     fn test_short_filter_hash_none() {
         assert_eq!(short_filter_hash(None), None);
     }
+
+    #[test]
+    fn test_short_query_hash_includes_search() {
+        let expr = Expr::Identifier("active".to_owned());
+
+        assert_eq!(
+            short_query_hash(Some(&expr), None),
+            short_filter_hash(Some(&expr))
+        );
+        assert_eq!(short_query_hash(None, None), None);
+
+        let with_search = short_query_hash(Some(&expr), Some("alice"));
+        assert!(with_search.is_some());
+        assert_ne!(with_search, short_filter_hash(Some(&expr)));
+        assert_ne!(with_search, short_query_hash(Some(&expr), Some("bob")));
+        assert_eq!(short_query_hash(None, Some("bob")).unwrap().len(), 16);
+    }
 }
@@ -12,9 +12,9 @@ impl From<Error> for Problem {
     fn from(err: Error) -> Self {
         use Error::{
             CursorInvalidBase64, CursorInvalidDirection, CursorInvalidFields, CursorInvalidJson,
-            CursorInvalidKeys, CursorInvalidVersion, Db, FilterMismatch, InvalidCursor,
-            InvalidFilter, InvalidLimit, InvalidOrderByField, OrderMismatch, OrderWithCursor,
-            ParsingUnavailable,
+            CursorInvalidKeys, CursorInvalidVersion, Db, FilterMismatch, InvalidApply,
+            InvalidCursor, InvalidExpand, InvalidFilter, InvalidLimit, InvalidOrderByField,
+            InvalidSearch, OrderMismatch, OrderWithCursor, ParsingUnavailable,
         };
 
         match err {
@@ -26,6 +26,18 @@ impl From<Error> for Problem {
             InvalidOrderByField(field) => ErrorCode::odata_errors_invalid_orderby_v1()
                 .as_problem(format!("Unsupported $orderby field: {field}")),
 
+            // Search parsing and validation errors → 422
+            InvalidSearch(msg) => ErrorCode::odata_errors_invalid_search_v1()
+                .as_problem(format!("Invalid $search: {msg}")),
+
+            // Unsupported navigation properties → 422
+            InvalidExpand(nav) => ErrorCode::odata_errors_invalid_expand_v1()
+                .as_problem(format!("Unsupported $expand: {nav}")),
+
+            // Aggregation parsing and validation errors → 422
+            InvalidApply(msg) => ErrorCode::odata_errors_invalid_apply_v1()
+                .as_problem(format!("Invalid $apply: {msg}")),
+
             // All cursor-related errors → 422
             InvalidCursor
             | CursorInvalidBase64
@@ -97,6 +109,29 @@ mod tests {
         assert!(problem.code.contains("invalid_orderby"));
     }
 
+    #[test]
+    fn test_search_and_expand_errors_convert_to_problem() {
+        use http::StatusCode;
+
+        let problem: Problem = Error::InvalidSearch("unbalanced quotes".to_owned()).into();
+        assert_eq!(problem.status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(problem.code.contains("invalid_search"));
+
+        let problem: Problem = Error::InvalidExpand("orders".to_owned()).into();
+        assert_eq!(problem.status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(problem.code.contains("invalid_expand"));
+        assert!(problem.detail.contains("orders"));
+    }
+
+    #[test]
+    fn test_apply_error_converts_to_problem() {
+        use http::StatusCode;
+
+        let problem: Problem = Error::InvalidApply("unsupported transformation".to_owned()).into();
+        assert_eq!(problem.status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(problem.code.contains("invalid_apply"));
+    }
+
     #[test]
     fn test_cursor_error_converts_to_problem() {
         use http::StatusCode;
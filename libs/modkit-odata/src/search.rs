@@ -0,0 +1,225 @@
+//! `$search` parsing and translation into typed filters
+//!
+//! The supported grammar is a deliberately small subset of `OData` `$search`:
+//! whitespace-separated terms (optionally joined by `AND`) that must all match,
+//! where double-quoted phrases are kept together. `OR` and `NOT` are rejected.
+//!
+//! Each term matches when any field marked `searchable` in
+//! `#[derive(ODataFilterable)]` contains it, so the search maps onto the same
+//! [`FilterNode`] machinery as `$filter`.
+
+use crate::Error;
+use crate::ast::Value;
+use crate::filter::{FieldKind, FilterField, FilterNode, FilterOp};
+
+/// Maximum number of terms in a `$search` expression
+pub const MAX_SEARCH_TERMS: usize = 16;
+
+/// Split a raw `$search` expression into terms.
+///
+/// # Errors
+/// Returns `Error::InvalidSearch` if the expression is empty, has unbalanced quotes,
+/// uses `OR`/`NOT`, or exceeds [`MAX_SEARCH_TERMS`].
+pub fn parse_search_terms(raw: &str) -> Result<Vec<String>, Error> {
+    let mut terms = Vec::new();
+    let mut chars = raw.trim().chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        if c == '"' {
+            chars.next();
+            let mut phrase = String::new();
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some(ch) => phrase.push(ch),
+                    None => return Err(Error::InvalidSearch("unbalanced quotes".to_owned())),
+                }
+            }
+            let phrase = phrase.trim();
+            if !phrase.is_empty() {
+                terms.push(phrase.to_owned());
+            }
+            continue;
+        }
+
+        let mut word = String::new();
+        while let Some(&ch) = chars.peek() {
+            if ch.is_whitespace() || ch == '"' {
+                break;
+            }
+            word.push(ch);
+            chars.next();
+        }
+        match word.as_str() {
+            "AND" => {}
+            "OR" | "NOT" => {
+                return Err(Error::InvalidSearch(format!("{word} is not supported")));
+            }
+            _ => terms.push(word),
+        }
+    }
+
+    if terms.is_empty() {
+        return Err(Error::InvalidSearch("no search terms".to_owned()));
+    }
+    if terms.len() > MAX_SEARCH_TERMS {
+        return Err(Error::InvalidSearch(format!(
+            "too many terms (max: {MAX_SEARCH_TERMS})"
+        )));
+    }
+    Ok(terms)
+}
+
+/// Translate a raw `$search` expression into a typed filter over the searchable fields of `F`.
+///
+/// # Errors
+/// Returns `Error::InvalidSearch` if the expression is invalid or `F` declares no
+/// searchable fields.
+pub fn search_to_filter_node<F: FilterField>(raw: &str) -> Result<FilterNode<F>, Error> {
+    let fields: Vec<F> = F::FIELDS
+        .iter()
+        .copied()
+        .filter(|f| f.searchable() && f.kind() == FieldKind::String)
+        .collect();
+    if fields.is_empty() {
+        return Err(Error::InvalidSearch(
+            "this resource does not support $search".to_owned(),
+        ));
+    }
+
+    let terms = parse_search_terms(raw)?;
+    let per_term = terms
+        .into_iter()
+        .map(|term| {
+            FilterNode::or(
+                fields
+                    .iter()
+                    .map(|f| {
+                        FilterNode::binary(*f, FilterOp::Contains, Value::String(term.clone()))
+                    })
+                    .collect(),
+            )
+        })
+        .collect();
+    Ok(FilterNode::and(per_term))
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+    enum Field {
+        Id,
+        Name,
+        Email,
+    }
+
+    impl FilterField for Field {
+        const FIELDS: &'static [Self] = &[Field::Id, Field::Name, Field::Email];
+
+        fn name(&self) -> &'static str {
+            match self {
+                Field::Id => "id",
+                Field::Name => "name",
+                Field::Email => "email",
+            }
+        }
+
+        fn kind(&self) -> FieldKind {
+            match self {
+                Field::Id => FieldKind::Uuid,
+                Field::Name | Field::Email => FieldKind::String,
+            }
+        }
+
+        fn searchable(&self) -> bool {
+            matches!(self, Field::Name | Field::Email)
+        }
+    }
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+    enum Unsearchable {
+        Id,
+    }
+
+    impl FilterField for Unsearchable {
+        const FIELDS: &'static [Self] = &[Unsearchable::Id];
+
+        fn name(&self) -> &'static str {
+            "id"
+        }
+
+        fn kind(&self) -> FieldKind {
+            FieldKind::Uuid
+        }
+    }
+
+    #[test]
+    fn parses_terms_and_phrases() {
+        let terms = parse_search_terms(r#"  alice AND "new york"  smith "#).unwrap();
+        assert_eq!(terms, vec!["alice", "new york", "smith"]);
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        assert!(matches!(
+            parse_search_terms(r#""open"#),
+            Err(Error::InvalidSearch(_))
+        ));
+        assert!(matches!(
+            parse_search_terms("a OR b"),
+            Err(Error::InvalidSearch(_))
+        ));
+        assert!(matches!(
+            parse_search_terms("   "),
+            Err(Error::InvalidSearch(_))
+        ));
+        let many = vec!["t"; MAX_SEARCH_TERMS + 1].join(" ");
+        assert!(matches!(
+            parse_search_terms(&many),
+            Err(Error::InvalidSearch(_))
+        ));
+    }
+
+    #[test]
+    fn builds_and_of_ors_over_searchable_fields() {
+        let node = search_to_filter_node::<Field>("alice smith").unwrap();
+        let FilterNode::Composite { op, children } = node else {
+            panic!("expected composite");
+        };
+        assert_eq!(op, FilterOp::And);
+        assert_eq!(children.len(), 2);
+        for child in children {
+            let FilterNode::Composite { op, children } = child else {
+                panic!("expected composite");
+            };
+            assert_eq!(op, FilterOp::Or);
+            let fields: Vec<_> = children
+                .iter()
+                .map(|c| match c {
+                    FilterNode::Binary { field, op, .. } => {
+                        assert_eq!(*op, FilterOp::Contains);
+                        *field
+                    }
+                    _ => panic!("expected binary"),
+                })
+                .collect();
+            assert_eq!(fields, vec![Field::Name, Field::Email]);
+        }
+    }
+
+    #[test]
+    fn rejects_search_without_searchable_fields() {
+        assert!(matches!(
+            search_to_filter_node::<Unsearchable>("alice"),
+            Err(Error::InvalidSearch(_))
+        ));
+    }
+}
@@ -382,4 +382,17 @@ mod tests {
             "unsupported $orderby field: unknown_field"
         );
     }
+
+    #[test]
+    fn test_validate_expand() {
+        let query = ODataQuery::new().with_expand(vec!["Addresses".to_owned()]);
+        assert!(query.expands("addresses"));
+        assert!(!query.expands("orders"));
+        assert!(query.validate_expand(&["addresses", "city"]).is_ok());
+        assert!(matches!(
+            query.validate_expand(&["city"]),
+            Err(Error::InvalidExpand(nav)) if nav == "Addresses"
+        ));
+        assert!(ODataQuery::new().validate_expand(&[]).is_ok());
+    }
 }
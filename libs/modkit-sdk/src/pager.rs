@@ -378,6 +378,8 @@ mod tests {
                 next_cursor: Some(encoded_cursor.clone()),
                 prev_cursor: None,
                 limit: 2,
+                total_count: None,
+                total_count_capped: false,
             },
         );
 
@@ -396,6 +398,8 @@ mod tests {
                 next_cursor: None,
                 prev_cursor: Some(encoded_cursor),
                 limit: 2,
+                total_count: None,
+                total_count_capped: false,
             },
         );
 
@@ -427,6 +431,8 @@ mod tests {
                 next_cursor: None,
                 prev_cursor: None,
                 limit: 10,
+                total_count: None,
+                total_count_capped: false,
             },
         );
 
@@ -465,6 +471,8 @@ mod tests {
                 next_cursor: Some(encoded_cursor),
                 prev_cursor: None,
                 limit: 1,
+                total_count: None,
+                total_count_capped: false,
             },
         );
 
@@ -518,6 +526,8 @@ mod tests {
                 next_cursor: Some(encoded_cursor.clone()),
                 prev_cursor: None,
                 limit: 2,
+                total_count: None,
+                total_count_capped: false,
             },
         );
 
@@ -530,6 +540,8 @@ mod tests {
                 next_cursor: None,
                 prev_cursor: Some(encoded_cursor),
                 limit: 2,
+                total_count: None,
+                total_count_capped: false,
             },
         );
 
@@ -564,6 +576,8 @@ mod tests {
                 next_cursor: None,
                 prev_cursor: None,
                 limit: 10,
+                total_count: None,
+                total_count_capped: false,
             },
         );
 
@@ -592,6 +606,8 @@ mod tests {
                 next_cursor: Some("invalid_cursor_string".to_owned()),
                 prev_cursor: None,
                 limit: 1,
+                total_count: None,
+                total_count_capped: false,
             },
         );
 
@@ -628,6 +644,8 @@ mod tests {
                 next_cursor: Some("invalid_cursor_string".to_owned()),
                 prev_cursor: None,
                 limit: 1,
+                total_count: None,
+                total_count_capped: false,
             },
         );
 
@@ -675,6 +693,8 @@ mod tests {
                 next_cursor: Some(encoded_cursor),
                 prev_cursor: None,
                 limit: 1,
+                total_count: None,
+                total_count_capped: false,
             },
         );
 
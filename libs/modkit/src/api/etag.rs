@@ -0,0 +1,228 @@
+//! Entity tags (RFC 9110 §8.8.3) and conditional request evaluation
+//!
+//! Handlers compute a strong [`ETag`] either from the serialized response DTO
+//! ([`ETag::from_json`]) or from an entity version column ([`ETag::from_version`]),
+//! and then evaluate `If-None-Match` (cache validation on reads) or `If-Match`
+//! (optimistic concurrency on writes) against it.
+//!
+//! See [`crate::api::response`] for response helpers built on top of this module.
+
+use std::fmt::{self, Write as _};
+
+use http::{HeaderMap, HeaderValue, header};
+use sha2::{Digest, Sha256};
+
+use crate::api::problem;
+use crate::result::ApiResult;
+
+/// Strong entity tag.
+///
+/// Stores the opaque tag without the surrounding double quotes; the quoted form
+/// is produced by [`ETag::header_value`] and [`fmt::Display`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ETag(String);
+
+impl ETag {
+    /// Build a strong tag from a hash of arbitrary bytes.
+    #[must_use]
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        let digest = Sha256::digest(bytes);
+        // 128 bits are plenty for cache validation and keep the header short
+        Self(hex::encode(&digest[..16]))
+    }
+
+    /// Build a strong tag from the JSON serialization of `value`.
+    ///
+    /// The value must serialize deterministically (e.g. no `HashMap` fields)
+    /// for the tag to be stable across requests.
+    ///
+    /// # Errors
+    /// Returns an error if `value` cannot be serialized to JSON.
+    pub fn from_json<T: serde::Serialize + ?Sized>(value: &T) -> Result<Self, serde_json::Error> {
+        let bytes = serde_json::to_vec(value)?;
+        Ok(Self::from_bytes(&bytes))
+    }
+
+    /// Build a strong tag from an entity version (revision counter, `updated_at`, ...).
+    ///
+    /// Bytes that may not appear in a tag, plus `,` (the list separator of
+    /// `If-Match`) and `%` itself, are percent-encoded, so distinct versions
+    /// always give distinct tags.
+    pub fn from_version(version: impl fmt::Display) -> Self {
+        let mut tag = String::new();
+        for byte in version.to_string().bytes() {
+            let c = char::from(byte);
+            if is_etagc(c) && c != ',' && c != '%' {
+                tag.push(c);
+            } else {
+                let _ = write!(tag, "%{byte:02X}");
+            }
+        }
+        Self(tag)
+    }
+
+    /// The opaque tag without quotes.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Quoted header value suitable for the `ETag` response header.
+    #[must_use]
+    pub fn header_value(&self) -> HeaderValue {
+        // Construction guarantees the tag only contains visible ASCII characters
+        HeaderValue::from_str(&self.to_string())
+            .unwrap_or_else(|_| HeaderValue::from_static("\"\""))
+    }
+
+    /// Whether the request's `If-None-Match` header matches this tag.
+    ///
+    /// Uses the weak comparison function, as required for `If-None-Match`.
+    /// Returns `false` when the header is absent.
+    #[must_use]
+    pub fn matches_if_none_match(&self, headers: &HeaderMap) -> bool {
+        header_matches(headers, &header::IF_NONE_MATCH, |tag| {
+            tag.trim_start_matches("W/") == self.quoted()
+        })
+    }
+
+    /// Whether the request's `If-Match` header matches this tag.
+    ///
+    /// Uses the strong comparison function, so weak tags never match.
+    /// Returns `false` when the header is absent.
+    #[must_use]
+    pub fn matches_if_match(&self, headers: &HeaderMap) -> bool {
+        header_matches(headers, &header::IF_MATCH, |tag| tag == self.quoted())
+    }
+
+    fn quoted(&self) -> String {
+        format!("\"{}\"", self.0)
+    }
+}
+
+impl fmt::Display for ETag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\"{}\"", self.0)
+    }
+}
+
+/// Evaluate `If-Match` for a state-changing request against the current representation.
+///
+/// Succeeds when the header is absent (unconditional request) or matches `current`.
+///
+/// # Errors
+/// Returns a `412 Precondition Failed` problem when `If-Match` is present and does not match.
+#[allow(clippy::result_large_err)] // Problem is the handler error type by design
+pub fn check_if_match(headers: &HeaderMap, current: &ETag) -> ApiResult {
+    if !headers.contains_key(header::IF_MATCH) || current.matches_if_match(headers) {
+        Ok(())
+    } else {
+        Err(problem::precondition_failed(
+            "The resource has been modified; refresh it and retry with the current ETag",
+        ))
+    }
+}
+
+/// Evaluate `If-None-Match` for a safe request.
+///
+/// Returns `true` when the client's cached representation is still current and
+/// the handler should answer `304 Not Modified`.
+#[must_use]
+pub fn is_not_modified(headers: &HeaderMap, current: &ETag) -> bool {
+    current.matches_if_none_match(headers)
+}
+
+/// `etagc` from RFC 9110: `%x21 / %x23-7E / obs-text` (we only admit ASCII).
+fn is_etagc(c: char) -> bool {
+    c == '\x21' || ('\x23'..='\x7e').contains(&c)
+}
+
+fn header_matches(
+    headers: &HeaderMap,
+    name: &header::HeaderName,
+    mut matches: impl FnMut(&str) -> bool,
+) -> bool {
+    headers
+        .get_all(name)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .map(str::trim)
+        .any(|tag| tag == "*" || matches(tag))
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    fn headers(name: header::HeaderName, value: &str) -> HeaderMap {
+        let mut h = HeaderMap::new();
+        h.insert(name, HeaderValue::from_str(value).unwrap());
+        h
+    }
+
+    #[test]
+    fn from_json_is_stable_and_content_sensitive() {
+        let a = ETag::from_json(&serde_json::json!({"theme": "dark"})).unwrap();
+        let b = ETag::from_json(&serde_json::json!({"theme": "dark"})).unwrap();
+        let c = ETag::from_json(&serde_json::json!({"theme": "light"})).unwrap();
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_eq!(a.as_str().len(), 32);
+    }
+
+    #[test]
+    fn from_version_encodes_and_quotes() {
+        let tag = ETag::from_version("2024-01-01 10:00:00");
+        assert_eq!(tag.as_str(), "2024-01-01%2010:00:00");
+        assert_eq!(tag.to_string(), "\"2024-01-01%2010:00:00\"");
+        assert_eq!(tag.header_value(), "\"2024-01-01%2010:00:00\"");
+        assert_eq!(ETag::from_version(7).to_string(), "\"7\"");
+        assert_eq!(ETag::from_version("a,b\"\u{e9}").as_str(), "a%2Cb%22%C3%A9");
+    }
+
+    #[test]
+    fn from_version_does_not_collide() {
+        let versions = ["a b", "a-b", "a%20b", "a,b", "a\"b"];
+        let tags: std::collections::HashSet<_> = versions.iter().map(ETag::from_version).collect();
+        assert_eq!(tags.len(), versions.len());
+    }
+
+    #[test]
+    fn version_with_comma_matches_only_itself() {
+        let tag = ETag::from_version("1,2");
+        let value = tag.to_string();
+        assert!(tag.matches_if_match(&headers(header::IF_MATCH, &value)));
+        assert!(!ETag::from_version(1).matches_if_match(&headers(header::IF_MATCH, "\"1,2\"")));
+    }
+
+    #[test]
+    fn if_none_match_uses_weak_comparison() {
+        let tag = ETag::from_version(3);
+        assert!(tag.matches_if_none_match(&headers(header::IF_NONE_MATCH, "\"3\"")));
+        assert!(tag.matches_if_none_match(&headers(header::IF_NONE_MATCH, "W/\"3\"")));
+        assert!(tag.matches_if_none_match(&headers(header::IF_NONE_MATCH, "\"1\", \"3\"")));
+        assert!(tag.matches_if_none_match(&headers(header::IF_NONE_MATCH, "*")));
+        assert!(!tag.matches_if_none_match(&headers(header::IF_NONE_MATCH, "\"4\"")));
+        assert!(!tag.matches_if_none_match(&HeaderMap::new()));
+    }
+
+    #[test]
+    fn if_match_uses_strong_comparison() {
+        let tag = ETag::from_version(3);
+        assert!(tag.matches_if_match(&headers(header::IF_MATCH, "\"3\"")));
+        assert!(tag.matches_if_match(&headers(header::IF_MATCH, "*")));
+        assert!(!tag.matches_if_match(&headers(header::IF_MATCH, "W/\"3\"")));
+    }
+
+    #[test]
+    fn check_if_match_allows_unconditional_and_rejects_stale() {
+        let tag = ETag::from_version(3);
+        assert!(check_if_match(&HeaderMap::new(), &tag).is_ok());
+        assert!(check_if_match(&headers(header::IF_MATCH, "\"3\""), &tag).is_ok());
+
+        let err = check_if_match(&headers(header::IF_MATCH, "\"2\""), &tag).unwrap_err();
+        assert_eq!(err.status, http::StatusCode::PRECONDITION_FAILED);
+    }
+}
@@ -6,6 +6,7 @@
 
 pub mod api_dto;
 pub mod error_layer;
+pub mod etag;
 pub mod odata;
 pub mod openapi_registry;
 pub mod operation_builder;
@@ -21,14 +22,15 @@ mod odata_policy_tests;
 pub use error_layer::{
     IntoProblem, error_mapping_middleware, extract_trace_id, map_error_to_problem,
 };
+pub use etag::{ETag, check_if_match, is_not_modified};
 pub use openapi_registry::{OpenApiInfo, OpenApiRegistry, OpenApiRegistryImpl, ensure_schema};
 pub use operation_builder::{
-    Missing, OperationBuilder, OperationSpec, ParamLocation, ParamSpec, Present, RateLimitSpec,
-    ResponseSpec, state,
+    ConditionalSpec, Missing, OperationBuilder, OperationSpec, ParamLocation, ParamSpec, Present,
+    RateLimitSpec, ResponseSpec, state,
 };
 pub use problem::{
     APPLICATION_PROBLEM_JSON, Problem, ValidationError, bad_request, conflict, internal_error,
-    not_found,
+    not_found, precondition_failed,
 };
 pub use select::{apply_select, page_to_projected_json, project_json};
 pub use trace_layer::{WithRequestContext, WithTraceContext};
@@ -42,7 +44,13 @@ pub mod prelude {
     pub use super::problem::Problem;
 
     // Response sugar
-    pub use super::response::{JsonBody, JsonPage, created_json, no_content, ok_json};
+    pub use super::response::{
+        JsonBody, JsonPage, created_json, no_content, not_modified, ok_json, ok_json_tagged,
+        ok_json_with_etag,
+    };
+
+    // Conditional requests
+    pub use super::etag::{ETag, check_if_match};
 
     // OData and field projection
     pub use super::select::apply_select;
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use axum::extract::{FromRequestParts, Query};
 use axum::http::request::Parts;
 use modkit_odata::{CursorV1, Error as ODataError, ODataOrderBy, OrderKey, SortDir};
@@ -19,16 +21,35 @@ pub struct ODataParams {
     pub orderby: Option<String>,
     #[serde(rename = "$select")]
     pub select: Option<String>,
+    #[serde(rename = "$count")]
+    pub count: Option<String>,
+    #[serde(rename = "$search")]
+    pub search: Option<String>,
+    #[serde(rename = "$expand")]
+    pub expand: Option<String>,
+    #[serde(rename = "$apply")]
+    pub apply: Option<String>,
     pub limit: Option<u64>,
     pub cursor: Option<String>,
 }
 
+/// Navigation properties an operation accepts in `$expand` (lowercase).
+///
+/// `OperationBuilder::register` adds it to the request extensions of operations
+/// declared with `with_odata_expand`; without it, `$expand` is rejected.
+#[derive(Clone, Debug)]
+pub struct ODataExpandable(pub Arc<[String]>);
+
 pub const MAX_FILTER_LEN: usize = 8 * 1024;
 pub const MAX_NODES: usize = 2000;
 pub const MAX_ORDERBY_LEN: usize = 1024;
 pub const MAX_ORDER_FIELDS: usize = 10;
 pub const MAX_SELECT_LEN: usize = 2048;
 pub const MAX_SELECT_FIELDS: usize = 100;
+pub const MAX_SEARCH_LEN: usize = 256;
+pub const MAX_EXPAND_LEN: usize = 512;
+pub const MAX_EXPAND_FIELDS: usize = 8;
+pub const MAX_APPLY_LEN: usize = 4 * 1024;
 
 /// Parse $select string into a list of field names.
 /// Format: "field1, field2, field3, ..."
@@ -76,6 +97,67 @@ pub fn parse_select(raw: &str) -> Result<Vec<String>, crate::api::problem::Probl
     Ok(fields)
 }
 
+/// Parse $count value (`true` or `false`, case-insensitive).
+///
+/// # Errors
+/// Returns a `Problem` if the value is not a boolean literal.
+#[allow(clippy::result_large_err)] // Problem is the handler error type by design
+pub fn parse_count(raw: &str) -> Result<bool, crate::api::problem::Problem> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "true" => Ok(true),
+        "false" => Ok(false),
+        _ => Err(crate::api::bad_request("$count must be true or false")),
+    }
+}
+
+/// Parse $expand string into a list of navigation property names.
+/// Format: "nav1, nav2, ..."
+/// Only one level is supported: nested paths (`a/b`) and expand options (`a($select=...)`)
+/// are rejected. Names are case-insensitive and whitespace is trimmed.
+///
+/// # Errors
+/// Returns `modkit_odata::Error::InvalidExpand` if the expand string is invalid.
+pub fn parse_expand(raw: &str) -> Result<Vec<String>, modkit_odata::Error> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return Err(ODataError::InvalidExpand("$expand cannot be empty".into()));
+    }
+
+    if raw.len() > MAX_EXPAND_LEN {
+        return Err(ODataError::InvalidExpand("$expand too long".into()));
+    }
+
+    let mut navs: Vec<String> = Vec::new();
+    for nav in raw.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+        if nav.contains(['/', '(', ')', '*']) {
+            return Err(ODataError::InvalidExpand(format!(
+                "only one-level navigation properties are supported: {nav}"
+            )));
+        }
+        let nav = nav.to_lowercase();
+        if navs.contains(&nav) {
+            return Err(ODataError::InvalidExpand(format!(
+                "duplicate navigation property: {nav}"
+            )));
+        }
+        navs.push(nav);
+    }
+
+    if navs.is_empty() {
+        return Err(ODataError::InvalidExpand(
+            "$expand must contain at least one navigation property".into(),
+        ));
+    }
+
+    if navs.len() > MAX_EXPAND_FIELDS {
+        return Err(ODataError::InvalidExpand(
+            "too many navigation properties".into(),
+        ));
+    }
+
+    Ok(navs)
+}
+
 /// Parse $orderby string into `ODataOrderBy`.
 /// Format: "field1 [asc|desc], field2 [asc|desc], ..."
 /// Default direction is asc if not specified.
@@ -135,7 +217,7 @@ pub fn parse_orderby(raw: &str) -> Result<ODataOrderBy, modkit_odata::Error> {
 }
 
 /// Extract and validate full `OData` query from request parts.
-/// - Parses $filter, $orderby, limit, cursor
+/// - Parses $filter, $orderby, $select, $count, $search, $expand, $apply, limit, cursor
 /// - Enforces budgets and validates formats
 /// - Returns unified `ODataQuery`
 ///
@@ -182,17 +264,26 @@ where
                 return Err(crate::api::bad_request("Filter too complex"));
             }
 
-            // Generate filter hash for cursor consistency (use non-consuming accessor)
-            let filter_hash = modkit_odata::pagination::short_filter_hash(Some(parsed.as_expr()));
-
             // Extract expression for query
-            let core_expr = parsed.into_expr();
+            query = query.with_filter(parsed.into_expr());
+        }
+    }
 
-            query = query.with_filter(core_expr);
-            if let Some(hash) = filter_hash {
-                query = query.with_filter_hash(hash);
-            }
+    // Parse search
+    if let Some(raw_search) = params.search.as_ref() {
+        let raw = raw_search.trim();
+        if raw.len() > MAX_SEARCH_LEN {
+            return Err(crate::api::bad_request("$search too long"));
         }
+        // Validate syntax early; the typed translation happens in the storage layer
+        modkit_odata::search::parse_search_terms(raw)
+            .map_err(|e| crate::api::odata::odata_error_to_problem(&e, parts.uri.path(), None))?;
+        query = query.with_search(raw);
+    }
+
+    // Generate filter hash for cursor consistency; covers both $filter and $search
+    if let Some(hash) = modkit_odata::pagination::short_query_hash(query.filter(), query.search()) {
+        query = query.with_filter_hash(hash);
     }
 
     // Check for cursor+orderby conflict before parsing either
@@ -237,13 +328,43 @@ where
         query = query.with_select(fields);
     }
 
+    // Parse count
+    if let Some(raw_count) = params.count.as_ref() {
+        query = query.with_count(parse_count(raw_count)?);
+    }
+
+    // Parse expand; only navigations declared by the operation are accepted
+    if let Some(raw_expand) = params.expand.as_ref() {
+        let navs = parse_expand(raw_expand)
+            .map_err(|e| crate::api::odata::odata_error_to_problem(&e, parts.uri.path(), None))?;
+        query = query.with_expand(navs);
+        let allowed: Vec<&str> = parts
+            .extensions
+            .get::<ODataExpandable>()
+            .map(|navs| navs.0.iter().map(String::as_str).collect())
+            .unwrap_or_default();
+        query
+            .validate_expand(&allowed)
+            .map_err(|e| crate::api::odata::odata_error_to_problem(&e, parts.uri.path(), None))?;
+    }
+
+    // Parse apply; fields are validated against the resource in the storage layer
+    if let Some(raw_apply) = params.apply.as_ref() {
+        if raw_apply.len() > MAX_APPLY_LEN {
+            return Err(crate::api::bad_request("$apply too long"));
+        }
+        let apply = modkit_odata::apply::parse_apply(raw_apply)
+            .map_err(|e| crate::api::odata::odata_error_to_problem(&e, parts.uri.path(), None))?;
+        query = query.with_apply(apply);
+    }
+
     Ok(query)
 }
 
 use std::ops::Deref;
 
 /// Simple Axum extractor for full `OData` query parameters.
-/// Parses $filter, $orderby, limit, and cursor parameters.
+/// Parses $filter, $orderby, $select, $count, $search, $expand, $apply, limit, and cursor parameters.
 /// Usage in handlers:
 ///   async fn `list_users(OData(query)`: `OData`, /* ... */) { /* use `query` */ }
 #[derive(Debug, Clone)]
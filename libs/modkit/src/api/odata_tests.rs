@@ -227,6 +227,102 @@ mod tests {
         let _problem_response = result.unwrap_err();
     }
 
+    #[tokio::test]
+    async fn test_extract_odata_query_count_search_expand() {
+        let uri =
+            "/?%24count=true&%24search=alice%20%22new%20york%22&%24expand=Addresses%2C%20city";
+
+        let request = Request::builder().uri(uri).body(()).unwrap();
+
+        let (mut parts, _body) = request.into_parts();
+        parts.extensions.insert(ODataExpandable(
+            vec!["addresses".to_owned(), "city".to_owned()].into(),
+        ));
+
+        let query = extract_odata_query(&mut parts, &()).await.unwrap();
+
+        assert!(query.count);
+        assert_eq!(query.search(), Some("alice \"new york\""));
+        assert_eq!(
+            query.expand.as_deref(),
+            Some(&["addresses".to_owned(), "city".to_owned()][..])
+        );
+        // Search participates in the cursor consistency hash
+        assert!(query.filter_hash.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_extract_odata_query_invalid_count_search_expand() {
+        for uri in [
+            "/?%24count=yes",
+            "/?%24search=%22unbalanced",
+            "/?%24search=a%20OR%20b",
+            "/?%24expand=addresses%2Fcity",
+            "/?%24expand=addresses(%24select%3Did)",
+            "/?%24expand=a%2Ca",
+        ] {
+            let request = Request::builder().uri(uri).body(()).unwrap();
+            let (mut parts, _body) = request.into_parts();
+            assert!(
+                extract_odata_query(&mut parts, &()).await.is_err(),
+                "{uri} should be rejected"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_extract_odata_query_rejects_undeclared_expand() {
+        // The operation does not declare any expandable navigation
+        let request = Request::builder()
+            .uri("/?%24expand=addresses")
+            .body(())
+            .unwrap();
+        let (mut parts, _body) = request.into_parts();
+        assert!(extract_odata_query(&mut parts, &()).await.is_err());
+
+        // The operation declares other navigations only
+        let request = Request::builder()
+            .uri("/?%24expand=addresses%2Croles")
+            .body(())
+            .unwrap();
+        let (mut parts, _body) = request.into_parts();
+        parts
+            .extensions
+            .insert(ODataExpandable(vec!["addresses".to_owned()].into()));
+        let problem = extract_odata_query(&mut parts, &()).await.unwrap_err();
+        assert_eq!(problem.status, http::StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(problem.detail.contains("roles"), "{}", problem.detail);
+    }
+
+    #[tokio::test]
+    async fn test_extract_odata_query_apply() {
+        let uri = "/?%24apply=groupby((status)%2Caggregate(amount%20with%20sum%20as%20total%2C%24count%20as%20n))";
+        let request = Request::builder().uri(uri).body(()).unwrap();
+        let (mut parts, _body) = request.into_parts();
+
+        let query = extract_odata_query(&mut parts, &()).await.unwrap();
+        let apply = query.apply().expect("apply");
+        assert_eq!(apply.group_by[0].field, "status");
+        assert_eq!(apply.aggregates.len(), 2);
+
+        let uri = "/?%24apply=topcount(2%2Camount)";
+        let request = Request::builder().uri(uri).body(()).unwrap();
+        let (mut parts, _body) = request.into_parts();
+        assert!(extract_odata_query(&mut parts, &()).await.is_err());
+    }
+
+    #[test]
+    fn test_parse_expand_limits() {
+        assert!(parse_expand("").is_err());
+        assert!(parse_expand(" , ").is_err());
+        let many = (0..=MAX_EXPAND_FIELDS)
+            .map(|i| format!("nav{i}"))
+            .collect::<Vec<_>>()
+            .join(",");
+        assert!(parse_expand(&many).is_err());
+        assert_eq!(parse_expand(" a , b ").unwrap(), vec!["a", "b"]);
+    }
+
     #[tokio::test]
     async fn test_odata_extractor() {
         let uri = "/?%24filter=email%20eq%20%27test%40example.com%27&limit=10";
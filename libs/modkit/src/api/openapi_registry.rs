@@ -11,6 +11,7 @@ use std::sync::Arc;
 use utoipa::openapi::{
     OpenApi, OpenApiBuilder, Ref, RefOr, Required,
     content::ContentBuilder,
+    header::{Header, HeaderBuilder},
     info::InfoBuilder,
     path::{
         HttpMethod, OperationBuilder as UOperationBuilder, ParameterBuilder, ParameterIn,
@@ -137,16 +138,13 @@ impl OpenApiRegistryImpl {
                 );
             }
 
-            // Pagination
-            if let Some(pagination) = spec.vendor_extensions.x_odata_filter.as_ref()
-                && let Ok(value) = serde_json::to_value(pagination)
+            // OData vendor extensions (x-odata-*), keyed as `VendorExtensions` serializes them
+            if let Ok(serde_json::Value::Object(odata)) =
+                serde_json::to_value(&spec.vendor_extensions)
             {
-                ext.insert("x-odata-filter".to_owned(), value);
-            }
-            if let Some(pagination) = spec.vendor_extensions.x_odata_orderby.as_ref()
-                && let Ok(value) = serde_json::to_value(pagination)
-            {
-                ext.insert("x-odata-orderby".to_owned(), value);
+                for (key, value) in odata {
+                    ext.insert(key, value);
+                }
             }
 
             if !ext.is_empty() {
@@ -251,7 +249,7 @@ impl OpenApiRegistryImpl {
                 let is_json_like = r.content_type == "application/json"
                     || r.content_type == problem::APPLICATION_PROBLEM_JSON
                     || r.content_type == "text/event-stream";
-                let resp = if is_json_like {
+                let mut resp = if is_json_like {
                     if let Some(name) = &r.schema_name {
                         // Manually build content to preserve the correct content type
                         let content = ContentBuilder::new()
@@ -285,8 +283,14 @@ impl OpenApiRegistryImpl {
                         .content(r.content_type, content)
                         .build()
                 };
+                if spec.conditional.has_etag() && (200..300).contains(&r.status) {
+                    resp.headers.insert("ETag".to_owned(), etag_header());
+                }
                 responses = responses.response(r.status.to_string(), resp);
             }
+            if spec.conditional.if_none_match {
+                responses = responses.response("304", not_modified_response());
+            }
             op = op.responses(responses.build());
 
             // Add security requirement if operation has explicit auth metadata
@@ -396,12 +400,31 @@ impl OpenApiRegistry for OpenApiRegistryImpl {
     }
 }
 
+/// Body-less `304 Not Modified` response for operations honouring `If-None-Match`
+fn not_modified_response() -> utoipa::openapi::response::Response {
+    let mut resp = ResponseBuilder::new().description("Not Modified").build();
+    resp.headers.insert("ETag".to_owned(), etag_header());
+    resp
+}
+
+/// `ETag` response header documentation shared by conditional operations
+fn etag_header() -> Header {
+    HeaderBuilder::new()
+        .schema(Schema::Object(
+            ObjectBuilder::new()
+                .schema_type(SchemaType::Type(utoipa::openapi::schema::Type::String))
+                .build(),
+        ))
+        .description(Some("Strong entity tag of the returned representation"))
+        .build()
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
     use super::*;
     use crate::api::operation_builder::{
-        OperationSpec, ParamLocation, ParamSpec, ResponseSpec, VendorExtensions,
+        ConditionalSpec, OperationSpec, ParamLocation, ParamSpec, ResponseSpec, VendorExtensions,
     };
     use http::Method;
 
@@ -437,6 +460,8 @@ mod tests {
             allowed_request_content_types: None,
             vendor_extensions: VendorExtensions::default(),
             license_requirement: None,
+            conditional: ConditionalSpec::default(),
+            skip_response_compression: false,
         };
 
         registry.register_operation(&spec);
@@ -500,6 +525,8 @@ mod tests {
             allowed_request_content_types: None,
             vendor_extensions: VendorExtensions::default(),
             license_requirement: None,
+            conditional: ConditionalSpec::default(),
+            skip_response_compression: false,
         };
 
         registry.register_operation(&spec);
@@ -560,6 +587,8 @@ mod tests {
             allowed_request_content_types: Some(vec!["application/octet-stream"]),
             vendor_extensions: VendorExtensions::default(),
             license_requirement: None,
+            conditional: ConditionalSpec::default(),
+            skip_response_compression: false,
         };
 
         registry.register_operation(&spec);
@@ -639,6 +668,8 @@ mod tests {
             allowed_request_content_types: None,
             vendor_extensions: VendorExtensions::default(),
             license_requirement: None,
+            conditional: ConditionalSpec::default(),
+            skip_response_compression: false,
         };
         spec.vendor_extensions.x_odata_filter = Some(filter);
         spec.vendor_extensions.x_odata_orderby = Some(order_by);
@@ -667,4 +698,55 @@ mod tests {
         assert!(allowed_order.iter().any(|v| v.as_str() == Some("name asc")));
         assert!(allowed_order.iter().any(|v| v.as_str() == Some("age desc")));
     }
+
+    #[test]
+    fn test_build_openapi_with_conditional_requests() {
+        let registry = OpenApiRegistryImpl::new();
+
+        let spec = OperationSpec {
+            method: Method::GET,
+            path: "/settings".to_owned(),
+            operation_id: Some("get_settings".to_owned()),
+            summary: None,
+            description: None,
+            tags: vec![],
+            params: vec![ParamSpec {
+                name: "If-None-Match".to_owned(),
+                location: ParamLocation::Header,
+                required: false,
+                description: None,
+                param_type: "string".to_owned(),
+            }],
+            request_body: None,
+            responses: vec![ResponseSpec {
+                status: 200,
+                content_type: "application/json",
+                description: "OK".to_owned(),
+                schema_name: None,
+            }],
+            handler_id: "get_settings".to_owned(),
+            sec_requirement: None,
+            is_public: false,
+            rate_limit: None,
+            allowed_request_content_types: None,
+            vendor_extensions: VendorExtensions::default(),
+            license_requirement: None,
+            conditional: ConditionalSpec {
+                if_none_match: true,
+                if_match: false,
+            },
+            skip_response_compression: false,
+        };
+
+        registry.register_operation(&spec);
+        let doc = registry.build_openapi(&OpenApiInfo::default()).unwrap();
+        let json = serde_json::to_value(&doc).unwrap();
+        let op = &json["paths"]["/settings"]["get"];
+
+        assert_eq!(op["parameters"][0]["in"], "header");
+        assert_eq!(op["parameters"][0]["name"], "If-None-Match");
+        assert!(op["responses"]["200"]["headers"]["ETag"].is_object());
+        assert!(op["responses"]["304"]["headers"]["ETag"].is_object());
+        assert!(op["responses"]["304"].get("content").is_none());
+    }
 }
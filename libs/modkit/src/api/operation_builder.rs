@@ -231,6 +231,31 @@ pub struct OperationSpec {
     /// `OpenAPI` vendor extensions (x-*)
     pub vendor_extensions: VendorExtensions,
     pub license_requirement: Option<LicenseReqSpec>,
+    /// Conditional request (`ETag`) support declared for this operation
+    pub conditional: ConditionalSpec,
+    /// Opt this operation out of gateway response compression
+    /// (already-compressed payloads, streaming/SSE responses)
+    pub skip_response_compression: bool,
+}
+
+/// Conditional request support for an operation.
+///
+/// Drives `OpenAPI` generation: 2xx responses document the `ETag` header,
+/// `If-None-Match` adds a `304 Not Modified` response and `If-Match` a `412`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConditionalSpec {
+    /// Operation honours `If-None-Match` and may answer `304 Not Modified`
+    pub if_none_match: bool,
+    /// Operation honours `If-Match` and answers `412 Precondition Failed` on mismatch
+    pub if_match: bool,
+}
+
+impl ConditionalSpec {
+    /// Whether successful responses carry an `ETag` header
+    #[must_use]
+    pub fn has_etag(&self) -> bool {
+        self.if_none_match || self.if_match
+    }
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -239,6 +264,9 @@ pub struct VendorExtensions {
     pub x_odata_filter: Option<ODataPagination<BTreeMap<String, Vec<String>>>>,
     #[serde(rename = "x-odata-orderby", skip_serializing_if = "Option::is_none")]
     pub x_odata_orderby: Option<ODataPagination<Vec<String>>>,
+    /// Navigation properties accepted in `$expand`; without it `$expand` is rejected
+    #[serde(rename = "x-odata-expand", skip_serializing_if = "Option::is_none")]
+    pub x_odata_expand: Option<Vec<String>>,
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -282,6 +310,30 @@ pub trait OperationBuilderODataExt<S, H, R> {
     fn with_odata_orderby<T>(self) -> Self
     where
         T: modkit_odata::filter::FilterField;
+
+    /// Adds optional `$count` query parameter to `OpenAPI`.
+    #[must_use]
+    fn with_odata_count(self) -> Self;
+
+    /// Adds optional `$search` query parameter to `OpenAPI`, listing the searchable fields of `T`.
+    #[must_use]
+    fn with_odata_search<T>(self) -> Self
+    where
+        T: modkit_odata::filter::FilterField;
+
+    /// Adds optional `$expand` query parameter to `OpenAPI` for the given navigation properties.
+    ///
+    /// `$expand` is rejected on operations that do not declare it, and requests naming
+    /// other navigation properties are rejected by the `OData` extractor.
+    #[must_use]
+    fn with_odata_expand(self, navs: &[&str]) -> Self;
+
+    /// Adds optional `$apply` query parameter to `OpenAPI`, listing the groupable and
+    /// aggregatable fields of `T`.
+    #[must_use]
+    fn with_odata_apply<T>(self) -> Self
+    where
+        T: modkit_odata::filter::FilterField;
 }
 
 impl<S, H, R, A, L> OperationBuilderODataExt<S, H, R> for OperationBuilder<H, R, S, A, L>
@@ -386,6 +438,112 @@ where
         self.spec.vendor_extensions.x_odata_orderby = Some(order_by);
         self
     }
+
+    fn with_odata_count(mut self) -> Self {
+        self.spec.params.push(ParamSpec {
+            name: "$count".to_owned(),
+            location: ParamLocation::Query,
+            required: false,
+            description: Some(
+                "Include the total number of matching items in page_info.total_count (true|false)"
+                    .to_owned(),
+            ),
+            param_type: "boolean".to_owned(),
+        });
+        self
+    }
+
+    fn with_odata_search<T>(mut self) -> Self
+    where
+        T: modkit_odata::filter::FilterField,
+    {
+        let fields: Vec<&str> = T::FIELDS
+            .iter()
+            .filter(|f| f.searchable())
+            .map(modkit_odata::filter::FilterField::name)
+            .collect();
+        self.spec.params.push(ParamSpec {
+            name: "$search".to_owned(),
+            location: ParamLocation::Query,
+            required: false,
+            description: Some(format!(
+                "OData v4 free-text search; all terms must match one of: {}",
+                fields.join(", ")
+            )),
+            param_type: "string".to_owned(),
+        });
+        self
+    }
+
+    fn with_odata_expand(mut self, navs: &[&str]) -> Self {
+        let expand = self
+            .spec
+            .vendor_extensions
+            .x_odata_expand
+            .get_or_insert_with(Vec::new);
+        for nav in navs {
+            let nav = nav.to_lowercase();
+            if !expand.contains(&nav) {
+                expand.push(nav);
+            }
+        }
+        self.spec.params.push(ParamSpec {
+            name: "$expand".to_owned(),
+            location: ParamLocation::Query,
+            required: false,
+            description: Some(format!(
+                "OData v4 expand (one level); supported: {}",
+                navs.join(", ")
+            )),
+            param_type: "string".to_owned(),
+        });
+        self
+    }
+
+    fn with_odata_apply<T>(mut self) -> Self
+    where
+        T: modkit_odata::filter::FilterField,
+    {
+        use modkit_odata::filter::FilterCaps;
+
+        let aggregations = [
+            (FilterCaps::SUM, "sum"),
+            (FilterCaps::AVERAGE, "average"),
+            (FilterCaps::MIN, "min"),
+            (FilterCaps::MAX, "max"),
+            (FilterCaps::COUNTDISTINCT, "countdistinct"),
+        ];
+        let groupable: Vec<&str> = T::FIELDS
+            .iter()
+            .filter(|f| f.caps().contains(FilterCaps::GROUPBY))
+            .map(modkit_odata::filter::FilterField::name)
+            .collect();
+        let aggregatable: Vec<String> = T::FIELDS
+            .iter()
+            .filter_map(|f| {
+                let methods: Vec<&str> = aggregations
+                    .iter()
+                    .filter(|(cap, _)| f.caps().contains(*cap))
+                    .map(|(_, name)| *name)
+                    .collect();
+                (!methods.is_empty()).then(|| format!("{} ({})", f.name(), methods.join("|")))
+            })
+            .collect();
+        self.spec.params.push(ParamSpec {
+            name: "$apply".to_owned(),
+            location: ParamLocation::Query,
+            required: false,
+            description: Some(format!(
+                "OData v4 aggregation: [filter(...)/]groupby((fields)[,aggregate(...)]) or aggregate(...); \
+                 groupby: {}, date fields also as date|year|month|day(field) [as alias]; \
+                 aggregate: {}; $count as alias is always available",
+                groupable.join(", "),
+                aggregatable.join(", ")
+            )),
+            param_type: "string".to_owned(),
+        });
+        self
+    }
 }
 
 // Re-export from openapi_registry for backward compatibility
@@ -447,6 +605,8 @@ impl<S> OperationBuilder<Missing, Missing, S, AuthNotSet> {
                 allowed_request_content_types: None,
                 vendor_extensions: VendorExtensions::default(),
                 license_requirement: None,
+                conditional: ConditionalSpec::default(),
+                skip_response_compression: false,
             },
             method_router: (), // no router in Missing state
             _has_handler: PhantomData,
@@ -778,6 +938,15 @@ where
         self
     }
 
+    /// Opt this operation out of gateway response compression.
+    ///
+    /// Use for responses that are already compressed (archives, images, media)
+    /// or streamed incrementally (SSE), where compression would only add latency.
+    pub fn skip_response_compression(mut self) -> Self {
+        self.spec.skip_response_compression = true;
+        self
+    }
+
     /// Configure allowed request MIME types for this operation.
     ///
     /// This attaches a whitelist of allowed Content-Type values (without parameters),
@@ -1474,6 +1643,50 @@ where
         self
     }
 
+    /// Declare that this (safe) operation honours `If-None-Match`.
+    ///
+    /// Documents the `If-None-Match` request header, the `ETag` header on
+    /// successful responses and a `304 Not Modified` response. The handler is
+    /// expected to use `modkit::api::response::ok_json_with_etag` (or
+    /// `ok_json_tagged`) to produce these.
+    pub fn with_if_none_match(mut self) -> Self {
+        self.spec.params.push(ParamSpec {
+            name: "If-None-Match".to_owned(),
+            location: ParamLocation::Header,
+            required: false,
+            description: Some(
+                "Entity tag(s) of a cached representation; returns 304 if still current".to_owned(),
+            ),
+            param_type: "string".to_owned(),
+        });
+        self.spec.conditional.if_none_match = true;
+        self
+    }
+
+    /// Declare that this (state-changing) operation honours `If-Match`.
+    ///
+    /// Documents the `If-Match` request header, the `ETag` header on successful
+    /// responses and a `412 Precondition Failed` problem response. The handler is
+    /// expected to call `modkit::api::etag::check_if_match` before mutating.
+    pub fn with_if_match(mut self, registry: &dyn OpenApiRegistry) -> Self {
+        self.spec.params.push(ParamSpec {
+            name: "If-Match".to_owned(),
+            location: ParamLocation::Header,
+            required: false,
+            description: Some(
+                "Entity tag of the representation being modified; returns 412 on mismatch"
+                    .to_owned(),
+            ),
+            param_type: "string".to_owned(),
+        });
+        self.spec.conditional.if_match = true;
+        self.problem_response(
+            registry,
+            http::StatusCode::PRECONDITION_FAILED,
+            "Precondition Failed",
+        )
+    }
+
     /// Add a 400 Bad Request error response.
     ///
     /// This is a convenience wrapper around `problem_response`.
@@ -1575,7 +1788,14 @@ where
         openapi.register_operation(&self.spec);
 
         // In Present state the method_router is guaranteed to be a real MethodRouter<S>.
-        router.route(&self.spec.path, self.method_router)
+        let mut method_router = self.method_router;
+        // Tell the `OData` extractor which navigations this operation can expand
+        if let Some(navs) = self.spec.vendor_extensions.x_odata_expand.as_ref() {
+            method_router = method_router.layer(axum::Extension(
+                crate::api::odata::ODataExpandable(navs.as_slice().into()),
+            ));
+        }
+        router.route(&self.spec.path, method_router)
     }
 }
 
@@ -1883,6 +2103,58 @@ mod tests {
         assert_eq!(sec_requirement.action, "read");
     }
 
+    #[tokio::test]
+    async fn with_odata_expand_limits_expand_to_declared_navigations() {
+        use tower::ServiceExt as _;
+
+        async fn expanded(crate::api::odata::OData(query): crate::api::odata::OData) -> String {
+            query.expand.unwrap_or_default().join(",")
+        }
+
+        let registry = MockRegistry::new();
+        let router = OperationBuilder::<Missing, Missing, ()>::get("/tests/v1/users")
+            .public()
+            .handler(expanded)
+            .json_response(http::StatusCode::OK, "OK")
+            .with_odata_expand(&["Addresses"])
+            .register(Router::new(), &registry);
+        let router = OperationBuilder::<Missing, Missing, ()>::get("/tests/v1/cities")
+            .public()
+            .handler(expanded)
+            .json_response(http::StatusCode::OK, "OK")
+            .register(router, &registry);
+
+        let status = |uri: &'static str| {
+            let router = router.clone();
+            async move {
+                let request = http::Request::get(uri)
+                    .body(axum::body::Body::empty())
+                    .unwrap();
+                router.oneshot(request).await.unwrap().status()
+            }
+        };
+        assert_eq!(
+            status("/tests/v1/users?$expand=addresses").await,
+            http::StatusCode::OK
+        );
+        assert_eq!(
+            status("/tests/v1/users?$expand=roles").await,
+            http::StatusCode::UNPROCESSABLE_ENTITY
+        );
+        assert_eq!(
+            status("/tests/v1/cities?$expand=addresses").await,
+            http::StatusCode::UNPROCESSABLE_ENTITY
+        );
+        assert_eq!(status("/tests/v1/cities").await, http::StatusCode::OK);
+
+        let ops = registry.operations.lock().unwrap();
+        assert_eq!(
+            ops[0].vendor_extensions.x_odata_expand.as_deref(),
+            Some(&["addresses".to_owned()][..])
+        );
+        assert!(ops[1].vendor_extensions.x_odata_expand.is_none());
+    }
+
     #[test]
     fn require_license_features_none() {
         let builder = OperationBuilder::<Missing, Missing, ()>::get("/tests/v1/test")
@@ -1950,6 +2222,52 @@ mod tests {
         assert!(ops[0].license_requirement.is_none());
     }
 
+    #[test]
+    fn skip_response_compression() {
+        let builder = OperationBuilder::<Missing, Missing, ()>::get("/tests/v1/events");
+        assert!(!builder.spec.skip_response_compression);
+
+        let builder = builder.skip_response_compression();
+        assert!(builder.spec.skip_response_compression);
+    }
+
+    #[test]
+    fn conditional_request_helpers() {
+        let registry = MockRegistry::new();
+        let builder = OperationBuilder::<Missing, Missing, ()>::patch("/tests/v1/settings")
+            .public()
+            .handler(test_handler)
+            .json_response(http::StatusCode::OK, "OK")
+            .with_if_match(&registry);
+
+        assert!(builder.spec.conditional.if_match);
+        assert!(!builder.spec.conditional.if_none_match);
+        assert!(builder.spec.conditional.has_etag());
+        assert!(
+            builder
+                .spec
+                .params
+                .iter()
+                .any(|p| p.name == "If-Match" && p.location == ParamLocation::Header)
+        );
+        assert!(builder.spec.responses.iter().any(|r| r.status == 412));
+
+        let builder = OperationBuilder::<Missing, Missing, ()>::get("/tests/v1/settings")
+            .public()
+            .handler(test_handler)
+            .json_response(http::StatusCode::OK, "OK")
+            .with_if_none_match();
+
+        assert!(builder.spec.conditional.if_none_match);
+        assert!(
+            builder
+                .spec
+                .params
+                .iter()
+                .any(|p| p.name == "If-None-Match" && p.location == ParamLocation::Header)
+        );
+    }
+
     #[test]
     fn with_422_validation_error() {
         let registry = MockRegistry::new();
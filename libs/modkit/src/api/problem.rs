@@ -20,6 +20,14 @@ pub fn conflict(detail: impl Into<String>) -> Problem {
     Problem::new(StatusCode::CONFLICT, "Conflict", detail)
 }
 
+pub fn precondition_failed(detail: impl Into<String>) -> Problem {
+    Problem::new(
+        StatusCode::PRECONDITION_FAILED,
+        "Precondition Failed",
+        detail,
+    )
+}
+
 pub fn internal_error(detail: impl Into<String>) -> Problem {
     Problem::new(
         StatusCode::INTERNAL_SERVER_ERROR,
@@ -91,6 +99,10 @@ mod tests {
         assert_eq!(conflict_resp.status, StatusCode::CONFLICT);
         assert_eq!(conflict_resp.title, "Conflict");
 
+        let precondition_resp = precondition_failed("ETag mismatch");
+        assert_eq!(precondition_resp.status, StatusCode::PRECONDITION_FAILED);
+        assert_eq!(precondition_resp.title, "Precondition Failed");
+
         let internal_resp = internal_error("Database connection failed");
         assert_eq!(internal_resp.status, StatusCode::INTERNAL_SERVER_ERROR);
         assert_eq!(internal_resp.title, "Internal Server Error");
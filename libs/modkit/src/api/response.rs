@@ -1,9 +1,13 @@
 use axum::{
     Json,
-    http::{StatusCode, Uri, header},
-    response::IntoResponse,
+    http::{HeaderMap, StatusCode, Uri, header},
+    response::{IntoResponse, Response},
 };
 
+use crate::api::etag::{self, ETag};
+use crate::api::problem::internal_error;
+use crate::result::ApiResult;
+
 /// Short aliases for JSON responses
 pub type JsonBody<T> = Json<T>;
 pub type JsonPage<T> = Json<modkit_odata::Page<T>>;
@@ -32,3 +36,84 @@ pub fn created_json<T: serde::Serialize>(
 pub fn no_content() -> impl IntoResponse {
     StatusCode::NO_CONTENT
 }
+
+/// 304 Not Modified carrying the current `ETag`
+#[must_use]
+pub fn not_modified(etag: &ETag) -> Response {
+    (
+        StatusCode::NOT_MODIFIED,
+        [(header::ETAG, etag.header_value())],
+    )
+        .into_response()
+}
+
+/// 200 OK + JSON with an `ETag` header for the given tag.
+///
+/// Answers `304 Not Modified` instead when the request's `If-None-Match` matches `etag`.
+/// Use this when the tag comes from an entity version column.
+pub fn ok_json_tagged<T: serde::Serialize>(headers: &HeaderMap, etag: &ETag, value: T) -> Response {
+    if etag::is_not_modified(headers, etag) {
+        return not_modified(etag);
+    }
+    (
+        StatusCode::OK,
+        [(header::ETAG, etag.header_value())],
+        Json(value),
+    )
+        .into_response()
+}
+
+/// 200 OK + JSON with a strong `ETag` computed from the serialized body.
+///
+/// Answers `304 Not Modified` instead when the request's `If-None-Match` matches.
+///
+/// # Errors
+/// Returns a 500 problem if the value cannot be serialized.
+#[allow(clippy::result_large_err)] // Problem is the handler error type by design
+pub fn ok_json_with_etag<T: serde::Serialize>(
+    headers: &HeaderMap,
+    value: T,
+) -> ApiResult<Response> {
+    let etag = ETag::from_json(&value)
+        .map_err(|e| internal_error(format!("Failed to compute ETag: {e}")))?;
+    Ok(ok_json_tagged(headers, &etag, value))
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn ok_json_with_etag_sets_header() {
+        let resp = ok_json_with_etag(&HeaderMap::new(), serde_json::json!({"a": 1})).unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(resp.headers().contains_key(header::ETAG));
+    }
+
+    #[test]
+    fn ok_json_with_etag_returns_304_on_match() {
+        let body = serde_json::json!({"a": 1});
+        let etag = ETag::from_json(&body).unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::IF_NONE_MATCH,
+            HeaderValue::from_str(&etag.to_string()).unwrap(),
+        );
+
+        let resp = ok_json_with_etag(&headers, body).unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(resp.headers().get(header::ETAG), Some(&etag.header_value()));
+    }
+
+    #[test]
+    fn ok_json_tagged_ignores_stale_if_none_match() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_NONE_MATCH, HeaderValue::from_static("\"1\""));
+
+        let resp = ok_json_tagged(&headers, &ETag::from_version(2), "body");
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+}
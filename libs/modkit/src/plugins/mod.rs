@@ -1,6 +1,8 @@
 use std::future::Future;
 use std::sync::Arc;
 
+use futures_core::Stream;
+use futures_util::StreamExt;
 use parking_lot::RwLock;
 use tokio::sync::Mutex;
 
@@ -83,6 +85,25 @@ impl GtsPluginSelector {
         let mut guard = self.cached.write();
         guard.take().is_some()
     }
+
+    /// Resets the selector every time `changes` yields an item, until the
+    /// stream ends.
+    ///
+    /// Feed it change notifications for the plugin instances the selector
+    /// chooses from (e.g. `TypesRegistryClient::watch` filtered to the plugin
+    /// type) so the next `get_or_init` picks up registered or replaced
+    /// instances. Usually run in a spawned task.
+    pub async fn reset_on_changes<S>(&self, changes: S)
+    where
+        S: Stream,
+    {
+        let mut changes = std::pin::pin!(changes);
+        while changes.next().await.is_some() {
+            if self.reset().await {
+                tracing::debug!("Plugin instance changed, selection reset");
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -205,4 +226,23 @@ mod tests {
         // Resolve should have been called exactly once
         assert_eq!(calls.load(Ordering::SeqCst), 1);
     }
+
+    #[tokio::test]
+    async fn changes_reset_the_selection() {
+        let selector = GtsPluginSelector::new();
+        let resolve = |id: &'static str| {
+            move || async move { Ok::<_, std::convert::Infallible>(id.to_owned()) }
+        };
+
+        selector.get_or_init(resolve("a")).await.unwrap();
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel::<()>();
+        tx.send(()).unwrap();
+        drop(tx);
+        selector
+            .reset_on_changes(tokio_stream::wrappers::UnboundedReceiverStream::new(rx))
+            .await;
+
+        let id = selector.get_or_init(resolve("b")).await.unwrap();
+        assert_eq!(&*id, "b");
+    }
 }
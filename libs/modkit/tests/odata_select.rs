@@ -79,6 +79,10 @@ fn test_odata_params_with_select() {
         select: Some("id, name".to_owned()),
         limit: None,
         cursor: None,
+        count: None,
+        search: None,
+        expand: None,
+        apply: None,
     };
     assert_eq!(params.select, Some("id, name".to_owned()));
 }
@@ -475,6 +479,8 @@ fn test_page_to_projected_json() {
             next_cursor: Some("abc123".to_owned()),
             prev_cursor: None,
             limit: 10,
+            total_count: None,
+            total_count_capped: false,
         },
     };
 
@@ -531,6 +537,8 @@ fn test_page_to_projected_json_without_fields() {
             next_cursor: None,
             prev_cursor: None,
             limit: 20,
+            total_count: None,
+            total_count_capped: false,
         },
     };
 
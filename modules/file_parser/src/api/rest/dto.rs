@@ -30,6 +30,27 @@ pub struct UploadQuery {
     #[serde(default)]
     pub render_markdown: Option<bool>,
     pub filename: Option<String>,
+    /// Response format, overriding the `Accept` header
+    pub format: Option<String>,
+}
+
+/// Unit of the chunk size limits
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChunkUnitDto {
+    Chars,
+    Tokens,
+}
+
+/// Query parameters for chunking endpoints
+#[derive(Debug, Deserialize)]
+pub struct ChunkQuery {
+    #[serde(default)]
+    pub unit: Option<ChunkUnitDto>,
+    pub max_size: Option<usize>,
+    pub overlap: Option<usize>,
+    /// Original filename, for uploads
+    pub filename: Option<String>,
 }
 
 /// REST DTO for parsed document metadata
@@ -41,6 +62,8 @@ pub struct ParsedDocMetadataDto {
     pub original_filename: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub content_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
     #[serde(with = "time::serde::rfc3339::option")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub created_at: Option<OffsetDateTime>,
@@ -49,6 +72,77 @@ pub struct ParsedDocMetadataDto {
     pub modified_at: Option<OffsetDateTime>,
     #[serde(skip_serializing_if = "std::ops::Not::not")]
     pub is_stub: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<EmailHeadersDto>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<ImageMetadataDto>,
+}
+
+/// REST DTO for e-mail message headers
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(request, response)]
+pub struct EmailHeadersDto {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub from: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub to: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub cc: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<OffsetDateTime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<String>,
+}
+
+/// REST DTO for the properties of an image
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(request, response)]
+pub struct ImageMetadataDto {
+    pub format: String,
+    pub width: u32,
+    pub height: u32,
+    /// Number of frames of the source, more than 1 for animations
+    pub frames: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exif: Option<ExifDataDto>,
+}
+
+/// REST DTO for EXIF tags of an image
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(request, response)]
+pub struct ExifDataDto {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub make: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub software: Option<String>,
+    /// Local time the image was taken, as `YYYY:MM:DD HH:MM:SS`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub taken_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub orientation: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artist: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub copyright: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gps: Option<GpsPositionDto>,
+}
+
+/// REST DTO for a GPS position, in degrees and meters
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(request, response)]
+pub struct GpsPositionDto {
+    pub latitude: f64,
+    pub longitude: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub altitude: Option<f64>,
 }
 
 /// REST DTO for document source
@@ -114,11 +208,32 @@ pub struct TableRowDto {
     pub cells: Vec<TableCellDto>,
 }
 
+/// REST DTO for a bounding box, in points from the top-left page corner
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(request, response)]
+pub struct BoundingBoxDto {
+    pub x0: f64,
+    pub y0: f64,
+    pub x1: f64,
+    pub y1: f64,
+}
+
+/// REST DTO for where a block appears in the source document
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(request, response)]
+pub struct SourceLocationDto {
+    pub page: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bbox: Option<BoundingBoxDto>,
+}
+
 /// REST DTO for table block
 #[derive(Debug, Clone)]
 #[modkit_macros::api_dto(request, response)]
 pub struct TableBlockDto {
     pub rows: Vec<TableRowDto>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<SourceLocationDto>,
 }
 
 /// REST DTO for parsed block
@@ -129,9 +244,13 @@ pub enum ParsedBlockDto {
     Heading {
         level: u8,
         inlines: Vec<InlineDto>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        location: Option<SourceLocationDto>,
     },
     Paragraph {
         inlines: Vec<InlineDto>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        location: Option<SourceLocationDto>,
     },
     ListItem {
         level: u8,
@@ -174,6 +293,19 @@ pub struct ParsedDocumentDto {
     pub blocks: Vec<ParsedBlockDto>,
 }
 
+/// Version of the JSON IR layout, raised on incompatible changes
+pub const IR_VERSION: u32 = 1;
+
+/// REST DTO for the versioned JSON serialization of a parsed document,
+/// returned by parse endpoints for `format=ir`
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(response)]
+pub struct ParsedDocumentIrDto {
+    /// Layout version of `document`
+    pub ir_version: u32,
+    pub document: ParsedDocumentDto,
+}
+
 /// REST DTO for file parse response (with optional markdown)
 #[derive(Debug, Clone)]
 #[modkit_macros::api_dto(response)]
@@ -184,3 +316,92 @@ pub struct ParsedDocResponseDto {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub markdown: Option<String>,
 }
+
+/// REST DTO for a chunk of a parsed document
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(response)]
+pub struct ChunkDto {
+    /// Position of the chunk in the document, from 0
+    pub index: usize,
+    /// Markdown text of the chunk
+    pub text: String,
+    /// Headings the chunk is under, outermost first
+    pub heading_path: Vec<String>,
+    /// Start of the chunk in characters of the rendered Markdown
+    pub start_offset: usize,
+    /// End of the chunk (exclusive) in characters of the rendered Markdown
+    pub end_offset: usize,
+    /// Index of the first document block in the chunk
+    pub first_block: usize,
+    /// Index of the last document block in the chunk
+    pub last_block: usize,
+    /// Size of the chunk in the requested unit
+    pub size: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+}
+
+/// REST DTO for a document split into chunks
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(response)]
+pub struct ChunkedDocResponseDto {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub meta: ParsedDocMetadataDto,
+    pub chunks: Vec<ChunkDto>,
+}
+
+/// Query parameters for submitting an upload parse job
+#[derive(Debug, Deserialize)]
+pub struct JobUploadQuery {
+    pub filename: Option<String>,
+}
+
+/// State of a parse job
+#[derive(Debug, Clone, Copy)]
+#[modkit_macros::api_dto(response)]
+pub enum JobStateDto {
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+/// REST DTO for how much of its file a parse job has read
+#[derive(Debug, Clone, Copy)]
+#[modkit_macros::api_dto(response)]
+pub struct JobProgressDto {
+    /// Bytes downloaded or uploaded so far
+    pub processed_bytes: u64,
+    /// Size of the file, if known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_bytes: Option<u64>,
+}
+
+/// REST DTO for the status of a parse job
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(response)]
+pub struct ParseJobDto {
+    pub id: Uuid,
+    pub state: JobStateDto,
+    /// File name or URL of the parsed file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339::option")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<OffsetDateTime>,
+    pub progress: JobProgressDto,
+    /// When the job and its result are dropped, once it has finished
+    #[serde(with = "time::serde::rfc3339::option")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<OffsetDateTime>,
+    /// Why the job failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
@@ -12,6 +12,12 @@ pub fn domain_error_to_problem(err: DomainError) -> Problem {
             format!("File not found: {path}"),
         ),
 
+        DomainError::PathNotAllowed { path } => Problem::new(
+            StatusCode::FORBIDDEN,
+            "Path Not Allowed",
+            format!("Path is outside the allowed local directories: {path}"),
+        ),
+
         DomainError::UnsupportedFileType { extension } => Problem::new(
             StatusCode::BAD_REQUEST,
             "Unsupported File Type",
@@ -38,6 +44,12 @@ pub fn domain_error_to_problem(err: DomainError) -> Problem {
             format!("Invalid URL: {url}"),
         ),
 
+        DomainError::UrlNotAllowed { url, reason } => Problem::new(
+            StatusCode::FORBIDDEN,
+            "URL Not Allowed",
+            format!("URL not allowed: {url}: {reason}"),
+        ),
+
         DomainError::DownloadError { message } => {
             Problem::new(StatusCode::BAD_GATEWAY, "Download Error", message)
         }
@@ -45,6 +57,32 @@ pub fn domain_error_to_problem(err: DomainError) -> Problem {
         DomainError::InvalidRequest { message } => {
             Problem::new(StatusCode::BAD_REQUEST, "Invalid Request", message)
         }
+
+        DomainError::JobNotFound { id } => Problem::new(
+            StatusCode::NOT_FOUND,
+            "Job Not Found",
+            format!("Parse job not found: {id}"),
+        ),
+
+        DomainError::TooManyJobs { limit } => Problem::new(
+            StatusCode::TOO_MANY_REQUESTS,
+            "Too Many Jobs",
+            format!("At most {limit} parse jobs may be pending; retry when some have finished"),
+        ),
+
+        DomainError::TooManyPendingBytes { limit } => Problem::new(
+            StatusCode::TOO_MANY_REQUESTS,
+            "Too Many Pending Bytes",
+            format!(
+                "At most {limit} bytes of uploads may be pending; retry when some jobs have finished"
+            ),
+        ),
+
+        DomainError::NoJobResult { id, state } => Problem::new(
+            StatusCode::CONFLICT,
+            "No Job Result",
+            format!("Parse job {id} has no result: it is {state}"),
+        ),
     }
 }
 
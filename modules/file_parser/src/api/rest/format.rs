@@ -0,0 +1,269 @@
+use std::convert::Infallible;
+use std::fmt::Write;
+
+use axum::body::Body;
+use axum::http::{HeaderMap, HeaderValue, header};
+use axum::response::{IntoResponse, Response};
+use bytes::Bytes;
+use futures_util::stream;
+
+use crate::api::rest::dto::{
+    IR_VERSION, ParsedBlockDto, ParsedDocMetadataDto, ParsedDocResponseDto, ParsedDocumentDto,
+};
+use crate::domain::error::DomainError;
+use crate::domain::html::HtmlRenderer;
+use crate::domain::ir::{ParsedBlock, ParsedDocument};
+use crate::domain::markdown::MarkdownRenderer;
+use crate::domain::plain_text::PlainTextRenderer;
+
+/// Media type of the versioned JSON IR
+pub const IR_MEDIA_TYPE: &str = "application/vnd.file-parser.ir+json";
+
+/// Response formats of the parse endpoints
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// `ParsedDocResponseDto`, with optional Markdown
+    Json,
+    /// `ParsedDocumentIrDto`, streamed
+    Ir,
+    Markdown,
+    Html,
+    Text,
+}
+
+impl OutputFormat {
+    /// Pick the format from the `format` query parameter, then from the
+    /// `Accept` header, falling back to the endpoint's `default`
+    ///
+    /// # Errors
+    /// Returns `DomainError::InvalidRequest` for an unknown `format` value.
+    pub fn negotiate(
+        format: Option<&str>,
+        headers: &HeaderMap,
+        default: Self,
+    ) -> Result<Self, DomainError> {
+        if let Some(name) = format {
+            return Self::from_name(name).ok_or_else(|| {
+                DomainError::invalid_request(format!(
+                    "Unsupported format '{name}', expected json, ir, markdown, html or text"
+                ))
+            });
+        }
+        Ok(headers
+            .get(header::ACCEPT)
+            .and_then(|value| value.to_str().ok())
+            .and_then(Self::from_accept)
+            .unwrap_or(default))
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "ir" => Some(Self::Ir),
+            "markdown" | "md" => Some(Self::Markdown),
+            "html" => Some(Self::Html),
+            "text" | "txt" => Some(Self::Text),
+            _ => None,
+        }
+    }
+
+    fn from_media_type(media_type: &str) -> Option<Self> {
+        match media_type {
+            "application/json" => Some(Self::Json),
+            IR_MEDIA_TYPE => Some(Self::Ir),
+            "text/markdown" => Some(Self::Markdown),
+            "text/html" => Some(Self::Html),
+            "text/plain" => Some(Self::Text),
+            _ => None,
+        }
+    }
+
+    /// The listed format with the highest quality; wildcards are ignored so
+    /// that they get the endpoint default
+    fn from_accept(accept: &str) -> Option<Self> {
+        let mut best: Option<(f32, Self)> = None;
+        for range in accept.split(',') {
+            let mut params = range.split(';');
+            let media_type = params
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_ascii_lowercase();
+            let Some(format) = Self::from_media_type(&media_type) else {
+                continue;
+            };
+            let quality = params
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .find_map(|q| q.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            if quality > 0.0 && best.is_none_or(|(best_quality, _)| quality > best_quality) {
+                best = Some((quality, format));
+            }
+        }
+        best.map(|(_, format)| format)
+    }
+
+    fn content_type(self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            Self::Ir => IR_MEDIA_TYPE,
+            Self::Markdown => "text/markdown; charset=utf-8",
+            Self::Html => "text/html; charset=utf-8",
+            Self::Text => "text/plain; charset=utf-8",
+        }
+    }
+}
+
+/// Respond with a parsed document in the given format; every format except
+/// `Json` is streamed block by block
+pub fn document_response(
+    document: ParsedDocument,
+    format: OutputFormat,
+    render_markdown: bool,
+) -> Response {
+    let chunks: Box<dyn Iterator<Item = String> + Send> = match format {
+        OutputFormat::Json => {
+            let markdown = render_markdown.then(|| MarkdownRenderer::render(&document));
+            let response = ParsedDocResponseDto {
+                document: ParsedDocumentDto::from(document),
+                markdown,
+            };
+            return axum::Json(response).into_response();
+        }
+        OutputFormat::Ir => Box::new(IrJsonRenderIter::new(document)),
+        OutputFormat::Markdown => Box::new(MarkdownRenderer::render_iter(document)),
+        OutputFormat::Html => Box::new(HtmlRenderer::render_iter(document)),
+        OutputFormat::Text => Box::new(PlainTextRenderer::render_iter(document)),
+    };
+
+    let stream = stream::iter(chunks.map(|chunk| Ok::<Bytes, Infallible>(Bytes::from(chunk))));
+    let mut resp = Response::new(Body::from_stream(stream));
+    *resp.status_mut() = axum::http::StatusCode::OK;
+    resp.headers_mut().insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static(format.content_type()),
+    );
+    resp
+}
+
+/// Iterator over the JSON IR (`ParsedDocumentIrDto`) of a document, with one
+/// chunk per block so that large documents are never held as a single string
+pub struct IrJsonRenderIter {
+    header: Option<String>,
+    blocks: std::vec::IntoIter<ParsedBlock>,
+    first_block: bool,
+    done: bool,
+}
+
+impl IrJsonRenderIter {
+    #[must_use]
+    pub fn new(document: ParsedDocument) -> Self {
+        let mut header = format!("{{\"ir_version\":{IR_VERSION},\"document\":{{");
+        if let Some(id) = document.id {
+            let _ = write!(header, "\"id\":{},", to_json(&id));
+        }
+        if let Some(title) = &document.title {
+            let _ = write!(header, "\"title\":{},", to_json(title));
+        }
+        if let Some(language) = &document.language {
+            let _ = write!(header, "\"language\":{},", to_json(language));
+        }
+        let meta = ParsedDocMetadataDto::from(document.meta);
+        let _ = write!(header, "\"meta\":{},\"blocks\":[", to_json(&meta));
+
+        Self {
+            header: Some(header),
+            blocks: document.blocks.into_iter(),
+            first_block: true,
+            done: false,
+        }
+    }
+}
+
+impl Iterator for IrJsonRenderIter {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(header) = self.header.take() {
+            return Some(header);
+        }
+        if let Some(block) = self.blocks.next() {
+            let json = to_json(&ParsedBlockDto::from(block));
+            if self.first_block {
+                self.first_block = false;
+                return Some(json);
+            }
+            return Some(format!(",{json}"));
+        }
+        if self.done {
+            return None;
+        }
+        self.done = true;
+        Some("]}}".to_owned())
+    }
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> String {
+    // DTOs only have string keys, so serialization cannot fail
+    serde_json::to_string(value).unwrap_or_else(|_| "null".to_owned())
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::api::rest::dto::ParsedDocumentIrDto;
+    use crate::domain::ir::{DocumentBuilder, Inline, ParsedSource};
+
+    fn headers(accept: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT, HeaderValue::from_str(accept).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_negotiate() {
+        let json = OutputFormat::Json;
+        assert_eq!(
+            OutputFormat::negotiate(Some("HTML"), &headers("text/plain"), json).unwrap(),
+            OutputFormat::Html
+        );
+        assert!(OutputFormat::negotiate(Some("pdf"), &HeaderMap::new(), json).is_err());
+        assert_eq!(
+            OutputFormat::negotiate(None, &headers("text/plain;q=0.5, text/html"), json).unwrap(),
+            OutputFormat::Html
+        );
+        assert_eq!(
+            OutputFormat::negotiate(None, &headers("*/*"), OutputFormat::Markdown).unwrap(),
+            OutputFormat::Markdown
+        );
+        assert_eq!(
+            OutputFormat::negotiate(None, &headers(IR_MEDIA_TYPE), json).unwrap(),
+            OutputFormat::Ir
+        );
+    }
+
+    #[test]
+    fn test_ir_stream_matches_dto() {
+        let document = DocumentBuilder::new(ParsedSource::LocalPath("a.txt".to_owned()))
+            .title("Title")
+            .blocks(vec![
+                ParsedBlock::Paragraph {
+                    inlines: vec![Inline::plain("one")],
+                    location: None,
+                },
+                ParsedBlock::HorizontalRule,
+            ])
+            .build();
+
+        let streamed: String = IrJsonRenderIter::new(document.clone()).collect();
+        let expected = serde_json::to_value(ParsedDocumentIrDto {
+            ir_version: IR_VERSION,
+            document: ParsedDocumentDto::from(document),
+        })
+        .unwrap();
+
+        let streamed: serde_json::Value = serde_json::from_str(&streamed).unwrap();
+        assert_eq!(streamed, expected);
+    }
+}
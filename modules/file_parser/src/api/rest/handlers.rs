@@ -1,30 +1,38 @@
 // False positives from #[axum::debug_handler] macro expansion
 #![allow(clippy::items_after_statements)]
 
-use axum::body::Body;
-use axum::extract::{Extension, Query};
+use axum::extract::{Extension, Path, Query};
 use axum::http::HeaderMap;
 use axum::response::Response;
 use bytes::Bytes;
-use futures_util::stream;
-use std::convert::Infallible;
 use tracing::{field::Empty, info};
 
 use crate::api::rest::dto::{
-    FileParserInfoDto, ParseLocalFileRequest, ParseUrlRequest, ParsedDocResponseDto,
-    ParsedDocumentDto, UploadQuery,
+    ChunkQuery, ChunkedDocResponseDto, FileParserInfoDto, JobUploadQuery, ParseJobDto,
+    ParseLocalFileRequest, ParseUrlRequest, UploadQuery,
 };
+use crate::api::rest::format::{OutputFormat, document_response};
+use crate::domain::chunking::{ChunkOptions, Chunker};
 use crate::domain::error::DomainError;
-use crate::domain::markdown::MarkdownRenderer;
+use crate::domain::ir::ParsedDocument;
+use crate::domain::jobs::{JobSource, ParseJobManager};
 use crate::domain::service::FileParserService;
 use modkit::api::prelude::*;
 use modkit_auth::axum_ext::Authz;
 
-/// Query parameter for `render_markdown` flag
+/// Query parameters for `render_markdown` flag and response format
 #[derive(Debug, serde::Deserialize)]
 pub struct RenderMarkdownQuery {
     #[serde(default)]
     pub render_markdown: Option<bool>,
+    /// Response format, overriding the `Accept` header
+    pub format: Option<String>,
+}
+
+/// Query parameter for the response format of streaming endpoints
+#[derive(Debug, serde::Deserialize)]
+pub struct FormatQuery {
+    pub format: Option<String>,
 }
 
 /// Get information about available file parsers
@@ -48,7 +56,7 @@ pub async fn get_parser_info(
 
 /// Parse a file from a local path
 #[tracing::instrument(
-    skip(svc, req_body, _ctx, query),
+    skip(svc, req_body, ctx, query, headers),
     fields(
         file_path = %req_body.file_path,
         render_markdown = ?query.render_markdown,
@@ -57,35 +65,26 @@ pub async fn get_parser_info(
 )]
 #[axum::debug_handler]
 pub async fn parse_local(
-    Authz(_ctx): Authz,
+    Authz(ctx): Authz,
     Extension(svc): Extension<std::sync::Arc<FileParserService>>,
     Query(query): Query<RenderMarkdownQuery>,
+    headers: HeaderMap,
     Json(req_body): Json<ParseLocalFileRequest>,
-) -> ApiResult<JsonBody<ParsedDocResponseDto>> {
+) -> ApiResult<Response> {
     let render_md = query.render_markdown.unwrap_or(false);
+    let format = OutputFormat::negotiate(query.format.as_deref(), &headers, OutputFormat::Json)?;
 
     info!(
         file_path = %req_body.file_path,
         render_markdown = render_md,
+        format = ?format,
         "Parsing file from local path"
     );
 
     let path = std::path::Path::new(&req_body.file_path);
-    let document = svc.parse_local(path).await?;
-
-    // Optionally render markdown
-    let markdown = if render_md {
-        Some(MarkdownRenderer::render(&document))
-    } else {
-        None
-    };
-
-    let response = ParsedDocResponseDto {
-        document: ParsedDocumentDto::from(document),
-        markdown,
-    };
+    let document = svc.parse_local(&ctx, path).await?;
 
-    Ok(Json(response))
+    Ok(document_response(document, format, render_md))
 }
 
 /// Upload and parse a file
@@ -105,9 +104,10 @@ pub async fn upload_and_parse(
     Query(query): Query<UploadQuery>,
     headers: HeaderMap,
     body: Bytes,
-) -> ApiResult<JsonBody<ParsedDocResponseDto>> {
+) -> ApiResult<Response> {
     let render_md = query.render_markdown.unwrap_or(false);
     let filename_opt = query.filename.as_deref();
+    let format = OutputFormat::negotiate(query.format.as_deref(), &headers, OutputFormat::Json)?;
 
     // Extract Content-Type from headers
     let content_type_str = headers
@@ -119,6 +119,7 @@ pub async fn upload_and_parse(
         filename = ?filename_opt,
         content_type = ?content_type_str,
         render_markdown = render_md,
+        format = ?format,
         size = body.len(),
         "Uploading and parsing raw file bytes"
     );
@@ -134,24 +135,12 @@ pub async fn upload_and_parse(
         .parse_bytes(filename_opt, content_type_str.as_deref(), body)
         .await?;
 
-    // Optionally render markdown
-    let markdown = if render_md {
-        Some(MarkdownRenderer::render(&document))
-    } else {
-        None
-    };
-
-    let response = ParsedDocResponseDto {
-        document: ParsedDocumentDto::from(document),
-        markdown,
-    };
-
-    Ok(Json(response))
+    Ok(document_response(document, format, render_md))
 }
 
 /// Parse a file from a URL
 #[tracing::instrument(
-    skip(svc, req_body, _ctx, query),
+    skip(svc, req_body, _ctx, query, headers),
     fields(
         url = %req_body.url,
         render_markdown = ?query.render_markdown,
@@ -163,13 +152,16 @@ pub async fn parse_url(
     Authz(_ctx): Authz,
     Extension(svc): Extension<std::sync::Arc<FileParserService>>,
     Query(query): Query<RenderMarkdownQuery>,
+    headers: HeaderMap,
     Json(req_body): Json<ParseUrlRequest>,
-) -> ApiResult<JsonBody<ParsedDocResponseDto>> {
+) -> ApiResult<Response> {
     let render_md = query.render_markdown.unwrap_or(false);
+    let format = OutputFormat::negotiate(query.format.as_deref(), &headers, OutputFormat::Json)?;
 
     info!(
         url = %req_body.url,
         render_markdown = render_md,
+        format = ?format,
         "Parsing file from URL"
     );
 
@@ -178,24 +170,12 @@ pub async fn parse_url(
 
     let document = svc.parse_url(&url).await?;
 
-    // Optionally render markdown
-    let markdown = if render_md {
-        Some(MarkdownRenderer::render(&document))
-    } else {
-        None
-    };
-
-    let response = ParsedDocResponseDto {
-        document: ParsedDocumentDto::from(document),
-        markdown,
-    };
-
-    Ok(Json(response))
+    Ok(document_response(document, format, render_md))
 }
 
 /// Parse a local file and stream Markdown response
 #[tracing::instrument(
-    skip(svc, req_body, _ctx),
+    skip(svc, req_body, ctx, query, headers),
     fields(
         file_path = %req_body.file_path,
         request_id = Empty
@@ -203,38 +183,30 @@ pub async fn parse_url(
 )]
 #[axum::debug_handler]
 pub async fn parse_local_markdown(
-    Authz(_ctx): Authz,
+    Authz(ctx): Authz,
     Extension(svc): Extension<std::sync::Arc<FileParserService>>,
+    Query(query): Query<FormatQuery>,
+    headers: HeaderMap,
     Json(req_body): Json<ParseLocalFileRequest>,
 ) -> ApiResult<Response> {
+    let format =
+        OutputFormat::negotiate(query.format.as_deref(), &headers, OutputFormat::Markdown)?;
+
     info!(
         file_path = %req_body.file_path,
+        format = ?format,
         "Parsing file from local path and streaming Markdown"
     );
 
     let path = std::path::Path::new(&req_body.file_path);
-    let document = svc.parse_local(path).await?;
-
-    // Create streaming response - render_iter takes ownership of document
-    let stream = stream::iter(
-        MarkdownRenderer::render_iter(document)
-            .map(|chunk| Ok::<Bytes, Infallible>(Bytes::from(chunk))),
-    );
+    let document = svc.parse_local(&ctx, path).await?;
 
-    let body = Body::from_stream(stream);
-    let mut resp = Response::new(body);
-    *resp.status_mut() = axum::http::StatusCode::OK;
-    resp.headers_mut().insert(
-        axum::http::header::CONTENT_TYPE,
-        axum::http::HeaderValue::from_static("text/markdown; charset=utf-8"),
-    );
-
-    Ok(resp)
+    Ok(document_response(document, format, false))
 }
 
 /// Upload and parse a file, streaming Markdown response
 #[tracing::instrument(
-    skip(svc, multipart, _ctx),
+    skip(svc, multipart, _ctx, query, headers),
     fields(
         request_id = Empty
     )
@@ -243,9 +215,14 @@ pub async fn parse_local_markdown(
 pub async fn upload_and_parse_markdown(
     Authz(_ctx): Authz,
     Extension(svc): Extension<std::sync::Arc<FileParserService>>,
+    Query(query): Query<FormatQuery>,
+    headers: HeaderMap,
     mut multipart: axum::extract::Multipart,
 ) -> ApiResult<Response> {
-    info!("Uploading and parsing file, streaming Markdown");
+    let format =
+        OutputFormat::negotiate(query.format.as_deref(), &headers, OutputFormat::Markdown)?;
+
+    info!(format = ?format, "Uploading and parsing file, streaming Markdown");
 
     // Extract the first file field (reuse logic from upload_and_parse)
     let mut file_name: Option<String> = None;
@@ -286,26 +263,12 @@ pub async fn upload_and_parse_markdown(
 
     let document = svc.parse_bytes(Some(&file_name), None, file_bytes).await?;
 
-    // Create streaming response - render_iter takes ownership of document
-    let stream = stream::iter(
-        MarkdownRenderer::render_iter(document)
-            .map(|chunk| Ok::<Bytes, Infallible>(Bytes::from(chunk))),
-    );
-
-    let body = Body::from_stream(stream);
-    let mut resp = Response::new(body);
-    *resp.status_mut() = axum::http::StatusCode::OK;
-    resp.headers_mut().insert(
-        axum::http::header::CONTENT_TYPE,
-        axum::http::HeaderValue::from_static("text/markdown; charset=utf-8"),
-    );
-
-    Ok(resp)
+    Ok(document_response(document, format, false))
 }
 
 /// Parse a file from a URL and stream Markdown response
 #[tracing::instrument(
-    skip(svc, req_body, _ctx),
+    skip(svc, req_body, _ctx, query, headers),
     fields(
         url = %req_body.url,
         request_id = Empty
@@ -315,10 +278,16 @@ pub async fn upload_and_parse_markdown(
 pub async fn parse_url_markdown(
     Authz(_ctx): Authz,
     Extension(svc): Extension<std::sync::Arc<FileParserService>>,
+    Query(query): Query<FormatQuery>,
+    headers: HeaderMap,
     Json(req_body): Json<ParseUrlRequest>,
 ) -> ApiResult<Response> {
+    let format =
+        OutputFormat::negotiate(query.format.as_deref(), &headers, OutputFormat::Markdown)?;
+
     info!(
         url = %req_body.url,
+        format = ?format,
         "Parsing file from URL and streaming Markdown"
     );
 
@@ -327,19 +296,247 @@ pub async fn parse_url_markdown(
 
     let document = svc.parse_url(&url).await?;
 
-    // Create streaming response - render_iter takes ownership of document
-    let stream = stream::iter(
-        MarkdownRenderer::render_iter(document)
-            .map(|chunk| Ok::<Bytes, Infallible>(Bytes::from(chunk))),
+    Ok(document_response(document, format, false))
+}
+
+/// Split a parsed document into chunks for the response
+fn chunked_response(
+    document: &ParsedDocument,
+    options: &ChunkOptions,
+) -> Result<ChunkedDocResponseDto, DomainError> {
+    let chunks = Chunker::chunk(document, options)?;
+    Ok(ChunkedDocResponseDto {
+        title: document.title.clone(),
+        meta: document.meta.clone().into(),
+        chunks: chunks.into_iter().map(Into::into).collect(),
+    })
+}
+
+/// Parse a local file and split it into chunks
+#[tracing::instrument(
+    skip(svc, req_body, ctx, query),
+    fields(
+        file_path = %req_body.file_path,
+        request_id = Empty
+    )
+)]
+#[axum::debug_handler]
+pub async fn parse_local_chunks(
+    Authz(ctx): Authz,
+    Extension(svc): Extension<std::sync::Arc<FileParserService>>,
+    Query(query): Query<ChunkQuery>,
+    Json(req_body): Json<ParseLocalFileRequest>,
+) -> ApiResult<JsonBody<ChunkedDocResponseDto>> {
+    let options = ChunkOptions::from(&query);
+    options.validate()?;
+
+    info!(
+        file_path = %req_body.file_path,
+        max_size = options.max_size,
+        "Parsing file from local path into chunks"
+    );
+
+    let path = std::path::Path::new(&req_body.file_path);
+    let document = svc.parse_local(&ctx, path).await?;
+
+    Ok(Json(chunked_response(&document, &options)?))
+}
+
+/// Upload a file and split it into chunks
+#[tracing::instrument(
+    skip(svc, body, _ctx, query, headers),
+    fields(
+        filename = ?query.filename,
+        size = body.len(),
+        request_id = Empty
+    )
+)]
+#[axum::debug_handler]
+pub async fn upload_and_chunk(
+    Authz(_ctx): Authz,
+    Extension(svc): Extension<std::sync::Arc<FileParserService>>,
+    Query(query): Query<ChunkQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> ApiResult<JsonBody<ChunkedDocResponseDto>> {
+    let options = ChunkOptions::from(&query);
+    options.validate()?;
+
+    let content_type_str = headers
+        .get(axum::http::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(ToString::to_string);
+
+    info!(
+        filename = ?query.filename,
+        content_type = ?content_type_str,
+        max_size = options.max_size,
+        size = body.len(),
+        "Uploading and parsing raw file bytes into chunks"
+    );
+
+    if body.is_empty() {
+        return Err(DomainError::invalid_request(
+            "Empty request body, expected file bytes".to_owned(),
+        )
+        .into());
+    }
+
+    let document = svc
+        .parse_bytes(query.filename.as_deref(), content_type_str.as_deref(), body)
+        .await?;
+
+    Ok(Json(chunked_response(&document, &options)?))
+}
+
+/// Parse a file from a URL and split it into chunks
+#[tracing::instrument(
+    skip(svc, req_body, _ctx, query),
+    fields(
+        url = %req_body.url,
+        request_id = Empty
+    )
+)]
+#[axum::debug_handler]
+pub async fn parse_url_chunks(
+    Authz(_ctx): Authz,
+    Extension(svc): Extension<std::sync::Arc<FileParserService>>,
+    Query(query): Query<ChunkQuery>,
+    Json(req_body): Json<ParseUrlRequest>,
+) -> ApiResult<JsonBody<ChunkedDocResponseDto>> {
+    let options = ChunkOptions::from(&query);
+    options.validate()?;
+
+    info!(
+        url = %req_body.url,
+        max_size = options.max_size,
+        "Parsing file from URL into chunks"
     );
 
-    let body = Body::from_stream(stream);
-    let mut resp = Response::new(body);
-    *resp.status_mut() = axum::http::StatusCode::OK;
-    resp.headers_mut().insert(
-        axum::http::header::CONTENT_TYPE,
-        axum::http::HeaderValue::from_static("text/markdown; charset=utf-8"),
+    let url = url::Url::parse(&req_body.url)
+        .map_err(|_| Problem::from(DomainError::invalid_url(req_body.url)))?;
+
+    let document = svc.parse_url(&url).await?;
+
+    Ok(Json(chunked_response(&document, &options)?))
+}
+
+/// Upload a file and queue a job parsing it
+#[tracing::instrument(
+    skip(jobs, body, ctx, query, headers),
+    fields(
+        filename = ?query.filename,
+        size = body.len(),
+        request_id = Empty
+    )
+)]
+#[axum::debug_handler]
+pub async fn submit_upload_job(
+    Authz(ctx): Authz,
+    Extension(jobs): Extension<std::sync::Arc<ParseJobManager>>,
+    Query(query): Query<JobUploadQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> ApiResult<(http::StatusCode, JsonBody<ParseJobDto>)> {
+    let content_type = headers
+        .get(axum::http::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(ToString::to_string);
+
+    info!(
+        filename = ?query.filename,
+        content_type = ?content_type,
+        size = body.len(),
+        "Submitting parse job for uploaded file"
     );
 
-    Ok(resp)
+    if body.is_empty() {
+        return Err(DomainError::invalid_request(
+            "Empty request body, expected file bytes".to_owned(),
+        )
+        .into());
+    }
+
+    let source = JobSource::Upload {
+        filename: query.filename,
+        content_type,
+        bytes: body,
+    };
+    let job = jobs.submit(ctx.tenant_id(), source).await?;
+
+    Ok((http::StatusCode::ACCEPTED, Json(ParseJobDto::from(job))))
+}
+
+/// Queue a job parsing a file from a URL
+#[tracing::instrument(
+    skip(jobs, req_body, ctx),
+    fields(
+        url = %req_body.url,
+        request_id = Empty
+    )
+)]
+#[axum::debug_handler]
+pub async fn submit_url_job(
+    Authz(ctx): Authz,
+    Extension(jobs): Extension<std::sync::Arc<ParseJobManager>>,
+    Json(req_body): Json<ParseUrlRequest>,
+) -> ApiResult<(http::StatusCode, JsonBody<ParseJobDto>)> {
+    info!(url = %req_body.url, "Submitting parse job for URL");
+
+    let url = url::Url::parse(&req_body.url)
+        .map_err(|_| Problem::from(DomainError::invalid_url(req_body.url)))?;
+
+    let job = jobs.submit(ctx.tenant_id(), JobSource::Url(url)).await?;
+
+    Ok((http::StatusCode::ACCEPTED, Json(ParseJobDto::from(job))))
+}
+
+/// Get the status of a parse job
+#[tracing::instrument(skip(jobs, ctx), fields(request_id = Empty))]
+#[axum::debug_handler]
+pub async fn get_job(
+    Authz(ctx): Authz,
+    Extension(jobs): Extension<std::sync::Arc<ParseJobManager>>,
+    Path(job_id): Path<uuid::Uuid>,
+) -> ApiResult<JsonBody<ParseJobDto>> {
+    let job = jobs.get(ctx.tenant_id(), job_id).await?;
+
+    Ok(Json(ParseJobDto::from(job)))
+}
+
+/// Get the parsed document of a succeeded parse job
+#[tracing::instrument(skip(jobs, ctx, query, headers), fields(request_id = Empty))]
+#[axum::debug_handler]
+pub async fn get_job_result(
+    Authz(ctx): Authz,
+    Extension(jobs): Extension<std::sync::Arc<ParseJobManager>>,
+    Path(job_id): Path<uuid::Uuid>,
+    Query(query): Query<RenderMarkdownQuery>,
+    headers: HeaderMap,
+) -> ApiResult<Response> {
+    let render_md = query.render_markdown.unwrap_or(false);
+    let format = OutputFormat::negotiate(query.format.as_deref(), &headers, OutputFormat::Json)?;
+
+    let document = jobs.result(ctx.tenant_id(), job_id).await?;
+
+    Ok(document_response(
+        std::sync::Arc::unwrap_or_clone(document),
+        format,
+        render_md,
+    ))
+}
+
+/// Cancel a queued or running parse job
+#[tracing::instrument(skip(jobs, ctx), fields(request_id = Empty))]
+#[axum::debug_handler]
+pub async fn cancel_job(
+    Authz(ctx): Authz,
+    Extension(jobs): Extension<std::sync::Arc<ParseJobManager>>,
+    Path(job_id): Path<uuid::Uuid>,
+) -> ApiResult<JsonBody<ParseJobDto>> {
+    info!(%job_id, "Cancelling parse job");
+
+    let job = jobs.cancel(ctx.tenant_id(), job_id).await?;
+
+    Ok(Json(ParseJobDto::from(job)))
 }
@@ -1,8 +1,12 @@
 use crate::api::rest::{
-    FileParserInfoDto, InlineDto, InlineStyleDto, ParsedBlockDto, ParsedDocMetadataDto,
-    ParsedDocSourceDto, ParsedDocumentDto, TableBlockDto, TableCellDto, TableRowDto,
+    BoundingBoxDto, ChunkDto, ChunkQuery, ChunkUnitDto, EmailHeadersDto, ExifDataDto,
+    FileParserInfoDto, GpsPositionDto, ImageMetadataDto, InlineDto, InlineStyleDto, JobProgressDto,
+    JobStateDto, ParseJobDto, ParsedBlockDto, ParsedDocMetadataDto, ParsedDocSourceDto,
+    ParsedDocumentDto, SourceLocationDto, TableBlockDto, TableCellDto, TableRowDto,
+};
+use crate::domain::{
+    Chunk, ChunkOptions, ChunkUnit, FileParserInfo, JobProgress, JobState, ParseJob, ir,
 };
-use crate::domain::{FileParserInfo, ir};
 
 // Conversion implementations
 impl From<FileParserInfo> for FileParserInfoDto {
@@ -31,9 +35,63 @@ impl From<ir::ParsedMetadata> for ParsedDocMetadataDto {
             source: meta.source.into(),
             original_filename: meta.original_filename,
             content_type: meta.content_type,
+            author: meta.author,
             created_at: meta.created_at,
             modified_at: meta.modified_at,
             is_stub: meta.is_stub,
+            email: meta.email.map(Into::into),
+            image: meta.image.map(Into::into),
+        }
+    }
+}
+
+impl From<ir::EmailHeaders> for EmailHeadersDto {
+    fn from(email: ir::EmailHeaders) -> Self {
+        Self {
+            from: email.from,
+            to: email.to,
+            cc: email.cc,
+            subject: email.subject,
+            date: email.date,
+            message_id: email.message_id,
+        }
+    }
+}
+
+impl From<ir::ImageMetadata> for ImageMetadataDto {
+    fn from(image: ir::ImageMetadata) -> Self {
+        Self {
+            format: image.format,
+            width: image.width,
+            height: image.height,
+            frames: image.frames,
+            exif: image.exif.map(Into::into),
+        }
+    }
+}
+
+impl From<ir::ExifData> for ExifDataDto {
+    fn from(exif: ir::ExifData) -> Self {
+        Self {
+            make: exif.make,
+            model: exif.model,
+            software: exif.software,
+            taken_at: exif.taken_at,
+            orientation: exif.orientation,
+            description: exif.description,
+            artist: exif.artist,
+            copyright: exif.copyright,
+            gps: exif.gps.map(Into::into),
+        }
+    }
+}
+
+impl From<ir::GpsPosition> for GpsPositionDto {
+    fn from(gps: ir::GpsPosition) -> Self {
+        Self {
+            latitude: gps.latitude,
+            longitude: gps.longitude,
+            altitude: gps.altitude,
         }
     }
 }
@@ -86,6 +144,26 @@ impl From<ir::Inline> for InlineDto {
     }
 }
 
+impl From<ir::BoundingBox> for BoundingBoxDto {
+    fn from(bbox: ir::BoundingBox) -> Self {
+        BoundingBoxDto {
+            x0: bbox.x0,
+            y0: bbox.y0,
+            x1: bbox.x1,
+            y1: bbox.y1,
+        }
+    }
+}
+
+impl From<ir::SourceLocation> for SourceLocationDto {
+    fn from(location: ir::SourceLocation) -> Self {
+        SourceLocationDto {
+            page: location.page,
+            bbox: location.bbox.map(Into::into),
+        }
+    }
+}
+
 impl From<ir::TableCell> for TableCellDto {
     fn from(cell: ir::TableCell) -> Self {
         TableCellDto {
@@ -107,6 +185,7 @@ impl From<ir::TableBlock> for TableBlockDto {
     fn from(table: ir::TableBlock) -> Self {
         TableBlockDto {
             rows: table.rows.into_iter().map(Into::into).collect(),
+            location: table.location.map(Into::into),
         }
     }
 }
@@ -114,12 +193,18 @@ impl From<ir::TableBlock> for TableBlockDto {
 impl From<ir::ParsedBlock> for ParsedBlockDto {
     fn from(block: ir::ParsedBlock) -> Self {
         match block {
-            ir::ParsedBlock::Heading { level, inlines } => ParsedBlockDto::Heading {
+            ir::ParsedBlock::Heading {
+                level,
+                inlines,
+                location,
+            } => ParsedBlockDto::Heading {
                 level,
                 inlines: inlines.into_iter().map(Into::into).collect(),
+                location: location.map(Into::into),
             },
-            ir::ParsedBlock::Paragraph { inlines } => ParsedBlockDto::Paragraph {
+            ir::ParsedBlock::Paragraph { inlines, location } => ParsedBlockDto::Paragraph {
                 inlines: inlines.into_iter().map(Into::into).collect(),
+                location: location.map(Into::into),
             },
             ir::ParsedBlock::ListItem {
                 level,
@@ -145,3 +230,76 @@ impl From<ir::ParsedBlock> for ParsedBlockDto {
         }
     }
 }
+
+impl From<ChunkUnitDto> for ChunkUnit {
+    fn from(unit: ChunkUnitDto) -> Self {
+        match unit {
+            ChunkUnitDto::Chars => ChunkUnit::Chars,
+            ChunkUnitDto::Tokens => ChunkUnit::Tokens,
+        }
+    }
+}
+
+impl From<&ChunkQuery> for ChunkOptions {
+    fn from(query: &ChunkQuery) -> Self {
+        let defaults = ChunkOptions::new(query.unit.map(Into::into).unwrap_or_default());
+        ChunkOptions {
+            max_size: query.max_size.unwrap_or(defaults.max_size),
+            overlap: query.overlap.unwrap_or(defaults.overlap),
+            ..defaults
+        }
+    }
+}
+
+impl From<Chunk> for ChunkDto {
+    fn from(chunk: Chunk) -> Self {
+        ChunkDto {
+            index: chunk.index,
+            text: chunk.text,
+            heading_path: chunk.heading_path,
+            start_offset: chunk.start_offset,
+            end_offset: chunk.end_offset,
+            first_block: chunk.first_block,
+            last_block: chunk.last_block,
+            size: chunk.size,
+            page: chunk.page,
+        }
+    }
+}
+
+impl From<JobState> for JobStateDto {
+    fn from(state: JobState) -> Self {
+        match state {
+            JobState::Queued => JobStateDto::Queued,
+            JobState::Running => JobStateDto::Running,
+            JobState::Succeeded => JobStateDto::Succeeded,
+            JobState::Failed => JobStateDto::Failed,
+            JobState::Cancelled => JobStateDto::Cancelled,
+        }
+    }
+}
+
+impl From<JobProgress> for JobProgressDto {
+    fn from(progress: JobProgress) -> Self {
+        JobProgressDto {
+            processed_bytes: progress.processed_bytes,
+            total_bytes: progress.total_bytes,
+        }
+    }
+}
+
+impl From<ParseJob> for ParseJobDto {
+    fn from(job: ParseJob) -> Self {
+        ParseJobDto {
+            id: job.id,
+            state: job.state.into(),
+            source: job.source,
+            created_at: job.created_at,
+            started_at: job.started_at,
+            finished_at: job.finished_at,
+            progress: job.progress.into(),
+            expires_at: job.expires_at,
+            error: job.error,
+        }
+    }
+}
@@ -1,10 +1,12 @@
 pub mod dto;
 pub mod error;
+pub mod format;
 pub mod handlers;
 mod mappers;
 pub mod routes;
 
 pub use dto::*;
 pub use error::*;
+pub use format::*;
 pub use handlers::*;
 pub use routes::*;
@@ -1,4 +1,5 @@
 use crate::api::rest::handlers;
+use crate::domain::jobs::ParseJobManager;
 use crate::domain::service::FileParserService;
 use axum::{Extension, Router};
 use modkit::api::{
@@ -50,6 +51,7 @@ pub fn register_routes(
     mut router: Router,
     openapi: &dyn OpenApiRegistry,
     service: Arc<FileParserService>,
+    jobs: Arc<ParseJobManager>,
 ) -> Router {
     // Explicitly register nested schemas that are only transitively referenced
     // These are used within ParsedBlockDto but not directly in any endpoint
@@ -59,7 +61,21 @@ pub fn register_routes(
     let _ = ensure_schema::<crate::api::rest::dto::TableCellDto>(openapi);
     let _ = ensure_schema::<crate::api::rest::dto::InlineStyleDto>(openapi);
     let _ = ensure_schema::<crate::api::rest::dto::InlineDto>(openapi);
+    // Streamed for `format=ir`, so not attached to any response
+    let _ = ensure_schema::<crate::api::rest::dto::ParsedDocumentIrDto>(openapi);
 
+    router = register_parse_routes(router, openapi);
+    router = register_chunk_routes(router, openapi);
+    router = register_job_routes(router, openapi);
+
+    router = router.layer(Extension(service));
+    router = router.layer(Extension(jobs));
+
+    router
+}
+
+/// Routes parsing a file within the request
+fn register_parse_routes(mut router: Router, openapi: &dyn OpenApiRegistry) -> Router {
     // GET /file-parser/v1/info - Get information about available file parsers
     router = OperationBuilder::get("/file-parser/v1/info")
         .operation_id("file_parser.get_parser_info")
@@ -89,6 +105,12 @@ pub fn register_routes(
             "Render Markdown output if true (optional, default false)",
             "boolean",
         )
+        .query_param_typed(
+            "format",
+            false,
+            "Response format: json (default), ir, markdown, html or text; the Accept header is used when absent",
+            "string",
+        )
         .json_request::<crate::api::rest::dto::ParseLocalFileRequest>(openapi, "Local file path")
         .allow_content_types(&["application/json"])
         .handler(handlers::parse_local)
@@ -114,6 +136,12 @@ pub fn register_routes(
             "Render Markdown output if true (optional, default false)",
             "boolean",
         )
+        .query_param_typed(
+            "format",
+            false,
+            "Response format: json (default), ir, markdown, html or text; the Accept header is used when absent",
+            "string",
+        )
         .query_param_typed(
             "filename",
             false,
@@ -144,6 +172,12 @@ pub fn register_routes(
             "Render Markdown output if true (optional, default false)",
             "boolean",
         )
+        .query_param_typed(
+            "format",
+            false,
+            "Response format: json (default), ir, markdown, html or text; the Accept header is used when absent",
+            "string",
+        )
         .json_request::<crate::api::rest::dto::ParseUrlRequest>(openapi, "URL to file")
         .allow_content_types(&["application/json"])
         .handler(handlers::parse_url)
@@ -163,6 +197,12 @@ pub fn register_routes(
         .tag("File Parser")
         .require_auth(&Resource::FileParser, &Action::Read)
         .require_license_features::<License>([])
+        .query_param_typed(
+            "format",
+            false,
+            "Response format: json, ir, markdown (default), html or text; the Accept header is used when absent",
+            "string",
+        )
         .json_request::<crate::api::rest::dto::ParseLocalFileRequest>(openapi, "Local file path")
         .allow_content_types(&["application/json"])
         .handler(handlers::parse_local_markdown)
@@ -177,6 +217,12 @@ pub fn register_routes(
         .tag("File Parser")
         .require_auth(&Resource::FileParser, &Action::Read)
         .require_license_features::<License>([])
+        .query_param_typed(
+            "format",
+            false,
+            "Response format: json, ir, markdown (default), html or text; the Accept header is used when absent",
+            "string",
+        )
         .multipart_file_request("file", Some("File to parse and stream as Markdown"))
         .handler(handlers::upload_and_parse_markdown)
         .text_response(http::StatusCode::OK, "Markdown stream", "text/markdown")
@@ -191,6 +237,12 @@ pub fn register_routes(
         .tag("File Parser")
         .require_auth(&Resource::FileParser, &Action::Read)
         .require_license_features::<License>([])
+        .query_param_typed(
+            "format",
+            false,
+            "Response format: json, ir, markdown (default), html or text; the Accept header is used when absent",
+            "string",
+        )
         .json_request::<crate::api::rest::dto::ParseUrlRequest>(openapi, "URL to file")
         .allow_content_types(&["application/json"])
         .handler(handlers::parse_url_markdown)
@@ -199,7 +251,253 @@ pub fn register_routes(
         .error_415(openapi)
         .register(router, openapi);
 
-    router = router.layer(Extension(service));
+    router
+}
+
+/// Routes splitting a parsed file into chunks
+fn register_chunk_routes(mut router: Router, openapi: &dyn OpenApiRegistry) -> Router {
+    // POST /file-parser/v1/parse-local/chunks - Parse a local file into chunks
+    router = OperationBuilder::post("/file-parser/v1/parse-local/chunks")
+        .operation_id("file_parser.parse_local_chunks")
+        .summary("Parse a local file and split it into chunks")
+        .tag("File Parser")
+        .require_auth(&Resource::FileParser, &Action::Read)
+        .require_license_features::<License>([])
+        .query_param_typed(
+            "unit",
+            false,
+            "Unit of max_size and overlap: chars (default) or tokens",
+            "string",
+        )
+        .query_param_typed(
+            "max_size",
+            false,
+            "Largest chunk size (default 1000 chars or 250 tokens)",
+            "integer",
+        )
+        .query_param_typed(
+            "overlap",
+            false,
+            "Size repeated between consecutive chunks of a section (default 100 chars or 25 tokens)",
+            "integer",
+        )
+        .json_request::<crate::api::rest::dto::ParseLocalFileRequest>(openapi, "Local file path")
+        .allow_content_types(&["application/json"])
+        .handler(handlers::parse_local_chunks)
+        .json_response_with_schema::<crate::api::rest::dto::ChunkedDocResponseDto>(
+            openapi,
+            http::StatusCode::OK,
+            "Document chunks with source offsets",
+        )
+        .standard_errors(openapi)
+        .error_415(openapi)
+        .register(router, openapi);
+
+    // POST /file-parser/v1/upload/chunks - Upload a file and split it into chunks
+    router = OperationBuilder::post("/file-parser/v1/upload/chunks")
+        .operation_id("file_parser.upload_chunks")
+        .summary("Upload a file and split it into chunks")
+        .tag("File Parser")
+        .require_auth(&Resource::FileParser, &Action::Read)
+        .require_license_features::<License>([])
+        .query_param_typed(
+            "filename",
+            false,
+            "Optional original filename (used to determine file type if Content-Type is ambiguous)",
+            "string",
+        )
+        .query_param_typed(
+            "unit",
+            false,
+            "Unit of max_size and overlap: chars (default) or tokens",
+            "string",
+        )
+        .query_param_typed(
+            "max_size",
+            false,
+            "Largest chunk size (default 1000 chars or 250 tokens)",
+            "integer",
+        )
+        .query_param_typed(
+            "overlap",
+            false,
+            "Size repeated between consecutive chunks of a section (default 100 chars or 25 tokens)",
+            "integer",
+        )
+        .octet_stream_request(Some("Raw file bytes to parse"))
+        .handler(handlers::upload_and_chunk)
+        .json_response_with_schema::<crate::api::rest::dto::ChunkedDocResponseDto>(
+            openapi,
+            http::StatusCode::OK,
+            "Document chunks with source offsets",
+        )
+        .standard_errors(openapi)
+        .error_415(openapi)
+        .register(router, openapi);
+
+    // POST /file-parser/v1/parse-url/chunks - Parse a file from a URL into chunks
+    router = OperationBuilder::post("/file-parser/v1/parse-url/chunks")
+        .operation_id("file_parser.parse_url_chunks")
+        .summary("Parse a file from a URL and split it into chunks")
+        .tag("File Parser")
+        .require_auth(&Resource::FileParser, &Action::Read)
+        .require_license_features::<License>([])
+        .query_param_typed(
+            "unit",
+            false,
+            "Unit of max_size and overlap: chars (default) or tokens",
+            "string",
+        )
+        .query_param_typed(
+            "max_size",
+            false,
+            "Largest chunk size (default 1000 chars or 250 tokens)",
+            "integer",
+        )
+        .query_param_typed(
+            "overlap",
+            false,
+            "Size repeated between consecutive chunks of a section (default 100 chars or 25 tokens)",
+            "integer",
+        )
+        .json_request::<crate::api::rest::dto::ParseUrlRequest>(openapi, "URL to file")
+        .allow_content_types(&["application/json"])
+        .handler(handlers::parse_url_chunks)
+        .json_response_with_schema::<crate::api::rest::dto::ChunkedDocResponseDto>(
+            openapi,
+            http::StatusCode::OK,
+            "Document chunks with source offsets",
+        )
+        .standard_errors(openapi)
+        .error_415(openapi)
+        .register(router, openapi);
+
+    router
+}
+
+/// Routes of asynchronous parse jobs
+fn register_job_routes(mut router: Router, openapi: &dyn OpenApiRegistry) -> Router {
+    // POST /file-parser/v1/jobs/upload - Upload a file and parse it asynchronously
+    router = OperationBuilder::post("/file-parser/v1/jobs/upload")
+        .operation_id("file_parser.submit_upload_job")
+        .summary("Upload a file and queue a job parsing it")
+        .description("Returns at once with the queued job; poll it for its state and fetch the result once it has succeeded.")
+        .tag("File Parser")
+        .require_auth(&Resource::FileParser, &Action::Read)
+        .require_license_features::<License>([])
+        .query_param_typed(
+            "filename",
+            false,
+            "Optional original filename (used to determine file type if Content-Type is ambiguous)",
+            "string",
+        )
+        .octet_stream_request(Some("Raw file bytes to parse"))
+        .handler(handlers::submit_upload_job)
+        .json_response_with_schema::<crate::api::rest::dto::ParseJobDto>(
+            openapi,
+            http::StatusCode::ACCEPTED,
+            "Queued parse job",
+        )
+        .problem_response(openapi, http::StatusCode::TOO_MANY_REQUESTS, "Too many pending jobs")
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    // POST /file-parser/v1/jobs/url - Parse a file from a URL asynchronously
+    router = OperationBuilder::post("/file-parser/v1/jobs/url")
+        .operation_id("file_parser.submit_url_job")
+        .summary("Queue a job parsing a file from a URL")
+        .description("Returns at once with the queued job; poll it for its state and fetch the result once it has succeeded.")
+        .tag("File Parser")
+        .require_auth(&Resource::FileParser, &Action::Read)
+        .require_license_features::<License>([])
+        .json_request::<crate::api::rest::dto::ParseUrlRequest>(openapi, "URL to file")
+        .allow_content_types(&["application/json"])
+        .handler(handlers::submit_url_job)
+        .json_response_with_schema::<crate::api::rest::dto::ParseJobDto>(
+            openapi,
+            http::StatusCode::ACCEPTED,
+            "Queued parse job",
+        )
+        .problem_response(openapi, http::StatusCode::TOO_MANY_REQUESTS, "Too many pending jobs")
+        .standard_errors(openapi)
+        .error_415(openapi)
+        .register(router, openapi);
+
+    // GET /file-parser/v1/jobs/{job_id} - Get the state of a parse job
+    router = OperationBuilder::get("/file-parser/v1/jobs/{job_id}")
+        .operation_id("file_parser.get_job")
+        .summary("Get the state of a parse job")
+        .tag("File Parser")
+        .require_auth(&Resource::FileParser, &Action::Read)
+        .require_license_features::<License>([])
+        .path_param("job_id", "ID of the parse job")
+        .handler(handlers::get_job)
+        .json_response_with_schema::<crate::api::rest::dto::ParseJobDto>(
+            openapi,
+            http::StatusCode::OK,
+            "Parse job",
+        )
+        .problem_response(
+            openapi,
+            http::StatusCode::NOT_FOUND,
+            "Job not found or expired",
+        )
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    // GET /file-parser/v1/jobs/{job_id}/result - Get the document parsed by a job
+    router = OperationBuilder::get("/file-parser/v1/jobs/{job_id}/result")
+        .operation_id("file_parser.get_job_result")
+        .summary("Get the document parsed by a job")
+        .tag("File Parser")
+        .require_auth(&Resource::FileParser, &Action::Read)
+        .require_license_features::<License>([])
+        .path_param("job_id", "ID of the parse job")
+        .query_param_typed(
+            "render_markdown",
+            false,
+            "Render Markdown output if true (optional, default false)",
+            "boolean",
+        )
+        .query_param_typed(
+            "format",
+            false,
+            "Response format: json (default), ir, markdown, html or text; the Accept header is used when absent",
+            "string",
+        )
+        .handler(handlers::get_job_result)
+        .json_response_with_schema::<crate::api::rest::dto::ParsedDocResponseDto>(
+            openapi,
+            http::StatusCode::OK,
+            "Parsed document with optional markdown",
+        )
+        .problem_response(openapi, http::StatusCode::NOT_FOUND, "Job not found or expired")
+        .problem_response(openapi, http::StatusCode::CONFLICT, "Job has not succeeded")
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    // POST /file-parser/v1/jobs/{job_id}/cancel - Cancel a parse job
+    router = OperationBuilder::post("/file-parser/v1/jobs/{job_id}/cancel")
+        .operation_id("file_parser.cancel_job")
+        .summary("Cancel a parse job")
+        .description("Cancel a queued or running job. Finished jobs are returned unchanged.")
+        .tag("File Parser")
+        .require_auth(&Resource::FileParser, &Action::Read)
+        .require_license_features::<License>([])
+        .path_param("job_id", "ID of the parse job")
+        .handler(handlers::cancel_job)
+        .json_response_with_schema::<crate::api::rest::dto::ParseJobDto>(
+            openapi,
+            http::StatusCode::OK,
+            "Parse job",
+        )
+        .problem_response(
+            openapi,
+            http::StatusCode::NOT_FOUND,
+            "Job not found or expired",
+        )
+        .standard_errors(openapi)
+        .register(router, openapi);
 
     router
 }
@@ -1,4 +1,8 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 /// Configuration for the `file_parser` module
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -8,6 +12,21 @@ pub struct FileParserConfig {
     pub max_file_size_mb: u64,
     #[serde(default = "default_download_timeout_secs")]
     pub download_timeout_secs: u64,
+    /// Which remote URLs `parse_url` may fetch
+    #[serde(default)]
+    pub url_fetch: UrlFetchConfig,
+    /// Which directories `parse_local` may read from
+    #[serde(default)]
+    pub local_files: LocalFilesConfig,
+    /// Limits for parsing the attachments of e-mail messages
+    #[serde(default)]
+    pub email: EmailConfig,
+    /// Decoding limits and metadata handling of images
+    #[serde(default)]
+    pub images: ImageConfig,
+    /// Worker pool and retention of asynchronous parse jobs
+    #[serde(default)]
+    pub jobs: JobsConfig,
 }
 
 impl Default for FileParserConfig {
@@ -15,6 +34,159 @@ impl Default for FileParserConfig {
         Self {
             max_file_size_mb: default_max_file_size_mb(),
             download_timeout_secs: default_download_timeout_secs(),
+            url_fetch: UrlFetchConfig::default(),
+            local_files: LocalFilesConfig::default(),
+            email: EmailConfig::default(),
+            images: ImageConfig::default(),
+            jobs: JobsConfig::default(),
+        }
+    }
+}
+
+/// Outbound fetch policy for `parse_url`, applied to the requested URL and
+/// to every redirect
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UrlFetchConfig {
+    /// URL schemes that may be fetched
+    #[serde(default = "default_allowed_schemes")]
+    pub allowed_schemes: Vec<String>,
+    /// Hosts that may be fetched; empty allows any host not denied.
+    /// `*.example.com` matches subdomains of `example.com`.
+    #[serde(default)]
+    pub allowed_hosts: Vec<String>,
+    /// Hosts that may never be fetched, with the same wildcard syntax
+    #[serde(default)]
+    pub denied_hosts: Vec<String>,
+    /// Refuse loopback, private, link-local and other non-public addresses,
+    /// checked on the resolved addresses
+    #[serde(default = "default_block_private_ips")]
+    pub block_private_ips: bool,
+    /// Maximum number of redirects to follow
+    #[serde(default = "default_max_redirects")]
+    pub max_redirects: usize,
+}
+
+impl Default for UrlFetchConfig {
+    fn default() -> Self {
+        Self {
+            allowed_schemes: default_allowed_schemes(),
+            allowed_hosts: Vec::new(),
+            denied_hosts: Vec::new(),
+            block_private_ips: default_block_private_ips(),
+            max_redirects: default_max_redirects(),
+        }
+    }
+}
+
+/// Sandbox for `parse_local`. With no roots configured, local parsing is
+/// refused for every path.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LocalFilesConfig {
+    /// Directories every tenant may read files from
+    #[serde(default)]
+    pub allowed_roots: Vec<PathBuf>,
+    /// Additional directories per tenant ID, only readable by that tenant
+    #[serde(default)]
+    pub tenant_roots: HashMap<Uuid, Vec<PathBuf>>,
+}
+
+/// Attachments of e-mail messages are parsed like uploaded files, within
+/// these limits
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EmailConfig {
+    /// How many levels of attached messages are followed; attachments below
+    /// that are listed but not parsed
+    #[serde(default = "default_max_attachment_depth")]
+    pub max_attachment_depth: usize,
+    /// Total decoded size of the attachments parsed for one file
+    #[serde(default = "default_max_attachments_mb")]
+    pub max_attachments_mb: u64,
+}
+
+impl Default for EmailConfig {
+    fn default() -> Self {
+        Self {
+            max_attachment_depth: default_max_attachment_depth(),
+            max_attachments_mb: default_max_attachments_mb(),
+        }
+    }
+}
+
+/// Images are checked from their headers before they are accepted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ImageConfig {
+    /// Largest width times height accepted, against decompression bombs
+    #[serde(default = "default_max_image_pixels")]
+    pub max_pixels: u64,
+    /// What to do with animated GIFs
+    #[serde(default)]
+    pub animated_gif: AnimatedGifPolicy,
+    /// Remove the GPS position from the EXIF data, both from the metadata
+    /// and from the embedded image
+    #[serde(default = "default_strip_gps")]
+    pub strip_gps: bool,
+}
+
+impl Default for ImageConfig {
+    fn default() -> Self {
+        Self {
+            max_pixels: default_max_image_pixels(),
+            animated_gif: AnimatedGifPolicy::default(),
+            strip_gps: default_strip_gps(),
+        }
+    }
+}
+
+/// Handling of GIFs with more than one frame
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnimatedGifPolicy {
+    /// Refuse the file
+    Reject,
+    /// Keep only the first frame
+    #[default]
+    FirstFrame,
+}
+
+/// Asynchronous parse jobs run on a shared pool of workers, with limits per
+/// tenant
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct JobsConfig {
+    /// Jobs running at once over all tenants
+    #[serde(default = "default_job_workers")]
+    pub workers: usize,
+    /// Jobs of one tenant running at once
+    #[serde(default = "default_max_running_jobs_per_tenant")]
+    pub max_running_per_tenant: usize,
+    /// Queued and running jobs of one tenant; further submissions are refused
+    #[serde(default = "default_max_pending_jobs_per_tenant")]
+    pub max_pending_per_tenant: usize,
+    /// Queued and running jobs over all tenants
+    #[serde(default = "default_max_pending_jobs")]
+    pub max_pending: usize,
+    /// Size of the uploads of queued and running jobs over all tenants, which
+    /// are held in memory until their job finishes
+    #[serde(default = "default_max_pending_upload_mb")]
+    pub max_pending_upload_mb: u64,
+    /// How long finished jobs and their results are kept
+    #[serde(default = "default_job_result_ttl_secs")]
+    pub result_ttl_secs: u64,
+}
+
+impl Default for JobsConfig {
+    fn default() -> Self {
+        Self {
+            workers: default_job_workers(),
+            max_running_per_tenant: default_max_running_jobs_per_tenant(),
+            max_pending_per_tenant: default_max_pending_jobs_per_tenant(),
+            max_pending: default_max_pending_jobs(),
+            max_pending_upload_mb: default_max_pending_upload_mb(),
+            result_ttl_secs: default_job_result_ttl_secs(),
         }
     }
 }
@@ -26,3 +198,55 @@ fn default_max_file_size_mb() -> u64 {
 fn default_download_timeout_secs() -> u64 {
     60
 }
+
+fn default_allowed_schemes() -> Vec<String> {
+    vec!["https".to_owned(), "http".to_owned()]
+}
+
+fn default_block_private_ips() -> bool {
+    true
+}
+
+fn default_max_redirects() -> usize {
+    5
+}
+
+fn default_max_attachment_depth() -> usize {
+    3
+}
+
+fn default_max_attachments_mb() -> u64 {
+    50
+}
+
+fn default_max_image_pixels() -> u64 {
+    100_000_000
+}
+
+fn default_strip_gps() -> bool {
+    true
+}
+
+fn default_job_workers() -> usize {
+    4
+}
+
+fn default_max_running_jobs_per_tenant() -> usize {
+    2
+}
+
+fn default_max_pending_jobs_per_tenant() -> usize {
+    20
+}
+
+fn default_max_pending_jobs() -> usize {
+    200
+}
+
+fn default_max_pending_upload_mb() -> u64 {
+    1024
+}
+
+fn default_job_result_ttl_secs() -> u64 {
+    3600
+}
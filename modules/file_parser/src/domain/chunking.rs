@@ -0,0 +1,716 @@
+use crate::domain::error::DomainError;
+use crate::domain::ir::{Inline, ParsedBlock, ParsedDocument};
+use crate::domain::markdown::{MarkdownRenderIter, MarkdownRenderer};
+
+/// Unit the size limits of a chunk are counted in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChunkUnit {
+    /// Unicode characters of the Markdown text
+    #[default]
+    Chars,
+    /// Whitespace-separated words, a tokenizer-independent estimate of tokens
+    Tokens,
+}
+
+/// Size limits for splitting a document into chunks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkOptions {
+    pub unit: ChunkUnit,
+    /// Largest chunk size; only a single word, table row or code line can
+    /// exceed it
+    pub max_size: usize,
+    /// How much of the end of a chunk is repeated at the start of the next
+    /// one, when both are in the same section
+    pub overlap: usize,
+}
+
+impl Default for ChunkOptions {
+    fn default() -> Self {
+        Self::new(ChunkUnit::Chars)
+    }
+}
+
+impl ChunkOptions {
+    /// Default limits for the given unit: 1000 characters with 100 of
+    /// overlap, or 250 tokens with 25 of overlap
+    #[must_use]
+    pub fn new(unit: ChunkUnit) -> Self {
+        match unit {
+            ChunkUnit::Chars => Self {
+                unit,
+                max_size: 1000,
+                overlap: 100,
+            },
+            ChunkUnit::Tokens => Self {
+                unit,
+                max_size: 250,
+                overlap: 25,
+            },
+        }
+    }
+
+    /// Check that the limits leave room for progress
+    ///
+    /// # Errors
+    /// Returns `DomainError::InvalidRequest` if `max_size` is 0 or `overlap`
+    /// is not smaller than `max_size`.
+    pub fn validate(&self) -> Result<(), DomainError> {
+        if self.max_size == 0 {
+            return Err(DomainError::invalid_request(
+                "max_size must be greater than 0",
+            ));
+        }
+        if self.overlap >= self.max_size {
+            return Err(DomainError::invalid_request(format!(
+                "overlap ({}) must be smaller than max_size ({})",
+                self.overlap, self.max_size
+            )));
+        }
+        Ok(())
+    }
+
+    fn measure(&self, text: &str) -> usize {
+        match self.unit {
+            ChunkUnit::Chars => text.chars().count(),
+            ChunkUnit::Tokens => text.split_whitespace().count(),
+        }
+    }
+
+    /// Size added by the line breaks joining `lines` lines
+    fn line_breaks(&self, lines: usize) -> usize {
+        match self.unit {
+            ChunkUnit::Chars => lines.saturating_sub(1),
+            ChunkUnit::Tokens => 0,
+        }
+    }
+
+    fn joined_size<'a>(&self, pieces: impl Iterator<Item = &'a Piece>) -> usize {
+        let mut total = 0;
+        let mut previous: Option<&Piece> = None;
+        for piece in pieces {
+            if let Some(previous) = previous {
+                total += self.measure(joiner(previous, piece));
+            }
+            total += piece.size;
+            previous = Some(piece);
+        }
+        total
+    }
+}
+
+/// A part of a document sized for retrieval
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chunk {
+    /// Position of the chunk in the document, from 0
+    pub index: usize,
+    /// Markdown text of the chunk
+    pub text: String,
+    /// Headings the chunk is under, outermost first
+    pub heading_path: Vec<String>,
+    /// Start of the chunk, in characters of `MarkdownRenderer::render` output
+    pub start_offset: usize,
+    /// End of the chunk (exclusive), in characters of `MarkdownRenderer::render` output
+    pub end_offset: usize,
+    /// Index of the first block the chunk takes text from
+    pub first_block: usize,
+    /// Index of the last block the chunk takes text from
+    pub last_block: usize,
+    /// Size of `text` in the requested unit
+    pub size: usize,
+    /// Page of the first block with a known location
+    pub page: Option<u32>,
+}
+
+/// Splits a `ParsedDocument` into overlapping chunks along block boundaries
+///
+/// Every heading starts a new chunk. Blocks are packed into chunks up to the
+/// size limit; a block larger than the limit is split between words, while
+/// tables are only split between rows and code blocks between lines, with the
+/// table header or code fence repeated in every part.
+pub struct Chunker;
+
+impl Chunker {
+    /// Split a document into chunks
+    ///
+    /// # Errors
+    /// Returns `DomainError::InvalidRequest` if the options are invalid.
+    pub fn chunk(doc: &ParsedDocument, options: &ChunkOptions) -> Result<Vec<Chunk>, DomainError> {
+        options.validate()?;
+
+        let mut offset = MarkdownRenderIter::render_header(doc).chars().count();
+        let mut headings: Vec<(u8, String)> = Vec::new();
+        let mut sections = Vec::with_capacity(doc.blocks.len());
+        let mut pieces = Vec::new();
+
+        for (index, block) in doc.blocks.iter().enumerate() {
+            let mut markdown = String::new();
+            MarkdownRenderer::render_block(block, &mut markdown);
+
+            if let ParsedBlock::Heading { level, inlines, .. } = block {
+                headings.retain(|(outer, _)| outer < level);
+                headings.push((*level, Inline::plain_text(inlines).trim().to_owned()));
+            }
+            sections.push(BlockInfo {
+                heading_path: headings.iter().map(|(_, text)| text.clone()).collect(),
+                page: block_page(block),
+            });
+
+            split_block(block, index, &markdown, offset, options, &mut pieces);
+            offset += markdown.chars().count();
+        }
+
+        Ok(pack(pieces, options, &sections))
+    }
+}
+
+/// Where a block sits in the document
+struct BlockInfo {
+    heading_path: Vec<String>,
+    page: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PieceKind {
+    Heading,
+    /// Prose, which may be cut between words
+    Text,
+    /// Table rows or code lines, which stay whole
+    Rows,
+}
+
+/// A block, or part of one, that goes into a chunk whole
+#[derive(Debug, Clone)]
+struct Piece {
+    text: String,
+    /// Character range in the rendered Markdown
+    start: usize,
+    end: usize,
+    block: usize,
+    size: usize,
+    kind: PieceKind,
+}
+
+fn split_block(
+    block: &ParsedBlock,
+    index: usize,
+    markdown: &str,
+    offset: usize,
+    options: &ChunkOptions,
+    out: &mut Vec<Piece>,
+) {
+    let kind = match block {
+        // Rules and page breaks carry no content
+        ParsedBlock::HorizontalRule | ParsedBlock::PageBreak => return,
+        ParsedBlock::Heading { .. } => PieceKind::Heading,
+        ParsedBlock::Table(_) | ParsedBlock::CodeBlock { .. } => PieceKind::Rows,
+        _ => PieceKind::Text,
+    };
+    let text = markdown.trim();
+    if text.is_empty() {
+        return;
+    }
+    let leading = markdown.len() - markdown.trim_start().len();
+    let start = offset + markdown[..leading].chars().count();
+
+    let size = options.measure(text);
+    if size <= options.max_size || kind == PieceKind::Heading {
+        out.push(Piece {
+            text: text.to_owned(),
+            start,
+            end: start + text.chars().count(),
+            block: index,
+            size,
+            kind,
+        });
+        return;
+    }
+
+    match block {
+        ParsedBlock::Table(_) => split_lines(text, start, index, (2, 0), options, out),
+        ParsedBlock::CodeBlock { .. } => split_lines(text, start, index, (1, 1), options, out),
+        _ => {
+            // Leave room for the overlap carried over from the previous part
+            let limit = options
+                .max_size
+                .saturating_sub(options.overlap + options.measure(" "))
+                .max(1);
+            let words = word_spans(text);
+            let mut first = 0;
+            while first < words.len() {
+                let mut last = first;
+                while last + 1 < words.len() && span_size(&words, first, last + 1, options) <= limit
+                {
+                    last += 1;
+                }
+                out.push(text_piece(text, start, index, &words, first, last, options));
+                first = last + 1;
+            }
+        }
+    }
+}
+
+/// Split a table or code block between lines, repeating the `frame` of
+/// leading and trailing lines (table header, code fences) in every part
+fn split_lines(
+    text: &str,
+    start: usize,
+    block: usize,
+    (head, tail): (usize, usize),
+    options: &ChunkOptions,
+    out: &mut Vec<Piece>,
+) {
+    let lines: Vec<&str> = text.split('\n').collect();
+    if lines.len() <= head + tail + 1 {
+        out.push(Piece {
+            text: text.to_owned(),
+            start,
+            end: start + text.chars().count(),
+            block,
+            size: options.measure(text),
+            kind: PieceKind::Rows,
+        });
+        return;
+    }
+
+    let mut line_starts = Vec::with_capacity(lines.len());
+    let mut position = start;
+    for line in &lines {
+        line_starts.push(position);
+        position += line.chars().count() + 1;
+    }
+    let text_end = position - 1;
+
+    let body = head..lines.len() - tail;
+    let frame: Vec<&str> = lines[..head]
+        .iter()
+        .chain(&lines[body.end..])
+        .copied()
+        .collect();
+    let frame_size: usize = frame.iter().map(|line| options.measure(line)).sum();
+
+    let mut first = body.start;
+    while first < body.end {
+        let mut last = first;
+        let mut size = frame_size + options.measure(lines[first]);
+        while last + 1 < body.end {
+            let next = size + options.measure(lines[last + 1]);
+            if next + options.line_breaks(frame.len() + last + 2 - first) > options.max_size {
+                break;
+            }
+            size = next;
+            last += 1;
+        }
+
+        let part: Vec<&str> = lines[..head]
+            .iter()
+            .chain(&lines[first..=last])
+            .chain(&lines[body.end..])
+            .copied()
+            .collect();
+        let part = part.join("\n");
+        let size = options.measure(&part);
+        out.push(Piece {
+            text: part,
+            start: if first == body.start {
+                start
+            } else {
+                line_starts[first]
+            },
+            end: if last + 1 == body.end {
+                text_end
+            } else {
+                line_starts[last + 1] - 1
+            },
+            block,
+            size,
+            kind: PieceKind::Rows,
+        });
+        first = last + 1;
+    }
+}
+
+/// A word as byte and character ranges within its text
+#[derive(Debug, Clone, Copy)]
+struct WordSpan {
+    byte_start: usize,
+    byte_end: usize,
+    char_start: usize,
+    char_end: usize,
+}
+
+fn word_spans(text: &str) -> Vec<WordSpan> {
+    let mut spans = Vec::new();
+    let mut current: Option<(usize, usize)> = None;
+    let mut chars = 0;
+    for (byte, ch) in text.char_indices() {
+        if ch.is_whitespace() {
+            if let Some((byte_start, char_start)) = current.take() {
+                spans.push(WordSpan {
+                    byte_start,
+                    byte_end: byte,
+                    char_start,
+                    char_end: chars,
+                });
+            }
+        } else if current.is_none() {
+            current = Some((byte, chars));
+        }
+        chars += 1;
+    }
+    if let Some((byte_start, char_start)) = current {
+        spans.push(WordSpan {
+            byte_start,
+            byte_end: text.len(),
+            char_start,
+            char_end: chars,
+        });
+    }
+    spans
+}
+
+/// Size of the text from word `first` through word `last`
+fn span_size(words: &[WordSpan], first: usize, last: usize, options: &ChunkOptions) -> usize {
+    match options.unit {
+        ChunkUnit::Chars => words[last].char_end - words[first].char_start,
+        ChunkUnit::Tokens => last + 1 - first,
+    }
+}
+
+fn text_piece(
+    text: &str,
+    start: usize,
+    block: usize,
+    words: &[WordSpan],
+    first: usize,
+    last: usize,
+    options: &ChunkOptions,
+) -> Piece {
+    Piece {
+        text: text[words[first].byte_start..words[last].byte_end].to_owned(),
+        start: start + words[first].char_start,
+        end: start + words[last].char_end,
+        block,
+        size: span_size(words, first, last, options),
+        kind: PieceKind::Text,
+    }
+}
+
+/// Greedily fill chunks with pieces, starting a new chunk at each section
+fn pack(pieces: Vec<Piece>, options: &ChunkOptions, blocks: &[BlockInfo]) -> Vec<Chunk> {
+    let mut chunks = Vec::new();
+    let mut current: Vec<Piece> = Vec::new();
+
+    for piece in pieces {
+        let new_section = piece.kind == PieceKind::Heading
+            && current.iter().any(|p| p.kind != PieceKind::Heading);
+        if new_section {
+            flush(&current, options, blocks, &mut chunks);
+            current.clear();
+        } else if !current.is_empty()
+            && options.joined_size(current.iter().chain([&piece])) > options.max_size
+        {
+            flush(&current, options, blocks, &mut chunks);
+            current = overlap(&current, options);
+            while !current.is_empty()
+                && options.joined_size(current.iter().chain([&piece])) > options.max_size
+            {
+                current.remove(0);
+            }
+        }
+        current.push(piece);
+    }
+    flush(&current, options, blocks, &mut chunks);
+
+    chunks
+}
+
+/// The end of a full chunk to repeat in the next one: whole pieces, or the
+/// last words of a text piece, never the entire chunk
+fn overlap(current: &[Piece], options: &ChunkOptions) -> Vec<Piece> {
+    let mut tail: Vec<Piece> = Vec::new();
+    let mut size = 0;
+    for (index, piece) in current.iter().enumerate().rev() {
+        let added = piece.size
+            + tail
+                .first()
+                .map_or(0, |next| options.measure(joiner(piece, next)));
+        if index > 0 && size + added <= options.overlap {
+            tail.insert(0, piece.clone());
+            size += added;
+            continue;
+        }
+        if tail.is_empty() && piece.kind == PieceKind::Text {
+            tail.extend(last_words(piece, options));
+        }
+        break;
+    }
+    tail
+}
+
+/// The longest run of final words of `piece` that fits the overlap, short of
+/// the whole piece
+fn last_words(piece: &Piece, options: &ChunkOptions) -> Option<Piece> {
+    let words = word_spans(&piece.text);
+    let last = words.len().checked_sub(1)?;
+    let mut first = None;
+    for candidate in (1..=last).rev() {
+        if span_size(&words, candidate, last, options) > options.overlap {
+            break;
+        }
+        first = Some(candidate);
+    }
+    let first = first?;
+    Some(text_piece(
+        &piece.text,
+        piece.start,
+        piece.block,
+        &words,
+        first,
+        last,
+        options,
+    ))
+}
+
+/// Parts of one paragraph are joined by a space, anything else by a blank line
+fn joiner(previous: &Piece, next: &Piece) -> &'static str {
+    if previous.block == next.block
+        && previous.kind == PieceKind::Text
+        && next.kind == PieceKind::Text
+    {
+        " "
+    } else {
+        "\n\n"
+    }
+}
+
+fn flush(pieces: &[Piece], options: &ChunkOptions, blocks: &[BlockInfo], out: &mut Vec<Chunk>) {
+    let (Some(first), Some(last)) = (pieces.first(), pieces.last()) else {
+        return;
+    };
+    let mut text = first.text.clone();
+    for pair in pieces.windows(2) {
+        text.push_str(joiner(&pair[0], &pair[1]));
+        text.push_str(&pair[1].text);
+    }
+    let size = options.measure(&text);
+    out.push(Chunk {
+        index: out.len(),
+        text,
+        heading_path: blocks[last.block].heading_path.clone(),
+        start_offset: first.start,
+        end_offset: last.end,
+        first_block: first.block,
+        last_block: last.block,
+        size,
+        page: pieces.iter().find_map(|piece| blocks[piece.block].page),
+    });
+}
+
+fn block_page(block: &ParsedBlock) -> Option<u32> {
+    match block {
+        ParsedBlock::Heading { location, .. } | ParsedBlock::Paragraph { location, .. } => {
+            location.map(|l| l.page)
+        }
+        ParsedBlock::Table(table) => table.location.map(|l| l.page),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::domain::ir::{DocumentBuilder, ParsedSource, TableBlock, TableCell, TableRow};
+
+    fn heading(level: u8, text: &str) -> ParsedBlock {
+        ParsedBlock::Heading {
+            level,
+            inlines: vec![Inline::plain(text)],
+            location: None,
+        }
+    }
+
+    fn paragraph(text: &str) -> ParsedBlock {
+        ParsedBlock::Paragraph {
+            inlines: vec![Inline::plain(text)],
+            location: None,
+        }
+    }
+
+    fn document(blocks: Vec<ParsedBlock>) -> ParsedDocument {
+        DocumentBuilder::new(ParsedSource::LocalPath("test.txt".to_owned()))
+            .title("Test")
+            .blocks(blocks)
+            .build()
+    }
+
+    fn chars(max_size: usize, overlap: usize) -> ChunkOptions {
+        ChunkOptions {
+            unit: ChunkUnit::Chars,
+            max_size,
+            overlap,
+        }
+    }
+
+    /// The rendered Markdown between the offsets of a chunk
+    fn source(doc: &ParsedDocument, chunk: &Chunk) -> String {
+        MarkdownRenderer::render(doc)
+            .chars()
+            .skip(chunk.start_offset)
+            .take(chunk.end_offset - chunk.start_offset)
+            .collect()
+    }
+
+    #[test]
+    fn test_sections_carry_heading_path() {
+        let doc = document(vec![
+            heading(1, "Intro"),
+            paragraph("Welcome."),
+            heading(2, "Details"),
+            paragraph("More."),
+            heading(1, "End"),
+            paragraph("Bye."),
+        ]);
+
+        let chunks = Chunker::chunk(&doc, &ChunkOptions::default()).unwrap();
+
+        let paths: Vec<Vec<String>> = chunks.iter().map(|c| c.heading_path.clone()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                vec!["Intro".to_owned()],
+                vec!["Intro".to_owned(), "Details".to_owned()],
+                vec!["End".to_owned()],
+            ]
+        );
+        assert_eq!(chunks[1].text, "## Details\n\nMore.");
+        assert_eq!((chunks[1].first_block, chunks[1].last_block), (2, 3));
+        for chunk in &chunks {
+            assert_eq!(source(&doc, chunk), chunk.text);
+        }
+    }
+
+    #[test]
+    fn test_long_paragraph_overlaps() {
+        let text = (0..60)
+            .map(|i| format!("word{i}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let doc = document(vec![paragraph(&text)]);
+
+        let chunks = Chunker::chunk(&doc, &chars(50, 12)).unwrap();
+
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk.size <= 50, "{chunk:?}");
+            assert_eq!(chunk.size, chunk.text.chars().count());
+            assert_eq!(source(&doc, chunk), chunk.text);
+        }
+        for pair in chunks.windows(2) {
+            assert!(pair[1].start_offset < pair[0].end_offset);
+            assert!(pair[1].start_offset > pair[0].start_offset);
+        }
+    }
+
+    #[test]
+    fn test_no_overlap_across_sections() {
+        let doc = document(vec![
+            heading(1, "A"),
+            paragraph("alpha beta gamma"),
+            heading(1, "B"),
+            paragraph("delta"),
+        ]);
+
+        let chunks = Chunker::chunk(&doc, &chars(100, 50)).unwrap();
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[1].text, "# B\n\ndelta");
+    }
+
+    #[test]
+    fn test_table_split_between_rows() {
+        let cell = |text: &str| TableCell {
+            blocks: vec![paragraph(text)],
+        };
+        let mut rows = vec![TableRow {
+            is_header: true,
+            cells: vec![cell("Name"), cell("Value")],
+        }];
+        for i in 0..20 {
+            rows.push(TableRow {
+                is_header: false,
+                cells: vec![cell(&format!("item {i}")), cell(&format!("{}", i * 10))],
+            });
+        }
+        let doc = document(vec![ParsedBlock::Table(TableBlock {
+            rows,
+            location: None,
+        })]);
+
+        let chunks = Chunker::chunk(&doc, &chars(120, 0)).unwrap();
+
+        assert!(chunks.len() > 1);
+        let mut data_rows = 0;
+        for chunk in &chunks {
+            let lines: Vec<&str> = chunk.text.lines().collect();
+            assert_eq!(lines[0], "| Name | Value |");
+            assert_eq!(lines[1], "| --- | --- |");
+            assert!(lines.iter().all(|l| l.starts_with('|') && l.ends_with('|')));
+            data_rows += lines.len() - 2;
+        }
+        assert_eq!(data_rows, 20);
+    }
+
+    #[test]
+    fn test_code_block_split_keeps_fences() {
+        let code = (0..30)
+            .map(|i| format!("let x{i} = {i};"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let doc = document(vec![ParsedBlock::CodeBlock {
+            language: Some("rust".to_owned()),
+            code,
+        }]);
+
+        let chunks = Chunker::chunk(&doc, &chars(100, 0)).unwrap();
+
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk.text.starts_with("```rust\nlet x"));
+            assert!(chunk.text.ends_with(";\n```"));
+        }
+    }
+
+    #[test]
+    fn test_token_limits() {
+        let doc = document(vec![
+            paragraph("one two three four"),
+            paragraph("five six seven"),
+            paragraph("eight nine"),
+        ]);
+        let options = ChunkOptions {
+            unit: ChunkUnit::Tokens,
+            max_size: 7,
+            overlap: 2,
+        };
+
+        let chunks = Chunker::chunk(&doc, &options).unwrap();
+
+        let texts: Vec<&str> = chunks.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "one two three four\n\nfive six seven",
+                "six seven\n\neight nine"
+            ]
+        );
+        assert_eq!(chunks[1].size, 4);
+    }
+
+    #[test]
+    fn test_invalid_options() {
+        let doc = document(vec![paragraph("text")]);
+        assert!(Chunker::chunk(&doc, &chars(0, 0)).is_err());
+        assert!(Chunker::chunk(&doc, &chars(10, 10)).is_err());
+    }
+}
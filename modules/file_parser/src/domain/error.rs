@@ -1,4 +1,5 @@
 use thiserror::Error;
+use uuid::Uuid;
 
 /// Domain-level errors for file parsing operations
 #[derive(Error, Debug, Clone)]
@@ -6,6 +7,9 @@ pub enum DomainError {
     #[error("File not found: {path}")]
     FileNotFound { path: String },
 
+    #[error("Path not allowed: {path}")]
+    PathNotAllowed { path: String },
+
     #[error("Unsupported file type: {extension}")]
     UnsupportedFileType { extension: String },
 
@@ -21,11 +25,26 @@ pub enum DomainError {
     #[error("Invalid URL: {url}")]
     InvalidUrl { url: String },
 
+    #[error("URL not allowed: {url}: {reason}")]
+    UrlNotAllowed { url: String, reason: String },
+
     #[error("Download error: {message}")]
     DownloadError { message: String },
 
     #[error("Invalid request: {message}")]
     InvalidRequest { message: String },
+
+    #[error("Parse job not found: {id}")]
+    JobNotFound { id: Uuid },
+
+    #[error("Too many parse jobs: at most {limit} may be pending")]
+    TooManyJobs { limit: usize },
+
+    #[error("Too many pending uploads: at most {limit} bytes may be pending")]
+    TooManyPendingBytes { limit: usize },
+
+    #[error("Parse job {id} has no result: it is {state}")]
+    NoJobResult { id: Uuid, state: String },
 }
 
 impl DomainError {
@@ -33,6 +52,10 @@ impl DomainError {
         Self::FileNotFound { path: path.into() }
     }
 
+    pub fn path_not_allowed(path: impl Into<String>) -> Self {
+        Self::PathNotAllowed { path: path.into() }
+    }
+
     pub fn unsupported_file_type(extension: impl Into<String>) -> Self {
         Self::UnsupportedFileType {
             extension: extension.into(),
@@ -61,6 +84,13 @@ impl DomainError {
         Self::InvalidUrl { url: url.into() }
     }
 
+    pub fn url_not_allowed(url: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self::UrlNotAllowed {
+            url: url.into(),
+            reason: reason.into(),
+        }
+    }
+
     pub fn download_error(message: impl Into<String>) -> Self {
         Self::DownloadError {
             message: message.into(),
@@ -72,4 +102,26 @@ impl DomainError {
             message: message.into(),
         }
     }
+
+    #[must_use]
+    pub fn job_not_found(id: Uuid) -> Self {
+        Self::JobNotFound { id }
+    }
+
+    #[must_use]
+    pub fn too_many_jobs(limit: usize) -> Self {
+        Self::TooManyJobs { limit }
+    }
+
+    #[must_use]
+    pub fn too_many_pending_bytes(limit: usize) -> Self {
+        Self::TooManyPendingBytes { limit }
+    }
+
+    pub fn no_job_result(id: Uuid, state: impl Into<String>) -> Self {
+        Self::NoJobResult {
+            id,
+            state: state.into(),
+        }
+    }
 }
@@ -0,0 +1,366 @@
+//! Downloading remote files for `parse_url` under the outbound fetch policy.
+//!
+//! The policy is enforced on the requested URL, on every redirect, and on the
+//! addresses hosts resolve to, so that neither a redirect nor a DNS record
+//! can point the server at an internal service.
+
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use bytes::{Bytes, BytesMut};
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use url::{Host, Url};
+
+use crate::config::UrlFetchConfig;
+use crate::domain::error::DomainError;
+
+/// Why the fetch policy refused a URL or an address
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+struct PolicyViolation(String);
+
+/// Whether `ip` is loopback, private, link-local or otherwise not a public
+/// unicast address
+#[must_use]
+pub fn is_non_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_non_public_ipv4(ip),
+        IpAddr::V6(ip) => {
+            if let Some(ip) = ip.to_ipv4_mapped() {
+                return is_non_public_ipv4(ip);
+            }
+            let segments = ip.segments();
+            // IPv4-compatible ::/96, NAT64 64:ff9b::/96 and 6to4 2002::/16
+            // embed an IPv4 address
+            let [a, b] =
+                if segments[..6] == [0; 6] || (segments[0] == 0x0064 && segments[1] == 0xff9b) {
+                    [segments[6], segments[7]]
+                } else if segments[0] == 0x2002 {
+                    [segments[1], segments[2]]
+                } else {
+                    return ip.is_multicast()
+                    // Unique local fc00::/7, link-local fe80::/10 and
+                    // deprecated site-local fec0::/10
+                    || (segments[0] & 0xfe00) == 0xfc00
+                    || (segments[0] & 0xffc0) == 0xfe80
+                    || (segments[0] & 0xffc0) == 0xfec0
+                    // Teredo 2001::/32 tunnels to an address that cannot be
+                    // checked, and documentation 2001:db8::/32
+                    || (segments[0] == 0x2001 && matches!(segments[1], 0 | 0x0db8));
+                };
+            let [a1, a2] = a.to_be_bytes();
+            let [b1, b2] = b.to_be_bytes();
+            is_non_public_ipv4(Ipv4Addr::new(a1, a2, b1, b2))
+        }
+    }
+}
+
+fn is_non_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        // "This network" 0.0.0.0/8, shared address space 100.64.0.0/10,
+        // benchmarking 198.18.0.0/15 and reserved 240.0.0.0/4
+        || a == 0
+        || (a == 100 && (b & 0xc0) == 64)
+        || (a == 198 && (b & 0xfe) == 18)
+        || a >= 240
+}
+
+/// Whether `host` matches `pattern`: equal, or a subdomain for `*.domain`
+fn host_matches(pattern: &str, host: &str) -> bool {
+    let pattern = pattern.to_ascii_lowercase();
+    match pattern.strip_prefix("*.") {
+        Some(domain) => host
+            .strip_suffix(domain)
+            .is_some_and(|sub| sub.ends_with('.')),
+        None => host == pattern,
+    }
+}
+
+/// Checks a URL against the scheme and host rules, and its host against
+/// the address rules if it is an IP literal.
+fn check_url(policy: &UrlFetchConfig, url: &Url) -> Result<(), String> {
+    let scheme = url.scheme();
+    if !policy
+        .allowed_schemes
+        .iter()
+        .any(|allowed| allowed.eq_ignore_ascii_case(scheme))
+    {
+        return Err(format!("scheme {scheme} is not allowed"));
+    }
+
+    let host = url
+        .host_str()
+        .ok_or_else(|| "URL has no host".to_owned())?
+        .to_ascii_lowercase();
+    if policy.denied_hosts.iter().any(|p| host_matches(p, &host)) {
+        return Err(format!("host {host} is denied"));
+    }
+    if !policy.allowed_hosts.is_empty()
+        && !policy.allowed_hosts.iter().any(|p| host_matches(p, &host))
+    {
+        return Err(format!("host {host} is not allowed"));
+    }
+
+    if policy.block_private_ips {
+        let ip = match url.host() {
+            Some(Host::Ipv4(ip)) => Some(IpAddr::V4(ip)),
+            Some(Host::Ipv6(ip)) => Some(IpAddr::V6(ip)),
+            _ => None,
+        };
+        if let Some(ip) = ip.filter(|ip| is_non_public_ip(*ip)) {
+            return Err(format!("address {ip} is not public"));
+        }
+    }
+    Ok(())
+}
+
+/// Resolves hosts with the system resolver and drops non-public addresses
+struct PublicIpResolver;
+
+impl Resolve for PublicIpResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_owned();
+        Box::pin(async move {
+            let public: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), 0))
+                .await?
+                .filter(|addr| !is_non_public_ip(addr.ip()))
+                .collect();
+            if public.is_empty() {
+                return Err(PolicyViolation(format!(
+                    "host {host} does not resolve to a public address"
+                ))
+                .into());
+            }
+            Ok(Box::new(public.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// Maps a request failure to `UrlNotAllowed` if the policy caused it
+fn request_error(url: &Url, error: &reqwest::Error) -> DomainError {
+    let mut source: Option<&(dyn std::error::Error + 'static)> = Some(error);
+    while let Some(err) = source {
+        if let Some(violation) = err.downcast_ref::<PolicyViolation>() {
+            return DomainError::url_not_allowed(url.as_str(), violation.0.clone());
+        }
+        source = err.source();
+    }
+    tracing::error!(?error, "UrlFetcher: failed to download file");
+    DomainError::download_error(format!("Failed to download file: {error}"))
+}
+
+fn too_large(max_bytes: usize) -> DomainError {
+    DomainError::invalid_request(format!("File exceeds maximum of {max_bytes} bytes"))
+}
+
+/// HTTP client for remote files, shared across `parse_url` calls
+pub struct UrlFetcher {
+    client: reqwest::Client,
+    policy: UrlFetchConfig,
+    max_bytes: usize,
+}
+
+impl UrlFetcher {
+    /// Create a fetcher enforcing `policy`, with a per-request `timeout` and
+    /// a cap of `max_bytes` on downloaded bodies
+    ///
+    /// # Errors
+    ///
+    /// Returns `DownloadError` if the HTTP client cannot be created
+    pub fn new(
+        policy: UrlFetchConfig,
+        timeout: Duration,
+        max_bytes: usize,
+    ) -> Result<Self, DomainError> {
+        let redirects = {
+            let policy = policy.clone();
+            reqwest::redirect::Policy::custom(move |attempt| {
+                if attempt.previous().len() > policy.max_redirects {
+                    let reason = format!("more than {} redirects", policy.max_redirects);
+                    return attempt.error(PolicyViolation(reason));
+                }
+                match check_url(&policy, attempt.url()) {
+                    Ok(()) => attempt.follow(),
+                    Err(reason) => attempt.error(PolicyViolation(reason)),
+                }
+            })
+        };
+
+        let mut builder = reqwest::Client::builder()
+            .timeout(timeout)
+            .redirect(redirects);
+        if policy.block_private_ips {
+            // A proxy would resolve hosts itself, bypassing the address check
+            builder = builder.dns_resolver(Arc::new(PublicIpResolver)).no_proxy();
+        }
+        let client = builder.build().map_err(|e| {
+            tracing::error!(?e, "UrlFetcher: failed to create HTTP client");
+            DomainError::download_error(format!("Failed to create HTTP client: {e}"))
+        })?;
+
+        Ok(Self {
+            client,
+            policy,
+            max_bytes,
+        })
+    }
+
+    /// Request `url`, following allowed redirects, and return the response
+    /// once its headers arrived
+    ///
+    /// # Errors
+    ///
+    /// Returns `UrlNotAllowed` if the policy refuses the URL, a redirect or
+    /// the resolved addresses, `InvalidRequest` if the announced length
+    /// exceeds the size cap, and `DownloadError` on any other failure
+    pub async fn open(&self, url: &Url) -> Result<Download, DomainError> {
+        check_url(&self.policy, url)
+            .map_err(|reason| DomainError::url_not_allowed(url.as_str(), reason))?;
+
+        let response = self
+            .client
+            .get(url.clone())
+            .send()
+            .await
+            .map_err(|e| request_error(url, &e))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            tracing::error!(?status, "UrlFetcher: HTTP error during download");
+            return Err(DomainError::download_error(format!("HTTP error: {status}")));
+        }
+        let max_bytes = u64::try_from(self.max_bytes).unwrap_or(u64::MAX);
+        let content_length = response.content_length();
+        if content_length.is_some_and(|len| len > max_bytes) {
+            return Err(too_large(self.max_bytes));
+        }
+
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(ToString::to_string);
+
+        Ok(Download {
+            url: url.clone(),
+            content_type,
+            content_length,
+            response,
+            max_bytes: self.max_bytes,
+        })
+    }
+}
+
+/// A response whose body has not been read yet
+pub struct Download {
+    url: Url,
+    /// `Content-Type` of the response, if any
+    pub content_type: Option<String>,
+    /// Announced length of the body, if any
+    pub content_length: Option<u64>,
+    response: reqwest::Response,
+    max_bytes: usize,
+}
+
+impl Download {
+    /// Stream the body, aborting as soon as it exceeds the size cap;
+    /// `on_chunk` is called with the number of bytes read so far
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidRequest` if the body exceeds the size cap and
+    /// `DownloadError` if it cannot be read
+    pub async fn bytes(mut self, mut on_chunk: impl FnMut(u64)) -> Result<Bytes, DomainError> {
+        let mut body = BytesMut::new();
+        while let Some(chunk) = self
+            .response
+            .chunk()
+            .await
+            .map_err(|e| request_error(&self.url, &e))?
+        {
+            if body.len() + chunk.len() > self.max_bytes {
+                return Err(too_large(self.max_bytes));
+            }
+            body.extend_from_slice(&chunk);
+            on_chunk(u64::try_from(body.len()).unwrap_or(u64::MAX));
+        }
+        Ok(body.freeze())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(s: &str) -> Url {
+        Url::parse(s).unwrap()
+    }
+
+    #[test]
+    fn test_non_public_ips() {
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "255.255.255.255",
+            "::1",
+            "::",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+            "64:ff9b::a9fe:a9fe",
+            "2002:c0a8:0101::1",
+            "::127.0.0.1",
+            "::a9fe:a9fe",
+            "2001:0:4136:e378:8000:63bf:3fff:fdd2",
+            "fec0::1",
+        ] {
+            assert!(is_non_public_ip(ip.parse().unwrap()), "{ip}");
+        }
+        for ip in ["93.184.216.34", "8.8.8.8", "2606:4700:4700::1111"] {
+            assert!(!is_non_public_ip(ip.parse().unwrap()), "{ip}");
+        }
+    }
+
+    #[test]
+    fn test_check_url() {
+        let policy = UrlFetchConfig {
+            allowed_hosts: vec!["*.example.com".to_owned(), "example.org".to_owned()],
+            denied_hosts: vec!["internal.example.com".to_owned()],
+            ..UrlFetchConfig::default()
+        };
+        assert!(check_url(&policy, &url("https://docs.example.com/a.pdf")).is_ok());
+        assert!(check_url(&policy, &url("http://EXAMPLE.org/a.pdf")).is_ok());
+        assert!(check_url(&policy, &url("https://example.com/a.pdf")).is_err());
+        assert!(check_url(&policy, &url("https://badexample.com/a.pdf")).is_err());
+        assert!(check_url(&policy, &url("https://internal.example.com/a.pdf")).is_err());
+        assert!(check_url(&policy, &url("ftp://docs.example.com/a.pdf")).is_err());
+        assert!(check_url(&policy, &url("file:///etc/passwd")).is_err());
+
+        let policy = UrlFetchConfig::default();
+        assert!(check_url(&policy, &url("https://example.com/a.pdf")).is_ok());
+        assert!(check_url(&policy, &url("http://127.0.0.1:8080/a.pdf")).is_err());
+        assert!(check_url(&policy, &url("http://[::1]/a.pdf")).is_err());
+        assert!(check_url(&policy, &url("http://[::127.0.0.1]/a.pdf")).is_err());
+        assert!(check_url(&policy, &url("http://[2001::a9fe:a9fe]/a.pdf")).is_err());
+        assert!(check_url(&policy, &url("http://169.254.169.254/latest")).is_err());
+
+        let policy = UrlFetchConfig {
+            block_private_ips: false,
+            ..UrlFetchConfig::default()
+        };
+        assert!(check_url(&policy, &url("http://127.0.0.1:8080/a.pdf")).is_ok());
+    }
+}
@@ -0,0 +1,528 @@
+use std::fmt::Write;
+
+use crate::domain::ir::{Inline, InlineStyle, ParsedBlock, ParsedDocument, TableBlock};
+
+/// HTML renderer that converts `ParsedDocument` to a standalone HTML page
+pub struct HtmlRenderer;
+
+impl Default for HtmlRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Iterator over HTML chunks from a `ParsedDocument`
+/// This iterator owns the document to avoid lifetime issues with async streaming
+pub struct HtmlRenderIter {
+    doc: ParsedDocument,
+    header_emitted: bool,
+    block_index: usize,
+    footer_emitted: bool,
+    /// Lists left open by the previous blocks, innermost last
+    lists: ListStack,
+}
+
+impl Iterator for HtmlRenderIter {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.header_emitted {
+            self.header_emitted = true;
+            return Some(HtmlRenderer::render_header(&self.doc));
+        }
+
+        // One chunk per block, then the closing tags
+        if let Some(block) = self.doc.blocks.get(self.block_index) {
+            self.block_index += 1;
+            let mut chunk = String::new();
+            HtmlRenderer::render_block(block, &mut self.lists, &mut chunk);
+            Some(chunk)
+        } else if self.footer_emitted {
+            None
+        } else {
+            self.footer_emitted = true;
+            let mut footer = String::new();
+            self.lists.close_all(&mut footer);
+            footer.push_str("</body>\n</html>\n");
+            Some(footer)
+        }
+    }
+}
+
+/// Open `<ul>`/`<ol>` elements, each with an open `<li>`
+#[derive(Default)]
+struct ListStack {
+    ordered: Vec<bool>,
+}
+
+impl ListStack {
+    /// Close or open lists so that an item at `level` can follow
+    fn enter_item(&mut self, level: u8, ordered: bool, output: &mut String) {
+        let depth = usize::from(level) + 1;
+        while self.ordered.len() > depth {
+            self.close_one(output);
+        }
+        if self.ordered.len() == depth {
+            if self.ordered.last() == Some(&ordered) {
+                output.push_str("</li>\n");
+            } else {
+                self.close_one(output);
+            }
+        }
+        while self.ordered.len() < depth {
+            output.push_str(if ordered { "<ol>\n" } else { "<ul>\n" });
+            self.ordered.push(ordered);
+        }
+        output.push_str("<li>");
+    }
+
+    fn close_one(&mut self, output: &mut String) {
+        if let Some(ordered) = self.ordered.pop() {
+            output.push_str(if ordered {
+                "</li>\n</ol>\n"
+            } else {
+                "</li>\n</ul>\n"
+            });
+        }
+    }
+
+    fn close_all(&mut self, output: &mut String) {
+        while !self.ordered.is_empty() {
+            self.close_one(output);
+        }
+    }
+}
+
+impl HtmlRenderer {
+    /// Create a new HTML renderer
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Create a streaming iterator over HTML chunks
+    /// Takes ownership of the document to avoid lifetime issues with async streaming
+    #[must_use]
+    pub fn render_iter(doc: ParsedDocument) -> HtmlRenderIter {
+        HtmlRenderIter {
+            doc,
+            header_emitted: false,
+            block_index: 0,
+            footer_emitted: false,
+            lists: ListStack::default(),
+        }
+    }
+
+    /// Render a parsed document to HTML
+    #[must_use]
+    pub fn render(doc: &ParsedDocument) -> String {
+        Self::render_iter(doc.clone()).collect()
+    }
+
+    /// Document head, and the title as top-level heading
+    fn render_header(doc: &ParsedDocument) -> String {
+        let mut header = String::from("<!DOCTYPE html>\n");
+        match &doc.language {
+            Some(lang) => {
+                header.push_str("<html lang=\"");
+                header.push_str(&escape(lang));
+                header.push_str("\">\n");
+            }
+            None => header.push_str("<html>\n"),
+        }
+        header.push_str("<head>\n<meta charset=\"utf-8\">\n");
+        if let Some(title) = &doc.title {
+            header.push_str("<title>");
+            header.push_str(&escape(title));
+            header.push_str("</title>\n");
+        }
+        if let Some(author) = &doc.meta.author {
+            header.push_str("<meta name=\"author\" content=\"");
+            header.push_str(&escape(author));
+            header.push_str("\">\n");
+        }
+        header.push_str("</head>\n<body>\n");
+        if let Some(title) = &doc.title {
+            header.push_str("<h1>");
+            header.push_str(&escape(title));
+            header.push_str("</h1>\n");
+        }
+        header
+    }
+
+    fn render_block(block: &ParsedBlock, lists: &mut ListStack, output: &mut String) {
+        if let ParsedBlock::ListItem {
+            level,
+            ordered,
+            blocks,
+        } = block
+        {
+            lists.enter_item(*level, *ordered, output);
+            Self::render_contents(blocks, output);
+            return;
+        }
+        lists.close_all(output);
+
+        match block {
+            ParsedBlock::Heading { level, inlines, .. } => {
+                let level = (*level).clamp(1, 6);
+                let _ = write!(output, "<h{level}>");
+                Self::render_inlines(inlines, output);
+                let _ = writeln!(output, "</h{level}>");
+            }
+            ParsedBlock::Paragraph { inlines, .. } => {
+                output.push_str("<p>");
+                Self::render_inlines(inlines, output);
+                output.push_str("</p>\n");
+            }
+            ParsedBlock::CodeBlock { language, code } => {
+                output.push_str("<pre><code");
+                if let Some(lang) = language {
+                    output.push_str(" class=\"language-");
+                    output.push_str(&escape(lang));
+                    output.push('"');
+                }
+                output.push('>');
+                output.push_str(&escape(code));
+                output.push_str("</code></pre>\n");
+            }
+            ParsedBlock::Table(table) => Self::render_table(table, output),
+            ParsedBlock::Quote { blocks } => {
+                output.push_str("<blockquote>\n");
+                Self::render_blocks(blocks, output);
+                output.push_str("</blockquote>\n");
+            }
+            ParsedBlock::HorizontalRule => output.push_str("<hr>\n"),
+            ParsedBlock::Image { alt, title, src } => {
+                output.push_str("<p><img");
+                if let Some(source) = src.as_deref().filter(|s| is_safe_url(s, UrlUse::Image)) {
+                    output.push_str(" src=\"");
+                    output.push_str(&escape(source));
+                    output.push('"');
+                }
+                output.push_str(" alt=\"");
+                output.push_str(&escape(alt.as_deref().unwrap_or_default()));
+                output.push('"');
+                if let Some(title_text) = title {
+                    output.push_str(" title=\"");
+                    output.push_str(&escape(title_text));
+                    output.push('"');
+                }
+                output.push_str("></p>\n");
+            }
+            ParsedBlock::PageBreak => output.push_str("<hr class=\"page-break\">\n"),
+            ParsedBlock::ListItem { .. } => {}
+        }
+    }
+
+    /// Render nested blocks with their own lists, closed at the end
+    fn render_blocks(blocks: &[ParsedBlock], output: &mut String) {
+        let mut lists = ListStack::default();
+        for block in blocks {
+            Self::render_block(block, &mut lists, output);
+        }
+        lists.close_all(output);
+    }
+
+    /// Content of a list item or table cell; a lone paragraph is inlined
+    fn render_contents(blocks: &[ParsedBlock], output: &mut String) {
+        if let [ParsedBlock::Paragraph { inlines, .. }] = blocks {
+            Self::render_inlines(inlines, output);
+        } else {
+            Self::render_blocks(blocks, output);
+        }
+    }
+
+    fn render_table(table: &TableBlock, output: &mut String) {
+        output.push_str("<table>\n");
+        let header_rows = table.rows.iter().take_while(|row| row.is_header).count();
+        let (head, body) = table.rows.split_at(header_rows);
+        for (rows, section, cell_tag) in [(head, "thead", "th"), (body, "tbody", "td")] {
+            if rows.is_empty() {
+                continue;
+            }
+            let _ = writeln!(output, "<{section}>");
+            for row in rows {
+                output.push_str("<tr>");
+                for cell in &row.cells {
+                    let _ = write!(output, "<{cell_tag}>");
+                    Self::render_contents(&cell.blocks, output);
+                    let _ = write!(output, "</{cell_tag}>");
+                }
+                output.push_str("</tr>\n");
+            }
+            let _ = writeln!(output, "</{section}>");
+        }
+        output.push_str("</table>\n");
+    }
+
+    fn render_inlines(inlines: &[Inline], output: &mut String) {
+        for inline in inlines {
+            match inline {
+                Inline::Text { text, style } => {
+                    Self::render_styled_text(text, style, output);
+                }
+                Inline::Link {
+                    text,
+                    target,
+                    style,
+                } => {
+                    if is_safe_url(target, UrlUse::Link) {
+                        output.push_str("<a href=\"");
+                        output.push_str(&escape(target));
+                        output.push_str("\">");
+                        Self::render_styled_text(text, style, output);
+                        output.push_str("</a>");
+                    } else {
+                        Self::render_styled_text(text, style, output);
+                    }
+                }
+                Inline::Code { text, style } => {
+                    output.push_str("<code>");
+                    Self::render_styled_text(text, style, output);
+                    output.push_str("</code>");
+                }
+            }
+        }
+    }
+
+    fn render_styled_text(text: &str, style: &InlineStyle, output: &mut String) {
+        let tags: Vec<&str> = [
+            (style.bold, "strong"),
+            (style.italic, "em"),
+            (style.underline, "u"),
+            (style.strike, "s"),
+            (style.code, "code"),
+        ]
+        .into_iter()
+        .filter_map(|(on, tag)| on.then_some(tag))
+        .collect();
+
+        for tag in &tags {
+            let _ = write!(output, "<{tag}>");
+        }
+        output.push_str(&escape(text));
+        for tag in tags.iter().rev() {
+            let _ = write!(output, "</{tag}>");
+        }
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// Element a URL is emitted into
+#[derive(Clone, Copy)]
+enum UrlUse {
+    Link,
+    Image,
+}
+
+/// Whether a link or image target can be emitted without running script.
+///
+/// Browsers ignore ASCII whitespace and control characters inside a scheme
+/// (`java\tscript:`), so they are dropped before the scheme is checked against an
+/// allowlist: `http`, `https`, `mailto`, relative URLs and, for images only,
+/// `data:image/*`.
+fn is_safe_url(url: &str, usage: UrlUse) -> bool {
+    let url: String = url
+        .chars()
+        .filter(|c| !c.is_ascii_whitespace() && !c.is_ascii_control())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    let Some((scheme, rest)) = url.split_once(':') else {
+        return true;
+    };
+    // A colon after a path, query or fragment delimiter does not end a scheme
+    if scheme.contains(['/', '?', '#']) {
+        return true;
+    }
+    match scheme {
+        "http" | "https" | "mailto" => true,
+        "data" => matches!(usage, UrlUse::Image) && rest.starts_with("image/"),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::domain::ir::{DocumentBuilder, ParsedSource, TableCell, TableRow};
+
+    fn paragraph(text: &str) -> ParsedBlock {
+        ParsedBlock::Paragraph {
+            inlines: vec![Inline::plain(text)],
+            location: None,
+        }
+    }
+
+    fn item(level: u8, ordered: bool, text: &str) -> ParsedBlock {
+        ParsedBlock::ListItem {
+            level,
+            ordered,
+            blocks: vec![paragraph(text)],
+        }
+    }
+
+    fn document(blocks: Vec<ParsedBlock>) -> ParsedDocument {
+        DocumentBuilder::new(ParsedSource::LocalPath("test.txt".to_owned()))
+            .blocks(blocks)
+            .build()
+    }
+
+    #[test]
+    fn test_render_page() {
+        let doc = DocumentBuilder::new(ParsedSource::LocalPath("test.txt".to_owned()))
+            .title("Q&A")
+            .language("en")
+            .blocks(vec![paragraph("1 < 2")])
+            .build();
+
+        let html = HtmlRenderer::render(&doc);
+
+        assert!(html.starts_with("<!DOCTYPE html>\n<html lang=\"en\">\n"));
+        assert!(html.contains("<title>Q&amp;A</title>"));
+        assert!(html.contains("<h1>Q&amp;A</h1>\n<p>1 &lt; 2</p>\n"));
+        assert!(html.ends_with("</body>\n</html>\n"));
+    }
+
+    #[test]
+    fn test_render_inline_styles_and_links() {
+        let doc = document(vec![ParsedBlock::Paragraph {
+            inlines: vec![
+                Inline::styled(
+                    "bold",
+                    InlineStyle {
+                        bold: true,
+                        italic: true,
+                        ..InlineStyle::default()
+                    },
+                ),
+                Inline::plain(" "),
+                Inline::link("site", "https://example.com/?a=1&b=2"),
+                Inline::plain(" "),
+                Inline::link("bad", "javascript:alert(1)"),
+            ],
+            location: None,
+        }]);
+
+        let html = HtmlRenderer::render(&doc);
+
+        assert!(html.contains(
+            "<p><strong><em>bold</em></strong> \
+             <a href=\"https://example.com/?a=1&amp;b=2\">site</a> bad</p>"
+        ));
+    }
+
+    #[test]
+    fn test_is_safe_url_allowlist() {
+        for url in [
+            "https://example.com",
+            "HTTP://example.com",
+            "mailto:a@example.com",
+            "docs/page.html#intro",
+            "/a/b?c=d:e",
+            "#section",
+        ] {
+            assert!(is_safe_url(url, UrlUse::Link), "{url}");
+        }
+        for url in [
+            "javascript:alert(1)",
+            " JavaScript:alert(1)",
+            "java\tscript:alert(1)",
+            "java\nscript:alert(1)",
+            "java\rscript:alert(1)",
+            "\u{1}javascript:alert(1)",
+            "vbscript:msgbox(1)",
+            "data:text/html,<script>alert(1)</script>",
+            "data:image/png;base64,AAAA",
+            "file:///etc/passwd",
+        ] {
+            assert!(!is_safe_url(url, UrlUse::Link), "{url:?}");
+        }
+        assert!(is_safe_url("data:image/png;base64,AAAA", UrlUse::Image));
+        assert!(!is_safe_url("data:text/html,x", UrlUse::Image));
+        assert!(!is_safe_url("java\tscript:alert(1)", UrlUse::Image));
+    }
+
+    #[test]
+    fn test_render_drops_links_with_whitespace_in_scheme() {
+        let doc = document(vec![ParsedBlock::Paragraph {
+            inlines: vec![
+                Inline::link("tab", "java\tscript:alert(1)"),
+                Inline::plain(" "),
+                Inline::link("newline", "java\nscript:alert(1)"),
+            ],
+            location: None,
+        }]);
+
+        let html = HtmlRenderer::render(&doc);
+
+        assert!(html.contains("<p>tab newline</p>"));
+        assert!(!html.contains("href"));
+    }
+
+    #[test]
+    fn test_render_nested_lists() {
+        let doc = document(vec![
+            item(0, false, "a"),
+            item(1, true, "b"),
+            item(0, false, "c"),
+            paragraph("after"),
+        ]);
+
+        let html = HtmlRenderer::render(&doc);
+
+        assert!(html.contains(
+            "<ul>\n<li>a<ol>\n<li>b</li>\n</ol>\n</li>\n<li>c</li>\n</ul>\n<p>after</p>\n"
+        ));
+    }
+
+    #[test]
+    fn test_render_table() {
+        let cell = |text: &str| TableCell {
+            blocks: vec![paragraph(text)],
+        };
+        let doc = document(vec![ParsedBlock::Table(TableBlock {
+            rows: vec![
+                TableRow {
+                    is_header: true,
+                    cells: vec![cell("Name")],
+                },
+                TableRow {
+                    is_header: false,
+                    cells: vec![cell("Ada")],
+                },
+            ],
+            location: None,
+        })]);
+
+        let html = HtmlRenderer::render(&doc);
+
+        assert!(html.contains(
+            "<table>\n<thead>\n<tr><th>Name</th></tr>\n</thead>\n\
+             <tbody>\n<tr><td>Ada</td></tr>\n</tbody>\n</table>\n"
+        ));
+    }
+
+    #[test]
+    fn test_render_iter_streams_blocks() {
+        let doc = document(vec![paragraph("one"), paragraph("two")]);
+        let chunks: Vec<String> = HtmlRenderer::render_iter(doc).collect();
+        assert_eq!(chunks.len(), 4);
+        assert_eq!(chunks[1], "<p>one</p>\n");
+    }
+}
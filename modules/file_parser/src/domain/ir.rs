@@ -17,9 +17,63 @@ pub struct ParsedMetadata {
     pub source: ParsedSource,
     pub original_filename: Option<String>,
     pub content_type: Option<String>,
+    pub author: Option<String>,
     pub created_at: Option<OffsetDateTime>,
     pub modified_at: Option<OffsetDateTime>,
     pub is_stub: bool,
+    /// Message headers, for e-mail documents
+    pub email: Option<EmailHeaders>,
+    /// Format, dimensions and EXIF data, for images
+    pub image: Option<ImageMetadata>,
+}
+
+/// Headers of an e-mail message; addresses are formatted as
+/// `Name <address>`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct EmailHeaders {
+    pub from: Vec<String>,
+    pub to: Vec<String>,
+    pub cc: Vec<String>,
+    pub subject: Option<String>,
+    pub date: Option<OffsetDateTime>,
+    pub message_id: Option<String>,
+}
+
+/// Properties of an image, read from its header
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageMetadata {
+    /// `png`, `jpeg`, `gif` or `webp`
+    pub format: String,
+    pub width: u32,
+    pub height: u32,
+    /// Number of frames of the source, more than 1 for animations
+    pub frames: u32,
+    pub exif: Option<ExifData>,
+}
+
+/// Commonly used EXIF tags of an image
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ExifData {
+    pub make: Option<String>,
+    pub model: Option<String>,
+    pub software: Option<String>,
+    /// `DateTimeOriginal`, or else `DateTime`, as written by the device
+    /// (`YYYY:MM:DD HH:MM:SS`, without time zone)
+    pub taken_at: Option<String>,
+    /// EXIF orientation, 1 to 8
+    pub orientation: Option<u16>,
+    pub description: Option<String>,
+    pub artist: Option<String>,
+    pub copyright: Option<String>,
+    pub gps: Option<GpsPosition>,
+}
+
+/// Position where an image was taken, in degrees and meters
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GpsPosition {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub altitude: Option<f64>,
 }
 
 /// Source of the parsed document
@@ -92,12 +146,62 @@ impl Inline {
             style: InlineStyle::default(),
         }
     }
+
+    /// The text of this element, without styling or link target
+    #[must_use]
+    pub fn text(&self) -> &str {
+        match self {
+            Inline::Text { text, .. } | Inline::Link { text, .. } | Inline::Code { text, .. } => {
+                text
+            }
+        }
+    }
+
+    /// The concatenated text of a run of inlines
+    #[must_use]
+    pub fn plain_text(inlines: &[Inline]) -> String {
+        inlines.iter().map(Inline::text).collect()
+    }
+
+    /// Trim whitespace at both ends of a run of inlines, dropping emptied
+    /// text and clearing the run if it is blank
+    pub fn trim(inlines: &mut Vec<Inline>) {
+        if inlines.iter().all(|inline| inline.text().trim().is_empty()) {
+            inlines.clear();
+            return;
+        }
+        if let Some(Inline::Text { text, .. }) = inlines.first_mut() {
+            *text = text.trim_start().to_owned();
+        }
+        if let Some(Inline::Text { text, .. }) = inlines.last_mut() {
+            *text = text.trim_end().to_owned();
+        }
+        inlines.retain(|inline| !matches!(inline, Inline::Text { text, .. } if text.is_empty()));
+    }
+}
+
+/// Where a block appears in the source document
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SourceLocation {
+    /// 1-based page number
+    pub page: u32,
+    pub bbox: Option<BoundingBox>,
+}
+
+/// Rectangle on a page in points, with the origin at the top-left corner
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    pub x0: f64,
+    pub y0: f64,
+    pub x1: f64,
+    pub y1: f64,
 }
 
 /// Structured table representation
 #[derive(Debug, Clone, PartialEq)]
 pub struct TableBlock {
     pub rows: Vec<TableRow>,
+    pub location: Option<SourceLocation>,
 }
 
 /// A single row in a table
@@ -119,9 +223,11 @@ pub enum ParsedBlock {
     Heading {
         level: u8, // 1-6
         inlines: Vec<Inline>,
+        location: Option<SourceLocation>,
     },
     Paragraph {
         inlines: Vec<Inline>,
+        location: Option<SourceLocation>,
     },
     ListItem {
         level: u8, // 0-based nesting level
@@ -154,9 +260,12 @@ pub struct DocumentBuilder {
     source: ParsedSource,
     original_filename: Option<String>,
     content_type: Option<String>,
+    author: Option<String>,
     created_at: Option<OffsetDateTime>,
     modified_at: Option<OffsetDateTime>,
     is_stub: bool,
+    email: Option<EmailHeaders>,
+    image: Option<ImageMetadata>,
     blocks: Vec<ParsedBlock>,
 }
 
@@ -170,9 +279,12 @@ impl DocumentBuilder {
             source,
             original_filename: None,
             content_type: None,
+            author: None,
             created_at: None,
             modified_at: None,
             is_stub: false,
+            email: None,
+            image: None,
             blocks: Vec::new(),
         }
     }
@@ -207,6 +319,12 @@ impl DocumentBuilder {
         self
     }
 
+    /// Set the author
+    pub fn author<T: Into<String>>(mut self, author: T) -> Self {
+        self.author = Some(author.into());
+        self
+    }
+
     /// Set the created timestamp
     pub fn created_at(mut self, created_at: OffsetDateTime) -> Self {
         self.created_at = Some(created_at);
@@ -225,6 +343,18 @@ impl DocumentBuilder {
         self
     }
 
+    /// Set the e-mail headers
+    pub fn email(mut self, email: EmailHeaders) -> Self {
+        self.email = Some(email);
+        self
+    }
+
+    /// Set the image properties
+    pub fn image(mut self, image: ImageMetadata) -> Self {
+        self.image = Some(image);
+        self
+    }
+
     /// Set the document blocks
     pub fn blocks(mut self, blocks: Vec<ParsedBlock>) -> Self {
         self.blocks = blocks;
@@ -242,9 +372,12 @@ impl DocumentBuilder {
                 source: self.source,
                 original_filename: self.original_filename,
                 content_type: self.content_type,
+                author: self.author,
                 created_at: self.created_at,
                 modified_at: self.modified_at,
                 is_stub: self.is_stub,
+                email: self.email,
+                image: self.image,
             },
             blocks: self.blocks,
         }
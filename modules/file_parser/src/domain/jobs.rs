@@ -0,0 +1,521 @@
+//! Asynchronous parse jobs, for files too large to parse within a request.
+//!
+//! Jobs wait for a slot of their tenant, then for one of the shared workers,
+//! so that one tenant cannot occupy every worker. Uploaded files are held in
+//! memory until their job finishes, bounded by `max_pending_upload_bytes`.
+//! Finished jobs and their results stay in the `JobStore` until their TTL
+//! expires.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use time::OffsetDateTime;
+use tokio::sync::{Mutex, Semaphore, watch};
+use tokio::task::AbortHandle;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::domain::error::DomainError;
+use crate::domain::ir::ParsedDocument;
+use crate::domain::service::FileParserService;
+
+/// Lifecycle of a parse job
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobState {
+    /// Waiting for a worker
+    Queued,
+    /// Downloading or parsing the file
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+impl JobState {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Queued => "queued",
+            Self::Running => "running",
+            Self::Succeeded => "succeeded",
+            Self::Failed => "failed",
+            Self::Cancelled => "cancelled",
+        }
+    }
+}
+
+/// How much of its file a job has read
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct JobProgress {
+    /// Bytes downloaded or uploaded so far
+    pub processed_bytes: u64,
+    /// Size of the file, if known; downloads without a `Content-Length`
+    /// have none until they complete
+    pub total_bytes: Option<u64>,
+}
+
+impl JobProgress {
+    fn complete(bytes: u64) -> Self {
+        Self {
+            processed_bytes: bytes,
+            total_bytes: Some(bytes),
+        }
+    }
+}
+
+/// File a job parses
+#[derive(Debug, Clone)]
+pub enum JobSource {
+    Upload {
+        filename: Option<String>,
+        content_type: Option<String>,
+        bytes: Bytes,
+    },
+    Url(url::Url),
+}
+
+impl JobSource {
+    /// File name or URL, for status responses
+    fn describe(&self) -> Option<String> {
+        match self {
+            Self::Upload { filename, .. } => filename.clone(),
+            Self::Url(url) => Some(url.to_string()),
+        }
+    }
+
+    /// Bytes held in memory until the job finishes
+    fn held_bytes(&self) -> usize {
+        match self {
+            Self::Upload { bytes, .. } => bytes.len(),
+            Self::Url(_) => 0,
+        }
+    }
+}
+
+/// A parse job and, once it has succeeded, its result
+#[derive(Debug, Clone)]
+pub struct ParseJob {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub state: JobState,
+    /// File name or URL of the source
+    pub source: Option<String>,
+    pub created_at: OffsetDateTime,
+    pub started_at: Option<OffsetDateTime>,
+    pub finished_at: Option<OffsetDateTime>,
+    /// Bytes of the file read so far; complete once parsing has started
+    pub progress: JobProgress,
+    /// When the job and its result are dropped; set once it has finished
+    pub expires_at: Option<OffsetDateTime>,
+    /// Why the job failed
+    pub error: Option<String>,
+    pub result: Option<Arc<ParsedDocument>>,
+}
+
+impl ParseJob {
+    fn is_expired(&self, now: OffsetDateTime) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
+/// Storage of parse jobs and their results
+#[async_trait]
+pub trait JobStore: Send + Sync {
+    /// Insert a job, or replace the job with the same ID
+    async fn save(&self, job: ParseJob) -> Result<(), DomainError>;
+
+    /// The job with this ID, which may have expired
+    async fn load(&self, id: Uuid) -> Result<Option<ParseJob>, DomainError>;
+
+    /// Drop the jobs that expired at or before `now`
+    async fn purge_expired(&self, now: OffsetDateTime) -> Result<(), DomainError>;
+}
+
+/// Limits of the parse job manager
+#[derive(Debug, Clone)]
+pub struct JobManagerConfig {
+    /// Jobs running at once over all tenants
+    pub workers: usize,
+    /// Jobs of one tenant running at once
+    pub max_running_per_tenant: usize,
+    /// Queued and running jobs of one tenant; further submissions are refused
+    pub max_pending_per_tenant: usize,
+    /// Queued and running jobs over all tenants
+    pub max_pending: usize,
+    /// Size of the uploads of queued and running jobs over all tenants
+    pub max_pending_upload_bytes: usize,
+    /// How long finished jobs and their results are kept
+    pub result_ttl: Duration,
+}
+
+impl Default for JobManagerConfig {
+    fn default() -> Self {
+        Self {
+            workers: 4,
+            max_running_per_tenant: 2,
+            max_pending_per_tenant: 20,
+            max_pending: 200,
+            max_pending_upload_bytes: 1024 * 1024 * 1024, // 1 GB
+            result_ttl: Duration::from_hours(1),
+        }
+    }
+}
+
+/// How often a running job saves its progress
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Runs parse jobs on a bounded pool of workers
+pub struct ParseJobManager {
+    service: Arc<FileParserService>,
+    store: Arc<dyn JobStore>,
+    config: JobManagerConfig,
+    workers: Arc<Semaphore>,
+    /// Jobs not finished yet; state changes of a job are saved while holding
+    /// this lock, so that cancelling and finishing cannot interleave
+    active: Mutex<ActiveJobs>,
+}
+
+#[derive(Default)]
+struct ActiveJobs {
+    jobs: HashMap<Uuid, ActiveJob>,
+    tenants: HashMap<Uuid, TenantSlots>,
+    /// Size of the uploads of all active jobs
+    upload_bytes: usize,
+}
+
+struct ActiveJob {
+    tenant_id: Uuid,
+    upload_bytes: usize,
+    task: AbortHandle,
+}
+
+struct TenantSlots {
+    pending: usize,
+    running: Arc<Semaphore>,
+}
+
+impl ActiveJobs {
+    /// Forget a job that is no longer queued or running
+    fn release(&mut self, id: Uuid) -> Option<ActiveJob> {
+        let job = self.jobs.remove(&id)?;
+        self.upload_bytes = self.upload_bytes.saturating_sub(job.upload_bytes);
+        if let Some(slots) = self.tenants.get_mut(&job.tenant_id) {
+            slots.pending = slots.pending.saturating_sub(1);
+            if slots.pending == 0 {
+                self.tenants.remove(&job.tenant_id);
+            }
+        }
+        Some(job)
+    }
+}
+
+impl ParseJobManager {
+    #[must_use]
+    pub fn new(
+        service: Arc<FileParserService>,
+        store: Arc<dyn JobStore>,
+        config: JobManagerConfig,
+    ) -> Self {
+        let workers = Arc::new(Semaphore::new(config.workers.max(1)));
+        Self {
+            service,
+            store,
+            config,
+            workers,
+            active: Mutex::new(ActiveJobs::default()),
+        }
+    }
+
+    /// Queue a job parsing `source` for `tenant_id`
+    ///
+    /// # Errors
+    /// Returns `TooManyJobs` if the tenant or all tenants together already
+    /// have the maximum number of pending jobs, `TooManyPendingBytes` if the
+    /// upload does not fit next to the pending ones, or the store's error if
+    /// the job cannot be saved.
+    pub async fn submit(
+        self: &Arc<Self>,
+        tenant_id: Uuid,
+        source: JobSource,
+    ) -> Result<ParseJob, DomainError> {
+        let now = OffsetDateTime::now_utc();
+        self.store.purge_expired(now).await?;
+
+        let upload_bytes = source.held_bytes();
+        let mut active = self.active.lock().await;
+        self.check_capacity(&active, tenant_id, upload_bytes)?;
+
+        let job = ParseJob {
+            id: Uuid::now_v7(),
+            tenant_id,
+            state: JobState::Queued,
+            source: source.describe(),
+            created_at: now,
+            started_at: None,
+            finished_at: None,
+            progress: JobProgress::default(),
+            expires_at: None,
+            error: None,
+            result: None,
+        };
+        self.store.save(job.clone()).await?;
+
+        let max_running = self.config.max_running_per_tenant.max(1);
+        let slots = active
+            .tenants
+            .entry(tenant_id)
+            .or_insert_with(|| TenantSlots {
+                pending: 0,
+                running: Arc::new(Semaphore::new(max_running)),
+            });
+        slots.pending += 1;
+        let tenant_slots = Arc::clone(&slots.running);
+        active.upload_bytes += upload_bytes;
+
+        // The task waits for `active` before changing the job, so it cannot
+        // finish before it is registered below
+        let task = tokio::spawn(Arc::clone(self).run(job.id, tenant_slots, source));
+        active.jobs.insert(
+            job.id,
+            ActiveJob {
+                tenant_id,
+                upload_bytes,
+                task: task.abort_handle(),
+            },
+        );
+
+        info!(job_id = %job.id, %tenant_id, "Parse job queued");
+        Ok(job)
+    }
+
+    /// The job with this ID, if it belongs to `tenant_id`
+    ///
+    /// # Errors
+    /// Returns `JobNotFound` if there is no such job for the tenant or it has
+    /// expired.
+    pub async fn get(&self, tenant_id: Uuid, id: Uuid) -> Result<ParseJob, DomainError> {
+        self.store
+            .load(id)
+            .await?
+            .filter(|job| job.tenant_id == tenant_id)
+            .filter(|job| !job.is_expired(OffsetDateTime::now_utc()))
+            .ok_or_else(|| DomainError::job_not_found(id))
+    }
+
+    /// The parsed document of a succeeded job
+    ///
+    /// # Errors
+    /// Returns `JobNotFound` as `get` does, and `NoJobResult` if the job has
+    /// not succeeded.
+    pub async fn result(
+        &self,
+        tenant_id: Uuid,
+        id: Uuid,
+    ) -> Result<Arc<ParsedDocument>, DomainError> {
+        let job = self.get(tenant_id, id).await?;
+        match job.result {
+            Some(document) if job.state == JobState::Succeeded => Ok(document),
+            _ => Err(DomainError::no_job_result(id, job.state.as_str())),
+        }
+    }
+
+    /// Cancel a queued or running job; finished jobs are returned unchanged.
+    ///
+    /// A running job stops at its next await point: parsing already handed
+    /// to a blocking thread runs to completion, but its result is dropped.
+    ///
+    /// # Errors
+    /// Returns `JobNotFound` as `get` does.
+    pub async fn cancel(&self, tenant_id: Uuid, id: Uuid) -> Result<ParseJob, DomainError> {
+        let mut job = self.get(tenant_id, id).await?;
+
+        let mut active = self.active.lock().await;
+        let Some(task) = active.release(id) else {
+            drop(active);
+            return self.get(tenant_id, id).await;
+        };
+        task.task.abort();
+
+        let now = OffsetDateTime::now_utc();
+        job.state = JobState::Cancelled;
+        job.finished_at = Some(now);
+        job.expires_at = Some(self.expiry(now));
+        self.store.save(job.clone()).await?;
+
+        info!(job_id = %id, %tenant_id, "Parse job cancelled");
+        Ok(job)
+    }
+
+    /// Refuse a job that would exceed the pending limits
+    fn check_capacity(
+        &self,
+        active: &ActiveJobs,
+        tenant_id: Uuid,
+        upload_bytes: usize,
+    ) -> Result<(), DomainError> {
+        let pending = active
+            .tenants
+            .get(&tenant_id)
+            .map_or(0, |slots| slots.pending);
+        if pending >= self.config.max_pending_per_tenant {
+            return Err(DomainError::too_many_jobs(
+                self.config.max_pending_per_tenant,
+            ));
+        }
+        if active.jobs.len() >= self.config.max_pending {
+            return Err(DomainError::too_many_jobs(self.config.max_pending));
+        }
+        if active.upload_bytes.saturating_add(upload_bytes) > self.config.max_pending_upload_bytes {
+            return Err(DomainError::too_many_pending_bytes(
+                self.config.max_pending_upload_bytes,
+            ));
+        }
+        Ok(())
+    }
+
+    async fn run(self: Arc<Self>, id: Uuid, tenant_slots: Arc<Semaphore>, source: JobSource) {
+        // The tenant slot is taken first, so that jobs of a busy tenant do
+        // not hold workers other tenants could use
+        let Ok(_tenant_permit) = tenant_slots.acquire_owned().await else {
+            return;
+        };
+        let Ok(_worker_permit) = Arc::clone(&self.workers).acquire_owned().await else {
+            return;
+        };
+
+        if !self.start(id).await {
+            return;
+        }
+        debug!(job_id = %id, "Parse job started");
+
+        let (progress, mut progress_rx) = watch::channel(JobProgress::default());
+        let parse = self.parse(source, progress);
+        tokio::pin!(parse);
+        let mut ticks = tokio::time::interval(PROGRESS_INTERVAL);
+        let outcome = loop {
+            tokio::select! {
+                outcome = &mut parse => break outcome,
+                _ = ticks.tick() => {
+                    if progress_rx.has_changed().unwrap_or(false) {
+                        let current = *progress_rx.borrow_and_update();
+                        self.save_progress(id, current).await;
+                    }
+                }
+            }
+        };
+
+        let progress = *progress_rx.borrow();
+        self.finish(id, progress, outcome).await;
+    }
+
+    /// Mark a job as running, unless it was cancelled while queued
+    async fn start(&self, id: Uuid) -> bool {
+        let active = self.active.lock().await;
+        if !active.jobs.contains_key(&id) {
+            return false;
+        }
+        let started = self.update(id, |job| {
+            job.state = JobState::Running;
+            job.started_at = Some(OffsetDateTime::now_utc());
+        });
+        if let Err(e) = started.await {
+            warn!(job_id = %id, error = %e, "Failed to save parse job state");
+        }
+        true
+    }
+
+    /// Parse the file of a job, reporting the bytes read through `progress`
+    async fn parse(
+        &self,
+        source: JobSource,
+        progress: watch::Sender<JobProgress>,
+    ) -> Result<ParsedDocument, DomainError> {
+        match source {
+            JobSource::Upload {
+                filename,
+                content_type,
+                bytes,
+            } => {
+                let len = u64::try_from(bytes.len()).unwrap_or(u64::MAX);
+                progress.send_replace(JobProgress::complete(len));
+                self.service
+                    .parse_bytes(filename.as_deref(), content_type.as_deref(), bytes)
+                    .await
+            }
+            JobSource::Url(url) => {
+                let report = |processed_bytes, total_bytes| {
+                    progress.send_replace(JobProgress {
+                        processed_bytes,
+                        total_bytes,
+                    });
+                };
+                self.service.parse_url_with_progress(&url, report).await
+            }
+        }
+    }
+
+    async fn save_progress(&self, id: Uuid, progress: JobProgress) {
+        let active = self.active.lock().await;
+        if !active.jobs.contains_key(&id) {
+            return;
+        }
+        let saved = self.update(id, |job| job.progress = progress);
+        if let Err(e) = saved.await {
+            warn!(job_id = %id, error = %e, "Failed to save parse job progress");
+        }
+        drop(active);
+    }
+
+    /// Save the outcome of a job, unless it was cancelled meanwhile
+    async fn finish(
+        &self,
+        id: Uuid,
+        progress: JobProgress,
+        outcome: Result<ParsedDocument, DomainError>,
+    ) {
+        let mut active = self.active.lock().await;
+        if active.release(id).is_none() {
+            return;
+        }
+        let now = OffsetDateTime::now_utc();
+        let expires_at = self.expiry(now);
+        let finished = self.update(id, |job| {
+            job.finished_at = Some(now);
+            job.expires_at = Some(expires_at);
+            job.progress = progress;
+            match outcome {
+                Ok(document) => {
+                    job.state = JobState::Succeeded;
+                    job.result = Some(Arc::new(document));
+                }
+                Err(e) => {
+                    job.state = JobState::Failed;
+                    job.error = Some(e.to_string());
+                }
+            }
+        });
+        if let Err(e) = finished.await {
+            warn!(job_id = %id, error = %e, "Failed to save parse job result");
+        }
+        drop(active);
+        info!(job_id = %id, "Parse job finished");
+    }
+
+    async fn update(&self, id: Uuid, apply: impl FnOnce(&mut ParseJob)) -> Result<(), DomainError> {
+        let mut job = self
+            .store
+            .load(id)
+            .await?
+            .ok_or_else(|| DomainError::job_not_found(id))?;
+        apply(&mut job);
+        self.store.save(job).await
+    }
+
+    fn expiry(&self, finished_at: OffsetDateTime) -> OffsetDateTime {
+        let ttl = time::Duration::try_from(self.config.result_ttl).unwrap_or(time::Duration::MAX);
+        finished_at.saturating_add(ttl)
+    }
+}
@@ -45,7 +45,7 @@ impl Iterator for MarkdownRenderIter {
 
 impl MarkdownRenderIter {
     /// Render the header chunk (title + metadata)
-    fn render_header(doc: &ParsedDocument) -> String {
+    pub(crate) fn render_header(doc: &ParsedDocument) -> String {
         let mut header = String::new();
 
         // Render title if present
@@ -59,6 +59,7 @@ impl MarkdownRenderIter {
         if doc.language.is_some()
             || doc.meta.original_filename.is_some()
             || doc.meta.content_type.is_some()
+            || doc.meta.author.is_some()
         {
             use std::fmt::Write;
             header.push_str("---\n");
@@ -71,6 +72,9 @@ impl MarkdownRenderIter {
             if let Some(ref content_type) = doc.meta.content_type {
                 let _ = writeln!(header, "content-type: {content_type}");
             }
+            if let Some(ref author) = doc.meta.author {
+                let _ = writeln!(header, "author: {author}");
+            }
             header.push_str("---\n\n");
         }
 
@@ -124,16 +128,16 @@ impl MarkdownRenderer {
         output
     }
 
-    fn render_block(block: &ParsedBlock, output: &mut String) {
+    pub(crate) fn render_block(block: &ParsedBlock, output: &mut String) {
         match block {
-            ParsedBlock::Heading { level, inlines } => {
+            ParsedBlock::Heading { level, inlines, .. } => {
                 let level = (*level).clamp(1, 6);
                 output.push_str(&"#".repeat(level as usize));
                 output.push(' ');
                 Self::render_inlines(inlines, output);
                 output.push_str("\n\n");
             }
-            ParsedBlock::Paragraph { inlines } => {
+            ParsedBlock::Paragraph { inlines, .. } => {
                 Self::render_inlines(inlines, output);
                 output.push_str("\n\n");
             }
@@ -385,18 +389,23 @@ mod tests {
                 source: ParsedSource::LocalPath("test.txt".to_owned()),
                 original_filename: None,
                 content_type: None,
+                author: None,
                 created_at: None,
                 modified_at: None,
                 is_stub: false,
+                email: None,
+                image: None,
             },
             blocks: vec![
                 ParsedBlock::Heading {
                     level: 1,
                     inlines: vec![Inline::plain("Title")],
+                    location: None,
                 },
                 ParsedBlock::Heading {
                     level: 2,
                     inlines: vec![Inline::plain("Subtitle")],
+                    location: None,
                 },
             ],
         };
@@ -416,12 +425,16 @@ mod tests {
                 source: ParsedSource::LocalPath("test.txt".to_owned()),
                 original_filename: None,
                 content_type: None,
+                author: None,
                 created_at: None,
                 modified_at: None,
                 is_stub: false,
+                email: None,
+                image: None,
             },
             blocks: vec![ParsedBlock::Paragraph {
                 inlines: vec![Inline::plain("Hello world")],
+                location: None,
             }],
         };
 
@@ -445,12 +458,16 @@ mod tests {
                 source: ParsedSource::LocalPath("test.txt".to_owned()),
                 original_filename: None,
                 content_type: None,
+                author: None,
                 created_at: None,
                 modified_at: None,
                 is_stub: false,
+                email: None,
+                image: None,
             },
             blocks: vec![ParsedBlock::Paragraph {
                 inlines: vec![Inline::styled("Bold and italic", style)],
+                location: None,
             }],
         };
 
@@ -468,9 +485,12 @@ mod tests {
                 source: ParsedSource::LocalPath("test.txt".to_owned()),
                 original_filename: None,
                 content_type: None,
+                author: None,
                 created_at: None,
                 modified_at: None,
                 is_stub: false,
+                email: None,
+                image: None,
             },
             blocks: vec![
                 ParsedBlock::ListItem {
@@ -478,6 +498,7 @@ mod tests {
                     ordered: false,
                     blocks: vec![ParsedBlock::Paragraph {
                         inlines: vec![Inline::plain("Item 1")],
+                        location: None,
                     }],
                 },
                 ParsedBlock::ListItem {
@@ -485,6 +506,7 @@ mod tests {
                     ordered: false,
                     blocks: vec![ParsedBlock::Paragraph {
                         inlines: vec![Inline::plain("Nested item")],
+                        location: None,
                     }],
                 },
             ],
@@ -505,9 +527,12 @@ mod tests {
                 source: ParsedSource::LocalPath("test.txt".to_owned()),
                 original_filename: None,
                 content_type: None,
+                author: None,
                 created_at: None,
                 modified_at: None,
                 is_stub: false,
+                email: None,
+                image: None,
             },
             blocks: vec![ParsedBlock::CodeBlock {
                 language: Some("rust".to_owned()),
@@ -530,11 +555,13 @@ mod tests {
                         TableCell {
                             blocks: vec![ParsedBlock::Paragraph {
                                 inlines: vec![Inline::plain("Name")],
+                                location: None,
                             }],
                         },
                         TableCell {
                             blocks: vec![ParsedBlock::Paragraph {
                                 inlines: vec![Inline::plain("Age")],
+                                location: None,
                             }],
                         },
                     ],
@@ -545,16 +572,19 @@ mod tests {
                         TableCell {
                             blocks: vec![ParsedBlock::Paragraph {
                                 inlines: vec![Inline::plain("Alice")],
+                                location: None,
                             }],
                         },
                         TableCell {
                             blocks: vec![ParsedBlock::Paragraph {
                                 inlines: vec![Inline::plain("30")],
+                                location: None,
                             }],
                         },
                     ],
                 },
             ],
+            location: None,
         };
 
         let doc = ParsedDocument {
@@ -565,9 +595,12 @@ mod tests {
                 source: ParsedSource::LocalPath("test.txt".to_owned()),
                 original_filename: None,
                 content_type: None,
+                author: None,
                 created_at: None,
                 modified_at: None,
                 is_stub: false,
+                email: None,
+                image: None,
             },
             blocks: vec![ParsedBlock::Table(table)],
         };
@@ -588,6 +621,7 @@ mod tests {
                     cells: vec![TableCell {
                         blocks: vec![ParsedBlock::Paragraph {
                             inlines: vec![Inline::plain("Column")],
+                            location: None,
                         }],
                     }],
                 },
@@ -596,6 +630,7 @@ mod tests {
                     cells: vec![TableCell {
                         blocks: vec![ParsedBlock::Paragraph {
                             inlines: vec![Inline::plain("Pipe|test")],
+                            location: None,
                         }],
                     }],
                 },
@@ -604,10 +639,12 @@ mod tests {
                     cells: vec![TableCell {
                         blocks: vec![ParsedBlock::Paragraph {
                             inlines: vec![Inline::plain("Backslash\\test")],
+                            location: None,
                         }],
                     }],
                 },
             ],
+            location: None,
         };
 
         let doc = ParsedDocument {
@@ -618,9 +655,12 @@ mod tests {
                 source: ParsedSource::LocalPath("test.txt".to_owned()),
                 original_filename: None,
                 content_type: None,
+                author: None,
                 created_at: None,
                 modified_at: None,
                 is_stub: false,
+                email: None,
+                image: None,
             },
             blocks: vec![ParsedBlock::Table(table)],
         };
@@ -640,6 +680,7 @@ mod tests {
                     cells: vec![TableCell {
                         blocks: vec![ParsedBlock::Paragraph {
                             inlines: vec![Inline::plain("Inner")],
+                            location: None,
                         }],
                     }],
                 },
@@ -648,10 +689,12 @@ mod tests {
                     cells: vec![TableCell {
                         blocks: vec![ParsedBlock::Paragraph {
                             inlines: vec![Inline::plain("Data")],
+                            location: None,
                         }],
                     }],
                 },
             ],
+            location: None,
         };
 
         let outer_table = TableBlock {
@@ -661,6 +704,7 @@ mod tests {
                     cells: vec![TableCell {
                         blocks: vec![ParsedBlock::Paragraph {
                             inlines: vec![Inline::plain("Outer")],
+                            location: None,
                         }],
                     }],
                 },
@@ -671,6 +715,7 @@ mod tests {
                     }],
                 },
             ],
+            location: None,
         };
 
         let doc = ParsedDocument {
@@ -681,9 +726,12 @@ mod tests {
                 source: ParsedSource::LocalPath("test.txt".to_owned()),
                 original_filename: None,
                 content_type: None,
+                author: None,
                 created_at: None,
                 modified_at: None,
                 is_stub: false,
+                email: None,
+                image: None,
             },
             blocks: vec![ParsedBlock::Table(outer_table)],
         };
@@ -704,12 +752,16 @@ mod tests {
                 source: ParsedSource::LocalPath("test.txt".to_owned()),
                 original_filename: None,
                 content_type: None,
+                author: None,
                 created_at: None,
                 modified_at: None,
                 is_stub: false,
+                email: None,
+                image: None,
             },
             blocks: vec![ParsedBlock::Paragraph {
                 inlines: vec![Inline::plain("Content")],
+                location: None,
             }],
         };
 
@@ -727,20 +779,26 @@ mod tests {
                 source: ParsedSource::LocalPath("test.txt".to_owned()),
                 original_filename: Some("test.txt".to_owned()),
                 content_type: Some("text/plain".to_owned()),
+                author: None,
                 created_at: None,
                 modified_at: None,
                 is_stub: false,
+                email: None,
+                image: None,
             },
             blocks: vec![
                 ParsedBlock::Heading {
                     level: 2,
                     inlines: vec![Inline::plain("Section 1")],
+                    location: None,
                 },
                 ParsedBlock::Paragraph {
                     inlines: vec![Inline::plain("First paragraph")],
+                    location: None,
                 },
                 ParsedBlock::Paragraph {
                     inlines: vec![Inline::plain("Second paragraph")],
+                    location: None,
                 },
             ],
         };
@@ -778,12 +836,16 @@ mod tests {
                 source: ParsedSource::LocalPath("test.txt".to_owned()),
                 original_filename: None,
                 content_type: None,
+                author: None,
                 created_at: None,
                 modified_at: None,
                 is_stub: false,
+                email: None,
+                image: None,
             },
             blocks: vec![ParsedBlock::Paragraph {
                 inlines: vec![Inline::plain("Only content")],
+                location: None,
             }],
         };
 
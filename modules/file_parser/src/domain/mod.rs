@@ -1,11 +1,27 @@
+pub mod chunking;
 pub mod error;
+pub mod fetch;
+pub mod html;
 pub mod ir;
+pub mod jobs;
 pub mod markdown;
+pub mod ocr;
 pub mod parser;
+pub mod plain_text;
+pub mod sandbox;
 pub mod service;
+pub mod sniff;
 
+pub use chunking::*;
 pub use error::*;
+pub use fetch::*;
+pub use html::*;
 pub use ir::*;
+pub use jobs::*;
 pub use markdown::*;
+pub use ocr::*;
 pub use parser::*;
+pub use plain_text::*;
+pub use sandbox::*;
 pub use service::*;
+pub use sniff::*;
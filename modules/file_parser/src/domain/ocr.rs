@@ -0,0 +1,20 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+
+use crate::domain::error::DomainError;
+use crate::domain::ir::ParsedBlock;
+
+/// Text recognizer for images. Deployments register an implementation as
+/// `dyn OcrProvider` in the `ClientHub`; without one, images have no text.
+#[async_trait]
+pub trait OcrProvider: Send + Sync {
+    /// Recognize the text of an image, as blocks placed after its `Image` block
+    ///
+    /// `bytes` is the image as embedded in the document, with GPS data
+    /// already stripped and animated GIFs cut to their first frame.
+    async fn recognize(
+        &self,
+        mime_type: &str,
+        bytes: Bytes,
+    ) -> Result<Vec<ParsedBlock>, DomainError>;
+}
@@ -0,0 +1,263 @@
+use std::fmt::Write;
+
+use crate::domain::ir::{Inline, ParsedBlock, ParsedDocument, TableBlock};
+
+/// Plain text renderer that converts `ParsedDocument` to unformatted text
+pub struct PlainTextRenderer;
+
+impl Default for PlainTextRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Iterator over plain text chunks from a `ParsedDocument`
+/// This iterator owns the document to avoid lifetime issues with async streaming
+pub struct PlainTextRenderIter {
+    doc: ParsedDocument,
+    header_emitted: bool,
+    block_index: usize,
+    /// Item numbers of the ordered lists in progress, by level
+    numbers: Vec<usize>,
+}
+
+impl Iterator for PlainTextRenderIter {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.header_emitted {
+            self.header_emitted = true;
+            if let Some(title) = &self.doc.title {
+                return Some(format!("{title}\n\n"));
+            }
+        }
+
+        let block = self.doc.blocks.get(self.block_index)?;
+        self.block_index += 1;
+        let mut chunk = String::new();
+        PlainTextRenderer::render_block(block, &mut self.numbers, &mut chunk);
+        Some(chunk)
+    }
+}
+
+impl PlainTextRenderer {
+    /// Create a new plain text renderer
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Create a streaming iterator over plain text chunks
+    /// Takes ownership of the document to avoid lifetime issues with async streaming
+    #[must_use]
+    pub fn render_iter(doc: ParsedDocument) -> PlainTextRenderIter {
+        PlainTextRenderIter {
+            doc,
+            header_emitted: false,
+            block_index: 0,
+            numbers: Vec::new(),
+        }
+    }
+
+    /// Render a parsed document to plain text
+    #[must_use]
+    pub fn render(doc: &ParsedDocument) -> String {
+        Self::render_iter(doc.clone()).collect()
+    }
+
+    fn render_block(block: &ParsedBlock, numbers: &mut Vec<usize>, output: &mut String) {
+        if let ParsedBlock::ListItem {
+            level,
+            ordered,
+            blocks,
+        } = block
+        {
+            let level = usize::from(*level);
+            numbers.resize(level + 1, 0);
+            output.push_str(&"  ".repeat(level));
+            if *ordered {
+                numbers[level] += 1;
+                let _ = write!(output, "{}. ", numbers[level]);
+            } else {
+                numbers[level] = 0;
+                output.push_str("- ");
+            }
+            let text = Self::render_nested(blocks);
+            let continuation = format!("\n{}", "  ".repeat(level + 1));
+            output.push_str(&text.replace('\n', &continuation));
+            output.push('\n');
+            return;
+        }
+        if !numbers.is_empty() {
+            // Blank line after the list
+            numbers.clear();
+            output.push('\n');
+        }
+
+        match block {
+            ParsedBlock::Heading { inlines, .. } | ParsedBlock::Paragraph { inlines, .. } => {
+                Self::render_inlines(inlines, output);
+                output.push_str("\n\n");
+            }
+            ParsedBlock::CodeBlock { code, .. } => {
+                output.push_str(code.trim_end_matches('\n'));
+                output.push_str("\n\n");
+            }
+            ParsedBlock::Table(table) => {
+                Self::render_table(table, output);
+                output.push('\n');
+            }
+            ParsedBlock::Quote { blocks } => {
+                for line in Self::render_nested(blocks).lines() {
+                    if !line.is_empty() {
+                        output.push_str("    ");
+                        output.push_str(line);
+                    }
+                    output.push('\n');
+                }
+                output.push('\n');
+            }
+            ParsedBlock::Image { alt, .. } => {
+                if let Some(alt_text) = alt.as_deref().filter(|a| !a.trim().is_empty()) {
+                    output.push_str(alt_text);
+                    output.push_str("\n\n");
+                }
+            }
+            ParsedBlock::PageBreak => output.push_str("\u{c}\n"),
+            ParsedBlock::HorizontalRule | ParsedBlock::ListItem { .. } => {}
+        }
+    }
+
+    /// Text of nested blocks, without trailing blank lines
+    fn render_nested(blocks: &[ParsedBlock]) -> String {
+        let mut numbers = Vec::new();
+        let mut text = String::new();
+        for block in blocks {
+            Self::render_block(block, &mut numbers, &mut text);
+        }
+        text.trim_end().to_owned()
+    }
+
+    /// One line per row with cells separated by ` | `
+    fn render_table(table: &TableBlock, output: &mut String) {
+        for row in &table.rows {
+            let cells: Vec<String> = row
+                .cells
+                .iter()
+                .map(|cell| Self::render_nested(&cell.blocks).replace('\n', " "))
+                .collect();
+            output.push_str(&cells.join(" | "));
+            output.push('\n');
+        }
+    }
+
+    fn render_inlines(inlines: &[Inline], output: &mut String) {
+        for inline in inlines {
+            match inline {
+                Inline::Text { text, .. } | Inline::Code { text, .. } => output.push_str(text),
+                Inline::Link { text, target, .. } => {
+                    output.push_str(text);
+                    if target != text && !target.starts_with('#') {
+                        let _ = write!(output, " ({target})");
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::domain::ir::{DocumentBuilder, InlineStyle, ParsedSource, TableCell, TableRow};
+
+    fn paragraph(text: &str) -> ParsedBlock {
+        ParsedBlock::Paragraph {
+            inlines: vec![Inline::plain(text)],
+            location: None,
+        }
+    }
+
+    fn document(blocks: Vec<ParsedBlock>) -> ParsedDocument {
+        DocumentBuilder::new(ParsedSource::LocalPath("test.txt".to_owned()))
+            .title("Report")
+            .blocks(blocks)
+            .build()
+    }
+
+    #[test]
+    fn test_render_drops_markup() {
+        let doc = document(vec![
+            ParsedBlock::Heading {
+                level: 2,
+                inlines: vec![Inline::plain("Results")],
+                location: None,
+            },
+            ParsedBlock::Paragraph {
+                inlines: vec![
+                    Inline::styled(
+                        "Sales",
+                        InlineStyle {
+                            bold: true,
+                            ..InlineStyle::default()
+                        },
+                    ),
+                    Inline::plain(" grew, see "),
+                    Inline::link("the site", "https://example.com"),
+                ],
+                location: None,
+            },
+        ]);
+
+        assert_eq!(
+            PlainTextRenderer::render(&doc),
+            "Report\n\nResults\n\nSales grew, see the site (https://example.com)\n\n"
+        );
+    }
+
+    #[test]
+    fn test_render_numbered_lists() {
+        let item = |level: u8, ordered: bool, text: &str| ParsedBlock::ListItem {
+            level,
+            ordered,
+            blocks: vec![paragraph(text)],
+        };
+        let doc = document(vec![
+            item(0, true, "first"),
+            item(1, false, "detail"),
+            item(0, true, "second"),
+            paragraph("done"),
+        ]);
+
+        assert_eq!(
+            PlainTextRenderer::render(&doc),
+            "Report\n\n1. first\n  - detail\n2. second\n\ndone\n\n"
+        );
+    }
+
+    #[test]
+    fn test_render_table_rows() {
+        let cell = |text: &str| TableCell {
+            blocks: vec![paragraph(text)],
+        };
+        let doc = document(vec![ParsedBlock::Table(TableBlock {
+            rows: vec![
+                TableRow {
+                    is_header: true,
+                    cells: vec![cell("Name"), cell("Age")],
+                },
+                TableRow {
+                    is_header: false,
+                    cells: vec![cell("Ada"), cell("36")],
+                },
+            ],
+            location: None,
+        })]);
+
+        assert_eq!(
+            PlainTextRenderer::render(&doc),
+            "Report\n\nName | Age\nAda | 36\n\n"
+        );
+    }
+}
@@ -0,0 +1,146 @@
+//! Sandbox for `parse_local`: which local files a tenant may read.
+//!
+//! A path is allowed if it lies under one of the shared roots or one of the
+//! caller's tenant roots, both before and after resolving symlinks, so that
+//! neither `..` nor a symlink inside a root can reach other files.
+
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+
+use uuid::Uuid;
+
+use crate::config::LocalFilesConfig;
+use crate::domain::error::DomainError;
+
+/// A configured root, as written and with symlinks resolved
+#[derive(Debug, Clone)]
+struct Root {
+    configured: PathBuf,
+    canonical: PathBuf,
+}
+
+impl Root {
+    fn new(path: &Path) -> Option<Self> {
+        match std::fs::canonicalize(path) {
+            Ok(canonical) => Some(Self {
+                configured: std::path::absolute(path).unwrap_or_else(|_| canonical.clone()),
+                canonical,
+            }),
+            Err(e) => {
+                tracing::warn!(
+                    root = %path.display(),
+                    error = %e,
+                    "Ignoring local files root that cannot be resolved"
+                );
+                None
+            }
+        }
+    }
+
+    fn contains_lexically(&self, path: &Path) -> bool {
+        path.starts_with(&self.configured) || path.starts_with(&self.canonical)
+    }
+}
+
+/// Local directories `parse_local` may read from
+#[derive(Debug, Clone, Default)]
+pub struct LocalSandbox {
+    shared: Vec<Root>,
+    tenants: HashMap<Uuid, Vec<Root>>,
+}
+
+impl LocalSandbox {
+    /// Build the sandbox, skipping roots that do not exist
+    #[must_use]
+    pub fn new(config: &LocalFilesConfig) -> Self {
+        let resolve = |roots: &[PathBuf]| roots.iter().filter_map(|r| Root::new(r)).collect();
+        Self {
+            shared: resolve(&config.allowed_roots),
+            tenants: config
+                .tenant_roots
+                .iter()
+                .map(|(tenant_id, roots)| (*tenant_id, resolve(roots)))
+                .collect(),
+        }
+    }
+
+    fn roots(&self, tenant_id: Uuid) -> impl Iterator<Item = &Root> {
+        self.shared
+            .iter()
+            .chain(self.tenants.get(&tenant_id).into_iter().flatten())
+    }
+
+    /// Resolve `path` for `tenant_id` to the canonical path of the file
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathNotAllowed` if the path is relative or lies outside the
+    /// tenant's roots, and `FileNotFound` if it does not exist within them
+    pub async fn resolve(&self, tenant_id: Uuid, path: &Path) -> Result<PathBuf, DomainError> {
+        let not_allowed = || DomainError::path_not_allowed(path.display().to_string());
+
+        // Check the path as given first, so nothing outside the roots is
+        // touched, not even to tell whether it exists
+        let normalized = normalize(path).ok_or_else(not_allowed)?;
+        if !self
+            .roots(tenant_id)
+            .any(|r| r.contains_lexically(&normalized))
+        {
+            return Err(not_allowed());
+        }
+
+        let canonical = tokio::fs::canonicalize(&normalized)
+            .await
+            .map_err(|_| DomainError::file_not_found(path.display().to_string()))?;
+        if !self
+            .roots(tenant_id)
+            .any(|r| canonical.starts_with(&r.canonical))
+        {
+            tracing::warn!(
+                path = %path.display(),
+                resolved = %canonical.display(),
+                "Path escapes the local files sandbox through a symlink"
+            );
+            return Err(not_allowed());
+        }
+        Ok(canonical)
+    }
+}
+
+/// Lexically resolve `.` and `..` in an absolute path; `None` for relative
+/// paths and for `..` above the filesystem root
+fn normalize(path: &Path) -> Option<PathBuf> {
+    if !path.is_absolute() {
+        return None;
+    }
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Prefix(_) | Component::RootDir | Component::Normal(_) => {
+                normalized.push(component);
+            }
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() || !normalized.has_root() {
+                    return None;
+                }
+            }
+        }
+    }
+    Some(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(
+            normalize(Path::new("/data/./docs/../a.pdf")),
+            Some(PathBuf::from("/data/a.pdf"))
+        );
+        assert_eq!(normalize(Path::new("/../etc/passwd")), None);
+        assert_eq!(normalize(Path::new("docs/a.pdf")), None);
+    }
+}
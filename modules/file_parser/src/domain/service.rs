@@ -1,12 +1,18 @@
 use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 
 use bytes::Bytes;
+use modkit_security::SecurityContext;
 use tracing::{debug, info, instrument};
 
+use crate::config::{LocalFilesConfig, UrlFetchConfig};
 use crate::domain::error::DomainError;
+use crate::domain::fetch::UrlFetcher;
 use crate::domain::ir::ParsedDocument;
 use crate::domain::parser::FileParserBackend;
+use crate::domain::sandbox::LocalSandbox;
+use crate::domain::sniff;
 
 /// Mapping of file extensions to MIME types
 /// Format: `(extension, mime_type)`
@@ -18,11 +24,32 @@ const EXTENSION_MIME_MAPPINGS: &[(&str, &str)] = &[
         "docx",
         "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
     ),
+    ("doc", "application/msword"),
+    ("rtf", "application/rtf"),
+    ("rtf", "text/rtf"),
+    ("odt", "application/vnd.oasis.opendocument.text"),
+    ("ods", "application/vnd.oasis.opendocument.spreadsheet"),
+    ("odp", "application/vnd.oasis.opendocument.presentation"),
+    ("csv", "text/csv"),
+    ("tsv", "text/tab-separated-values"),
+    ("json", "application/json"),
+    ("yaml", "application/yaml"),
+    ("yaml", "application/x-yaml"),
+    ("yaml", "text/yaml"),
+    ("yml", "application/yaml"),
+    ("yml", "application/x-yaml"),
+    ("yml", "text/yaml"),
+    ("xml", "application/xml"),
+    ("xml", "text/xml"),
+    ("epub", "application/epub+zip"),
+    ("eml", "message/rfc822"),
+    ("mbox", "application/mbox"),
     ("png", "image/png"),
     ("jpg", "image/jpeg"),
     ("jpeg", "image/jpeg"),
     ("webp", "image/webp"),
     ("gif", "image/gif"),
+    ("txt", "text/plain"),
 ];
 
 /// File parser service that routes to appropriate backends
@@ -30,6 +57,8 @@ const EXTENSION_MIME_MAPPINGS: &[(&str, &str)] = &[
 pub struct FileParserService {
     parsers: Vec<Arc<dyn FileParserBackend>>,
     config: ServiceConfig,
+    fetcher: Arc<UrlFetcher>,
+    sandbox: Arc<LocalSandbox>,
 }
 
 /// Configuration for the file parser service
@@ -37,6 +66,10 @@ pub struct FileParserService {
 pub struct ServiceConfig {
     pub max_file_size_bytes: usize,
     pub download_timeout_secs: u64,
+    /// Outbound policy for `parse_url`
+    pub url_fetch: UrlFetchConfig,
+    /// Directories `parse_local` may read from
+    pub local_files: LocalFilesConfig,
 }
 
 impl Default for ServiceConfig {
@@ -44,6 +77,8 @@ impl Default for ServiceConfig {
         Self {
             max_file_size_bytes: 100 * 1024 * 1024, // 100 MB
             download_timeout_secs: 60,
+            url_fetch: UrlFetchConfig::default(),
+            local_files: LocalFilesConfig::default(),
         }
     }
 }
@@ -56,9 +91,26 @@ pub struct FileParserInfo {
 
 impl FileParserService {
     /// Create a new service with the given parsers
-    #[must_use]
-    pub fn new(parsers: Vec<Arc<dyn FileParserBackend>>, config: ServiceConfig) -> Self {
-        Self { parsers, config }
+    ///
+    /// # Errors
+    ///
+    /// Returns `DownloadError` if the HTTP client for `parse_url` cannot be created
+    pub fn new(
+        parsers: Vec<Arc<dyn FileParserBackend>>,
+        config: ServiceConfig,
+    ) -> Result<Self, DomainError> {
+        let fetcher = UrlFetcher::new(
+            config.url_fetch.clone(),
+            Duration::from_secs(config.download_timeout_secs),
+            config.max_file_size_bytes,
+        )?;
+        let sandbox = LocalSandbox::new(&config.local_files);
+        Ok(Self {
+            parsers,
+            config,
+            fetcher: Arc::new(fetcher),
+            sandbox: Arc::new(sandbox),
+        })
     }
 
     /// Get information about available parsers
@@ -83,15 +135,18 @@ impl FileParserService {
         }
     }
 
-    /// Parse a file from a local path
-    #[instrument(skip(self), fields(path = %path.display()))]
-    pub async fn parse_local(&self, path: &Path) -> Result<ParsedDocument, DomainError> {
+    /// Parse a file from a local path within the caller's allowed roots
+    #[instrument(skip(self, ctx), fields(path = %path.display(), tenant_id = %ctx.tenant_id()))]
+    pub async fn parse_local(
+        &self,
+        ctx: &SecurityContext,
+        path: &Path,
+    ) -> Result<ParsedDocument, DomainError> {
         info!("Parsing file from local path");
 
-        // Check if file exists
-        if !path.exists() {
-            return Err(DomainError::file_not_found(path.display().to_string()));
-        }
+        // Resolve the path inside the sandbox; this also checks it exists
+        let resolved = self.sandbox.resolve(ctx.tenant_id(), path).await?;
+        let path = resolved.as_path();
 
         // Extract extension
         let extension = path
@@ -187,69 +242,67 @@ impl FileParserService {
     }
 
     /// Parse a file from a URL
-    #[instrument(skip(self), fields(url = %url))]
     pub async fn parse_url(&self, url: &url::Url) -> Result<ParsedDocument, DomainError> {
+        self.parse_url_with_progress(url, |_, _| {}).await
+    }
+
+    /// Parse a file from a URL, calling `progress` with the bytes downloaded
+    /// so far and the size of the file, if announced or once downloaded
+    #[instrument(skip(self, progress), fields(url = %url))]
+    pub async fn parse_url_with_progress(
+        &self,
+        url: &url::Url,
+        mut progress: impl FnMut(u64, Option<u64>) + Send,
+    ) -> Result<ParsedDocument, DomainError> {
         info!("Parsing file from URL");
 
-        // Extract extension from URL path
+        // Extract extension from URL path, if there is one
         let path = Path::new(url.path());
-        let extension = path
+        let url_extension = path
             .extension()
             .and_then(|s| s.to_str())
-            .ok_or_else(|| DomainError::unsupported_file_type("no extension in URL"))?;
+            .map(ToString::to_string);
 
-        // Find parser
-        let parser = self
-            .find_parser_by_extension(extension)
-            .ok_or_else(|| DomainError::no_parser_available(extension))?;
+        // Find parser up front so unsupported files are not downloaded
+        let url_parser = url_extension
+            .as_deref()
+            .map(|ext| {
+                self.find_parser_by_extension(ext)
+                    .ok_or_else(|| DomainError::no_parser_available(ext))
+            })
+            .transpose()?;
 
         // Download file
         debug!("Downloading file from URL");
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(
-                self.config.download_timeout_secs,
-            ))
-            .build()
-            .map_err(|e| {
-                tracing::error!(?e, "FileParserService: failed to create HTTP client");
-                DomainError::download_error(format!("Failed to create HTTP client: {e}"))
-            })?;
-
-        let response = client.get(url.as_str()).send().await.map_err(|e| {
-            tracing::error!(?e, "FileParserService: failed to download file");
-            DomainError::download_error(format!("Failed to download file: {e}"))
-        })?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            tracing::error!(?status, "FileParserService: HTTP error during download");
-            return Err(DomainError::download_error(format!("HTTP error: {status}")));
-        }
-
-        let content_type = response
-            .headers()
-            .get("content-type")
-            .and_then(|v| v.to_str().ok())
-            .map(ToString::to_string);
+        let download = self.fetcher.open(url).await?;
+        let content_type = download.content_type.clone();
 
         // Validate MIME type if present
-        if let Some(ref ct) = content_type {
-            Self::validate_mime_type(extension, ct)?;
+        if let (Some(ext), Some(ct)) = (&url_extension, &content_type) {
+            Self::validate_mime_type(ext, ct)?;
         }
 
-        let bytes = response.bytes().await.map_err(|e| {
-            tracing::error!(?e, "FileParserService: failed to read response bytes");
-            DomainError::download_error(format!("Failed to read response: {e}"))
-        })?;
+        let total = download.content_length;
+        let bytes = download.bytes(|read| progress(read, total)).await?;
+        // The size is known once the download is complete
+        let len = u64::try_from(bytes.len()).unwrap_or(u64::MAX);
+        progress(len, Some(len));
 
-        // Check file size
-        if bytes.len() > self.config.max_file_size_bytes {
-            return Err(DomainError::invalid_request(format!(
-                "File size {} exceeds maximum of {} bytes",
-                bytes.len(),
-                self.config.max_file_size_bytes
-            )));
-        }
+        // Without an extension in the URL, go by Content-Type, then by content
+        let parser = if let Some(parser) = url_parser {
+            parser
+        } else {
+            let extension = content_type
+                .as_deref()
+                .and_then(Self::extension_from_content_type)
+                .or_else(|| sniff::extension_from_content(&bytes).map(ToString::to_string))
+                .ok_or_else(|| {
+                    DomainError::unsupported_file_type("no extension in URL and unknown content")
+                })?;
+            debug!(extension, "Detected file type of downloaded content");
+            self.find_parser_by_extension(&extension)
+                .ok_or_else(|| DomainError::no_parser_available(&extension))?
+        };
 
         // Parse the downloaded file
         let file_name = path
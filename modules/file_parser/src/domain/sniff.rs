@@ -0,0 +1,129 @@
+//! Content sniffing: guessing the file type from the leading bytes, for
+//! files whose name carries no extension.
+
+/// ZIP-based formats, told apart by the entries they contain
+const ZIP_ENTRY_MARKERS: &[(&[u8], &str)] = &[
+    (b"application/epub+zip", "epub"),
+    (b"word/", "docx"),
+    (b"xl/", "xlsx"),
+    (b"ppt/", "pptx"),
+    (b"application/vnd.oasis.opendocument.text", "odt"),
+    (b"application/vnd.oasis.opendocument.spreadsheet", "ods"),
+    (b"application/vnd.oasis.opendocument.presentation", "odp"),
+];
+
+/// Signature of the OLE compound file container used by legacy Office files
+const COMPOUND_FILE_SIGNATURE: &[u8] = &[0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+
+/// Name of the main stream of a Word document, as stored in the directory
+const WORD_DOCUMENT_ENTRY: &[u8] = b"W\0o\0r\0d\0D\0o\0c\0u\0m\0e\0n\0t\0";
+
+/// Guess the file extension from the content
+#[must_use]
+pub fn extension_from_content(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(b"%PDF-") {
+        return Some("pdf");
+    }
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        return Some("png");
+    }
+    if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        return Some("jpg");
+    }
+    if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        return Some("gif");
+    }
+    if bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(b"WEBP") {
+        return Some("webp");
+    }
+    if bytes.starts_with(b"{\\rtf") {
+        return Some("rtf");
+    }
+    if bytes.starts_with(COMPOUND_FILE_SIGNATURE) {
+        return contains(bytes, WORD_DOCUMENT_ENTRY).then_some("doc");
+    }
+    if bytes.starts_with(b"PK\x03\x04") {
+        return ZIP_ENTRY_MARKERS
+            .iter()
+            .find(|(marker, _)| contains(bytes, marker))
+            .map(|(_, ext)| *ext);
+    }
+
+    let text = std::str::from_utf8(bytes).ok()?;
+    if text.contains('\0') {
+        return None;
+    }
+    let text = text.trim_start_matches('\u{feff}').trim_start();
+    let head = text.get(..64).unwrap_or(text).to_ascii_lowercase();
+    if head.starts_with("<!doctype html") || head.starts_with("<html") {
+        Some("html")
+    } else if text.starts_with("From ") {
+        Some("mbox")
+    } else if EMAIL_HEADERS.iter().any(|header| head.starts_with(header)) {
+        Some("eml")
+    } else {
+        Some("txt")
+    }
+}
+
+/// Header names a message commonly starts with
+const EMAIL_HEADERS: &[&str] = &[
+    "return-path:",
+    "received:",
+    "delivered-to:",
+    "message-id:",
+    "mime-version:",
+    "from:",
+    "date:",
+    "subject:",
+];
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extension_from_content() {
+        assert_eq!(extension_from_content(b"%PDF-1.7\n..."), Some("pdf"));
+        assert_eq!(
+            extension_from_content(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"),
+            Some("png")
+        );
+        assert_eq!(
+            extension_from_content(b"RIFF\0\0\0\0WEBPVP8 "),
+            Some("webp")
+        );
+        assert_eq!(
+            extension_from_content(b"PK\x03\x04\x14\0\0\0word/document.xml"),
+            Some("docx")
+        );
+        assert_eq!(
+            extension_from_content(b"PK\x03\x04\x14\0\0\0mimetypeapplication/epub+zip"),
+            Some("epub")
+        );
+        assert_eq!(
+            extension_from_content(b"PK\x03\x04\x14\0\0\0other.bin"),
+            None
+        );
+        assert_eq!(
+            extension_from_content(b"\n  <!DOCTYPE html><html><body>Hi</body></html>"),
+            Some("html")
+        );
+        assert_eq!(
+            extension_from_content(b"From alice@example.com Fri Mar 15 09:30:00 2024\n"),
+            Some("mbox")
+        );
+        assert_eq!(
+            extension_from_content(b"Received: from mx.example.com\r\nFrom: a@example.com"),
+            Some("eml")
+        );
+        assert_eq!(extension_from_content(b"Hello, world"), Some("txt"));
+        assert_eq!(extension_from_content(&[0, 159, 146, 150]), None);
+    }
+}
@@ -0,0 +1,44 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use time::OffsetDateTime;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::domain::error::DomainError;
+use crate::domain::jobs::{JobStore, ParseJob};
+
+/// Job store keeping jobs and results in process memory; they are lost on
+/// restart and not shared between instances
+#[derive(Default)]
+pub struct InMemoryJobStore {
+    jobs: RwLock<HashMap<Uuid, ParseJob>>,
+}
+
+impl InMemoryJobStore {
+    /// Create an empty store
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl JobStore for InMemoryJobStore {
+    async fn save(&self, job: ParseJob) -> Result<(), DomainError> {
+        self.jobs.write().await.insert(job.id, job);
+        Ok(())
+    }
+
+    async fn load(&self, id: Uuid) -> Result<Option<ParseJob>, DomainError> {
+        Ok(self.jobs.read().await.get(&id).cloned())
+    }
+
+    async fn purge_expired(&self, now: OffsetDateTime) -> Result<(), DomainError> {
+        self.jobs
+            .write()
+            .await
+            .retain(|_, job| job.expires_at.is_none_or(|expires_at| expires_at > now));
+        Ok(())
+    }
+}
@@ -1,3 +1,5 @@
+pub mod job_store;
 pub mod parsers;
 
+pub use job_store::InMemoryJobStore;
 pub use parsers::*;
@@ -0,0 +1,335 @@
+use async_trait::async_trait;
+use std::borrow::Cow;
+use std::path::Path;
+
+use crate::domain::error::DomainError;
+use crate::domain::ir::{
+    DocumentBuilder, Inline, ParsedBlock, ParsedSource, TableBlock, TableCell, TableRow,
+};
+use crate::domain::parser::FileParserBackend;
+
+/// CSV/TSV parser producing a single table
+pub struct CsvParser;
+
+/// File extension constants
+const EXT_CSV: &str = "csv";
+const EXT_TSV: &str = "tsv";
+
+/// Supported file extensions
+const SUPPORTED_EXTENSIONS: &[&str] = &[EXT_CSV, EXT_TSV];
+
+/// MIME type constants
+const MIME_TYPE_CSV: &str = "text/csv";
+const MIME_TYPE_TSV: &str = "text/tab-separated-values";
+
+/// Rows beyond this many (header excluded) are dropped
+const MAX_ROWS: usize = 10_000;
+
+/// Columns beyond this many are dropped
+const MAX_COLUMNS: usize = 256;
+
+/// Delimiters considered when sniffing a `.csv` file
+const CANDIDATE_DELIMITERS: &[char] = &[',', ';', '\t', '|'];
+
+impl CsvParser {
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for CsvParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl FileParserBackend for CsvParser {
+    fn id(&self) -> &'static str {
+        EXT_CSV
+    }
+
+    fn supported_extensions(&self) -> &'static [&'static str] {
+        SUPPORTED_EXTENSIONS
+    }
+
+    async fn parse_local_path(
+        &self,
+        path: &Path,
+    ) -> Result<crate::domain::ir::ParsedDocument, DomainError> {
+        let bytes = tokio::fs::read(path)
+            .await
+            .map_err(|e| DomainError::io_error(format!("Failed to read file: {e}")))?;
+
+        let filename = path.file_name().and_then(|s| s.to_str());
+        let is_tsv = is_tsv(filename);
+        let blocks = tokio::task::spawn_blocking(move || parse_delimited(&bytes, is_tsv))
+            .await
+            .map_err(|e| DomainError::parse_error(format!("Task join error: {e}")))?;
+
+        let mut builder = DocumentBuilder::new(ParsedSource::LocalPath(path.display().to_string()))
+            .content_type(if is_tsv { MIME_TYPE_TSV } else { MIME_TYPE_CSV })
+            .blocks(blocks);
+
+        if let Some(filename) = filename {
+            builder = builder.title(filename).original_filename(filename);
+        }
+
+        Ok(builder.build())
+    }
+
+    async fn parse_bytes(
+        &self,
+        filename_hint: Option<&str>,
+        content_type: Option<&str>,
+        bytes: bytes::Bytes,
+    ) -> Result<crate::domain::ir::ParsedDocument, DomainError> {
+        let is_tsv = is_tsv(filename_hint) || content_type == Some(MIME_TYPE_TSV);
+        let blocks = tokio::task::spawn_blocking(move || parse_delimited(&bytes, is_tsv))
+            .await
+            .map_err(|e| DomainError::parse_error(format!("Task join error: {e}")))?;
+
+        let source = ParsedSource::Uploaded {
+            original_name: filename_hint.unwrap_or("unknown.csv").to_owned(),
+        };
+
+        let mut builder = DocumentBuilder::new(source)
+            .content_type(if is_tsv { MIME_TYPE_TSV } else { MIME_TYPE_CSV })
+            .blocks(blocks);
+
+        if let Some(filename) = filename_hint {
+            builder = builder.title(filename).original_filename(filename);
+        }
+
+        Ok(builder.build())
+    }
+}
+
+fn is_tsv(filename: Option<&str>) -> bool {
+    filename
+        .and_then(|name| Path::new(name).extension())
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(EXT_TSV))
+}
+
+/// Decode text as UTF-8 (without BOM), falling back to Windows-1252 for
+/// files saved by older spreadsheet applications
+pub(crate) fn decode_text(bytes: &[u8]) -> Cow<'_, str> {
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    match std::str::from_utf8(bytes) {
+        Ok(text) => Cow::Borrowed(text),
+        Err(_) => encoding_rs::WINDOWS_1252.decode(bytes).0,
+    }
+}
+
+fn parse_delimited(bytes: &[u8], is_tsv: bool) -> Vec<ParsedBlock> {
+    let text = decode_text(bytes);
+    let delimiter = if is_tsv { '\t' } else { sniff_delimiter(&text) };
+
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut total = 0;
+    for record in Records::new(&text, delimiter) {
+        if record.iter().all(|cell| cell.trim().is_empty()) {
+            continue;
+        }
+        total += 1;
+        // One extra row in case the first one is a header
+        if rows.len() <= MAX_ROWS {
+            rows.push(record);
+        }
+    }
+    if rows.is_empty() {
+        return Vec::new();
+    }
+
+    let has_header = has_header(&rows);
+    let data_rows = if has_header { total - 1 } else { total };
+    rows.truncate(if has_header { MAX_ROWS + 1 } else { MAX_ROWS });
+
+    let width = rows
+        .iter()
+        .map(Vec::len)
+        .max()
+        .unwrap_or(0)
+        .min(MAX_COLUMNS);
+    let table_rows = rows
+        .into_iter()
+        .enumerate()
+        .map(|(index, mut record)| {
+            record.resize(width, String::new());
+            TableRow {
+                is_header: has_header && index == 0,
+                cells: record
+                    .into_iter()
+                    .map(|cell| TableCell {
+                        blocks: vec![ParsedBlock::Paragraph {
+                            inlines: vec![Inline::plain(cell.trim())],
+                            location: None,
+                        }],
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+
+    let mut blocks = vec![ParsedBlock::Table(TableBlock {
+        rows: table_rows,
+        location: None,
+    })];
+    if data_rows > MAX_ROWS {
+        blocks.push(ParsedBlock::Paragraph {
+            inlines: vec![Inline::plain(format!(
+                "Showing the first {MAX_ROWS} of {data_rows} rows."
+            ))],
+            location: None,
+        });
+    }
+    blocks
+}
+
+/// Pick the candidate delimiter that occurs most often, outside quotes, in
+/// the first line
+fn sniff_delimiter(text: &str) -> char {
+    let mut counts = [0_usize; 4];
+    let mut in_quotes = false;
+    for ch in text.chars() {
+        match ch {
+            '"' => in_quotes = !in_quotes,
+            '\n' if !in_quotes => break,
+            _ if !in_quotes => {
+                if let Some(index) = CANDIDATE_DELIMITERS.iter().position(|&d| d == ch) {
+                    counts[index] += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    counts
+        .iter()
+        .enumerate()
+        .filter(|(_, count)| **count > 0)
+        .max_by_key(|(index, count)| (**count, std::cmp::Reverse(*index)))
+        .map_or(',', |(index, _)| CANDIDATE_DELIMITERS[index])
+}
+
+/// The first row is a header when rows follow it and its cells are distinct,
+/// non-empty and not numbers
+fn has_header(rows: &[Vec<String>]) -> bool {
+    let Some((first, rest)) = rows.split_first() else {
+        return false;
+    };
+    if rest.is_empty() {
+        return false;
+    }
+    let labels: Vec<&str> = first.iter().map(|cell| cell.trim()).collect();
+    if labels
+        .iter()
+        .any(|label| label.is_empty() || is_number(label))
+    {
+        return false;
+    }
+    let mut unique = labels.clone();
+    unique.sort_unstable();
+    unique.dedup();
+    unique.len() == labels.len()
+}
+
+fn is_number(value: &str) -> bool {
+    let value = value.trim().trim_start_matches(['+', '-', '$']);
+    let value = value.trim_end_matches('%').replace([',', '_'], "");
+    !value.is_empty() && value.parse::<f64>().is_ok()
+}
+
+/// RFC 4180 record reader: quoted fields may contain delimiters, doubled
+/// quotes and line breaks
+struct Records<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    delimiter: char,
+}
+
+impl<'a> Records<'a> {
+    fn new(text: &'a str, delimiter: char) -> Self {
+        Self {
+            chars: text.chars().peekable(),
+            delimiter,
+        }
+    }
+}
+
+impl Iterator for Records<'_> {
+    type Item = Vec<String>;
+
+    fn next(&mut self) -> Option<Vec<String>> {
+        self.chars.peek()?;
+        let mut record = Vec::new();
+        let mut field = String::new();
+        let mut in_quotes = false;
+
+        while let Some(ch) = self.chars.next() {
+            if in_quotes {
+                if ch == '"' {
+                    if self.chars.peek() == Some(&'"') {
+                        self.chars.next();
+                        field.push('"');
+                    } else {
+                        in_quotes = false;
+                    }
+                } else {
+                    field.push(ch);
+                }
+            } else if ch == '"' && field.trim().is_empty() {
+                field.clear();
+                in_quotes = true;
+            } else if ch == self.delimiter {
+                record.push(std::mem::take(&mut field));
+            } else if ch == '\n' || ch == '\r' {
+                if ch == '\r' && self.chars.peek() == Some(&'\n') {
+                    self.chars.next();
+                }
+                break;
+            } else {
+                field.push(ch);
+            }
+        }
+        record.push(field);
+        Some(record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(text: &str, delimiter: char) -> Vec<Vec<String>> {
+        Records::new(text, delimiter).collect()
+    }
+
+    #[test]
+    fn test_records_handle_quotes_and_line_breaks() {
+        assert_eq!(
+            rows("a,\"b,c\",\"say \"\"hi\"\"\"\r\n1,\"two\nlines\",3", ','),
+            vec![vec!["a", "b,c", "say \"hi\""], vec!["1", "two\nlines", "3"],]
+        );
+    }
+
+    #[test]
+    fn test_sniff_delimiter() {
+        assert_eq!(sniff_delimiter("a;b;c\n1;2;3"), ';');
+        assert_eq!(sniff_delimiter("\"x;y\",b,c\n"), ',');
+        assert_eq!(sniff_delimiter("single column"), ',');
+    }
+
+    #[test]
+    fn test_has_header() {
+        let table = |rows: &[&[&str]]| -> Vec<Vec<String>> {
+            rows.iter()
+                .map(|row| row.iter().map(|cell| (*cell).to_owned()).collect())
+                .collect()
+        };
+        assert!(has_header(&table(&[&["name", "qty"], &["apple", "3"]])));
+        assert!(!has_header(&table(&[&["2024", "3"], &["2025", "4"]])));
+        assert!(!has_header(&table(&[&["name", ""], &["apple", "3"]])));
+        assert!(!has_header(&table(&[&["a", "a"], &["b", "c"]])));
+    }
+}
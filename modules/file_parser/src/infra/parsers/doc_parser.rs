@@ -0,0 +1,664 @@
+use async_trait::async_trait;
+use std::path::Path;
+
+use time::OffsetDateTime;
+
+use super::metadata::{DocumentInfo, non_empty};
+use super::rtf_parser::{codepage_encoding, parse_rtf};
+use crate::domain::error::DomainError;
+use crate::domain::ir::{DocumentBuilder, Inline, ParsedBlock, ParsedSource};
+use crate::domain::parser::FileParserBackend;
+
+/// Best-effort text extraction from legacy Word (`.doc`) files.
+///
+/// Reads the text through the piece table of the Word binary format; styles
+/// are not interpreted, so everything comes out as paragraphs and table
+/// cells as separate paragraphs. Files saved as RTF with a `.doc` name are
+/// handed to the RTF reader.
+pub struct DocParser;
+
+/// MIME type constants
+const MIME_TYPE_DOC: &str = "application/msword";
+const MIME_TYPE_RTF: &str = "application/rtf";
+
+const CFB_SIGNATURE: &[u8] = &[0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+const END_OF_CHAIN: u32 = 0xFFFF_FFFE;
+const FREE_SECTOR: u32 = 0xFFFF_FFFF;
+const NO_STREAM: u32 = 0xFFFF_FFFF;
+const MINI_SECTOR_SIZE: usize = 64;
+
+/// `wIdent` of a Word 97+ File Information Block
+const WORD_IDENT: u16 = 0xA5EC;
+/// First `nFib` of the Word 97 format
+const WORD97_NFIB: u16 = 0xC1;
+
+impl DocParser {
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for DocParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl FileParserBackend for DocParser {
+    fn id(&self) -> &'static str {
+        "doc"
+    }
+
+    fn supported_extensions(&self) -> &'static [&'static str] {
+        &["doc"]
+    }
+
+    async fn parse_local_path(
+        &self,
+        path: &Path,
+    ) -> Result<crate::domain::ir::ParsedDocument, DomainError> {
+        let bytes = tokio::fs::read(path)
+            .await
+            .map_err(|e| DomainError::io_error(format!("Failed to read file: {e}")))?;
+
+        let doc = tokio::task::spawn_blocking(move || parse_doc(&bytes))
+            .await
+            .map_err(|e| DomainError::parse_error(format!("Task join error: {e}")))??;
+
+        let builder = DocumentBuilder::new(ParsedSource::LocalPath(path.display().to_string()));
+        let filename = path.file_name().and_then(|s| s.to_str());
+        Ok(doc.into_document(builder, filename))
+    }
+
+    async fn parse_bytes(
+        &self,
+        filename_hint: Option<&str>,
+        _content_type: Option<&str>,
+        bytes: bytes::Bytes,
+    ) -> Result<crate::domain::ir::ParsedDocument, DomainError> {
+        let doc = tokio::task::spawn_blocking(move || parse_doc(&bytes))
+            .await
+            .map_err(|e| DomainError::parse_error(format!("Task join error: {e}")))??;
+
+        let source = ParsedSource::Uploaded {
+            original_name: filename_hint.unwrap_or("unknown.doc").to_owned(),
+        };
+        Ok(doc.into_document(DocumentBuilder::new(source), filename_hint))
+    }
+}
+
+/// Blocks and document properties extracted from a DOC file
+struct ParsedDoc {
+    content_type: &'static str,
+    blocks: Vec<ParsedBlock>,
+    info: DocumentInfo,
+}
+
+impl ParsedDoc {
+    fn into_document(
+        self,
+        builder: DocumentBuilder,
+        filename: Option<&str>,
+    ) -> crate::domain::ir::ParsedDocument {
+        let builder = builder.content_type(self.content_type).blocks(self.blocks);
+        self.info.apply(builder, filename).build()
+    }
+}
+
+fn parse_doc(bytes: &[u8]) -> Result<ParsedDoc, DomainError> {
+    if bytes.trim_ascii_start().starts_with(b"{\\rtf") {
+        let (blocks, info) = parse_rtf(bytes)?;
+        return Ok(ParsedDoc {
+            content_type: MIME_TYPE_RTF,
+            blocks,
+            info,
+        });
+    }
+
+    let cfb = CompoundFile::open(bytes)?;
+    let word = cfb
+        .stream("WordDocument")?
+        .ok_or_else(|| DomainError::parse_error("Not a Word document: no WordDocument stream"))?;
+    let text = document_text(&cfb, &word)?;
+    let info = match cfb.stream("\u{5}SummaryInformation") {
+        Ok(Some(summary)) => summary_information(&summary),
+        _ => DocumentInfo::default(),
+    };
+
+    Ok(ParsedDoc {
+        content_type: MIME_TYPE_DOC,
+        blocks: text_blocks(&text),
+        info,
+    })
+}
+
+fn doc_error(message: &str) -> DomainError {
+    DomainError::parse_error(format!("Malformed DOC file: {message}"))
+}
+
+fn u16_at(data: &[u8], offset: usize) -> Option<u16> {
+    let bytes = data.get(offset..offset.checked_add(2)?)?;
+    Some(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn u32_at(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset.checked_add(4)?)?;
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+fn u64_at(data: &[u8], offset: usize) -> Option<u64> {
+    let low = u32_at(data, offset)?;
+    let high = u32_at(data, offset.checked_add(4)?)?;
+    Some(u64::from(high) << 32 | u64::from(low))
+}
+
+fn to_usize(value: u32) -> usize {
+    usize::try_from(value).unwrap_or(usize::MAX)
+}
+
+// ---------------------------------------------------------------------------
+// Compound File Binary container
+// ---------------------------------------------------------------------------
+
+/// A directory entry of a compound file
+struct DirEntry {
+    name: String,
+    kind: u8,
+    left: u32,
+    right: u32,
+    child: u32,
+    start: u32,
+    size: u64,
+}
+
+/// Minimal reader for the OLE compound file container
+struct CompoundFile<'a> {
+    data: &'a [u8],
+    sector_size: usize,
+    fat: Vec<u32>,
+    mini_fat: Vec<u32>,
+    mini_stream: Vec<u8>,
+    mini_cutoff: u64,
+    entries: Vec<DirEntry>,
+}
+
+impl<'a> CompoundFile<'a> {
+    fn open(data: &'a [u8]) -> Result<Self, DomainError> {
+        if !data.starts_with(CFB_SIGNATURE) {
+            return Err(DomainError::parse_error(
+                "Not a legacy Word document: missing compound file signature",
+            ));
+        }
+        let header = |offset| u32_at(data, offset).ok_or_else(|| doc_error("truncated header"));
+        let sector_shift = u16_at(data, 0x1E).ok_or_else(|| doc_error("truncated header"))?;
+        if !(7..=16).contains(&sector_shift) {
+            return Err(doc_error("invalid sector size"));
+        }
+
+        let mut cfb = Self {
+            data,
+            sector_size: 1 << sector_shift,
+            fat: Vec::new(),
+            mini_fat: Vec::new(),
+            mini_stream: Vec::new(),
+            mini_cutoff: u64::from(header(0x38)?),
+            entries: Vec::new(),
+        };
+
+        let fat_sectors = cfb.fat_sectors(to_usize(header(0x2C)?), header(0x44)?);
+        for sector in fat_sectors {
+            let bytes = cfb.sector(sector)?;
+            cfb.fat
+                .extend(bytes.chunks_exact(4).filter_map(|chunk| u32_at(chunk, 0)));
+        }
+
+        let directory = cfb.chain(header(0x30)?)?;
+        cfb.entries = directory
+            .chunks_exact(128)
+            .map(|entry| cfb.dir_entry(entry))
+            .collect();
+
+        let mini_fat = cfb.chain(header(0x3C)?)?;
+        cfb.mini_fat = mini_fat
+            .chunks_exact(4)
+            .filter_map(|chunk| u32_at(chunk, 0))
+            .collect();
+        if let Some(root) = cfb.entries.first() {
+            let mut mini_stream = cfb.chain(root.start)?;
+            mini_stream.truncate(usize::try_from(root.size).unwrap_or(usize::MAX));
+            cfb.mini_stream = mini_stream;
+        }
+
+        Ok(cfb)
+    }
+
+    /// Sector numbers of the FAT, from the header and the DIFAT chain
+    fn fat_sectors(&self, count: usize, mut difat_sector: u32) -> Vec<u32> {
+        let mut sectors: Vec<u32> = (0..109)
+            .filter_map(|i| u32_at(self.data, 0x4C + i * 4))
+            .collect();
+        let per_sector = self.sector_size.div_euclid(4) - 1;
+        let mut guard = 0;
+        while difat_sector != END_OF_CHAIN && difat_sector != FREE_SECTOR && guard < count {
+            let Ok(bytes) = self.sector(difat_sector) else {
+                break;
+            };
+            sectors.extend((0..per_sector).filter_map(|i| u32_at(bytes, i * 4)));
+            difat_sector = u32_at(bytes, per_sector * 4).unwrap_or(END_OF_CHAIN);
+            guard += 1;
+        }
+        sectors.retain(|&sector| sector != FREE_SECTOR);
+        sectors.truncate(count);
+        sectors
+    }
+
+    fn sector(&self, sector: u32) -> Result<&'a [u8], DomainError> {
+        let start = to_usize(sector)
+            .checked_add(1)
+            .and_then(|n| n.checked_mul(self.sector_size))
+            .ok_or_else(|| doc_error("sector out of range"))?;
+        let end = start.saturating_add(self.sector_size).min(self.data.len());
+        self.data
+            .get(start..end)
+            .ok_or_else(|| doc_error("sector out of range"))
+    }
+
+    /// Concatenate a chain of regular sectors. Each sector is read at most once,
+    /// so a looping chain fails instead of repeating data.
+    fn chain(&self, mut sector: u32) -> Result<Vec<u8>, DomainError> {
+        let mut out = Vec::new();
+        let mut visited = vec![false; self.fat.len()];
+        while sector != END_OF_CHAIN && sector != FREE_SECTOR {
+            let seen = visited
+                .get_mut(to_usize(sector))
+                .ok_or_else(|| doc_error("sector outside the allocation table"))?;
+            if std::mem::replace(seen, true) {
+                return Err(doc_error("sector chain loops"));
+            }
+            out.extend_from_slice(self.sector(sector)?);
+            sector = *self
+                .fat
+                .get(to_usize(sector))
+                .ok_or_else(|| doc_error("sector outside the allocation table"))?;
+        }
+        Ok(out)
+    }
+
+    /// Concatenate a chain of mini sectors from the mini stream, reading each
+    /// mini sector at most once
+    fn mini_chain(&self, mut sector: u32) -> Result<Vec<u8>, DomainError> {
+        let mut out = Vec::new();
+        let mut visited = vec![false; self.mini_fat.len()];
+        while sector != END_OF_CHAIN && sector != FREE_SECTOR {
+            let seen = visited
+                .get_mut(to_usize(sector))
+                .ok_or_else(|| doc_error("mini sector outside the allocation table"))?;
+            if std::mem::replace(seen, true) {
+                return Err(doc_error("mini sector chain loops"));
+            }
+            let start = to_usize(sector).saturating_mul(MINI_SECTOR_SIZE);
+            let end = start
+                .saturating_add(MINI_SECTOR_SIZE)
+                .min(self.mini_stream.len());
+            out.extend_from_slice(
+                self.mini_stream
+                    .get(start..end)
+                    .ok_or_else(|| doc_error("mini sector out of range"))?,
+            );
+            sector = *self
+                .mini_fat
+                .get(to_usize(sector))
+                .ok_or_else(|| doc_error("mini sector outside the allocation table"))?;
+        }
+        Ok(out)
+    }
+
+    fn dir_entry(&self, entry: &[u8]) -> DirEntry {
+        let name_len = usize::from(u16_at(entry, 0x40).unwrap_or(0)).min(64);
+        let units: Vec<u16> = (0..name_len.div_euclid(2))
+            .filter_map(|i| u16_at(entry, i * 2))
+            .take_while(|&unit| unit != 0)
+            .collect();
+        let size = u64_at(entry, 0x78).unwrap_or(0);
+        DirEntry {
+            name: String::from_utf16_lossy(&units),
+            kind: entry.get(0x42).copied().unwrap_or(0),
+            left: u32_at(entry, 0x44).unwrap_or(NO_STREAM),
+            right: u32_at(entry, 0x48).unwrap_or(NO_STREAM),
+            child: u32_at(entry, 0x4C).unwrap_or(NO_STREAM),
+            start: u32_at(entry, 0x74).unwrap_or(END_OF_CHAIN),
+            // Version 3 files only use the low 32 bits
+            size: if self.sector_size == 512 {
+                size & 0xFFFF_FFFF
+            } else {
+                size
+            },
+        }
+    }
+
+    /// Read a stream that sits directly in the root storage
+    fn stream(&self, name: &str) -> Result<Option<Vec<u8>>, DomainError> {
+        let Some(entry) = self.root_child(name) else {
+            return Ok(None);
+        };
+        let mut bytes = if entry.size < self.mini_cutoff {
+            self.mini_chain(entry.start)?
+        } else {
+            self.chain(entry.start)?
+        };
+        bytes.truncate(usize::try_from(entry.size).unwrap_or(usize::MAX));
+        Ok(Some(bytes))
+    }
+
+    /// Find a stream among the children of the root storage, which are kept
+    /// in a tree linked through the left/right sibling fields
+    fn root_child(&self, name: &str) -> Option<&DirEntry> {
+        let mut pending = vec![self.entries.first()?.child];
+        let mut visited = 0;
+        while let Some(index) = pending.pop() {
+            if index == NO_STREAM || visited > self.entries.len() {
+                continue;
+            }
+            visited += 1;
+            let entry = self.entries.get(to_usize(index))?;
+            // Stream entries only
+            if entry.kind == 2 && entry.name.eq_ignore_ascii_case(name) {
+                return Some(entry);
+            }
+            pending.push(entry.left);
+            pending.push(entry.right);
+        }
+        None
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Word binary format
+// ---------------------------------------------------------------------------
+
+/// The main document text (footnotes, headers and the like excluded)
+fn document_text(cfb: &CompoundFile, word: &[u8]) -> Result<String, DomainError> {
+    if u16_at(word, 0) != Some(WORD_IDENT) {
+        return Err(DomainError::parse_error(
+            "Not a Word document: unknown file information block",
+        ));
+    }
+    let flags = u16_at(word, 0x0A).ok_or_else(|| doc_error("truncated FIB"))?;
+    if flags & 0x0100 != 0 {
+        return Err(DomainError::parse_error(
+            "Encrypted DOC files are not supported",
+        ));
+    }
+
+    let n_fib = u16_at(word, 0x02).unwrap_or(0);
+    if n_fib < WORD97_NFIB {
+        // Word 6/95 files keep the text as one 8-bit run
+        let fc_min = to_usize(u32_at(word, 0x18).unwrap_or(0));
+        let fc_mac = to_usize(u32_at(word, 0x1C).unwrap_or(0));
+        let text = word
+            .get(fc_min..fc_mac)
+            .ok_or_else(|| doc_error("text outside the WordDocument stream"))?;
+        return Ok(encoding_rs::WINDOWS_1252.decode(text).0.into_owned());
+    }
+
+    // FibBase (32 bytes), then the length-prefixed FibRgW, FibRgLw and
+    // FibRgFcLcb arrays
+    let words_at = 34;
+    let word_count = usize::from(u16_at(word, 32).ok_or_else(|| doc_error("truncated FIB"))?);
+    let long_count_at = words_at + word_count * 2;
+    let long_count =
+        usize::from(u16_at(word, long_count_at).ok_or_else(|| doc_error("truncated FIB"))?);
+    let longs_at = long_count_at + 2;
+    let ccp_text = to_usize(u32_at(word, longs_at + 12).ok_or_else(|| doc_error("truncated FIB"))?);
+    let fc_lcb_at = longs_at + long_count * 4 + 2;
+    let fc_clx =
+        to_usize(u32_at(word, fc_lcb_at + 33 * 8).ok_or_else(|| doc_error("truncated FIB"))?);
+    let lcb_clx =
+        to_usize(u32_at(word, fc_lcb_at + 33 * 8 + 4).ok_or_else(|| doc_error("truncated FIB"))?);
+
+    let table_name = if flags & 0x0200 == 0 {
+        "0Table"
+    } else {
+        "1Table"
+    };
+    let table = cfb
+        .stream(table_name)?
+        .ok_or_else(|| doc_error("missing table stream"))?;
+    let clx = table
+        .get(fc_clx..fc_clx.saturating_add(lcb_clx))
+        .ok_or_else(|| doc_error("piece table outside the table stream"))?;
+
+    piece_table_text(clx, word, ccp_text)
+}
+
+/// Assemble the first `ccp_text` characters from the pieces listed in the
+/// `Clx` structure
+fn piece_table_text(clx: &[u8], word: &[u8], ccp_text: usize) -> Result<String, DomainError> {
+    // Skip the property modifiers (Prc) that precede the piece table (Pcdt)
+    let mut pos = 0;
+    while clx.get(pos) == Some(&0x01) {
+        let cb = usize::from(u16_at(clx, pos + 1).ok_or_else(|| doc_error("truncated Clx"))?);
+        pos += 3 + cb;
+    }
+    if clx.get(pos) != Some(&0x02) {
+        return Err(doc_error("missing piece table"));
+    }
+    let lcb = to_usize(u32_at(clx, pos + 1).ok_or_else(|| doc_error("truncated Clx"))?);
+    let plc = clx
+        .get(pos + 5..pos + 5 + lcb)
+        .ok_or_else(|| doc_error("truncated piece table"))?;
+
+    // n + 1 character positions followed by n 8-byte piece descriptors
+    let pieces = lcb.saturating_sub(4).div_euclid(12);
+    let mut text = String::new();
+    for i in 0..pieces {
+        let (Some(cp_start), Some(cp_end), Some(fc)) = (
+            u32_at(plc, i * 4),
+            u32_at(plc, (i + 1) * 4),
+            u32_at(plc, (pieces + 1) * 4 + i * 8 + 2),
+        ) else {
+            break;
+        };
+        let cp_start = to_usize(cp_start);
+        let cp_end = to_usize(cp_end).min(ccp_text);
+        if cp_start >= cp_end {
+            continue;
+        }
+        let count = cp_end - cp_start;
+
+        if fc & 0x4000_0000 == 0 {
+            let offset = to_usize(fc);
+            let bytes = word
+                .get(offset..offset.saturating_add(count * 2))
+                .ok_or_else(|| doc_error("piece outside the WordDocument stream"))?;
+            let units: Vec<u16> = bytes
+                .chunks_exact(2)
+                .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+                .collect();
+            text.push_str(&String::from_utf16_lossy(&units));
+        } else {
+            // Compressed pieces store one byte per character
+            let offset = to_usize(fc & 0x3FFF_FFFF).div_euclid(2);
+            let bytes = word
+                .get(offset..offset.saturating_add(count))
+                .ok_or_else(|| doc_error("piece outside the WordDocument stream"))?;
+            text.push_str(&encoding_rs::WINDOWS_1252.decode(bytes).0);
+        }
+    }
+    Ok(text)
+}
+
+/// Split document text into paragraphs, dropping field instructions and
+/// other special characters
+fn text_blocks(text: &str) -> Vec<ParsedBlock> {
+    let mut blocks = Vec::new();
+    let mut paragraph = String::new();
+    // One entry per open field: whether its result part has started
+    let mut fields: Vec<bool> = Vec::new();
+
+    for ch in text.chars() {
+        match ch {
+            '\u{13}' => fields.push(false),
+            '\u{14}' => {
+                if let Some(in_result) = fields.last_mut() {
+                    *in_result = true;
+                }
+            }
+            '\u{15}' => {
+                fields.pop();
+            }
+            _ if fields.iter().any(|in_result| !in_result) => {}
+            // Paragraph, cell and row marks
+            '\r' | '\u{7}' => end_paragraph(&mut paragraph, &mut blocks),
+            // Page and section breaks
+            '\u{c}' => {
+                end_paragraph(&mut paragraph, &mut blocks);
+                if !blocks.is_empty() && blocks.last() != Some(&ParsedBlock::PageBreak) {
+                    blocks.push(ParsedBlock::PageBreak);
+                }
+            }
+            '\u{b}' => paragraph.push('\n'),
+            '\u{1e}' => paragraph.push('-'),
+            '\t' => paragraph.push('\t'),
+            // Pictures, footnote marks, optional hyphens and other controls
+            _ if ch < ' ' => {}
+            _ => paragraph.push(ch),
+        }
+    }
+    end_paragraph(&mut paragraph, &mut blocks);
+    if blocks.last() == Some(&ParsedBlock::PageBreak) {
+        blocks.pop();
+    }
+    blocks
+}
+
+fn end_paragraph(paragraph: &mut String, blocks: &mut Vec<ParsedBlock>) {
+    let text = paragraph.trim();
+    if !text.is_empty() {
+        blocks.push(ParsedBlock::Paragraph {
+            inlines: vec![Inline::plain(text)],
+            location: None,
+        });
+    }
+    paragraph.clear();
+}
+
+// ---------------------------------------------------------------------------
+// Summary information property set
+// ---------------------------------------------------------------------------
+
+const PID_CODEPAGE: u32 = 1;
+const PID_TITLE: u32 = 2;
+const PID_AUTHOR: u32 = 4;
+const PID_CREATE_DTM: u32 = 12;
+const PID_LASTSAVE_DTM: u32 = 13;
+
+const VT_I2: u32 = 0x02;
+const VT_LPSTR: u32 = 0x1E;
+const VT_FILETIME: u32 = 0x40;
+
+/// Seconds between 1601-01-01 (the FILETIME epoch) and the Unix epoch
+const FILETIME_UNIX_OFFSET: i64 = 11_644_473_600;
+
+fn summary_information(stream: &[u8]) -> DocumentInfo {
+    let Some(section) = u32_at(stream, 44).map(to_usize) else {
+        return DocumentInfo::default();
+    };
+    let count = u32_at(stream, section + 4).map_or(0, to_usize).min(1024);
+    let property = |pid: u32| {
+        (0..count).find_map(|i| {
+            let entry = section + 8 + i * 8;
+            (u32_at(stream, entry)? == pid)
+                .then(|| u32_at(stream, entry + 4))
+                .flatten()
+                .map(|offset| section + to_usize(offset))
+        })
+    };
+
+    let codepage = property(PID_CODEPAGE)
+        .filter(|&at| u32_at(stream, at) == Some(VT_I2))
+        .and_then(|at| u16_at(stream, at + 4));
+    let string = |pid| {
+        let at = property(pid)?;
+        if u32_at(stream, at)? != VT_LPSTR {
+            return None;
+        }
+        let len = to_usize(u32_at(stream, at + 4)?);
+        let bytes = stream.get(at + 8..(at + 8).checked_add(len)?)?;
+        let text = if codepage == Some(1200) {
+            let units: Vec<u16> = bytes
+                .chunks_exact(2)
+                .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+                .collect();
+            String::from_utf16_lossy(&units)
+        } else {
+            // Code pages are stored as signed 16-bit values
+            let encoding = codepage
+                .and_then(|cp| codepage_encoding(i32::from(cp)))
+                .unwrap_or(encoding_rs::WINDOWS_1252);
+            encoding.decode(bytes).0.into_owned()
+        };
+        non_empty(text.trim_end_matches('\0').to_owned())
+    };
+    let filetime = |pid| {
+        let at = property(pid)?;
+        if u32_at(stream, at)? != VT_FILETIME {
+            return None;
+        }
+        filetime_to_datetime(u64_at(stream, at + 4)?)
+    };
+
+    DocumentInfo {
+        title: string(PID_TITLE),
+        author: string(PID_AUTHOR),
+        language: None,
+        created_at: filetime(PID_CREATE_DTM),
+        modified_at: filetime(PID_LASTSAVE_DTM),
+    }
+}
+
+/// Convert a FILETIME (100 ns ticks since 1601) to a date-time; zero means
+/// the property was never set
+fn filetime_to_datetime(ticks: u64) -> Option<OffsetDateTime> {
+    if ticks == 0 {
+        return None;
+    }
+    let seconds = i64::try_from(ticks.div_euclid(10_000_000)).ok()? - FILETIME_UNIX_OFFSET;
+    OffsetDateTime::from_unix_timestamp(seconds).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_blocks_drops_field_codes() {
+        let text = "Title\r\u{13} HYPERLINK \"https://example.com\" \u{14}Example\u{15} site\r\u{c}Next page\u{7}Cell\u{7}\u{7}";
+        let blocks = text_blocks(text);
+        let paragraphs: Vec<String> = blocks
+            .iter()
+            .map(|block| match block {
+                ParsedBlock::Paragraph { inlines, .. } => match &inlines[0] {
+                    Inline::Text { text, .. } => text.clone(),
+                    other => panic!("unexpected inline {other:?}"),
+                },
+                ParsedBlock::PageBreak => "---".to_owned(),
+                other => panic!("unexpected block {other:?}"),
+            })
+            .collect();
+        assert_eq!(
+            paragraphs,
+            vec!["Title", "Example site", "---", "Next page", "Cell"]
+        );
+    }
+
+    #[test]
+    fn test_filetime_to_datetime() {
+        // 2024-01-01T00:00:00Z
+        let date = filetime_to_datetime(133_485_408_000_000_000).unwrap();
+        assert_eq!(date.unix_timestamp(), 1_704_067_200);
+        assert!(filetime_to_datetime(0).is_none());
+    }
+}
@@ -143,9 +143,16 @@ fn extract_paragraph_block(paragraph: &docx_rust::document::Paragraph) -> Option
     let heading_level = detect_heading_level(paragraph);
 
     if let Some(level) = heading_level {
-        Some(ParsedBlock::Heading { level, inlines })
+        Some(ParsedBlock::Heading {
+            level,
+            inlines,
+            location: None,
+        })
     } else {
-        Some(ParsedBlock::Paragraph { inlines })
+        Some(ParsedBlock::Paragraph {
+            inlines,
+            location: None,
+        })
     }
 }
 
@@ -316,6 +323,7 @@ fn extract_table_block(table: &docx_rust::document::Table) -> ParsedBlock {
                 if cell_blocks.is_empty() {
                     cell_blocks.push(ParsedBlock::Paragraph {
                         inlines: vec![Inline::plain("")],
+                        location: None,
                     });
                 }
 
@@ -334,5 +342,8 @@ fn extract_table_block(table: &docx_rust::document::Table) -> ParsedBlock {
         total_cells
     );
 
-    ParsedBlock::Table(TableBlock { rows })
+    ParsedBlock::Table(TableBlock {
+        rows,
+        location: None,
+    })
 }
@@ -0,0 +1,553 @@
+use async_trait::async_trait;
+use std::io::Cursor;
+use std::path::Path;
+use std::sync::{Arc, OnceLock, Weak};
+
+use futures_util::FutureExt;
+use futures_util::future::BoxFuture;
+use mail_parser::mailbox::mbox::MessageIterator;
+use mail_parser::{Address, Message, MessageParser, MessagePart, MimeHeaders, PartType};
+use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+use super::html_parser::parse_html_bytes;
+use super::plain_text::text_to_blocks;
+use crate::config::EmailConfig;
+use crate::domain::error::DomainError;
+use crate::domain::ir::{
+    DocumentBuilder, EmailHeaders, Inline, InlineStyle, ParsedBlock, ParsedSource,
+};
+use crate::domain::parser::FileParserBackend;
+use crate::domain::service::FileParserService;
+
+/// Parser for e-mail messages (EML) and mailboxes (MBOX).
+///
+/// Message bodies go through the HTML and plain text parsers; attachments are
+/// parsed through the service and rendered as sections below the body.
+pub struct EmailParser {
+    config: EmailConfig,
+    service: OnceLock<Weak<FileParserService>>,
+}
+
+/// Supported file extensions
+const SUPPORTED_EXTENSIONS: &[&str] = &["eml", "mbox"];
+
+/// MIME type constants
+const MIME_TYPE_EML: &str = "message/rfc822";
+const MIME_TYPE_MBOX: &str = "application/mbox";
+
+/// Messages beyond this many in a mailbox are ignored
+const MAX_MESSAGES: usize = 10_000;
+
+const BYTES_IN_MB: u64 = 1024 * 1024;
+
+impl EmailParser {
+    #[must_use]
+    pub fn new(config: EmailConfig) -> Self {
+        Self {
+            config,
+            service: OnceLock::new(),
+        }
+    }
+
+    /// Parse attachments through `service`. Until bound, attachments are
+    /// listed without their content.
+    pub fn bind_service(&self, service: &Arc<FileParserService>) {
+        // Binding twice keeps the first service
+        let _ = self.service.set(Arc::downgrade(service));
+    }
+
+    async fn parse(
+        &self,
+        builder: DocumentBuilder,
+        filename: Option<&str>,
+        content_type: Option<&str>,
+        bytes: bytes::Bytes,
+    ) -> Result<crate::domain::ir::ParsedDocument, DomainError> {
+        let is_mbox = has_extension(filename, "mbox") || content_type == Some(MIME_TYPE_MBOX);
+        let mut budget = Budget {
+            max_depth: self.config.max_attachment_depth,
+            remaining_bytes: usize::try_from(
+                self.config.max_attachments_mb.saturating_mul(BYTES_IN_MB),
+            )
+            .unwrap_or(usize::MAX),
+        };
+        let messages = tokio::task::spawn_blocking(move || {
+            if is_mbox {
+                read_mailbox(&bytes, &mut budget)
+            } else {
+                read_message(&bytes, &mut budget).map(|message| vec![message])
+            }
+        })
+        .await
+        .map_err(|e| DomainError::parse_error(format!("Task join error: {e}")))??;
+
+        let mut blocks = Vec::new();
+        let mut builder = builder.content_type(if is_mbox {
+            MIME_TYPE_MBOX
+        } else {
+            MIME_TYPE_EML
+        });
+        if let Some(filename) = filename {
+            builder = builder.original_filename(filename);
+        }
+
+        if is_mbox {
+            for message in messages {
+                blocks.push(heading(1, message.display_subject()));
+                self.render_message(message, 1, false, &mut blocks).await;
+            }
+            if let Some(filename) = filename {
+                builder = builder.title(filename);
+            }
+        } else if let Some(message) = messages.into_iter().next() {
+            let headers = message.headers.clone();
+            if let Some(title) = headers.subject.as_deref().or(filename) {
+                builder = builder.title(title);
+            }
+            if let Some(from) = headers.from.first() {
+                builder = builder.author(from);
+            }
+            if let Some(date) = headers.date {
+                builder = builder.created_at(date);
+            }
+            builder = builder.email(headers);
+            self.render_message(message, 0, false, &mut blocks).await;
+        }
+
+        Ok(builder.blocks(blocks).build())
+    }
+
+    /// Render a message with its headings moved down by `shift` levels;
+    /// attachments become sections one level below that
+    fn render_message<'a>(
+        &'a self,
+        message: MailMessage,
+        shift: u8,
+        with_subject: bool,
+        out: &'a mut Vec<ParsedBlock>,
+    ) -> BoxFuture<'a, ()> {
+        async move {
+            header_blocks(&message.headers, with_subject, out);
+            out.extend(shift_headings(message.body, shift));
+
+            let level = shift.saturating_add(1);
+            for attachment in message.attachments {
+                out.push(heading(level, format!("Attachment: {}", attachment.name)));
+                match attachment.content {
+                    AttachmentContent::Message(nested) => {
+                        self.render_message(*nested, level, true, out).await;
+                    }
+                    AttachmentContent::File(bytes) => {
+                        let Some(service) = self.service.get().and_then(Weak::upgrade) else {
+                            out.push(note(
+                                "Not parsed: attachment parsing is not available".to_owned(),
+                            ));
+                            continue;
+                        };
+                        match service
+                            .parse_bytes(
+                                Some(&attachment.name),
+                                Some(&attachment.content_type),
+                                bytes,
+                            )
+                            .await
+                        {
+                            Ok(document) => out.extend(shift_headings(document.blocks, level)),
+                            Err(e) => out.push(note(format!("Not parsed: {e}"))),
+                        }
+                    }
+                    AttachmentContent::Skipped(reason) => {
+                        out.push(note(format!("Not parsed: {reason}")));
+                    }
+                }
+            }
+        }
+        .boxed()
+    }
+}
+
+#[async_trait]
+impl FileParserBackend for EmailParser {
+    fn id(&self) -> &'static str {
+        "email"
+    }
+
+    fn supported_extensions(&self) -> &'static [&'static str] {
+        SUPPORTED_EXTENSIONS
+    }
+
+    async fn parse_local_path(
+        &self,
+        path: &Path,
+    ) -> Result<crate::domain::ir::ParsedDocument, DomainError> {
+        let bytes = tokio::fs::read(path)
+            .await
+            .map_err(|e| DomainError::io_error(format!("Failed to read file: {e}")))?;
+
+        let builder = DocumentBuilder::new(ParsedSource::LocalPath(path.display().to_string()));
+        let filename = path.file_name().and_then(|s| s.to_str());
+        self.parse(builder, filename, None, bytes::Bytes::from(bytes))
+            .await
+    }
+
+    async fn parse_bytes(
+        &self,
+        filename_hint: Option<&str>,
+        content_type: Option<&str>,
+        bytes: bytes::Bytes,
+    ) -> Result<crate::domain::ir::ParsedDocument, DomainError> {
+        let source = ParsedSource::Uploaded {
+            original_name: filename_hint.unwrap_or("unknown.eml").to_owned(),
+        };
+        self.parse(
+            DocumentBuilder::new(source),
+            filename_hint,
+            content_type,
+            bytes,
+        )
+        .await
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Reading messages
+// ---------------------------------------------------------------------------
+
+/// Attachment limits shared by all messages of one file
+struct Budget {
+    max_depth: usize,
+    remaining_bytes: usize,
+}
+
+/// A message with its body converted and its attachments extracted
+struct MailMessage {
+    headers: EmailHeaders,
+    body: Vec<ParsedBlock>,
+    attachments: Vec<Attachment>,
+}
+
+impl MailMessage {
+    fn display_subject(&self) -> String {
+        self.headers
+            .subject
+            .clone()
+            .unwrap_or_else(|| "(no subject)".to_owned())
+    }
+}
+
+struct Attachment {
+    name: String,
+    content_type: String,
+    content: AttachmentContent,
+}
+
+enum AttachmentContent {
+    /// An attached message, read like the outer one
+    Message(Box<MailMessage>),
+    /// A file to parse through the service
+    File(bytes::Bytes),
+    /// Not parsed, for the given reason
+    Skipped(String),
+}
+
+fn read_mailbox(bytes: &[u8], budget: &mut Budget) -> Result<Vec<MailMessage>, DomainError> {
+    let mut messages = Vec::new();
+    for entry in MessageIterator::new(Cursor::new(bytes)).take(MAX_MESSAGES) {
+        let entry =
+            entry.map_err(|e| DomainError::parse_error(format!("Failed to read mailbox: {e}")))?;
+        if let Some(message) = MessageParser::default().parse(entry.contents()) {
+            messages.push(mail_message(&message, 0, budget));
+        }
+    }
+    if messages.is_empty() {
+        return Err(DomainError::parse_error("Mailbox contains no messages"));
+    }
+    Ok(messages)
+}
+
+fn read_message(bytes: &[u8], budget: &mut Budget) -> Result<MailMessage, DomainError> {
+    let message = MessageParser::default()
+        .parse(bytes)
+        .ok_or_else(|| DomainError::parse_error("Not an e-mail message: no headers found"))?;
+    Ok(mail_message(&message, 0, budget))
+}
+
+/// Convert a message at the given attachment depth
+fn mail_message(message: &Message, depth: usize, budget: &mut Budget) -> MailMessage {
+    let body = message
+        .html_body
+        .iter()
+        .filter_map(|&id| message.part(id))
+        .flat_map(body_blocks)
+        .collect();
+
+    let attachments = message
+        .attachments()
+        .enumerate()
+        .map(|(index, part)| attachment(part, index, depth, budget))
+        .collect();
+
+    MailMessage {
+        headers: email_headers(message),
+        body,
+        attachments,
+    }
+}
+
+fn body_blocks(part: &MessagePart) -> Vec<ParsedBlock> {
+    match &part.body {
+        PartType::Html(html) => parse_html_bytes(html.as_bytes(), None)
+            .map_or_else(|_| text_to_blocks(html), |(blocks, _)| blocks),
+        PartType::Text(text) => text_to_blocks(&text.replace("\r\n", "\n")),
+        _ => Vec::new(),
+    }
+}
+
+fn attachment(part: &MessagePart, index: usize, depth: usize, budget: &mut Budget) -> Attachment {
+    let nested = part.message();
+    let name = part
+        .attachment_name()
+        .map(ToOwned::to_owned)
+        .or_else(|| nested.and_then(|m| m.subject()).map(|s| format!("{s}.eml")))
+        .unwrap_or_else(|| format!("attachment-{}", index + 1));
+    let content_type = part.content_type().map_or_else(
+        || "application/octet-stream".to_owned(),
+        |ct| match &ct.c_subtype {
+            Some(subtype) => format!("{}/{subtype}", ct.c_type),
+            None => ct.c_type.to_string(),
+        },
+    );
+
+    let content = if depth >= budget.max_depth {
+        AttachmentContent::Skipped("attachments are nested too deeply".to_owned())
+    } else if part.len() > budget.remaining_bytes {
+        AttachmentContent::Skipped("the attachment size limit is reached".to_owned())
+    } else {
+        budget.remaining_bytes -= part.len();
+        if let Some(nested) = nested {
+            AttachmentContent::Message(Box::new(mail_message(nested, depth + 1, budget)))
+        } else if is_mailbox(&name, &content_type) {
+            AttachmentContent::Skipped("attached mailboxes are not expanded".to_owned())
+        } else if is_message(&name, &content_type) {
+            match MessageParser::default().parse(part.contents()) {
+                Some(nested) => {
+                    AttachmentContent::Message(Box::new(mail_message(&nested, depth + 1, budget)))
+                }
+                None => AttachmentContent::Skipped("not an e-mail message".to_owned()),
+            }
+        } else {
+            AttachmentContent::File(bytes::Bytes::copy_from_slice(part.contents()))
+        }
+    };
+
+    Attachment {
+        name,
+        content_type,
+        content,
+    }
+}
+
+fn is_message(name: &str, content_type: &str) -> bool {
+    has_extension(Some(name), "eml") || content_type.eq_ignore_ascii_case(MIME_TYPE_EML)
+}
+
+fn is_mailbox(name: &str, content_type: &str) -> bool {
+    has_extension(Some(name), "mbox") || content_type.eq_ignore_ascii_case(MIME_TYPE_MBOX)
+}
+
+fn has_extension(filename: Option<&str>, extension: &str) -> bool {
+    filename
+        .and_then(|name| Path::new(name).extension())
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(extension))
+}
+
+fn email_headers(message: &Message) -> EmailHeaders {
+    EmailHeaders {
+        from: addresses(message.from()),
+        to: addresses(message.to()),
+        cc: addresses(message.cc()),
+        subject: message.subject().map(ToOwned::to_owned),
+        date: message.date().and_then(to_offset_date_time),
+        message_id: message.message_id().map(ToOwned::to_owned),
+    }
+}
+
+fn addresses(address: Option<&Address>) -> Vec<String> {
+    address
+        .into_iter()
+        .flat_map(Address::iter)
+        .filter_map(|addr| match (addr.name(), addr.address()) {
+            (Some(name), Some(address)) => Some(format!("{name} <{address}>")),
+            (Some(value), None) | (None, Some(value)) => Some(value.to_owned()),
+            (None, None) => None,
+        })
+        .collect()
+}
+
+fn to_offset_date_time(date: &mail_parser::DateTime) -> Option<OffsetDateTime> {
+    let month = Month::try_from(date.month).ok()?;
+    let date_part = Date::from_calendar_date(i32::from(date.year), month, date.day).ok()?;
+    let time_part = Time::from_hms(date.hour, date.minute, date.second).ok()?;
+    let sign = if date.tz_before_gmt { -1 } else { 1 };
+    let offset = UtcOffset::from_hms(
+        sign * i8::try_from(date.tz_hour).ok()?,
+        sign * i8::try_from(date.tz_minute).ok()?,
+        0,
+    )
+    .ok()?;
+    Some(PrimitiveDateTime::new(date_part, time_part).assume_offset(offset))
+}
+
+// ---------------------------------------------------------------------------
+// Rendering
+// ---------------------------------------------------------------------------
+
+fn heading(level: u8, text: impl Into<String>) -> ParsedBlock {
+    ParsedBlock::Heading {
+        level: level.clamp(1, 6),
+        inlines: vec![Inline::plain(text)],
+        location: None,
+    }
+}
+
+fn note(text: String) -> ParsedBlock {
+    ParsedBlock::Paragraph {
+        inlines: vec![Inline::styled(
+            text,
+            InlineStyle {
+                italic: true,
+                ..InlineStyle::default()
+            },
+        )],
+        location: None,
+    }
+}
+
+/// One paragraph per header, as `**From:** value`
+fn header_blocks(headers: &EmailHeaders, with_subject: bool, out: &mut Vec<ParsedBlock>) {
+    let date = headers.date.map(|date| {
+        date.format(&time::format_description::well_known::Rfc2822)
+            .unwrap_or_else(|_| date.to_string())
+    });
+    let subject = headers.subject.clone().filter(|_| with_subject);
+    let lines = [
+        ("From", headers.from.join(", ")),
+        ("To", headers.to.join(", ")),
+        ("Cc", headers.cc.join(", ")),
+        ("Subject", subject.unwrap_or_default()),
+        ("Date", date.unwrap_or_default()),
+    ];
+    for (label, value) in lines {
+        if value.is_empty() {
+            continue;
+        }
+        out.push(ParsedBlock::Paragraph {
+            inlines: vec![
+                Inline::styled(
+                    format!("{label}:"),
+                    InlineStyle {
+                        bold: true,
+                        ..InlineStyle::default()
+                    },
+                ),
+                Inline::plain(format!(" {value}")),
+            ],
+            location: None,
+        });
+    }
+}
+
+/// Move headings down by `shift` levels so they nest under a section
+fn shift_headings(blocks: Vec<ParsedBlock>, shift: u8) -> Vec<ParsedBlock> {
+    blocks
+        .into_iter()
+        .map(|block| match block {
+            ParsedBlock::Heading {
+                level,
+                inlines,
+                location,
+            } => ParsedBlock::Heading {
+                level: level.saturating_add(shift).min(6),
+                inlines,
+                location,
+            },
+            other => other,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_offset_date_time() {
+        let date = mail_parser::DateTime {
+            year: 2024,
+            month: 3,
+            day: 15,
+            hour: 9,
+            minute: 30,
+            second: 0,
+            tz_before_gmt: true,
+            tz_hour: 5,
+            tz_minute: 0,
+        };
+        let date = to_offset_date_time(&date).unwrap();
+        assert_eq!(date.offset().whole_hours(), -5);
+        assert_eq!(date.unix_timestamp(), 1_710_513_000);
+    }
+
+    #[test]
+    fn test_attachment_depth_and_size_limits() {
+        let raw = b"From: a@example.com\r\n\
+Subject: Outer\r\n\
+Content-Type: multipart/mixed; boundary=\"b\"\r\n\
+\r\n\
+--b\r\n\
+Content-Type: text/plain\r\n\
+\r\n\
+Body\r\n\
+--b\r\n\
+Content-Type: text/plain\r\n\
+Content-Disposition: attachment; filename=\"one.txt\"\r\n\
+\r\n\
+0123456789\r\n\
+--b\r\n\
+Content-Type: text/plain\r\n\
+Content-Disposition: attachment; filename=\"two.txt\"\r\n\
+\r\n\
+0123456789\r\n\
+--b--\r\n";
+
+        let mut budget = Budget {
+            max_depth: 1,
+            remaining_bytes: 15,
+        };
+        let message = read_message(raw, &mut budget).unwrap();
+        let kinds: Vec<(&str, bool)> = message
+            .attachments
+            .iter()
+            .map(|a| {
+                (
+                    a.name.as_str(),
+                    matches!(a.content, AttachmentContent::File(_)),
+                )
+            })
+            .collect();
+        assert_eq!(kinds, vec![("one.txt", true), ("two.txt", false)]);
+
+        let mut budget = Budget {
+            max_depth: 0,
+            remaining_bytes: usize::MAX,
+        };
+        let message = read_message(raw, &mut budget).unwrap();
+        assert!(
+            message
+                .attachments
+                .iter()
+                .all(|a| matches!(a.content, AttachmentContent::Skipped(_)))
+        );
+    }
+}
@@ -0,0 +1,823 @@
+use async_trait::async_trait;
+use std::collections::{HashMap, HashSet};
+use std::io::{Cursor, Read, Seek};
+use std::path::Path;
+
+use roxmltree::{Document, Node, ParsingOptions};
+use time::format_description::well_known::Iso8601;
+use time::{Date, OffsetDateTime, PrimitiveDateTime};
+
+use super::metadata::{DocumentInfo, non_empty};
+use crate::domain::error::DomainError;
+use crate::domain::ir::{
+    DocumentBuilder, Inline, InlineStyle, ParsedBlock, ParsedSource, TableBlock, TableCell,
+    TableRow,
+};
+use crate::domain::parser::FileParserBackend;
+
+/// EPUB parser: chapters are read in spine order, each starting with a
+/// level-1 heading taken from the table of contents
+pub struct EpubParser;
+
+/// Supported file extensions
+const SUPPORTED_EXTENSIONS: &[&str] = &["epub"];
+
+/// MIME type constants
+const MIME_TYPE_EPUB: &str = "application/epub+zip";
+
+/// Upper bound on the decompressed size of a single package entry
+const MAX_ENTRY_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Upper bound on the decompressed size of all entries read from one package
+const MAX_TOTAL_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Spine items beyond this many are ignored
+const MAX_CHAPTERS: usize = 2000;
+
+const CONTAINER_PATH: &str = "META-INF/container.xml";
+
+const NS_OPF: &str = "http://www.idpf.org/2007/opf";
+const NS_DC: &str = "http://purl.org/dc/elements/1.1/";
+const NS_OPS: &str = "http://www.idpf.org/2007/ops";
+
+/// HTML entities commonly found in EPUB 2 content, which XML parsers do not
+/// know without the XHTML DTD
+const HTML_ENTITIES: &[(&str, &str)] = &[
+    ("&nbsp;", "&#160;"),
+    ("&ndash;", "&#8211;"),
+    ("&mdash;", "&#8212;"),
+    ("&lsquo;", "&#8216;"),
+    ("&rsquo;", "&#8217;"),
+    ("&ldquo;", "&#8220;"),
+    ("&rdquo;", "&#8221;"),
+    ("&hellip;", "&#8230;"),
+    ("&copy;", "&#169;"),
+];
+
+impl EpubParser {
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for EpubParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl FileParserBackend for EpubParser {
+    fn id(&self) -> &'static str {
+        "epub"
+    }
+
+    fn supported_extensions(&self) -> &'static [&'static str] {
+        SUPPORTED_EXTENSIONS
+    }
+
+    async fn parse_local_path(
+        &self,
+        path: &Path,
+    ) -> Result<crate::domain::ir::ParsedDocument, DomainError> {
+        let bytes = tokio::fs::read(path)
+            .await
+            .map_err(|e| DomainError::io_error(format!("Failed to read file: {e}")))?;
+
+        let (blocks, info) = tokio::task::spawn_blocking(move || parse_epub(&bytes))
+            .await
+            .map_err(|e| DomainError::parse_error(format!("Task join error: {e}")))??;
+
+        let builder = DocumentBuilder::new(ParsedSource::LocalPath(path.display().to_string()))
+            .content_type(MIME_TYPE_EPUB)
+            .blocks(blocks);
+        let filename = path.file_name().and_then(|s| s.to_str());
+        Ok(info.apply(builder, filename).build())
+    }
+
+    async fn parse_bytes(
+        &self,
+        filename_hint: Option<&str>,
+        _content_type: Option<&str>,
+        bytes: bytes::Bytes,
+    ) -> Result<crate::domain::ir::ParsedDocument, DomainError> {
+        let (blocks, info) = tokio::task::spawn_blocking(move || parse_epub(&bytes))
+            .await
+            .map_err(|e| DomainError::parse_error(format!("Task join error: {e}")))??;
+
+        let source = ParsedSource::Uploaded {
+            original_name: filename_hint.unwrap_or("unknown.epub").to_owned(),
+        };
+        let builder = DocumentBuilder::new(source)
+            .content_type(MIME_TYPE_EPUB)
+            .blocks(blocks);
+        Ok(info.apply(builder, filename_hint).build())
+    }
+}
+
+fn parse_epub(bytes: &[u8]) -> Result<(Vec<ParsedBlock>, DocumentInfo), DomainError> {
+    let archive = zip::ZipArchive::new(Cursor::new(bytes))
+        .map_err(|e| DomainError::parse_error(format!("Failed to open EPUB package: {e}")))?;
+    let mut archive = Entries {
+        archive,
+        remaining: MAX_TOTAL_BYTES,
+    };
+
+    let container = archive
+        .read(CONTAINER_PATH)?
+        .ok_or_else(|| DomainError::parse_error("EPUB package has no META-INF/container.xml"))?;
+    let opf_path = parse_xml(&container)?
+        .descendants()
+        .find(|n| n.has_tag_name("rootfile"))
+        .and_then(|n| n.attribute("full-path"))
+        .map(ToOwned::to_owned)
+        .ok_or_else(|| DomainError::parse_error("EPUB container names no package document"))?;
+    let opf = archive
+        .read(&opf_path)?
+        .ok_or_else(|| DomainError::parse_error(format!("EPUB package has no {opf_path}")))?;
+    let package = Package::read(&parse_xml(&opf)?, &opf_path);
+
+    let titles = package.chapter_titles(&mut archive)?;
+
+    let mut blocks = Vec::new();
+    let mut read = HashSet::new();
+    for (index, href) in package.spine.iter().take(MAX_CHAPTERS).enumerate() {
+        // A spine listing the same document again would only repeat it
+        if !read.insert(href.as_str()) {
+            continue;
+        }
+        let Some(text) = archive.read(href)? else {
+            continue;
+        };
+        let chapter = match parse_xml(&replace_html_entities(&text)) {
+            Ok(doc) => chapter_blocks(&doc, href),
+            Err(e) => {
+                tracing::debug!(chapter = %href, error = %e, "Skipping unreadable EPUB chapter");
+                continue;
+            }
+        };
+
+        let title = titles
+            .get(href.as_str())
+            .cloned()
+            .or(chapter.title)
+            .unwrap_or_else(|| format!("Chapter {}", index + 1));
+        // Drop the chapter's own copy of the title
+        let mut body = chapter.blocks;
+        if let Some(ParsedBlock::Heading { inlines, .. }) = body.first()
+            && Inline::plain_text(inlines) == title
+        {
+            body.remove(0);
+        }
+        if body.is_empty() {
+            continue;
+        }
+        blocks.push(ParsedBlock::Heading {
+            level: 1,
+            inlines: vec![Inline::plain(title)],
+            location: None,
+        });
+        blocks.extend(body);
+    }
+
+    Ok((blocks, package.info))
+}
+
+/// Package entries read under a shared budget for the decompressed size
+struct Entries<R> {
+    archive: zip::ZipArchive<R>,
+    /// Bytes that may still be decompressed, see `MAX_TOTAL_BYTES`
+    remaining: u64,
+}
+
+impl<R: Read + Seek> Entries<R> {
+    fn read(&mut self, name: &str) -> Result<Option<String>, DomainError> {
+        let entry = match self.archive.by_name(name) {
+            Ok(entry) => entry,
+            Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+            Err(e) => {
+                return Err(DomainError::parse_error(format!(
+                    "Failed to read {name} from EPUB package: {e}"
+                )));
+            }
+        };
+
+        let limit = MAX_ENTRY_BYTES.min(self.remaining);
+        let mut data = Vec::new();
+        entry
+            .take(limit + 1)
+            .read_to_end(&mut data)
+            .map_err(|e| DomainError::parse_error(format!("Failed to read {name}: {e}")))?;
+        let len = u64::try_from(data.len()).unwrap_or(u64::MAX);
+        if len > MAX_ENTRY_BYTES {
+            return Err(DomainError::parse_error(format!(
+                "{name} exceeds {MAX_ENTRY_BYTES} bytes when decompressed"
+            )));
+        }
+        if len > limit {
+            return Err(DomainError::parse_error(format!(
+                "EPUB package exceeds {MAX_TOTAL_BYTES} bytes when decompressed"
+            )));
+        }
+        self.remaining -= len;
+        let data = data.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(&data);
+        Ok(Some(String::from_utf8_lossy(data).into_owned()))
+    }
+}
+
+fn parse_xml(text: &str) -> Result<Document<'_>, DomainError> {
+    let options = ParsingOptions {
+        allow_dtd: true,
+        ..ParsingOptions::default()
+    };
+    Document::parse_with_options(text, options)
+        .map_err(|e| DomainError::parse_error(format!("Malformed EPUB XML: {e}")))
+}
+
+fn replace_html_entities(text: &str) -> String {
+    let mut text = text.to_owned();
+    for (entity, reference) in HTML_ENTITIES {
+        if text.contains(entity) {
+            text = text.replace(entity, reference);
+        }
+    }
+    text
+}
+
+/// Resolve `href` against the directory of the archive entry `base`,
+/// dropping any fragment
+fn resolve_href(base: &str, href: &str) -> String {
+    let href = href.split('#').next().unwrap_or_default();
+    let href = percent_decode(href);
+    let mut parts: Vec<&str> = match base.rfind('/') {
+        Some(index) => base[..index].split('/').collect(),
+        None => Vec::new(),
+    };
+    for part in href.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            _ => parts.push(part),
+        }
+    }
+    parts.join("/")
+}
+
+fn percent_decode(value: &str) -> String {
+    if !value.contains('%') {
+        return value.to_owned();
+    }
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%'
+            && let Some(byte) = value
+                .get(index + 1..index + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            out.push(byte);
+            index += 3;
+        } else {
+            out.push(bytes[index]);
+            index += 1;
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+// ---------------------------------------------------------------------------
+// Package document
+// ---------------------------------------------------------------------------
+
+/// A manifest entry of the package document
+struct ManifestItem {
+    path: String,
+    media_type: String,
+    properties: String,
+}
+
+/// The parts of the package document (OPF) the parser needs
+struct Package {
+    info: DocumentInfo,
+    /// Archive paths of the content documents, in reading order
+    spine: Vec<String>,
+    /// EPUB 3 navigation document
+    nav: Option<String>,
+    /// EPUB 2 NCX table of contents
+    ncx: Option<String>,
+}
+
+impl Package {
+    fn read(doc: &Document, opf_path: &str) -> Self {
+        let manifest: HashMap<&str, ManifestItem> = doc
+            .descendants()
+            .filter(|n| n.has_tag_name((NS_OPF, "item")))
+            .filter_map(|item| {
+                let id = item.attribute("id")?;
+                let href = item.attribute("href")?;
+                Some((
+                    id,
+                    ManifestItem {
+                        path: resolve_href(opf_path, href),
+                        media_type: item.attribute("media-type").unwrap_or_default().to_owned(),
+                        properties: item.attribute("properties").unwrap_or_default().to_owned(),
+                    },
+                ))
+            })
+            .collect();
+
+        let spine_node = doc
+            .descendants()
+            .find(|n| n.has_tag_name((NS_OPF, "spine")));
+        let spine = spine_node
+            .into_iter()
+            .flat_map(|spine| spine.children())
+            .filter(|n| n.has_tag_name((NS_OPF, "itemref")))
+            .filter_map(|itemref| manifest.get(itemref.attribute("idref")?))
+            .filter(|item| is_content_document(&item.media_type))
+            .map(|item| item.path.clone())
+            .collect();
+
+        let nav = manifest
+            .values()
+            .find(|item| item.properties.split_whitespace().any(|p| p == "nav"))
+            .map(|item| item.path.clone());
+        let ncx = spine_node
+            .and_then(|spine| spine.attribute("toc"))
+            .and_then(|id| manifest.get(id))
+            .or_else(|| {
+                manifest
+                    .values()
+                    .find(|item| item.media_type == "application/x-dtbncx+xml")
+            })
+            .map(|item| item.path.clone());
+
+        Self {
+            info: read_metadata(doc),
+            spine,
+            nav,
+            ncx,
+        }
+    }
+
+    /// Chapter titles by archive path, from the navigation document or the
+    /// NCX; the first entry pointing into a chapter wins
+    fn chapter_titles<R: Read + Seek>(
+        &self,
+        archive: &mut Entries<R>,
+    ) -> Result<HashMap<String, String>, DomainError> {
+        let mut titles = HashMap::new();
+        if let Some(nav) = &self.nav
+            && let Some(text) = archive.read(nav)?
+            && let Ok(doc) = parse_xml(&replace_html_entities(&text))
+        {
+            let toc = doc
+                .descendants()
+                .find(|n| n.has_tag_name("nav") && n.attribute((NS_OPS, "type")) == Some("toc"));
+            let toc = toc.or_else(|| doc.descendants().find(|n| n.has_tag_name("nav")));
+            for link in toc
+                .into_iter()
+                .flat_map(|toc| toc.descendants())
+                .filter(|n| n.has_tag_name("a"))
+            {
+                if let (Some(href), Some(label)) = (link.attribute("href"), node_text(link)) {
+                    titles.entry(resolve_href(nav, href)).or_insert(label);
+                }
+            }
+        }
+        if titles.is_empty()
+            && let Some(ncx) = &self.ncx
+            && let Some(text) = archive.read(ncx)?
+            && let Ok(doc) = parse_xml(&text)
+        {
+            for point in doc.descendants().filter(|n| n.has_tag_name("navPoint")) {
+                let label = point
+                    .children()
+                    .find(|n| n.has_tag_name("navLabel"))
+                    .and_then(node_text);
+                let src = point
+                    .children()
+                    .find(|n| n.has_tag_name("content"))
+                    .and_then(|n| n.attribute("src"));
+                if let (Some(label), Some(src)) = (label, src) {
+                    titles.entry(resolve_href(ncx, src)).or_insert(label);
+                }
+            }
+        }
+        Ok(titles)
+    }
+}
+
+fn is_content_document(media_type: &str) -> bool {
+    matches!(media_type, "application/xhtml+xml" | "text/html")
+}
+
+fn read_metadata(doc: &Document) -> DocumentInfo {
+    let text = |name: &str| {
+        doc.descendants()
+            .find(|n| n.has_tag_name((NS_DC, name)))
+            .and_then(node_text)
+    };
+    let modified = doc
+        .descendants()
+        .find(|n| {
+            n.has_tag_name((NS_OPF, "meta")) && n.attribute("property") == Some("dcterms:modified")
+        })
+        .and_then(node_text);
+
+    DocumentInfo {
+        title: text("title"),
+        author: text("creator"),
+        language: text("language"),
+        created_at: text("date").and_then(|d| parse_epub_date(&d)),
+        modified_at: modified.and_then(|d| parse_epub_date(&d)),
+    }
+}
+
+/// Parse an ISO 8601 date or date-time; values without an offset are taken
+/// as UTC midnight or UTC time
+fn parse_epub_date(value: &str) -> Option<OffsetDateTime> {
+    OffsetDateTime::parse(value, &Iso8601::DEFAULT)
+        .or_else(|_| {
+            PrimitiveDateTime::parse(value, &Iso8601::DEFAULT).map(PrimitiveDateTime::assume_utc)
+        })
+        .or_else(|_| Date::parse(value, &Iso8601::DEFAULT).map(|d| d.midnight().assume_utc()))
+        .ok()
+}
+
+/// Collapsed text content of an element, `None` when blank
+fn node_text(node: Node) -> Option<String> {
+    let text: String = node
+        .descendants()
+        .filter(Node::is_text)
+        .filter_map(|n| n.text())
+        .collect();
+    non_empty(collapse_whitespace(&text))
+}
+
+// ---------------------------------------------------------------------------
+// Content documents
+// ---------------------------------------------------------------------------
+
+/// Blocks of one content document and its `<title>`
+struct Chapter {
+    title: Option<String>,
+    blocks: Vec<ParsedBlock>,
+}
+
+fn chapter_blocks(doc: &Document, path: &str) -> Chapter {
+    let title = doc
+        .descendants()
+        .find(|n| n.has_tag_name("title"))
+        .and_then(node_text);
+    let mut blocks = Vec::new();
+    if let Some(body) = doc.descendants().find(|n| n.has_tag_name("body")) {
+        Converter { path }.blocks(body, 0, &mut blocks);
+    }
+    Chapter { title, blocks }
+}
+
+struct Converter<'a> {
+    /// Archive path of the chapter, for resolving image sources
+    path: &'a str,
+}
+
+impl Converter<'_> {
+    fn blocks(&self, node: Node, list_level: u8, out: &mut Vec<ParsedBlock>) {
+        // Text directly inside containers such as <div> forms its own
+        // paragraph
+        let mut loose = Vec::new();
+        for child in node.children() {
+            if child.is_element() && is_block(child) {
+                flush_paragraph(&mut loose, out);
+                self.block(child, list_level, out);
+            } else {
+                collect_inlines(child, &InlineStyle::default(), &mut loose);
+            }
+        }
+        flush_paragraph(&mut loose, out);
+    }
+
+    fn block(&self, node: Node, list_level: u8, out: &mut Vec<ParsedBlock>) {
+        match node.tag_name().name() {
+            name @ ("h1" | "h2" | "h3" | "h4" | "h5" | "h6") => {
+                // Chapter titles take level 1, so headings move down a level
+                let level = name[1..]
+                    .parse::<u8>()
+                    .unwrap_or(1)
+                    .saturating_add(1)
+                    .min(6);
+                let inlines = paragraph_inlines(node);
+                if !inlines.is_empty() {
+                    out.push(ParsedBlock::Heading {
+                        level,
+                        inlines,
+                        location: None,
+                    });
+                }
+            }
+            "p" => {
+                let inlines = paragraph_inlines(node);
+                if !inlines.is_empty() {
+                    out.push(ParsedBlock::Paragraph {
+                        inlines,
+                        location: None,
+                    });
+                }
+            }
+            "ul" | "ol" => self.list(node, list_level, out),
+            "pre" => {
+                let code: String = node
+                    .descendants()
+                    .filter(Node::is_text)
+                    .filter_map(|n| n.text())
+                    .collect();
+                if !code.trim().is_empty() {
+                    out.push(ParsedBlock::CodeBlock {
+                        language: None,
+                        code: code.trim_matches('\n').to_owned(),
+                    });
+                }
+            }
+            "blockquote" => {
+                let mut blocks = Vec::new();
+                self.blocks(node, list_level, &mut blocks);
+                if !blocks.is_empty() {
+                    out.push(ParsedBlock::Quote { blocks });
+                }
+            }
+            "table" => {
+                if let Some(table) = self.table(node) {
+                    out.push(table);
+                }
+            }
+            "hr" => out.push(ParsedBlock::HorizontalRule),
+            "img" | "image" => out.push(self.image(node)),
+            "head" | "script" | "style" | "nav" => {}
+            _ => self.blocks(node, list_level, out),
+        }
+    }
+
+    fn list(&self, node: Node, level: u8, out: &mut Vec<ParsedBlock>) {
+        let ordered = node.tag_name().name() == "ol";
+        for item in node.children().filter(|n| n.tag_name().name() == "li") {
+            let mut blocks = Vec::new();
+            let mut nested = Vec::new();
+            let mut loose = Vec::new();
+            for child in item.children() {
+                match child.tag_name().name() {
+                    "ul" | "ol" if child.is_element() => {
+                        self.list(child, level.saturating_add(1), &mut nested);
+                    }
+                    _ if child.is_element() && is_block(child) => {
+                        flush_paragraph(&mut loose, &mut blocks);
+                        self.block(child, level.saturating_add(1), &mut blocks);
+                    }
+                    _ => collect_inlines(child, &InlineStyle::default(), &mut loose),
+                }
+            }
+            flush_paragraph(&mut loose, &mut blocks);
+            if !blocks.is_empty() {
+                out.push(ParsedBlock::ListItem {
+                    level,
+                    ordered,
+                    blocks,
+                });
+            }
+            out.append(&mut nested);
+        }
+    }
+
+    fn table(&self, node: Node) -> Option<ParsedBlock> {
+        let mut rows: Vec<TableRow> = node
+            .descendants()
+            .filter(|n| n.tag_name().name() == "tr")
+            .map(|row| {
+                let in_head = row
+                    .parent_element()
+                    .is_some_and(|p| p.tag_name().name() == "thead");
+                let cells: Vec<Node> = row
+                    .children()
+                    .filter(|n| matches!(n.tag_name().name(), "td" | "th"))
+                    .collect();
+                TableRow {
+                    is_header: in_head
+                        || (!cells.is_empty()
+                            && cells.iter().all(|cell| cell.tag_name().name() == "th")),
+                    cells: cells
+                        .into_iter()
+                        .map(|cell| {
+                            let mut blocks = Vec::new();
+                            self.blocks(cell, 0, &mut blocks);
+                            TableCell { blocks }
+                        })
+                        .collect(),
+                }
+            })
+            .filter(|row| !row.cells.is_empty())
+            .collect();
+        if rows.is_empty() {
+            return None;
+        }
+
+        let width = rows.iter().map(|row| row.cells.len()).max().unwrap_or(0);
+        for row in &mut rows {
+            row.cells
+                .resize_with(width, || TableCell { blocks: Vec::new() });
+        }
+        Some(ParsedBlock::Table(TableBlock {
+            rows,
+            location: None,
+        }))
+    }
+
+    fn image(&self, node: Node) -> ParsedBlock {
+        let src = node
+            .attribute("src")
+            .or_else(|| {
+                node.attributes()
+                    .find(|attr| attr.name() == "href")
+                    .map(|attr| attr.value())
+            })
+            .map(|src| resolve_href(self.path, src));
+        ParsedBlock::Image {
+            alt: node.attribute("alt").map(ToOwned::to_owned),
+            title: node.attribute("title").map(ToOwned::to_owned),
+            src,
+        }
+    }
+}
+
+fn paragraph_inlines(node: Node) -> Vec<Inline> {
+    let mut inlines = Vec::new();
+    collect_inlines(node, &InlineStyle::default(), &mut inlines);
+    Inline::trim(&mut inlines);
+    inlines
+}
+
+/// Append the inline content of `node`, styled by the enclosing elements
+fn collect_inlines(node: Node, style: &InlineStyle, out: &mut Vec<Inline>) {
+    if node.is_text() {
+        if let Some(text) = node.text() {
+            push_text(out, &collapse_whitespace(text), style);
+        }
+        return;
+    }
+    if !node.is_element() {
+        return;
+    }
+    let mut style = style.clone();
+    match node.tag_name().name() {
+        "br" => {
+            push_text(out, "\n", &style);
+            return;
+        }
+        "a" => {
+            let mut text = Vec::new();
+            for child in node.children() {
+                collect_inlines(child, &style, &mut text);
+            }
+            let text = Inline::plain_text(&text);
+            match node.attribute("href") {
+                // Links between chapters mean nothing outside the book
+                Some(target) if !text.trim().is_empty() && target.contains("://") => {
+                    out.push(Inline::Link {
+                        text,
+                        target: target.to_owned(),
+                        style,
+                    });
+                }
+                _ => push_text(out, &text, &style),
+            }
+            return;
+        }
+        "b" | "strong" => style.bold = true,
+        "i" | "em" | "cite" => style.italic = true,
+        "u" | "ins" => style.underline = true,
+        "s" | "strike" | "del" => style.strike = true,
+        "code" | "kbd" | "samp" | "tt" => style.code = true,
+        "script" | "style" => return,
+        _ => {}
+    }
+    for child in node.children() {
+        collect_inlines(child, &style, out);
+    }
+}
+
+fn is_block(node: Node) -> bool {
+    matches!(
+        node.tag_name().name(),
+        "h1" | "h2"
+            | "h3"
+            | "h4"
+            | "h5"
+            | "h6"
+            | "p"
+            | "div"
+            | "section"
+            | "article"
+            | "aside"
+            | "header"
+            | "footer"
+            | "figure"
+            | "ul"
+            | "ol"
+            | "pre"
+            | "blockquote"
+            | "table"
+            | "hr"
+            | "img"
+            | "svg"
+            | "image"
+            | "nav"
+            | "script"
+            | "style"
+    )
+}
+
+fn flush_paragraph(inlines: &mut Vec<Inline>, out: &mut Vec<ParsedBlock>) {
+    Inline::trim(inlines);
+    if !inlines.is_empty() {
+        out.push(ParsedBlock::Paragraph {
+            inlines: std::mem::take(inlines),
+            location: None,
+        });
+    }
+}
+
+/// HTML treats runs of ASCII whitespace as a single space; no-break spaces
+/// are kept
+fn collapse_whitespace(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut last_space = false;
+    for ch in text.chars() {
+        if ch.is_ascii_whitespace() {
+            if !last_space {
+                out.push(' ');
+            }
+            last_space = true;
+        } else {
+            out.push(ch);
+            last_space = false;
+        }
+    }
+    out
+}
+
+fn push_text(out: &mut Vec<Inline>, text: &str, style: &InlineStyle) {
+    if text.is_empty() {
+        return;
+    }
+    if let Some(Inline::Text {
+        text: last,
+        style: last_style,
+    }) = out.last_mut()
+        && last_style == style
+    {
+        // Avoid doubled spaces where inline elements meet
+        if last.ends_with(' ') && text.starts_with(' ') {
+            last.push_str(&text[1..]);
+        } else {
+            last.push_str(text);
+        }
+        return;
+    }
+    if style.code {
+        out.push(Inline::code(text));
+    } else {
+        out.push(Inline::styled(text, style.clone()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_href() {
+        assert_eq!(
+            resolve_href("OEBPS/content.opf", "text/ch1.xhtml#start"),
+            "OEBPS/text/ch1.xhtml"
+        );
+        assert_eq!(
+            resolve_href("OEBPS/text/ch1.xhtml", "../images/a%20b.png"),
+            "OEBPS/images/a b.png"
+        );
+        assert_eq!(resolve_href("content.opf", "./ch1.xhtml"), "ch1.xhtml");
+    }
+
+    #[test]
+    fn test_parse_epub_date() {
+        let date = parse_epub_date("2024-03-15").unwrap();
+        assert_eq!(
+            (date.year(), u8::from(date.month()), date.day()),
+            (2024, 3, 15)
+        );
+        let date = parse_epub_date("2024-03-15T09:30:00Z").unwrap();
+        assert_eq!(date.hour(), 9);
+    }
+}
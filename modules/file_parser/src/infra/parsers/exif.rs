@@ -0,0 +1,309 @@
+//! Reading of EXIF tags from their TIFF structure, and in-place removal of
+//! the GPS position.
+
+use std::ops::Range;
+
+use crate::domain::ir::{ExifData, GpsPosition};
+
+const TAG_DESCRIPTION: u16 = 0x010E;
+const TAG_MAKE: u16 = 0x010F;
+const TAG_MODEL: u16 = 0x0110;
+const TAG_ORIENTATION: u16 = 0x0112;
+const TAG_SOFTWARE: u16 = 0x0131;
+const TAG_DATE_TIME: u16 = 0x0132;
+const TAG_ARTIST: u16 = 0x013B;
+const TAG_COPYRIGHT: u16 = 0x8298;
+const TAG_EXIF_IFD: u16 = 0x8769;
+const TAG_GPS_IFD: u16 = 0x8825;
+const TAG_DATE_TIME_ORIGINAL: u16 = 0x9003;
+
+const GPS_LATITUDE_REF: u16 = 1;
+const GPS_LATITUDE: u16 = 2;
+const GPS_LONGITUDE_REF: u16 = 3;
+const GPS_LONGITUDE: u16 = 4;
+const GPS_ALTITUDE_REF: u16 = 5;
+const GPS_ALTITUDE: u16 = 6;
+
+/// Most entries an IFD may have, so that corrupt counts stay cheap
+const MAX_IFD_ENTRIES: u16 = 512;
+
+/// An IFD entry
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    tag: u16,
+    kind: u16,
+    count: u32,
+    /// Offset of the 4-byte value field of the entry
+    field: usize,
+}
+
+/// TIFF structure of EXIF data, in either byte order
+struct Tiff<'a> {
+    data: &'a [u8],
+    big_endian: bool,
+}
+
+impl<'a> Tiff<'a> {
+    fn new(data: &'a [u8]) -> Option<Self> {
+        let big_endian = match data.get(..2)? {
+            b"MM" => true,
+            b"II" => false,
+            _ => return None,
+        };
+        let tiff = Self { data, big_endian };
+        (tiff.u16(2)? == 42).then_some(tiff)
+    }
+
+    fn u16(&self, at: usize) -> Option<u16> {
+        let b: [u8; 2] = self.data.get(at..at + 2)?.try_into().ok()?;
+        Some(if self.big_endian {
+            u16::from_be_bytes(b)
+        } else {
+            u16::from_le_bytes(b)
+        })
+    }
+
+    fn u32(&self, at: usize) -> Option<u32> {
+        let b: [u8; 4] = self.data.get(at..at + 4)?.try_into().ok()?;
+        Some(if self.big_endian {
+            u32::from_be_bytes(b)
+        } else {
+            u32::from_le_bytes(b)
+        })
+    }
+
+    fn offset(&self, at: usize) -> Option<usize> {
+        usize::try_from(self.u32(at)?).ok()
+    }
+
+    fn first_ifd(&self) -> Option<usize> {
+        self.offset(4)
+    }
+
+    fn entries(&self, ifd: usize) -> Vec<Entry> {
+        let count = self.u16(ifd).unwrap_or(0).min(MAX_IFD_ENTRIES);
+        (0..usize::from(count))
+            .map_while(|i| {
+                let at = ifd + 2 + i * 12;
+                Some(Entry {
+                    tag: self.u16(at)?,
+                    kind: self.u16(at + 2)?,
+                    count: self.u32(at + 4)?,
+                    field: at + 8,
+                })
+            })
+            .collect()
+    }
+
+    /// Bytes of the entry's value, inline or at its offset
+    fn value(&self, entry: Entry) -> Option<Range<usize>> {
+        let unit = match entry.kind {
+            1 | 2 | 6 | 7 => 1,
+            3 | 8 => 2,
+            4 | 9 | 11 => 4,
+            5 | 10 | 12 => 8,
+            _ => return None,
+        };
+        let len = unit * usize::try_from(entry.count).ok()?;
+        let start = if len <= 4 {
+            entry.field
+        } else {
+            self.offset(entry.field)?
+        };
+        let range = start..start.checked_add(len)?;
+        self.data.get(range.clone())?;
+        Some(range)
+    }
+
+    fn string(&self, entry: Entry) -> Option<String> {
+        if entry.kind != 2 {
+            return None;
+        }
+        let bytes = &self.data[self.value(entry)?];
+        let text = String::from_utf8_lossy(bytes);
+        let text = text.trim_end_matches('\0').trim();
+        (!text.is_empty()).then(|| text.to_owned())
+    }
+
+    fn short(&self, entry: Entry) -> Option<u16> {
+        match entry.kind {
+            3 => self.u16(entry.field),
+            _ => None,
+        }
+    }
+
+    fn rationals(&self, entry: Entry) -> Option<Vec<f64>> {
+        if entry.kind != 5 {
+            return None;
+        }
+        let range = self.value(entry)?;
+        range
+            .step_by(8)
+            .map(|at| {
+                let numerator = self.u32(at)?;
+                let denominator = self.u32(at + 4)?;
+                (denominator != 0).then(|| f64::from(numerator) / f64::from(denominator))
+            })
+            .collect()
+    }
+
+    fn sub_ifd(&self, entries: &[Entry], tag: u16) -> Option<usize> {
+        let entry = entries.iter().find(|e| e.tag == tag)?;
+        self.offset(entry.field)
+    }
+}
+
+/// Read the common tags of EXIF data; `None` if it is not a TIFF structure
+pub fn read_exif(data: &[u8]) -> Option<ExifData> {
+    let tiff = Tiff::new(data)?;
+    let ifd0 = tiff.entries(tiff.first_ifd()?);
+    let string = |tag: u16| {
+        ifd0.iter()
+            .find(|e| e.tag == tag)
+            .and_then(|&e| tiff.string(e))
+    };
+
+    let mut exif = ExifData {
+        make: string(TAG_MAKE),
+        model: string(TAG_MODEL),
+        software: string(TAG_SOFTWARE),
+        taken_at: string(TAG_DATE_TIME),
+        orientation: ifd0
+            .iter()
+            .find(|e| e.tag == TAG_ORIENTATION)
+            .and_then(|&e| tiff.short(e)),
+        description: string(TAG_DESCRIPTION),
+        artist: string(TAG_ARTIST),
+        copyright: string(TAG_COPYRIGHT),
+        gps: None,
+    };
+
+    if let Some(ifd) = tiff.sub_ifd(&ifd0, TAG_EXIF_IFD)
+        && let Some(original) = tiff
+            .entries(ifd)
+            .into_iter()
+            .find(|e| e.tag == TAG_DATE_TIME_ORIGINAL)
+            .and_then(|e| tiff.string(e))
+    {
+        exif.taken_at = Some(original);
+    }
+    if let Some(ifd) = tiff.sub_ifd(&ifd0, TAG_GPS_IFD) {
+        exif.gps = read_gps(&tiff, &tiff.entries(ifd));
+    }
+    Some(exif)
+}
+
+fn read_gps(tiff: &Tiff<'_>, entries: &[Entry]) -> Option<GpsPosition> {
+    let find = |tag: u16| entries.iter().copied().find(|e| e.tag == tag);
+    let degrees = |tag: u16, negative_ref: &str, ref_tag: u16| {
+        let parts = tiff.rationals(find(tag)?)?;
+        let [d, m, s] = parts.as_slice() else {
+            return None;
+        };
+        let value = d + m / 60.0 + s / 3600.0;
+        let negative = find(ref_tag)
+            .and_then(|e| tiff.string(e))
+            .is_some_and(|r| r.eq_ignore_ascii_case(negative_ref));
+        Some(if negative { -value } else { value })
+    };
+
+    let latitude = degrees(GPS_LATITUDE, "S", GPS_LATITUDE_REF)?;
+    let longitude = degrees(GPS_LONGITUDE, "W", GPS_LONGITUDE_REF)?;
+    let altitude = find(GPS_ALTITUDE)
+        .and_then(|e| tiff.rationals(e))
+        .and_then(|parts| parts.first().copied())
+        .map(|altitude| {
+            // Reference 1 means below sea level
+            let below = find(GPS_ALTITUDE_REF)
+                .and_then(|e| tiff.value(e))
+                .is_some_and(|range| tiff.data[range.start] == 1);
+            if below { -altitude } else { altitude }
+        });
+    Some(GpsPosition {
+        latitude,
+        longitude,
+        altitude,
+    })
+}
+
+/// Erase the GPS IFD of EXIF data in place: its values are zeroed and it is
+/// left with no entries, so that offsets elsewhere stay valid. Returns
+/// whether there was anything to erase.
+pub fn strip_gps(data: &mut [u8]) -> bool {
+    let (ifd, values) = {
+        let Some(tiff) = Tiff::new(data) else {
+            return false;
+        };
+        let Some(first) = tiff.first_ifd() else {
+            return false;
+        };
+        let Some(ifd) = tiff.sub_ifd(&tiff.entries(first), TAG_GPS_IFD) else {
+            return false;
+        };
+        let entries = tiff.entries(ifd);
+        if entries.is_empty() {
+            return false;
+        }
+        let values: Vec<Range<usize>> = entries.iter().filter_map(|&e| tiff.value(e)).collect();
+        // Entry count, entries and the offset of the next IFD
+        (ifd..ifd + 2 + entries.len() * 12 + 4, values)
+    };
+
+    for range in values.into_iter().chain([ifd]) {
+        let end = range.end.min(data.len());
+        if let Some(bytes) = data.get_mut(range.start..end) {
+            bytes.fill(0);
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    /// Little-endian TIFF with Make and a GPS IFD at 52.5 N, 13.25 W
+    fn sample() -> Vec<u8> {
+        let mut t = b"II\x2A\0\x08\0\0\0".to_vec();
+        // IFD0 at 8: Make (inline "Cam\0"), GPS pointer to 38
+        t.extend_from_slice(&2_u16.to_le_bytes());
+        t.extend_from_slice(&[0x0F, 0x01, 2, 0, 4, 0, 0, 0, b'C', b'a', b'm', 0]);
+        t.extend_from_slice(&[0x25, 0x88, 4, 0, 1, 0, 0, 0, 38, 0, 0, 0]);
+        t.extend_from_slice(&[0; 4]);
+        // GPS IFD at 38 with 4 entries, values from 92
+        t.extend_from_slice(&4_u16.to_le_bytes());
+        t.extend_from_slice(&[1, 0, 2, 0, 2, 0, 0, 0, b'N', 0, 0, 0]);
+        t.extend_from_slice(&[2, 0, 5, 0, 3, 0, 0, 0, 92, 0, 0, 0]);
+        t.extend_from_slice(&[3, 0, 2, 0, 2, 0, 0, 0, b'W', 0, 0, 0]);
+        t.extend_from_slice(&[4, 0, 5, 0, 3, 0, 0, 0, 116, 0, 0, 0]);
+        t.extend_from_slice(&[0; 4]);
+        for (n, d) in [(52, 1), (30, 1), (0, 1), (13, 1), (15, 1), (0, 1)] {
+            t.extend_from_slice(&u32::to_le_bytes(n));
+            t.extend_from_slice(&u32::to_le_bytes(d));
+        }
+        t
+    }
+
+    #[test]
+    fn test_read_exif() {
+        let exif = read_exif(&sample()).unwrap();
+        assert_eq!(exif.make.as_deref(), Some("Cam"));
+        let gps = exif.gps.unwrap();
+        assert!((gps.latitude - 52.5).abs() < 1e-9);
+        assert!((gps.longitude + 13.25).abs() < 1e-9);
+        assert_eq!(gps.altitude, None);
+    }
+
+    #[test]
+    fn test_strip_gps() {
+        let mut data = sample();
+        assert!(strip_gps(&mut data));
+        assert!(!strip_gps(&mut data));
+
+        let exif = read_exif(&data).unwrap();
+        assert_eq!(exif.make.as_deref(), Some("Cam"));
+        assert_eq!(exif.gps, None);
+        assert!(data[92..].iter().all(|&b| b == 0));
+    }
+}
@@ -96,7 +96,7 @@ impl FileParserBackend for HtmlParser {
     }
 }
 
-fn parse_html_bytes(
+pub(crate) fn parse_html_bytes(
     bytes: &[u8],
     filename: Option<&str>,
 ) -> Result<(Vec<ParsedBlock>, Option<String>), DomainError> {
@@ -138,6 +138,7 @@ fn parse_html_bytes(
         if !text.is_empty() {
             blocks.push(ParsedBlock::Paragraph {
                 inlines: vec![Inline::plain(text)],
+                location: None,
             });
         }
     }
@@ -167,6 +168,7 @@ fn extract_blocks_from_node(
                 blocks.push(ParsedBlock::Heading {
                     level,
                     inlines: vec![Inline::plain(text)],
+                    location: None,
                 });
             }
         }
@@ -176,6 +178,7 @@ fn extract_blocks_from_node(
                 // TODO: Parse inline styles from HTML
                 blocks.push(ParsedBlock::Paragraph {
                     inlines: vec![Inline::plain(text)],
+                    location: None,
                 });
             }
         }
@@ -188,6 +191,7 @@ fn extract_blocks_from_node(
                     ordered: false,
                     blocks: vec![ParsedBlock::Paragraph {
                         inlines: vec![Inline::plain(text)],
+                        location: None,
                     }],
                 });
             }
@@ -206,6 +210,7 @@ fn extract_blocks_from_node(
                             ordered,
                             blocks: vec![ParsedBlock::Paragraph {
                                 inlines: vec![Inline::plain(text)],
+                                location: None,
                             }],
                         });
                     }
@@ -228,6 +233,7 @@ fn extract_blocks_from_node(
                 blocks.push(ParsedBlock::Quote {
                     blocks: vec![ParsedBlock::Paragraph {
                         inlines: vec![Inline::plain(text)],
+                        location: None,
                     }],
                 });
             }
@@ -0,0 +1,406 @@
+//! Header-level reading of PNG, JPEG, GIF and WebP files: format, dimensions,
+//! frame count and the location of EXIF data, without decoding pixels.
+
+use std::ops::Range;
+
+use crate::domain::error::DomainError;
+
+/// Image formats accepted by the image parser
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Png,
+    Jpeg,
+    Gif,
+    Webp,
+}
+
+impl ImageFormat {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Jpeg => "jpeg",
+            Self::Gif => "gif",
+            Self::Webp => "webp",
+        }
+    }
+
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            Self::Png => "image/png",
+            Self::Jpeg => "image/jpeg",
+            Self::Gif => "image/gif",
+            Self::Webp => "image/webp",
+        }
+    }
+}
+
+/// What the header of an image tells without decoding it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageHeader {
+    pub format: ImageFormat,
+    pub width: u32,
+    pub height: u32,
+    pub frames: u32,
+    /// TIFF structure holding the EXIF tags, within the file
+    pub exif: Option<Range<usize>>,
+}
+
+impl ImageHeader {
+    pub fn pixels(&self) -> u64 {
+        u64::from(self.width) * u64::from(self.height)
+    }
+}
+
+/// Read the header of an image
+///
+/// # Errors
+/// Returns `ParseError` if the data is not a PNG, JPEG, GIF or WebP image,
+/// or its header is truncated.
+pub fn read_header(bytes: &[u8]) -> Result<ImageHeader, DomainError> {
+    let header = if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        read_png(bytes)
+    } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        read_jpeg(bytes)
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        read_gif(bytes)
+    } else if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        read_webp(bytes)
+    } else {
+        return Err(DomainError::parse_error(
+            "Unrecognized image data, expected PNG, JPEG, GIF or WebP",
+        ));
+    };
+    header.ok_or_else(|| DomainError::parse_error("Truncated or malformed image header"))
+}
+
+fn be_u16(bytes: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(bytes.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn be_u32(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn le_u16(bytes: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(bytes.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn le_u24(bytes: &[u8], at: usize) -> Option<u32> {
+    let b = bytes.get(at..at + 3)?;
+    Some(u32::from(b[0]) | u32::from(b[1]) << 8 | u32::from(b[2]) << 16)
+}
+
+fn le_u32(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+/// PNG chunks in file order, as `(type, data range)`
+fn png_chunks(bytes: &[u8]) -> impl Iterator<Item = (&[u8], Range<usize>)> {
+    let mut pos = 8;
+    std::iter::from_fn(move || {
+        let len = usize::try_from(be_u32(bytes, pos)?).ok()?;
+        let kind = bytes.get(pos + 4..pos + 8)?;
+        let data = pos + 8..pos + 8 + len;
+        // Data and CRC must be complete
+        bytes.get(data.start..data.end + 4)?;
+        pos = data.end + 4;
+        Some((kind, data))
+    })
+}
+
+fn read_png(bytes: &[u8]) -> Option<ImageHeader> {
+    let mut chunks = png_chunks(bytes);
+    let (kind, ihdr) = chunks.next()?;
+    if kind != b"IHDR" {
+        return None;
+    }
+    let mut header = ImageHeader {
+        format: ImageFormat::Png,
+        width: be_u32(bytes, ihdr.start)?,
+        height: be_u32(bytes, ihdr.start + 4)?,
+        frames: 1,
+        exif: None,
+    };
+    for (kind, data) in chunks {
+        match kind {
+            // Animation control of APNG files
+            b"acTL" => header.frames = be_u32(bytes, data.start)?.max(1),
+            b"eXIf" => header.exif = Some(data),
+            b"IEND" => break,
+            _ => {}
+        }
+    }
+    Some(header)
+}
+
+fn read_jpeg(bytes: &[u8]) -> Option<ImageHeader> {
+    let mut pos = 2;
+    let mut exif = None;
+    loop {
+        // Markers may be preceded by fill bytes
+        while bytes.get(pos) == Some(&0xFF) && bytes.get(pos + 1) == Some(&0xFF) {
+            pos += 1;
+        }
+        if *bytes.get(pos)? != 0xFF {
+            return None;
+        }
+        let marker = *bytes.get(pos + 1)?;
+        if matches!(marker, 0x01 | 0xD0..=0xD7) {
+            pos += 2;
+            continue;
+        }
+        let len = usize::from(be_u16(bytes, pos + 2)?);
+        let data = pos + 4..pos + 2 + len;
+        match marker {
+            0xE1 if bytes.get(data.start..data.start + 6) == Some(b"Exif\0\0") => {
+                exif = Some(data.start + 6..data.end.min(bytes.len()));
+            }
+            // Start of frame, except DHT, JPG and DAC which share the range
+            0xC0..=0xCF if !matches!(marker, 0xC4 | 0xC8 | 0xCC) => {
+                return Some(ImageHeader {
+                    format: ImageFormat::Jpeg,
+                    width: u32::from(be_u16(bytes, data.start + 3)?),
+                    height: u32::from(be_u16(bytes, data.start + 1)?),
+                    frames: 1,
+                    exif,
+                });
+            }
+            // Start of scan or end of image without a frame header
+            0xDA | 0xD9 => return None,
+            _ => {}
+        }
+        pos = data.end;
+    }
+}
+
+/// Position after a run of GIF data sub-blocks starting at `pos`
+fn skip_sub_blocks(bytes: &[u8], mut pos: usize) -> Option<usize> {
+    loop {
+        let len = usize::from(*bytes.get(pos)?);
+        pos += 1;
+        if len == 0 {
+            return Some(pos);
+        }
+        pos += len;
+    }
+}
+
+/// Size of the color table announced by a GIF packed field
+fn color_table_len(packed: u8) -> usize {
+    if packed & 0x80 == 0 {
+        0
+    } else {
+        3 << ((packed & 0x07) + 1)
+    }
+}
+
+/// Blocks of a GIF after its header, as `(kind, range)`: `0x21` for
+/// extensions and `0x2C` for images, including their color table and data
+fn gif_blocks(bytes: &[u8]) -> impl Iterator<Item = (u8, Range<usize>)> {
+    let mut pos = bytes
+        .get(10)
+        .map_or(bytes.len(), |&packed| 13 + color_table_len(packed));
+    std::iter::from_fn(move || {
+        let start = pos;
+        let kind = *bytes.get(start)?;
+        let end = match kind {
+            0x21 => skip_sub_blocks(bytes, start + 2)?,
+            0x2C => {
+                let packed = *bytes.get(start + 9)?;
+                // Local color table, then the LZW minimum code size
+                skip_sub_blocks(bytes, start + 10 + color_table_len(packed) + 1)?
+            }
+            _ => return None,
+        };
+        bytes.get(start..end)?;
+        pos = end;
+        Some((kind, start..end))
+    })
+}
+
+fn read_gif(bytes: &[u8]) -> Option<ImageHeader> {
+    let frames = gif_blocks(bytes).filter(|(kind, _)| *kind == 0x2C).count();
+    Some(ImageHeader {
+        format: ImageFormat::Gif,
+        width: u32::from(le_u16(bytes, 6)?),
+        height: u32::from(le_u16(bytes, 8)?),
+        frames: u32::try_from(frames).unwrap_or(u32::MAX).max(1),
+        exif: None,
+    })
+}
+
+/// A GIF with only the first frame of `bytes`, and without the application
+/// extensions that control looping
+///
+/// # Errors
+/// Returns `ParseError` if the GIF has no complete frame.
+pub fn gif_first_frame(bytes: &[u8]) -> Result<Vec<u8>, DomainError> {
+    let screen_end = bytes
+        .get(10)
+        .map_or(bytes.len(), |&packed| 13 + color_table_len(packed));
+    let mut gif = bytes
+        .get(..screen_end)
+        .ok_or_else(|| DomainError::parse_error("Truncated GIF header"))?
+        .to_vec();
+    for (kind, range) in gif_blocks(bytes) {
+        let is_application = kind == 0x21 && bytes.get(range.start + 1) == Some(&0xFF);
+        if !is_application {
+            gif.extend_from_slice(&bytes[range]);
+        }
+        if kind == 0x2C {
+            gif.push(0x3B);
+            return Ok(gif);
+        }
+    }
+    Err(DomainError::parse_error("GIF has no complete frame"))
+}
+
+/// RIFF chunks of a WebP file, as `(fourcc, data range)`
+fn webp_chunks(bytes: &[u8]) -> impl Iterator<Item = (&[u8], Range<usize>)> {
+    let mut pos = 12;
+    std::iter::from_fn(move || {
+        let kind = bytes.get(pos..pos + 4)?;
+        let len = usize::try_from(le_u32(bytes, pos + 4)?).ok()?;
+        let data = pos + 8..pos + 8 + len;
+        bytes.get(data.clone())?;
+        // Chunks are padded to an even size
+        pos = data.end + (len & 1);
+        Some((kind, data))
+    })
+}
+
+fn read_webp(bytes: &[u8]) -> Option<ImageHeader> {
+    let mut chunks = webp_chunks(bytes);
+    let (kind, data) = chunks.next()?;
+    let (width, height) = match kind {
+        b"VP8 " => {
+            if bytes.get(data.start + 3..data.start + 6)? != [0x9D, 0x01, 0x2A] {
+                return None;
+            }
+            (
+                u32::from(le_u16(bytes, data.start + 6)? & 0x3FFF),
+                u32::from(le_u16(bytes, data.start + 8)? & 0x3FFF),
+            )
+        }
+        b"VP8L" => {
+            if *bytes.get(data.start)? != 0x2F {
+                return None;
+            }
+            let bits = le_u32(bytes, data.start + 1)?;
+            ((bits & 0x3FFF) + 1, ((bits >> 14) & 0x3FFF) + 1)
+        }
+        b"VP8X" => (
+            le_u24(bytes, data.start + 4)? + 1,
+            le_u24(bytes, data.start + 7)? + 1,
+        ),
+        _ => return None,
+    };
+    let mut header = ImageHeader {
+        format: ImageFormat::Webp,
+        width,
+        height,
+        frames: 1,
+        exif: None,
+    };
+    if kind == b"VP8X" {
+        let mut frames = 0_u32;
+        for (kind, data) in chunks {
+            match kind {
+                b"ANMF" => frames = frames.saturating_add(1),
+                b"EXIF" => {
+                    // Some writers keep the JPEG APP1 prefix
+                    let skip = if bytes.get(data.start..data.start + 6) == Some(b"Exif\0\0") {
+                        6
+                    } else {
+                        0
+                    };
+                    header.exif = Some(data.start + skip..data.end);
+                }
+                _ => {}
+            }
+        }
+        header.frames = frames.max(1);
+    }
+    Some(header)
+}
+
+/// Recompute the CRC of the PNG chunk whose data is at `data`, after it was
+/// changed in place
+pub fn fix_png_chunk_crc(bytes: &mut [u8], data: &Range<usize>) {
+    let Some(covered) = bytes.get(data.start.saturating_sub(4)..data.end) else {
+        return;
+    };
+    let crc = crc32(covered).to_be_bytes();
+    if let Some(slot) = bytes.get_mut(data.end..data.end + 4) {
+        slot.copy_from_slice(&crc);
+    }
+}
+
+/// CRC-32 as used by PNG (ISO 3309, reflected polynomial `0xEDB88320`)
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFF_u32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    /// 2x1 GIF with two frames and a looping extension
+    const ANIMATED_GIF: &[u8] = &[
+        b'G', b'I', b'F', b'8', b'9', b'a', 2, 0, 1, 0, 0x80, 0, 0, // screen
+        0, 0, 0, 0xFF, 0xFF, 0xFF, // color table
+        0x21, 0xFF, 11, b'N', b'E', b'T', b'S', b'C', b'A', b'P', b'E', b'2', b'.', b'0', 3, 1, 0,
+        0, 0, // loop forever
+        0x21, 0xF9, 4, 0, 10, 0, 0, 0, // delay
+        0x2C, 0, 0, 0, 0, 2, 0, 1, 0, 0, 2, 2, 0x44, 0x01, 0, // frame 1
+        0x21, 0xF9, 4, 0, 10, 0, 0, 0, // delay
+        0x2C, 0, 0, 0, 0, 2, 0, 1, 0, 0, 2, 2, 0x84, 0x01, 0, // frame 2
+        0x3B,
+    ];
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b"IEND"), 0xAE42_6082);
+    }
+
+    #[test]
+    fn test_read_gif_frames() {
+        let header = read_header(ANIMATED_GIF).unwrap();
+        assert_eq!(header.format, ImageFormat::Gif);
+        assert_eq!((header.width, header.height, header.frames), (2, 1, 2));
+    }
+
+    #[test]
+    fn test_gif_first_frame() {
+        let first = gif_first_frame(ANIMATED_GIF).unwrap();
+        let header = read_header(&first).unwrap();
+        assert_eq!(header.frames, 1);
+        assert!(!first.windows(8).any(|w| w == b"NETSCAPE"));
+        assert_eq!(first.last(), Some(&0x3B));
+    }
+
+    #[test]
+    fn test_read_vp8l_dimensions() {
+        let bits: u32 = (640 - 1) | (480 - 1) << 14;
+        let mut webp = b"RIFF\0\0\0\0WEBPVP8L\x05\0\0\0\x2F".to_vec();
+        webp.extend_from_slice(&bits.to_le_bytes());
+        webp.push(0);
+        let header = read_header(&webp).unwrap();
+        assert_eq!((header.width, header.height), (640, 480));
+    }
+
+    #[test]
+    fn test_rejects_unknown_data() {
+        assert!(read_header(b"BM\0\0\0\0").is_err());
+        assert!(read_header(b"\x89PNG\r\n\x1a\n\0\0").is_err());
+    }
+}
@@ -1,14 +1,20 @@
 use async_trait::async_trait;
 use std::path::Path;
+use std::sync::Arc;
 
+use crate::config::{AnimatedGifPolicy, ImageConfig};
 use crate::domain::error::DomainError;
-use crate::domain::ir::{DocumentBuilder, ParsedBlock, ParsedSource};
+use crate::domain::ir::{DocumentBuilder, ImageMetadata, ParsedBlock, ParsedSource};
+use crate::domain::ocr::OcrProvider;
 use crate::domain::parser::FileParserBackend;
+use crate::infra::parsers::exif;
+use crate::infra::parsers::image_header::{self, ImageFormat};
 
 /// Image parser that handles standard image formats
 ///
-/// Treats images as opaque binary payloads and encodes them as base64 data URIs.
-/// No decoding, resizing, or re-encoding is performed.
+/// Images are embedded as base64 data URIs. Only their headers are read, for
+/// the format, dimensions, frame count and EXIF data; pixels are never
+/// decoded, resized or re-encoded.
 ///
 /// Supported formats:
 /// - PNG (.png, image/png)
@@ -16,17 +22,26 @@ use crate::domain::parser::FileParserBackend;
 /// - WebP (.webp, image/webp)
 /// - GIF (.gif, image/gif)
 ///
-/// # File Size Limits
-/// Individual image files are limited to 50 MB to prevent memory exhaustion.
-/// The service layer enforces an additional global file size limit (configurable,
+/// The format is taken from the content, not from the file name or
+/// `Content-Type`.
+///
+/// # Limits
+/// Individual image files are limited to 50 MB to prevent memory exhaustion,
+/// and to `ImageConfig::max_pixels` against decompression bombs. The service
+/// layer enforces an additional global file size limit (configurable,
 /// defaults to 100 MB) that applies to all file types.
 ///
-/// # Note on GIF format
-/// This parser accepts GIF files but does **not** validate whether they are static or animated.
-/// Animated GIFs are generally unsuitable for LLM consumption (they contain multiple frames,
-/// but only the first frame would typically be useful). Future implementations may add validation
-/// to reject animated GIFs or extract only the first frame.
-pub struct ImageParser;
+/// # Animated GIFs
+/// Animated GIFs are generally unsuitable for LLM consumption. Depending on
+/// `ImageConfig::animated_gif` they are rejected, or cut to their first frame.
+///
+/// # OCR
+/// With an `OcrProvider`, the recognized text follows the `Image` block.
+/// Recognition failures are logged and leave the image without text.
+pub struct ImageParser {
+    config: ImageConfig,
+    ocr: Option<Arc<dyn OcrProvider>>,
+}
 
 /// Supported file extensions for image formats
 const SUPPORTED_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "webp", "gif"];
@@ -40,7 +55,19 @@ const MAX_IMAGE_SIZE_BYTES: u64 = MAX_IMAGE_SIZE_MB * 1024 * 1024;
 impl ImageParser {
     #[must_use]
     pub fn new() -> Self {
-        Self
+        Self::with_config(ImageConfig::default())
+    }
+
+    #[must_use]
+    pub fn with_config(config: ImageConfig) -> Self {
+        Self { config, ocr: None }
+    }
+
+    /// Recognize the text of every image with `ocr`
+    #[must_use]
+    pub fn with_ocr(mut self, ocr: Arc<dyn OcrProvider>) -> Self {
+        self.ocr = Some(ocr);
+        self
     }
 
     /// Determine MIME type from file extension
@@ -91,6 +118,98 @@ impl ImageParser {
         let encoded = base64::engine::general_purpose::STANDARD.encode(bytes);
         format!("data:{mime_type};base64,{encoded}")
     }
+
+    /// Check an image against the limits and apply the GIF and GPS policies,
+    /// returning the bytes to embed
+    fn inspect(
+        &self,
+        bytes: bytes::Bytes,
+    ) -> Result<(ImageFormat, ImageMetadata, bytes::Bytes), DomainError> {
+        let header = image_header::read_header(&bytes)?;
+        if header.pixels() > self.config.max_pixels {
+            return Err(DomainError::invalid_request(format!(
+                "Image too large: {}x{} pixels (max {} pixels)",
+                header.width, header.height, self.config.max_pixels
+            )));
+        }
+
+        let mut exif = header
+            .exif
+            .as_ref()
+            .and_then(|range| bytes.get(range.clone()))
+            .and_then(exif::read_exif);
+
+        let mut output = bytes;
+        if header.format == ImageFormat::Gif && header.frames > 1 {
+            match self.config.animated_gif {
+                AnimatedGifPolicy::Reject => {
+                    return Err(DomainError::invalid_request(format!(
+                        "Animated GIFs are not accepted ({} frames)",
+                        header.frames
+                    )));
+                }
+                AnimatedGifPolicy::FirstFrame => {
+                    output = image_header::gif_first_frame(&output)?.into();
+                }
+            }
+        }
+
+        // Erase whatever GPS IFD there is, even one too partial to read
+        if self.config.strip_gps
+            && let Some(range) = header.exif.clone()
+        {
+            if let Some(exif) = exif.as_mut() {
+                exif.gps = None;
+            }
+            let mut stripped = output.to_vec();
+            if let Some(data) = stripped.get_mut(range.clone())
+                && exif::strip_gps(data)
+            {
+                if header.format == ImageFormat::Png {
+                    image_header::fix_png_chunk_crc(&mut stripped, &range);
+                }
+                output = stripped.into();
+            }
+        }
+
+        let metadata = ImageMetadata {
+            format: header.format.name().to_owned(),
+            width: header.width,
+            height: header.height,
+            frames: header.frames,
+            exif,
+        };
+        Ok((header.format, metadata, output))
+    }
+
+    /// Build the document of an image: its `Image` block, then any text
+    /// recognized in it
+    async fn build_document(
+        &self,
+        builder: DocumentBuilder,
+        bytes: bytes::Bytes,
+    ) -> Result<crate::domain::ir::ParsedDocument, DomainError> {
+        let (format, metadata, bytes) = self.inspect(bytes)?;
+        let mime_type = format.mime_type();
+
+        let mut blocks = vec![ParsedBlock::Image {
+            alt: None,
+            title: None,
+            src: Some(Self::build_data_uri(mime_type, &bytes)),
+        }];
+        if let Some(ocr) = &self.ocr {
+            match ocr.recognize(mime_type, bytes).await {
+                Ok(text) => blocks.extend(text),
+                Err(e) => tracing::warn!(error = %e, "Text recognition failed"),
+            }
+        }
+
+        Ok(builder
+            .content_type(mime_type)
+            .image(metadata)
+            .blocks(blocks)
+            .build())
+    }
 }
 
 impl Default for ImageParser {
@@ -131,36 +250,25 @@ impl FileParserBackend for ImageParser {
             .await
             .map_err(|e| DomainError::io_error(format!("Failed to read image file: {e}")))?;
 
-        // Determine MIME type from extension
+        // Only image extensions are accepted
         let extension = path
             .extension()
             .and_then(|s| s.to_str())
             .ok_or_else(|| DomainError::unsupported_file_type("no extension"))?;
 
-        let mime_type = Self::mime_type_from_extension(extension)
+        Self::mime_type_from_extension(extension)
             .ok_or_else(|| DomainError::unsupported_file_type(extension))?;
 
-        // Build data URI
-        let data_uri = Self::build_data_uri(mime_type, &bytes);
-
         // Extract filename
         let filename = path
             .file_name()
             .and_then(|s| s.to_str())
             .map(ToString::to_string);
 
-        // Build document with single Image block
-        let document = DocumentBuilder::new(ParsedSource::LocalPath(path.display().to_string()))
-            .content_type(mime_type)
-            .original_filename(filename.unwrap_or_else(|| "unknown".to_owned()))
-            .blocks(vec![ParsedBlock::Image {
-                alt: None,
-                title: None,
-                src: Some(data_uri),
-            }])
-            .build();
-
-        Ok(document)
+        let builder = DocumentBuilder::new(ParsedSource::LocalPath(path.display().to_string()))
+            .original_filename(filename.unwrap_or_else(|| "unknown".to_owned()));
+
+        self.build_document(builder, bytes.into()).await
     }
 
     async fn parse_bytes(
@@ -179,11 +287,9 @@ impl FileParserBackend for ImageParser {
             )));
         }
 
-        // Determine MIME type
-        let mime_type = Self::determine_mime_type(filename_hint, content_type)?;
-
-        // Build data URI
-        let data_uri = Self::build_data_uri(&mime_type, &bytes);
+        // The file must be named or typed as an image; the actual format
+        // comes from the content
+        Self::determine_mime_type(filename_hint, content_type)?;
 
         // Determine source
         let source = if let Some(name) = filename_hint {
@@ -196,17 +302,9 @@ impl FileParserBackend for ImageParser {
             }
         };
 
-        // Build document with single Image block
-        let document = DocumentBuilder::new(source)
-            .content_type(mime_type)
-            .original_filename(filename_hint.unwrap_or("unknown"))
-            .blocks(vec![ParsedBlock::Image {
-                alt: None,
-                title: None,
-                src: Some(data_uri),
-            }])
-            .build();
-
-        Ok(document)
+        let builder =
+            DocumentBuilder::new(source).original_filename(filename_hint.unwrap_or("unknown"));
+
+        self.build_document(builder, bytes).await
     }
 }
@@ -0,0 +1,51 @@
+use time::OffsetDateTime;
+
+use crate::domain::ir::DocumentBuilder;
+
+/// Document properties read from a file's embedded metadata
+#[derive(Debug, Default)]
+pub struct DocumentInfo {
+    pub title: Option<String>,
+    pub author: Option<String>,
+    pub language: Option<String>,
+    pub created_at: Option<OffsetDateTime>,
+    pub modified_at: Option<OffsetDateTime>,
+}
+
+impl DocumentInfo {
+    /// Copy the properties into the builder, preferring the embedded title
+    /// over the file name
+    pub fn apply(self, mut builder: DocumentBuilder, filename: Option<&str>) -> DocumentBuilder {
+        if let Some(filename) = filename {
+            builder = builder.original_filename(filename);
+        }
+        if let Some(title) = self.title.as_deref().or(filename) {
+            builder = builder.title(title);
+        }
+        if let Some(author) = self.author {
+            builder = builder.author(author);
+        }
+        if let Some(language) = self.language {
+            builder = builder.language(language);
+        }
+        if let Some(created_at) = self.created_at {
+            builder = builder.created_at(created_at);
+        }
+        if let Some(modified_at) = self.modified_at {
+            builder = builder.modified_at(modified_at);
+        }
+        builder
+    }
+}
+
+/// Drop empty and whitespace-only values
+pub fn non_empty(value: String) -> Option<String> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        None
+    } else if trimmed.len() == value.len() {
+        Some(value)
+    } else {
+        Some(trimmed.to_owned())
+    }
+}
@@ -1,17 +1,34 @@
+pub mod csv_parser;
+pub mod doc_parser;
 pub mod docx_parser;
+pub mod email_parser;
+pub mod epub_parser;
+pub(crate) mod exif;
 pub mod html_parser;
+pub(crate) mod image_header;
 pub mod image_parser;
+pub(crate) mod metadata;
+pub mod odf_parser;
 pub mod pdf_parser;
 pub mod plain_text;
 pub mod pptx_parser;
+pub mod rtf_parser;
+pub mod structured_parser;
 pub mod stub;
 pub mod xlsx_parser;
 
+pub use csv_parser::CsvParser;
+pub use doc_parser::DocParser;
 pub use docx_parser::DocxParser;
+pub use email_parser::EmailParser;
+pub use epub_parser::EpubParser;
 pub use html_parser::HtmlParser;
 pub use image_parser::ImageParser;
+pub use odf_parser::OdfParser;
 pub use pdf_parser::PdfParser;
 pub use plain_text::PlainTextParser;
 pub use pptx_parser::PptxParser;
+pub use rtf_parser::RtfParser;
+pub use structured_parser::StructuredDataParser;
 pub use stub::StubParser;
 pub use xlsx_parser::XlsxParser;
@@ -0,0 +1,702 @@
+use async_trait::async_trait;
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::io::{Cursor, Read};
+use std::path::Path;
+
+use roxmltree::{Document, Node};
+use time::format_description::well_known::Iso8601;
+use time::{OffsetDateTime, PrimitiveDateTime};
+
+use super::metadata::{DocumentInfo, non_empty};
+use crate::domain::error::DomainError;
+use crate::domain::ir::{
+    DocumentBuilder, Inline, InlineStyle, ParsedBlock, ParsedSource, TableBlock, TableCell,
+    TableRow,
+};
+use crate::domain::parser::FileParserBackend;
+
+/// `OpenDocument` parser for text documents, spreadsheets and presentations
+/// (ODT, ODS, ODP), reading `content.xml` and `meta.xml` from the package
+pub struct OdfParser;
+
+/// Supported file extensions
+const SUPPORTED_EXTENSIONS: &[&str] = &["odt", "ods", "odp"];
+
+/// MIME type constants
+const MIME_TYPE_ODT: &str = "application/vnd.oasis.opendocument.text";
+const MIME_TYPE_ODS: &str = "application/vnd.oasis.opendocument.spreadsheet";
+const MIME_TYPE_ODP: &str = "application/vnd.oasis.opendocument.presentation";
+
+/// Upper bound on the decompressed size of a single package entry
+const MAX_ENTRY_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Upper bound on how many times a repeated row or cell is expanded
+const MAX_REPEAT: usize = 1024;
+
+/// Upper bound on the table cells produced for the whole document. Row and column
+/// repeats multiply (and tables nest), so `MAX_REPEAT` alone does not bound the
+/// output; tables are truncated once the budget runs out.
+const MAX_CELLS: usize = 256 * 1024;
+
+const NS_OFFICE: &str = "urn:oasis:names:tc:opendocument:xmlns:office:1.0";
+const NS_STYLE: &str = "urn:oasis:names:tc:opendocument:xmlns:style:1.0";
+const NS_TEXT: &str = "urn:oasis:names:tc:opendocument:xmlns:text:1.0";
+const NS_TABLE: &str = "urn:oasis:names:tc:opendocument:xmlns:table:1.0";
+const NS_DRAW: &str = "urn:oasis:names:tc:opendocument:xmlns:drawing:1.0";
+const NS_PRESENTATION: &str = "urn:oasis:names:tc:opendocument:xmlns:presentation:1.0";
+const NS_FO: &str = "urn:oasis:names:tc:opendocument:xmlns:xsl-fo-compatible:1.0";
+const NS_META: &str = "urn:oasis:names:tc:opendocument:xmlns:meta:1.0";
+const NS_DC: &str = "http://purl.org/dc/elements/1.1/";
+const NS_XLINK: &str = "http://www.w3.org/1999/xlink";
+
+impl OdfParser {
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for OdfParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl FileParserBackend for OdfParser {
+    fn id(&self) -> &'static str {
+        "odf"
+    }
+
+    fn supported_extensions(&self) -> &'static [&'static str] {
+        SUPPORTED_EXTENSIONS
+    }
+
+    async fn parse_local_path(
+        &self,
+        path: &Path,
+    ) -> Result<crate::domain::ir::ParsedDocument, DomainError> {
+        let bytes = tokio::fs::read(path)
+            .await
+            .map_err(|e| DomainError::io_error(format!("Failed to read file: {e}")))?;
+
+        let odf = tokio::task::spawn_blocking(move || parse_odf(&bytes))
+            .await
+            .map_err(|e| DomainError::parse_error(format!("Task join error: {e}")))??;
+
+        let builder = DocumentBuilder::new(ParsedSource::LocalPath(path.display().to_string()));
+        let filename = path.file_name().and_then(|s| s.to_str());
+        Ok(odf.into_document(builder, filename))
+    }
+
+    async fn parse_bytes(
+        &self,
+        filename_hint: Option<&str>,
+        _content_type: Option<&str>,
+        bytes: bytes::Bytes,
+    ) -> Result<crate::domain::ir::ParsedDocument, DomainError> {
+        let odf = tokio::task::spawn_blocking(move || parse_odf(&bytes))
+            .await
+            .map_err(|e| DomainError::parse_error(format!("Task join error: {e}")))??;
+
+        let source = ParsedSource::Uploaded {
+            original_name: filename_hint.unwrap_or("unknown.odt").to_owned(),
+        };
+        Ok(odf.into_document(DocumentBuilder::new(source), filename_hint))
+    }
+}
+
+/// Blocks and document properties extracted from an `OpenDocument` package
+struct ParsedOdf {
+    content_type: &'static str,
+    blocks: Vec<ParsedBlock>,
+    info: DocumentInfo,
+}
+
+impl ParsedOdf {
+    fn into_document(
+        self,
+        builder: DocumentBuilder,
+        filename: Option<&str>,
+    ) -> crate::domain::ir::ParsedDocument {
+        let builder = builder.content_type(self.content_type).blocks(self.blocks);
+        self.info.apply(builder, filename).build()
+    }
+}
+
+fn parse_odf(bytes: &[u8]) -> Result<ParsedOdf, DomainError> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).map_err(|e| {
+        DomainError::parse_error(format!("Failed to open OpenDocument package: {e}"))
+    })?;
+
+    let content = read_entry(&mut archive, "content.xml")?
+        .ok_or_else(|| DomainError::parse_error("OpenDocument package has no content.xml"))?;
+    let styles = read_entry(&mut archive, "styles.xml")?;
+    let meta = read_entry(&mut archive, "meta.xml")?;
+
+    let content = parse_xml(&content)?;
+    let mut style_map = Styles::default();
+    if let Some(styles) = styles.as_deref() {
+        style_map.collect(&parse_xml(styles)?);
+    }
+    style_map.collect(&content);
+
+    let body = content
+        .descendants()
+        .find(|n| is(*n, NS_OFFICE, "body"))
+        .and_then(|body| body.children().find(Node::is_element))
+        .ok_or_else(|| DomainError::parse_error("OpenDocument content has no body"))?;
+
+    let converter = Converter {
+        styles: &style_map,
+        cells_left: Cell::new(MAX_CELLS),
+    };
+    let mut blocks = Vec::new();
+    let content_type = match body.tag_name().name() {
+        "spreadsheet" => {
+            converter.spreadsheet(body, &mut blocks);
+            MIME_TYPE_ODS
+        }
+        "presentation" | "drawing" => {
+            converter.presentation(body, &mut blocks);
+            MIME_TYPE_ODP
+        }
+        _ => {
+            converter.blocks(body, &mut blocks);
+            MIME_TYPE_ODT
+        }
+    };
+
+    let info = match meta.as_deref() {
+        Some(meta) => read_meta(&parse_xml(meta)?),
+        None => DocumentInfo::default(),
+    };
+
+    Ok(ParsedOdf {
+        content_type,
+        blocks,
+        info,
+    })
+}
+
+fn read_entry<R: Read + std::io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    name: &str,
+) -> Result<Option<String>, DomainError> {
+    let entry = match archive.by_name(name) {
+        Ok(entry) => entry,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => {
+            return Err(DomainError::parse_error(format!(
+                "Failed to read {name} from OpenDocument package: {e}"
+            )));
+        }
+    };
+
+    let mut text = String::new();
+    entry
+        .take(MAX_ENTRY_BYTES + 1)
+        .read_to_string(&mut text)
+        .map_err(|e| DomainError::parse_error(format!("Failed to read {name}: {e}")))?;
+    if u64::try_from(text.len()).unwrap_or(u64::MAX) > MAX_ENTRY_BYTES {
+        return Err(DomainError::parse_error(format!(
+            "{name} exceeds {MAX_ENTRY_BYTES} bytes when decompressed"
+        )));
+    }
+    Ok(Some(text))
+}
+
+fn parse_xml(text: &str) -> Result<Document<'_>, DomainError> {
+    Document::parse(text)
+        .map_err(|e| DomainError::parse_error(format!("Malformed OpenDocument XML: {e}")))
+}
+
+fn is(node: Node, namespace: &str, name: &str) -> bool {
+    node.is_element()
+        && node.tag_name().namespace() == Some(namespace)
+        && node.tag_name().name() == name
+}
+
+// ---------------------------------------------------------------------------
+// Styles
+// ---------------------------------------------------------------------------
+
+/// The parts of the style definitions that affect the IR
+#[derive(Default)]
+struct Styles {
+    /// Character formatting by style name
+    text: HashMap<String, InlineStyle>,
+    /// Per-level "is numbered" flags by list style name
+    lists: HashMap<String, HashMap<u8, bool>>,
+}
+
+impl Styles {
+    fn collect(&mut self, doc: &Document) {
+        for node in doc.descendants() {
+            if is(node, NS_STYLE, "style") {
+                let Some(name) = node.attribute((NS_STYLE, "name")) else {
+                    continue;
+                };
+                if let Some(props) = node
+                    .children()
+                    .find(|n| is(*n, NS_STYLE, "text-properties"))
+                {
+                    self.text.insert(name.to_owned(), text_style(props));
+                }
+            } else if is(node, NS_TEXT, "list-style") {
+                let Some(name) = node.attribute((NS_STYLE, "name")) else {
+                    continue;
+                };
+                let levels = node
+                    .children()
+                    .filter(Node::is_element)
+                    .filter_map(|level| {
+                        let number = level.attribute((NS_TEXT, "level"))?.parse().ok()?;
+                        Some((number, level.tag_name().name() == "list-level-style-number"))
+                    })
+                    .collect();
+                self.lists.insert(name.to_owned(), levels);
+            }
+        }
+    }
+
+    fn text_style(&self, name: Option<&str>) -> Option<&InlineStyle> {
+        name.and_then(|name| self.text.get(name))
+    }
+
+    /// Whether the list at the 0-based `level` is numbered
+    fn is_ordered(&self, list_style: Option<&str>, level: u8) -> bool {
+        list_style
+            .and_then(|name| self.lists.get(name))
+            .and_then(|levels| levels.get(&level.saturating_add(1)))
+            .copied()
+            .unwrap_or(false)
+    }
+}
+
+fn text_style(props: Node) -> InlineStyle {
+    let bold = props
+        .attribute((NS_FO, "font-weight"))
+        .is_some_and(|w| w == "bold" || w.parse::<u16>().is_ok_and(|w| w >= 600));
+    let italic = props
+        .attribute((NS_FO, "font-style"))
+        .is_some_and(|s| s == "italic" || s == "oblique");
+    let underline = props
+        .attribute((NS_STYLE, "text-underline-style"))
+        .is_some_and(|s| s != "none");
+    let strike = props
+        .attribute((NS_STYLE, "text-line-through-style"))
+        .is_some_and(|s| s != "none");
+    InlineStyle {
+        bold,
+        italic,
+        underline,
+        strike,
+        ..InlineStyle::default()
+    }
+}
+
+fn merge_style(base: &InlineStyle, overlay: Option<&InlineStyle>) -> InlineStyle {
+    let Some(overlay) = overlay else {
+        return base.clone();
+    };
+    InlineStyle {
+        bold: base.bold || overlay.bold,
+        italic: base.italic || overlay.italic,
+        underline: base.underline || overlay.underline,
+        strike: base.strike || overlay.strike,
+        code: base.code || overlay.code,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Content
+// ---------------------------------------------------------------------------
+
+struct Converter<'a> {
+    styles: &'a Styles,
+    /// Table cells that may still be produced, see `MAX_CELLS`
+    cells_left: Cell<usize>,
+}
+
+impl Converter<'_> {
+    /// Take up to `wanted` cells from the budget; returns how many were granted
+    fn take_cells(&self, wanted: usize) -> usize {
+        let left = self.cells_left.get();
+        let granted = wanted.min(left);
+        self.cells_left.set(left - granted);
+        if granted < wanted && left > 0 {
+            tracing::debug!(
+                limit = MAX_CELLS,
+                "Truncating OpenDocument tables at the cell limit"
+            );
+        }
+        granted
+    }
+
+    /// Convert the block-level children of `node`
+    fn blocks(&self, node: Node, out: &mut Vec<ParsedBlock>) {
+        for child in node.children().filter(Node::is_element) {
+            self.block(child, out);
+        }
+    }
+
+    fn block(&self, node: Node, out: &mut Vec<ParsedBlock>) {
+        let tag = node.tag_name();
+        match (tag.namespace(), tag.name()) {
+            (Some(NS_TEXT), "h") => {
+                let level = node
+                    .attribute((NS_TEXT, "outline-level"))
+                    .and_then(|l| l.parse::<u8>().ok())
+                    .unwrap_or(1)
+                    .clamp(1, 6);
+                let inlines = self.paragraph_inlines(node);
+                if !inlines.is_empty() {
+                    out.push(ParsedBlock::Heading {
+                        level,
+                        inlines,
+                        location: None,
+                    });
+                }
+            }
+            (Some(NS_TEXT), "p") => {
+                let inlines = self.paragraph_inlines(node);
+                if !inlines.is_empty() {
+                    out.push(ParsedBlock::Paragraph {
+                        inlines,
+                        location: None,
+                    });
+                }
+            }
+            (Some(NS_TEXT), "list") => self.list(node, 0, None, out),
+            (Some(NS_TABLE), "table") => {
+                if let Some(table) = self.table(node) {
+                    out.push(table);
+                }
+            }
+            (
+                Some(NS_TEXT),
+                "soft-page-break" | "sequence-decls" | "tracked-changes" | "variable-decls",
+            )
+            | (Some(NS_OFFICE), "forms" | "annotation")
+            | (Some(NS_TABLE), "table-columns" | "table-column" | "named-expressions")
+            | (Some(NS_PRESENTATION), "notes") => {}
+            // Sections, frames, text boxes and the like only wrap other blocks
+            _ => self.blocks(node, out),
+        }
+    }
+
+    fn spreadsheet(&self, body: Node, out: &mut Vec<ParsedBlock>) {
+        for sheet in body.children().filter(|n| is(*n, NS_TABLE, "table")) {
+            let name = sheet.attribute((NS_TABLE, "name")).unwrap_or("Sheet");
+            out.push(ParsedBlock::Heading {
+                level: 2,
+                inlines: vec![Inline::plain(name)],
+                location: None,
+            });
+            if let Some(table) = self.table(sheet) {
+                out.push(table);
+            }
+        }
+    }
+
+    fn presentation(&self, body: Node, out: &mut Vec<ParsedBlock>) {
+        let pages = body.children().filter(|n| is(*n, NS_DRAW, "page"));
+        for (index, page) in pages.enumerate() {
+            out.push(ParsedBlock::Heading {
+                level: 2,
+                inlines: vec![Inline::plain(format!("Slide {}", index + 1))],
+                location: None,
+            });
+            self.blocks(page, out);
+        }
+    }
+
+    fn list(&self, node: Node, level: u8, list_style: Option<&str>, out: &mut Vec<ParsedBlock>) {
+        let list_style = node.attribute((NS_TEXT, "style-name")).or(list_style);
+        let ordered = self.styles.is_ordered(list_style, level);
+
+        for item in node
+            .children()
+            .filter(|n| is(*n, NS_TEXT, "list-item") || is(*n, NS_TEXT, "list-header"))
+        {
+            for child in item.children().filter(Node::is_element) {
+                if is(child, NS_TEXT, "list") {
+                    self.list(child, level.saturating_add(1), list_style, out);
+                    continue;
+                }
+                let mut blocks = Vec::new();
+                if is(child, NS_TEXT, "h") || is(child, NS_TEXT, "p") {
+                    // Headings inside list items are numbered outlines; keep
+                    // them as list text
+                    let inlines = self.paragraph_inlines(child);
+                    if !inlines.is_empty() {
+                        blocks.push(ParsedBlock::Paragraph {
+                            inlines,
+                            location: None,
+                        });
+                    }
+                } else {
+                    self.block(child, &mut blocks);
+                }
+                if !blocks.is_empty() {
+                    out.push(ParsedBlock::ListItem {
+                        level,
+                        ordered,
+                        blocks,
+                    });
+                }
+            }
+        }
+    }
+
+    fn table(&self, node: Node) -> Option<ParsedBlock> {
+        let mut rows = Vec::new();
+        self.rows(node, false, &mut rows);
+
+        // Spreadsheets pad the used area with repeated empty rows and cells
+        for row in &mut rows {
+            while row.cells.last().is_some_and(|cell| cell.blocks.is_empty()) {
+                row.cells.pop();
+            }
+        }
+        while rows.last().is_some_and(|row| row.cells.is_empty()) {
+            rows.pop();
+        }
+        if rows.is_empty() {
+            return None;
+        }
+
+        let width = rows.iter().map(|row| row.cells.len()).max().unwrap_or(0);
+        for row in &mut rows {
+            row.cells
+                .resize_with(width, || TableCell { blocks: Vec::new() });
+        }
+        // Without explicit header rows, treat the first row as the header
+        if !rows.iter().any(|row| row.is_header) {
+            rows[0].is_header = true;
+        }
+
+        Some(ParsedBlock::Table(TableBlock {
+            rows,
+            location: None,
+        }))
+    }
+
+    fn rows(&self, node: Node, header: bool, rows: &mut Vec<TableRow>) {
+        for child in node.children().filter(Node::is_element) {
+            if self.cells_left.get() == 0 {
+                return;
+            }
+            if is(child, NS_TABLE, "table-header-rows") {
+                self.rows(child, true, rows);
+            } else if is(child, NS_TABLE, "table-rows") || is(child, NS_TABLE, "table-row-group") {
+                self.rows(child, header, rows);
+            } else if is(child, NS_TABLE, "table-row") {
+                let row = TableRow {
+                    is_header: header,
+                    cells: self.cells(child),
+                };
+                let repeat = repeat_count(child, "number-rows-repeated");
+                // Blank filler rows collapse to one; trailing ones are trimmed
+                let repeat = if row.cells.iter().all(|cell| cell.blocks.is_empty()) {
+                    1
+                } else {
+                    repeat
+                };
+                for _ in 1..repeat {
+                    // A repeat that does not fit the cell budget is dropped whole
+                    if self.take_cells(row.cells.len()) < row.cells.len() {
+                        break;
+                    }
+                    rows.push(row.clone());
+                }
+                rows.push(row);
+            }
+        }
+    }
+
+    fn cells(&self, row: Node) -> Vec<TableCell> {
+        let mut cells = Vec::new();
+        for node in row
+            .children()
+            .filter(|n| is(*n, NS_TABLE, "table-cell") || is(*n, NS_TABLE, "covered-table-cell"))
+        {
+            let repeat = self.take_cells(repeat_count(node, "number-columns-repeated"));
+            if repeat == 0 {
+                break;
+            }
+            let mut blocks = Vec::new();
+            self.blocks(node, &mut blocks);
+            let cell = TableCell { blocks };
+            for _ in 1..repeat {
+                cells.push(cell.clone());
+            }
+            cells.push(cell);
+        }
+        cells
+    }
+
+    /// Inline content of a paragraph or heading, with surrounding whitespace
+    /// trimmed; empty when the paragraph holds no text
+    fn paragraph_inlines(&self, node: Node) -> Vec<Inline> {
+        let base = self
+            .styles
+            .text_style(node.attribute((NS_TEXT, "style-name")))
+            .cloned()
+            .unwrap_or_default();
+        let mut inlines = Vec::new();
+        self.inlines(node, &base, &mut inlines);
+        Inline::trim(&mut inlines);
+        inlines
+    }
+
+    fn inlines(&self, node: Node, style: &InlineStyle, out: &mut Vec<Inline>) {
+        for child in node.children() {
+            if child.is_text() {
+                if let Some(text) = child.text() {
+                    push_text(out, &collapse_whitespace(text), style);
+                }
+                continue;
+            }
+            let tag = child.tag_name();
+            match (tag.namespace(), tag.name()) {
+                (Some(NS_TEXT), "s") => {
+                    let count = child
+                        .attribute((NS_TEXT, "c"))
+                        .and_then(|c| c.parse::<usize>().ok())
+                        .unwrap_or(1)
+                        .min(MAX_REPEAT);
+                    push_text(out, &" ".repeat(count), style);
+                }
+                (Some(NS_TEXT), "tab") => push_text(out, "\t", style),
+                (Some(NS_TEXT), "line-break") => push_text(out, "\n", style),
+                (Some(NS_TEXT), "span") => {
+                    let style = merge_style(
+                        style,
+                        self.styles
+                            .text_style(child.attribute((NS_TEXT, "style-name"))),
+                    );
+                    self.inlines(child, &style, out);
+                }
+                (Some(NS_TEXT), "a") => {
+                    let mut text = Vec::new();
+                    self.inlines(child, style, &mut text);
+                    let text = Inline::plain_text(&text);
+                    match child.attribute((NS_XLINK, "href")) {
+                        Some(target) if !text.trim().is_empty() => out.push(Inline::Link {
+                            text,
+                            target: target.to_owned(),
+                            style: style.clone(),
+                        }),
+                        _ => push_text(out, &text, style),
+                    }
+                }
+                // Notes, comments and anchored frames are not part of the
+                // running text
+                (Some(NS_TEXT), "note" | "bookmark" | "bookmark-start" | "bookmark-end")
+                | (Some(NS_OFFICE), "annotation" | "annotation-end")
+                | (Some(NS_DRAW), _) => {}
+                _ => self.inlines(child, style, out),
+            }
+        }
+    }
+}
+
+fn repeat_count(node: Node, attribute: &str) -> usize {
+    node.attribute((NS_TABLE, attribute))
+        .and_then(|n| n.parse::<usize>().ok())
+        .unwrap_or(1)
+        .clamp(1, MAX_REPEAT)
+}
+
+/// ODF treats runs of whitespace in text nodes as a single space; explicit
+/// spaces, tabs and breaks are elements
+fn collapse_whitespace(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut last_space = false;
+    for ch in text.chars() {
+        if ch.is_whitespace() {
+            if !last_space {
+                out.push(' ');
+            }
+            last_space = true;
+        } else {
+            out.push(ch);
+            last_space = false;
+        }
+    }
+    out
+}
+
+fn push_text(out: &mut Vec<Inline>, text: &str, style: &InlineStyle) {
+    if text.is_empty() {
+        return;
+    }
+    if let Some(Inline::Text {
+        text: last,
+        style: last_style,
+    }) = out.last_mut()
+        && last_style == style
+    {
+        last.push_str(text);
+        return;
+    }
+    out.push(Inline::styled(text, style.clone()));
+}
+
+// ---------------------------------------------------------------------------
+// Metadata
+// ---------------------------------------------------------------------------
+
+fn read_meta(doc: &Document) -> DocumentInfo {
+    let text = |namespace: &str, name: &str| {
+        doc.descendants()
+            .find(|n| is(*n, namespace, name))
+            .and_then(|n| n.text())
+            .and_then(|t| non_empty(t.to_owned()))
+    };
+
+    DocumentInfo {
+        title: text(NS_DC, "title"),
+        author: text(NS_DC, "creator").or_else(|| text(NS_META, "initial-creator")),
+        language: text(NS_DC, "language"),
+        created_at: text(NS_META, "creation-date").and_then(|d| parse_odf_date(&d)),
+        modified_at: text(NS_DC, "date").and_then(|d| parse_odf_date(&d)),
+    }
+}
+
+/// Parse an ISO 8601 date-time; values without an offset are taken as UTC
+fn parse_odf_date(value: &str) -> Option<OffsetDateTime> {
+    OffsetDateTime::parse(value, &Iso8601::DEFAULT)
+        .or_else(|_| {
+            PrimitiveDateTime::parse(value, &Iso8601::DEFAULT).map(PrimitiveDateTime::assume_utc)
+        })
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_odf_date() {
+        let date = parse_odf_date("2024-03-15T09:30:00.123456789").unwrap();
+        assert_eq!(
+            (date.year(), u8::from(date.month()), date.day()),
+            (2024, 3, 15)
+        );
+        assert_eq!(date.hour(), 9);
+
+        let date = parse_odf_date("2024-03-15T09:30:00+02:00").unwrap();
+        assert_eq!(date.offset().whole_hours(), 2);
+
+        assert!(parse_odf_date("yesterday").is_none());
+    }
+
+    #[test]
+    fn test_collapse_whitespace() {
+        assert_eq!(collapse_whitespace("a \n\t b"), "a b");
+    }
+}
@@ -1,13 +1,18 @@
 use async_trait::async_trait;
-use std::io::Write;
 use std::path::Path;
 
+use pdf_extract::{Document, MediaBox, OutputDev, OutputError, Transform};
+use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
 use crate::domain::error::DomainError;
-use crate::domain::ir::{DocumentBuilder, Inline, ParsedBlock, ParsedSource};
+use crate::domain::ir::{
+    BoundingBox, DocumentBuilder, Inline, ParsedBlock, ParsedSource, SourceLocation, TableBlock,
+    TableCell, TableRow,
+};
 use crate::domain::parser::FileParserBackend;
 
-/// PDF parser that extracts text from PDF files
-/// TODO: Migrate to ferrules when it's available as a library crate
+/// PDF parser that reconstructs headings, paragraphs and tables from the
+/// positions and sizes of the glyphs on each page
 pub struct PdfParser;
 
 impl PdfParser {
@@ -39,19 +44,17 @@ impl FileParserBackend for PdfParser {
     ) -> Result<crate::domain::ir::ParsedDocument, DomainError> {
         let path_buf = path.to_path_buf();
 
-        let blocks = tokio::task::spawn_blocking(move || parse_pdf_from_path(&path_buf))
-            .await
-            .map_err(|e| DomainError::parse_error(format!("Task join error: {e}")))??;
-
-        let mut builder = DocumentBuilder::new(ParsedSource::LocalPath(path.display().to_string()))
-            .content_type("application/pdf")
-            .blocks(blocks);
+        let pdf = tokio::task::spawn_blocking(move || {
+            let doc = Document::load(&path_buf)
+                .map_err(|e| DomainError::parse_error(format!("Failed to load PDF: {e}")))?;
+            parse_pdf(doc)
+        })
+        .await
+        .map_err(|e| DomainError::parse_error(format!("Task join error: {e}")))??;
 
-        if let Some(filename) = path.file_name().and_then(|s| s.to_str()) {
-            builder = builder.title(filename).original_filename(filename);
-        }
-
-        Ok(builder.build())
+        let builder = DocumentBuilder::new(ParsedSource::LocalPath(path.display().to_string()));
+        let filename = path.file_name().and_then(|s| s.to_str());
+        Ok(pdf.into_document(builder, filename))
     }
 
     async fn parse_bytes(
@@ -60,70 +63,704 @@ impl FileParserBackend for PdfParser {
         _content_type: Option<&str>,
         bytes: bytes::Bytes,
     ) -> Result<crate::domain::ir::ParsedDocument, DomainError> {
-        let blocks = tokio::task::spawn_blocking(move || parse_pdf_bytes(&bytes))
-            .await
-            .map_err(|e| DomainError::parse_error(format!("Task join error: {e}")))??;
+        let pdf = tokio::task::spawn_blocking(move || {
+            let doc = Document::load_mem(&bytes)
+                .map_err(|e| DomainError::parse_error(format!("Failed to load PDF: {e}")))?;
+            parse_pdf(doc)
+        })
+        .await
+        .map_err(|e| DomainError::parse_error(format!("Task join error: {e}")))??;
 
         let source = ParsedSource::Uploaded {
             original_name: filename_hint.unwrap_or("unknown.pdf").to_owned(),
         };
+        Ok(pdf.into_document(DocumentBuilder::new(source), filename_hint))
+    }
+}
 
-        let mut builder = DocumentBuilder::new(source)
-            .content_type("application/pdf")
-            .blocks(blocks);
+/// Blocks and document information extracted from a PDF
+struct ParsedPdf {
+    blocks: Vec<ParsedBlock>,
+    info: PdfInfo,
+}
 
-        if let Some(filename) = filename_hint {
-            builder = builder.title(filename).original_filename(filename);
+impl ParsedPdf {
+    fn into_document(
+        self,
+        mut builder: DocumentBuilder,
+        filename: Option<&str>,
+    ) -> crate::domain::ir::ParsedDocument {
+        builder = builder.content_type("application/pdf").blocks(self.blocks);
+        if let Some(filename) = filename {
+            builder = builder.original_filename(filename);
         }
-
-        Ok(builder.build())
+        // Prefer the title from the document information over the file name
+        if let Some(title) = self.info.title.as_deref().or(filename) {
+            builder = builder.title(title);
+        }
+        if let Some(author) = self.info.author {
+            builder = builder.author(author);
+        }
+        if let Some(created_at) = self.info.created_at {
+            builder = builder.created_at(created_at);
+        }
+        if let Some(modified_at) = self.info.modified_at {
+            builder = builder.modified_at(modified_at);
+        }
+        builder.build()
     }
 }
 
-fn parse_pdf_from_path(path: &Path) -> Result<Vec<ParsedBlock>, DomainError> {
-    // Use pdf-extract for now; TODO: migrate to ferrules when available
-    let text = pdf_extract::extract_text(path)
+fn parse_pdf(mut doc: Document) -> Result<ParsedPdf, DomainError> {
+    if doc.is_encrypted() {
+        // Documents with only an owner password open with an empty user password
+        doc.decrypt("")
+            .map_err(|e| DomainError::parse_error(format!("Failed to decrypt PDF: {e}")))?;
+    }
+
+    let mut collector = GlyphCollector::default();
+    pdf_extract::output_doc(&doc, &mut collector)
         .map_err(|e| DomainError::parse_error(format!("Failed to extract text from PDF: {e}")))?;
 
-    Ok(text_to_blocks(&text))
+    Ok(ParsedPdf {
+        blocks: layout_blocks(&collector.pages),
+        info: PdfInfo::from_document(&doc),
+    })
 }
 
-fn parse_pdf_bytes(bytes: &[u8]) -> Result<Vec<ParsedBlock>, DomainError> {
-    // Create a temporary file for pdf-extract (it requires a path)
-    let mut temp_file = tempfile::NamedTempFile::new()
-        .map_err(|e| DomainError::io_error(format!("Failed to create temp file: {e}")))?;
+// ---------------------------------------------------------------------------
+// Document information
+// ---------------------------------------------------------------------------
 
-    temp_file
-        .write_all(bytes)
-        .map_err(|e| DomainError::io_error(format!("Failed to write to temp file: {e}")))?;
+/// Entries of the document information dictionary
+#[derive(Debug, Default)]
+struct PdfInfo {
+    title: Option<String>,
+    author: Option<String>,
+    created_at: Option<OffsetDateTime>,
+    modified_at: Option<OffsetDateTime>,
+}
 
-    let text = pdf_extract::extract_text(temp_file.path())
-        .map_err(|e| DomainError::parse_error(format!("Failed to extract text from PDF: {e}")))?;
+impl PdfInfo {
+    fn from_document(doc: &Document) -> Self {
+        let Some(info) = doc
+            .trailer
+            .get(b"Info")
+            .and_then(|info| doc.dereference(info))
+            .and_then(|(_, info)| info.as_dict())
+            .ok()
+        else {
+            return Self::default();
+        };
+
+        let text = |key: &[u8]| {
+            info.get(key)
+                .and_then(|value| doc.dereference(value))
+                .and_then(|(_, value)| pdf_extract::decode_text_string(value))
+                .ok()
+                .map(|value| value.trim().to_owned())
+                .filter(|value| !value.is_empty())
+        };
+        let date = |key: &[u8]| text(key).as_deref().and_then(parse_pdf_date);
 
-    Ok(text_to_blocks(&text))
+        Self {
+            title: text(b"Title"),
+            author: text(b"Author"),
+            created_at: date(b"CreationDate"),
+            modified_at: date(b"ModDate"),
+        }
+    }
 }
 
-fn text_to_blocks(text: &str) -> Vec<ParsedBlock> {
-    // Split text into paragraphs and add page breaks where appropriate
-    let mut blocks = Vec::new();
+/// Parse a PDF date string, `D:YYYYMMDDHHmmSSOHH'mm'`, where everything after
+/// the year is optional
+fn parse_pdf_date(value: &str) -> Option<OffsetDateTime> {
+    let value = value.strip_prefix("D:").unwrap_or(value);
+    let digits = |range: std::ops::Range<usize>, default: u8| -> Option<u8> {
+        match value.get(range) {
+            Some(s) if s.bytes().all(|b| b.is_ascii_digit()) && !s.is_empty() => s.parse().ok(),
+            Some(_) => None,
+            None => Some(default),
+        }
+    };
 
-    // Split by form feed (page break) character or double newlines
-    for (idx, chunk) in text.split('\x0C').enumerate() {
-        if idx > 0 {
-            blocks.push(ParsedBlock::PageBreak);
+    let year: i32 = value.get(0..4)?.parse().ok()?;
+    let month = Month::try_from(digits(4..6, 1)?).ok()?;
+    let date = Date::from_calendar_date(year, month, digits(6..8, 1)?).ok()?;
+    let time = Time::from_hms(digits(8..10, 0)?, digits(10..12, 0)?, digits(12..14, 0)?).ok()?;
+
+    let offset = match value.get(14..) {
+        None | Some("" | "Z" | "Z00'00'" | "Z00'00") => UtcOffset::UTC,
+        Some(tz) => {
+            let sign: i8 = match tz.as_bytes().first() {
+                Some(b'+') => 1,
+                Some(b'-') => -1,
+                _ => return None,
+            };
+            let mut parts = tz[1..].split('\'').filter(|p| !p.is_empty());
+            let hours: i8 = parts.next()?.parse().ok()?;
+            let minutes: i8 = parts.next().map_or(Some(0), |m| m.parse().ok())?;
+            UtcOffset::from_hms(sign * hours, sign * minutes, 0).ok()?
         }
+    };
+
+    Some(PrimitiveDateTime::new(date, time).assume_offset(offset))
+}
+
+// ---------------------------------------------------------------------------
+// Glyph collection
+// ---------------------------------------------------------------------------
 
-        // Split each page into paragraphs
-        for para in chunk.split("\n\n") {
-            let trimmed = para.trim();
-            if !trimmed.is_empty() {
-                // TODO: improve PDF structure extraction (headings, columns, styles, etc)
-                blocks.push(ParsedBlock::Paragraph {
-                    inlines: vec![Inline::plain(trimmed)],
+/// A character drawn on a page, in points from the top-left corner
+#[derive(Debug, Clone)]
+struct Glyph {
+    text: String,
+    /// Left edge
+    x: f64,
+    /// Baseline
+    y: f64,
+    width: f64,
+    size: f64,
+}
+
+/// Glyphs of one page
+#[derive(Debug, Default)]
+struct Page {
+    number: u32,
+    glyphs: Vec<Glyph>,
+}
+
+/// Receives the characters of a document from pdf-extract
+#[derive(Default)]
+struct GlyphCollector {
+    pages: Vec<Page>,
+    page_top: f64,
+}
+
+impl OutputDev for GlyphCollector {
+    fn begin_page(
+        &mut self,
+        page_num: u32,
+        media_box: &MediaBox,
+        _art_box: Option<(f64, f64, f64, f64)>,
+    ) -> Result<(), OutputError> {
+        self.page_top = media_box.ury;
+        self.pages.push(Page {
+            number: page_num,
+            glyphs: Vec::new(),
+        });
+        Ok(())
+    }
+
+    fn end_page(&mut self) -> Result<(), OutputError> {
+        Ok(())
+    }
+
+    fn output_character(
+        &mut self,
+        trm: &Transform,
+        width: f64,
+        _spacing: f64,
+        font_size: f64,
+        char: &str,
+    ) -> Result<(), OutputError> {
+        // Side of the square with the area of the transformed em box
+        let size_x = font_size * (trm.m11 + trm.m21);
+        let size_y = font_size * (trm.m12 + trm.m22);
+        let size = (size_x * size_y).abs().sqrt();
+        if let Some(page) = self.pages.last_mut() {
+            page.glyphs.push(Glyph {
+                text: char.to_owned(),
+                x: trm.m31,
+                y: self.page_top - trm.m32,
+                width: width * size,
+                size,
+            });
+        }
+        Ok(())
+    }
+
+    fn begin_word(&mut self) -> Result<(), OutputError> {
+        Ok(())
+    }
+
+    fn end_word(&mut self) -> Result<(), OutputError> {
+        Ok(())
+    }
+
+    fn end_line(&mut self) -> Result<(), OutputError> {
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Layout analysis
+// ---------------------------------------------------------------------------
+
+/// Horizontal gap, in font sizes, above which words are separated by a space
+const WORD_GAP: f64 = 0.15;
+/// Horizontal gap, in font sizes, above which text on a line belongs to
+/// separate columns (table cells)
+const COLUMN_GAP: f64 = 1.5;
+/// Minimum ratio to the body font size for a line to be a heading
+const HEADING_RATIO: f64 = 1.15;
+/// Vertical distance between baselines, in font sizes, above which a new
+/// paragraph starts
+const PARAGRAPH_GAP: f64 = 1.6;
+/// Tolerance, in font sizes, for cells of a table column to be aligned
+const COLUMN_ALIGNMENT: f64 = 1.5;
+/// Longest text treated as a heading
+const MAX_HEADING_CHARS: usize = 200;
+
+/// Text on a line between two column gaps
+#[derive(Debug, Clone)]
+struct Segment {
+    text: String,
+    x0: f64,
+    x1: f64,
+}
+
+/// Text sharing a baseline
+#[derive(Debug, Clone)]
+struct Line {
+    segments: Vec<Segment>,
+    y: f64,
+    size: f64,
+}
+
+impl Line {
+    fn text(&self) -> String {
+        self.segments
+            .iter()
+            .map(|s| s.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn bbox(&self) -> BoundingBox {
+        BoundingBox {
+            x0: self.segments.first().map_or(0.0, |s| s.x0),
+            y0: self.y - self.size * 0.8,
+            x1: self.segments.last().map_or(0.0, |s| s.x1),
+            y1: self.y + self.size * 0.2,
+        }
+    }
+
+    fn same_baseline(&self, other: &Line) -> bool {
+        (self.y - other.y).abs() < self.size.min(other.size) * 0.3
+    }
+}
+
+/// Group the glyphs of a page into lines, in reading order
+fn build_lines(glyphs: &[Glyph]) -> Vec<Line> {
+    let mut lines: Vec<Line> = Vec::new();
+    let mut last_end = f64::NEG_INFINITY;
+
+    for glyph in glyphs {
+        let is_space = glyph.text.trim().is_empty();
+        let line = lines.last_mut().filter(|line| {
+            (line.y - glyph.y).abs() <= line.size.max(glyph.size) * 0.5
+                && glyph.x >= last_end - glyph.size * 0.5
+        });
+        let Some(line) = line else {
+            if !is_space {
+                lines.push(Line {
+                    segments: vec![Segment {
+                        text: glyph.text.clone(),
+                        x0: glyph.x,
+                        x1: glyph.x + glyph.width,
+                    }],
+                    y: glyph.y,
+                    size: glyph.size,
                 });
+                last_end = glyph.x + glyph.width;
+            }
+            continue;
+        };
+
+        if is_space {
+            continue;
+        }
+        let gap = glyph.x - last_end;
+        match line.segments.last_mut() {
+            Some(segment) if gap <= glyph.size * COLUMN_GAP => {
+                if gap > glyph.size * WORD_GAP && !segment.text.ends_with(' ') {
+                    segment.text.push(' ');
+                }
+                segment.text.push_str(&glyph.text);
+                segment.x1 = glyph.x + glyph.width;
             }
+            _ => line.segments.push(Segment {
+                text: glyph.text.clone(),
+                x0: glyph.x,
+                x1: glyph.x + glyph.width,
+            }),
         }
+        line.size = line.size.max(glyph.size);
+        last_end = glyph.x + glyph.width;
+    }
+
+    // Content streams do not have to draw text top to bottom; text drawn
+    // piecewise on the same baseline (such as table cells) is merged
+    lines.sort_by(|a, b| {
+        a.y.total_cmp(&b.y)
+            .then(a.segments[0].x0.total_cmp(&b.segments[0].x0))
+    });
+    let mut merged: Vec<Line> = Vec::with_capacity(lines.len());
+    for line in lines {
+        match merged.last_mut() {
+            Some(last) if last.same_baseline(&line) => {
+                last.segments.extend(line.segments);
+                last.size = last.size.max(line.size);
+            }
+            _ => merged.push(line),
+        }
+    }
+    for line in &mut merged {
+        line.segments.sort_by(|a, b| a.x0.total_cmp(&b.x0));
+        line.segments = join_close_segments(std::mem::take(&mut line.segments), line.size);
+        for segment in &mut line.segments {
+            segment.text = segment.text.trim().to_owned();
+        }
+    }
+    merged
+}
+
+fn join_close_segments(segments: Vec<Segment>, size: f64) -> Vec<Segment> {
+    let mut joined: Vec<Segment> = Vec::with_capacity(segments.len());
+    for segment in segments {
+        let gap = joined
+            .last()
+            .map_or(f64::INFINITY, |last| segment.x0 - last.x1);
+        match joined.last_mut() {
+            Some(last) if gap <= size * COLUMN_GAP => {
+                if gap > size * WORD_GAP {
+                    last.text.push(' ');
+                }
+                last.text.push_str(&segment.text);
+                last.x1 = last.x1.max(segment.x1);
+            }
+            _ => joined.push(segment),
+        }
+    }
+    joined
+}
+
+/// Font size of most of the text, weighted by number of characters
+fn body_size(pages: &[Page]) -> f64 {
+    let mut sizes: Vec<(f64, usize)> = Vec::new();
+    for glyph in pages.iter().flat_map(|p| &p.glyphs) {
+        if glyph.text.trim().is_empty() {
+            continue;
+        }
+        match sizes
+            .iter_mut()
+            .find(|(size, _)| (size - glyph.size).abs() < 0.25)
+        {
+            Some((_, count)) => *count += 1,
+            None => sizes.push((glyph.size, 1)),
+        }
+    }
+    sizes
+        .into_iter()
+        .max_by_key(|(_, count)| *count)
+        .map_or(0.0, |(size, _)| size)
+}
+
+/// Heading level for each distinct heading font size, largest first
+struct HeadingLevels {
+    sizes: Vec<f64>,
+}
+
+impl HeadingLevels {
+    fn new(lines: &[&Line], body_size: f64) -> Self {
+        let mut sizes: Vec<f64> = Vec::new();
+        for line in lines {
+            if is_heading_candidate(line, body_size)
+                && !sizes.iter().any(|s| (s - line.size).abs() < 0.5)
+            {
+                sizes.push(line.size);
+            }
+        }
+        sizes.sort_by(|a, b| b.total_cmp(a));
+        Self { sizes }
+    }
+
+    fn level(&self, line: &Line) -> u8 {
+        let index = self
+            .sizes
+            .iter()
+            .position(|s| (s - line.size).abs() < 0.5)
+            .unwrap_or(self.sizes.len());
+        u8::try_from(index + 1).unwrap_or(6).min(6)
+    }
+}
+
+fn is_heading_candidate(line: &Line, body_size: f64) -> bool {
+    body_size > 0.0
+        && line.size >= body_size * HEADING_RATIO
+        && line.segments.len() == 1
+        && line.text().chars().count() <= MAX_HEADING_CHARS
+}
+
+/// Whether `line` continues the table whose first row is `first`
+fn same_columns(first: &Line, line: &Line) -> bool {
+    let tolerance = first.size.max(line.size) * COLUMN_ALIGNMENT;
+    first.segments.len() == line.segments.len()
+        && first
+            .segments
+            .iter()
+            .zip(&line.segments)
+            .all(|(a, b)| (a.x0 - b.x0).abs() <= tolerance || (a.x1 - b.x1).abs() <= tolerance)
+}
+
+/// Number of lines from the start of `lines` that form a table
+fn table_len(lines: &[Line]) -> usize {
+    let Some(first) = lines.first().filter(|l| l.segments.len() >= 2) else {
+        return 0;
+    };
+    let len = lines
+        .iter()
+        .take_while(|line| same_columns(first, line))
+        .count();
+    if len >= 2 { len } else { 0 }
+}
+
+fn union(boxes: impl IntoIterator<Item = BoundingBox>) -> Option<BoundingBox> {
+    let round = |v: f64| (v * 100.0).round() / 100.0;
+    boxes
+        .into_iter()
+        .reduce(|a, b| BoundingBox {
+            x0: a.x0.min(b.x0),
+            y0: a.y0.min(b.y0),
+            x1: a.x1.max(b.x1),
+            y1: a.y1.max(b.y1),
+        })
+        .map(|b| BoundingBox {
+            x0: round(b.x0),
+            y0: round(b.y0),
+            x1: round(b.x1),
+            y1: round(b.y1),
+        })
+}
+
+fn location(page: u32, lines: &[Line]) -> SourceLocation {
+    SourceLocation {
+        page,
+        bbox: union(lines.iter().map(Line::bbox)),
+    }
+}
+
+/// Join wrapped lines of a paragraph, undoing hyphenation at line ends
+fn join_lines(lines: &[Line]) -> String {
+    let mut text = String::new();
+    for line in lines {
+        let line_text = line.text();
+        let continues_word = text.ends_with('-')
+            && text[..text.len() - 1].ends_with(char::is_alphabetic)
+            && line_text.starts_with(char::is_lowercase);
+        if continues_word {
+            text.pop();
+        } else if !text.is_empty() {
+            text.push(' ');
+        }
+        text.push_str(&line_text);
+    }
+    text
+}
+
+fn table_block(page: u32, lines: &[Line]) -> ParsedBlock {
+    let rows = lines
+        .iter()
+        .enumerate()
+        .map(|(idx, line)| TableRow {
+            is_header: idx == 0,
+            cells: line
+                .segments
+                .iter()
+                .map(|segment| TableCell {
+                    blocks: vec![ParsedBlock::Paragraph {
+                        inlines: vec![Inline::plain(segment.text.clone())],
+                        location: None,
+                    }],
+                })
+                .collect(),
+        })
+        .collect();
+    ParsedBlock::Table(TableBlock {
+        rows,
+        location: Some(location(page, lines)),
+    })
+}
+
+/// Turn the glyphs of each page into headings, paragraphs and tables, with
+/// a page break between pages
+fn layout_blocks(pages: &[Page]) -> Vec<ParsedBlock> {
+    let body_size = body_size(pages);
+    let page_lines: Vec<(u32, Vec<Line>)> = pages
+        .iter()
+        .map(|page| (page.number, build_lines(&page.glyphs)))
+        .collect();
+    let all_lines: Vec<&Line> = page_lines.iter().flat_map(|(_, lines)| lines).collect();
+    let levels = HeadingLevels::new(&all_lines, body_size);
+
+    let mut blocks = Vec::new();
+    for (page, lines) in &page_lines {
+        let page_blocks = page_blocks(*page, lines, body_size, &levels);
+        // Page breaks only separate pages with content
+        if page_blocks.is_empty() {
+            continue;
+        }
+        if !blocks.is_empty() {
+            blocks.push(ParsedBlock::PageBreak);
+        }
+        blocks.extend(page_blocks);
+    }
+
+    blocks
+}
+
+/// Tables, headings and paragraphs of one page, from its lines
+fn page_blocks(
+    page: u32,
+    lines: &[Line],
+    body_size: f64,
+    levels: &HeadingLevels,
+) -> Vec<ParsedBlock> {
+    let mut blocks = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let table = table_len(&lines[i..]);
+        if table > 0 {
+            blocks.push(table_block(page, &lines[i..i + table]));
+            i += table;
+            continue;
+        }
+
+        let first = &lines[i];
+        let heading = is_heading_candidate(first, body_size);
+        // Extend the block with following lines of the same kind
+        let mut end = i + 1;
+        while let Some(next) = lines.get(end) {
+            let prev = &lines[end - 1];
+            let close = next.y - prev.y <= prev.size.max(next.size) * PARAGRAPH_GAP;
+            let same_kind = if heading {
+                is_heading_candidate(next, body_size) && levels.level(next) == levels.level(first)
+            } else {
+                !is_heading_candidate(next, body_size)
+                    && (next.size - first.size).abs() < first.size * (HEADING_RATIO - 1.0)
+            };
+            if !close || !same_kind || table_len(&lines[end..]) > 0 {
+                break;
+            }
+            end += 1;
+        }
+
+        let group = &lines[i..end];
+        let inlines = vec![Inline::plain(join_lines(group))];
+        let location = Some(location(page, group));
+        blocks.push(if heading {
+            ParsedBlock::Heading {
+                level: levels.level(first),
+                inlines,
+                location,
+            }
+        } else {
+            ParsedBlock::Paragraph { inlines, location }
+        });
+        i = end;
     }
 
     blocks
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glyphs(text: &str, x: f64, y: f64, size: f64) -> Vec<Glyph> {
+        text.chars()
+            .enumerate()
+            .map(|(i, c)| Glyph {
+                text: c.to_string(),
+                x: x + f64::from(u32::try_from(i).unwrap()) * size * 0.5,
+                y,
+                width: size * 0.5,
+                size,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_pdf_date() {
+        let date = parse_pdf_date("D:20240315093000+02'00'").unwrap();
+        assert_eq!(date.to_string(), "2024-03-15 9:30:00.0 +02:00:00");
+        let date = parse_pdf_date("D:2023").unwrap();
+        assert_eq!(date.to_string(), "2023-01-01 0:00:00.0 +00:00:00");
+        assert!(parse_pdf_date("yesterday").is_none());
+        assert!(parse_pdf_date("D:20241340").is_none());
+    }
+
+    #[test]
+    fn test_layout_headings_paragraphs_and_tables() {
+        let mut page = Vec::new();
+        page.extend(glyphs("Title", 72.0, 80.0, 24.0));
+        page.extend(glyphs("First line of the", 72.0, 120.0, 10.0));
+        page.extend(glyphs("paragraph.", 72.0, 132.0, 10.0));
+        // Table cells drawn column by column
+        for (col, x) in [
+            (["Name", "Ada", "Alan"], 72.0),
+            (["Year", "1815", "1912"], 200.0),
+        ] {
+            for (row, text) in col.iter().enumerate() {
+                page.extend(glyphs(
+                    text,
+                    x,
+                    170.0 + 14.0 * f64::from(u8::try_from(row).unwrap()),
+                    10.0,
+                ));
+            }
+        }
+        page.extend(glyphs("After the table.", 72.0, 240.0, 10.0));
+        let pages = vec![Page {
+            number: 1,
+            glyphs: page,
+        }];
+
+        let blocks = layout_blocks(&pages);
+        assert_eq!(blocks.len(), 4, "{blocks:#?}");
+        assert!(matches!(&blocks[0], ParsedBlock::Heading { level: 1, .. }));
+        let ParsedBlock::Paragraph { inlines, location } = &blocks[1] else {
+            panic!("expected paragraph: {:?}", blocks[1]);
+        };
+        assert_eq!(inlines, &[Inline::plain("First line of the paragraph.")]);
+        let location = location.unwrap();
+        assert_eq!(location.page, 1);
+        assert!((location.bbox.unwrap().x0 - 72.0).abs() < f64::EPSILON);
+        let ParsedBlock::Table(table) = &blocks[2] else {
+            panic!("expected table: {:?}", blocks[2]);
+        };
+        assert_eq!(table.rows.len(), 3);
+        assert!(table.rows[0].is_header);
+        assert_eq!(table.rows[2].cells.len(), 2);
+        assert!(matches!(&blocks[3], ParsedBlock::Paragraph { .. }));
+    }
+
+    #[test]
+    fn test_join_lines_undoes_hyphenation() {
+        let line = |text: &str| Line {
+            segments: vec![Segment {
+                text: text.to_owned(),
+                x0: 0.0,
+                x1: 1.0,
+            }],
+            y: 0.0,
+            size: 10.0,
+        };
+        assert_eq!(
+            join_lines(&[line("a hyphen-"), line("ated word"), line("Next")]),
+            "a hyphenated word Next"
+        );
+        assert_eq!(join_lines(&[line("1990-"), line("now")]), "1990- now");
+    }
+}
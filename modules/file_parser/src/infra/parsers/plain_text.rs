@@ -83,11 +83,12 @@ impl FileParserBackend for PlainTextParser {
 }
 
 /// Convert plain text into blocks by splitting on double newlines
-fn text_to_blocks(text: &str) -> Vec<ParsedBlock> {
+pub(crate) fn text_to_blocks(text: &str) -> Vec<ParsedBlock> {
     text.split("\n\n")
         .filter(|para| !para.trim().is_empty())
         .map(|para| ParsedBlock::Paragraph {
             inlines: vec![Inline::plain(para.trim())],
+            location: None,
         })
         .collect()
 }
@@ -173,6 +173,7 @@ fn extract_blocks_from_slides(slides: &[pptx_to_md::Slide]) -> Vec<ParsedBlock>
         blocks.push(ParsedBlock::Heading {
             level: 2,
             inlines: vec![Inline::plain(format!("Slide {}", slide_idx + 1))],
+            location: None,
         });
 
         // Process slide elements
@@ -189,6 +190,7 @@ fn extract_blocks_from_slides(slides: &[pptx_to_md::Slide]) -> Vec<ParsedBlock>
                     if !trimmed.is_empty() {
                         blocks.push(ParsedBlock::Paragraph {
                             inlines: vec![Inline::plain(trimmed)],
+                            location: None,
                         });
                     }
                 }
@@ -236,6 +238,7 @@ fn convert_pptx_table(table: &TableElement) -> Option<ParsedBlock> {
             cells.push(TableCell {
                 blocks: vec![ParsedBlock::Paragraph {
                     inlines: vec![Inline::plain(cell_text.trim())],
+                    location: None,
                 }],
             });
         }
@@ -243,7 +246,10 @@ fn convert_pptx_table(table: &TableElement) -> Option<ParsedBlock> {
         rows.push(TableRow { is_header, cells });
     }
 
-    Some(ParsedBlock::Table(TableBlock { rows }))
+    Some(ParsedBlock::Table(TableBlock {
+        rows,
+        location: None,
+    }))
 }
 
 fn convert_pptx_list(list: &ListElement) -> Vec<ParsedBlock> {
@@ -266,6 +272,7 @@ fn convert_pptx_list(list: &ListElement) -> Vec<ParsedBlock> {
                 ordered: item.is_ordered,
                 blocks: vec![ParsedBlock::Paragraph {
                     inlines: vec![Inline::plain(trimmed)],
+                    location: None,
                 }],
             });
         }
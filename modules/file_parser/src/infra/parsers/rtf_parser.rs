@@ -0,0 +1,837 @@
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::path::Path;
+
+use encoding_rs::Encoding;
+use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time};
+
+use super::metadata::{DocumentInfo, non_empty};
+use crate::domain::error::DomainError;
+use crate::domain::ir::{
+    DocumentBuilder, Inline, InlineStyle, ParsedBlock, ParsedSource, TableBlock, TableCell,
+    TableRow,
+};
+use crate::domain::parser::FileParserBackend;
+
+/// RTF parser producing paragraphs, headings, lists and tables
+pub struct RtfParser;
+
+/// MIME type constant
+const MIME_TYPE_RTF: &str = "application/rtf";
+
+/// Deeper nesting than this is treated as a malformed document
+const MAX_GROUP_DEPTH: usize = 1024;
+
+impl RtfParser {
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for RtfParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl FileParserBackend for RtfParser {
+    fn id(&self) -> &'static str {
+        "rtf"
+    }
+
+    fn supported_extensions(&self) -> &'static [&'static str] {
+        &["rtf"]
+    }
+
+    async fn parse_local_path(
+        &self,
+        path: &Path,
+    ) -> Result<crate::domain::ir::ParsedDocument, DomainError> {
+        let bytes = tokio::fs::read(path)
+            .await
+            .map_err(|e| DomainError::io_error(format!("Failed to read file: {e}")))?;
+
+        let (blocks, info) = tokio::task::spawn_blocking(move || parse_rtf(&bytes))
+            .await
+            .map_err(|e| DomainError::parse_error(format!("Task join error: {e}")))??;
+
+        let builder = DocumentBuilder::new(ParsedSource::LocalPath(path.display().to_string()))
+            .content_type(MIME_TYPE_RTF)
+            .blocks(blocks);
+        let filename = path.file_name().and_then(|s| s.to_str());
+        Ok(info.apply(builder, filename).build())
+    }
+
+    async fn parse_bytes(
+        &self,
+        filename_hint: Option<&str>,
+        _content_type: Option<&str>,
+        bytes: bytes::Bytes,
+    ) -> Result<crate::domain::ir::ParsedDocument, DomainError> {
+        let (blocks, info) = tokio::task::spawn_blocking(move || parse_rtf(&bytes))
+            .await
+            .map_err(|e| DomainError::parse_error(format!("Task join error: {e}")))??;
+
+        let source = ParsedSource::Uploaded {
+            original_name: filename_hint.unwrap_or("unknown.rtf").to_owned(),
+        };
+        let builder = DocumentBuilder::new(source)
+            .content_type(MIME_TYPE_RTF)
+            .blocks(blocks);
+        Ok(info.apply(builder, filename_hint).build())
+    }
+}
+
+/// Parse an RTF document into blocks and document properties
+pub(crate) fn parse_rtf(bytes: &[u8]) -> Result<(Vec<ParsedBlock>, DocumentInfo), DomainError> {
+    let start = bytes
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(bytes.len());
+    if !bytes[start..].starts_with(b"{\\rtf") {
+        return Err(DomainError::parse_error("Not an RTF document"));
+    }
+
+    let mut reader = RtfReader::new();
+    reader.run(&bytes[start..])?;
+    Ok(reader.finish())
+}
+
+/// Where the text of the current group goes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Destination {
+    Body,
+    Skip,
+    FontTable,
+    StyleSheet,
+    ListText,
+    FieldInstruction,
+    Info(InfoField),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InfoField {
+    Other,
+    Title,
+    Author,
+    Created,
+    Revised,
+}
+
+/// Formatting state scoped to a `{...}` group
+#[derive(Debug, Clone)]
+struct GroupState {
+    destination: Destination,
+    style: InlineStyle,
+    encoding: &'static Encoding,
+    /// Number of fallback characters following a `\uN`
+    unicode_skip: usize,
+    link: Option<String>,
+}
+
+/// Paragraph properties, reset by `\pard`
+#[derive(Debug, Default)]
+struct ParagraphState {
+    style: Option<i32>,
+    outline_level: Option<u8>,
+    list_level: Option<u8>,
+    in_table: bool,
+}
+
+/// A stylesheet entry being read
+#[derive(Debug, Default)]
+struct StyleEntry {
+    number: Option<i32>,
+    outline_level: Option<u8>,
+    name: String,
+}
+
+struct RtfReader {
+    stack: Vec<GroupState>,
+    ansi_encoding: &'static Encoding,
+    /// Undecoded text bytes of the current run
+    pending: Vec<u8>,
+    /// Fallback characters still to drop after a `\uN`
+    skip_chars: usize,
+    /// Set by `\*`: an unknown destination that follows is skipped
+    ignorable: bool,
+
+    fonts: HashMap<i32, &'static Encoding>,
+    font_entry: Option<i32>,
+    /// Heading level by paragraph style number
+    heading_styles: HashMap<i32, u8>,
+    style_entry: StyleEntry,
+
+    paragraph_state: ParagraphState,
+    paragraph: Vec<Inline>,
+    list_text: String,
+    field_instruction: String,
+    field_link: Option<String>,
+
+    blocks: Vec<ParsedBlock>,
+    cell_blocks: Vec<ParsedBlock>,
+    row_cells: Vec<TableCell>,
+    rows: Vec<TableRow>,
+
+    title: String,
+    author: String,
+    date_parts: [i32; 5],
+    info: DocumentInfo,
+}
+
+impl RtfReader {
+    fn new() -> Self {
+        Self {
+            stack: vec![GroupState {
+                destination: Destination::Body,
+                style: InlineStyle::default(),
+                encoding: encoding_rs::WINDOWS_1252,
+                unicode_skip: 1,
+                link: None,
+            }],
+            ansi_encoding: encoding_rs::WINDOWS_1252,
+            pending: Vec::new(),
+            skip_chars: 0,
+            ignorable: false,
+            fonts: HashMap::new(),
+            font_entry: None,
+            heading_styles: HashMap::new(),
+            style_entry: StyleEntry::default(),
+            paragraph_state: ParagraphState::default(),
+            paragraph: Vec::new(),
+            list_text: String::new(),
+            field_instruction: String::new(),
+            field_link: None,
+            blocks: Vec::new(),
+            cell_blocks: Vec::new(),
+            row_cells: Vec::new(),
+            rows: Vec::new(),
+            title: String::new(),
+            author: String::new(),
+            date_parts: [0; 5],
+            info: DocumentInfo::default(),
+        }
+    }
+
+    fn state(&self) -> &GroupState {
+        // The root state is never popped
+        &self.stack[self.stack.len() - 1]
+    }
+
+    fn state_mut(&mut self) -> &mut GroupState {
+        let last = self.stack.len() - 1;
+        &mut self.stack[last]
+    }
+
+    fn run(&mut self, bytes: &[u8]) -> Result<(), DomainError> {
+        let mut i = 0;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'{' => {
+                    self.flush();
+                    if self.stack.len() >= MAX_GROUP_DEPTH {
+                        return Err(DomainError::parse_error("RTF groups are nested too deeply"));
+                    }
+                    let state = self.state().clone();
+                    self.stack.push(state);
+                    self.skip_chars = 0;
+                    i += 1;
+                }
+                b'}' => {
+                    self.end_group();
+                    if self.stack.len() == 1 {
+                        // Closing brace of the document; anything after is junk
+                        break;
+                    }
+                    i += 1;
+                }
+                b'\\' => i = self.control(bytes, i + 1),
+                b'\r' | b'\n' => i += 1,
+                byte => {
+                    self.text_byte(byte);
+                    i += 1;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(mut self) -> (Vec<ParsedBlock>, DocumentInfo) {
+        self.flush();
+        self.end_paragraph();
+        self.flush_table();
+        self.info.title = non_empty(std::mem::take(&mut self.title));
+        self.info.author = non_empty(std::mem::take(&mut self.author));
+        (self.blocks, self.info)
+    }
+
+    fn text_byte(&mut self, byte: u8) {
+        if self.skip_chars > 0 {
+            self.skip_chars -= 1;
+        } else {
+            self.pending.push(byte);
+        }
+    }
+
+    fn end_group(&mut self) {
+        self.flush();
+        self.skip_chars = 0;
+        self.ignorable = false;
+        if self.stack.len() == 1 {
+            return;
+        }
+        let Some(popped) = self.stack.pop() else {
+            return;
+        };
+        if popped.destination == self.state().destination {
+            return;
+        }
+        match popped.destination {
+            Destination::Info(InfoField::Created) => {
+                self.info.created_at = rtf_date(self.date_parts);
+            }
+            Destination::Info(InfoField::Revised) => {
+                self.info.modified_at = rtf_date(self.date_parts);
+            }
+            Destination::FieldInstruction => {
+                self.field_link = hyperlink_target(&std::mem::take(&mut self.field_instruction));
+            }
+            Destination::StyleSheet => self.end_style_entry(),
+            _ => {}
+        }
+    }
+
+    /// Handle the control word or symbol starting at `i` (just past the
+    /// backslash); returns the index after it
+    fn control(&mut self, bytes: &[u8], mut i: usize) -> usize {
+        let Some(&first) = bytes.get(i) else {
+            return i;
+        };
+
+        if !first.is_ascii_alphabetic() {
+            i += 1;
+            match first {
+                b'\'' => {
+                    let hex = bytes
+                        .get(i..i + 2)
+                        .and_then(|h| std::str::from_utf8(h).ok());
+                    if let Some(byte) = hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                        self.text_byte(byte);
+                        return i + 2;
+                    }
+                }
+                b'\\' | b'{' | b'}' => self.text_byte(first),
+                b'*' => self.ignorable = true,
+                b'~' => self.emit_char('\u{a0}'),
+                b'_' => self.emit_char('-'),
+                b'\r' | b'\n' => self.word("par", None),
+                _ => {}
+            }
+            return i;
+        }
+
+        let name_start = i;
+        while bytes.get(i).is_some_and(u8::is_ascii_alphabetic) {
+            i += 1;
+        }
+        let name = std::str::from_utf8(&bytes[name_start..i]).unwrap_or_default();
+
+        let negative = bytes.get(i) == Some(&b'-');
+        let digits_start = if negative { i + 1 } else { i };
+        let mut end = digits_start;
+        while bytes.get(end).is_some_and(u8::is_ascii_digit) {
+            end += 1;
+        }
+        let param = if end > digits_start {
+            i = end;
+            let value = bytes[digits_start..end].iter().fold(0_i32, |acc, d| {
+                acc.saturating_mul(10).saturating_add(i32::from(d - b'0'))
+            });
+            Some(if negative { -value } else { value })
+        } else {
+            None
+        };
+        if bytes.get(i) == Some(&b' ') {
+            i += 1;
+        }
+
+        if name == "bin" {
+            // Raw binary data follows; it is never text
+            self.flush();
+            let len = usize::try_from(param.unwrap_or(0)).unwrap_or(0);
+            return i.saturating_add(len).min(bytes.len());
+        }
+        self.word(name, param);
+        i
+    }
+
+    fn word(&mut self, name: &str, param: Option<i32>) {
+        let ignorable = std::mem::take(&mut self.ignorable);
+        let destination = self.state().destination;
+        if destination == Destination::Skip {
+            return;
+        }
+        if name == "u" {
+            self.flush();
+            if let Some(param) = param {
+                // Values above 32767 are written as negative numbers
+                let code = if param < 0 { param + 65536 } else { param };
+                let ch = u32::try_from(code)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .unwrap_or('\u{fffd}');
+                self.emit_char(ch);
+            }
+            self.skip_chars = self.state().unicode_skip;
+            return;
+        }
+        self.flush();
+
+        if let Some(new) = Self::destination_for(name, destination) {
+            match new {
+                Destination::Info(InfoField::Created | InfoField::Revised) => {
+                    self.date_parts = [0; 5];
+                }
+                Destination::FieldInstruction => self.field_instruction.clear(),
+                _ => {}
+            }
+            if name == "fldrslt" {
+                let link = self.field_link.take();
+                self.state_mut().link = link;
+            }
+            self.state_mut().destination = new;
+            return;
+        }
+        if ignorable {
+            self.state_mut().destination = Destination::Skip;
+            return;
+        }
+
+        match destination {
+            Destination::FontTable => self.font_table_word(name, param),
+            Destination::StyleSheet => match name {
+                "s" => self.style_entry.number = param,
+                "outlinelevel" => self.style_entry.outline_level = outline_level(param),
+                _ => {}
+            },
+            Destination::Info(field) => {
+                let index = match name {
+                    "yr" => 0,
+                    "mo" => 1,
+                    "dy" => 2,
+                    "hr" => 3,
+                    "min" => 4,
+                    _ => return,
+                };
+                if matches!(field, InfoField::Created | InfoField::Revised) {
+                    self.date_parts[index] = param.unwrap_or(0);
+                }
+            }
+            Destination::Body | Destination::ListText | Destination::FieldInstruction => {
+                self.body_word(name, param);
+            }
+            Destination::Skip => {}
+        }
+    }
+
+    fn destination_for(name: &str, current: Destination) -> Option<Destination> {
+        let in_info = matches!(current, Destination::Info(_));
+        let destination = match name {
+            "fonttbl" => Destination::FontTable,
+            "stylesheet" => Destination::StyleSheet,
+            "info" => Destination::Info(InfoField::Other),
+            "title" if in_info => Destination::Info(InfoField::Title),
+            "author" if in_info => Destination::Info(InfoField::Author),
+            "creatim" if in_info => Destination::Info(InfoField::Created),
+            "revtim" if in_info => Destination::Info(InfoField::Revised),
+            "fldinst" => Destination::FieldInstruction,
+            "fldrslt" => Destination::Body,
+            "listtext" | "pntext" => Destination::ListText,
+            "colortbl" | "listtable" | "listoverridetable" | "revtbl" | "rsidtbl" | "filetbl"
+            | "pict" | "object" | "nonshppict" | "header" | "headerl" | "headerr" | "headerf"
+            | "footer" | "footerl" | "footerr" | "footerf" | "footnote" | "annotation"
+            | "atnid" | "atnauthor" | "pn" | "printim" | "buptim" | "xmlnstbl" | "generator"
+            | "themedata" | "colorschememapping" | "latentstyles" | "datastore" => {
+                Destination::Skip
+            }
+            _ => return None,
+        };
+        Some(destination)
+    }
+
+    fn font_table_word(&mut self, name: &str, param: Option<i32>) {
+        match name {
+            "f" => self.font_entry = param,
+            "fcharset" => {
+                if let (Some(font), Some(encoding)) =
+                    (self.font_entry, param.and_then(charset_encoding))
+                {
+                    self.fonts.insert(font, encoding);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn body_word(&mut self, name: &str, param: Option<i32>) {
+        let on = param != Some(0);
+        match name {
+            "par" | "sect" => self.end_paragraph(),
+            "page" => {
+                self.end_paragraph();
+                if !self.paragraph_state.in_table {
+                    self.flush_table();
+                    self.blocks.push(ParsedBlock::PageBreak);
+                }
+            }
+            "line" => self.emit("\n"),
+            "tab" => self.emit("\t"),
+            "emdash" => self.emit_char('\u{2014}'),
+            "endash" => self.emit_char('\u{2013}'),
+            "bullet" => self.emit_char('\u{2022}'),
+            "lquote" => self.emit_char('\u{2018}'),
+            "rquote" => self.emit_char('\u{2019}'),
+            "ldblquote" => self.emit_char('\u{201c}'),
+            "rdblquote" => self.emit_char('\u{201d}'),
+            "emspace" | "enspace" | "qmspace" => self.emit_char(' '),
+            "cell" | "nestcell" => self.end_cell(),
+            "row" | "nestrow" => self.end_row(),
+            "intbl" => self.paragraph_state.in_table = true,
+            "pard" => self.paragraph_state = ParagraphState::default(),
+            "s" => self.paragraph_state.style = param,
+            "outlinelevel" => self.paragraph_state.outline_level = outline_level(param),
+            "ls" => {
+                self.paragraph_state.list_level.get_or_insert(0);
+            }
+            "ilvl" => {
+                self.paragraph_state.list_level =
+                    Some(u8::try_from(param.unwrap_or(0)).unwrap_or(0));
+            }
+            "plain" => self.state_mut().style = InlineStyle::default(),
+            "b" => self.state_mut().style.bold = on,
+            "i" => self.state_mut().style.italic = on,
+            "strike" | "striked" => self.state_mut().style.strike = on,
+            "ul" | "uld" | "uldb" | "ulw" | "ulwave" | "uldash" | "ulth" => {
+                self.state_mut().style.underline = on;
+            }
+            "ulnone" => self.state_mut().style.underline = false,
+            "uc" => {
+                self.state_mut().unicode_skip = usize::try_from(param.unwrap_or(1)).unwrap_or(1);
+            }
+            "f" => {
+                let encoding = param
+                    .and_then(|font| self.fonts.get(&font).copied())
+                    .unwrap_or(self.ansi_encoding);
+                self.state_mut().encoding = encoding;
+            }
+            "ansicpg" => {
+                if let Some(encoding) = param.and_then(codepage_encoding) {
+                    self.set_ansi_encoding(encoding);
+                }
+            }
+            "mac" => self.set_ansi_encoding(encoding_rs::MACINTOSH),
+            _ => {}
+        }
+    }
+
+    fn set_ansi_encoding(&mut self, encoding: &'static Encoding) {
+        self.ansi_encoding = encoding;
+        for state in &mut self.stack {
+            state.encoding = encoding;
+        }
+    }
+
+    /// Decode and emit the pending text bytes
+    fn flush(&mut self) {
+        if self.pending.is_empty() {
+            return;
+        }
+        let bytes = std::mem::take(&mut self.pending);
+        let (text, _, _) = self.state().encoding.decode(&bytes);
+        self.emit(&text);
+    }
+
+    fn emit_char(&mut self, ch: char) {
+        self.flush();
+        self.emit(ch.encode_utf8(&mut [0; 4]));
+    }
+
+    fn emit(&mut self, text: &str) {
+        match self.state().destination {
+            Destination::Body => {
+                let style = self.state().style.clone();
+                let link = self.state().link.clone();
+                push_inline(&mut self.paragraph, text, &style, link);
+            }
+            Destination::ListText => self.list_text.push_str(text),
+            Destination::FieldInstruction => self.field_instruction.push_str(text),
+            Destination::StyleSheet => {
+                let mut parts = text.split(';');
+                if let Some(first) = parts.next() {
+                    self.style_entry.name.push_str(first);
+                }
+                for part in parts {
+                    self.end_style_entry();
+                    self.style_entry.name.push_str(part);
+                }
+            }
+            Destination::Info(InfoField::Title) => self.title.push_str(text),
+            Destination::Info(InfoField::Author) => self.author.push_str(text),
+            _ => {}
+        }
+    }
+
+    fn end_style_entry(&mut self) {
+        let entry = std::mem::take(&mut self.style_entry);
+        let level = entry.outline_level.or_else(|| {
+            let name = entry.name.trim().to_lowercase();
+            let level = name.strip_prefix("heading")?.trim().parse::<u8>().ok()?;
+            (1..=9).contains(&level).then_some(level)
+        });
+        if let Some(level) = level {
+            self.heading_styles
+                .insert(entry.number.unwrap_or(0), level.min(6));
+        }
+    }
+
+    fn end_paragraph(&mut self) {
+        let mut inlines = std::mem::take(&mut self.paragraph);
+        let list_text = std::mem::take(&mut self.list_text);
+        Inline::trim(&mut inlines);
+
+        if self.paragraph_state.in_table {
+            if !inlines.is_empty() {
+                self.cell_blocks.push(ParsedBlock::Paragraph {
+                    inlines,
+                    location: None,
+                });
+            }
+            return;
+        }
+
+        self.flush_table();
+        if inlines.is_empty() {
+            return;
+        }
+
+        let heading = self.paragraph_state.outline_level.or_else(|| {
+            self.paragraph_state
+                .style
+                .and_then(|style| self.heading_styles.get(&style).copied())
+        });
+        let list_level = self
+            .paragraph_state
+            .list_level
+            .or_else(|| (!list_text.trim().is_empty()).then_some(0));
+
+        let block = if let Some(level) = heading {
+            ParsedBlock::Heading {
+                level: level.clamp(1, 6),
+                inlines,
+                location: None,
+            }
+        } else if let Some(level) = list_level {
+            ParsedBlock::ListItem {
+                level,
+                ordered: is_ordered_marker(&list_text),
+                blocks: vec![ParsedBlock::Paragraph {
+                    inlines,
+                    location: None,
+                }],
+            }
+        } else {
+            ParsedBlock::Paragraph {
+                inlines,
+                location: None,
+            }
+        };
+        self.blocks.push(block);
+    }
+
+    fn end_cell(&mut self) {
+        // The last paragraph of a cell is ended by \cell instead of \par
+        self.paragraph_state.in_table = true;
+        self.end_paragraph();
+        let blocks = std::mem::take(&mut self.cell_blocks);
+        self.row_cells.push(TableCell { blocks });
+    }
+
+    fn end_row(&mut self) {
+        let mut cells = std::mem::take(&mut self.row_cells);
+        if !self.cell_blocks.is_empty() {
+            cells.push(TableCell {
+                blocks: std::mem::take(&mut self.cell_blocks),
+            });
+        }
+        if !cells.is_empty() {
+            self.rows.push(TableRow {
+                is_header: self.rows.is_empty(),
+                cells,
+            });
+        }
+    }
+
+    fn flush_table(&mut self) {
+        if !self.row_cells.is_empty() {
+            self.end_row();
+        }
+        if self.rows.is_empty() {
+            return;
+        }
+        let mut rows = std::mem::take(&mut self.rows);
+        let width = rows.iter().map(|row| row.cells.len()).max().unwrap_or(0);
+        for row in &mut rows {
+            row.cells
+                .resize_with(width, || TableCell { blocks: Vec::new() });
+        }
+        self.blocks.push(ParsedBlock::Table(TableBlock {
+            rows,
+            location: None,
+        }));
+    }
+}
+
+/// `\outlinelevelN` is 0-based; levels past 8 mean body text
+fn outline_level(param: Option<i32>) -> Option<u8> {
+    let level = u8::try_from(param?).ok()?;
+    (level < 9).then_some(level + 1)
+}
+
+/// Whether a list marker like `1.`, `iv)` or `a.` denotes a numbered list
+fn is_ordered_marker(marker: &str) -> bool {
+    let marker = marker.trim();
+    let Some(first) = marker.chars().next() else {
+        return false;
+    };
+    first.is_ascii_digit()
+        || (first.is_ascii_alphabetic()
+            && marker.len() <= 5
+            && (marker.ends_with('.') || marker.ends_with(')')))
+}
+
+/// Target of a `HYPERLINK "url"` field instruction
+fn hyperlink_target(instruction: &str) -> Option<String> {
+    let instruction = instruction.trim();
+    let rest = instruction
+        .get(..9)
+        .filter(|head| head.eq_ignore_ascii_case("HYPERLINK"))
+        .map(|_| instruction[9..].trim_start())?;
+    let target = if let Some(quoted) = rest.strip_prefix('"') {
+        quoted.split('"').next().unwrap_or_default()
+    } else {
+        rest.split_whitespace().next().unwrap_or_default()
+    };
+    non_empty(target.to_owned())
+}
+
+fn rtf_date([year, month, day, hour, minute]: [i32; 5]) -> Option<OffsetDateTime> {
+    let month = Month::try_from(u8::try_from(month).ok()?).ok()?;
+    let date = Date::from_calendar_date(year, month, u8::try_from(day).ok()?).ok()?;
+    let time = Time::from_hms(u8::try_from(hour).ok()?, u8::try_from(minute).ok()?, 0).ok()?;
+    Some(PrimitiveDateTime::new(date, time).assume_utc())
+}
+
+/// Encoding for a Windows code page number
+pub(crate) fn codepage_encoding(codepage: i32) -> Option<&'static Encoding> {
+    let encoding = match codepage {
+        437 | 850 | 1252 => encoding_rs::WINDOWS_1252,
+        866 => encoding_rs::IBM866,
+        874 => encoding_rs::WINDOWS_874,
+        932 => encoding_rs::SHIFT_JIS,
+        936 => encoding_rs::GBK,
+        949 => encoding_rs::EUC_KR,
+        950 => encoding_rs::BIG5,
+        1250 => encoding_rs::WINDOWS_1250,
+        1251 => encoding_rs::WINDOWS_1251,
+        1253 => encoding_rs::WINDOWS_1253,
+        1254 => encoding_rs::WINDOWS_1254,
+        1255 => encoding_rs::WINDOWS_1255,
+        1256 => encoding_rs::WINDOWS_1256,
+        1257 => encoding_rs::WINDOWS_1257,
+        1258 => encoding_rs::WINDOWS_1258,
+        10000 => encoding_rs::MACINTOSH,
+        20866 => encoding_rs::KOI8_R,
+        65001 => encoding_rs::UTF_8,
+        _ => return None,
+    };
+    Some(encoding)
+}
+
+/// Encoding for an RTF `\fcharsetN` value
+fn charset_encoding(charset: i32) -> Option<&'static Encoding> {
+    let codepage = match charset {
+        0 => 1252,
+        77 => 10000,
+        128 => 932,
+        129 => 949,
+        134 => 936,
+        136 => 950,
+        161 => 1253,
+        162 => 1254,
+        163 => 1258,
+        177 => 1255,
+        178 => 1256,
+        186 => 1257,
+        204 => 1251,
+        222 => 874,
+        238 => 1250,
+        _ => return None,
+    };
+    codepage_encoding(codepage)
+}
+
+fn push_inline(out: &mut Vec<Inline>, text: &str, style: &InlineStyle, link: Option<String>) {
+    match (out.last_mut(), link) {
+        (
+            Some(Inline::Text {
+                text: last,
+                style: last_style,
+            }),
+            None,
+        ) if last_style == style => {
+            last.push_str(text);
+        }
+        (
+            Some(Inline::Link {
+                text: last,
+                target,
+                style: last_style,
+            }),
+            Some(link),
+        ) if *target == link && last_style == style => last.push_str(text),
+        (_, None) => out.push(Inline::styled(text, style.clone())),
+        (_, Some(target)) => out.push(Inline::Link {
+            text: text.to_owned(),
+            target,
+            style: style.clone(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hyperlink_target() {
+        assert_eq!(
+            hyperlink_target(r#" HYPERLINK "https://example.com/a" \o "tip""#).as_deref(),
+            Some("https://example.com/a")
+        );
+        assert_eq!(
+            hyperlink_target("hyperlink https://example.com").as_deref(),
+            Some("https://example.com")
+        );
+        assert_eq!(hyperlink_target(r"PAGE \* MERGEFORMAT"), None);
+    }
+
+    #[test]
+    fn test_is_ordered_marker() {
+        assert!(is_ordered_marker("1.\t"));
+        assert!(is_ordered_marker("iv)"));
+        assert!(!is_ordered_marker("\u{b7}\t"));
+        assert!(!is_ordered_marker("o"));
+    }
+}
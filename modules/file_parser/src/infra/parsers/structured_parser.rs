@@ -0,0 +1,485 @@
+use async_trait::async_trait;
+use std::fmt;
+use std::path::Path;
+
+use serde::de::{Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+
+use super::csv_parser::decode_text;
+use crate::domain::error::DomainError;
+use crate::domain::ir::{DocumentBuilder, Inline, InlineStyle, ParsedBlock, ParsedSource};
+use crate::domain::parser::FileParserBackend;
+
+/// Parser for JSON, YAML and XML data files.
+///
+/// Small documents become nested lists of keys and values; documents too
+/// large or too deep to read well that way are kept as a code block.
+pub struct StructuredDataParser;
+
+/// Supported file extensions
+const SUPPORTED_EXTENSIONS: &[&str] = &["json", "yaml", "yml", "xml"];
+
+/// MIME type constants
+const MIME_TYPE_JSON: &str = "application/json";
+const MIME_TYPE_YAML: &str = "application/yaml";
+const MIME_TYPE_XML: &str = "application/xml";
+
+/// Documents with more values than this are shown as code
+const MAX_LIST_NODES: usize = 1000;
+
+/// Documents nested deeper than this are shown as code
+const MAX_LIST_DEPTH: usize = 6;
+
+/// Code blocks are cut off after this many bytes
+const MAX_CODE_BYTES: usize = 64 * 1024;
+
+/// The data formats handled by this parser
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Json,
+    Yaml,
+    Xml,
+}
+
+impl Format {
+    fn from_hints(filename: Option<&str>, content_type: Option<&str>) -> Self {
+        let extension = filename
+            .and_then(|name| Path::new(name).extension())
+            .and_then(|ext| ext.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("json") => return Self::Json,
+            Some("yaml" | "yml") => return Self::Yaml,
+            Some("xml") => return Self::Xml,
+            _ => {}
+        }
+        match content_type {
+            Some(ct) if ct.contains("yaml") => Self::Yaml,
+            Some(ct) if ct.contains("xml") => Self::Xml,
+            _ => Self::Json,
+        }
+    }
+
+    fn mime_type(self) -> &'static str {
+        match self {
+            Self::Json => MIME_TYPE_JSON,
+            Self::Yaml => MIME_TYPE_YAML,
+            Self::Xml => MIME_TYPE_XML,
+        }
+    }
+
+    fn language(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Yaml => "yaml",
+            Self::Xml => "xml",
+        }
+    }
+}
+
+impl StructuredDataParser {
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for StructuredDataParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl FileParserBackend for StructuredDataParser {
+    fn id(&self) -> &'static str {
+        "structured_data"
+    }
+
+    fn supported_extensions(&self) -> &'static [&'static str] {
+        SUPPORTED_EXTENSIONS
+    }
+
+    async fn parse_local_path(
+        &self,
+        path: &Path,
+    ) -> Result<crate::domain::ir::ParsedDocument, DomainError> {
+        let bytes = tokio::fs::read(path)
+            .await
+            .map_err(|e| DomainError::io_error(format!("Failed to read file: {e}")))?;
+
+        let filename = path.file_name().and_then(|s| s.to_str());
+        let format = Format::from_hints(filename, None);
+        let blocks = tokio::task::spawn_blocking(move || parse_structured(&bytes, format))
+            .await
+            .map_err(|e| DomainError::parse_error(format!("Task join error: {e}")))??;
+
+        let mut builder = DocumentBuilder::new(ParsedSource::LocalPath(path.display().to_string()))
+            .content_type(format.mime_type())
+            .blocks(blocks);
+
+        if let Some(filename) = filename {
+            builder = builder.title(filename).original_filename(filename);
+        }
+
+        Ok(builder.build())
+    }
+
+    async fn parse_bytes(
+        &self,
+        filename_hint: Option<&str>,
+        content_type: Option<&str>,
+        bytes: bytes::Bytes,
+    ) -> Result<crate::domain::ir::ParsedDocument, DomainError> {
+        let format = Format::from_hints(filename_hint, content_type);
+        let blocks = tokio::task::spawn_blocking(move || parse_structured(&bytes, format))
+            .await
+            .map_err(|e| DomainError::parse_error(format!("Task join error: {e}")))??;
+
+        let source = ParsedSource::Uploaded {
+            original_name: filename_hint.map_or_else(
+                || format!("unknown.{}", format.language()),
+                ToOwned::to_owned,
+            ),
+        };
+
+        let mut builder = DocumentBuilder::new(source)
+            .content_type(format.mime_type())
+            .blocks(blocks);
+
+        if let Some(filename) = filename_hint {
+            builder = builder.title(filename).original_filename(filename);
+        }
+
+        Ok(builder.build())
+    }
+}
+
+fn parse_structured(bytes: &[u8], format: Format) -> Result<Vec<ParsedBlock>, DomainError> {
+    let text = decode_text(bytes);
+    let documents = match format {
+        Format::Json => vec![
+            serde_json::from_str::<DataNode>(&text)
+                .map_err(|e| DomainError::parse_error(format!("Invalid JSON: {e}")))?,
+        ],
+        Format::Yaml => serde_saphyr::from_multiple::<DataNode>(&text)
+            .map_err(|e| DomainError::parse_error(format!("Invalid YAML: {e}")))?,
+        Format::Xml => vec![xml_document(&text)?],
+    };
+
+    let readable = documents.iter().all(|doc| doc.depth() <= MAX_LIST_DEPTH)
+        && documents.iter().map(DataNode::count).sum::<usize>() <= MAX_LIST_NODES;
+    if !readable {
+        return Ok(code_blocks(&text, format));
+    }
+
+    let mut blocks = Vec::new();
+    for (index, document) in documents.iter().enumerate() {
+        if index > 0 {
+            blocks.push(ParsedBlock::HorizontalRule);
+        }
+        match document {
+            DataNode::Map(entries) => map_items(entries, 0, &mut blocks),
+            DataNode::Seq(items) => seq_items(items, 0, &mut blocks),
+            DataNode::Null | DataNode::Scalar(_) => blocks.push(ParsedBlock::Paragraph {
+                inlines: vec![Inline::plain(document.scalar_text())],
+                location: None,
+            }),
+        }
+    }
+    Ok(blocks)
+}
+
+/// The source as a code block, cut off at `MAX_CODE_BYTES`
+fn code_blocks(text: &str, format: Format) -> Vec<ParsedBlock> {
+    let mut end = text.len().min(MAX_CODE_BYTES);
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    let mut blocks = vec![ParsedBlock::CodeBlock {
+        language: Some(format.language().to_owned()),
+        code: text[..end].to_owned(),
+    }];
+    if end < text.len() {
+        blocks.push(ParsedBlock::Paragraph {
+            inlines: vec![Inline::plain(format!(
+                "Showing the first {end} of {} bytes.",
+                text.len()
+            ))],
+            location: None,
+        });
+    }
+    blocks
+}
+
+// ---------------------------------------------------------------------------
+// Data tree
+// ---------------------------------------------------------------------------
+
+/// A parsed data value; maps keep their keys in document order
+#[derive(Debug, Clone, PartialEq)]
+enum DataNode {
+    Null,
+    Scalar(String),
+    Seq(Vec<DataNode>),
+    Map(Vec<(String, DataNode)>),
+}
+
+impl DataNode {
+    fn count(&self) -> usize {
+        match self {
+            Self::Null | Self::Scalar(_) => 1,
+            Self::Seq(items) => 1 + items.iter().map(Self::count).sum::<usize>(),
+            Self::Map(entries) => 1 + entries.iter().map(|(_, v)| v.count()).sum::<usize>(),
+        }
+    }
+
+    fn depth(&self) -> usize {
+        match self {
+            Self::Null | Self::Scalar(_) => 0,
+            Self::Seq(items) => 1 + items.iter().map(Self::depth).max().unwrap_or(0),
+            Self::Map(entries) => 1 + entries.iter().map(|(_, v)| v.depth()).max().unwrap_or(0),
+        }
+    }
+
+    fn scalar_text(&self) -> String {
+        match self {
+            Self::Scalar(text) => text.clone(),
+            Self::Null => "null".to_owned(),
+            Self::Seq(items) if items.is_empty() => "[]".to_owned(),
+            Self::Map(entries) if entries.is_empty() => "{}".to_owned(),
+            Self::Seq(_) | Self::Map(_) => String::new(),
+        }
+    }
+
+    fn is_leaf(&self) -> bool {
+        match self {
+            Self::Null | Self::Scalar(_) => true,
+            Self::Seq(items) => items.is_empty(),
+            Self::Map(entries) => entries.is_empty(),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for DataNode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(DataNodeVisitor)
+    }
+}
+
+struct DataNodeVisitor;
+
+impl<'de> Visitor<'de> for DataNodeVisitor {
+    type Value = DataNode;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any data value")
+    }
+
+    fn visit_bool<E>(self, value: bool) -> Result<DataNode, E> {
+        Ok(DataNode::Scalar(value.to_string()))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<DataNode, E> {
+        Ok(DataNode::Scalar(value.to_string()))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<DataNode, E> {
+        Ok(DataNode::Scalar(value.to_string()))
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<DataNode, E> {
+        Ok(DataNode::Scalar(value.to_string()))
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<DataNode, E> {
+        Ok(DataNode::Scalar(value.to_owned()))
+    }
+
+    fn visit_string<E>(self, value: String) -> Result<DataNode, E> {
+        Ok(DataNode::Scalar(value))
+    }
+
+    fn visit_unit<E>(self) -> Result<DataNode, E> {
+        Ok(DataNode::Null)
+    }
+
+    fn visit_none<E>(self) -> Result<DataNode, E> {
+        Ok(DataNode::Null)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<DataNode, D::Error> {
+        DataNode::deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<DataNode, A::Error> {
+        let mut items = Vec::new();
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(DataNode::Seq(items))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<DataNode, A::Error> {
+        let mut entries = Vec::new();
+        while let Some((key, value)) = map.next_entry::<DataNode, DataNode>()? {
+            entries.push((key.scalar_text(), value));
+        }
+        Ok(DataNode::Map(entries))
+    }
+}
+
+/// Convert an XML document into a data tree: attributes become `@name`
+/// entries, text next to child elements becomes `#text`
+fn xml_document(text: &str) -> Result<DataNode, DomainError> {
+    let doc = roxmltree::Document::parse(text)
+        .map_err(|e| DomainError::parse_error(format!("Invalid XML: {e}")))?;
+    let root = doc.root_element();
+    Ok(DataNode::Map(vec![(
+        root.tag_name().name().to_owned(),
+        xml_element(root),
+    )]))
+}
+
+fn xml_element(node: roxmltree::Node) -> DataNode {
+    let mut entries: Vec<(String, DataNode)> = node
+        .attributes()
+        .map(|attr| {
+            (
+                format!("@{}", attr.name()),
+                DataNode::Scalar(attr.value().to_owned()),
+            )
+        })
+        .collect();
+    let mut text = String::new();
+    for child in node.children() {
+        if child.is_element() {
+            entries.push((child.tag_name().name().to_owned(), xml_element(child)));
+        } else if child.is_text()
+            && let Some(value) = child.text()
+        {
+            text.push_str(value);
+        }
+    }
+    let text = text.trim();
+
+    if entries.is_empty() {
+        return if text.is_empty() {
+            DataNode::Null
+        } else {
+            DataNode::Scalar(text.to_owned())
+        };
+    }
+    if !text.is_empty() {
+        entries.push(("#text".to_owned(), DataNode::Scalar(text.to_owned())));
+    }
+    DataNode::Map(entries)
+}
+
+// ---------------------------------------------------------------------------
+// Rendering
+// ---------------------------------------------------------------------------
+
+fn list_item(level: u8, ordered: bool, inlines: Vec<Inline>) -> ParsedBlock {
+    ParsedBlock::ListItem {
+        level,
+        ordered,
+        blocks: vec![ParsedBlock::Paragraph {
+            inlines,
+            location: None,
+        }],
+    }
+}
+
+fn bold(text: &str) -> Inline {
+    Inline::styled(
+        text,
+        InlineStyle {
+            bold: true,
+            ..InlineStyle::default()
+        },
+    )
+}
+
+fn map_items(entries: &[(String, DataNode)], level: u8, out: &mut Vec<ParsedBlock>) {
+    for (key, value) in entries {
+        if value.is_leaf() {
+            out.push(list_item(
+                level,
+                false,
+                vec![
+                    bold(key),
+                    Inline::plain(format!(": {}", value.scalar_text())),
+                ],
+            ));
+        } else {
+            out.push(list_item(level, false, vec![bold(key)]));
+            children(value, level + 1, out);
+        }
+    }
+}
+
+fn seq_items(items: &[DataNode], level: u8, out: &mut Vec<ParsedBlock>) {
+    for (index, item) in items.iter().enumerate() {
+        if item.is_leaf() {
+            out.push(list_item(
+                level,
+                true,
+                vec![Inline::plain(item.scalar_text())],
+            ));
+        } else {
+            out.push(list_item(
+                level,
+                true,
+                vec![Inline::plain(format!("Item {}", index + 1))],
+            ));
+            children(item, level + 1, out);
+        }
+    }
+}
+
+fn children(node: &DataNode, level: u8, out: &mut Vec<ParsedBlock>) {
+    match node {
+        DataNode::Map(entries) => map_items(entries, level, out),
+        DataNode::Seq(items) => seq_items(items, level, out),
+        DataNode::Null | DataNode::Scalar(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_keeps_key_order() {
+        let node: DataNode = serde_json::from_str(r#"{"b": 1, "a": [true, null]}"#).unwrap();
+        assert_eq!(
+            node,
+            DataNode::Map(vec![
+                ("b".to_owned(), DataNode::Scalar("1".to_owned())),
+                (
+                    "a".to_owned(),
+                    DataNode::Seq(vec![DataNode::Scalar("true".to_owned()), DataNode::Null])
+                ),
+            ])
+        );
+        assert_eq!(node.depth(), 2);
+        assert_eq!(node.count(), 5);
+    }
+
+    #[test]
+    fn test_xml_attributes_and_text() {
+        let node = xml_document(r#"<a id="1"><b>x</b><c/>tail</a>"#).unwrap();
+        assert_eq!(
+            node,
+            DataNode::Map(vec![(
+                "a".to_owned(),
+                DataNode::Map(vec![
+                    ("@id".to_owned(), DataNode::Scalar("1".to_owned())),
+                    ("b".to_owned(), DataNode::Scalar("x".to_owned())),
+                    ("c".to_owned(), DataNode::Null),
+                    ("#text".to_owned(), DataNode::Scalar("tail".to_owned())),
+                ])
+            )])
+        );
+    }
+}
@@ -5,8 +5,8 @@ use crate::domain::error::DomainError;
 use crate::domain::ir::{DocumentBuilder, Inline, ParsedBlock, ParsedSource};
 use crate::domain::parser::FileParserBackend;
 
-/// Stub parser that provides placeholder parsing for various file types
-/// This is a temporary implementation until proper parsers are integrated
+/// Stub parser that provides placeholder parsing for file types without a
+/// real backend (currently legacy `PowerPoint` only)
 pub struct StubParser;
 
 impl StubParser {
@@ -29,7 +29,7 @@ impl FileParserBackend for StubParser {
     }
 
     fn supported_extensions(&self) -> &'static [&'static str] {
-        &["doc", "rtf", "odt", "xls", "xlsx", "ppt", "pptx"]
+        &["ppt"]
     }
 
     async fn parse_local_path(
@@ -99,6 +99,7 @@ impl StubParser {
 
         let blocks = vec![ParsedBlock::Paragraph {
             inlines: vec![Inline::plain(text)],
+            location: None,
         }];
 
         DocumentBuilder::new(source)
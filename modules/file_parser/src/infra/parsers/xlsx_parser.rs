@@ -192,6 +192,7 @@ fn extract_blocks_from_workbook<RS: std::io::Read + std::io::Seek, R: Reader<RS>
         blocks.push(ParsedBlock::Heading {
             level: 2,
             inlines: vec![Inline::plain(&sheet_name)],
+            location: None,
         });
 
         // Get the worksheet range
@@ -231,6 +232,7 @@ fn range_to_table_block(range: &calamine::Range<Data>) -> Option<ParsedBlock> {
             cells.push(TableCell {
                 blocks: vec![ParsedBlock::Paragraph {
                     inlines: vec![Inline::plain(text)],
+                    location: None,
                 }],
             });
         }
@@ -238,7 +240,10 @@ fn range_to_table_block(range: &calamine::Range<Data>) -> Option<ParsedBlock> {
         rows.push(TableRow { is_header, cells });
     }
 
-    Some(ParsedBlock::Table(TableBlock { rows }))
+    Some(ParsedBlock::Table(TableBlock {
+        rows,
+        location: None,
+    }))
 }
 
 fn cell_to_string(cell: &Data) -> String {
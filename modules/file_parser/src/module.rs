@@ -6,9 +6,13 @@ use modkit::{Module, ModuleCtx, RestApiCapability};
 use tracing::{debug, info};
 
 use crate::config::FileParserConfig;
+use crate::domain::jobs::{JobManagerConfig, ParseJobManager};
+use crate::domain::ocr::OcrProvider;
 use crate::domain::service::{FileParserService, ServiceConfig};
+use crate::infra::InMemoryJobStore;
 use crate::infra::parsers::{
-    DocxParser, HtmlParser, ImageParser, PdfParser, PlainTextParser, PptxParser, StubParser,
+    CsvParser, DocParser, DocxParser, EmailParser, EpubParser, HtmlParser, ImageParser, OdfParser,
+    PdfParser, PlainTextParser, PptxParser, RtfParser, StructuredDataParser, StubParser,
     XlsxParser,
 };
 
@@ -20,12 +24,14 @@ use crate::infra::parsers::{
 pub struct FileParserModule {
     // Keep the service behind ArcSwap for cheap read-mostly access.
     service: arc_swap::ArcSwapOption<FileParserService>,
+    jobs: arc_swap::ArcSwapOption<ParseJobManager>,
 }
 
 impl Default for FileParserModule {
     fn default() -> Self {
         Self {
             service: arc_swap::ArcSwapOption::from(None),
+            jobs: arc_swap::ArcSwapOption::from(None),
         }
     }
 }
@@ -34,6 +40,7 @@ impl Clone for FileParserModule {
     fn clone(&self) -> Self {
         Self {
             service: arc_swap::ArcSwapOption::new(self.service.load().as_ref().map(Clone::clone)),
+            jobs: arc_swap::ArcSwapOption::new(self.jobs.load().as_ref().map(Clone::clone)),
         }
     }
 }
@@ -53,6 +60,17 @@ impl Module for FileParserModule {
             cfg.max_file_size_mb, cfg.download_timeout_secs
         );
 
+        // The e-mail parser hands attachments back to the service, so it is
+        // bound once the service exists
+        let email_parser = Arc::new(EmailParser::new(cfg.email));
+
+        // Text recognition is optional, provided by another module if at all
+        let mut image_parser = ImageParser::with_config(cfg.images);
+        if let Ok(ocr) = ctx.client_hub().get::<dyn OcrProvider>() {
+            info!("Recognizing text in images with the registered OcrProvider");
+            image_parser = image_parser.with_ocr(ocr);
+        }
+
         // Build parser backends
         let parsers: Vec<Arc<dyn crate::domain::parser::FileParserBackend>> = vec![
             Arc::new(PlainTextParser::new()),
@@ -61,7 +79,14 @@ impl Module for FileParserModule {
             Arc::new(DocxParser::new()),
             Arc::new(XlsxParser::new()),
             Arc::new(PptxParser::new()),
-            Arc::new(ImageParser::new()),
+            Arc::new(OdfParser::new()),
+            Arc::new(RtfParser::new()),
+            Arc::new(DocParser::new()),
+            Arc::new(CsvParser::new()),
+            Arc::new(StructuredDataParser::new()),
+            Arc::new(EpubParser::new()),
+            email_parser.clone(),
+            Arc::new(image_parser),
             Arc::new(StubParser::new()),
         ];
 
@@ -72,13 +97,32 @@ impl Module for FileParserModule {
             max_file_size_bytes: usize::try_from(cfg.max_file_size_mb * BYTES_IN_MB)
                 .unwrap_or(usize::MAX),
             download_timeout_secs: cfg.download_timeout_secs,
+            url_fetch: cfg.url_fetch,
+            local_files: cfg.local_files,
         };
 
         // Create file parser service
-        let file_parser_service = Arc::new(FileParserService::new(parsers, service_config));
+        let file_parser_service = Arc::new(FileParserService::new(parsers, service_config)?);
+        email_parser.bind_service(&file_parser_service);
+
+        let job_config = JobManagerConfig {
+            workers: cfg.jobs.workers,
+            max_running_per_tenant: cfg.jobs.max_running_per_tenant,
+            max_pending_per_tenant: cfg.jobs.max_pending_per_tenant,
+            max_pending: cfg.jobs.max_pending,
+            max_pending_upload_bytes: usize::try_from(cfg.jobs.max_pending_upload_mb * BYTES_IN_MB)
+                .unwrap_or(usize::MAX),
+            result_ttl: std::time::Duration::from_secs(cfg.jobs.result_ttl_secs),
+        };
+        let job_manager = ParseJobManager::new(
+            file_parser_service.clone(),
+            Arc::new(InMemoryJobStore::new()),
+            job_config,
+        );
 
         // Store service for REST usage
         self.service.store(Some(file_parser_service));
+        self.jobs.store(Some(Arc::new(job_manager)));
 
         info!("FileParserService initialized successfully");
         Ok(())
@@ -100,8 +144,14 @@ impl RestApiCapability for FileParserModule {
             .as_ref()
             .ok_or_else(|| anyhow::anyhow!("Service not initialized"))?
             .clone();
+        let jobs = self
+            .jobs
+            .load()
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Job manager not initialized"))?
+            .clone();
 
-        let router = crate::api::rest::routes::register_routes(router, openapi, service);
+        let router = crate::api::rest::routes::register_routes(router, openapi, service, jobs);
 
         info!("File parser REST routes registered successfully");
         Ok(router)
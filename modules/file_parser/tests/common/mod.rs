@@ -0,0 +1,8 @@
+//! Helpers shared by the parser integration tests
+
+use file_parser::domain::ir::Inline;
+
+/// The concatenated text of a run of inlines
+pub fn text_of(inlines: &[Inline]) -> String {
+    Inline::plain_text(inlines)
+}
@@ -0,0 +1,109 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use std::fmt::Write;
+
+use common::text_of;
+use file_parser::domain::ir::{ParsedBlock, ParsedDocument, TableBlock};
+use file_parser::domain::parser::FileParserBackend;
+use file_parser::infra::parsers::CsvParser;
+
+async fn parse(name: &str, text: &str) -> ParsedDocument {
+    CsvParser::new()
+        .parse_bytes(Some(name), None, bytes::Bytes::from(text.to_owned()))
+        .await
+        .expect("Failed to parse CSV")
+}
+
+fn table(doc: &ParsedDocument) -> &TableBlock {
+    match doc.blocks.first() {
+        Some(ParsedBlock::Table(table)) => table,
+        other => panic!("Expected table, got {other:?}"),
+    }
+}
+
+fn cell_text(table: &TableBlock, row: usize, cell: usize) -> String {
+    match table.rows[row].cells[cell].blocks.first() {
+        Some(ParsedBlock::Paragraph { inlines, .. }) => text_of(inlines),
+        other => panic!("Unexpected cell content: {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_csv_with_header_and_quotes() {
+    let doc = parse(
+        "people.csv",
+        "\u{feff}name,city,note\r\nAlice,\"Paris, FR\",\"says \"\"hi\"\"\"\r\nBob,Berlin\r\n\r\n",
+    )
+    .await;
+
+    assert_eq!(doc.meta.content_type.as_deref(), Some("text/csv"));
+    assert_eq!(doc.title.as_deref(), Some("people.csv"));
+    assert_eq!(doc.blocks.len(), 1);
+
+    let table = table(&doc);
+    assert_eq!(table.rows.len(), 3);
+    assert!(table.rows[0].is_header);
+    assert!(!table.rows[1].is_header);
+    assert_eq!(cell_text(table, 0, 0), "name");
+    assert_eq!(cell_text(table, 1, 1), "Paris, FR");
+    assert_eq!(cell_text(table, 1, 2), "says \"hi\"");
+    // Short rows are padded to the table width
+    assert_eq!(table.rows[2].cells.len(), 3);
+    assert_eq!(cell_text(table, 2, 2), "");
+}
+
+#[tokio::test]
+async fn test_numeric_first_row_is_not_a_header() {
+    let doc = parse("data.csv", "1;2;3\n4;5;6\n").await;
+
+    let table = table(&doc);
+    assert_eq!(table.rows.len(), 2);
+    assert!(table.rows.iter().all(|row| !row.is_header));
+    assert_eq!(cell_text(table, 1, 2), "6");
+}
+
+#[tokio::test]
+async fn test_tsv() {
+    let doc = parse("data.tsv", "id\tlabel\n1\ta,b\n").await;
+
+    assert_eq!(
+        doc.meta.content_type.as_deref(),
+        Some("text/tab-separated-values")
+    );
+    let table = table(&doc);
+    assert_eq!(cell_text(table, 1, 1), "a,b");
+}
+
+#[tokio::test]
+async fn test_row_limit() {
+    let mut text = String::from("n\n");
+    for i in 0..10_005 {
+        writeln!(text, "{i}").unwrap();
+    }
+    let doc = parse("big.csv", &text).await;
+
+    let table = table(&doc);
+    assert_eq!(table.rows.len(), 10_001);
+    let Some(ParsedBlock::Paragraph { inlines, .. }) = doc.blocks.get(1) else {
+        panic!("Expected truncation note, got {:?}", doc.blocks.get(1));
+    };
+    assert_eq!(text_of(inlines), "Showing the first 10000 of 10005 rows.");
+}
+
+#[tokio::test]
+async fn test_windows_1252_fallback() {
+    let doc = CsvParser::new()
+        .parse_bytes(
+            Some("legacy.csv"),
+            None,
+            bytes::Bytes::from_static(b"name,city\nJos\xe9,M\xfcnchen\n"),
+        )
+        .await
+        .unwrap();
+
+    let table = table(&doc);
+    assert_eq!(cell_text(table, 1, 0), "Jos\u{e9}");
+    assert_eq!(cell_text(table, 1, 1), "M\u{fc}nchen");
+}
@@ -0,0 +1,201 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use file_parser::domain::ir::{Inline, ParsedBlock, ParsedDocument};
+use file_parser::domain::parser::FileParserBackend;
+use file_parser::infra::parsers::DocParser;
+
+const SECTOR: usize = 512;
+const END_OF_CHAIN: u32 = 0xFFFF_FFFE;
+const FREE: u32 = 0xFFFF_FFFF;
+const NO_STREAM: u32 = 0xFFFF_FFFF;
+
+fn put_u16(buf: &mut [u8], offset: usize, value: u16) {
+    buf[offset..offset + 2].copy_from_slice(&value.to_le_bytes());
+}
+
+fn put_u32(buf: &mut [u8], offset: usize, value: u32) {
+    buf[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+}
+
+/// A 128-byte compound file directory entry
+fn dir_entry(name: &str, kind: u8, right: u32, child: u32, start: u32, size: u32) -> Vec<u8> {
+    let mut entry = vec![0_u8; 128];
+    let units: Vec<u16> = name.encode_utf16().collect();
+    for (i, unit) in units.iter().enumerate() {
+        put_u16(&mut entry, i * 2, *unit);
+    }
+    put_u16(
+        &mut entry,
+        0x40,
+        u16::try_from((units.len() + 1) * 2).unwrap(),
+    );
+    entry[0x42] = kind;
+    put_u32(&mut entry, 0x44, NO_STREAM);
+    put_u32(&mut entry, 0x48, right);
+    put_u32(&mut entry, 0x4C, child);
+    put_u32(&mut entry, 0x74, start);
+    put_u32(&mut entry, 0x78, size);
+    entry
+}
+
+/// Build a Word 97 document whose text is split over an 8-bit piece and a
+/// UTF-16 piece; only the first `ccp_text` characters are main text
+fn build_doc(compressed: &str, unicode: &str, ccp_text: usize) -> Vec<u8> {
+    // WordDocument stream: FIB followed by the text of both pieces
+    let mut word = vec![0_u8; 8 * SECTOR];
+    put_u16(&mut word, 0, 0xA5EC);
+    put_u16(&mut word, 2, 0xC1);
+    // fWhichTblStm: the piece table lives in 1Table
+    put_u16(&mut word, 0x0A, 0x0200);
+    put_u16(&mut word, 32, 14);
+    put_u16(&mut word, 62, 22);
+    put_u32(&mut word, 64 + 12, u32::try_from(ccp_text).unwrap());
+    put_u16(&mut word, 152, 93);
+    let compressed_at = 1024;
+    word[compressed_at..compressed_at + compressed.len()].copy_from_slice(compressed.as_bytes());
+    let unicode_at = 2048;
+    let units: Vec<u16> = unicode.encode_utf16().collect();
+    for (i, unit) in units.iter().enumerate() {
+        put_u16(&mut word, unicode_at + i * 2, *unit);
+    }
+
+    // 1Table stream: the Clx with a two-piece table
+    let mut table = vec![0_u8; 8 * SECTOR];
+    let first = u32::try_from(compressed.len()).unwrap();
+    let total = first + u32::try_from(units.len()).unwrap();
+    table[0] = 0x02;
+    put_u32(&mut table, 1, 3 * 4 + 2 * 8);
+    put_u32(&mut table, 5, 0);
+    put_u32(&mut table, 9, first);
+    put_u32(&mut table, 13, total);
+    put_u32(
+        &mut table,
+        17 + 2,
+        0x4000_0000 | u32::try_from(compressed_at * 2).unwrap(),
+    );
+    put_u32(&mut table, 25 + 2, u32::try_from(unicode_at).unwrap());
+    let clx_len = 1 + 4 + 3 * 4 + 2 * 8;
+    put_u32(&mut word, 154 + 33 * 8, 0);
+    put_u32(&mut word, 154 + 33 * 8 + 4, u32::try_from(clx_len).unwrap());
+
+    // Sector 0 holds the FAT, sector 1 the directory, then both streams
+    let mut fat = FREE.to_le_bytes().repeat(SECTOR.div_euclid(4));
+    put_u32(&mut fat, 0, 0xFFFF_FFFD);
+    put_u32(&mut fat, 4, END_OF_CHAIN);
+    for start in [2_u32, 10] {
+        for sector in start..start + 7 {
+            put_u32(&mut fat, sector as usize * 4, sector + 1);
+        }
+        put_u32(&mut fat, (start + 7) as usize * 4, END_OF_CHAIN);
+    }
+
+    let stream_size = u32::try_from(8 * SECTOR).unwrap();
+    let mut directory = Vec::new();
+    directory.extend(dir_entry("Root Entry", 5, NO_STREAM, 1, END_OF_CHAIN, 0));
+    directory.extend(dir_entry("WordDocument", 2, 2, NO_STREAM, 2, stream_size));
+    directory.extend(dir_entry(
+        "1Table",
+        2,
+        NO_STREAM,
+        NO_STREAM,
+        10,
+        stream_size,
+    ));
+    directory.extend(dir_entry("", 0, NO_STREAM, NO_STREAM, END_OF_CHAIN, 0));
+
+    let mut header = vec![0_u8; SECTOR];
+    header[..8].copy_from_slice(&[0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1]);
+    put_u16(&mut header, 0x18, 0x3E);
+    put_u16(&mut header, 0x1A, 3);
+    put_u16(&mut header, 0x1C, 0xFFFE);
+    put_u16(&mut header, 0x1E, 9);
+    put_u16(&mut header, 0x20, 6);
+    put_u32(&mut header, 0x2C, 1);
+    put_u32(&mut header, 0x30, 1);
+    put_u32(&mut header, 0x38, 4096);
+    put_u32(&mut header, 0x3C, END_OF_CHAIN);
+    put_u32(&mut header, 0x44, END_OF_CHAIN);
+    for i in 0..109 {
+        put_u32(&mut header, 0x4C + i * 4, if i == 0 { 0 } else { FREE });
+    }
+
+    [header, fat, directory, word, table].concat()
+}
+
+async fn parse(bytes: Vec<u8>) -> ParsedDocument {
+    DocParser::new()
+        .parse_bytes(Some("letter.doc"), None, bytes::Bytes::from(bytes))
+        .await
+        .expect("Failed to parse DOC")
+}
+
+fn paragraphs(doc: &ParsedDocument) -> Vec<String> {
+    doc.blocks
+        .iter()
+        .map(|block| match block {
+            ParsedBlock::Paragraph { inlines, .. } => match &inlines[0] {
+                Inline::Text { text, .. } => text.clone(),
+                other => panic!("Unexpected inline: {other:?}"),
+            },
+            ParsedBlock::PageBreak => "<page>".to_owned(),
+            other => panic!("Unexpected block: {other:?}"),
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_doc_text_from_piece_table() {
+    let compressed = "Dear reader,\r\u{13} PAGE \u{14}1\u{15} of the letter.\r\u{c}";
+    let unicode = "Second page \u{43f}\u{440}\u{438}\u{432}\u{435}\u{442}\rFootnote text\r";
+    let main_len = compressed.len()
+        + "Second page \u{43f}\u{440}\u{438}\u{432}\u{435}\u{442}\r"
+            .chars()
+            .count();
+    let doc = parse(build_doc(compressed, unicode, main_len)).await;
+
+    assert_eq!(doc.meta.content_type.as_deref(), Some("application/msword"));
+    assert_eq!(doc.title.as_deref(), Some("letter.doc"));
+    assert_eq!(
+        paragraphs(&doc),
+        vec![
+            "Dear reader,",
+            "1 of the letter.",
+            "<page>",
+            "Second page \u{43f}\u{440}\u{438}\u{432}\u{435}\u{442}",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_rtf_saved_as_doc() {
+    let rtf = br"{\rtf1\ansi{\info{\title Memo}}\pard Hello from RTF\par}";
+    let doc = parse(rtf.to_vec()).await;
+
+    assert_eq!(doc.meta.content_type.as_deref(), Some("application/rtf"));
+    assert_eq!(doc.title.as_deref(), Some("Memo"));
+    assert_eq!(paragraphs(&doc), vec!["Hello from RTF"]);
+}
+
+#[tokio::test]
+async fn test_rejects_non_word_files() {
+    let result = DocParser::new()
+        .parse_bytes(
+            Some("fake.doc"),
+            None,
+            bytes::Bytes::from_static(b"definitely not a Word file"),
+        )
+        .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_rejects_looping_sector_chains() {
+    let mut bytes = build_doc("Hello\r", "", 6);
+    // Point the last WordDocument sector back to its first one
+    put_u32(&mut bytes, SECTOR + 9 * 4, 2);
+
+    let result = DocParser::new()
+        .parse_bytes(Some("loop.doc"), None, bytes::Bytes::from(bytes))
+        .await;
+    assert!(result.is_err());
+}
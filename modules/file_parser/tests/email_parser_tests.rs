@@ -0,0 +1,219 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! EML and MBOX parsing, with attachments parsed through the service
+
+mod common;
+
+use std::sync::Arc;
+
+use common::text_of;
+use file_parser::config::EmailConfig;
+use file_parser::domain::ir::{ParsedBlock, ParsedDocument};
+use file_parser::domain::parser::FileParserBackend;
+use file_parser::domain::service::{FileParserService, ServiceConfig};
+use file_parser::infra::parsers::{EmailParser, HtmlParser, PlainTextParser};
+
+const SIMPLE: &str = "From: Alice Example <alice@example.com>\r\n\
+To: Bob <bob@example.com>, carol@example.com\r\n\
+Cc: Dave <dave@example.com>\r\n\
+Subject: Quarterly report\r\n\
+Date: Fri, 15 Mar 2024 09:30:00 -0500\r\n\
+Message-ID: <report-1@example.com>\r\n\
+Content-Type: text/html; charset=utf-8\r\n\
+\r\n\
+<html><body><h1>Summary</h1><p>Revenue is <b>up</b>.</p></body></html>\r\n";
+
+const WITH_ATTACHMENTS: &str = "From: alice@example.com\r\n\
+Subject: Files\r\n\
+Content-Type: multipart/mixed; boundary=\"outer\"\r\n\
+\r\n\
+--outer\r\n\
+Content-Type: text/plain\r\n\
+\r\n\
+See attached.\r\n\
+--outer\r\n\
+Content-Type: text/plain\r\n\
+Content-Disposition: attachment; filename=\"notes.txt\"\r\n\
+\r\n\
+Attached notes\r\n\
+--outer\r\n\
+Content-Type: message/rfc822\r\n\
+Content-Disposition: attachment; filename=\"forwarded.eml\"\r\n\
+\r\n\
+From: Bob <bob@example.com>\r\n\
+Subject: Original\r\n\
+Content-Type: text/plain\r\n\
+\r\n\
+Forwarded body\r\n\
+--outer--\r\n";
+
+fn service(config: EmailConfig) -> Arc<FileParserService> {
+    let email = Arc::new(EmailParser::new(config));
+    let parsers: Vec<Arc<dyn FileParserBackend>> = vec![
+        Arc::new(PlainTextParser::new()),
+        Arc::new(HtmlParser::new()),
+        email.clone(),
+    ];
+    let service = Arc::new(FileParserService::new(parsers, ServiceConfig::default()).unwrap());
+    email.bind_service(&service);
+    service
+}
+
+async fn parse(config: EmailConfig, name: &str, raw: &str) -> ParsedDocument {
+    service(config)
+        .parse_bytes(Some(name), None, bytes::Bytes::from(raw.to_owned()))
+        .await
+        .expect("Failed to parse e-mail")
+}
+
+/// Headings as `(level, text)` and paragraphs as `(0, text)`
+fn outline(doc: &ParsedDocument) -> Vec<(u8, String)> {
+    doc.blocks
+        .iter()
+        .filter_map(|block| match block {
+            ParsedBlock::Heading { level, inlines, .. } => Some((*level, text_of(inlines))),
+            ParsedBlock::Paragraph { inlines, .. } => Some((0, text_of(inlines))),
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_eml_headers_and_html_body() {
+    let doc = parse(EmailConfig::default(), "report.eml", SIMPLE).await;
+
+    assert_eq!(doc.title.as_deref(), Some("Quarterly report"));
+    assert_eq!(
+        doc.meta.author.as_deref(),
+        Some("Alice Example <alice@example.com>")
+    );
+    assert_eq!(doc.meta.content_type.as_deref(), Some("message/rfc822"));
+    assert_eq!(
+        doc.meta
+            .created_at
+            .map(time::OffsetDateTime::unix_timestamp),
+        Some(1_710_513_000)
+    );
+
+    let email = doc.meta.email.as_ref().expect("e-mail headers");
+    assert_eq!(email.to, vec!["Bob <bob@example.com>", "carol@example.com"]);
+    assert_eq!(email.cc, vec!["Dave <dave@example.com>"]);
+    assert_eq!(email.message_id.as_deref(), Some("report-1@example.com"));
+
+    assert_eq!(
+        outline(&doc),
+        vec![
+            (0, "From: Alice Example <alice@example.com>".to_owned()),
+            (0, "To: Bob <bob@example.com>, carol@example.com".to_owned()),
+            (0, "Cc: Dave <dave@example.com>".to_owned()),
+            (0, "Date: Fri, 15 Mar 2024 09:30:00 -0500".to_owned()),
+            (1, "Summary".to_owned()),
+            (0, "Revenue is up.".to_owned()),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_attachments_are_parsed() {
+    let doc = parse(EmailConfig::default(), "files.eml", WITH_ATTACHMENTS).await;
+
+    assert_eq!(
+        outline(&doc),
+        vec![
+            (0, "From: alice@example.com".to_owned()),
+            (0, "See attached.".to_owned()),
+            (1, "Attachment: notes.txt".to_owned()),
+            (0, "Attached notes".to_owned()),
+            (1, "Attachment: forwarded.eml".to_owned()),
+            (0, "From: Bob <bob@example.com>".to_owned()),
+            (0, "Subject: Original".to_owned()),
+            (0, "Forwarded body".to_owned()),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_attachment_limits() {
+    let config = EmailConfig {
+        max_attachment_depth: 0,
+        ..EmailConfig::default()
+    };
+    let doc = parse(config, "files.eml", WITH_ATTACHMENTS).await;
+    let outline = outline(&doc);
+    assert!(outline.contains(&(
+        0,
+        "Not parsed: attachments are nested too deeply".to_owned()
+    )));
+    assert!(!outline.iter().any(|(_, text)| text == "Attached notes"));
+
+    let config = EmailConfig {
+        max_attachments_mb: 0,
+        ..EmailConfig::default()
+    };
+    let doc = parse(config, "files.eml", WITH_ATTACHMENTS).await;
+    assert!(outline_contains(
+        &doc,
+        "Not parsed: the attachment size limit is reached"
+    ));
+}
+
+fn outline_contains(doc: &ParsedDocument, text: &str) -> bool {
+    outline(doc).iter().any(|(_, t)| t == text)
+}
+
+#[tokio::test]
+async fn test_unbound_parser_lists_attachments() {
+    let doc = EmailParser::new(EmailConfig::default())
+        .parse_bytes(
+            Some("files.eml"),
+            None,
+            bytes::Bytes::from_static(WITH_ATTACHMENTS.as_bytes()),
+        )
+        .await
+        .unwrap();
+    assert!(outline_contains(
+        &doc,
+        "Not parsed: attachment parsing is not available"
+    ));
+    // Attached messages do not need the service
+    assert!(outline_contains(&doc, "Forwarded body"));
+}
+
+#[tokio::test]
+async fn test_mbox_sections_per_message() {
+    let mbox = "From alice@example.com Fri Mar 15 09:30:00 2024\n\
+From: alice@example.com\n\
+Subject: First\n\
+\n\
+Hello\n\
+\n\
+From bob@example.com Sat Mar 16 10:00:00 2024\n\
+From: bob@example.com\n\
+\n\
+Second body\n";
+
+    let doc = parse(EmailConfig::default(), "inbox.mbox", mbox).await;
+
+    assert_eq!(doc.title.as_deref(), Some("inbox.mbox"));
+    assert_eq!(doc.meta.content_type.as_deref(), Some("application/mbox"));
+    assert!(doc.meta.email.is_none());
+    assert_eq!(
+        outline(&doc),
+        vec![
+            (1, "First".to_owned()),
+            (0, "From: alice@example.com".to_owned()),
+            (0, "Hello".to_owned()),
+            (1, "(no subject)".to_owned()),
+            (0, "From: bob@example.com".to_owned()),
+            (0, "Second body".to_owned()),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_not_an_email() {
+    let result = EmailParser::new(EmailConfig::default())
+        .parse_bytes(Some("empty.eml"), None, bytes::Bytes::new())
+        .await;
+    assert!(result.is_err());
+}
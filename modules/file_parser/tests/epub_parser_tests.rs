@@ -0,0 +1,261 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use std::io::Write;
+
+use common::text_of;
+use file_parser::domain::ir::{Inline, ParsedBlock, ParsedDocument};
+use file_parser::domain::parser::FileParserBackend;
+use file_parser::infra::parsers::EpubParser;
+
+const CONTAINER: &str = r#"<?xml version="1.0"?>
+<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+  <rootfiles>
+    <rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/>
+  </rootfiles>
+</container>"#;
+
+/// Build an EPUB package from `(path, content)` entries
+fn build_package(entries: &[(&str, &str)]) -> bytes::Bytes {
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options =
+        zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+    for (name, data) in [
+        ("mimetype", "application/epub+zip"),
+        ("META-INF/container.xml", CONTAINER),
+    ]
+    .iter()
+    .chain(entries)
+    {
+        zip.start_file(*name, options).unwrap();
+        zip.write_all(data.as_bytes()).unwrap();
+    }
+    bytes::Bytes::from(zip.finish().unwrap().into_inner())
+}
+
+fn chapter(title: &str, body: &str) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<html xmlns="http://www.w3.org/1999/xhtml" xmlns:epub="http://www.idpf.org/2007/ops">
+<head><title>{title}</title></head>
+<body>{body}</body>
+</html>"#
+    )
+}
+
+async fn parse(package: bytes::Bytes) -> ParsedDocument {
+    EpubParser::new()
+        .parse_bytes(Some("book.epub"), None, package)
+        .await
+        .expect("Failed to parse EPUB")
+}
+
+/// Headings of a document as `(level, text)`
+fn headings(doc: &ParsedDocument) -> Vec<(u8, String)> {
+    doc.blocks
+        .iter()
+        .filter_map(|block| match block {
+            ParsedBlock::Heading { level, inlines, .. } => Some((*level, text_of(inlines))),
+            _ => None,
+        })
+        .collect()
+}
+
+/// An EPUB 3 book whose spine lists the second chapter first
+fn epub3_book() -> bytes::Bytes {
+    let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0" unique-identifier="id">
+  <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+    <dc:identifier id="id">urn:uuid:1</dc:identifier>
+    <dc:title>A Short Book</dc:title>
+    <dc:creator>Jane Doe</dc:creator>
+    <dc:language>en</dc:language>
+    <dc:date>2024-03-15</dc:date>
+    <meta property="dcterms:modified">2024-04-01T12:00:00Z</meta>
+  </metadata>
+  <manifest>
+    <item id="nav" href="nav.xhtml" media-type="application/xhtml+xml" properties="nav"/>
+    <item id="c1" href="text/one.xhtml" media-type="application/xhtml+xml"/>
+    <item id="c2" href="text/two.xhtml" media-type="application/xhtml+xml"/>
+    <item id="img" href="images/map.png" media-type="image/png"/>
+  </manifest>
+  <spine>
+    <itemref idref="c2"/>
+    <itemref idref="c1"/>
+  </spine>
+</package>"#;
+    let nav = chapter(
+        "Contents",
+        r#"<nav epub:type="toc"><ol>
+             <li><a href="text/one.xhtml">Beginnings</a></li>
+             <li><a href="text/two.xhtml#top">Prologue</a></li>
+           </ol></nav>"#,
+    );
+    let one = chapter(
+        "one",
+        r#"<h1>Beginnings</h1>
+           <p>It was a <em>dark</em>&nbsp;night, see <a href="https://example.com">this</a>.</p>
+           <h2>Later</h2>
+           <ul><li>first<ul><li>nested</li></ul></li><li>second</li></ul>
+           <img src="../images/map.png" alt="Map"/>"#,
+    );
+    let two = chapter(
+        "two",
+        "<div>Loose text <b>here</b></div>
+           <table><tr><th>Key</th><th>Value</th></tr><tr><td>a</td><td>1</td></tr></table>",
+    );
+    build_package(&[
+        ("OEBPS/content.opf", opf),
+        ("OEBPS/nav.xhtml", nav.as_str()),
+        ("OEBPS/text/one.xhtml", one.as_str()),
+        ("OEBPS/text/two.xhtml", two.as_str()),
+    ])
+}
+
+#[tokio::test]
+async fn test_epub3_spine_order_and_nav_titles() {
+    let doc = parse(epub3_book()).await;
+
+    assert_eq!(
+        doc.meta.content_type.as_deref(),
+        Some("application/epub+zip")
+    );
+    assert_eq!(doc.title.as_deref(), Some("A Short Book"));
+    assert_eq!(doc.meta.author.as_deref(), Some("Jane Doe"));
+    assert_eq!(doc.language.as_deref(), Some("en"));
+    assert_eq!(doc.meta.created_at.unwrap().year(), 2024);
+    assert_eq!(u8::from(doc.meta.modified_at.unwrap().month()), 4);
+
+    // Spine order, titles from the navigation document, and the chapter's
+    // own copy of its title dropped
+    assert_eq!(
+        headings(&doc),
+        vec![
+            (1, "Prologue".to_owned()),
+            (1, "Beginnings".to_owned()),
+            (3, "Later".to_owned()),
+        ]
+    );
+
+    let ParsedBlock::Paragraph { inlines, .. } = &doc.blocks[1] else {
+        panic!("Expected paragraph, got {:?}", doc.blocks[1]);
+    };
+    assert_eq!(text_of(inlines), "Loose text here");
+    let ParsedBlock::Table(table) = &doc.blocks[2] else {
+        panic!("Expected table, got {:?}", doc.blocks[2]);
+    };
+    assert!(table.rows[0].is_header);
+    assert_eq!(table.rows.len(), 2);
+
+    let ParsedBlock::Paragraph { inlines, .. } = &doc.blocks[4] else {
+        panic!("Expected paragraph, got {:?}", doc.blocks[4]);
+    };
+    assert_eq!(text_of(inlines), "It was a dark\u{a0}night, see this.");
+    assert!(inlines.iter().any(
+        |inline| matches!(inline, Inline::Text { text, style } if text == "dark" && style.italic)
+    ));
+    assert!(inlines.iter().any(
+        |inline| matches!(inline, Inline::Link { target, .. } if target == "https://example.com")
+    ));
+
+    let lists: Vec<(u8, String)> = doc
+        .blocks
+        .iter()
+        .filter_map(|block| match block {
+            ParsedBlock::ListItem { level, blocks, .. } => match blocks.first() {
+                Some(ParsedBlock::Paragraph { inlines, .. }) => Some((*level, text_of(inlines))),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        lists,
+        vec![
+            (0, "first".to_owned()),
+            (1, "nested".to_owned()),
+            (0, "second".to_owned()),
+        ]
+    );
+
+    assert!(doc.blocks.iter().any(|block| matches!(
+        block,
+        ParsedBlock::Image { alt: Some(alt), src: Some(src), .. }
+            if alt == "Map" && src == "OEBPS/images/map.png"
+    )));
+}
+
+#[tokio::test]
+async fn test_epub2_ncx_titles() {
+    let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="2.0">
+  <metadata xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>Old Book</dc:title></metadata>
+  <manifest>
+    <item id="ncx" href="toc.ncx" media-type="application/x-dtbncx+xml"/>
+    <item id="c1" href="ch1.html" media-type="application/xhtml+xml"/>
+    <item id="c2" href="ch2.html" media-type="application/xhtml+xml"/>
+  </manifest>
+  <spine toc="ncx"><itemref idref="c1"/><itemref idref="c2"/></spine>
+</package>"#;
+    let ncx = r#"<?xml version="1.0"?>
+<ncx xmlns="http://www.daisy.org/z3986/2005/ncx/" version="2005-1">
+  <navMap>
+    <navPoint id="p1" playOrder="1">
+      <navLabel><text>Chapter One</text></navLabel><content src="ch1.html"/>
+    </navPoint>
+  </navMap>
+</ncx>"#;
+    let doc = parse(build_package(&[
+        ("OEBPS/content.opf", opf),
+        ("OEBPS/toc.ncx", ncx),
+        ("OEBPS/ch1.html", chapter("One", "<p>First.</p>").as_str()),
+        (
+            "OEBPS/ch2.html",
+            chapter("Second Part", "<p>Second.</p>").as_str(),
+        ),
+    ]))
+    .await;
+
+    assert_eq!(doc.title.as_deref(), Some("Old Book"));
+    // Chapters missing from the NCX fall back to their <title>
+    assert_eq!(
+        headings(&doc),
+        vec![(1, "Chapter One".to_owned()), (1, "Second Part".to_owned())]
+    );
+}
+
+#[tokio::test]
+async fn test_repeated_spine_items_are_read_once() {
+    let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="2.0">
+  <metadata xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>Loop</dc:title></metadata>
+  <manifest><item id="c1" href="ch1.html" media-type="application/xhtml+xml"/></manifest>
+  <spine><itemref idref="c1"/><itemref idref="c1"/><itemref idref="c1"/></spine>
+</package>"#;
+    let doc = parse(build_package(&[
+        ("OEBPS/content.opf", opf),
+        (
+            "OEBPS/ch1.html",
+            chapter("One", "<p>Only once.</p>").as_str(),
+        ),
+    ]))
+    .await;
+
+    assert_eq!(headings(&doc), vec![(1, "One".to_owned())]);
+    assert_eq!(doc.blocks.len(), 2);
+}
+
+#[tokio::test]
+async fn test_missing_container_is_an_error() {
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    zip.start_file("mimetype", zip::write::SimpleFileOptions::default())
+        .unwrap();
+    zip.write_all(b"application/epub+zip").unwrap();
+    let package = bytes::Bytes::from(zip.finish().unwrap().into_inner());
+
+    let result = EpubParser::new()
+        .parse_bytes(Some("broken.epub"), None, package)
+        .await;
+    assert!(result.is_err());
+}
@@ -1,10 +1,16 @@
 #![allow(clippy::unwrap_used, clippy::expect_used, clippy::use_debug)]
 
+use async_trait::async_trait;
 use base64::Engine;
-use file_parser::domain::ir::ParsedBlock;
+use bytes::Bytes;
+use file_parser::config::{AnimatedGifPolicy, ImageConfig};
+use file_parser::domain::error::DomainError;
+use file_parser::domain::ir::{Inline, ParsedBlock, ParsedDocument};
+use file_parser::domain::ocr::OcrProvider;
 use file_parser::domain::parser::FileParserBackend;
 use file_parser::infra::parsers::image_parser::ImageParser;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 /// Helper to get the path to test data files
 fn get_test_file_path(filename: &str) -> PathBuf {
@@ -257,3 +263,256 @@ async fn test_image_parser_unsupported_extension() {
     // Should fail with unsupported file type error
     assert!(result.is_err(), "Should fail for unsupported extension");
 }
+
+/// 2x1 GIF with two frames
+const ANIMATED_GIF: &[u8] = &[
+    b'G', b'I', b'F', b'8', b'9', b'a', 2, 0, 1, 0, 0x80, 0, 0, // screen
+    0, 0, 0, 0xFF, 0xFF, 0xFF, // color table
+    0x2C, 0, 0, 0, 0, 2, 0, 1, 0, 0, 2, 2, 0x44, 0x01, 0, // frame 1
+    0x2C, 0, 0, 0, 0, 2, 0, 1, 0, 0, 2, 2, 0x84, 0x01, 0, // frame 2
+    0x3B,
+];
+
+/// Little-endian EXIF with Make "Cam" and a GPS position of 52.5 N, 13.25 E
+fn exif_with_gps() -> Vec<u8> {
+    let mut t = b"II\x2A\0\x08\0\0\0".to_vec();
+    t.extend_from_slice(&2_u16.to_le_bytes());
+    t.extend_from_slice(&[0x0F, 0x01, 2, 0, 4, 0, 0, 0, b'C', b'a', b'm', 0]);
+    t.extend_from_slice(&[0x25, 0x88, 4, 0, 1, 0, 0, 0, 38, 0, 0, 0]);
+    t.extend_from_slice(&[0; 4]);
+    t.extend_from_slice(&4_u16.to_le_bytes());
+    t.extend_from_slice(&[1, 0, 2, 0, 2, 0, 0, 0, b'N', 0, 0, 0]);
+    t.extend_from_slice(&[2, 0, 5, 0, 3, 0, 0, 0, 92, 0, 0, 0]);
+    t.extend_from_slice(&[3, 0, 2, 0, 2, 0, 0, 0, b'E', 0, 0, 0]);
+    t.extend_from_slice(&[4, 0, 5, 0, 3, 0, 0, 0, 116, 0, 0, 0]);
+    t.extend_from_slice(&[0; 4]);
+    for (n, d) in [(52, 1), (30, 1), (0, 1), (13, 1), (15, 1), (0, 1)] {
+        t.extend_from_slice(&u32::to_le_bytes(n));
+        t.extend_from_slice(&u32::to_le_bytes(d));
+    }
+    t
+}
+
+/// Little-endian EXIF with Make "Cam" and a GPS IFD holding only a latitude
+/// of 52.5 N
+fn exif_with_partial_gps() -> Vec<u8> {
+    let mut t = b"II\x2A\0\x08\0\0\0".to_vec();
+    t.extend_from_slice(&2_u16.to_le_bytes());
+    t.extend_from_slice(&[0x0F, 0x01, 2, 0, 4, 0, 0, 0, b'C', b'a', b'm', 0]);
+    t.extend_from_slice(&[0x25, 0x88, 4, 0, 1, 0, 0, 0, 38, 0, 0, 0]);
+    t.extend_from_slice(&[0; 4]);
+    t.extend_from_slice(&2_u16.to_le_bytes());
+    t.extend_from_slice(&[1, 0, 2, 0, 2, 0, 0, 0, b'N', 0, 0, 0]);
+    t.extend_from_slice(&[2, 0, 5, 0, 3, 0, 0, 0, 68, 0, 0, 0]);
+    t.extend_from_slice(&[0; 4]);
+    for (n, d) in [(52, 1), (30, 1), (0, 1)] {
+        t.extend_from_slice(&u32::to_le_bytes(n));
+        t.extend_from_slice(&u32::to_le_bytes(d));
+    }
+    t
+}
+
+/// JPEG headers of a 640x480 image with the given EXIF data
+fn jpeg_with_exif(exif: &[u8]) -> Vec<u8> {
+    let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xE1];
+    jpeg.extend_from_slice(&u16::try_from(exif.len() + 8).unwrap().to_be_bytes());
+    jpeg.extend_from_slice(b"Exif\0\0");
+    jpeg.extend_from_slice(exif);
+    jpeg.extend_from_slice(&[0xFF, 0xC0, 0, 11, 8, 0x01, 0xE0, 0x02, 0x80, 1, 1, 0x11, 0]);
+    jpeg.extend_from_slice(&[0xFF, 0xD9]);
+    jpeg
+}
+
+/// PNG signature and header of an image with the given dimensions
+fn png_header(width: u32, height: u32) -> Vec<u8> {
+    let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+    png.extend_from_slice(&width.to_be_bytes());
+    png.extend_from_slice(&height.to_be_bytes());
+    png.extend_from_slice(&[8, 2, 0, 0, 0, 0, 0, 0, 0]);
+    png
+}
+
+fn embedded_bytes(document: &ParsedDocument) -> Vec<u8> {
+    let ParsedBlock::Image { src: Some(src), .. } = &document.blocks[0] else {
+        panic!("Expected Image block, got {:?}", document.blocks[0]);
+    };
+    let (_, data) = src.split_once(";base64,").unwrap();
+    base64::engine::general_purpose::STANDARD
+        .decode(data)
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_image_parser_reports_dimensions() {
+    let parser = ImageParser::new();
+    let test_file = get_test_file_path("tiny.webp");
+
+    if !test_file.exists() {
+        eprintln!("Skipping test: test file not found at {test_file:?}");
+        return;
+    }
+
+    let document = parser.parse_local_path(&test_file).await.unwrap();
+    let image = document.meta.image.unwrap();
+    assert_eq!(image.format, "webp");
+    assert_eq!((image.width, image.height, image.frames), (1, 1, 1));
+}
+
+#[tokio::test]
+async fn test_image_parser_rejects_too_many_pixels() {
+    let parser = ImageParser::with_config(ImageConfig {
+        max_pixels: 1_000_000,
+        ..ImageConfig::default()
+    });
+
+    let result = parser
+        .parse_bytes(
+            Some("bomb.png"),
+            None,
+            Bytes::from(png_header(20_000, 20_000)),
+        )
+        .await;
+    assert!(matches!(result, Err(DomainError::InvalidRequest { .. })));
+}
+
+#[tokio::test]
+async fn test_image_parser_rejects_non_image_content() {
+    let parser = ImageParser::new();
+
+    let result = parser
+        .parse_bytes(Some("fake.png"), None, Bytes::from_static(b"not an image"))
+        .await;
+    assert!(matches!(result, Err(DomainError::ParseError { .. })));
+}
+
+#[tokio::test]
+async fn test_image_parser_animated_gif() {
+    let first_frame = ImageParser::new()
+        .parse_bytes(Some("anim.gif"), None, Bytes::from_static(ANIMATED_GIF))
+        .await
+        .unwrap();
+    assert_eq!(first_frame.meta.image.as_ref().unwrap().frames, 2);
+    let embedded = embedded_bytes(&first_frame);
+    assert!(embedded.len() < ANIMATED_GIF.len());
+
+    // The embedded image is a still GIF
+    let still = ImageParser::new()
+        .parse_bytes(Some("still.gif"), None, Bytes::from(embedded))
+        .await
+        .unwrap();
+    assert_eq!(still.meta.image.unwrap().frames, 1);
+
+    let rejecting = ImageParser::with_config(ImageConfig {
+        animated_gif: AnimatedGifPolicy::Reject,
+        ..ImageConfig::default()
+    });
+    let result = rejecting
+        .parse_bytes(Some("anim.gif"), None, Bytes::from_static(ANIMATED_GIF))
+        .await;
+    assert!(matches!(result, Err(DomainError::InvalidRequest { .. })));
+}
+
+#[tokio::test]
+async fn test_image_parser_exif_and_gps_stripping() {
+    let jpeg = Bytes::from(jpeg_with_exif(&exif_with_gps()));
+
+    let kept = ImageParser::with_config(ImageConfig {
+        strip_gps: false,
+        ..ImageConfig::default()
+    })
+    .parse_bytes(Some("photo.jpg"), None, jpeg.clone())
+    .await
+    .unwrap();
+    let image = kept.meta.image.as_ref().unwrap();
+    assert_eq!((image.width, image.height), (640, 480));
+    let exif = image.exif.as_ref().unwrap();
+    assert_eq!(exif.make.as_deref(), Some("Cam"));
+    let gps = exif.gps.unwrap();
+    assert!((gps.latitude - 52.5).abs() < 1e-9);
+    assert!((gps.longitude - 13.25).abs() < 1e-9);
+    assert_eq!(embedded_bytes(&kept), jpeg);
+
+    let stripped = ImageParser::new()
+        .parse_bytes(Some("photo.jpg"), None, jpeg.clone())
+        .await
+        .unwrap();
+    let exif = stripped.meta.image.as_ref().unwrap().exif.as_ref().unwrap();
+    assert_eq!(exif.make.as_deref(), Some("Cam"));
+    assert_eq!(exif.gps, None);
+
+    // The embedded image no longer has a position either
+    let embedded = embedded_bytes(&stripped);
+    assert_eq!(embedded.len(), jpeg.len());
+    let reparsed = ImageParser::with_config(ImageConfig {
+        strip_gps: false,
+        ..ImageConfig::default()
+    })
+    .parse_bytes(Some("photo.jpg"), None, Bytes::from(embedded))
+    .await
+    .unwrap();
+    let exif = reparsed.meta.image.unwrap().exif.unwrap();
+    assert_eq!(exif.gps, None);
+}
+
+#[tokio::test]
+async fn test_image_parser_strips_partial_gps() {
+    let exif = exif_with_partial_gps();
+    let jpeg = Bytes::from(jpeg_with_exif(&exif));
+
+    let stripped = ImageParser::new()
+        .parse_bytes(Some("photo.jpg"), None, jpeg.clone())
+        .await
+        .unwrap();
+    let image = stripped.meta.image.as_ref().unwrap();
+    assert_eq!(image.exif.as_ref().unwrap().gps, None);
+
+    // The latitude is gone from the embedded image, the rest is intact
+    let embedded = embedded_bytes(&stripped);
+    assert_eq!(embedded.len(), jpeg.len());
+    let start = embedded
+        .windows(exif.len())
+        .position(|w| w.starts_with(&exif[..38]))
+        .unwrap();
+    assert!(
+        embedded[start + 38..start + exif.len()]
+            .iter()
+            .all(|&b| b == 0)
+    );
+    assert_eq!(embedded[..start + 38], jpeg[..start + 38]);
+}
+
+struct FixedOcr;
+
+#[async_trait]
+impl OcrProvider for FixedOcr {
+    async fn recognize(
+        &self,
+        mime_type: &str,
+        _bytes: Bytes,
+    ) -> Result<Vec<ParsedBlock>, DomainError> {
+        Ok(vec![ParsedBlock::Paragraph {
+            inlines: vec![Inline::plain(format!("text of {mime_type}"))],
+            location: None,
+        }])
+    }
+}
+
+#[tokio::test]
+async fn test_image_parser_ocr_provider() {
+    let parser = ImageParser::new().with_ocr(Arc::new(FixedOcr));
+
+    let document = parser
+        .parse_bytes(Some("anim.gif"), None, Bytes::from_static(ANIMATED_GIF))
+        .await
+        .unwrap();
+
+    assert_eq!(document.blocks.len(), 2);
+    assert!(matches!(document.blocks[0], ParsedBlock::Image { .. }));
+    assert_eq!(
+        document.blocks[1],
+        ParsedBlock::Paragraph {
+            inlines: vec![Inline::plain("text of image/gif")],
+            location: None,
+        }
+    );
+}
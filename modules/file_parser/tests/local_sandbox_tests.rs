@@ -0,0 +1,126 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! `parse_local` sandbox: shared and per-tenant roots, `..` and symlink escapes
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use file_parser::config::LocalFilesConfig;
+use file_parser::domain::error::DomainError;
+use file_parser::domain::parser::FileParserBackend;
+use file_parser::domain::service::{FileParserService, ServiceConfig};
+use file_parser::infra::parsers::PlainTextParser;
+use modkit_security::SecurityContext;
+use tempfile::TempDir;
+use uuid::Uuid;
+
+const TENANT_A: Uuid = Uuid::from_u128(0xA);
+const TENANT_B: Uuid = Uuid::from_u128(0xB);
+
+/// Layout: `shared/notes.txt`, `tenant_a/own.txt`, `outside/secret.txt`
+fn layout() -> TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    for (sub, file) in [
+        ("shared", "notes.txt"),
+        ("tenant_a", "own.txt"),
+        ("outside", "secret.txt"),
+    ] {
+        std::fs::create_dir(dir.path().join(sub)).unwrap();
+        std::fs::write(dir.path().join(sub).join(file), "Some text").unwrap();
+    }
+    dir
+}
+
+fn service(root: &Path) -> FileParserService {
+    let parsers: Vec<Arc<dyn FileParserBackend>> = vec![Arc::new(PlainTextParser::new())];
+    let config = ServiceConfig {
+        local_files: LocalFilesConfig {
+            allowed_roots: vec![root.join("shared")],
+            tenant_roots: HashMap::from([(TENANT_A, vec![root.join("tenant_a")])]),
+        },
+        ..ServiceConfig::default()
+    };
+    FileParserService::new(parsers, config).unwrap()
+}
+
+fn ctx(tenant_id: Uuid) -> SecurityContext {
+    SecurityContext::builder().tenant_id(tenant_id).build()
+}
+
+fn is_not_allowed(result: &Result<impl Sized, DomainError>) -> bool {
+    matches!(result, Err(DomainError::PathNotAllowed { .. }))
+}
+
+#[tokio::test]
+async fn test_shared_and_tenant_roots() {
+    let dir = layout();
+    let service = service(dir.path());
+
+    let shared = dir.path().join("shared/notes.txt");
+    assert!(service.parse_local(&ctx(TENANT_A), &shared).await.is_ok());
+    assert!(service.parse_local(&ctx(TENANT_B), &shared).await.is_ok());
+
+    let own = dir.path().join("tenant_a/own.txt");
+    assert!(service.parse_local(&ctx(TENANT_A), &own).await.is_ok());
+    assert!(is_not_allowed(
+        &service.parse_local(&ctx(TENANT_B), &own).await
+    ));
+}
+
+#[tokio::test]
+async fn test_paths_outside_roots_are_rejected() {
+    let dir = layout();
+    let service = service(dir.path());
+    let ctx = ctx(TENANT_A);
+
+    for path in [
+        dir.path().join("outside/secret.txt"),
+        dir.path().join("shared/../outside/secret.txt"),
+        dir.path().join("outside/missing.txt"),
+        "shared/notes.txt".into(),
+    ] {
+        let result = service.parse_local(&ctx, &path).await;
+        assert!(is_not_allowed(&result), "{}", path.display());
+    }
+
+    let missing = dir.path().join("shared/missing.txt");
+    assert!(matches!(
+        service.parse_local(&ctx, &missing).await,
+        Err(DomainError::FileNotFound { .. })
+    ));
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_symlink_escape_is_rejected() {
+    let dir = layout();
+    let service = service(dir.path());
+    let ctx = ctx(TENANT_B);
+
+    std::os::unix::fs::symlink(
+        dir.path().join("outside/secret.txt"),
+        dir.path().join("shared/link.txt"),
+    )
+    .unwrap();
+    std::os::unix::fs::symlink(dir.path().join("outside"), dir.path().join("shared/dir")).unwrap();
+
+    let link = dir.path().join("shared/link.txt");
+    assert!(is_not_allowed(&service.parse_local(&ctx, &link).await));
+    let through_dir = dir.path().join("shared/dir/secret.txt");
+    assert!(is_not_allowed(
+        &service.parse_local(&ctx, &through_dir).await
+    ));
+}
+
+#[tokio::test]
+async fn test_no_roots_disables_local_parsing() {
+    let dir = layout();
+    let parsers: Vec<Arc<dyn FileParserBackend>> = vec![Arc::new(PlainTextParser::new())];
+    let service = FileParserService::new(parsers, ServiceConfig::default()).unwrap();
+
+    let path = dir.path().join("shared/notes.txt");
+    assert!(is_not_allowed(
+        &service.parse_local(&ctx(TENANT_A), &path).await
+    ));
+}
@@ -0,0 +1,303 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use std::io::Write;
+
+use common::text_of;
+use file_parser::domain::ir::{Inline, ParsedBlock, ParsedDocument};
+use file_parser::domain::parser::FileParserBackend;
+use file_parser::infra::parsers::OdfParser;
+
+const CONTENT_HEADER: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document-content
+    xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0"
+    xmlns:style="urn:oasis:names:tc:opendocument:xmlns:style:1.0"
+    xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0"
+    xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0"
+    xmlns:draw="urn:oasis:names:tc:opendocument:xmlns:drawing:1.0"
+    xmlns:presentation="urn:oasis:names:tc:opendocument:xmlns:presentation:1.0"
+    xmlns:fo="urn:oasis:names:tc:opendocument:xmlns:xsl-fo-compatible:1.0"
+    xmlns:xlink="http://www.w3.org/1999/xlink">"#;
+
+const META: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document-meta
+    xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0"
+    xmlns:meta="urn:oasis:names:tc:opendocument:xmlns:meta:1.0"
+    xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <office:meta>
+    <dc:title>Quarterly Report</dc:title>
+    <dc:creator>Jane Doe</dc:creator>
+    <dc:language>en-US</dc:language>
+    <meta:creation-date>2024-03-15T09:30:00.123456789</meta:creation-date>
+    <dc:date>2024-04-01T12:00:00Z</dc:date>
+  </office:meta>
+</office:document-meta>"#;
+
+/// Build an `OpenDocument` package from its mimetype and `content.xml` body
+fn build_package(mimetype: &str, automatic_styles: &str, body: &str) -> bytes::Bytes {
+    let content = format!(
+        "{CONTENT_HEADER}<office:automatic-styles>{automatic_styles}</office:automatic-styles>\
+         <office:body>{body}</office:body></office:document-content>"
+    );
+
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options =
+        zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+    for (name, data) in [
+        ("mimetype", mimetype),
+        ("content.xml", content.as_str()),
+        ("meta.xml", META),
+    ] {
+        zip.start_file(name, options).unwrap();
+        zip.write_all(data.as_bytes()).unwrap();
+    }
+    bytes::Bytes::from(zip.finish().unwrap().into_inner())
+}
+
+async fn parse(name: &str, package: bytes::Bytes) -> ParsedDocument {
+    OdfParser::new()
+        .parse_bytes(Some(name), None, package)
+        .await
+        .expect("Failed to parse OpenDocument package")
+}
+
+fn cell_text(table: &file_parser::domain::ir::TableBlock, row: usize, cell: usize) -> String {
+    match table.rows[row].cells[cell].blocks.first() {
+        Some(ParsedBlock::Paragraph { inlines, .. }) => text_of(inlines),
+        None => String::new(),
+        other => panic!("Unexpected cell content: {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_odt_headings_lists_tables_and_metadata() {
+    let styles = r#"
+        <style:style style:name="T1" style:family="text">
+          <style:text-properties fo:font-weight="bold"/>
+        </style:style>
+        <text:list-style style:name="L1">
+          <text:list-level-style-number text:level="1"/>
+          <text:list-level-style-bullet text:level="2"/>
+        </text:list-style>"#;
+    let body = r#"<office:text>
+        <text:h text:outline-level="1">Overview</text:h>
+        <text:p>Revenue was <text:span text:style-name="T1">up</text:span>,<text:s text:c="2"/>see
+          <text:a xlink:href="https://example.com/q1">the dashboard</text:a>.</text:p>
+        <text:p/>
+        <text:list text:style-name="L1">
+          <text:list-item><text:p>First</text:p>
+            <text:list><text:list-item><text:p>Nested</text:p></text:list-item></text:list>
+          </text:list-item>
+          <text:list-item><text:p>Second</text:p></text:list-item>
+        </text:list>
+        <table:table table:name="Numbers">
+          <table:table-column table:number-columns-repeated="3"/>
+          <table:table-header-rows>
+            <table:table-row>
+              <table:table-cell><text:p>Region</text:p></table:table-cell>
+              <table:table-cell><text:p>Total</text:p></table:table-cell>
+              <table:table-cell/>
+            </table:table-row>
+          </table:table-header-rows>
+          <table:table-row>
+            <table:table-cell><text:p>North</text:p></table:table-cell>
+            <table:table-cell><text:p>42</text:p></table:table-cell>
+            <table:table-cell/>
+          </table:table-row>
+        </table:table>
+      </office:text>"#;
+    let package = build_package("application/vnd.oasis.opendocument.text", styles, body);
+    let doc = parse("report.odt", package).await;
+
+    assert_eq!(
+        doc.meta.content_type.as_deref(),
+        Some("application/vnd.oasis.opendocument.text")
+    );
+    assert_eq!(doc.title.as_deref(), Some("Quarterly Report"));
+    assert_eq!(doc.language.as_deref(), Some("en-US"));
+    assert_eq!(doc.meta.author.as_deref(), Some("Jane Doe"));
+    assert_eq!(doc.meta.created_at.unwrap().year(), 2024);
+    assert_eq!(u8::from(doc.meta.modified_at.unwrap().month()), 4);
+    assert_eq!(doc.meta.original_filename.as_deref(), Some("report.odt"));
+
+    assert!(
+        matches!(&doc.blocks[0], ParsedBlock::Heading { level: 1, inlines, .. } if text_of(inlines) == "Overview")
+    );
+
+    let ParsedBlock::Paragraph { inlines, .. } = &doc.blocks[1] else {
+        panic!("Expected paragraph, got {:?}", doc.blocks[1]);
+    };
+    assert_eq!(text_of(inlines), "Revenue was up,  see the dashboard.");
+    assert!(inlines.iter().any(
+        |inline| matches!(inline, Inline::Text { text, style } if text == "up" && style.bold)
+    ));
+    assert!(inlines.iter().any(|inline| matches!(
+        inline,
+        Inline::Link { text, target, .. } if text == "the dashboard" && target == "https://example.com/q1"
+    )));
+
+    let items: Vec<(u8, bool, String)> = doc
+        .blocks
+        .iter()
+        .filter_map(|block| match block {
+            ParsedBlock::ListItem {
+                level,
+                ordered,
+                blocks,
+            } => match &blocks[0] {
+                ParsedBlock::Paragraph { inlines, .. } => {
+                    Some((*level, *ordered, text_of(inlines)))
+                }
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        items,
+        vec![
+            (0, true, "First".to_owned()),
+            (1, false, "Nested".to_owned()),
+            (0, true, "Second".to_owned()),
+        ]
+    );
+
+    let ParsedBlock::Table(table) = doc.blocks.last().unwrap() else {
+        panic!("Expected a table at the end");
+    };
+    assert_eq!(table.rows.len(), 2);
+    assert!(table.rows[0].is_header);
+    assert!(!table.rows[1].is_header);
+    // The trailing empty column is trimmed
+    assert_eq!(table.rows[0].cells.len(), 2);
+    assert_eq!(cell_text(table, 1, 0), "North");
+    assert_eq!(cell_text(table, 1, 1), "42");
+}
+
+#[tokio::test]
+async fn test_ods_sheets_become_headed_tables() {
+    let body = r#"<office:spreadsheet>
+        <table:table table:name="Sales">
+          <table:table-row>
+            <table:table-cell><text:p>Item</text:p></table:table-cell>
+            <table:table-cell table:number-columns-repeated="2"><text:p>Qty</text:p></table:table-cell>
+            <table:table-cell table:number-columns-repeated="16380"/>
+          </table:table-row>
+          <table:table-row>
+            <table:table-cell><text:p>Apples</text:p></table:table-cell>
+            <table:table-cell office:value-type="float" office:value="3"><text:p>3</text:p></table:table-cell>
+          </table:table-row>
+          <table:table-row table:number-rows-repeated="1048574">
+            <table:table-cell table:number-columns-repeated="16384"/>
+          </table:table-row>
+        </table:table>
+        <table:table table:name="Empty"/>
+      </office:spreadsheet>"#;
+    let package = build_package("application/vnd.oasis.opendocument.spreadsheet", "", body);
+    let doc = parse("sales.ods", package).await;
+
+    assert_eq!(
+        doc.meta.content_type.as_deref(),
+        Some("application/vnd.oasis.opendocument.spreadsheet")
+    );
+    assert!(
+        matches!(&doc.blocks[0], ParsedBlock::Heading { level: 2, inlines, .. } if text_of(inlines) == "Sales")
+    );
+    let ParsedBlock::Table(table) = &doc.blocks[1] else {
+        panic!("Expected a table, got {:?}", doc.blocks[1]);
+    };
+    assert_eq!(table.rows.len(), 2);
+    assert!(table.rows[0].is_header);
+    assert_eq!(table.rows[0].cells.len(), 3);
+    assert_eq!(cell_text(table, 0, 2), "Qty");
+    assert_eq!(cell_text(table, 1, 1), "3");
+    // The second row is padded to the table width
+    assert_eq!(cell_text(table, 1, 2), "");
+
+    // An empty sheet still gets its heading but no table
+    assert_eq!(doc.blocks.len(), 3);
+    assert!(
+        matches!(&doc.blocks[2], ParsedBlock::Heading { inlines, .. } if text_of(inlines) == "Empty")
+    );
+}
+
+#[tokio::test]
+async fn test_ods_repeats_are_capped_by_a_cell_budget() {
+    // Each table alone expands to 1024 x 1024 cells; together they would be 4M
+    let table = r#"<table:table table:name="Grid">
+          <table:table-row table:number-rows-repeated="1024">
+            <table:table-cell table:number-columns-repeated="1024"><text:p>x</text:p></table:table-cell>
+          </table:table-row>
+        </table:table>"#;
+    let body = format!(
+        "<office:spreadsheet>{}</office:spreadsheet>",
+        table.repeat(4)
+    );
+    let package = build_package("application/vnd.oasis.opendocument.spreadsheet", "", &body);
+    let doc = parse("grid.ods", package).await;
+
+    let cells: usize = doc
+        .blocks
+        .iter()
+        .filter_map(|block| match block {
+            ParsedBlock::Table(table) => {
+                Some(table.rows.iter().map(|r| r.cells.len()).sum::<usize>())
+            }
+            _ => None,
+        })
+        .sum();
+    assert!(cells > 0);
+    assert!(cells <= 256 * 1024, "{cells} cells");
+}
+
+#[tokio::test]
+async fn test_odp_slides_and_frames() {
+    let body = r#"<office:presentation>
+        <draw:page draw:name="page1">
+          <draw:frame presentation:class="title"><draw:text-box>
+            <text:p>Welcome</text:p>
+          </draw:text-box></draw:frame>
+          <presentation:notes><draw:frame><draw:text-box>
+            <text:p>Speaker notes</text:p>
+          </draw:text-box></draw:frame></presentation:notes>
+        </draw:page>
+        <draw:page draw:name="page2">
+          <draw:frame><draw:text-box>
+            <text:list><text:list-item><text:p>Point one</text:p></text:list-item></text:list>
+          </draw:text-box></draw:frame>
+        </draw:page>
+      </office:presentation>"#;
+    let package = build_package("application/vnd.oasis.opendocument.presentation", "", body);
+    let doc = parse("deck.odp", package).await;
+
+    let summary: Vec<String> = doc
+        .blocks
+        .iter()
+        .map(|block| match block {
+            ParsedBlock::Heading { inlines, .. } => format!("# {}", text_of(inlines)),
+            ParsedBlock::Paragraph { inlines, .. } => text_of(inlines),
+            ParsedBlock::ListItem { blocks, .. } => match &blocks[0] {
+                ParsedBlock::Paragraph { inlines, .. } => format!("- {}", text_of(inlines)),
+                other => panic!("Unexpected list content: {other:?}"),
+            },
+            other => panic!("Unexpected block: {other:?}"),
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec!["# Slide 1", "Welcome", "# Slide 2", "- Point one"]
+    );
+}
+
+#[tokio::test]
+async fn test_rejects_packages_without_content() {
+    let result = OdfParser::new()
+        .parse_bytes(
+            Some("broken.odt"),
+            None,
+            bytes::Bytes::from_static(b"not a zip file"),
+        )
+        .await;
+    assert!(result.is_err());
+}
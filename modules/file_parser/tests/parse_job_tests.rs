@@ -0,0 +1,259 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Asynchronous parse jobs: worker and tenant limits, cancellation and expiry
+
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use file_parser::domain::error::DomainError;
+use file_parser::domain::ir::{DocumentBuilder, ParsedDocument, ParsedSource};
+use file_parser::domain::jobs::{
+    JobManagerConfig, JobProgress, JobSource, JobState, ParseJob, ParseJobManager,
+};
+use file_parser::domain::parser::FileParserBackend;
+use file_parser::domain::service::{FileParserService, ServiceConfig};
+use file_parser::infra::InMemoryJobStore;
+use file_parser::infra::parsers::PlainTextParser;
+use uuid::Uuid;
+
+/// Parser for `.slow` files that never finishes within a test
+struct SlowParser;
+
+#[async_trait]
+impl FileParserBackend for SlowParser {
+    fn id(&self) -> &'static str {
+        "slow"
+    }
+
+    fn supported_extensions(&self) -> &'static [&'static str] {
+        &["slow"]
+    }
+
+    async fn parse_local_path(&self, path: &Path) -> Result<ParsedDocument, DomainError> {
+        self.parse_bytes(path.to_str(), None, Bytes::new()).await
+    }
+
+    async fn parse_bytes(
+        &self,
+        _filename_hint: Option<&str>,
+        _content_type: Option<&str>,
+        _bytes: Bytes,
+    ) -> Result<ParsedDocument, DomainError> {
+        tokio::time::sleep(Duration::from_mins(10)).await;
+        Ok(DocumentBuilder::new(ParsedSource::LocalPath("slow".to_owned())).build())
+    }
+}
+
+fn manager(config: JobManagerConfig) -> Arc<ParseJobManager> {
+    let parsers: Vec<Arc<dyn FileParserBackend>> =
+        vec![Arc::new(PlainTextParser::new()), Arc::new(SlowParser)];
+    let service = FileParserService::new(parsers, ServiceConfig::default()).unwrap();
+    Arc::new(ParseJobManager::new(
+        Arc::new(service),
+        Arc::new(InMemoryJobStore::new()),
+        config,
+    ))
+}
+
+fn upload(filename: &str, content: &str) -> JobSource {
+    JobSource::Upload {
+        filename: Some(filename.to_owned()),
+        content_type: None,
+        bytes: Bytes::from(content.to_owned()),
+    }
+}
+
+async fn wait_for(jobs: &ParseJobManager, tenant_id: Uuid, id: Uuid, state: JobState) -> ParseJob {
+    for _ in 0..500 {
+        let job = jobs.get(tenant_id, id).await.unwrap();
+        if job.state == state {
+            return job;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("job {id} did not reach {state:?}");
+}
+
+#[tokio::test]
+async fn test_upload_job_succeeds() {
+    let jobs = manager(JobManagerConfig::default());
+    let tenant = Uuid::now_v7();
+
+    let job = jobs
+        .submit(tenant, upload("notes.txt", "Hello jobs"))
+        .await
+        .unwrap();
+    assert_eq!(job.state, JobState::Queued);
+    assert_eq!(job.source.as_deref(), Some("notes.txt"));
+
+    let job = wait_for(&jobs, tenant, job.id, JobState::Succeeded).await;
+    assert!(job.started_at.is_some());
+    assert!(job.finished_at.unwrap() < job.expires_at.unwrap());
+    assert_eq!(
+        job.progress,
+        JobProgress {
+            processed_bytes: 10,
+            total_bytes: Some(10),
+        }
+    );
+
+    let document = jobs.result(tenant, job.id).await.unwrap();
+    assert!(!document.blocks.is_empty());
+}
+
+#[tokio::test]
+async fn test_failed_job_has_no_result() {
+    let jobs = manager(JobManagerConfig::default());
+    let tenant = Uuid::now_v7();
+
+    let job = jobs
+        .submit(tenant, upload("data.unknown", "?"))
+        .await
+        .unwrap();
+    let job = wait_for(&jobs, tenant, job.id, JobState::Failed).await;
+    assert!(job.error.unwrap().contains("unknown"));
+
+    let err = jobs.result(tenant, job.id).await.unwrap_err();
+    assert!(matches!(err, DomainError::NoJobResult { .. }));
+}
+
+#[tokio::test]
+async fn test_jobs_are_scoped_to_tenant() {
+    let jobs = manager(JobManagerConfig::default());
+    let tenant = Uuid::now_v7();
+    let other = Uuid::now_v7();
+
+    let job = jobs.submit(tenant, upload("a.txt", "text")).await.unwrap();
+
+    let err = jobs.get(other, job.id).await.unwrap_err();
+    assert!(matches!(err, DomainError::JobNotFound { .. }));
+    let err = jobs.cancel(other, job.id).await.unwrap_err();
+    assert!(matches!(err, DomainError::JobNotFound { .. }));
+}
+
+#[tokio::test]
+async fn test_pending_jobs_are_limited_per_tenant() {
+    let jobs = manager(JobManagerConfig {
+        max_pending_per_tenant: 1,
+        ..JobManagerConfig::default()
+    });
+    let tenant = Uuid::now_v7();
+
+    jobs.submit(tenant, upload("a.slow", "")).await.unwrap();
+    let err = jobs.submit(tenant, upload("b.slow", "")).await.unwrap_err();
+    assert!(matches!(err, DomainError::TooManyJobs { limit: 1 }));
+
+    jobs.submit(Uuid::now_v7(), upload("c.txt", "text"))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_pending_jobs_are_limited_over_all_tenants() {
+    let jobs = manager(JobManagerConfig {
+        max_pending: 2,
+        ..JobManagerConfig::default()
+    });
+
+    jobs.submit(Uuid::now_v7(), upload("a.slow", ""))
+        .await
+        .unwrap();
+    jobs.submit(Uuid::now_v7(), upload("b.slow", ""))
+        .await
+        .unwrap();
+    let err = jobs
+        .submit(Uuid::now_v7(), upload("c.txt", "text"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DomainError::TooManyJobs { limit: 2 }));
+}
+
+#[tokio::test]
+async fn test_pending_uploads_are_limited_in_size() {
+    let jobs = manager(JobManagerConfig {
+        max_pending_upload_bytes: 10,
+        ..JobManagerConfig::default()
+    });
+    let tenant = Uuid::now_v7();
+
+    let slow = jobs
+        .submit(tenant, upload("a.slow", "123456"))
+        .await
+        .unwrap();
+    let err = jobs
+        .submit(tenant, upload("b.txt", "123456"))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        DomainError::TooManyPendingBytes { limit: 10 }
+    ));
+
+    // Cancelling releases the upload
+    jobs.cancel(tenant, slow.id).await.unwrap();
+    jobs.submit(tenant, upload("b.txt", "123456"))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_busy_tenant_leaves_workers_to_others() {
+    let jobs = manager(JobManagerConfig {
+        workers: 2,
+        max_running_per_tenant: 1,
+        ..JobManagerConfig::default()
+    });
+    let busy = Uuid::now_v7();
+    let other = Uuid::now_v7();
+
+    let first = jobs.submit(busy, upload("a.slow", "")).await.unwrap();
+    let second = jobs.submit(busy, upload("b.slow", "")).await.unwrap();
+    wait_for(&jobs, busy, first.id, JobState::Running).await;
+
+    let job = jobs.submit(other, upload("c.txt", "text")).await.unwrap();
+    wait_for(&jobs, other, job.id, JobState::Succeeded).await;
+
+    let second = jobs.get(busy, second.id).await.unwrap();
+    assert_eq!(second.state, JobState::Queued);
+}
+
+#[tokio::test]
+async fn test_cancel_frees_tenant_slot() {
+    let jobs = manager(JobManagerConfig {
+        max_running_per_tenant: 1,
+        max_pending_per_tenant: 2,
+        ..JobManagerConfig::default()
+    });
+    let tenant = Uuid::now_v7();
+
+    let slow = jobs.submit(tenant, upload("a.slow", "")).await.unwrap();
+    let queued = jobs.submit(tenant, upload("b.txt", "text")).await.unwrap();
+    wait_for(&jobs, tenant, slow.id, JobState::Running).await;
+
+    let cancelled = jobs.cancel(tenant, slow.id).await.unwrap();
+    assert_eq!(cancelled.state, JobState::Cancelled);
+    assert!(cancelled.expires_at.is_some());
+
+    let done = wait_for(&jobs, tenant, queued.id, JobState::Succeeded).await;
+    let unchanged = jobs.cancel(tenant, done.id).await.unwrap();
+    assert_eq!(unchanged.state, JobState::Succeeded);
+}
+
+#[tokio::test]
+async fn test_finished_jobs_expire() {
+    let jobs = manager(JobManagerConfig {
+        result_ttl: Duration::from_millis(300),
+        ..JobManagerConfig::default()
+    });
+    let tenant = Uuid::now_v7();
+
+    let job = jobs.submit(tenant, upload("a.txt", "text")).await.unwrap();
+    wait_for(&jobs, tenant, job.id, JobState::Succeeded).await;
+
+    tokio::time::sleep(Duration::from_millis(400)).await;
+    let err = jobs.result(tenant, job.id).await.unwrap_err();
+    assert!(matches!(err, DomainError::JobNotFound { .. }));
+}
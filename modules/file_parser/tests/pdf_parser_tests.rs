@@ -0,0 +1,249 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::text_of;
+use file_parser::domain::ir::ParsedBlock;
+use file_parser::domain::parser::FileParserBackend;
+use file_parser::infra::parsers::PdfParser;
+use pdf_extract::content::{Content, Operation};
+use pdf_extract::{Dictionary, Document, Object, Stream, StringFormat};
+use std::path::PathBuf;
+
+/// Helper to get the path to test data files
+fn get_test_file_path(filename: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .join("testing/e2e/testdata/pdf")
+        .join(filename)
+}
+
+fn literal(text: &str) -> Object {
+    Object::String(text.as_bytes().to_vec(), StringFormat::Literal)
+}
+
+fn dict(entries: Vec<(&str, Object)>) -> Dictionary {
+    let mut dict = Dictionary::new();
+    for (key, value) in entries {
+        dict.set(key, value);
+    }
+    dict
+}
+
+/// Text drawn at `(x, y)` from the bottom-left corner in Helvetica of `size`
+fn text(x: i64, y: i64, size: i64, text: &str) -> Vec<Operation> {
+    vec![
+        Operation::new("BT", vec![]),
+        Operation::new("Tf", vec!["F1".into(), size.into()]),
+        Operation::new("Td", vec![x.into(), y.into()]),
+        Operation::new("Tj", vec![literal(text)]),
+        Operation::new("ET", vec![]),
+    ]
+}
+
+/// Build a PDF with one content stream per page and a document information
+/// dictionary
+fn build_pdf(pages: Vec<Vec<Operation>>) -> Vec<u8> {
+    let mut doc = Document::with_version("1.5");
+    let pages_id = doc.new_object_id();
+    let font_id = doc.add_object(dict(vec![
+        ("Type", "Font".into()),
+        ("Subtype", "Type1".into()),
+        ("BaseFont", "Helvetica".into()),
+        ("Encoding", "WinAnsiEncoding".into()),
+    ]));
+    let resources_id = doc.add_object(dict(vec![(
+        "Font",
+        dict(vec![("F1", font_id.into())]).into(),
+    )]));
+
+    let kids: Vec<Object> = pages
+        .into_iter()
+        .map(|operations| {
+            let content = Content { operations }.encode().unwrap();
+            let content_id = doc.add_object(Stream::new(Dictionary::new(), content));
+            doc.add_object(dict(vec![
+                ("Type", "Page".into()),
+                ("Parent", pages_id.into()),
+                ("Contents", content_id.into()),
+            ]))
+            .into()
+        })
+        .collect();
+    let count = i64::try_from(kids.len()).unwrap();
+    doc.objects.insert(
+        pages_id,
+        dict(vec![
+            ("Type", "Pages".into()),
+            ("Kids", kids.into()),
+            ("Count", count.into()),
+            ("Resources", resources_id.into()),
+            (
+                "MediaBox",
+                vec![0.into(), 0.into(), 595.into(), 842.into()].into(),
+            ),
+        ])
+        .into(),
+    );
+    let catalog_id = doc.add_object(dict(vec![
+        ("Type", "Catalog".into()),
+        ("Pages", pages_id.into()),
+    ]));
+    let info_id = doc.add_object(dict(vec![
+        ("Title", literal("Quarterly Report")),
+        ("Author", literal("Jane Doe")),
+        ("CreationDate", literal("D:20240315093000+02'00'")),
+        ("ModDate", literal("D:20240401120000Z")),
+    ]));
+    doc.trailer.set("Root", catalog_id);
+    doc.trailer.set("Info", info_id);
+
+    let mut bytes = Vec::new();
+    doc.save_to(&mut bytes).unwrap();
+    bytes
+}
+
+fn report_pdf() -> Vec<u8> {
+    let mut first = Vec::new();
+    first.extend(text(72, 760, 24, "Quarterly Report"));
+    first.extend(text(72, 720, 16, "Summary"));
+    first.extend(text(72, 696, 11, "Revenue grew in every region"));
+    first.extend(text(72, 682, 11, "during the quarter."));
+    for (row, y) in [
+        (["Region", "Revenue"], 640),
+        (["North", "120"], 626),
+        (["South", "95"], 612),
+    ] {
+        first.extend(text(72, y, 11, row[0]));
+        first.extend(text(300, y, 11, row[1]));
+    }
+    let mut second = Vec::new();
+    second.extend(text(72, 760, 16, "Outlook"));
+    second.extend(text(72, 736, 11, "Growth is expected to continue."));
+    build_pdf(vec![first, second])
+}
+
+#[tokio::test]
+async fn test_pdf_parser_metadata() {
+    let parser = PdfParser::new();
+    let doc = parser
+        .parse_bytes(Some("report.pdf"), None, report_pdf().into())
+        .await
+        .unwrap();
+
+    assert_eq!(doc.title.as_deref(), Some("Quarterly Report"));
+    assert_eq!(doc.meta.original_filename.as_deref(), Some("report.pdf"));
+    assert_eq!(doc.meta.author.as_deref(), Some("Jane Doe"));
+    let created_at = doc.meta.created_at.unwrap();
+    assert_eq!(created_at.unix_timestamp(), 1_710_487_800);
+    assert_eq!(
+        doc.meta.modified_at.unwrap().unix_timestamp(),
+        1_711_972_800
+    );
+}
+
+#[tokio::test]
+async fn test_pdf_parser_layout() {
+    let parser = PdfParser::new();
+    let doc = parser
+        .parse_bytes(Some("report.pdf"), None, report_pdf().into())
+        .await
+        .unwrap();
+
+    let summary: Vec<String> = doc
+        .blocks
+        .iter()
+        .map(|block| match block {
+            ParsedBlock::Heading {
+                level,
+                inlines,
+                location,
+            } => format!("h{level} p{} {}", location.unwrap().page, text_of(inlines)),
+            ParsedBlock::Paragraph { inlines, location } => {
+                format!("p p{} {}", location.unwrap().page, text_of(inlines))
+            }
+            ParsedBlock::Table(table) => {
+                format!(
+                    "table p{} {}",
+                    table.location.unwrap().page,
+                    table.rows.len()
+                )
+            }
+            ParsedBlock::PageBreak => "break".to_owned(),
+            other => format!("{other:?}"),
+        })
+        .collect();
+
+    assert_eq!(
+        summary,
+        [
+            "h1 p1 Quarterly Report",
+            "h2 p1 Summary",
+            "p p1 Revenue grew in every region during the quarter.",
+            "table p1 3",
+            "break",
+            "h2 p2 Outlook",
+            "p p2 Growth is expected to continue.",
+        ]
+    );
+
+    let ParsedBlock::Table(table) = &doc.blocks[3] else {
+        panic!("expected a table");
+    };
+    assert!(table.rows[0].is_header);
+    let cells: Vec<String> = table.rows[1]
+        .cells
+        .iter()
+        .map(|cell| match &cell.blocks[0] {
+            ParsedBlock::Paragraph { inlines, .. } => text_of(inlines),
+            other => panic!("unexpected cell block: {other:?}"),
+        })
+        .collect();
+    assert_eq!(cells, ["North", "120"]);
+
+    // Bounding boxes are measured from the top of the page
+    let bbox = table.location.unwrap().bbox.unwrap();
+    assert!((bbox.x0 - 72.0).abs() < 0.5, "{bbox:?}");
+    assert!(
+        bbox.y0 > 842.0 - 650.0 && bbox.y1 < 842.0 - 600.0,
+        "{bbox:?}"
+    );
+}
+
+#[tokio::test]
+async fn test_pdf_parser_skips_empty_pages() {
+    let parser = PdfParser::new();
+    let doc = parser
+        .parse_local_path(&get_test_file_path(
+            "test_file_three_pages_two_empty_en.pdf",
+        ))
+        .await
+        .unwrap();
+
+    // Only page 2 has text, so there is nothing to separate
+    let pages: Vec<Option<u32>> = doc
+        .blocks
+        .iter()
+        .map(|b| match b {
+            ParsedBlock::Paragraph { location, .. } => location.map(|l| l.page),
+            other => panic!("unexpected block: {other:?}"),
+        })
+        .collect();
+    assert_eq!(pages, [Some(2)]);
+}
+
+#[tokio::test]
+async fn test_pdf_parser_invalid_bytes() {
+    let parser = PdfParser::new();
+    let result = parser
+        .parse_bytes(
+            Some("broken.pdf"),
+            None,
+            bytes::Bytes::from_static(b"not a pdf"),
+        )
+        .await;
+    assert!(result.is_err());
+}
@@ -0,0 +1,150 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::text_of;
+use file_parser::domain::ir::{Inline, ParsedBlock, ParsedDocument};
+use file_parser::domain::parser::FileParserBackend;
+use file_parser::infra::parsers::RtfParser;
+
+async fn parse(rtf: &str) -> ParsedDocument {
+    RtfParser::new()
+        .parse_bytes(
+            Some("sample.rtf"),
+            Some("application/rtf"),
+            bytes::Bytes::from(rtf.to_owned()),
+        )
+        .await
+        .expect("Failed to parse RTF")
+}
+
+#[tokio::test]
+async fn test_rtf_structure_and_metadata() {
+    let rtf = r#"{\rtf1\ansi\ansicpg1252\deff0
+{\fonttbl{\f0\fswiss\fcharset0 Arial;}{\f1\fnil\fcharset2 Symbol;}}
+{\colortbl;\red0\green0\blue0;}
+{\stylesheet{\s0 Normal;}{\s1\outlinelevel0 heading 1;}{\s2 heading 2;}}
+{\info{\title Project Plan}{\author Jane Doe}{\creatim\yr2024\mo3\dy15\hr9\min30}{\printim\yr1999\mo1\dy1}}
+{\*\generator Some Writer;}
+\pard\s1 Introduction\par
+\pard\s2 Scope\par
+\pard Plain, {\b bold} and {\i italic\i0} text with caf\'e9 and \u8364?uro.\par
+\pard{\listtext\f1\'b7\tab}\ls1\ilvl0 Bullet one\par
+\pard{\listtext 1.\tab}\ls2\ilvl1 Numbered\par
+\pard See {\field{\*\fldinst HYPERLINK "https://example.com"}{\fldrslt example}} now.\par
+{\pict\pngblip 89504e470d0a}
+\trowd\cellx1000\cellx2000
+\pard\intbl Name\cell Value\cell\row
+\trowd\cellx1000\cellx2000
+\pard\intbl Alpha\cell 1\cell\row
+\pard After table\par
+\page
+\pard Last page\par
+}"#;
+    let doc = parse(rtf).await;
+
+    assert_eq!(doc.meta.content_type.as_deref(), Some("application/rtf"));
+    assert_eq!(doc.title.as_deref(), Some("Project Plan"));
+    assert_eq!(doc.meta.author.as_deref(), Some("Jane Doe"));
+    let created = doc.meta.created_at.unwrap();
+    assert_eq!(
+        (created.year(), created.hour(), created.minute()),
+        (2024, 9, 30)
+    );
+
+    let blocks = &doc.blocks;
+    assert!(
+        matches!(&blocks[0], ParsedBlock::Heading { level: 1, inlines, .. } if text_of(inlines) == "Introduction")
+    );
+    assert!(
+        matches!(&blocks[1], ParsedBlock::Heading { level: 2, inlines, .. } if text_of(inlines) == "Scope")
+    );
+
+    let ParsedBlock::Paragraph { inlines, .. } = &blocks[2] else {
+        panic!("Expected paragraph, got {:?}", blocks[2]);
+    };
+    assert_eq!(
+        text_of(inlines),
+        "Plain, bold and italic text with caf\u{e9} and \u{20ac}uro."
+    );
+    assert!(inlines.iter().any(
+        |inline| matches!(inline, Inline::Text { text, style } if text == "bold" && style.bold)
+    ));
+    assert!(inlines.iter().any(
+        |inline| matches!(inline, Inline::Text { text, style } if text == "italic" && style.italic)
+    ));
+
+    assert!(matches!(
+        &blocks[3],
+        ParsedBlock::ListItem {
+            level: 0,
+            ordered: false,
+            ..
+        }
+    ));
+    assert!(matches!(
+        &blocks[4],
+        ParsedBlock::ListItem {
+            level: 1,
+            ordered: true,
+            ..
+        }
+    ));
+
+    let ParsedBlock::Paragraph { inlines, .. } = &blocks[5] else {
+        panic!("Expected paragraph, got {:?}", blocks[5]);
+    };
+    assert_eq!(text_of(inlines), "See example now.");
+    assert!(inlines.iter().any(|inline| matches!(
+        inline,
+        Inline::Link { text, target, .. } if text == "example" && target == "https://example.com"
+    )));
+
+    let ParsedBlock::Table(table) = &blocks[6] else {
+        panic!("Expected table, got {:?}", blocks[6]);
+    };
+    assert_eq!(table.rows.len(), 2);
+    assert!(table.rows[0].is_header);
+    let ParsedBlock::Paragraph { inlines, .. } = &table.rows[1].cells[1].blocks[0] else {
+        panic!("Expected cell paragraph");
+    };
+    assert_eq!(text_of(inlines), "1");
+
+    assert!(
+        matches!(&blocks[7], ParsedBlock::Paragraph { inlines, .. } if text_of(inlines) == "After table")
+    );
+    assert_eq!(blocks[8], ParsedBlock::PageBreak);
+    assert!(
+        matches!(&blocks[9], ParsedBlock::Paragraph { inlines, .. } if text_of(inlines) == "Last page")
+    );
+    assert_eq!(blocks.len(), 10);
+}
+
+#[tokio::test]
+async fn test_rtf_font_charset_selects_code_page() {
+    // The bytes spell a Russian greeting in Windows-1251
+    let rtf = r"{\rtf1\ansi\ansicpg1252{\fonttbl{\f0\fcharset204 Times New Roman Cyr;}}\f0 \'cf\'f0\'e8\'e2\'e5\'f2\par}";
+    let doc = parse(rtf).await;
+
+    let ParsedBlock::Paragraph { inlines, .. } = &doc.blocks[0] else {
+        panic!("Expected paragraph, got {:?}", doc.blocks[0]);
+    };
+    assert_eq!(
+        text_of(inlines),
+        "\u{41f}\u{440}\u{438}\u{432}\u{435}\u{442}"
+    );
+    // No info group: the file name is the title
+    assert_eq!(doc.title.as_deref(), Some("sample.rtf"));
+}
+
+#[tokio::test]
+async fn test_rejects_non_rtf_content() {
+    let result = RtfParser::new()
+        .parse_bytes(
+            Some("fake.rtf"),
+            None,
+            bytes::Bytes::from_static(b"plain text"),
+        )
+        .await;
+    assert!(result.is_err());
+}
@@ -0,0 +1,157 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::text_of;
+use file_parser::domain::ir::{Inline, ParsedBlock, ParsedDocument};
+use file_parser::domain::parser::FileParserBackend;
+use file_parser::infra::parsers::StructuredDataParser;
+
+async fn parse(name: &str, text: &str) -> ParsedDocument {
+    StructuredDataParser::new()
+        .parse_bytes(Some(name), None, bytes::Bytes::from(text.to_owned()))
+        .await
+        .expect("Failed to parse structured data")
+}
+
+/// The list items of a document as `(level, ordered, text)`
+fn items(doc: &ParsedDocument) -> Vec<(u8, bool, String)> {
+    doc.blocks
+        .iter()
+        .filter_map(|block| match block {
+            ParsedBlock::ListItem {
+                level,
+                ordered,
+                blocks,
+            } => match blocks.first() {
+                Some(ParsedBlock::Paragraph { inlines, .. }) => {
+                    Some((*level, *ordered, text_of(inlines)))
+                }
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_json_as_nested_list() {
+    let doc = parse(
+        "config.json",
+        r#"{"name": "demo", "tags": ["a", "b"], "server": {"port": 8080, "tls": null}}"#,
+    )
+    .await;
+
+    assert_eq!(doc.meta.content_type.as_deref(), Some("application/json"));
+    assert_eq!(
+        items(&doc),
+        vec![
+            (0, false, "name: demo".to_owned()),
+            (0, false, "tags".to_owned()),
+            (1, true, "a".to_owned()),
+            (1, true, "b".to_owned()),
+            (0, false, "server".to_owned()),
+            (1, false, "port: 8080".to_owned()),
+            (1, false, "tls: null".to_owned()),
+        ]
+    );
+    let ParsedBlock::ListItem { blocks, .. } = &doc.blocks[0] else {
+        panic!("Expected list item");
+    };
+    let ParsedBlock::Paragraph { inlines, .. } = &blocks[0] else {
+        panic!("Expected paragraph");
+    };
+    assert!(matches!(&inlines[0], Inline::Text { text, style } if text == "name" && style.bold));
+}
+
+#[tokio::test]
+async fn test_yaml_documents_are_separated() {
+    let doc = parse(
+        "deploy.yaml",
+        "kind: Service\nports:\n  - name: http\n    port: 80\n---\nkind: Deployment\n",
+    )
+    .await;
+
+    assert_eq!(doc.meta.content_type.as_deref(), Some("application/yaml"));
+    assert_eq!(
+        items(&doc),
+        vec![
+            (0, false, "kind: Service".to_owned()),
+            (0, false, "ports".to_owned()),
+            (1, true, "Item 1".to_owned()),
+            (2, false, "name: http".to_owned()),
+            (2, false, "port: 80".to_owned()),
+            (0, false, "kind: Deployment".to_owned()),
+        ]
+    );
+    assert!(
+        doc.blocks
+            .iter()
+            .any(|block| matches!(block, ParsedBlock::HorizontalRule))
+    );
+}
+
+#[tokio::test]
+async fn test_xml_attributes() {
+    let doc = parse(
+        "feed.xml",
+        r#"<?xml version="1.0"?><feed lang="en"><title>News</title></feed>"#,
+    )
+    .await;
+
+    assert_eq!(doc.meta.content_type.as_deref(), Some("application/xml"));
+    assert_eq!(
+        items(&doc),
+        vec![
+            (0, false, "feed".to_owned()),
+            (1, false, "@lang: en".to_owned()),
+            (1, false, "title: News".to_owned()),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_large_document_falls_back_to_code() {
+    let values: Vec<String> = (0..2000).map(|i| i.to_string()).collect();
+    let json = format!("[{}]", values.join(","));
+    let doc = parse("numbers.json", &json).await;
+
+    assert_eq!(doc.blocks.len(), 1);
+    assert!(matches!(
+        &doc.blocks[0],
+        ParsedBlock::CodeBlock { language: Some(language), code }
+            if language == "json" && *code == json
+    ));
+}
+
+#[tokio::test]
+async fn test_code_block_size_cap() {
+    // Nested too deeply for a list, so the source is kept as code
+    let deep = format!(
+        "{}\"{}\"{}",
+        "[".repeat(10),
+        "x".repeat(100 * 1024),
+        "]".repeat(10)
+    );
+    let doc = parse("deep.json", &deep).await;
+    let ParsedBlock::CodeBlock { code, .. } = &doc.blocks[0] else {
+        panic!("Expected code block, got {:?}", doc.blocks[0]);
+    };
+    assert_eq!(code.len(), 64 * 1024);
+    let Some(ParsedBlock::Paragraph { inlines, .. }) = doc.blocks.get(1) else {
+        panic!("Expected truncation note");
+    };
+    assert!(text_of(inlines).starts_with("Showing the first 65536 of"));
+}
+
+#[tokio::test]
+async fn test_invalid_json_is_an_error() {
+    let result = StructuredDataParser::new()
+        .parse_bytes(
+            Some("broken.json"),
+            None,
+            bytes::Bytes::from_static(b"{\"a\":"),
+        )
+        .await;
+    assert!(result.is_err());
+}
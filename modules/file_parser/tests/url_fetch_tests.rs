@@ -0,0 +1,173 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! `parse_url` against a local HTTP server: fetch policy, size cap and sniffing
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::Router;
+use axum::body::Body;
+use axum::http::header;
+use axum::response::{IntoResponse, Redirect};
+use axum::routing::get;
+use bytes::Bytes;
+use file_parser::config::UrlFetchConfig;
+use file_parser::domain::error::DomainError;
+use file_parser::domain::parser::FileParserBackend;
+use file_parser::domain::service::{FileParserService, ServiceConfig};
+use file_parser::infra::parsers::{HtmlParser, PlainTextParser};
+use url::Url;
+
+const MAX_BYTES: usize = 1024;
+
+async fn serve() -> SocketAddr {
+    let app = Router::new()
+        .route("/notes.txt", get(|| async { "Hello from the server" }))
+        .route(
+            "/page",
+            get(|| async {
+                (
+                    [(header::CONTENT_TYPE, "application/octet-stream")],
+                    "<!DOCTYPE html><html><head><title>Sniffed</title></head><body><p>Hi</p></body></html>",
+                )
+            }),
+        )
+        .route(
+            "/elsewhere.txt",
+            get(|| async { Redirect::temporary("http://localhost:1/notes.txt") }),
+        )
+        .route("/loop.txt", get(|| async { Redirect::temporary("/loop.txt") }))
+        .route("/large.txt", get(|| async { "x".repeat(MAX_BYTES + 1) }))
+        .route(
+            "/streamed.txt",
+            get(|| async {
+                // Chunked, so the size is only known while reading
+                let chunks = (0..4).map(|_| Ok::<_, std::io::Error>(Bytes::from("y".repeat(400))));
+                Body::from_stream(futures_util::stream::iter(chunks)).into_response()
+            }),
+        );
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    addr
+}
+
+fn service(url_fetch: UrlFetchConfig) -> FileParserService {
+    let parsers: Vec<Arc<dyn FileParserBackend>> = vec![
+        Arc::new(PlainTextParser::new()),
+        Arc::new(HtmlParser::new()),
+    ];
+    let config = ServiceConfig {
+        max_file_size_bytes: MAX_BYTES,
+        url_fetch,
+        ..ServiceConfig::default()
+    };
+    FileParserService::new(parsers, config).unwrap()
+}
+
+fn local_policy() -> UrlFetchConfig {
+    UrlFetchConfig {
+        block_private_ips: false,
+        ..UrlFetchConfig::default()
+    }
+}
+
+fn url(addr: SocketAddr, path: &str) -> Url {
+    Url::parse(&format!("http://{addr}{path}")).unwrap()
+}
+
+#[tokio::test]
+async fn test_default_policy_blocks_loopback() {
+    let addr = serve().await;
+    let service = service(UrlFetchConfig::default());
+
+    let err = service
+        .parse_url(&url(addr, "/notes.txt"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DomainError::UrlNotAllowed { .. }), "{err}");
+
+    // Hostnames are checked after resolution
+    let localhost = Url::parse(&format!("http://localhost:{}/notes.txt", addr.port())).unwrap();
+    let err = service.parse_url(&localhost).await.unwrap_err();
+    assert!(matches!(err, DomainError::UrlNotAllowed { .. }), "{err}");
+}
+
+#[tokio::test]
+async fn test_fetch_allowed_url() {
+    let addr = serve().await;
+    let service = service(local_policy());
+
+    let document = service.parse_url(&url(addr, "/notes.txt")).await.unwrap();
+    assert!(!document.blocks.is_empty());
+}
+
+#[tokio::test]
+async fn test_fetch_reports_progress() {
+    let addr = serve().await;
+    let service = service(local_policy());
+
+    let mut reports = Vec::new();
+    service
+        .parse_url_with_progress(&url(addr, "/notes.txt"), |read, total| {
+            reports.push((read, total));
+        })
+        .await
+        .unwrap();
+    assert_eq!(reports.last(), Some(&(21, Some(21))));
+}
+
+#[tokio::test]
+async fn test_redirects_are_checked() {
+    let addr = serve().await;
+    let service = service(UrlFetchConfig {
+        allowed_hosts: vec!["127.0.0.1".to_owned()],
+        ..local_policy()
+    });
+
+    let err = service
+        .parse_url(&url(addr, "/elsewhere.txt"))
+        .await
+        .unwrap_err();
+    match err {
+        DomainError::UrlNotAllowed { reason, .. } => {
+            assert!(reason.contains("localhost"), "{reason}");
+        }
+        other => panic!("unexpected error: {other}"),
+    }
+
+    let err = service
+        .parse_url(&url(addr, "/loop.txt"))
+        .await
+        .unwrap_err();
+    match err {
+        DomainError::UrlNotAllowed { reason, .. } => {
+            assert!(reason.contains("redirects"), "{reason}");
+        }
+        other => panic!("unexpected error: {other}"),
+    }
+}
+
+#[tokio::test]
+async fn test_size_cap_applies_while_downloading() {
+    let addr = serve().await;
+    let service = service(local_policy());
+
+    for path in ["/large.txt", "/streamed.txt"] {
+        let err = service.parse_url(&url(addr, path)).await.unwrap_err();
+        assert!(
+            matches!(err, DomainError::InvalidRequest { ref message } if message.contains("exceeds")),
+            "{path}: {err}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_content_is_sniffed_without_extension() {
+    let addr = serve().await;
+    let service = service(local_policy());
+
+    let document = service.parse_url(&url(addr, "/page")).await.unwrap();
+    assert_eq!(document.title.as_deref(), Some("Sniffed"));
+}
@@ -308,7 +308,7 @@ async fn test_xlsx_parser_formula_cells() {
         .flat_map(|row| {
             row.cells.iter().filter_map(|cell| {
                 cell.blocks.first().and_then(|block| {
-                    if let file_parser::domain::ir::ParsedBlock::Paragraph { inlines } = block {
+                    if let file_parser::domain::ir::ParsedBlock::Paragraph { inlines, .. } = block {
                         inlines.first().and_then(|inline| {
                             if let file_parser::domain::ir::Inline::Text { text, .. } = inline {
                                 Some(text.clone())
@@ -14,6 +14,7 @@ mod tests {
             tenant_id,
             theme: Some("dark".to_owned()),
             language: Some("en".to_owned()),
+            version: 1,
         };
 
         let dto: dto::SimpleUserSettingsDto = settings.into();
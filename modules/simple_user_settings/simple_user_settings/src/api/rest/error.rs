@@ -14,6 +14,7 @@ pub fn domain_error_to_problem(e: &DomainError, instance: &str) -> Problem {
         DomainError::Validation { field, message } => {
             build_validation_problem(field, message, instance, trace_id)
         }
+        DomainError::PreconditionFailed => build_precondition_failed_problem(instance, trace_id),
         DomainError::Forbidden(msg) => build_forbidden_problem(e, msg, instance, trace_id),
         DomainError::Internal(msg) => build_internal_problem(e, msg, instance, trace_id),
         DomainError::Database(_) => build_database_problem(e, instance, trace_id),
@@ -41,6 +42,14 @@ fn build_validation_problem(
     )
 }
 
+fn build_precondition_failed_problem(instance: &str, trace_id: Option<String>) -> Problem {
+    ErrorCode::settings_simple_user_settings_precondition_failed_v1().with_context(
+        "Settings have been modified; refresh them and retry with the current ETag",
+        instance,
+        trace_id,
+    )
+}
+
 fn build_forbidden_problem(
     e: &DomainError,
     msg: &str,
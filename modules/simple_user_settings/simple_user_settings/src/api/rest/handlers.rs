@@ -1,9 +1,10 @@
 use std::sync::Arc;
 
-use axum::{Json, extract::Extension};
+use axum::{Json, extract::Extension, http::HeaderMap, response::Response};
 use modkit::api::prelude::*;
 use modkit_auth::axum_ext::Authz;
-use simple_user_settings_sdk::models::SimpleUserSettingsUpdate;
+use modkit_security::SecurityContext;
+use simple_user_settings_sdk::models::{SimpleUserSettings, SimpleUserSettingsUpdate};
 
 use crate::api::rest::routes::ConcreteService;
 
@@ -14,30 +15,59 @@ use super::dto::{
 pub async fn get_settings(
     Authz(ctx): Authz,
     Extension(svc): Extension<Arc<ConcreteService>>,
-) -> ApiResult<JsonBody<SimpleUserSettingsDto>> {
+    headers: HeaderMap,
+) -> ApiResult<Response> {
     let settings = svc.get_settings(&ctx).await?;
-    Ok(Json(settings.into()))
+    Ok(tagged_response(&headers, settings))
 }
 
 pub async fn update_settings(
     Authz(ctx): Authz,
     Extension(svc): Extension<Arc<ConcreteService>>,
+    headers: HeaderMap,
     Json(req): Json<UpdateSimpleUserSettingsRequest>,
-) -> ApiResult<impl IntoResponse> {
+) -> ApiResult<Response> {
+    let if_version = expected_version(&svc, &ctx, &headers).await?;
     let update = SimpleUserSettingsUpdate {
         theme: req.theme,
         language: req.language,
     };
-    let settings = svc.update_settings(&ctx, update).await?;
-    let dto: SimpleUserSettingsDto = settings.into();
-    Ok((StatusCode::OK, Json(dto)))
+    let settings = svc.update_settings(&ctx, update, if_version).await?;
+    Ok(tagged_response(&HeaderMap::new(), settings))
 }
 
 pub async fn patch_settings(
     Authz(ctx): Authz,
     Extension(svc): Extension<Arc<ConcreteService>>,
+    headers: HeaderMap,
     Json(req): Json<PatchSimpleUserSettingsRequest>,
-) -> ApiResult<JsonBody<SimpleUserSettingsDto>> {
-    let settings = svc.patch_settings(&ctx, req.into()).await?;
-    Ok(Json(settings.into()))
+) -> ApiResult<Response> {
+    let if_version = expected_version(&svc, &ctx, &headers).await?;
+    let settings = svc.patch_settings(&ctx, req.into(), if_version).await?;
+    Ok(tagged_response(&HeaderMap::new(), settings))
+}
+
+/// Settings as JSON, tagged with their revision.
+fn tagged_response(headers: &HeaderMap, settings: SimpleUserSettings) -> Response {
+    let etag = ETag::from_version(settings.version);
+    ok_json_tagged(headers, &etag, SimpleUserSettingsDto::from(settings))
+}
+
+/// Evaluate `If-Match` against the `ETag` of the currently stored settings,
+/// returning the revision the write must still find.
+///
+/// The service checks that revision again as part of the write, so a
+/// concurrent writer that got there first fails the request with 412 too.
+/// Skips the extra read entirely for unconditional requests.
+async fn expected_version(
+    svc: &ConcreteService,
+    ctx: &SecurityContext,
+    headers: &HeaderMap,
+) -> ApiResult<Option<i64>> {
+    if !headers.contains_key(axum::http::header::IF_MATCH) {
+        return Ok(None);
+    }
+    let current = svc.get_settings(ctx).await?;
+    check_if_match(headers, &ETag::from_version(current.version))?;
+    Ok(Some(current.version))
 }
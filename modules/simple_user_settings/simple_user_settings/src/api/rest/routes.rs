@@ -58,7 +58,9 @@ pub fn register_routes(
     router = OperationBuilder::get("/simple-user-settings/v1/settings")
         .operation_id("settings.get_settings")
         .summary("Get user settings")
-        .description("Retrieve settings for the authenticated user")
+        .description(
+            "Retrieve settings for the authenticated user. Supports `If-None-Match` cache validation.",
+        )
         .tag("Settings")
         .require_auth(&Resource::Settings, &Action::Read)
         .require_license_features::<License>([])
@@ -68,6 +70,7 @@ pub fn register_routes(
             StatusCode::OK,
             "Settings retrieved",
         )
+        .with_if_none_match()
         .error_401(openapi)
         .error_403(openapi)
         .error_500(openapi)
@@ -76,7 +79,9 @@ pub fn register_routes(
     router = OperationBuilder::post("/simple-user-settings/v1/settings")
         .operation_id("settings.update_settings")
         .summary("Update user settings")
-        .description("Full update of user settings (POST semantics)")
+        .description(
+            "Full update of user settings (POST semantics). Supports `If-Match` optimistic concurrency.",
+        )
         .tag("Settings")
         .require_auth(&Resource::Settings, &Action::Write)
         .require_license_features::<License>([])
@@ -87,6 +92,7 @@ pub fn register_routes(
             StatusCode::OK,
             "Settings updated",
         )
+        .with_if_match(openapi)
         .error_400(openapi)
         .error_401(openapi)
         .error_403(openapi)
@@ -97,7 +103,9 @@ pub fn register_routes(
     router = OperationBuilder::patch("/simple-user-settings/v1/settings")
         .operation_id("settings.patch_settings")
         .summary("Partially update user settings")
-        .description("Partial update of user settings (PATCH semantics)")
+        .description(
+            "Partial update of user settings (PATCH semantics). Supports `If-Match` optimistic concurrency.",
+        )
         .tag("Settings")
         .require_auth(&Resource::Settings, &Action::Write)
         .require_license_features::<License>([])
@@ -108,6 +116,7 @@ pub fn register_routes(
             StatusCode::OK,
             "Settings patched",
         )
+        .with_if_match(openapi)
         .error_400(openapi)
         .error_401(openapi)
         .error_403(openapi)
@@ -9,6 +9,9 @@ pub enum DomainError {
     #[error("Validation error on field '{field}': {message}")]
     Validation { field: String, message: String },
 
+    #[error("Settings were modified since the expected revision")]
+    PreconditionFailed,
+
     #[error("Access forbidden: {0}")]
     Forbidden(String),
 
@@ -41,6 +44,7 @@ impl From<DomainError> for SettingsError {
         match e {
             DomainError::NotFound => Self::not_found(),
             DomainError::Validation { field, message } => Self::validation(field, message),
+            DomainError::PreconditionFailed => Self::precondition_failed(),
             DomainError::Forbidden(_) => Self::forbidden(),
             DomainError::Internal(_) | DomainError::Database(_) => Self::internal(),
         }
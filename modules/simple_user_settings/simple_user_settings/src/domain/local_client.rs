@@ -35,7 +35,7 @@ impl<R: SettingsRepository + 'static> SimpleUserSettingsClient for LocalClient<R
         update: SimpleUserSettingsUpdate,
     ) -> Result<SimpleUserSettings, SettingsError> {
         self.service
-            .update_settings(ctx, update)
+            .update_settings(ctx, update, None)
             .await
             .map_err(Into::into)
     }
@@ -46,7 +46,7 @@ impl<R: SettingsRepository + 'static> SimpleUserSettingsClient for LocalClient<R
         patch: SimpleUserSettingsPatch,
     ) -> Result<SimpleUserSettings, SettingsError> {
         self.service
-            .patch_settings(ctx, patch)
+            .patch_settings(ctx, patch, None)
             .await
             .map_err(Into::into)
     }
@@ -1,7 +1,7 @@
 use async_trait::async_trait;
 use modkit_db::secure::DBRunner;
 use modkit_security::{AccessScope, SecurityContext};
-use simple_user_settings_sdk::models::{SimpleUserSettings, SimpleUserSettingsPatch};
+use simple_user_settings_sdk::models::SimpleUserSettings;
 
 use super::error::DomainError;
 
@@ -14,20 +14,17 @@ pub trait SettingsRepository: Send + Sync {
         ctx: &SecurityContext,
     ) -> Result<Option<SimpleUserSettings>, DomainError>;
 
-    async fn upsert_full<C: DBRunner>(
+    /// Store `theme` and `language` if the stored revision is still
+    /// `expected_version` (`0` when nothing is stored yet), raising it by one.
+    ///
+    /// Returns `None` without writing anything when the revision has moved on.
+    async fn save_if_version<C: DBRunner>(
         &self,
         conn: &C,
         scope: &AccessScope,
         ctx: &SecurityContext,
+        expected_version: i64,
         theme: Option<String>,
         language: Option<String>,
-    ) -> Result<SimpleUserSettings, DomainError>;
-
-    async fn upsert_patch<C: DBRunner>(
-        &self,
-        conn: &C,
-        scope: &AccessScope,
-        ctx: &SecurityContext,
-        patch: SimpleUserSettingsPatch,
-    ) -> Result<SimpleUserSettings, DomainError>;
+    ) -> Result<Option<SimpleUserSettings>, DomainError>;
 }
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use modkit_db::DBProvider;
+use modkit_db::secure::DBRunner;
 use modkit_security::{AccessScope, SecurityContext};
 use simple_user_settings_sdk::models::{
     SimpleUserSettings, SimpleUserSettingsPatch, SimpleUserSettingsUpdate,
@@ -50,49 +51,39 @@ impl<R: SettingsRepository> Service<R> {
         let conn = self.db.conn().map_err(DomainError::from)?;
         let scope = build_scope(ctx);
 
-        if let Some(settings) = self.repo.find_by_user(&conn, &scope, ctx).await? {
-            Ok(settings)
-        } else {
-            let user_id = ctx.subject_id();
-            let tenant_id = ctx.tenant_id();
-
-            Ok(SimpleUserSettings {
-                user_id,
-                tenant_id,
-                theme: None,
-                language: None,
-            })
-        }
+        self.current(&conn, &scope, ctx).await
     }
 
+    /// Replace the settings, if they are still at revision `if_version` when
+    /// one is given.
+    ///
+    /// # Errors
+    /// Returns `PreconditionFailed` if the stored revision is not `if_version`.
     pub async fn update_settings(
         &self,
         ctx: &SecurityContext,
         update: SimpleUserSettingsUpdate,
+        if_version: Option<i64>,
     ) -> Result<SimpleUserSettings, DomainError> {
         self.validate_field(SettingsFields::THEME, &update.theme)?;
         self.validate_field(SettingsFields::LANGUAGE, &update.language)?;
 
-        let conn = self.db.conn().map_err(DomainError::from)?;
-        let scope = build_scope(ctx);
-
-        let settings = self
-            .repo
-            .upsert_full(
-                &conn,
-                &scope,
-                ctx,
-                Some(update.theme),
-                Some(update.language),
-            )
-            .await?;
-        Ok(settings)
+        self.save(ctx, if_version, |_| {
+            (Some(update.theme.clone()), Some(update.language.clone()))
+        })
+        .await
     }
 
+    /// Update the given fields, if the settings are still at revision
+    /// `if_version` when one is given.
+    ///
+    /// # Errors
+    /// Returns `PreconditionFailed` if the stored revision is not `if_version`.
     pub async fn patch_settings(
         &self,
         ctx: &SecurityContext,
         patch: SimpleUserSettingsPatch,
+        if_version: Option<i64>,
     ) -> Result<SimpleUserSettings, DomainError> {
         if let Some(ref theme) = patch.theme {
             self.validate_field(SettingsFields::THEME, theme)?;
@@ -101,11 +92,64 @@ impl<R: SettingsRepository> Service<R> {
             self.validate_field(SettingsFields::LANGUAGE, language)?;
         }
 
+        self.save(ctx, if_version, |current| {
+            (
+                patch.theme.clone().or_else(|| current.theme.clone()),
+                patch.language.clone().or_else(|| current.language.clone()),
+            )
+        })
+        .await
+    }
+
+    /// Stored settings, or empty ones at revision 0
+    async fn current<C: DBRunner>(
+        &self,
+        conn: &C,
+        scope: &AccessScope,
+        ctx: &SecurityContext,
+    ) -> Result<SimpleUserSettings, DomainError> {
+        let settings = self.repo.find_by_user(conn, scope, ctx).await?;
+        Ok(settings.unwrap_or_else(|| SimpleUserSettings {
+            user_id: ctx.subject_id(),
+            tenant_id: ctx.tenant_id(),
+            theme: None,
+            language: None,
+            version: 0,
+        }))
+    }
+
+    /// Write the theme and language that `merge` derives from the stored
+    /// settings, conditionally on the revision they were read at.
+    ///
+    /// A conditional request fails when that is not `if_version`. Otherwise a
+    /// write that lost a race is derived again from the new settings, so that
+    /// concurrent patches never undo each other.
+    async fn save(
+        &self,
+        ctx: &SecurityContext,
+        if_version: Option<i64>,
+        merge: impl Fn(&SimpleUserSettings) -> (Option<String>, Option<String>) + Send,
+    ) -> Result<SimpleUserSettings, DomainError> {
         let conn = self.db.conn().map_err(DomainError::from)?;
         let scope = build_scope(ctx);
 
-        let settings = self.repo.upsert_patch(&conn, &scope, ctx, patch).await?;
-        Ok(settings)
+        loop {
+            let current = self.current(&conn, &scope, ctx).await?;
+            if if_version.is_some_and(|version| version != current.version) {
+                return Err(DomainError::PreconditionFailed);
+            }
+
+            let (theme, language) = merge(&current);
+            let saved = self
+                .repo
+                .save_if_version(&conn, &scope, ctx, current.version, theme, language)
+                .await?;
+            match saved {
+                Some(settings) => return Ok(settings),
+                None if if_version.is_some() => return Err(DomainError::PreconditionFailed),
+                None => {}
+            }
+        }
     }
 
     fn validate_field(&self, field: &str, value: &str) -> Result<(), DomainError> {
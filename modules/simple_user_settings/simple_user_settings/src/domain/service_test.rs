@@ -85,6 +85,7 @@ mod tests {
                     theme: "dark".to_owned(),
                     language: "en".to_owned(),
                 },
+                None,
             )
             .await
             .unwrap();
@@ -113,6 +114,7 @@ mod tests {
                     theme: "light".to_owned(),
                     language: "es".to_owned(),
                 },
+                None,
             )
             .await
             .unwrap();
@@ -142,6 +144,7 @@ mod tests {
                     theme: too_long,
                     language: "en".to_owned(),
                 },
+                None,
             )
             .await;
 
@@ -169,6 +172,7 @@ mod tests {
                     theme: "dark".to_owned(),
                     language: too_long,
                 },
+                None,
             )
             .await;
 
@@ -195,6 +199,7 @@ mod tests {
                     theme: "dark".to_owned(),
                     language: "en".to_owned(),
                 },
+                None,
             )
             .await
             .unwrap();
@@ -207,6 +212,7 @@ mod tests {
                     theme: Some("light".to_owned()),
                     language: None,
                 },
+                None,
             )
             .await
             .unwrap();
@@ -234,6 +240,7 @@ mod tests {
                     theme: None,
                     language: Some(too_long),
                 },
+                None,
             )
             .await;
 
@@ -256,6 +263,7 @@ mod tests {
                     theme: "dark".to_owned(),
                     language: "en".to_owned(),
                 },
+                None,
             )
             .await
             .unwrap();
@@ -268,6 +276,7 @@ mod tests {
                     theme: None,
                     language: None,
                 },
+                None,
             )
             .await
             .unwrap();
@@ -291,6 +300,7 @@ mod tests {
                     theme: Some("dark".to_owned()),
                     language: None,
                 },
+                None,
             )
             .await
             .unwrap();
@@ -326,6 +336,7 @@ mod tests {
                     theme: "dark".to_owned(),
                     language: "en".to_owned(),
                 },
+                None,
             )
             .await
             .unwrap();
@@ -360,6 +371,7 @@ mod tests {
                     theme: "dark".to_owned(),
                     language: "en".to_owned(),
                 },
+                None,
             )
             .await
             .unwrap();
@@ -370,4 +382,126 @@ mod tests {
         assert_eq!(result.language, None);
         assert_eq!(result.tenant_id, tenant2.tenant_id());
     }
+
+    // =========================================================================
+    // Conditional write tests
+    // =========================================================================
+
+    #[tokio::test]
+    async fn test_writes_raise_version() {
+        let db = inmem_db().await;
+        let service = build_service(db, ServiceConfig::default());
+        let ctx = create_test_context();
+
+        assert_eq!(service.get_settings(&ctx).await.unwrap().version, 0);
+
+        let created = service
+            .patch_settings(
+                &ctx,
+                SimpleUserSettingsPatch {
+                    theme: Some("dark".to_owned()),
+                    language: None,
+                },
+                Some(0),
+            )
+            .await
+            .unwrap();
+        assert_eq!(created.version, 1);
+
+        let updated = service
+            .update_settings(
+                &ctx,
+                SimpleUserSettingsUpdate {
+                    theme: "light".to_owned(),
+                    language: "en".to_owned(),
+                },
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(updated.version, 2);
+        assert_eq!(service.get_settings(&ctx).await.unwrap(), updated);
+    }
+
+    #[tokio::test]
+    async fn test_stale_version_is_rejected() {
+        let db = inmem_db().await;
+        let service = build_service(db, ServiceConfig::default());
+        let ctx = create_test_context();
+
+        let first = service
+            .update_settings(
+                &ctx,
+                SimpleUserSettingsUpdate {
+                    theme: "dark".to_owned(),
+                    language: "en".to_owned(),
+                },
+                Some(0),
+            )
+            .await
+            .unwrap();
+
+        // A second writer that read the same (empty) settings loses
+        let err = service
+            .update_settings(
+                &ctx,
+                SimpleUserSettingsUpdate {
+                    theme: "light".to_owned(),
+                    language: "fr".to_owned(),
+                },
+                Some(0),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::PreconditionFailed));
+
+        let err = service
+            .patch_settings(
+                &ctx,
+                SimpleUserSettingsPatch {
+                    theme: Some("light".to_owned()),
+                    language: None,
+                },
+                Some(first.version + 1),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::PreconditionFailed));
+
+        assert_eq!(service.get_settings(&ctx).await.unwrap(), first);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_conditional_writes_have_one_winner() {
+        let db = inmem_db().await;
+        let service = Arc::new(build_service(db, ServiceConfig::default()));
+        let ctx = create_test_context();
+
+        let writes = ["dark", "light", "blue", "green"].map(|theme| {
+            let service = Arc::clone(&service);
+            let ctx = ctx.clone();
+            tokio::spawn(async move {
+                service
+                    .patch_settings(
+                        &ctx,
+                        SimpleUserSettingsPatch {
+                            theme: Some(theme.to_owned()),
+                            language: None,
+                        },
+                        Some(0),
+                    )
+                    .await
+            })
+        });
+
+        let mut winners = Vec::new();
+        for write in writes {
+            match write.await.unwrap() {
+                Ok(settings) => winners.push(settings),
+                Err(e) => assert!(matches!(e, DomainError::PreconditionFailed)),
+            }
+        }
+        assert_eq!(winners.len(), 1);
+        assert_eq!(service.get_settings(&ctx).await.unwrap(), winners[0]);
+    }
 }
@@ -9,6 +9,7 @@ impl From<entity::Model> for SimpleUserSettings {
             tenant_id: entity.tenant_id,
             theme: entity.theme,
             language: entity.language,
+            version: entity.version,
         }
     }
 }
@@ -14,6 +14,7 @@ mod tests {
             user_id,
             theme: Some("dark".to_owned()),
             language: Some("en".to_owned()),
+            version: 3,
         };
 
         let settings: SimpleUserSettings = entity.into();
@@ -22,5 +23,6 @@ mod tests {
         assert_eq!(settings.tenant_id, tenant_id);
         assert_eq!(settings.theme, Some("dark".to_owned()));
         assert_eq!(settings.language, Some("en".to_owned()));
+        assert_eq!(settings.version, 3);
     }
 }
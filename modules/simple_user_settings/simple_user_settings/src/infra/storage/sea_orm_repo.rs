@@ -1,8 +1,9 @@
 use async_trait::async_trait;
-use modkit_db::secure::{DBRunner, ScopeError, SecureEntityExt, SecureInsertExt, SecureOnConflict};
+use modkit_db::secure::{DBRunner, ScopeError, SecureEntityExt, SecureInsertExt, SecureUpdateExt};
 use modkit_security::{AccessScope, SecurityContext};
-use sea_orm::{ActiveValue, EntityTrait};
-use simple_user_settings_sdk::models::{SimpleUserSettings, SimpleUserSettingsPatch};
+use sea_orm::sea_query::Expr;
+use sea_orm::{ActiveValue, ColumnTrait, Condition, EntityTrait, SqlErr};
+use simple_user_settings_sdk::models::SimpleUserSettings;
 
 use crate::domain::error::DomainError;
 use crate::domain::repo::SettingsRepository;
@@ -54,87 +55,63 @@ impl SettingsRepository for SeaOrmSettingsRepository {
         Ok(result.map(Into::into))
     }
 
-    async fn upsert_full<C: DBRunner>(
+    async fn save_if_version<C: DBRunner>(
         &self,
         conn: &C,
         scope: &AccessScope,
         ctx: &SecurityContext,
+        expected_version: i64,
         theme: Option<String>,
         language: Option<String>,
-    ) -> Result<SimpleUserSettings, DomainError> {
+    ) -> Result<Option<SimpleUserSettings>, DomainError> {
         let user_id = ctx.subject_id();
         let tenant_id = ctx.tenant_id();
 
-        let active_model = entity::ActiveModel {
-            tenant_id: ActiveValue::Set(tenant_id),
-            user_id: ActiveValue::Set(user_id),
-            theme: ActiveValue::Set(theme.clone()),
-            language: ActiveValue::Set(language.clone()),
+        let saved = if expected_version == 0 {
+            // Nothing stored yet: a plain insert, which fails on the primary
+            // key if another writer stored settings in the meantime
+            let active_model = entity::ActiveModel {
+                tenant_id: ActiveValue::Set(tenant_id),
+                user_id: ActiveValue::Set(user_id),
+                theme: ActiveValue::Set(theme.clone()),
+                language: ActiveValue::Set(language.clone()),
+                version: ActiveValue::Set(1),
+            };
+            let result = SettingsEntity::insert(active_model.clone())
+                .secure()
+                .scope_with_model(scope, &active_model)
+                .map_err(map_scope_error)?
+                .exec(conn)
+                .await;
+            match result {
+                Ok(_) => true,
+                Err(ScopeError::Db(e))
+                    if matches!(e.sql_err(), Some(SqlErr::UniqueConstraintViolation(_))) =>
+                {
+                    false
+                }
+                Err(e) => return Err(map_scope_error(e)),
+            }
+        } else {
+            let result = SettingsEntity::update_many()
+                .col_expr(entity::Column::Theme, Expr::value(theme.clone()))
+                .col_expr(entity::Column::Language, Expr::value(language.clone()))
+                .col_expr(entity::Column::Version, Expr::value(expected_version + 1))
+                .secure()
+                .scope_with(scope)
+                .filter(Condition::all().add(entity::Column::Version.eq(expected_version)))
+                .exec(conn)
+                .await
+                .map_err(map_scope_error)?;
+            result.rows_affected == 1
         };
 
-        // Full replacement - overwrites all columns (SecureOnConflict validates tenant immutability)
-        let on_conflict = SecureOnConflict::<SettingsEntity>::columns([
-            entity::Column::TenantId,
-            entity::Column::UserId,
-        ])
-        .update_columns([entity::Column::Theme, entity::Column::Language])
-        .map_err(map_scope_error)?;
-
-        SettingsEntity::insert(active_model)
-            .secure()
-            .scope_with_model(
-                scope,
-                &entity::ActiveModel {
-                    tenant_id: ActiveValue::Set(tenant_id),
-                    user_id: ActiveValue::Set(user_id),
-                    theme: ActiveValue::Set(theme.clone()),
-                    language: ActiveValue::Set(language.clone()),
-                },
-            )
-            .map_err(map_scope_error)?
-            .on_conflict(on_conflict)
-            .exec(conn)
-            .await
-            .map_err(map_scope_error)?;
-
-        Ok(SimpleUserSettings {
+        Ok(saved.then_some(SimpleUserSettings {
             user_id,
             tenant_id,
             theme,
             language,
-        })
-    }
-
-    async fn upsert_patch<C: DBRunner>(
-        &self,
-        conn: &C,
-        scope: &AccessScope,
-        ctx: &SecurityContext,
-        patch: SimpleUserSettingsPatch,
-    ) -> Result<SimpleUserSettings, DomainError> {
-        // Read existing settings to merge with patch
-        // This approach is database-agnostic and avoids SQLite COALESCE type issues
-        let existing = SettingsEntity::find()
-            .secure()
-            .scope_with(scope)
-            .one(conn)
-            .await
-            .map_err(map_scope_error)?;
-
-        // Merge patch with existing values
-        let (theme, language) = match existing {
-            Some(e) => {
-                let theme = patch.theme.or(e.theme);
-                let language = patch.language.or(e.language);
-                (theme, language)
-            }
-            None => {
-                // No existing record - use patch values directly
-                (patch.theme, patch.language)
-            }
-        };
-
-        // Use upsert_full with merged values
-        self.upsert_full(conn, scope, ctx, theme, language).await
+            version: expected_version + 1,
+        }))
     }
 }
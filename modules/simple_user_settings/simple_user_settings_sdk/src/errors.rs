@@ -10,6 +10,9 @@ pub enum SettingsError {
     #[error("Validation error on field '{field}': {message}")]
     Validation { field: String, message: String },
 
+    #[error("Settings were modified since the expected revision")]
+    PreconditionFailed,
+
     #[error("Access forbidden")]
     Forbidden,
 
@@ -31,6 +34,11 @@ impl SettingsError {
         }
     }
 
+    #[must_use]
+    pub fn precondition_failed() -> Self {
+        Self::PreconditionFailed
+    }
+
     #[must_use]
     pub fn forbidden() -> Self {
         Self::Forbidden
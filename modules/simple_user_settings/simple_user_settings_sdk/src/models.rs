@@ -12,6 +12,9 @@ pub struct SimpleUserSettings {
     pub tenant_id: Uuid,
     pub theme: Option<String>,
     pub language: Option<String>,
+    /// Revision of the stored settings, raised by every write; `0` while
+    /// nothing is stored.
+    pub version: i64,
 }
 
 /// Partial update data for user settings.
@@ -30,6 +30,10 @@ pub struct ApiGatewayConfig {
     #[serde(default)]
     pub defaults: Defaults,
 
+    /// Response compression and request decompression
+    #[serde(default)]
+    pub compression: CompressionConfig,
+
     /// Disable authentication and authorization completely.
     /// When true, middleware automatically injects `SecurityCtx::root_ctx()` for all requests,
     /// providing full system-level access with no tenant filtering (`scope.is_root()` == true).
@@ -91,6 +95,69 @@ impl Default for RateLimitDefaults {
     }
 }
 
+/// Content encodings supported for response compression and request decompression
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CompressionAlgorithm {
+    Gzip,
+    Br,
+    Zstd,
+}
+
+/// Compression effort; trades CPU for smaller responses
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CompressionLevelConfig {
+    Fastest,
+    #[default]
+    Default,
+    Best,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct CompressionConfig {
+    /// Compress responses for clients that send a matching `Accept-Encoding`
+    pub enabled: bool,
+    /// Encodings offered for responses and accepted for request bodies
+    pub algorithms: Vec<CompressionAlgorithm>,
+    /// Compression level for responses
+    pub level: CompressionLevelConfig,
+    /// Responses with a known size below this threshold are sent uncompressed
+    pub min_size_bytes: u16,
+    /// Transparently decompress request bodies sent with `Content-Encoding`
+    pub request_decompression: bool,
+    /// Maximum ratio of decompressed to compressed request body bytes;
+    /// larger ratios are rejected as decompression bombs. Times the body
+    /// limit, which counts wire bytes, it bounds the decompressed body size.
+    pub max_decompression_ratio: u32,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            algorithms: vec![
+                CompressionAlgorithm::Gzip,
+                CompressionAlgorithm::Br,
+                CompressionAlgorithm::Zstd,
+            ],
+            level: CompressionLevelConfig::Default,
+            min_size_bytes: 1024,
+            request_decompression: true,
+            max_decompression_ratio: 100,
+        }
+    }
+}
+
+impl CompressionConfig {
+    /// Whether `algorithm` is enabled
+    #[must_use]
+    pub fn allows(&self, algorithm: CompressionAlgorithm) -> bool {
+        self.algorithms.contains(&algorithm)
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(deny_unknown_fields, default)]
 pub struct CorsConfig {
@@ -0,0 +1,257 @@
+//! Response compression and guarded request decompression
+//!
+//! Responses are compressed with the encodings enabled in [`CompressionConfig`]
+//! unless the operation opted out via `OperationBuilder::skip_response_compression`.
+//! Compressed request bodies are decompressed transparently; the ratio between
+//! decompressed and wire bytes is capped to reject decompression bombs.
+//! The body limit applies to wire bytes, so a decompressed body can reach up
+//! to `max_decompression_ratio` times the body limit.
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use axum::body::Body;
+use axum::extract::{MatchedPath, Request};
+use axum::http::{Extensions, HeaderMap, StatusCode, Version, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use dashmap::DashSet;
+use futures_util::TryStreamExt;
+use http::Method;
+use tower_http::compression::predicate::{NotForContentType, Predicate, SizeAbove};
+use tower_http::compression::{CompressionLayer, CompressionLevel};
+use tower_http::decompression::RequestDecompressionLayer;
+
+use modkit::api::{OperationSpec, Problem};
+
+use crate::config::{CompressionAlgorithm, CompressionConfig, CompressionLevelConfig};
+
+/// Decompressed request bodies below this size are never rejected by the ratio guard,
+/// so tiny but highly repetitive payloads are not mistaken for bombs.
+const RATIO_GUARD_MIN_BYTES: u64 = 64 * 1024;
+
+/// Set of (method, path) operations that opted out of response compression
+pub type CompressionOptOutSet = Arc<DashSet<(Method, String)>>;
+
+/// Response extension marking a response that must not be compressed
+#[derive(Clone, Copy, Debug)]
+pub struct SkipCompression;
+
+/// Build the opt-out set from operation specs
+#[must_use]
+pub fn build_compression_opt_out_set(specs: &[OperationSpec]) -> CompressionOptOutSet {
+    let set = DashSet::new();
+    for spec in specs.iter().filter(|s| s.skip_response_compression) {
+        set.insert((spec.method.clone(), spec.path.clone()));
+    }
+    Arc::new(set)
+}
+
+/// Build the response compression layer from config.
+#[must_use]
+pub fn build_compression_layer(
+    cfg: &CompressionConfig,
+) -> CompressionLayer<impl Predicate + use<>> {
+    let level = match cfg.level {
+        CompressionLevelConfig::Fastest => CompressionLevel::Fastest,
+        CompressionLevelConfig::Default => CompressionLevel::Default,
+        CompressionLevelConfig::Best => CompressionLevel::Best,
+    };
+
+    let predicate = SizeAbove::new(cfg.min_size_bytes)
+        .and(NotForContentType::GRPC)
+        .and(NotForContentType::IMAGES)
+        .and(NotForContentType::SSE)
+        .and(
+            |_: StatusCode, _: Version, _: &HeaderMap, extensions: &Extensions| {
+                extensions.get::<SkipCompression>().is_none()
+            },
+        );
+
+    CompressionLayer::new()
+        .gzip(cfg.allows(CompressionAlgorithm::Gzip))
+        .br(cfg.allows(CompressionAlgorithm::Br))
+        .zstd(cfg.allows(CompressionAlgorithm::Zstd))
+        .no_deflate()
+        .quality(level)
+        .compress_when(predicate)
+}
+
+/// Build the request decompression layer from config.
+///
+/// Bodies with an unsupported `Content-Encoding` are rejected with 415.
+#[must_use]
+pub fn build_request_decompression_layer(cfg: &CompressionConfig) -> RequestDecompressionLayer {
+    RequestDecompressionLayer::new()
+        .gzip(cfg.allows(CompressionAlgorithm::Gzip))
+        .br(cfg.allows(CompressionAlgorithm::Br))
+        .zstd(cfg.allows(CompressionAlgorithm::Zstd))
+        .no_deflate()
+}
+
+/// Mark responses of opted-out operations so the compression predicate skips them.
+///
+/// Must run inside the compression layer.
+pub async fn compression_opt_out_middleware(
+    opt_out: CompressionOptOutSet,
+    req: Request,
+    next: Next,
+) -> Response {
+    let method = req.method().clone();
+    let path = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or_else(|| req.uri().path().to_owned(), |p| p.as_str().to_owned());
+
+    let mut response = next.run(req).await;
+    if opt_out.contains(&(method, path)) {
+        response.extensions_mut().insert(SkipCompression);
+    }
+    response
+}
+
+/// Number of compressed (wire) request body bytes consumed so far
+#[derive(Clone, Debug, Default)]
+pub struct CompressedBodyBytes(Arc<AtomicU64>);
+
+impl CompressedBodyBytes {
+    fn add(&self, n: usize) {
+        self.0.fetch_add(n as u64, Ordering::Relaxed);
+    }
+
+    fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Count wire bytes of compressed request bodies.
+///
+/// Must run outside the decompression layer, which strips `Content-Encoding`.
+pub async fn count_compressed_body_middleware(req: Request, next: Next) -> Response {
+    let is_encoded = req
+        .headers()
+        .get(header::CONTENT_ENCODING)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| !v.trim().eq_ignore_ascii_case("identity"));
+    if !is_encoded {
+        return next.run(req).await;
+    }
+
+    let counter = CompressedBodyBytes::default();
+    let (mut parts, body) = req.into_parts();
+    parts.extensions.insert(counter.clone());
+
+    let body = Body::from_stream(body.into_data_stream().inspect_ok(move |chunk| {
+        counter.add(chunk.len());
+    }));
+    next.run(Request::from_parts(parts, body)).await
+}
+
+/// Error raised while streaming a request body whose decompression ratio is too high
+#[derive(Debug, thiserror::Error)]
+#[error("decompressed request body exceeds the allowed compression ratio of {max_ratio}")]
+pub struct DecompressionRatioExceeded {
+    pub max_ratio: u32,
+}
+
+/// Abort decompressed request bodies that grow beyond `max_ratio` times their wire size,
+/// answering `413 Payload Too Large`.
+///
+/// Since the body limit counts wire bytes, `max_ratio` times the body limit is
+/// the real bound on the decompressed size a handler may receive.
+///
+/// Must run inside the decompression layer; requests that were not compressed
+/// (no [`CompressedBodyBytes`] extension) pass through untouched.
+pub async fn decompression_ratio_guard_middleware(
+    max_ratio: u32,
+    req: Request,
+    next: Next,
+) -> Response {
+    let Some(counter) = req.extensions().get::<CompressedBodyBytes>().cloned() else {
+        return next.run(req).await;
+    };
+
+    let tripped = Arc::new(AtomicBool::new(false));
+    let (parts, body) = req.into_parts();
+    let mut decompressed: u64 = 0;
+    let flag = tripped.clone();
+    let body = Body::from_stream(
+        body.into_data_stream()
+            .map_err(axum::Error::into_inner)
+            .and_then(move |chunk| {
+                decompressed += chunk.len() as u64;
+                let allowed = counter.get().saturating_mul(u64::from(max_ratio));
+                let result = if decompressed > RATIO_GUARD_MIN_BYTES && decompressed > allowed {
+                    tracing::warn!(
+                        decompressed,
+                        compressed = counter.get(),
+                        max_ratio,
+                        "Rejecting request body: decompression ratio exceeded"
+                    );
+                    flag.store(true, Ordering::Relaxed);
+                    Err(DecompressionRatioExceeded { max_ratio }.into())
+                } else {
+                    Ok(chunk)
+                };
+                std::future::ready(result)
+            }),
+    );
+    let response = next.run(Request::from_parts(parts, body)).await;
+
+    // Whatever the handler made of the aborted body, report the actual cause
+    if tripped.load(Ordering::Relaxed) {
+        return Problem::new(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "Payload Too Large",
+            DecompressionRatioExceeded { max_ratio }.to_string(),
+        )
+        .into_response();
+    }
+    response
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use modkit::api::operation_builder::{ConditionalSpec, VendorExtensions};
+
+    fn spec(path: &str, skip: bool) -> OperationSpec {
+        OperationSpec {
+            method: Method::GET,
+            path: path.to_owned(),
+            operation_id: None,
+            summary: None,
+            description: None,
+            tags: vec![],
+            params: vec![],
+            request_body: None,
+            responses: vec![],
+            handler_id: path.to_owned(),
+            sec_requirement: None,
+            is_public: true,
+            rate_limit: None,
+            allowed_request_content_types: None,
+            vendor_extensions: VendorExtensions::default(),
+            license_requirement: None,
+            conditional: ConditionalSpec::default(),
+            skip_response_compression: skip,
+        }
+    }
+
+    #[test]
+    fn opt_out_set_contains_only_opted_out_operations() {
+        let set = build_compression_opt_out_set(&[spec("/a", true), spec("/b", false)]);
+        assert!(set.contains(&(Method::GET, "/a".to_owned())));
+        assert!(!set.contains(&(Method::GET, "/b".to_owned())));
+    }
+
+    #[test]
+    fn compression_config_allows() {
+        let cfg = CompressionConfig {
+            algorithms: vec![CompressionAlgorithm::Gzip],
+            ..CompressionConfig::default()
+        };
+        assert!(cfg.allows(CompressionAlgorithm::Gzip));
+        assert!(!cfg.allows(CompressionAlgorithm::Br));
+    }
+}
@@ -132,7 +132,7 @@ pub async fn mime_validation_middleware(
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
     use super::*;
-    use modkit::api::operation_builder::VendorExtensions;
+    use modkit::api::operation_builder::{ConditionalSpec, VendorExtensions};
 
     #[test]
     fn test_build_mime_validation_map() {
@@ -159,6 +159,8 @@ mod tests {
             sec_requirement: None,
             is_public: false,
             license_requirement: None,
+            conditional: ConditionalSpec::default(),
+            skip_response_compression: false,
             rate_limit: None,
             allowed_request_content_types: Some(vec!["multipart/form-data", "application/pdf"]),
             vendor_extensions: VendorExtensions::default(),
@@ -1,3 +1,4 @@
+pub mod compression;
 pub mod license_validation;
 pub mod mime_validation;
 pub mod rate_limit;
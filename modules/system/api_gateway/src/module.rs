@@ -21,6 +21,7 @@ use std::time::Duration;
 use tokio_util::sync::CancellationToken;
 use tower_http::{
     limit::RequestBodyLimitLayer,
+    map_request_body::MapRequestBodyLayer,
     request_id::{PropagateRequestIdLayer, SetRequestIdLayer},
     timeout::TimeoutLayer,
 };
@@ -169,7 +170,8 @@ impl ApiGateway {
         //
         // Desired request execution order (outermost -> innermost):
         // SetRequestId -> PropagateRequestId -> Trace -> push_req_id_to_extensions
-        // -> Timeout -> BodyLimit -> CORS -> MIME validation -> RateLimit -> ErrorMapping -> Auth -> Router
+        // -> Timeout -> BodyLimit -> Compression -> Decompression -> CORS -> MIME validation
+        // -> RateLimit -> ErrorMapping -> Auth -> Router
         //
         // Therefore we must add layers in the reverse order (innermost -> outermost) below.
         // Due future refactoring, this order must be maintained.
@@ -263,6 +265,43 @@ impl ApiGateway {
             router = router.layer(crate::cors::build_cors_layer(&config));
         }
 
+        // 5c) Mark responses of operations that opted out of compression
+        // (inner to compression so the marker is visible to its predicate)
+        let compression_opt_out = middleware::compression::build_compression_opt_out_set(&specs);
+        router = router.layer(from_fn(
+            move |req: axum::extract::Request, next: axum::middleware::Next| {
+                let set = compression_opt_out.clone();
+                middleware::compression::compression_opt_out_middleware(set, req, next)
+            },
+        ));
+
+        // 5b) Request decompression: the wire-byte counter is outer and the ratio guard inner
+        // to the decompression layer; the body limit below still applies to wire bytes.
+        if config.compression.request_decompression {
+            let max_ratio = config.compression.max_decompression_ratio;
+            router = router.layer(from_fn(
+                move |req: axum::extract::Request, next: axum::middleware::Next| {
+                    middleware::compression::decompression_ratio_guard_middleware(
+                        max_ratio, req, next,
+                    )
+                },
+            ));
+            router = router.layer(MapRequestBodyLayer::new(axum::body::Body::new));
+            router = router.layer(middleware::compression::build_request_decompression_layer(
+                &config.compression,
+            ));
+            router = router.layer(from_fn(
+                middleware::compression::count_compressed_body_middleware,
+            ));
+        }
+
+        // 5a) Response compression
+        if config.compression.enabled {
+            router = router.layer(middleware::compression::build_compression_layer(
+                &config.compression,
+            ));
+        }
+
         // 5) Body limit
         router = router.layer(RequestBodyLimitLayer::new(config.defaults.body_limit_bytes));
         router = router.layer(DefaultBodyLimit::max(config.defaults.body_limit_bytes));
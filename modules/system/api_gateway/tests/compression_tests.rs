@@ -0,0 +1,277 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Integration tests for response compression and guarded request decompression
+
+use std::io::Write;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::{
+    Router,
+    body::Body,
+    extract::Json,
+    http::{Request, StatusCode, header},
+};
+use flate2::{Compression, write::GzEncoder};
+use modkit::{
+    Module, ModuleCtx, RestApiCapability,
+    api::OperationBuilder,
+    config::ConfigProvider,
+    contracts::{ApiGatewayCapability, OpenApiRegistry},
+};
+use modkit_security::SecurityContext;
+use serde_json::json;
+use tenant_resolver_sdk::{
+    AccessOptions, TenantFilter, TenantId, TenantInfo, TenantResolverError,
+    TenantResolverGatewayClient, TenantStatus,
+};
+use tower::ServiceExt;
+use uuid::Uuid;
+
+struct MockTenantResolver;
+
+#[async_trait]
+impl TenantResolverGatewayClient for MockTenantResolver {
+    async fn get_tenant(
+        &self,
+        _ctx: &SecurityContext,
+        id: TenantId,
+    ) -> std::result::Result<TenantInfo, TenantResolverError> {
+        Ok(TenantInfo {
+            id,
+            name: format!("Tenant {id}"),
+            status: TenantStatus::Active,
+            tenant_type: None,
+        })
+    }
+
+    async fn can_access(
+        &self,
+        _ctx: &SecurityContext,
+        _target: TenantId,
+        _options: Option<&AccessOptions>,
+    ) -> std::result::Result<bool, TenantResolverError> {
+        Ok(true)
+    }
+
+    async fn get_accessible_tenants(
+        &self,
+        _ctx: &SecurityContext,
+        _filter: Option<&TenantFilter>,
+        _options: Option<&AccessOptions>,
+    ) -> std::result::Result<Vec<TenantInfo>, TenantResolverError> {
+        Ok(vec![])
+    }
+}
+
+struct TestConfigProvider {
+    config: serde_json::Value,
+}
+
+impl ConfigProvider for TestConfigProvider {
+    fn get_module_config(&self, module: &str) -> Option<&serde_json::Value> {
+        if module == "api_gateway" {
+            Some(&self.config)
+        } else {
+            None
+        }
+    }
+}
+
+fn create_ctx(compression: &serde_json::Value) -> ModuleCtx {
+    let config = json!({
+        "config": {
+            "bind_addr": "127.0.0.1:0",
+            "auth_disabled": true,
+            "compression": compression,
+        }
+    });
+
+    let hub = Arc::new(modkit::ClientHub::new());
+    hub.register::<dyn TenantResolverGatewayClient>(Arc::new(MockTenantResolver));
+
+    ModuleCtx::new(
+        "api_gateway",
+        Uuid::new_v4(),
+        Arc::new(TestConfigProvider { config }),
+        hub,
+        tokio_util::sync::CancellationToken::new(),
+        None,
+    )
+}
+
+pub struct CompressionTestModule;
+
+#[async_trait]
+impl Module for CompressionTestModule {
+    async fn init(&self, _ctx: &ModuleCtx) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+impl RestApiCapability for CompressionTestModule {
+    fn register_rest(
+        &self,
+        _ctx: &ModuleCtx,
+        router: Router,
+        openapi: &dyn OpenApiRegistry,
+    ) -> anyhow::Result<Router> {
+        let router = OperationBuilder::get("/tests/v1/large")
+            .operation_id("test:large")
+            .public()
+            .handler(large_handler)
+            .json_response(StatusCode::OK, "Large payload")
+            .register(router, openapi);
+
+        let router = OperationBuilder::get("/tests/v1/large-raw")
+            .operation_id("test:large_raw")
+            .skip_response_compression()
+            .public()
+            .handler(large_handler)
+            .json_response(StatusCode::OK, "Large payload, never compressed")
+            .register(router, openapi);
+
+        let router = OperationBuilder::post("/tests/v1/echo-len")
+            .operation_id("test:echo_len")
+            .public()
+            .handler(echo_len_handler)
+            .json_response(StatusCode::OK, "Length of the received payload")
+            .register(router, openapi);
+
+        Ok(router)
+    }
+}
+
+async fn large_handler() -> Json<serde_json::Value> {
+    Json(json!({ "items": vec!["lorem ipsum dolor sit amet"; 500] }))
+}
+
+async fn echo_len_handler(Json(payload): Json<serde_json::Value>) -> Json<serde_json::Value> {
+    Json(json!({ "len": payload["data"].as_str().map_or(0, str::len) }))
+}
+
+async fn build_app(compression: &serde_json::Value) -> Router {
+    let api_gateway = api_gateway::ApiGateway::default();
+    let ctx = create_ctx(compression);
+    api_gateway.init(&ctx).await.expect("Failed to init");
+
+    let router = CompressionTestModule
+        .register_rest(&ctx, Router::new(), &api_gateway)
+        .expect("Failed to register routes");
+    api_gateway
+        .rest_finalize(&ctx, router)
+        .expect("Failed to finalize router")
+}
+
+fn gzip(data: &[u8]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+fn get(uri: &str, accept_encoding: &str) -> Request<Body> {
+    Request::builder()
+        .uri(uri)
+        .header(header::ACCEPT_ENCODING, accept_encoding)
+        .body(Body::empty())
+        .unwrap()
+}
+
+fn gzip_post(body: &serde_json::Value) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri("/tests/v1/echo-len")
+        .header(header::CONTENT_TYPE, "application/json")
+        .header(header::CONTENT_ENCODING, "gzip")
+        .body(Body::from(gzip(body.to_string().as_bytes())))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn compresses_large_responses_when_accepted() {
+    let app = build_app(&json!({})).await;
+
+    let res = app.oneshot(get("/tests/v1/large", "gzip")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers().get(header::CONTENT_ENCODING).unwrap(), "gzip");
+}
+
+#[tokio::test]
+async fn honours_configured_algorithms() {
+    let app = build_app(&json!({ "algorithms": ["zstd"] })).await;
+
+    let res = app
+        .clone()
+        .oneshot(get("/tests/v1/large", "gzip"))
+        .await
+        .unwrap();
+    assert!(res.headers().get(header::CONTENT_ENCODING).is_none());
+
+    let res = app
+        .oneshot(get("/tests/v1/large", "gzip, zstd"))
+        .await
+        .unwrap();
+    assert_eq!(res.headers().get(header::CONTENT_ENCODING).unwrap(), "zstd");
+}
+
+#[tokio::test]
+async fn skips_compression_for_opted_out_routes() {
+    let app = build_app(&json!({})).await;
+
+    let res = app
+        .oneshot(get("/tests/v1/large-raw", "gzip, br"))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert!(res.headers().get(header::CONTENT_ENCODING).is_none());
+}
+
+#[tokio::test]
+async fn skips_compression_when_disabled() {
+    let app = build_app(&json!({ "enabled": false })).await;
+
+    let res = app.oneshot(get("/tests/v1/large", "gzip")).await.unwrap();
+    assert!(res.headers().get(header::CONTENT_ENCODING).is_none());
+}
+
+#[tokio::test]
+async fn decompresses_gzip_request_bodies() {
+    let app = build_app(&json!({})).await;
+
+    let res = app
+        .oneshot(gzip_post(&json!({ "data": "hello" })))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let body = axum::body::to_bytes(res.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["len"], 5);
+}
+
+#[tokio::test]
+async fn rejects_decompression_bombs() {
+    let app = build_app(&json!({ "max_decompression_ratio": 10 })).await;
+
+    // ~1 MiB of a single repeated byte compresses by roughly three orders of magnitude
+    let bomb = json!({ "data": "0".repeat(1024 * 1024) });
+    let res = app.oneshot(gzip_post(&bomb)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(
+        res.headers().get(header::CONTENT_TYPE).unwrap(),
+        "application/problem+json"
+    );
+}
+
+#[tokio::test]
+async fn rejects_unsupported_content_encoding() {
+    let app = build_app(&json!({ "algorithms": ["br"] })).await;
+
+    let res = app
+        .oneshot(gzip_post(&json!({ "data": "hello" })))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+}
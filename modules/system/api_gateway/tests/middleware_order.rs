@@ -4,7 +4,8 @@
 //!
 //! The intended order is documented in `modules/api_gateway/src/lib.rs`:
 //! set request id -> propagate request id -> trace -> push request id to extensions
-//! -> timeout -> body limit -> CORS -> MIME validation -> rate limit -> error mapping -> auth -> router
+//! -> timeout -> body limit -> compression -> decompression -> CORS -> MIME validation -> rate limit
+//! -> error mapping -> auth -> router
 //!
 use anyhow::Result;
 use async_trait::async_trait;
@@ -20,7 +20,7 @@ use tower::ServiceExt; // for oneshot
 use api_gateway::middleware::mime_validation::{
     build_mime_validation_map, mime_validation_middleware,
 };
-use modkit::api::operation_builder::VendorExtensions;
+use modkit::api::operation_builder::{ConditionalSpec, VendorExtensions};
 
 /// Helper to extract Problem from response
 async fn extract_problem(response: axum::response::Response) -> Problem {
@@ -52,6 +52,8 @@ async fn test_middleware_allows_configured_content_type() {
         sec_requirement: None,
         is_public: true,
         license_requirement: None,
+        conditional: ConditionalSpec::default(),
+        skip_response_compression: false,
         rate_limit: None,
         allowed_request_content_types: Some(vec!["application/json"]),
         vendor_extensions: VendorExtensions::default(),
@@ -97,6 +99,8 @@ async fn test_middleware_strips_content_type_parameters() {
         sec_requirement: None,
         is_public: true,
         license_requirement: None,
+        conditional: ConditionalSpec::default(),
+        skip_response_compression: false,
         rate_limit: None,
         allowed_request_content_types: Some(vec!["application/json"]),
         vendor_extensions: VendorExtensions::default(),
@@ -142,6 +146,8 @@ async fn test_middleware_rejects_disallowed_content_type() {
         sec_requirement: None,
         is_public: true,
         license_requirement: None,
+        conditional: ConditionalSpec::default(),
+        skip_response_compression: false,
         rate_limit: None,
         allowed_request_content_types: Some(vec!["application/json"]),
         vendor_extensions: VendorExtensions::default(),
@@ -193,6 +199,8 @@ async fn test_middleware_rejects_missing_content_type() {
         sec_requirement: None,
         is_public: true,
         license_requirement: None,
+        conditional: ConditionalSpec::default(),
+        skip_response_compression: false,
         rate_limit: None,
         allowed_request_content_types: Some(vec!["multipart/form-data"]),
         vendor_extensions: VendorExtensions::default(),
@@ -268,6 +276,8 @@ async fn test_middleware_allows_multiple_content_types() {
         sec_requirement: None,
         is_public: true,
         license_requirement: None,
+        conditional: ConditionalSpec::default(),
+        skip_response_compression: false,
         rate_limit: None,
         allowed_request_content_types: Some(vec![
             "application/json",
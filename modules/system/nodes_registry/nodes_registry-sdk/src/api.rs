@@ -1,5 +1,5 @@
 use crate::error::NodesRegistryError;
-use crate::{Node, NodeSysCap, NodeSysInfo};
+use crate::{CapabilityQuery, Node, NodeMember, NodeRegistration, NodeSysCap, NodeSysInfo, SysCap};
 
 /// Client trait for accessing nodes registry functionality
 #[async_trait::async_trait]
@@ -7,8 +7,20 @@ pub trait NodesRegistryClient: Send + Sync {
     /// Get a node by ID
     async fn get_node(&self, id: uuid::Uuid) -> Result<Node, NodesRegistryError>;
 
-    /// List all nodes
-    async fn list_nodes(&self) -> Result<Vec<Node>, NodesRegistryError>;
+    /// List all nodes of the fleet with their status and last heartbeat
+    async fn list_nodes(&self) -> Result<Vec<NodeMember>, NodesRegistryError>;
+
+    /// Register (or re-register) a node in the fleet
+    async fn register_node(
+        &self,
+        registration: NodeRegistration,
+    ) -> Result<NodeMember, NodesRegistryError>;
+
+    /// Record a heartbeat from a registered node
+    async fn heartbeat(&self, node_id: uuid::Uuid) -> Result<NodeMember, NodesRegistryError>;
+
+    /// Remove a node from the fleet (graceful shutdown)
+    async fn deregister_node(&self, node_id: uuid::Uuid) -> Result<(), NodesRegistryError>;
 
     /// Get system information for a node
     async fn get_node_sysinfo(
@@ -18,4 +30,30 @@ pub trait NodesRegistryClient: Send + Sync {
 
     /// Get system capabilities for a node
     async fn get_node_syscap(&self, node_id: uuid::Uuid) -> Result<NodeSysCap, NodesRegistryError>;
+
+    /// Add or replace custom capabilities of a node.
+    ///
+    /// Entries expire `cache_ttl_secs` after being set; a TTL of 0 keeps them until
+    /// removed. Returns the node's merged capabilities.
+    async fn set_custom_syscap(
+        &self,
+        node_id: uuid::Uuid,
+        caps: Vec<SysCap>,
+    ) -> Result<NodeSysCap, NodesRegistryError>;
+
+    /// Remove custom capabilities of a node by key
+    async fn remove_custom_syscap(
+        &self,
+        node_id: uuid::Uuid,
+        keys: Vec<String>,
+    ) -> Result<(), NodesRegistryError>;
+
+    /// Remove all custom capabilities of a node
+    async fn clear_custom_syscap(&self, node_id: uuid::Uuid) -> Result<(), NodesRegistryError>;
+
+    /// Find the nodes whose capabilities satisfy `query`
+    async fn find_nodes(
+        &self,
+        query: &CapabilityQuery,
+    ) -> Result<Vec<NodeMember>, NodesRegistryError>;
 }
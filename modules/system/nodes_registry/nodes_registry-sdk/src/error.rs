@@ -4,6 +4,9 @@ pub enum NodesRegistryError {
     #[error("Node not found with ID: {0}")]
     NodeNotFound(uuid::Uuid),
 
+    #[error("System information is not available for remote node: {0}")]
+    NodeInfoUnavailable(uuid::Uuid),
+
     #[error("Failed to collect system information: {0}")]
     SysInfoCollectionFailed(String),
 
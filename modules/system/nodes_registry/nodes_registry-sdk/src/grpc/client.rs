@@ -0,0 +1,139 @@
+//! gRPC client for fleet membership
+//!
+//! Used by nodes that join a registry running in another process.
+
+use anyhow::Result;
+use tonic::transport::Channel;
+
+use modkit_security::SecurityContext;
+use modkit_transport_grpc::attach_secctx;
+use modkit_transport_grpc::client::{GrpcClientConfig, connect_with_retry};
+
+use super::{NodesRegistryServiceClient, proto, status_to_error};
+use crate::{NodeMember, NodeRegistration, NodesRegistryError};
+
+/// gRPC client for the membership part of the nodes registry API
+#[derive(Clone)]
+pub struct NodesRegistryGrpcClient {
+    inner: NodesRegistryServiceClient<Channel>,
+}
+
+impl NodesRegistryGrpcClient {
+    /// Connect to a nodes registry using default configuration with retries.
+    ///
+    /// # Errors
+    /// Returns an error if the connection cannot be established.
+    pub async fn connect(uri: impl Into<String>) -> Result<Self> {
+        let cfg = GrpcClientConfig::new("nodes_registry");
+        Self::connect_with_retry(uri, &cfg).await
+    }
+
+    /// Connect to a nodes registry with custom configuration and retry logic.
+    ///
+    /// # Errors
+    /// Returns an error if the connection cannot be established.
+    pub async fn connect_with_retry(
+        uri: impl Into<String>,
+        cfg: &GrpcClientConfig,
+    ) -> Result<Self> {
+        let channel: Channel = connect_with_retry(uri, cfg).await?;
+        Ok(Self::from_channel(channel))
+    }
+
+    /// Create from an existing channel (useful for testing or custom setup)
+    #[must_use]
+    pub fn from_channel(channel: Channel) -> Self {
+        Self {
+            inner: NodesRegistryServiceClient::new(channel),
+        }
+    }
+
+    /// Register (or re-register) a node on behalf of `ctx`.
+    ///
+    /// # Errors
+    /// Returns `Validation` if the registry rejects the registration, `Internal` on
+    /// transport failures or if the caller is not authenticated.
+    pub async fn register_node(
+        &self,
+        ctx: &SecurityContext,
+        registration: NodeRegistration,
+    ) -> Result<NodeMember, NodesRegistryError> {
+        let node_id = registration.id;
+        let mut client = self.inner.clone();
+        let member = client
+            .register_node(with_secctx(ctx, registration.into())?)
+            .await
+            .map_err(|s| status_to_error(&s, Some(node_id)))?
+            .into_inner();
+        member.try_into()
+    }
+
+    /// Send a heartbeat for a registered node on behalf of `ctx`.
+    ///
+    /// # Errors
+    /// Returns `NodeNotFound` if the registry does not know the node (it has to
+    /// register again), `Internal` on transport failures or if the caller is not
+    /// authenticated.
+    pub async fn heartbeat(
+        &self,
+        ctx: &SecurityContext,
+        node_id: uuid::Uuid,
+    ) -> Result<NodeMember, NodesRegistryError> {
+        let mut client = self.inner.clone();
+        let request = proto::HeartbeatRequest {
+            id: node_id.to_string(),
+        };
+        let member = client
+            .heartbeat(with_secctx(ctx, request)?)
+            .await
+            .map_err(|s| status_to_error(&s, Some(node_id)))?
+            .into_inner();
+        member.try_into()
+    }
+
+    /// Remove a node from the fleet on behalf of `ctx`.
+    ///
+    /// # Errors
+    /// Returns `NodeNotFound` if the node is not registered, `Internal` on transport
+    /// failures or if the caller is not authenticated.
+    pub async fn deregister_node(
+        &self,
+        ctx: &SecurityContext,
+        node_id: uuid::Uuid,
+    ) -> Result<(), NodesRegistryError> {
+        let mut client = self.inner.clone();
+        let request = proto::DeregisterNodeRequest {
+            id: node_id.to_string(),
+        };
+        client
+            .deregister_node(with_secctx(ctx, request)?)
+            .await
+            .map_err(|s| status_to_error(&s, Some(node_id)))?;
+        Ok(())
+    }
+
+    /// List all nodes known to the registry.
+    ///
+    /// # Errors
+    /// Returns `Internal` on transport failures.
+    pub async fn list_nodes(&self) -> Result<Vec<NodeMember>, NodesRegistryError> {
+        let mut client = self.inner.clone();
+        let response = client
+            .list_nodes(tonic::Request::new(()))
+            .await
+            .map_err(|s| status_to_error(&s, None))?
+            .into_inner();
+        response.nodes.into_iter().map(TryInto::try_into).collect()
+    }
+}
+
+/// Build a request carrying the caller's security context; the registry rejects
+/// membership changes without one.
+fn with_secctx<T>(
+    ctx: &SecurityContext,
+    message: T,
+) -> Result<tonic::Request<T>, NodesRegistryError> {
+    let mut request = tonic::Request::new(message);
+    attach_secctx(request.metadata_mut(), ctx).map_err(|s| status_to_error(&s, None))?;
+    Ok(request)
+}
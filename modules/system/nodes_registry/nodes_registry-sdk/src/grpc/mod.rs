@@ -0,0 +1,145 @@
+//! gRPC transport for fleet membership.
+//!
+//! Remote nodes use [`NodesRegistryGrpcClient`] to register with, and heartbeat to,
+//! the registry; the module serves [`NodesRegistryServiceServer`] through `grpc_hub`.
+//! Conversions between protobuf messages and SDK models live here so that both
+//! sides agree on them.
+mod client;
+
+// Generated protobuf types for NodesRegistryService
+#[allow(clippy::all, clippy::pedantic, clippy::nursery, warnings)] // protoc problem
+pub mod proto {
+    tonic::include_proto!("nodes_registry.v1");
+}
+
+pub use client::NodesRegistryGrpcClient;
+pub use proto::nodes_registry_service_client::NodesRegistryServiceClient;
+pub use proto::nodes_registry_service_server::{NodesRegistryService, NodesRegistryServiceServer};
+
+use chrono::{DateTime, Utc};
+use tonic::{Code, Status};
+
+use crate::{Node, NodeMember, NodeRegistration, NodeStatus, NodesRegistryError};
+
+/// Service name constant for `NodesRegistryService`
+pub const NODES_REGISTRY_SERVICE_NAME: &str =
+    <NodesRegistryServiceServer<()> as tonic::server::NamedService>::NAME;
+
+fn parse_id(raw: &str) -> Result<uuid::Uuid, NodesRegistryError> {
+    uuid::Uuid::parse_str(raw)
+        .map_err(|e| NodesRegistryError::Validation(format!("invalid node id '{raw}': {e}")))
+}
+
+fn from_millis(ms: i64) -> Result<DateTime<Utc>, NodesRegistryError> {
+    DateTime::from_timestamp_millis(ms)
+        .ok_or_else(|| NodesRegistryError::Validation(format!("invalid timestamp {ms}")))
+}
+
+impl From<NodeStatus> for proto::NodeStatus {
+    fn from(status: NodeStatus) -> Self {
+        match status {
+            NodeStatus::Alive => Self::Alive,
+            NodeStatus::Suspect => Self::Suspect,
+            NodeStatus::Dead => Self::Dead,
+        }
+    }
+}
+
+impl From<&NodeMember> for proto::NodeMember {
+    fn from(member: &NodeMember) -> Self {
+        Self {
+            id: member.node.id.to_string(),
+            hostname: member.node.hostname.clone(),
+            ip_address: member.node.ip_address.clone(),
+            created_at_ms: member.node.created_at.timestamp_millis(),
+            updated_at_ms: member.node.updated_at.timestamp_millis(),
+            last_seen_at_ms: member.last_seen_at.timestamp_millis(),
+            status: proto::NodeStatus::from(member.status).into(),
+        }
+    }
+}
+
+impl TryFrom<proto::NodeMember> for NodeMember {
+    type Error = NodesRegistryError;
+
+    fn try_from(msg: proto::NodeMember) -> Result<Self, Self::Error> {
+        let status = match proto::NodeStatus::try_from(msg.status) {
+            Ok(proto::NodeStatus::Alive) => NodeStatus::Alive,
+            Ok(proto::NodeStatus::Suspect) => NodeStatus::Suspect,
+            Ok(proto::NodeStatus::Dead) => NodeStatus::Dead,
+            Ok(proto::NodeStatus::Unspecified) | Err(_) => {
+                return Err(NodesRegistryError::Validation(format!(
+                    "invalid node status {}",
+                    msg.status
+                )));
+            }
+        };
+        Ok(Self {
+            node: Node {
+                id: parse_id(&msg.id)?,
+                hostname: msg.hostname,
+                ip_address: msg.ip_address,
+                created_at: from_millis(msg.created_at_ms)?,
+                updated_at: from_millis(msg.updated_at_ms)?,
+            },
+            status,
+            last_seen_at: from_millis(msg.last_seen_at_ms)?,
+        })
+    }
+}
+
+impl From<NodeRegistration> for proto::RegisterNodeRequest {
+    fn from(reg: NodeRegistration) -> Self {
+        Self {
+            id: reg.id.to_string(),
+            hostname: reg.hostname,
+            ip_address: reg.ip_address,
+        }
+    }
+}
+
+impl TryFrom<proto::RegisterNodeRequest> for NodeRegistration {
+    type Error = NodesRegistryError;
+
+    fn try_from(msg: proto::RegisterNodeRequest) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: parse_id(&msg.id)?,
+            hostname: msg.hostname,
+            ip_address: msg.ip_address,
+        })
+    }
+}
+
+/// Parse the node id of a heartbeat or deregistration request.
+///
+/// # Errors
+/// Returns `NodesRegistryError::Validation` if `raw` is not a UUID.
+pub fn parse_node_id(raw: &str) -> Result<uuid::Uuid, NodesRegistryError> {
+    parse_id(raw)
+}
+
+impl From<NodesRegistryError> for Status {
+    fn from(e: NodesRegistryError) -> Self {
+        match e {
+            NodesRegistryError::NodeNotFound(_) | NodesRegistryError::NodeInfoUnavailable(_) => {
+                Status::not_found(e.to_string())
+            }
+            NodesRegistryError::Validation(_) => Status::invalid_argument(e.to_string()),
+            NodesRegistryError::SysInfoCollectionFailed(_)
+            | NodesRegistryError::SysCapCollectionFailed(_)
+            | NodesRegistryError::Internal => Status::internal(e.to_string()),
+        }
+    }
+}
+
+/// Map a gRPC status returned for an operation on `node_id` to an SDK error.
+fn status_to_error(status: &Status, node_id: Option<uuid::Uuid>) -> NodesRegistryError {
+    match (status.code(), node_id) {
+        (Code::NotFound, Some(id)) => NodesRegistryError::NodeNotFound(id),
+        (Code::InvalidArgument, _) => NodesRegistryError::Validation(status.message().to_owned()),
+        _ => {
+            tracing::warn!(code = ?status.code(), message = status.message(), "nodes registry gRPC call failed");
+            NodesRegistryError::Internal
+        }
+    }
+}
@@ -3,11 +3,19 @@
 
 pub mod api;
 pub mod error;
+pub mod models;
+
+#[cfg(feature = "grpc")]
+pub mod grpc;
 
 pub use api::NodesRegistryClient;
 pub use error::NodesRegistryError;
+pub use models::{
+    CapabilityQuery, CapabilityRequirement, NodeMember, NodeRegistration, NodeStatus,
+};
 
 pub use modkit_node_info::{
-    BatteryInfo, CpuInfo, GpuInfo, HostInfo, MemoryInfo, Node, NodeSysCap, NodeSysInfo, OsInfo,
+    BatteryInfo, ContainerInfo, ContainerRuntime, CpuInfo, DiskInfo, GpuInfo, HostInfo,
+    KubernetesInfo, MemoryInfo, NetworkInterfaceInfo, Node, NodeSysCap, NodeSysInfo, OsInfo,
     SysCap,
 };
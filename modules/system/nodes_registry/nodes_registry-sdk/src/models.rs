@@ -0,0 +1,231 @@
+use chrono::{DateTime, Utc};
+use modkit_node_info::{Node, SysCap};
+use std::time::Duration;
+
+/// Liveness of a node, derived from the age of its last heartbeat
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NodeStatus {
+    /// Heartbeat seen within the suspect threshold
+    Alive,
+    /// Heartbeat overdue, but not yet long enough to declare the node dead
+    Suspect,
+    /// No heartbeat for longer than the dead threshold
+    Dead,
+}
+
+impl NodeStatus {
+    /// Derive the status from the time elapsed since `last_seen_at`.
+    ///
+    /// Timestamps in the future (clock skew between nodes) count as just seen.
+    #[must_use]
+    pub fn from_last_seen(
+        last_seen_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+        suspect_after: Duration,
+        dead_after: Duration,
+    ) -> Self {
+        let age = (now - last_seen_at).to_std().unwrap_or_default();
+        if age >= dead_after {
+            Self::Dead
+        } else if age >= suspect_after {
+            Self::Suspect
+        } else {
+            Self::Alive
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Alive => "alive",
+            Self::Suspect => "suspect",
+            Self::Dead => "dead",
+        }
+    }
+}
+
+impl std::fmt::Display for NodeStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A node together with its membership state in the fleet
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeMember {
+    pub node: Node,
+    pub status: NodeStatus,
+    /// When the last heartbeat (or registration) from this node was received
+    pub last_seen_at: DateTime<Utc>,
+}
+
+/// Registration sent by a node joining the fleet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeRegistration {
+    pub id: uuid::Uuid,
+    pub hostname: String,
+    pub ip_address: Option<String>,
+}
+
+impl From<&Node> for NodeRegistration {
+    fn from(node: &Node) -> Self {
+        Self {
+            id: node.id,
+            hostname: node.hostname.clone(),
+            ip_address: node.ip_address.clone(),
+        }
+    }
+}
+
+/// A single constraint on a node capability
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapabilityRequirement {
+    /// Capability key, e.g. `hardware:ram`
+    pub key: String,
+    /// Require the capability to be present (`true`) or absent/not present (`false`)
+    pub present: bool,
+    /// Minimum `amount`, inclusive
+    pub min_amount: Option<f64>,
+    /// Maximum `amount`, inclusive
+    pub max_amount: Option<f64>,
+    /// Dimension of `min_amount`/`max_amount`. Byte sizes (`B`, `KB`, `MB`, `GB`, `TB`)
+    /// are converted; other dimensions must match the capability's exactly.
+    pub amount_dimension: Option<String>,
+}
+
+impl CapabilityRequirement {
+    /// Requirement that `key` is present, without amount bounds
+    #[must_use]
+    pub fn present(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            present: true,
+            min_amount: None,
+            max_amount: None,
+            amount_dimension: None,
+        }
+    }
+
+    /// Requirement that `key` is absent or reported as not present
+    #[must_use]
+    pub fn absent(key: impl Into<String>) -> Self {
+        Self {
+            present: false,
+            ..Self::present(key)
+        }
+    }
+
+    /// Require at least `amount` (in `dimension`, if given)
+    #[must_use]
+    pub fn at_least(mut self, amount: f64, dimension: Option<&str>) -> Self {
+        self.min_amount = Some(amount);
+        self.amount_dimension = dimension.map(str::to_owned);
+        self
+    }
+
+    /// Require at most `amount` (in `dimension`, if given)
+    #[must_use]
+    pub fn at_most(mut self, amount: f64, dimension: Option<&str>) -> Self {
+        self.max_amount = Some(amount);
+        self.amount_dimension = dimension.map(str::to_owned);
+        self
+    }
+
+    /// Whether a node with `capabilities` satisfies this requirement
+    #[must_use]
+    pub fn is_satisfied_by(&self, capabilities: &[SysCap]) -> bool {
+        let cap = capabilities
+            .iter()
+            .find(|cap| cap.key == self.key && cap.present);
+        match cap {
+            None => !self.present,
+            Some(_) if !self.present => false,
+            Some(cap) => self.amount_in_bounds(cap),
+        }
+    }
+
+    fn amount_in_bounds(&self, cap: &SysCap) -> bool {
+        if self.min_amount.is_none() && self.max_amount.is_none() {
+            return true;
+        }
+        let Some(amount) = cap.amount else {
+            return false;
+        };
+        let Some(amount) = convert_amount(
+            amount,
+            cap.amount_dimension.as_deref(),
+            self.amount_dimension.as_deref(),
+        ) else {
+            return false;
+        };
+        self.min_amount.is_none_or(|min| amount >= min)
+            && self.max_amount.is_none_or(|max| amount <= max)
+    }
+}
+
+/// Query selecting nodes by capabilities, e.g. for workload placement
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CapabilityQuery {
+    /// All requirements must hold
+    pub requirements: Vec<CapabilityRequirement>,
+    /// Statuses a node may have to match; empty means alive nodes only
+    pub statuses: Vec<NodeStatus>,
+}
+
+impl CapabilityQuery {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a requirement
+    #[must_use]
+    pub fn require(mut self, requirement: CapabilityRequirement) -> Self {
+        self.requirements.push(requirement);
+        self
+    }
+
+    /// Whether nodes with `status` are eligible
+    #[must_use]
+    pub fn accepts_status(&self, status: NodeStatus) -> bool {
+        if self.statuses.is_empty() {
+            status == NodeStatus::Alive
+        } else {
+            self.statuses.contains(&status)
+        }
+    }
+
+    /// Whether a node with `capabilities` satisfies every requirement
+    #[must_use]
+    pub fn is_satisfied_by(&self, capabilities: &[SysCap]) -> bool {
+        self.requirements
+            .iter()
+            .all(|req| req.is_satisfied_by(capabilities))
+    }
+}
+
+/// Size of a byte dimension in bytes (binary multiples, as reported by node info)
+fn byte_scale(dimension: &str) -> Option<f64> {
+    let scale = match dimension.to_ascii_uppercase().as_str() {
+        "B" => 1.0,
+        "KB" | "KIB" => 1024.0,
+        "MB" | "MIB" => 1024.0 * 1024.0,
+        "GB" | "GIB" => 1024.0 * 1024.0 * 1024.0,
+        "TB" | "TIB" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+        _ => return None,
+    };
+    Some(scale)
+}
+
+/// Convert `amount` from dimension `from` to `to`; `None` if they are incompatible
+fn convert_amount(amount: f64, from: Option<&str>, to: Option<&str>) -> Option<f64> {
+    match (from, to) {
+        (_, None) => Some(amount),
+        (None, Some(_)) => None,
+        (Some(from), Some(to)) => match (byte_scale(from), byte_scale(to)) {
+            (Some(from_scale), Some(to_scale)) => Some(amount * from_scale / to_scale),
+            _ if from.eq_ignore_ascii_case(to) => Some(amount),
+            _ => None,
+        },
+    }
+}
@@ -0,0 +1,101 @@
+//! gRPC server for fleet membership (`NodesRegistryService`)
+
+use std::sync::Arc;
+use tonic::metadata::MetadataMap;
+use tonic::{Request, Response, Status};
+
+use modkit_security::SecurityContext;
+use modkit_transport_grpc::extract_secctx;
+
+use nodes_registry_sdk::grpc::proto::{
+    DeregisterNodeRequest, HeartbeatRequest, ListNodesResponse, NodeMember as NodeMemberMsg,
+    RegisterNodeRequest,
+};
+use nodes_registry_sdk::grpc::{NodesRegistryService, NodesRegistryServiceServer, parse_node_id};
+use nodes_registry_sdk::{NodeRegistration, NodesRegistryError};
+
+use crate::domain::service::Service;
+
+/// gRPC service implementation backed by the domain service
+#[derive(Clone)]
+pub struct NodesRegistryGrpcService {
+    service: Arc<Service>,
+}
+
+impl NodesRegistryGrpcService {
+    #[must_use]
+    pub fn new(service: Arc<Service>) -> Self {
+        Self { service }
+    }
+
+    #[must_use]
+    pub fn into_server(self) -> NodesRegistryServiceServer<Self> {
+        NodesRegistryServiceServer::new(self)
+    }
+}
+
+fn to_status(e: impl Into<NodesRegistryError>) -> Status {
+    e.into().into()
+}
+
+/// Resolve the caller of a write RPC from the request metadata.
+///
+/// Mirrors `require_auth` on the REST routes: fleet membership may only be changed
+/// by an authenticated caller. `list_nodes` stays open like `GET /nodes`.
+fn require_caller(meta: &MetadataMap) -> Result<SecurityContext, Status> {
+    let ctx = extract_secctx(meta)?;
+    if ctx.subject_id().is_nil() {
+        return Err(Status::unauthenticated(
+            "anonymous callers cannot change fleet membership",
+        ));
+    }
+    Ok(ctx)
+}
+
+#[tonic::async_trait]
+impl NodesRegistryService for NodesRegistryGrpcService {
+    async fn register_node(
+        &self,
+        request: Request<RegisterNodeRequest>,
+    ) -> Result<Response<NodeMemberMsg>, Status> {
+        require_caller(request.metadata())?;
+        let registration = NodeRegistration::try_from(request.into_inner()).map_err(to_status)?;
+        let member = self
+            .service
+            .register_node(registration)
+            .await
+            .map_err(to_status)?;
+        Ok(Response::new((&member).into()))
+    }
+
+    async fn heartbeat(
+        &self,
+        request: Request<HeartbeatRequest>,
+    ) -> Result<Response<NodeMemberMsg>, Status> {
+        require_caller(request.metadata())?;
+        let node_id = parse_node_id(&request.into_inner().id).map_err(to_status)?;
+        let member = self.service.heartbeat(node_id).await.map_err(to_status)?;
+        Ok(Response::new((&member).into()))
+    }
+
+    async fn deregister_node(
+        &self,
+        request: Request<DeregisterNodeRequest>,
+    ) -> Result<Response<()>, Status> {
+        require_caller(request.metadata())?;
+        let node_id = parse_node_id(&request.into_inner().id).map_err(to_status)?;
+        self.service
+            .deregister_node(node_id)
+            .await
+            .map_err(to_status)?;
+        Ok(Response::new(()))
+    }
+
+    async fn list_nodes(
+        &self,
+        _request: Request<()>,
+    ) -> Result<Response<ListNodesResponse>, Status> {
+        let nodes = self.service.list_members().iter().map(Into::into).collect();
+        Ok(Response::new(ListNodesResponse { nodes }))
+    }
+}
@@ -9,6 +9,10 @@ pub struct NodeDto {
     pub ip_address: Option<String>,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
+    /// Liveness derived from the age of the last heartbeat
+    pub status: NodeStatusDto,
+    /// When the last heartbeat (or registration) was received
+    pub last_seen_at: chrono::DateTime<chrono::Utc>,
     /// System information (included when details=true)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sysinfo: Option<NodeSysInfoDto>,
@@ -17,6 +21,23 @@ pub struct NodeDto {
     pub syscap: Option<NodeSysCapDto>,
 }
 
+#[derive(Debug, Clone, Copy)]
+#[modkit_macros::api_dto(request, response)]
+pub enum NodeStatusDto {
+    Alive,
+    Suspect,
+    Dead,
+}
+
+/// Node registration request DTO
+#[modkit_macros::api_dto(request)]
+pub struct RegisterNodeReq {
+    pub id: Uuid,
+    pub hostname: String,
+    #[serde(default)]
+    pub ip_address: Option<String>,
+}
+
 /// System information response DTO
 #[modkit_macros::api_dto(request, response)]
 pub struct NodeSysInfoDto {
@@ -28,6 +49,11 @@ pub struct NodeSysInfoDto {
     pub gpus: Vec<GpuInfoDto>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub battery: Option<BatteryInfoDto>,
+    pub disks: Vec<DiskInfoDto>,
+    pub network_interfaces: Vec<NetworkInterfaceInfoDto>,
+    /// Present when the node runs in a container
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub container: Option<ContainerInfoDto>,
     pub collected_at: chrono::DateTime<chrono::Utc>,
 }
 
@@ -44,6 +70,9 @@ pub struct CpuInfoDto {
     pub num_cpus: u32,
     pub cores: u32,
     pub frequency_mhz: f64,
+    /// CPU quota from the cgroup, in cores
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quota_cores: Option<f64>,
 }
 
 #[modkit_macros::api_dto(request, response)]
@@ -52,6 +81,9 @@ pub struct MemoryInfoDto {
     pub available_bytes: u64,
     pub used_bytes: u64,
     pub used_percent: u32,
+    /// Memory limit from the cgroup; totals and usage are the cgroup's when set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit_bytes: Option<u64>,
 }
 
 #[modkit_macros::api_dto(request, response)]
@@ -79,6 +111,48 @@ pub struct BatteryInfoDto {
     pub percentage: u32,
 }
 
+#[modkit_macros::api_dto(request, response)]
+pub struct DiskInfoDto {
+    pub name: String,
+    pub mount_point: String,
+    pub file_system: String,
+    pub total_bytes: u64,
+    pub available_bytes: u64,
+    pub is_removable: bool,
+    pub is_read_only: bool,
+}
+
+#[modkit_macros::api_dto(request, response)]
+pub struct NetworkInterfaceInfoDto {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mac_address: Option<String>,
+    pub ip_addresses: Vec<String>,
+    pub mtu: u64,
+    pub received_bytes: u64,
+    pub transmitted_bytes: u64,
+}
+
+#[modkit_macros::api_dto(request, response)]
+pub struct ContainerInfoDto {
+    /// docker, podman, containerd, cri-o, lxc or unknown
+    pub runtime: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub container_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kubernetes: Option<KubernetesInfoDto>,
+}
+
+#[modkit_macros::api_dto(request, response)]
+pub struct KubernetesInfoDto {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pod_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node_name: Option<String>,
+}
+
 /// System capabilities response DTO
 #[modkit_macros::api_dto(request, response)]
 pub struct NodeSysCapDto {
@@ -107,3 +181,130 @@ pub struct SysCapDto {
     /// When this capability was last fetched (Unix timestamp in seconds)
     pub fetched_at_secs: i64,
 }
+
+/// Custom capability to set on a node
+#[modkit_macros::api_dto(request)]
+pub struct CustomSysCapReq {
+    pub key: String,
+    pub category: String,
+    pub name: String,
+    pub display_name: String,
+    /// Defaults to true
+    #[serde(default)]
+    pub present: Option<bool>,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub amount: Option<f64>,
+    #[serde(default)]
+    pub amount_dimension: Option<String>,
+    #[serde(default)]
+    pub details: Option<String>,
+    /// Seconds until the entry expires; 0 (default) keeps it until removed
+    #[serde(default)]
+    pub cache_ttl_secs: u64,
+}
+
+/// Custom capabilities to add or replace
+#[modkit_macros::api_dto(request)]
+pub struct SetCustomSysCapReq {
+    pub capabilities: Vec<CustomSysCapReq>,
+}
+
+/// Constraint on a node capability
+#[modkit_macros::api_dto(request)]
+pub struct CapabilityRequirementDto {
+    /// Capability key, e.g. `hardware:ram`
+    pub key: String,
+    /// Require the capability to be present (default) or absent
+    #[serde(default)]
+    pub present: Option<bool>,
+    /// Minimum amount, inclusive
+    #[serde(default)]
+    pub min_amount: Option<f64>,
+    /// Maximum amount, inclusive
+    #[serde(default)]
+    pub max_amount: Option<f64>,
+    /// Dimension of the bounds; byte sizes (B, KB, MB, GB, TB) are converted
+    #[serde(default)]
+    pub amount_dimension: Option<String>,
+}
+
+/// Capability query for workload placement
+#[modkit_macros::api_dto(request)]
+pub struct NodeQueryReq {
+    /// All requirements must hold
+    #[serde(default)]
+    pub requirements: Vec<CapabilityRequirementDto>,
+    /// Eligible statuses; alive nodes only when empty
+    #[serde(default)]
+    pub statuses: Vec<NodeStatusDto>,
+}
+
+/// Aggregated usage of a node over one time bucket
+#[modkit_macros::api_dto(response)]
+pub struct MetricPointDto {
+    /// Start of the bucket
+    pub at: chrono::DateTime<chrono::Utc>,
+    /// Number of samples aggregated into the bucket
+    pub samples: u32,
+    pub cpu_percent_avg: f64,
+    pub cpu_percent_max: f64,
+    pub memory_percent_avg: f64,
+    pub memory_percent_max: f64,
+    pub memory_used_bytes_max: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gpu_memory_percent_avg: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gpu_memory_percent_max: Option<f64>,
+}
+
+/// Where a metric series is read from
+#[derive(Debug, Clone, Copy, Default)]
+#[modkit_macros::api_dto(request, response)]
+pub enum MetricSourceDto {
+    /// In-memory samples of the local node
+    #[default]
+    Memory,
+    /// Rollups persisted in the database
+    Rollups,
+}
+
+/// Usage time series of a node
+#[modkit_macros::api_dto(response)]
+pub struct MetricSeriesDto {
+    pub node_id: Uuid,
+    pub source: MetricSourceDto,
+    /// Width of each point
+    pub step_secs: u64,
+    /// Points in time order; buckets without samples are omitted
+    pub points: Vec<MetricPointDto>,
+}
+
+#[derive(Debug, Clone, Copy)]
+#[modkit_macros::api_dto(response)]
+pub enum MetricKindDto {
+    Cpu,
+    Memory,
+    GpuMemory,
+}
+
+#[derive(Debug, Clone, Copy)]
+#[modkit_macros::api_dto(response)]
+pub enum AlertStateDto {
+    Firing,
+    Resolved,
+}
+
+/// Threshold rule state change
+#[modkit_macros::api_dto(response)]
+pub struct MetricAlertDto {
+    pub rule: String,
+    pub metric: MetricKindDto,
+    pub node_id: Uuid,
+    pub state: AlertStateDto,
+    /// Value of the metric when the rule changed state
+    pub value: f64,
+    pub threshold: f64,
+    pub at: chrono::DateTime<chrono::Utc>,
+}
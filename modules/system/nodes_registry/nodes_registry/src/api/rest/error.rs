@@ -17,6 +17,14 @@ pub fn domain_error_to_problem(err: DomainError, instance: &str) -> Problem {
         .with_type("https://errors.hyperspot.com/NODES_NOT_FOUND")
         .with_code("NODES_NOT_FOUND")
         .with_instance(instance),
+        DomainError::NodeInfoUnavailable(id) => Problem::new(
+            StatusCode::NOT_FOUND,
+            "Node information unavailable",
+            format!("System information is only collected for the local node, not for {id}"),
+        )
+        .with_type("https://errors.hyperspot.com/NODE_INFO_UNAVAILABLE")
+        .with_code("NODE_INFO_UNAVAILABLE")
+        .with_instance(instance),
         DomainError::SysInfoCollectionFailed(msg) => Problem::new(
             StatusCode::INTERNAL_SERVER_ERROR,
             "System information collection failed",
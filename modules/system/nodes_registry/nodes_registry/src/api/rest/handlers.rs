@@ -6,9 +6,19 @@ use modkit::api::prelude::*;
 use serde::Deserialize;
 use std::sync::Arc;
 
-use super::dto::{NodeDto, NodeSysCapDto, NodeSysInfoDto};
+use super::dto::{
+    MetricAlertDto, MetricSeriesDto, MetricSourceDto, NodeDto, NodeQueryReq, NodeSysCapDto,
+    NodeSysInfoDto, RegisterNodeReq, SetCustomSysCapReq,
+};
+use crate::domain::error::DomainError;
 use crate::domain::service::Service;
 
+/// Upper bound on the number of points in a metric series
+const MAX_SERIES_POINTS: u64 = 1440;
+
+/// Upper bound on the length of a metric series (90 days)
+const MAX_WINDOW_SECS: u64 = 90 * 24 * 60 * 60;
+
 #[derive(Debug, Deserialize)]
 pub struct DetailsQuery {
     #[serde(default)]
@@ -24,18 +34,30 @@ pub struct SysCapQuery {
     pub force_refresh: bool,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct MetricsQuery {
+    /// How far back the series goes (default 1 hour)
+    #[serde(default)]
+    pub window_secs: Option<u64>,
+    /// Width of each point (default 60 seconds)
+    #[serde(default)]
+    pub step_secs: Option<u64>,
+    #[serde(default)]
+    pub source: MetricSourceDto,
+}
+
 /// List all nodes
 pub async fn list_nodes(
     Extension(svc): Extension<Arc<Service>>,
     Query(query): Query<DetailsQuery>,
 ) -> ApiResult<Json<Vec<NodeDto>>> {
-    let nodes = svc.list_nodes();
+    let nodes = svc.list_members();
 
     if query.details {
         // Include sysinfo and syscap for each node
         let mut detailed_nodes = Vec::new();
         for node in nodes {
-            let node_id = node.id;
+            let node_id = node.node.id;
             let sysinfo = svc.get_node_sysinfo(node_id).ok().map(Into::into);
             let syscap = svc
                 .get_node_syscap(node_id, query.force_refresh)
@@ -59,7 +81,7 @@ pub async fn get_node(
     Path(id): Path<uuid::Uuid>,
     Query(query): Query<DetailsQuery>,
 ) -> ApiResult<Json<NodeDto>> {
-    let node = svc.get_node(id)?;
+    let node = svc.get_member(id)?;
 
     if query.details {
         let sysinfo = svc.get_node_sysinfo(id).ok().map(Into::into);
@@ -77,6 +99,33 @@ pub async fn get_node(
     }
 }
 
+/// Register (or re-register) a node
+pub async fn register_node(
+    Extension(svc): Extension<Arc<Service>>,
+    Json(req): Json<RegisterNodeReq>,
+) -> ApiResult<Json<NodeDto>> {
+    let member = svc.register_node(req.into()).await?;
+    Ok(Json(member.into()))
+}
+
+/// Record a heartbeat from a node
+pub async fn heartbeat(
+    Extension(svc): Extension<Arc<Service>>,
+    Path(id): Path<uuid::Uuid>,
+) -> ApiResult<Json<NodeDto>> {
+    let member = svc.heartbeat(id).await?;
+    Ok(Json(member.into()))
+}
+
+/// Remove a node from the fleet
+pub async fn deregister_node(
+    Extension(svc): Extension<Arc<Service>>,
+    Path(id): Path<uuid::Uuid>,
+) -> ApiResult<StatusCode> {
+    svc.deregister_node(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
 /// Get system information for a node
 pub async fn get_node_sysinfo(
     Extension(svc): Extension<Arc<Service>>,
@@ -95,3 +144,101 @@ pub async fn get_node_syscap(
     let syscap = svc.get_node_syscap(node_id, query.force_refresh)?;
     Ok(Json(syscap.into()))
 }
+
+/// Add or replace custom capabilities of a node
+pub async fn set_custom_syscap(
+    Extension(svc): Extension<Arc<Service>>,
+    Path(node_id): Path<uuid::Uuid>,
+    Json(req): Json<SetCustomSysCapReq>,
+) -> ApiResult<Json<NodeSysCapDto>> {
+    let caps = req.capabilities.into_iter().map(Into::into).collect();
+    svc.set_custom_syscap(node_id, caps)?;
+    let syscap = svc.get_node_syscap(node_id, false)?;
+    Ok(Json(syscap.into()))
+}
+
+/// Remove a single custom capability of a node
+pub async fn remove_custom_syscap(
+    Extension(svc): Extension<Arc<Service>>,
+    Path((node_id, key)): Path<(uuid::Uuid, String)>,
+) -> ApiResult<StatusCode> {
+    svc.remove_custom_syscap(node_id, vec![key])?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Remove all custom capabilities of a node
+pub async fn clear_custom_syscap(
+    Extension(svc): Extension<Arc<Service>>,
+    Path(node_id): Path<uuid::Uuid>,
+) -> ApiResult<StatusCode> {
+    svc.clear_custom_syscap(node_id)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Find nodes whose capabilities satisfy a query
+pub async fn query_nodes(
+    Extension(svc): Extension<Arc<Service>>,
+    Json(req): Json<NodeQueryReq>,
+) -> ApiResult<Json<Vec<NodeDto>>> {
+    let nodes = svc
+        .find_nodes(&req.into())
+        .into_iter()
+        .map(|(member, syscap)| {
+            let mut node_dto: NodeDto = member.into();
+            node_dto.syscap = syscap.map(Into::into);
+            node_dto
+        })
+        .collect();
+    Ok(Json(nodes))
+}
+
+/// Get the usage time series of a node
+pub async fn get_node_metrics(
+    Extension(svc): Extension<Arc<Service>>,
+    Path(node_id): Path<uuid::Uuid>,
+    Query(query): Query<MetricsQuery>,
+) -> ApiResult<Json<MetricSeriesDto>> {
+    let window_secs = query.window_secs.unwrap_or(3600);
+    let step_secs = query.step_secs.unwrap_or(60);
+    if window_secs == 0 || step_secs == 0 {
+        return Err(DomainError::InvalidInput(
+            "window_secs and step_secs must be greater than 0".to_owned(),
+        )
+        .into());
+    }
+    if window_secs > MAX_WINDOW_SECS {
+        return Err(DomainError::InvalidInput(format!(
+            "window_secs must not exceed {MAX_WINDOW_SECS}"
+        ))
+        .into());
+    }
+    if window_secs.div_ceil(step_secs) > MAX_SERIES_POINTS {
+        return Err(DomainError::InvalidInput(format!(
+            "window_secs / step_secs must not exceed {MAX_SERIES_POINTS} points"
+        ))
+        .into());
+    }
+
+    let until = chrono::Utc::now();
+    let since = until - chrono::Duration::seconds(i64::try_from(window_secs).unwrap_or(0));
+    let step = std::time::Duration::from_secs(step_secs);
+    let points = match query.source {
+        MetricSourceDto::Memory => svc.metric_series(node_id, since, until, step)?,
+        MetricSourceDto::Rollups => svc.metric_rollups(node_id, since, until, step).await?,
+    };
+
+    Ok(Json(MetricSeriesDto {
+        node_id,
+        source: query.source,
+        step_secs,
+        points: points.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// List the latest threshold alerts
+pub async fn list_alerts(
+    Extension(svc): Extension<Arc<Service>>,
+) -> ApiResult<Json<Vec<MetricAlertDto>>> {
+    let alerts = svc.recent_alerts().into_iter().map(Into::into).collect();
+    Ok(Json(alerts))
+}
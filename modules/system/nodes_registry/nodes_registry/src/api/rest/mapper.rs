@@ -1,27 +1,65 @@
+use super::dto::{AlertStateDto, MetricAlertDto, MetricKindDto, MetricPointDto};
 use super::dto::{
-    BatteryInfoDto, CpuInfoDto, GpuInfoDto, HostInfoDto, MemoryInfoDto, NodeDto, NodeSysCapDto,
-    NodeSysInfoDto, OsInfoDto, SysCapDto,
+    BatteryInfoDto, CapabilityRequirementDto, ContainerInfoDto, CpuInfoDto, CustomSysCapReq,
+    DiskInfoDto, GpuInfoDto, HostInfoDto, KubernetesInfoDto, MemoryInfoDto,
+    NetworkInterfaceInfoDto, NodeDto, NodeQueryReq, NodeStatusDto, NodeSysCapDto, NodeSysInfoDto,
+    OsInfoDto, RegisterNodeReq, SysCapDto,
 };
+use crate::domain::metrics::{AlertState, MetricAlert, MetricKind, MetricPoint};
 use nodes_registry_sdk::{
-    BatteryInfo, CpuInfo, GpuInfo, HostInfo, MemoryInfo, Node, NodeSysCap, NodeSysInfo, OsInfo,
-    SysCap,
+    BatteryInfo, CapabilityQuery, CapabilityRequirement, ContainerInfo, CpuInfo, DiskInfo, GpuInfo,
+    HostInfo, KubernetesInfo, MemoryInfo, NetworkInterfaceInfo, NodeMember, NodeRegistration,
+    NodeStatus, NodeSysCap, NodeSysInfo, OsInfo, SysCap,
 };
 
 // Node mappings
-impl From<Node> for NodeDto {
-    fn from(node: Node) -> Self {
+impl From<NodeMember> for NodeDto {
+    fn from(member: NodeMember) -> Self {
+        let node = member.node;
         Self {
             id: node.id,
             hostname: node.hostname,
             ip_address: node.ip_address,
             created_at: node.created_at,
             updated_at: node.updated_at,
+            status: member.status.into(),
+            last_seen_at: member.last_seen_at,
             sysinfo: None,
             syscap: None,
         }
     }
 }
 
+impl From<NodeStatus> for NodeStatusDto {
+    fn from(status: NodeStatus) -> Self {
+        match status {
+            NodeStatus::Alive => Self::Alive,
+            NodeStatus::Suspect => Self::Suspect,
+            NodeStatus::Dead => Self::Dead,
+        }
+    }
+}
+
+impl From<NodeStatusDto> for NodeStatus {
+    fn from(status: NodeStatusDto) -> Self {
+        match status {
+            NodeStatusDto::Alive => Self::Alive,
+            NodeStatusDto::Suspect => Self::Suspect,
+            NodeStatusDto::Dead => Self::Dead,
+        }
+    }
+}
+
+impl From<RegisterNodeReq> for NodeRegistration {
+    fn from(req: RegisterNodeReq) -> Self {
+        Self {
+            id: req.id,
+            hostname: req.hostname,
+            ip_address: req.ip_address,
+        }
+    }
+}
+
 // SysInfo mappings
 impl From<NodeSysInfo> for NodeSysInfoDto {
     fn from(info: NodeSysInfo) -> Self {
@@ -33,6 +71,13 @@ impl From<NodeSysInfo> for NodeSysInfoDto {
             host: info.host.into(),
             gpus: info.gpus.into_iter().map(Into::into).collect(),
             battery: info.battery.map(Into::into),
+            disks: info.disks.into_iter().map(Into::into).collect(),
+            network_interfaces: info
+                .network_interfaces
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            container: info.container.map(Into::into),
             collected_at: info.collected_at,
         }
     }
@@ -55,6 +100,7 @@ impl From<CpuInfo> for CpuInfoDto {
             num_cpus: info.num_cpus,
             cores: info.cores,
             frequency_mhz: info.frequency_mhz,
+            quota_cores: info.quota_cores,
         }
     }
 }
@@ -66,6 +112,7 @@ impl From<MemoryInfo> for MemoryInfoDto {
             available_bytes: info.available_bytes,
             used_bytes: info.used_bytes,
             used_percent: info.used_percent,
+            limit_bytes: info.limit_bytes,
         }
     }
 }
@@ -100,6 +147,53 @@ impl From<BatteryInfo> for BatteryInfoDto {
     }
 }
 
+impl From<DiskInfo> for DiskInfoDto {
+    fn from(info: DiskInfo) -> Self {
+        Self {
+            name: info.name,
+            mount_point: info.mount_point,
+            file_system: info.file_system,
+            total_bytes: info.total_bytes,
+            available_bytes: info.available_bytes,
+            is_removable: info.is_removable,
+            is_read_only: info.is_read_only,
+        }
+    }
+}
+
+impl From<NetworkInterfaceInfo> for NetworkInterfaceInfoDto {
+    fn from(info: NetworkInterfaceInfo) -> Self {
+        Self {
+            name: info.name,
+            mac_address: info.mac_address,
+            ip_addresses: info.ip_addresses,
+            mtu: info.mtu,
+            received_bytes: info.received_bytes,
+            transmitted_bytes: info.transmitted_bytes,
+        }
+    }
+}
+
+impl From<ContainerInfo> for ContainerInfoDto {
+    fn from(info: ContainerInfo) -> Self {
+        Self {
+            runtime: info.runtime.as_str().to_owned(),
+            container_id: info.container_id,
+            kubernetes: info.kubernetes.map(Into::into),
+        }
+    }
+}
+
+impl From<KubernetesInfo> for KubernetesInfoDto {
+    fn from(info: KubernetesInfo) -> Self {
+        Self {
+            namespace: info.namespace,
+            pod_name: info.pod_name,
+            node_name: info.node_name,
+        }
+    }
+}
+
 // SysCap mappings
 impl From<NodeSysCap> for NodeSysCapDto {
     fn from(cap: NodeSysCap) -> Self {
@@ -128,3 +222,88 @@ impl From<SysCap> for SysCapDto {
         }
     }
 }
+
+// Custom syscap and capability query mappings
+impl From<CustomSysCapReq> for SysCap {
+    fn from(req: CustomSysCapReq) -> Self {
+        Self {
+            key: req.key,
+            category: req.category,
+            name: req.name,
+            display_name: req.display_name,
+            present: req.present.unwrap_or(true),
+            version: req.version,
+            amount: req.amount,
+            amount_dimension: req.amount_dimension,
+            details: req.details,
+            cache_ttl_secs: req.cache_ttl_secs,
+            // TTL counts from when the registry received the entry
+            fetched_at_secs: chrono::Utc::now().timestamp(),
+        }
+    }
+}
+
+impl From<CapabilityRequirementDto> for CapabilityRequirement {
+    fn from(dto: CapabilityRequirementDto) -> Self {
+        Self {
+            key: dto.key,
+            present: dto.present.unwrap_or(true),
+            min_amount: dto.min_amount,
+            max_amount: dto.max_amount,
+            amount_dimension: dto.amount_dimension,
+        }
+    }
+}
+
+impl From<NodeQueryReq> for CapabilityQuery {
+    fn from(req: NodeQueryReq) -> Self {
+        Self {
+            requirements: req.requirements.into_iter().map(Into::into).collect(),
+            statuses: req.statuses.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+// Metrics mappings
+impl From<MetricPoint> for MetricPointDto {
+    fn from(point: MetricPoint) -> Self {
+        Self {
+            at: point.at,
+            samples: point.samples,
+            cpu_percent_avg: point.cpu_percent_avg,
+            cpu_percent_max: point.cpu_percent_max,
+            memory_percent_avg: point.memory_percent_avg,
+            memory_percent_max: point.memory_percent_max,
+            memory_used_bytes_max: point.memory_used_bytes_max,
+            gpu_memory_percent_avg: point.gpu_memory_percent_avg,
+            gpu_memory_percent_max: point.gpu_memory_percent_max,
+        }
+    }
+}
+
+impl From<MetricKind> for MetricKindDto {
+    fn from(kind: MetricKind) -> Self {
+        match kind {
+            MetricKind::Cpu => Self::Cpu,
+            MetricKind::Memory => Self::Memory,
+            MetricKind::GpuMemory => Self::GpuMemory,
+        }
+    }
+}
+
+impl From<MetricAlert> for MetricAlertDto {
+    fn from(alert: MetricAlert) -> Self {
+        Self {
+            rule: alert.rule,
+            metric: alert.metric.into(),
+            node_id: alert.node_id,
+            state: match alert.state {
+                AlertState::Firing => AlertStateDto::Firing,
+                AlertState::Resolved => AlertStateDto::Resolved,
+            },
+            value: alert.value,
+            threshold: alert.threshold,
+            at: alert.at,
+        }
+    }
+}
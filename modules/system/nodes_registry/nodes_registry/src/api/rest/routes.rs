@@ -1,12 +1,54 @@
 use axum::http;
 use axum::{Extension, Router};
+use modkit::api::operation_builder::{AuthReqAction, AuthReqResource, LicenseFeature};
 use modkit::api::{Missing, OpenApiRegistry, OperationBuilder};
 use std::sync::Arc;
 
-use super::dto::{NodeDto, NodeSysCapDto, NodeSysInfoDto};
+use super::dto::{
+    MetricAlertDto, MetricSeriesDto, NodeDto, NodeQueryReq, NodeSysCapDto, NodeSysInfoDto,
+    RegisterNodeReq, SetCustomSysCapReq,
+};
 use super::handlers;
 use crate::domain::service::Service;
 
+enum Resource {
+    Nodes,
+}
+
+enum Action {
+    Write,
+}
+
+impl AsRef<str> for Resource {
+    fn as_ref(&self) -> &'static str {
+        match self {
+            Resource::Nodes => "nodes_registry",
+        }
+    }
+}
+
+impl AuthReqResource for Resource {}
+
+impl AsRef<str> for Action {
+    fn as_ref(&self) -> &'static str {
+        match self {
+            Action::Write => "write",
+        }
+    }
+}
+
+impl AuthReqAction for Action {}
+
+struct License;
+
+impl AsRef<str> for License {
+    fn as_ref(&self) -> &'static str {
+        "gts.x.core.lic.feat.v1~x.core.global.base.v1"
+    }
+}
+
+impl LicenseFeature for License {}
+
 /// Register all REST routes for the nodes registry module
 pub fn register_routes(
     mut router: Router,
@@ -17,7 +59,7 @@ pub fn register_routes(
     router = OperationBuilder::<Missing, Missing, ()>::get("/nodes-registry/v1/nodes")
         .operation_id("nodes_registry.list_nodes")
         .summary("List all nodes")
-        .description("Get a list of all nodes in the deployment. Use ?details=true to include sysinfo and syscap. Use ?force_refresh=true to invalidate syscap cache.")
+        .description("Get a list of all nodes in the fleet with their status (alive, suspect, dead) and last heartbeat. Use ?details=true to include sysinfo and syscap. Use ?force_refresh=true to invalidate syscap cache.")
         .tag("nodes")
         .public()
         .query_param("details", false, "Include detailed system information and capabilities")
@@ -43,6 +85,58 @@ pub fn register_routes(
         .error_500(openapi)
         .register(router, openapi);
 
+    // POST /nodes - Register a node
+    router = OperationBuilder::post("/nodes-registry/v1/nodes")
+        .operation_id("nodes_registry.register_node")
+        .require_auth(&Resource::Nodes, &Action::Write)
+        .require_license_features::<License>([])
+        .summary("Register node")
+        .description("Register a node in the fleet, or update the hostname and IP of an already registered node. Registration counts as a heartbeat.")
+        .tag("nodes")
+        .json_request::<RegisterNodeReq>(openapi, "Node to register")
+        .handler(handlers::register_node)
+        .json_response_with_schema::<NodeDto>(openapi, http::StatusCode::OK, "Registered node")
+        .error_400(openapi)
+        .error_401(openapi)
+        .error_403(openapi)
+        .error_500(openapi)
+        .register(router, openapi);
+
+    // POST /nodes/{id}/heartbeat - Record a heartbeat
+    router = OperationBuilder::post("/nodes-registry/v1/nodes/{id}/heartbeat")
+        .operation_id("nodes_registry.heartbeat")
+        .require_auth(&Resource::Nodes, &Action::Write)
+        .require_license_features::<License>([])
+        .summary("Node heartbeat")
+        .description("Record a heartbeat from a registered node. Returns 404 if the node is unknown and has to register again.")
+        .tag("nodes")
+        .path_param("id", "Node UUID")
+        .handler(handlers::heartbeat)
+        .json_response_with_schema::<NodeDto>(openapi, http::StatusCode::OK, "Node after the heartbeat")
+        .error_401(openapi)
+        .error_403(openapi)
+        .error_404(openapi)
+        .error_500(openapi)
+        .register(router, openapi);
+
+    // DELETE /nodes/{id} - Deregister a node
+    router = OperationBuilder::delete("/nodes-registry/v1/nodes/{id}")
+        .operation_id("nodes_registry.deregister_node")
+        .require_auth(&Resource::Nodes, &Action::Write)
+        .require_license_features::<License>([])
+        .summary("Deregister node")
+        .description("Remove a node from the fleet, e.g. on graceful shutdown. The registry's own node cannot be removed.")
+        .tag("nodes")
+        .path_param("id", "Node UUID")
+        .handler(handlers::deregister_node)
+        .json_response(http::StatusCode::NO_CONTENT, "Node deregistered")
+        .error_400(openapi)
+        .error_401(openapi)
+        .error_403(openapi)
+        .error_404(openapi)
+        .error_500(openapi)
+        .register(router, openapi);
+
     // GET /nodes/{id}/sysinfo - Get system information for a node
     router = OperationBuilder::<Missing, Missing, ()>::get("/nodes-registry/v1/nodes/{id}/sysinfo")
         .operation_id("nodes_registry.get_node_sysinfo")
@@ -76,6 +170,108 @@ pub fn register_routes(
         .error_500(openapi)
         .register(router, openapi);
 
+    // PUT /nodes/{id}/syscap/custom - Add or replace custom capabilities
+    router = OperationBuilder::put("/nodes-registry/v1/nodes/{id}/syscap/custom")
+        .operation_id("nodes_registry.set_custom_syscap")
+        .require_auth(&Resource::Nodes, &Action::Write)
+        .require_license_features::<License>([])
+        .summary("Set custom node capabilities")
+        .description("Add or replace custom capabilities of a node. Each entry expires cache_ttl_secs after being set; 0 keeps it until removed. Custom entries override system capabilities with the same key.")
+        .tag("nodes")
+        .path_param("id", "Node UUID")
+        .json_request::<SetCustomSysCapReq>(openapi, "Custom capabilities")
+        .handler(handlers::set_custom_syscap)
+        .json_response_with_schema::<NodeSysCapDto>(openapi, http::StatusCode::OK, "System capabilities (merged)")
+        .error_400(openapi)
+        .error_401(openapi)
+        .error_403(openapi)
+        .error_404(openapi)
+        .error_500(openapi)
+        .register(router, openapi);
+
+    // DELETE /nodes/{id}/syscap/custom - Clear custom capabilities
+    router = OperationBuilder::delete("/nodes-registry/v1/nodes/{id}/syscap/custom")
+        .operation_id("nodes_registry.clear_custom_syscap")
+        .require_auth(&Resource::Nodes, &Action::Write)
+        .require_license_features::<License>([])
+        .summary("Clear custom node capabilities")
+        .description(
+            "Remove all custom capabilities of a node. System capabilities are not affected.",
+        )
+        .tag("nodes")
+        .path_param("id", "Node UUID")
+        .handler(handlers::clear_custom_syscap)
+        .json_response(http::StatusCode::NO_CONTENT, "Custom capabilities removed")
+        .error_401(openapi)
+        .error_403(openapi)
+        .error_404(openapi)
+        .error_500(openapi)
+        .register(router, openapi);
+
+    // DELETE /nodes/{id}/syscap/custom/{key} - Remove one custom capability
+    router = OperationBuilder::delete("/nodes-registry/v1/nodes/{id}/syscap/custom/{key}")
+        .operation_id("nodes_registry.remove_custom_syscap")
+        .require_auth(&Resource::Nodes, &Action::Write)
+        .require_license_features::<License>([])
+        .summary("Remove custom node capability")
+        .description(
+            "Remove a custom capability of a node by key. Removing a key that is not set succeeds.",
+        )
+        .tag("nodes")
+        .path_param("id", "Node UUID")
+        .path_param("key", "Capability key")
+        .handler(handlers::remove_custom_syscap)
+        .json_response(http::StatusCode::NO_CONTENT, "Custom capability removed")
+        .error_401(openapi)
+        .error_403(openapi)
+        .error_404(openapi)
+        .error_500(openapi)
+        .register(router, openapi);
+
+    // POST /nodes/query - Find nodes by capabilities
+    router = OperationBuilder::post("/nodes-registry/v1/nodes/query")
+        .operation_id("nodes_registry.query_nodes")
+        .summary("Query nodes by capabilities")
+        .description("Find nodes whose merged capabilities satisfy all requirements, e.g. `gpu` present and `hardware:ram` >= 16 GB. Only alive nodes are considered unless statuses are given. Matching nodes include their capabilities.")
+        .tag("nodes")
+        .public()
+        .json_request::<NodeQueryReq>(openapi, "Capability query")
+        .handler(handlers::query_nodes)
+        .json_response_with_schema::<Vec<NodeDto>>(openapi, http::StatusCode::OK, "Matching nodes")
+        .error_400(openapi)
+        .error_500(openapi)
+        .register(router, openapi);
+
+    // GET /nodes/{id}/metrics - Usage time series
+    router = OperationBuilder::get("/nodes-registry/v1/nodes/{id}/metrics")
+        .operation_id("nodes_registry.get_node_metrics")
+        .summary("Get node usage metrics")
+        .description("CPU, memory and GPU memory usage of a node over the last `window_secs` (default 3600), downsampled to `step_secs` buckets (default 60) with average and maximum per bucket. `source=memory` (default) reads the in-memory samples, which exist for the local node only; `source=rollups` reads the rollups persisted by every replica.")
+        .tag("nodes")
+        .public()
+        .path_param("id", "Node UUID")
+        .query_param("window_secs", false, "Length of the series in seconds")
+        .query_param("step_secs", false, "Width of each point in seconds")
+        .query_param("source", false, "memory or rollups")
+        .handler(handlers::get_node_metrics)
+        .json_response_with_schema::<MetricSeriesDto>(openapi, http::StatusCode::OK, "Usage series")
+        .error_400(openapi)
+        .error_404(openapi)
+        .error_500(openapi)
+        .register(router, openapi);
+
+    // GET /alerts - Latest threshold alerts
+    router = OperationBuilder::get("/nodes-registry/v1/alerts")
+        .operation_id("nodes_registry.list_alerts")
+        .summary("List metric alerts")
+        .description("Latest threshold rule transitions (firing and resolved) of the local node, oldest first.")
+        .tag("nodes")
+        .public()
+        .handler(handlers::list_alerts)
+        .json_response_with_schema::<Vec<MetricAlertDto>>(openapi, http::StatusCode::OK, "Alerts")
+        .error_500(openapi)
+        .register(router, openapi);
+
     // Attach service to router as extension
     router = router.layer(Extension(service));
 
@@ -1,4 +1,8 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::time::Duration;
+
+use crate::domain::metrics::ThresholdRule;
 
 /// Configuration for the nodes registry module
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -7,16 +11,199 @@ pub struct NodesRegistryConfig {
     /// Enable/disable the nodes registry module
     #[serde(default = "default_enabled")]
     pub enabled: bool,
+
+    /// Fleet this registry belongs to. Replicas sharing a database only see nodes
+    /// of the same cluster.
+    #[serde(default)]
+    pub cluster_id: uuid::Uuid,
+
+    /// How often the local node heartbeats and the fleet view is refreshed
+    #[serde(default = "default_heartbeat_interval_secs")]
+    pub heartbeat_interval_secs: u64,
+
+    /// Heartbeat age after which a node is reported as `suspect`
+    #[serde(default = "default_suspect_after_secs")]
+    pub suspect_after_secs: u64,
+
+    /// Heartbeat age after which a node is reported as `dead`
+    #[serde(default = "default_dead_after_secs")]
+    pub dead_after_secs: u64,
+
+    /// gRPC endpoint of a remote nodes registry to join (e.g. `http://10.0.0.1:50051`).
+    /// When set, the local node registers there and sends heartbeats to it.
+    #[serde(default)]
+    pub join_endpoint: Option<String>,
+
+    /// Sampling of the local node's resource usage
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+}
+
+/// Resource usage sampling, rollups and threshold alerts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MetricsConfig {
+    /// Enable/disable sampling. Off by default: every sample collects GPU
+    /// usage, which may run external tools on the host.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// How often CPU/memory/GPU usage is sampled
+    #[serde(default = "default_sample_interval_secs")]
+    pub sample_interval_secs: u64,
+
+    /// Number of samples kept in memory (6 hours at the default interval)
+    #[serde(default = "default_retention_samples")]
+    pub retention_samples: usize,
+
+    /// Width of the rollups persisted to the database; 0 disables rollups.
+    /// Ignored when the module has no database.
+    #[serde(default = "default_rollup_interval_secs")]
+    pub rollup_interval_secs: u64,
+
+    /// How long persisted rollups are kept
+    #[serde(default = "default_rollup_retention_days")]
+    pub rollup_retention_days: u64,
+
+    /// Threshold rules evaluated on every sample
+    #[serde(default)]
+    pub rules: Vec<ThresholdRule>,
+}
+
+fn default_sample_interval_secs() -> u64 {
+    10
+}
+
+fn default_retention_samples() -> usize {
+    2160
+}
+
+fn default_rollup_interval_secs() -> u64 {
+    300
+}
+
+fn default_rollup_retention_days() -> u64 {
+    30
+}
+
+impl MetricsConfig {
+    /// Check the intervals and threshold rules.
+    ///
+    /// # Errors
+    /// Returns a description of the first invalid setting.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.sample_interval_secs == 0 {
+            return Err("metrics.sample_interval_secs must be greater than 0".to_owned());
+        }
+        if self.retention_samples == 0 {
+            return Err("metrics.retention_samples must be greater than 0".to_owned());
+        }
+        if self.rollup_interval_secs != 0 && self.rollup_interval_secs < self.sample_interval_secs {
+            return Err(
+                "metrics.rollup_interval_secs must be 0 or at least sample_interval_secs"
+                    .to_owned(),
+            );
+        }
+        let mut names = HashSet::new();
+        for rule in &self.rules {
+            if rule.name.trim().is_empty() {
+                return Err("metrics.rules: rule name must not be empty".to_owned());
+            }
+            if !rule.above.is_finite() {
+                return Err(format!(
+                    "metrics.rules.{}: threshold must be finite",
+                    rule.name
+                ));
+            }
+            if !names.insert(rule.name.as_str()) {
+                return Err(format!("metrics.rules.{}: duplicate rule name", rule.name));
+            }
+        }
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn sample_interval(&self) -> Duration {
+        Duration::from_secs(self.sample_interval_secs)
+    }
+
+    /// Rollup width, `None` when rollups are disabled
+    #[must_use]
+    pub fn rollup_interval(&self) -> Option<Duration> {
+        (self.rollup_interval_secs > 0).then(|| Duration::from_secs(self.rollup_interval_secs))
+    }
+
+    #[must_use]
+    pub fn rollup_retention(&self) -> Duration {
+        Duration::from_secs(self.rollup_retention_days.saturating_mul(24 * 60 * 60))
+    }
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            sample_interval_secs: default_sample_interval_secs(),
+            retention_samples: default_retention_samples(),
+            rollup_interval_secs: default_rollup_interval_secs(),
+            rollup_retention_days: default_rollup_retention_days(),
+            rules: Vec::new(),
+        }
+    }
 }
 
 fn default_enabled() -> bool {
     true
 }
 
+fn default_heartbeat_interval_secs() -> u64 {
+    10
+}
+
+fn default_suspect_after_secs() -> u64 {
+    30
+}
+
+fn default_dead_after_secs() -> u64 {
+    120
+}
+
+impl NodesRegistryConfig {
+    /// Check that the heartbeat thresholds are consistent.
+    ///
+    /// # Errors
+    /// Returns a description of the first invalid setting.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.heartbeat_interval_secs == 0 {
+            return Err("heartbeat_interval_secs must be greater than 0".to_owned());
+        }
+        if self.suspect_after_secs <= self.heartbeat_interval_secs {
+            return Err(
+                "suspect_after_secs must be greater than heartbeat_interval_secs".to_owned(),
+            );
+        }
+        if self.dead_after_secs <= self.suspect_after_secs {
+            return Err("dead_after_secs must be greater than suspect_after_secs".to_owned());
+        }
+        self.metrics.validate()
+    }
+
+    #[must_use]
+    pub fn heartbeat_interval(&self) -> Duration {
+        Duration::from_secs(self.heartbeat_interval_secs)
+    }
+}
+
 impl Default for NodesRegistryConfig {
     fn default() -> Self {
         Self {
             enabled: default_enabled(),
+            cluster_id: uuid::Uuid::nil(),
+            heartbeat_interval_secs: default_heartbeat_interval_secs(),
+            suspect_after_secs: default_suspect_after_secs(),
+            dead_after_secs: default_dead_after_secs(),
+            join_endpoint: None,
+            metrics: MetricsConfig::default(),
         }
     }
 }
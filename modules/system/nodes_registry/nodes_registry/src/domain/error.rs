@@ -4,6 +4,9 @@ pub enum DomainError {
     #[error("Node not found: {0}")]
     NodeNotFound(uuid::Uuid),
 
+    #[error("System information is not available for remote node: {0}")]
+    NodeInfoUnavailable(uuid::Uuid),
+
     #[error("Failed to collect system information: {0}")]
     SysInfoCollectionFailed(String),
 
@@ -23,6 +26,18 @@ impl From<anyhow::Error> for DomainError {
     }
 }
 
+impl From<modkit_db::DbError> for DomainError {
+    fn from(e: modkit_db::DbError) -> Self {
+        Self::Internal(format!("database error: {e}"))
+    }
+}
+
+impl From<modkit_db::secure::ScopeError> for DomainError {
+    fn from(e: modkit_db::secure::ScopeError) -> Self {
+        Self::Internal(format!("membership store: {e}"))
+    }
+}
+
 impl From<modkit_node_info::NodeInfoError> for DomainError {
     fn from(e: modkit_node_info::NodeInfoError) -> Self {
         match e {
@@ -44,6 +59,7 @@ impl From<DomainError> for nodes_registry_sdk::NodesRegistryError {
     fn from(e: DomainError) -> Self {
         match e {
             DomainError::NodeNotFound(id) => Self::NodeNotFound(id),
+            DomainError::NodeInfoUnavailable(id) => Self::NodeInfoUnavailable(id),
             DomainError::SysInfoCollectionFailed(msg) => Self::SysInfoCollectionFailed(msg),
             DomainError::SysCapCollectionFailed(msg) => Self::SysCapCollectionFailed(msg),
             DomainError::InvalidInput(msg) => Self::Validation(msg),
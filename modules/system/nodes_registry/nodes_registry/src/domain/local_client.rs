@@ -1,5 +1,8 @@
 use crate::domain::service::Service;
-use nodes_registry_sdk::{Node, NodeSysCap, NodeSysInfo, NodesRegistryClient, NodesRegistryError};
+use nodes_registry_sdk::{
+    CapabilityQuery, Node, NodeMember, NodeRegistration, NodeSysCap, NodeSysInfo,
+    NodesRegistryClient, NodesRegistryError, SysCap,
+};
 use std::sync::Arc;
 
 /// Local client implementation for the nodes registry
@@ -20,8 +23,29 @@ impl NodesRegistryClient for NodesRegistryLocalClient {
         self.service.get_node(id).map_err(Into::into)
     }
 
-    async fn list_nodes(&self) -> Result<Vec<Node>, NodesRegistryError> {
-        Ok(self.service.list_nodes())
+    async fn list_nodes(&self) -> Result<Vec<NodeMember>, NodesRegistryError> {
+        Ok(self.service.list_members())
+    }
+
+    async fn register_node(
+        &self,
+        registration: NodeRegistration,
+    ) -> Result<NodeMember, NodesRegistryError> {
+        self.service
+            .register_node(registration)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn heartbeat(&self, node_id: uuid::Uuid) -> Result<NodeMember, NodesRegistryError> {
+        self.service.heartbeat(node_id).await.map_err(Into::into)
+    }
+
+    async fn deregister_node(&self, node_id: uuid::Uuid) -> Result<(), NodesRegistryError> {
+        self.service
+            .deregister_node(node_id)
+            .await
+            .map_err(Into::into)
     }
 
     async fn get_node_sysinfo(
@@ -36,4 +60,43 @@ impl NodesRegistryClient for NodesRegistryLocalClient {
             .get_node_syscap(node_id, false)
             .map_err(Into::into)
     }
+
+    async fn set_custom_syscap(
+        &self,
+        node_id: uuid::Uuid,
+        caps: Vec<SysCap>,
+    ) -> Result<NodeSysCap, NodesRegistryError> {
+        self.service.set_custom_syscap(node_id, caps)?;
+        self.service
+            .get_node_syscap(node_id, false)
+            .map_err(Into::into)
+    }
+
+    async fn remove_custom_syscap(
+        &self,
+        node_id: uuid::Uuid,
+        keys: Vec<String>,
+    ) -> Result<(), NodesRegistryError> {
+        self.service
+            .remove_custom_syscap(node_id, keys)
+            .map_err(Into::into)
+    }
+
+    async fn clear_custom_syscap(&self, node_id: uuid::Uuid) -> Result<(), NodesRegistryError> {
+        self.service
+            .clear_custom_syscap(node_id)
+            .map_err(Into::into)
+    }
+
+    async fn find_nodes(
+        &self,
+        query: &CapabilityQuery,
+    ) -> Result<Vec<NodeMember>, NodesRegistryError> {
+        Ok(self
+            .service
+            .find_nodes(query)
+            .into_iter()
+            .map(|(member, _)| member)
+            .collect())
+    }
 }
@@ -0,0 +1,48 @@
+use chrono::{DateTime, Utc};
+use nodes_registry_sdk::{Node, NodeMember, NodeStatus};
+use std::time::Duration;
+
+use crate::config::NodesRegistryConfig;
+
+/// Heartbeat thresholds used to derive node status
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MembershipPolicy {
+    pub suspect_after: Duration,
+    pub dead_after: Duration,
+}
+
+impl MembershipPolicy {
+    #[must_use]
+    pub fn member(
+        &self,
+        node: Node,
+        last_seen_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> NodeMember {
+        NodeMember {
+            status: NodeStatus::from_last_seen(
+                last_seen_at,
+                now,
+                self.suspect_after,
+                self.dead_after,
+            ),
+            node,
+            last_seen_at,
+        }
+    }
+}
+
+impl Default for MembershipPolicy {
+    fn default() -> Self {
+        Self::from(&NodesRegistryConfig::default())
+    }
+}
+
+impl From<&NodesRegistryConfig> for MembershipPolicy {
+    fn from(cfg: &NodesRegistryConfig) -> Self {
+        Self {
+            suspect_after: Duration::from_secs(cfg.suspect_after_secs),
+            dead_after: Duration::from_secs(cfg.dead_after_secs),
+        }
+    }
+}
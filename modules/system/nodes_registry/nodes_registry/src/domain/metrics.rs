@@ -0,0 +1,407 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Mutex, RwLock};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Alerts kept for the REST API and late subscribers
+const RECENT_ALERTS: usize = 100;
+
+/// Metric a threshold rule watches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricKind {
+    Cpu,
+    Memory,
+    GpuMemory,
+}
+
+impl MetricKind {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Cpu => "cpu",
+            Self::Memory => "memory",
+            Self::GpuMemory => "gpu_memory",
+        }
+    }
+}
+
+/// One usage sample of the local node
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricSample {
+    pub at: DateTime<Utc>,
+    pub cpu_percent: f64,
+    pub memory_used_bytes: u64,
+    pub memory_total_bytes: u64,
+    pub gpu_memory_percent: Option<f64>,
+}
+
+impl MetricSample {
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn memory_percent(&self) -> f64 {
+        if self.memory_total_bytes == 0 {
+            return 0.0;
+        }
+        self.memory_used_bytes as f64 * 100.0 / self.memory_total_bytes as f64
+    }
+
+    #[must_use]
+    pub fn value(&self, kind: MetricKind) -> Option<f64> {
+        match kind {
+            MetricKind::Cpu => Some(self.cpu_percent),
+            MetricKind::Memory => Some(self.memory_percent()),
+            MetricKind::GpuMemory => self.gpu_memory_percent,
+        }
+    }
+}
+
+impl From<modkit_node_info::ResourceUsage> for MetricSample {
+    fn from(usage: modkit_node_info::ResourceUsage) -> Self {
+        let gpu_memory_percent = match (usage.gpu_memory_used_mb, usage.gpu_memory_total_mb) {
+            (Some(used), Some(total)) if total > 0.0 => Some(used * 100.0 / total),
+            _ => None,
+        };
+        Self {
+            at: usage.collected_at,
+            cpu_percent: usage.cpu_percent,
+            memory_used_bytes: usage.memory_used_bytes,
+            memory_total_bytes: usage.memory_total_bytes,
+            gpu_memory_percent,
+        }
+    }
+}
+
+/// Aggregate of the samples in one time bucket; also the shape of stored rollups
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricPoint {
+    /// Start of the bucket
+    pub at: DateTime<Utc>,
+    pub samples: u32,
+    pub cpu_percent_avg: f64,
+    pub cpu_percent_max: f64,
+    pub memory_percent_avg: f64,
+    pub memory_percent_max: f64,
+    pub memory_used_bytes_max: u64,
+    pub gpu_memory_percent_avg: Option<f64>,
+    pub gpu_memory_percent_max: Option<f64>,
+}
+
+impl MetricPoint {
+    /// Aggregate `samples` into a bucket starting at `at`; `None` if empty
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn aggregate(at: DateTime<Utc>, samples: &[MetricSample]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let n = samples.len() as f64;
+        let avg = |f: &dyn Fn(&MetricSample) -> f64| samples.iter().map(f).sum::<f64>() / n;
+        let max = |f: &dyn Fn(&MetricSample) -> f64| samples.iter().map(f).fold(0.0, f64::max);
+
+        let gpu: Vec<f64> = samples
+            .iter()
+            .filter_map(|s| s.gpu_memory_percent)
+            .collect();
+        let (gpu_memory_percent_avg, gpu_memory_percent_max) = if gpu.is_empty() {
+            (None, None)
+        } else {
+            (
+                Some(gpu.iter().sum::<f64>() / gpu.len() as f64),
+                Some(gpu.iter().copied().fold(0.0, f64::max)),
+            )
+        };
+
+        Some(Self {
+            at,
+            samples: u32::try_from(samples.len()).unwrap_or(u32::MAX),
+            cpu_percent_avg: avg(&|s| s.cpu_percent),
+            cpu_percent_max: max(&|s| s.cpu_percent),
+            memory_percent_avg: avg(&MetricSample::memory_percent),
+            memory_percent_max: max(&MetricSample::memory_percent),
+            memory_used_bytes_max: samples
+                .iter()
+                .map(|s| s.memory_used_bytes)
+                .max()
+                .unwrap_or(0),
+            gpu_memory_percent_avg,
+            gpu_memory_percent_max,
+        })
+    }
+}
+
+/// Start of the `step`-sized bucket containing `at`, aligned to the Unix epoch
+#[must_use]
+pub fn bucket_start(at: DateTime<Utc>, step: Duration) -> DateTime<Utc> {
+    let step_ms = i64::try_from(step.as_millis()).unwrap_or(i64::MAX).max(1);
+    let ms = at.timestamp_millis();
+    DateTime::from_timestamp_millis(ms - ms.rem_euclid(step_ms)).unwrap_or(at)
+}
+
+/// Group time-ordered `samples` into `step`-sized buckets
+#[must_use]
+pub fn downsample(samples: &[MetricSample], step: Duration) -> Vec<MetricPoint> {
+    samples
+        .chunk_by(|a, b| bucket_start(a.at, step) == bucket_start(b.at, step))
+        .filter_map(|chunk| MetricPoint::aggregate(bucket_start(chunk[0].at, step), chunk))
+        .collect()
+}
+
+/// Re-aggregate time-ordered `points` (e.g. stored rollups) into `step`-sized
+/// buckets, weighting averages by sample count
+#[must_use]
+pub fn downsample_points(points: &[MetricPoint], step: Duration) -> Vec<MetricPoint> {
+    points
+        .chunk_by(|a, b| bucket_start(a.at, step) == bucket_start(b.at, step))
+        .map(|chunk| merge_points(bucket_start(chunk[0].at, step), chunk))
+        .collect()
+}
+
+fn weighted_avg(points: &[MetricPoint], f: impl Fn(&MetricPoint) -> Option<f64>) -> Option<f64> {
+    let (sum, weight) = points
+        .iter()
+        .filter_map(|p| f(p).map(|v| (v, f64::from(p.samples.max(1)))))
+        .fold((0.0, 0.0), |(sum, weight), (v, w)| {
+            (sum + v * w, weight + w)
+        });
+    (weight > 0.0).then(|| sum / weight)
+}
+
+fn max_of(points: &[MetricPoint], f: impl Fn(&MetricPoint) -> Option<f64>) -> Option<f64> {
+    points.iter().filter_map(f).reduce(f64::max)
+}
+
+fn merge_points(at: DateTime<Utc>, points: &[MetricPoint]) -> MetricPoint {
+    MetricPoint {
+        at,
+        samples: points.iter().map(|p| p.samples).sum(),
+        cpu_percent_avg: weighted_avg(points, |p| Some(p.cpu_percent_avg)).unwrap_or(0.0),
+        cpu_percent_max: max_of(points, |p| Some(p.cpu_percent_max)).unwrap_or(0.0),
+        memory_percent_avg: weighted_avg(points, |p| Some(p.memory_percent_avg)).unwrap_or(0.0),
+        memory_percent_max: max_of(points, |p| Some(p.memory_percent_max)).unwrap_or(0.0),
+        memory_used_bytes_max: points
+            .iter()
+            .map(|p| p.memory_used_bytes_max)
+            .max()
+            .unwrap_or(0),
+        gpu_memory_percent_avg: weighted_avg(points, |p| p.gpu_memory_percent_avg),
+        gpu_memory_percent_max: max_of(points, |p| p.gpu_memory_percent_max),
+    }
+}
+
+/// Bounded, time-ordered history of the local node's samples
+pub struct MetricsHistory {
+    capacity: usize,
+    samples: RwLock<VecDeque<MetricSample>>,
+}
+
+impl MetricsHistory {
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            samples: RwLock::new(VecDeque::with_capacity(capacity.clamp(1, 4096))),
+        }
+    }
+
+    /// Append a sample, dropping the oldest one when full
+    pub fn push(&self, sample: MetricSample) {
+        if let Ok(mut samples) = self.samples.write() {
+            if samples.len() == self.capacity {
+                samples.pop_front();
+            }
+            samples.push_back(sample);
+        } else {
+            warn!("RwLock is poisoned in MetricsHistory::push, dropping sample");
+        }
+    }
+
+    /// Samples taken in `[since, until]`
+    pub fn range(&self, since: DateTime<Utc>, until: DateTime<Utc>) -> Vec<MetricSample> {
+        if let Ok(samples) = self.samples.read() {
+            samples
+                .iter()
+                .filter(|s| s.at >= since && s.at <= until)
+                .cloned()
+                .collect()
+        } else {
+            warn!("RwLock is poisoned in MetricsHistory::range, cannot access samples");
+            Vec::new()
+        }
+    }
+
+    /// Time of the oldest retained sample
+    pub fn oldest(&self) -> Option<DateTime<Utc>> {
+        self.samples
+            .read()
+            .ok()
+            .and_then(|samples| samples.front().map(|s| s.at))
+    }
+}
+
+/// Raise an alert when `metric` stays above `above` for `for_secs`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ThresholdRule {
+    pub name: String,
+    pub metric: MetricKind,
+    pub above: f64,
+    /// How long the threshold must be exceeded; 0 fires on a single sample
+    #[serde(default)]
+    pub for_secs: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertState {
+    Firing,
+    Resolved,
+}
+
+/// Transition of a threshold rule
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricAlert {
+    pub rule: String,
+    pub metric: MetricKind,
+    pub node_id: Uuid,
+    pub state: AlertState,
+    /// Latest value of the metric
+    pub value: f64,
+    pub threshold: f64,
+    pub at: DateTime<Utc>,
+}
+
+/// Evaluates threshold rules against the history and keeps track of firing rules
+pub struct AlertEvaluator {
+    rules: Vec<ThresholdRule>,
+    firing: Mutex<HashSet<String>>,
+    recent: Mutex<VecDeque<MetricAlert>>,
+    events: broadcast::Sender<MetricAlert>,
+}
+
+impl AlertEvaluator {
+    #[must_use]
+    pub fn new(rules: Vec<ThresholdRule>) -> Self {
+        let (events, _) = broadcast::channel(RECENT_ALERTS);
+        Self {
+            rules,
+            firing: Mutex::new(HashSet::new()),
+            recent: Mutex::new(VecDeque::with_capacity(RECENT_ALERTS)),
+            events,
+        }
+    }
+
+    /// Whether `rule` is breached: every sample in its window exceeds the threshold
+    /// and the history covers the whole window. Returns the latest value.
+    fn breach(
+        rule: &ThresholdRule,
+        history: &MetricsHistory,
+        now: DateTime<Utc>,
+    ) -> Option<(bool, f64)> {
+        let window = chrono::Duration::seconds(i64::try_from(rule.for_secs).unwrap_or(i64::MAX));
+        let since = now - window;
+        let samples = history.range(since, now);
+        let values: Vec<f64> = samples
+            .iter()
+            .filter_map(|s| s.value(rule.metric))
+            .collect();
+        let latest = *values.last()?;
+        let covered = rule.for_secs == 0 || history.oldest().is_some_and(|oldest| oldest <= since);
+        let breached = if rule.for_secs == 0 {
+            latest > rule.above
+        } else {
+            covered && values.iter().all(|v| *v > rule.above)
+        };
+        Some((breached, latest))
+    }
+
+    /// Evaluate all rules after a new sample; returns the alerts that changed state
+    pub fn evaluate(
+        &self,
+        node_id: Uuid,
+        history: &MetricsHistory,
+        now: DateTime<Utc>,
+    ) -> Vec<MetricAlert> {
+        let Ok(mut firing) = self.firing.lock() else {
+            warn!("Mutex is poisoned in AlertEvaluator::evaluate, skipping rules");
+            return Vec::new();
+        };
+
+        let mut alerts = Vec::new();
+        for rule in &self.rules {
+            let Some((breached, value)) = Self::breach(rule, history, now) else {
+                continue;
+            };
+            let state = match (breached, firing.contains(&rule.name)) {
+                (true, false) => {
+                    firing.insert(rule.name.clone());
+                    AlertState::Firing
+                }
+                (false, true) => {
+                    firing.remove(&rule.name);
+                    AlertState::Resolved
+                }
+                _ => continue,
+            };
+            alerts.push(MetricAlert {
+                rule: rule.name.clone(),
+                metric: rule.metric,
+                node_id,
+                state,
+                value,
+                threshold: rule.above,
+                at: now,
+            });
+        }
+        drop(firing);
+
+        for alert in &alerts {
+            self.emit(alert.clone());
+        }
+        alerts
+    }
+
+    fn emit(&self, alert: MetricAlert) {
+        match alert.state {
+            AlertState::Firing => warn!(
+                rule = %alert.rule,
+                metric = alert.metric.as_str(),
+                value = alert.value,
+                threshold = alert.threshold,
+                node_id = %alert.node_id,
+                "Node metric threshold exceeded"
+            ),
+            AlertState::Resolved => tracing::info!(
+                rule = %alert.rule,
+                metric = alert.metric.as_str(),
+                value = alert.value,
+                node_id = %alert.node_id,
+                "Node metric back below threshold"
+            ),
+        }
+        if let Ok(mut recent) = self.recent.lock() {
+            if recent.len() == RECENT_ALERTS {
+                recent.pop_front();
+            }
+            recent.push_back(alert.clone());
+        }
+        // No subscribers is fine
+        let _ = self.events.send(alert);
+    }
+
+    /// Latest alerts, oldest first
+    pub fn recent(&self) -> Vec<MetricAlert> {
+        self.recent
+            .lock()
+            .map(|recent| recent.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Receive alerts as they change state
+    pub fn subscribe(&self) -> broadcast::Receiver<MetricAlert> {
+        self.events.subscribe()
+    }
+}
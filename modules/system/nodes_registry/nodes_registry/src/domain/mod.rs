@@ -1,4 +1,8 @@
 pub mod error;
 pub mod local_client;
+pub mod membership;
+pub mod metrics;
 pub mod node_storage;
+pub mod repo;
+pub mod sampler;
 pub mod service;
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use nodes_registry_sdk::{Node, NodeSysCap, NodeSysInfo, SysCap};
 use std::collections::HashMap;
 use std::sync::RwLock;
@@ -8,6 +9,8 @@ use uuid::Uuid;
 #[derive(Debug, Clone)]
 struct CachedNodeData {
     node: Node,
+    /// Last heartbeat or registration
+    last_seen_at: DateTime<Utc>,
     sysinfo: Option<NodeSysInfo>,
     /// System-collected capabilities from modkit-node-info
     syscap_system: Option<NodeSysCap>,
@@ -36,6 +39,7 @@ impl NodeStorage {
                     node.id,
                     CachedNodeData {
                         node,
+                        last_seen_at: Utc::now(),
                         sysinfo: None,
                         syscap_system: None,
                         syscap_custom: HashMap::new(),
@@ -48,6 +52,93 @@ impl NodeStorage {
         }
     }
 
+    /// Register or update a node seen at `last_seen_at`, keeping cached sysinfo/syscap.
+    ///
+    /// The earliest known `created_at` wins, so re-registrations and copies loaded from
+    /// a shared store do not reset the node's age. Returns the stored node.
+    pub fn merge_node(&self, mut node: Node, last_seen_at: DateTime<Utc>) -> Option<Node> {
+        let Ok(mut nodes) = self.nodes.write() else {
+            warn!("RwLock is poisoned in merge_node, cannot update node");
+            return None;
+        };
+        if let Some(data) = nodes.get_mut(&node.id) {
+            node.created_at = node.created_at.min(data.node.created_at);
+            data.node = node.clone();
+            data.last_seen_at = data.last_seen_at.max(last_seen_at);
+        } else {
+            nodes.insert(
+                node.id,
+                CachedNodeData {
+                    node: node.clone(),
+                    last_seen_at,
+                    sysinfo: None,
+                    syscap_system: None,
+                    syscap_custom: HashMap::new(),
+                },
+            );
+        }
+        Some(node)
+    }
+
+    /// Record a heartbeat; returns `false` if the node is unknown
+    pub fn touch(&self, node_id: Uuid, at: DateTime<Utc>) -> bool {
+        if let Ok(mut nodes) = self.nodes.write() {
+            if let Some(data) = nodes.get_mut(&node_id) {
+                data.last_seen_at = data.last_seen_at.max(at);
+                true
+            } else {
+                false
+            }
+        } else {
+            warn!("RwLock is poisoned in touch, cannot update node");
+            false
+        }
+    }
+
+    /// Remove a node and everything cached for it
+    pub fn remove_node(&self, node_id: Uuid) -> bool {
+        if let Ok(mut nodes) = self.nodes.write() {
+            nodes.remove(&node_id).is_some()
+        } else {
+            warn!("RwLock is poisoned in remove_node, cannot update node");
+            false
+        }
+    }
+
+    /// Keep only the nodes for which `keep` returns true
+    pub fn retain_nodes(&self, keep: impl Fn(&Uuid) -> bool) {
+        if let Ok(mut nodes) = self.nodes.write() {
+            nodes.retain(|id, _| keep(id));
+        } else {
+            warn!("RwLock is poisoned in retain_nodes, cannot update nodes");
+        }
+    }
+
+    /// Get a node with its last heartbeat time
+    pub fn get_member(&self, id: Uuid) -> Option<(Node, DateTime<Utc>)> {
+        if let Ok(nodes) = self.nodes.read() {
+            nodes
+                .get(&id)
+                .map(|data| (data.node.clone(), data.last_seen_at))
+        } else {
+            warn!("RwLock is poisoned in get_member, cannot access node");
+            None
+        }
+    }
+
+    /// List all nodes with their last heartbeat time
+    pub fn list_members(&self) -> Vec<(Node, DateTime<Utc>)> {
+        if let Ok(nodes) = self.nodes.read() {
+            nodes
+                .values()
+                .map(|data| (data.node.clone(), data.last_seen_at))
+                .collect()
+        } else {
+            warn!("RwLock is poisoned in list_members, cannot access nodes");
+            Vec::new()
+        }
+    }
+
     /// Get a node by ID
     pub fn get_node(&self, id: Uuid) -> Option<Node> {
         if let Ok(nodes) = self.nodes.read() {
@@ -122,9 +213,12 @@ impl NodeStorage {
                     }
                 }
 
-                // Override/add custom capabilities
+                // Override/add custom capabilities that have not expired yet
+                let now = chrono::Utc::now();
                 for (key, cap) in &data.syscap_custom {
-                    cap_map.insert(key.clone(), cap.clone());
+                    if !cap.custom_is_expired(now) {
+                        cap_map.insert(key.clone(), cap.clone());
+                    }
                 }
 
                 if cap_map.is_empty() {
@@ -149,6 +243,9 @@ impl NodeStorage {
     pub fn set_custom_syscap(&self, node_id: Uuid, caps: Vec<SysCap>) -> bool {
         if let Ok(mut nodes) = self.nodes.write() {
             if let Some(data) = nodes.get_mut(&node_id) {
+                let now = chrono::Utc::now();
+                data.syscap_custom
+                    .retain(|_, cap| !cap.custom_is_expired(now));
                 for cap in caps {
                     data.syscap_custom.insert(cap.key.clone(), cap);
                 }
@@ -242,6 +339,9 @@ impl NodeStorage {
 
 trait CacheableCapability {
     fn cache_is_expired(&self, now: chrono::DateTime<chrono::Utc>) -> bool;
+
+    /// Custom entries with a zero TTL never expire
+    fn custom_is_expired(&self, now: chrono::DateTime<chrono::Utc>) -> bool;
 }
 
 impl CacheableCapability for SysCap {
@@ -251,6 +351,10 @@ impl CacheableCapability for SysCap {
         let age_secs = (now_secs - self.fetched_at_secs).max(0) as u64;
         age_secs >= self.cache_ttl_secs
     }
+
+    fn custom_is_expired(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        self.cache_ttl_secs > 0 && self.cache_is_expired(now)
+    }
 }
 
 impl Default for NodeStorage {
@@ -326,4 +430,20 @@ mod tests {
 
         assert!(cap.cache_is_expired(now));
     }
+
+    #[test]
+    fn custom_zero_ttl_never_expires() {
+        let now = Utc::now();
+        let cap = make_syscap_with(now.timestamp() - 86_400, 0);
+
+        assert!(!cap.custom_is_expired(now));
+    }
+
+    #[test]
+    fn custom_expires_after_ttl() {
+        let now = Utc::now();
+        let cap = make_syscap_with(now.timestamp() - 30, 30);
+
+        assert!(cap.custom_is_expired(now));
+    }
 }
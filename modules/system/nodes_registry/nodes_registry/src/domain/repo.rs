@@ -0,0 +1,52 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use nodes_registry_sdk::Node;
+use uuid::Uuid;
+
+use super::error::DomainError;
+use super::metrics::MetricPoint;
+
+/// A node row as persisted by a [`MembershipStore`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredNode {
+    pub node: Node,
+    pub last_seen_at: DateTime<Utc>,
+}
+
+/// Shared persistence for fleet membership.
+///
+/// Replicas pointing at the same store see each other's nodes; without a store the
+/// registry only knows the nodes that registered with this process.
+#[async_trait]
+pub trait MembershipStore: Send + Sync {
+    /// Insert the node or update it, keeping the original `created_at`
+    async fn upsert(&self, node: &Node, last_seen_at: DateTime<Utc>) -> Result<(), DomainError>;
+
+    /// Update `last_seen_at`; returns `false` if the node is not stored
+    async fn touch(&self, node_id: Uuid, at: DateTime<Utc>) -> Result<bool, DomainError>;
+
+    /// Delete the node; returns `false` if it was not stored
+    async fn remove(&self, node_id: Uuid) -> Result<bool, DomainError>;
+
+    /// All stored nodes
+    async fn list(&self) -> Result<Vec<StoredNode>, DomainError>;
+}
+
+/// Persistence for metric rollups, so usage history survives restarts and is visible
+/// to every replica sharing the store
+#[async_trait]
+pub trait MetricsStore: Send + Sync {
+    /// Insert or replace the rollup of `node_id` for the bucket starting at `point.at`
+    async fn insert_rollup(&self, node_id: Uuid, point: &MetricPoint) -> Result<(), DomainError>;
+
+    /// Rollups of `node_id` with buckets starting in `[since, until]`, oldest first
+    async fn rollups(
+        &self,
+        node_id: Uuid,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<MetricPoint>, DomainError>;
+
+    /// Delete rollups older than `before`; returns the number of deleted rows
+    async fn prune_rollups(&self, before: DateTime<Utc>) -> Result<u64, DomainError>;
+}
@@ -0,0 +1,90 @@
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+use crate::domain::metrics::bucket_start;
+use crate::domain::service::Service;
+
+/// Background task sampling the local node's resource usage into the metrics
+/// history and, when a rollup interval is set, persisting per-interval rollups.
+pub struct MetricsSampler {
+    service: Arc<Service>,
+    sample_interval: Duration,
+    rollup_interval: Option<Duration>,
+    rollup_retention: Duration,
+}
+
+impl MetricsSampler {
+    #[must_use]
+    pub fn new(
+        service: Arc<Service>,
+        sample_interval: Duration,
+        rollup_interval: Option<Duration>,
+        rollup_retention: Duration,
+    ) -> Self {
+        Self {
+            service,
+            sample_interval,
+            rollup_interval,
+            rollup_retention,
+        }
+    }
+
+    /// Collecting usage takes a lock and may query GPU drivers or run tools
+    /// such as `lspci`, so it runs on the blocking pool.
+    async fn sample(&self) {
+        let service = Arc::clone(&self.service);
+        match tokio::task::spawn_blocking(move || service.sample_metrics()).await {
+            Ok(Ok(_)) => {}
+            Ok(Err(e)) => tracing::warn!(error = %e, "Sampling node metrics failed"),
+            Err(e) => tracing::warn!(error = %e, "Node metrics sampling task failed"),
+        }
+    }
+
+    /// Persist the rollup of the bucket that ended before `now`, if a new bucket
+    /// started since `current`. Returns the bucket `now` falls in.
+    async fn rollup(
+        &self,
+        step: Duration,
+        current: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> DateTime<Utc> {
+        let bucket = bucket_start(now, step);
+        if bucket <= current {
+            return current;
+        }
+        if let Err(e) = self.service.store_rollup(current, step).await {
+            tracing::warn!(error = %e, "Storing node metric rollup failed");
+        }
+        let retention = chrono::Duration::from_std(self.rollup_retention).unwrap_or_default();
+        if let Err(e) = self.service.prune_rollups(now - retention).await {
+            tracing::warn!(error = %e, "Pruning node metric rollups failed");
+        }
+        bucket
+    }
+}
+
+#[async_trait::async_trait]
+impl modkit::lifecycle::Runnable for MetricsSampler {
+    async fn run(self: Arc<Self>, cancel: CancellationToken) -> anyhow::Result<()> {
+        let mut ticker = tokio::time::interval(self.sample_interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let mut bucket = self
+            .rollup_interval
+            .map(|step| bucket_start(Utc::now(), step));
+
+        loop {
+            tokio::select! {
+                () = cancel.cancelled() => break,
+                _ = ticker.tick() => {}
+            }
+
+            self.sample().await;
+            if let (Some(step), Some(current)) = (self.rollup_interval, bucket) {
+                bucket = Some(self.rollup(step, current, Utc::now()).await);
+            }
+        }
+        Ok(())
+    }
+}
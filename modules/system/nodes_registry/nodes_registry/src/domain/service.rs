@@ -1,8 +1,26 @@
 use crate::domain::error::DomainError;
+use crate::domain::membership::MembershipPolicy;
+use crate::domain::metrics::{
+    AlertEvaluator, MetricAlert, MetricPoint, MetricSample, MetricsHistory, ThresholdRule,
+    downsample, downsample_points,
+};
 use crate::domain::node_storage::NodeStorage;
+use crate::domain::repo::{MembershipStore, MetricsStore};
+use chrono::{DateTime, Utc};
 use modkit_node_info::NodeInfoCollector;
-use nodes_registry_sdk::{Node, NodeSysCap, NodeSysInfo, SysCap};
+use nodes_registry_sdk::{
+    CapabilityQuery, Node, NodeMember, NodeRegistration, NodeSysCap, NodeSysInfo, SysCap,
+};
+use std::collections::HashSet;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+/// Samples kept when no metrics configuration is given (1 hour at 10s)
+const DEFAULT_METRICS_HISTORY: usize = 360;
+
+const MAX_HOSTNAME_LEN: usize = 255;
+const MAX_SYSCAP_KEY_LEN: usize = 255;
 
 /// Check if a UUID is a fallback UUID (hardware detection failed)
 /// Fallback UUIDs have zeros in the first 8 bytes: 00000000-0000-0000-xxxx-xxxxxxxxxxxx
@@ -16,10 +34,28 @@ fn is_fallback_uuid(id: &uuid::Uuid) -> bool {
 pub struct Service {
     storage: Arc<NodeStorage>,
     node_info_collector: Arc<NodeInfoCollector>,
+    local_node_id: uuid::Uuid,
+    policy: MembershipPolicy,
+    store: Option<Arc<dyn MembershipStore>>,
+    history: Arc<MetricsHistory>,
+    alerts: Arc<AlertEvaluator>,
+    metrics_store: Option<Arc<dyn MetricsStore>>,
 }
 
 impl Service {
+    /// In-memory registry with default heartbeat thresholds
+    #[must_use]
     pub fn new() -> Self {
+        Self::with_membership(MembershipPolicy::default(), None)
+    }
+
+    /// Registry deriving node status with `policy`, optionally sharing membership
+    /// with other replicas through `store`
+    #[must_use]
+    pub fn with_membership(
+        policy: MembershipPolicy,
+        store: Option<Arc<dyn MembershipStore>>,
+    ) -> Self {
         let node_info_collector = Arc::new(NodeInfoCollector::new());
         let current_node = NodeInfoCollector::create_current_node();
         let storage = Arc::new(NodeStorage::new());
@@ -41,14 +77,190 @@ impl Service {
             );
         }
 
+        let local_node_id = current_node.id;
         storage.upsert_node(current_node);
 
         Self {
             storage,
             node_info_collector,
+            local_node_id,
+            policy,
+            store,
+            history: Arc::new(MetricsHistory::new(DEFAULT_METRICS_HISTORY)),
+            alerts: Arc::new(AlertEvaluator::new(Vec::new())),
+            metrics_store: None,
         }
     }
 
+    /// Keep `capacity` metric samples of the local node, evaluate `rules` on each new
+    /// sample and persist rollups to `store`, if given
+    #[must_use]
+    pub fn with_metrics(
+        mut self,
+        capacity: usize,
+        rules: Vec<ThresholdRule>,
+        store: Option<Arc<dyn MetricsStore>>,
+    ) -> Self {
+        self.history = Arc::new(MetricsHistory::new(capacity));
+        self.alerts = Arc::new(AlertEvaluator::new(rules));
+        self.metrics_store = store;
+        self
+    }
+
+    /// ID of the node this process runs on
+    #[must_use]
+    pub fn local_node_id(&self) -> uuid::Uuid {
+        self.local_node_id
+    }
+
+    fn member(&self, node: Node, last_seen_at: chrono::DateTime<Utc>) -> NodeMember {
+        let now = Utc::now();
+        // This process is serving the call, so the local node is alive by definition
+        let last_seen_at = if node.id == self.local_node_id {
+            now
+        } else {
+            last_seen_at
+        };
+        self.policy.member(node, last_seen_at, now)
+    }
+
+    /// Get a node with its membership status
+    pub fn get_member(&self, id: uuid::Uuid) -> Result<NodeMember, DomainError> {
+        self.storage
+            .get_member(id)
+            .map(|(node, last_seen_at)| self.member(node, last_seen_at))
+            .ok_or(DomainError::NodeNotFound(id))
+    }
+
+    /// List the fleet with membership status, ordered by hostname
+    #[must_use]
+    pub fn list_members(&self) -> Vec<NodeMember> {
+        let mut members: Vec<NodeMember> = self
+            .storage
+            .list_members()
+            .into_iter()
+            .map(|(node, last_seen_at)| self.member(node, last_seen_at))
+            .collect();
+        members.sort_by(|a, b| {
+            a.node
+                .hostname
+                .cmp(&b.node.hostname)
+                .then(a.node.id.cmp(&b.node.id))
+        });
+        members
+    }
+
+    /// Register (or re-register) a remote node
+    pub async fn register_node(
+        &self,
+        registration: NodeRegistration,
+    ) -> Result<NodeMember, DomainError> {
+        validate_registration(&registration)?;
+        if registration.id == self.local_node_id {
+            return Err(DomainError::InvalidInput(format!(
+                "node id {} belongs to the registry's own node",
+                registration.id
+            )));
+        }
+
+        let now = Utc::now();
+        let node = Node {
+            id: registration.id,
+            hostname: registration.hostname,
+            ip_address: registration.ip_address,
+            created_at: now,
+            updated_at: now,
+        };
+        let node = self
+            .storage
+            .merge_node(node, now)
+            .ok_or_else(|| DomainError::Internal("node storage is unavailable".to_owned()))?;
+        if let Some(store) = &self.store {
+            store.upsert(&node, now).await?;
+        }
+
+        tracing::info!(node_id = %node.id, hostname = %node.hostname, "Node registered");
+        Ok(self.member(node, now))
+    }
+
+    /// Record a heartbeat from a registered node.
+    ///
+    /// Returns `NodeNotFound` if the node is unknown (or was deregistered through
+    /// another replica); the node is expected to register again.
+    pub async fn heartbeat(&self, node_id: uuid::Uuid) -> Result<NodeMember, DomainError> {
+        let now = Utc::now();
+        let known = self.storage.touch(node_id, now);
+
+        if let Some(store) = &self.store {
+            if store.touch(node_id, now).await? {
+                if !known {
+                    // Registered through another replica
+                    self.sync_from_store().await?;
+                }
+            } else if node_id == self.local_node_id {
+                self.local_heartbeat().await?;
+            } else {
+                self.storage.remove_node(node_id);
+                return Err(DomainError::NodeNotFound(node_id));
+            }
+        } else if !known {
+            return Err(DomainError::NodeNotFound(node_id));
+        }
+
+        self.get_member(node_id)
+    }
+
+    /// Remove a remote node from the fleet
+    pub async fn deregister_node(&self, node_id: uuid::Uuid) -> Result<(), DomainError> {
+        if node_id == self.local_node_id {
+            return Err(DomainError::InvalidInput(
+                "the registry's own node cannot be deregistered".to_owned(),
+            ));
+        }
+
+        let mut removed = self.storage.remove_node(node_id);
+        if let Some(store) = &self.store {
+            removed |= store.remove(node_id).await?;
+        }
+        if !removed {
+            return Err(DomainError::NodeNotFound(node_id));
+        }
+
+        tracing::info!(node_id = %node_id, "Node deregistered");
+        Ok(())
+    }
+
+    /// Heartbeat of the local node; also (re)creates its row in the shared store
+    pub async fn local_heartbeat(&self) -> Result<(), DomainError> {
+        let now = Utc::now();
+        self.storage.touch(self.local_node_id, now);
+        if let Some(store) = &self.store {
+            let node = self.get_node(self.local_node_id)?;
+            store.upsert(&node, now).await?;
+        }
+        Ok(())
+    }
+
+    /// Reload the fleet from the shared store, picking up nodes registered with (or
+    /// removed through) other replicas. No-op without a store.
+    pub async fn sync_from_store(&self) -> Result<(), DomainError> {
+        let Some(store) = &self.store else {
+            return Ok(());
+        };
+
+        let stored = store.list().await?;
+        let mut ids: HashSet<uuid::Uuid> = HashSet::with_capacity(stored.len() + 1);
+        ids.insert(self.local_node_id);
+        for row in stored {
+            ids.insert(row.node.id);
+            if row.node.id != self.local_node_id {
+                self.storage.merge_node(row.node, row.last_seen_at);
+            }
+        }
+        self.storage.retain_nodes(|id| ids.contains(id));
+        Ok(())
+    }
+
     /// Get a node by ID
     pub fn get_node(&self, id: uuid::Uuid) -> Result<Node, DomainError> {
         self.storage
@@ -74,6 +286,11 @@ impl Service {
             return Ok(cached);
         }
 
+        // The collector only sees the host this process runs on
+        if node_id != self.local_node_id {
+            return Err(DomainError::NodeInfoUnavailable(node_id));
+        }
+
         // Collect fresh sysinfo
         let sysinfo = self
             .node_info_collector
@@ -97,6 +314,14 @@ impl Service {
             return Err(DomainError::NodeNotFound(node_id));
         }
 
+        // Remote nodes only have the custom capabilities set for them
+        if node_id != self.local_node_id {
+            return self
+                .storage
+                .get_syscap(node_id)
+                .ok_or(DomainError::NodeInfoUnavailable(node_id));
+        }
+
         // Check if we need to refresh system capabilities
         let expired_keys = self.storage.get_expired_syscap_keys(node_id);
         let needs_refresh =
@@ -127,6 +352,7 @@ impl Service {
         node_id: uuid::Uuid,
         caps: Vec<SysCap>,
     ) -> Result<(), DomainError> {
+        validate_custom_syscap(&caps)?;
         if !self.storage.set_custom_syscap(node_id, caps) {
             return Err(DomainError::NodeNotFound(node_id));
         }
@@ -152,6 +378,159 @@ impl Service {
         }
         Ok(())
     }
+
+    /// Sample the local node's resource usage into the metrics history
+    pub fn sample_metrics(&self) -> Result<MetricSample, DomainError> {
+        let sample = MetricSample::from(self.node_info_collector.collect_usage()?);
+        // Alerts are logged and broadcast by the evaluator
+        let _alerts = self.record_sample(sample.clone());
+        Ok(sample)
+    }
+
+    /// Add a sample of the local node and evaluate threshold rules; returns the
+    /// alerts that changed state
+    #[must_use]
+    pub fn record_sample(&self, sample: MetricSample) -> Vec<MetricAlert> {
+        let at = sample.at;
+        self.history.push(sample);
+        self.alerts.evaluate(self.local_node_id, &self.history, at)
+    }
+
+    /// Usage of a node in `[since, until]` from the in-memory history, downsampled
+    /// to `step`. Only the local node is sampled.
+    pub fn metric_series(
+        &self,
+        node_id: uuid::Uuid,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+        step: Duration,
+    ) -> Result<Vec<MetricPoint>, DomainError> {
+        if self.storage.get_node(node_id).is_none() {
+            return Err(DomainError::NodeNotFound(node_id));
+        }
+        if node_id != self.local_node_id {
+            return Err(DomainError::NodeInfoUnavailable(node_id));
+        }
+        Ok(downsample(&self.history.range(since, until), step))
+    }
+
+    /// Stored rollups of a node in `[since, until]`, re-aggregated to `step`.
+    ///
+    /// Rollups are written by every replica sharing the store, so this also covers
+    /// other nodes running the registry.
+    pub async fn metric_rollups(
+        &self,
+        node_id: uuid::Uuid,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+        step: Duration,
+    ) -> Result<Vec<MetricPoint>, DomainError> {
+        if self.storage.get_node(node_id).is_none() {
+            return Err(DomainError::NodeNotFound(node_id));
+        }
+        let Some(store) = &self.metrics_store else {
+            return Err(DomainError::InvalidInput(
+                "metric rollups require a database".to_owned(),
+            ));
+        };
+        let points = store.rollups(node_id, since, until).await?;
+        Ok(downsample_points(&points, step))
+    }
+
+    /// Aggregate the local samples of the bucket `[start, start + step)` and persist
+    /// them. Returns `false` if there is no store or no samples.
+    pub async fn store_rollup(
+        &self,
+        start: DateTime<Utc>,
+        step: Duration,
+    ) -> Result<bool, DomainError> {
+        let Some(store) = &self.metrics_store else {
+            return Ok(false);
+        };
+        let end = start + chrono::Duration::from_std(step).unwrap_or_default()
+            - chrono::Duration::milliseconds(1);
+        let Some(point) = MetricPoint::aggregate(start, &self.history.range(start, end)) else {
+            return Ok(false);
+        };
+        store.insert_rollup(self.local_node_id, &point).await?;
+        Ok(true)
+    }
+
+    /// Delete stored rollups older than `before`
+    pub async fn prune_rollups(&self, before: DateTime<Utc>) -> Result<u64, DomainError> {
+        match &self.metrics_store {
+            Some(store) => store.prune_rollups(before).await,
+            None => Ok(0),
+        }
+    }
+
+    /// Latest threshold alerts, oldest first
+    #[must_use]
+    pub fn recent_alerts(&self) -> Vec<MetricAlert> {
+        self.alerts.recent()
+    }
+
+    /// Receive threshold alerts as they fire and resolve
+    #[must_use]
+    pub fn subscribe_alerts(&self) -> broadcast::Receiver<MetricAlert> {
+        self.alerts.subscribe()
+    }
+
+    /// Find the nodes with an eligible status whose merged capabilities satisfy
+    /// `query`, together with those capabilities. Nodes without known capabilities
+    /// are matched against an empty set.
+    #[must_use]
+    pub fn find_nodes(&self, query: &CapabilityQuery) -> Vec<(NodeMember, Option<NodeSysCap>)> {
+        self.list_members()
+            .into_iter()
+            .filter(|member| query.accepts_status(member.status))
+            .filter_map(|member| {
+                let syscap = match self.get_node_syscap(member.node.id, false) {
+                    Ok(syscap) => Some(syscap),
+                    Err(DomainError::NodeInfoUnavailable(_)) => None,
+                    Err(e) => {
+                        tracing::warn!(node_id = %member.node.id, error = %e, "Skipping node in capability query");
+                        return None;
+                    }
+                };
+                let caps = syscap.as_ref().map_or(&[][..], |s| &s.capabilities[..]);
+                query.is_satisfied_by(caps).then_some((member, syscap))
+            })
+            .collect()
+    }
+}
+
+fn validate_custom_syscap(caps: &[SysCap]) -> Result<(), DomainError> {
+    for cap in caps {
+        if cap.key.trim().is_empty() || cap.key.len() > MAX_SYSCAP_KEY_LEN {
+            return Err(DomainError::InvalidInput(format!(
+                "capability key must be 1-{MAX_SYSCAP_KEY_LEN} characters"
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn validate_registration(registration: &NodeRegistration) -> Result<(), DomainError> {
+    if registration.id.is_nil() {
+        return Err(DomainError::InvalidInput(
+            "node id must not be nil".to_owned(),
+        ));
+    }
+    let hostname = registration.hostname.trim();
+    if hostname.is_empty() || hostname.len() > MAX_HOSTNAME_LEN {
+        return Err(DomainError::InvalidInput(format!(
+            "hostname must be 1-{MAX_HOSTNAME_LEN} characters"
+        )));
+    }
+    if let Some(ip) = &registration.ip_address
+        && ip.parse::<std::net::IpAddr>().is_err()
+    {
+        return Err(DomainError::InvalidInput(format!(
+            "invalid ip_address '{ip}'"
+        )));
+    }
+    Ok(())
 }
 
 impl Default for Service {
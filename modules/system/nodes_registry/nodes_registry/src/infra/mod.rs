@@ -0,0 +1,2 @@
+pub mod remote_registry;
+pub mod storage;
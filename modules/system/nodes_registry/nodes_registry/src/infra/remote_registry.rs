@@ -0,0 +1,118 @@
+//! Membership in a nodes registry running in another process.
+
+use std::time::Duration;
+
+use modkit_security::SecurityContext;
+use modkit_transport_grpc::client::GrpcClientConfig;
+use nodes_registry_sdk::grpc::NodesRegistryGrpcClient;
+use nodes_registry_sdk::{Node, NodeRegistration, NodesRegistryError};
+
+/// Keeps the local node registered with a remote registry over gRPC.
+///
+/// Connects lazily and re-registers whenever the remote side no longer knows the
+/// node (e.g. after a restart without persistence). Failures are logged and retried
+/// on the next heartbeat. Calls are made with the node's own identity as the
+/// caller, since the registry rejects membership changes from anonymous callers.
+pub struct RemoteRegistry {
+    endpoint: String,
+    cfg: GrpcClientConfig,
+    client: Option<NodesRegistryGrpcClient>,
+    registered: bool,
+}
+
+impl RemoteRegistry {
+    #[must_use]
+    pub fn new(endpoint: impl Into<String>, heartbeat_interval: Duration) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            cfg: GrpcClientConfig::new("nodes_registry")
+                .with_connect_timeout(heartbeat_interval)
+                .with_rpc_timeout(heartbeat_interval)
+                .with_max_retries(1),
+            client: None,
+            registered: false,
+        }
+    }
+
+    async fn client(&mut self) -> Option<NodesRegistryGrpcClient> {
+        if self.client.is_none() {
+            match NodesRegistryGrpcClient::connect_with_retry(self.endpoint.clone(), &self.cfg)
+                .await
+            {
+                Ok(client) => self.client = Some(client),
+                Err(e) => {
+                    tracing::warn!(endpoint = %self.endpoint, error = %e, "Cannot connect to remote nodes registry");
+                }
+            }
+        }
+        self.client.clone()
+    }
+
+    /// Register `node` if needed, otherwise send a heartbeat for it
+    pub async fn beat(&mut self, node: &Node) {
+        let Some(client) = self.client().await else {
+            return;
+        };
+        if self.registered {
+            self.registered = self.heartbeat(&client, node.id).await;
+            if self.registered {
+                return;
+            }
+        }
+        self.registered = self.register(&client, node).await;
+    }
+
+    /// Returns `false` if the remote registry no longer knows the node
+    async fn heartbeat(&self, client: &NodesRegistryGrpcClient, node_id: uuid::Uuid) -> bool {
+        match client.heartbeat(&node_ctx(node_id), node_id).await {
+            Ok(_) => true,
+            Err(NodesRegistryError::NodeNotFound(_)) => {
+                tracing::info!(endpoint = %self.endpoint, "Remote nodes registry forgot this node, registering again");
+                false
+            }
+            Err(e) => {
+                // Transient failure: keep the registration and retry next time
+                tracing::warn!(endpoint = %self.endpoint, error = %e, "Heartbeat to remote nodes registry failed");
+                true
+            }
+        }
+    }
+
+    async fn register(&self, client: &NodesRegistryGrpcClient, node: &Node) -> bool {
+        match client
+            .register_node(&node_ctx(node.id), NodeRegistration::from(node))
+            .await
+        {
+            Ok(_) => {
+                tracing::info!(endpoint = %self.endpoint, node_id = %node.id, "Joined remote nodes registry");
+                true
+            }
+            Err(e) => {
+                tracing::warn!(endpoint = %self.endpoint, error = %e, "Registration with remote nodes registry failed");
+                false
+            }
+        }
+    }
+
+    /// Deregister the node on shutdown (best effort)
+    pub async fn leave(&mut self, node_id: uuid::Uuid) {
+        if !self.registered {
+            return;
+        }
+        self.registered = false;
+        let Some(client) = &self.client else {
+            return;
+        };
+        if let Err(e) = client.deregister_node(&node_ctx(node_id), node_id).await {
+            tracing::warn!(endpoint = %self.endpoint, error = %e, "Leaving remote nodes registry failed");
+        }
+    }
+}
+
+/// Security context the local node presents to the remote registry
+fn node_ctx(node_id: uuid::Uuid) -> SecurityContext {
+    SecurityContext::builder()
+        .subject_id(node_id)
+        .subject_type("node")
+        .build()
+}
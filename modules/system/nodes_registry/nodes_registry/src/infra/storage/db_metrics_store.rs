@@ -0,0 +1,122 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use modkit_db::secure::{SecureDeleteExt, SecureEntityExt, SecureInsertExt, SecureOnConflict};
+use modkit_db::{DBProvider, DbError};
+use modkit_security::AccessScope;
+use sea_orm::{ActiveValue, ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+use uuid::Uuid;
+
+use super::rollup_entity::{self, Entity as RollupEntity};
+use crate::domain::error::DomainError;
+use crate::domain::metrics::MetricPoint;
+use crate::domain::repo::MetricsStore;
+
+/// [`MetricsStore`] backed by the module database, partitioned by cluster id
+pub struct DbMetricsStore {
+    db: DBProvider<DbError>,
+    cluster_id: Uuid,
+    scope: AccessScope,
+}
+
+impl DbMetricsStore {
+    #[must_use]
+    pub fn new(db: DBProvider<DbError>, cluster_id: Uuid) -> Self {
+        Self {
+            db,
+            cluster_id,
+            scope: AccessScope::tenant(cluster_id),
+        }
+    }
+}
+
+impl From<rollup_entity::Model> for MetricPoint {
+    fn from(m: rollup_entity::Model) -> Self {
+        Self {
+            at: m.bucket_start,
+            samples: u32::try_from(m.samples).unwrap_or(0),
+            cpu_percent_avg: m.cpu_percent_avg,
+            cpu_percent_max: m.cpu_percent_max,
+            memory_percent_avg: m.memory_percent_avg,
+            memory_percent_max: m.memory_percent_max,
+            memory_used_bytes_max: u64::try_from(m.memory_used_bytes_max).unwrap_or(0),
+            gpu_memory_percent_avg: m.gpu_memory_percent_avg,
+            gpu_memory_percent_max: m.gpu_memory_percent_max,
+        }
+    }
+}
+
+#[async_trait]
+impl MetricsStore for DbMetricsStore {
+    async fn insert_rollup(&self, node_id: Uuid, point: &MetricPoint) -> Result<(), DomainError> {
+        let conn = self.db.conn()?;
+        let am = rollup_entity::ActiveModel {
+            cluster_id: ActiveValue::Set(self.cluster_id),
+            node_id: ActiveValue::Set(node_id),
+            bucket_start: ActiveValue::Set(point.at),
+            samples: ActiveValue::Set(i32::try_from(point.samples).unwrap_or(i32::MAX)),
+            cpu_percent_avg: ActiveValue::Set(point.cpu_percent_avg),
+            cpu_percent_max: ActiveValue::Set(point.cpu_percent_max),
+            memory_percent_avg: ActiveValue::Set(point.memory_percent_avg),
+            memory_percent_max: ActiveValue::Set(point.memory_percent_max),
+            memory_used_bytes_max: ActiveValue::Set(
+                i64::try_from(point.memory_used_bytes_max).unwrap_or(i64::MAX),
+            ),
+            gpu_memory_percent_avg: ActiveValue::Set(point.gpu_memory_percent_avg),
+            gpu_memory_percent_max: ActiveValue::Set(point.gpu_memory_percent_max),
+        };
+
+        let on_conflict = SecureOnConflict::<RollupEntity>::columns([
+            rollup_entity::Column::ClusterId,
+            rollup_entity::Column::NodeId,
+            rollup_entity::Column::BucketStart,
+        ])
+        .update_columns([
+            rollup_entity::Column::Samples,
+            rollup_entity::Column::CpuPercentAvg,
+            rollup_entity::Column::CpuPercentMax,
+            rollup_entity::Column::MemoryPercentAvg,
+            rollup_entity::Column::MemoryPercentMax,
+            rollup_entity::Column::MemoryUsedBytesMax,
+            rollup_entity::Column::GpuMemoryPercentAvg,
+            rollup_entity::Column::GpuMemoryPercentMax,
+        ])?;
+
+        RollupEntity::insert(am.clone())
+            .secure()
+            .scope_with_model(&self.scope, &am)?
+            .on_conflict(on_conflict)
+            .exec(&conn)
+            .await?;
+        Ok(())
+    }
+
+    async fn rollups(
+        &self,
+        node_id: Uuid,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<MetricPoint>, DomainError> {
+        let conn = self.db.conn()?;
+        let rows = RollupEntity::find()
+            .filter(rollup_entity::Column::NodeId.eq(node_id))
+            .filter(rollup_entity::Column::BucketStart.gte(since))
+            .filter(rollup_entity::Column::BucketStart.lte(until))
+            .order_by_asc(rollup_entity::Column::BucketStart)
+            .secure()
+            .scope_with(&self.scope)
+            .all(&conn)
+            .await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn prune_rollups(&self, before: DateTime<Utc>) -> Result<u64, DomainError> {
+        let conn = self.db.conn()?;
+        let result = RollupEntity::delete_many()
+            .filter(rollup_entity::Column::BucketStart.lt(before))
+            .secure()
+            .scope_with(&self.scope)
+            .exec(&conn)
+            .await?;
+        Ok(result.rows_affected)
+    }
+}
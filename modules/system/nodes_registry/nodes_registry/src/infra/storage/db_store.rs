@@ -0,0 +1,117 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use modkit_db::secure::{
+    SecureDeleteExt, SecureEntityExt, SecureInsertExt, SecureOnConflict, SecureUpdateExt,
+};
+use modkit_db::{DBProvider, DbError};
+use modkit_security::AccessScope;
+use nodes_registry_sdk::Node;
+use sea_orm::sea_query::Expr;
+use sea_orm::{ActiveValue, ColumnTrait, EntityTrait, QueryFilter};
+use uuid::Uuid;
+
+use super::entity::{self, Entity as NodeEntity};
+use crate::domain::error::DomainError;
+use crate::domain::repo::{MembershipStore, StoredNode};
+
+/// [`MembershipStore`] backed by the module database, partitioned by cluster id
+pub struct DbMembershipStore {
+    db: DBProvider<DbError>,
+    cluster_id: Uuid,
+    scope: AccessScope,
+}
+
+impl DbMembershipStore {
+    #[must_use]
+    pub fn new(db: DBProvider<DbError>, cluster_id: Uuid) -> Self {
+        Self {
+            db,
+            cluster_id,
+            scope: AccessScope::tenant(cluster_id),
+        }
+    }
+}
+
+impl From<entity::Model> for StoredNode {
+    fn from(m: entity::Model) -> Self {
+        Self {
+            node: Node {
+                id: m.id,
+                hostname: m.hostname,
+                ip_address: m.ip_address,
+                created_at: m.created_at,
+                updated_at: m.updated_at,
+            },
+            last_seen_at: m.last_seen_at,
+        }
+    }
+}
+
+#[async_trait]
+impl MembershipStore for DbMembershipStore {
+    async fn upsert(&self, node: &Node, last_seen_at: DateTime<Utc>) -> Result<(), DomainError> {
+        let conn = self.db.conn()?;
+        let am = entity::ActiveModel {
+            cluster_id: ActiveValue::Set(self.cluster_id),
+            id: ActiveValue::Set(node.id),
+            hostname: ActiveValue::Set(node.hostname.clone()),
+            ip_address: ActiveValue::Set(node.ip_address.clone()),
+            created_at: ActiveValue::Set(node.created_at),
+            updated_at: ActiveValue::Set(node.updated_at),
+            last_seen_at: ActiveValue::Set(last_seen_at),
+        };
+
+        // created_at is kept from the first registration
+        let on_conflict = SecureOnConflict::<NodeEntity>::columns([
+            entity::Column::ClusterId,
+            entity::Column::Id,
+        ])
+        .update_columns([
+            entity::Column::Hostname,
+            entity::Column::IpAddress,
+            entity::Column::UpdatedAt,
+            entity::Column::LastSeenAt,
+        ])?;
+
+        NodeEntity::insert(am.clone())
+            .secure()
+            .scope_with_model(&self.scope, &am)?
+            .on_conflict(on_conflict)
+            .exec(&conn)
+            .await?;
+        Ok(())
+    }
+
+    async fn touch(&self, node_id: Uuid, at: DateTime<Utc>) -> Result<bool, DomainError> {
+        let conn = self.db.conn()?;
+        let result = NodeEntity::update_many()
+            .col_expr(entity::Column::LastSeenAt, Expr::value(at))
+            .filter(entity::Column::Id.eq(node_id))
+            .secure()
+            .scope_with(&self.scope)
+            .exec(&conn)
+            .await?;
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn remove(&self, node_id: Uuid) -> Result<bool, DomainError> {
+        let conn = self.db.conn()?;
+        let result = NodeEntity::delete_many()
+            .filter(entity::Column::Id.eq(node_id))
+            .secure()
+            .scope_with(&self.scope)
+            .exec(&conn)
+            .await?;
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn list(&self) -> Result<Vec<StoredNode>, DomainError> {
+        let conn = self.db.conn()?;
+        let rows = NodeEntity::find()
+            .secure()
+            .scope_with(&self.scope)
+            .all(&conn)
+            .await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+}
@@ -0,0 +1,68 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let backend = manager.get_database_backend();
+        let conn = manager.get_connection();
+
+        let sql = match backend {
+            sea_orm::DatabaseBackend::Postgres => {
+                r"
+CREATE TABLE IF NOT EXISTS nodes_registry_nodes (
+    cluster_id UUID NOT NULL,
+    id UUID NOT NULL,
+    hostname VARCHAR(255) NOT NULL,
+    ip_address VARCHAR(64),
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL,
+    last_seen_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (cluster_id, id)
+);
+                "
+            }
+            sea_orm::DatabaseBackend::MySql => {
+                r"
+CREATE TABLE IF NOT EXISTS nodes_registry_nodes (
+    cluster_id VARCHAR(36) NOT NULL,
+    id VARCHAR(36) NOT NULL,
+    hostname VARCHAR(255) NOT NULL,
+    ip_address VARCHAR(64),
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL,
+    last_seen_at TIMESTAMP NOT NULL,
+    PRIMARY KEY (cluster_id, id)
+);
+                "
+            }
+            sea_orm::DatabaseBackend::Sqlite => {
+                r"
+CREATE TABLE IF NOT EXISTS nodes_registry_nodes (
+    cluster_id TEXT NOT NULL,
+    id TEXT NOT NULL,
+    hostname TEXT NOT NULL,
+    ip_address TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    last_seen_at TEXT NOT NULL,
+    PRIMARY KEY (cluster_id, id)
+);
+                "
+            }
+        };
+
+        conn.execute_unprepared(sql).await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+        let sql = "DROP TABLE IF EXISTS nodes_registry_nodes;";
+        conn.execute_unprepared(sql).await?;
+        Ok(())
+    }
+}
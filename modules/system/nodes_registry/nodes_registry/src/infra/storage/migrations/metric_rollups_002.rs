@@ -0,0 +1,80 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let backend = manager.get_database_backend();
+        let conn = manager.get_connection();
+
+        let sql = match backend {
+            sea_orm::DatabaseBackend::Postgres => {
+                r"
+CREATE TABLE IF NOT EXISTS nodes_registry_metric_rollups (
+    cluster_id UUID NOT NULL,
+    node_id UUID NOT NULL,
+    bucket_start TIMESTAMPTZ NOT NULL,
+    samples INTEGER NOT NULL,
+    cpu_percent_avg DOUBLE PRECISION NOT NULL,
+    cpu_percent_max DOUBLE PRECISION NOT NULL,
+    memory_percent_avg DOUBLE PRECISION NOT NULL,
+    memory_percent_max DOUBLE PRECISION NOT NULL,
+    memory_used_bytes_max BIGINT NOT NULL,
+    gpu_memory_percent_avg DOUBLE PRECISION,
+    gpu_memory_percent_max DOUBLE PRECISION,
+    PRIMARY KEY (cluster_id, node_id, bucket_start)
+);
+                "
+            }
+            sea_orm::DatabaseBackend::MySql => {
+                r"
+CREATE TABLE IF NOT EXISTS nodes_registry_metric_rollups (
+    cluster_id VARCHAR(36) NOT NULL,
+    node_id VARCHAR(36) NOT NULL,
+    bucket_start TIMESTAMP NOT NULL,
+    samples INT NOT NULL,
+    cpu_percent_avg DOUBLE NOT NULL,
+    cpu_percent_max DOUBLE NOT NULL,
+    memory_percent_avg DOUBLE NOT NULL,
+    memory_percent_max DOUBLE NOT NULL,
+    memory_used_bytes_max BIGINT NOT NULL,
+    gpu_memory_percent_avg DOUBLE,
+    gpu_memory_percent_max DOUBLE,
+    PRIMARY KEY (cluster_id, node_id, bucket_start)
+);
+                "
+            }
+            sea_orm::DatabaseBackend::Sqlite => {
+                r"
+CREATE TABLE IF NOT EXISTS nodes_registry_metric_rollups (
+    cluster_id TEXT NOT NULL,
+    node_id TEXT NOT NULL,
+    bucket_start TEXT NOT NULL,
+    samples INTEGER NOT NULL,
+    cpu_percent_avg REAL NOT NULL,
+    cpu_percent_max REAL NOT NULL,
+    memory_percent_avg REAL NOT NULL,
+    memory_percent_max REAL NOT NULL,
+    memory_used_bytes_max INTEGER NOT NULL,
+    gpu_memory_percent_avg REAL,
+    gpu_memory_percent_max REAL,
+    PRIMARY KEY (cluster_id, node_id, bucket_start)
+);
+                "
+            }
+        };
+
+        conn.execute_unprepared(sql).await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+        let sql = "DROP TABLE IF EXISTS nodes_registry_metric_rollups;";
+        conn.execute_unprepared(sql).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,5 @@
+pub mod db_metrics_store;
+pub mod db_store;
+pub mod entity;
+pub mod migrations;
+pub mod rollup_entity;
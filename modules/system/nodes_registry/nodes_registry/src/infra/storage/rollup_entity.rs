@@ -0,0 +1,30 @@
+use modkit_db_macros::Scopable;
+use sea_orm::entity::prelude::*;
+use uuid::Uuid;
+
+/// Aggregated usage metrics of a node for one rollup bucket, scoped by `cluster_id`
+/// like the membership table
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Scopable)]
+#[sea_orm(table_name = "nodes_registry_metric_rollups")]
+#[secure(tenant_col = "cluster_id", resource_col = "node_id", no_owner, no_type)]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub cluster_id: Uuid,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub node_id: Uuid,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub bucket_start: DateTimeUtc,
+    pub samples: i32,
+    pub cpu_percent_avg: f64,
+    pub cpu_percent_max: f64,
+    pub memory_percent_avg: f64,
+    pub memory_percent_max: f64,
+    pub memory_used_bytes_max: i64,
+    pub gpu_memory_percent_avg: Option<f64>,
+    pub gpu_memory_percent_max: Option<f64>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -9,16 +9,22 @@
 //! - Node metadata (ID, hostname, IP, etc.)
 //!
 //! The module provides REST API endpoints to:
-//! - List all nodes
+//! - List all nodes of the fleet with their status and last heartbeat
 //! - Get node information by ID
+//! - Register nodes, receive heartbeats and deregister nodes (also over gRPC)
 //! - Access node sysinfo via /nodes/{id}/sysinfo
 //! - Access node syscap via /nodes/{id}/syscap
+//!
+//! Node status (`alive`, `suspect`, `dead`) is derived from the age of the last
+//! heartbeat. With a database configured, replicas share the membership table and
+//! therefore report the same fleet.
 #![cfg_attr(coverage_nightly, feature(coverage_attribute))]
 
 // === PUBLIC CONTRACT ===
 pub use nodes_registry_sdk::{
-    BatteryInfo, CpuInfo, GpuInfo, HostInfo, MemoryInfo, Node, NodeSysCap, NodeSysInfo,
-    NodesRegistryClient, NodesRegistryError, OsInfo, SysCap,
+    BatteryInfo, CapabilityQuery, CapabilityRequirement, CpuInfo, GpuInfo, HostInfo, MemoryInfo,
+    Node, NodeMember, NodeRegistration, NodeStatus, NodeSysCap, NodeSysInfo, NodesRegistryClient,
+    NodesRegistryError, OsInfo, SysCap,
 };
 
 // === MODULE DEFINITION ===
@@ -32,3 +38,5 @@ pub mod api;
 pub mod config;
 #[doc(hidden)]
 pub mod domain;
+#[doc(hidden)]
+pub mod infra;
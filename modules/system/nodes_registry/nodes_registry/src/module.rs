@@ -1,13 +1,27 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
 
 use modkit::Module;
 use modkit::context::ModuleCtx;
-use modkit::contracts::{OpenApiRegistry, RestApiCapability};
+use modkit::contracts::{
+    DatabaseCapability, GrpcServiceCapability, OpenApiRegistry, RegisterGrpcServiceFn,
+    RestApiCapability,
+};
+use nodes_registry_sdk::grpc::NODES_REGISTRY_SERVICE_NAME;
 
+use crate::api::grpc::NodesRegistryGrpcService;
+use crate::config::NodesRegistryConfig;
 use crate::domain::local_client::NodesRegistryLocalClient;
+use crate::domain::membership::MembershipPolicy;
+use crate::domain::repo::{MembershipStore, MetricsStore};
+use crate::domain::sampler::MetricsSampler;
 use crate::domain::service::Service;
+use crate::infra::remote_registry::RemoteRegistry;
+use crate::infra::storage::db_metrics_store::DbMetricsStore;
+use crate::infra::storage::db_store::DbMembershipStore;
+use modkit::lifecycle::Runnable;
 use nodes_registry_sdk::NodesRegistryClient;
 
 /// Nodes Registry Module
@@ -16,46 +30,143 @@ use nodes_registry_sdk::NodesRegistryClient;
 /// Provides REST API endpoints for:
 /// - Listing nodes
 /// - Getting node details
+/// - Registering nodes, heartbeats and deregistration (also over gRPC)
 /// - Accessing node system information (sysinfo)
 /// - Accessing node system capabilities (syscap)
+///
+/// Membership is shared between replicas when a database is configured; the
+/// background task heartbeats the local node and refreshes the fleet view.
+///
+/// When metrics are enabled, the local node's CPU/memory/GPU usage is sampled into
+/// an in-memory history (and rolled up into the database), and threshold rules
+/// raise alerts.
 #[modkit::module(
     name = "nodes_registry",
-    capabilities = [rest],
-    client = nodes_registry_sdk::NodesRegistryClient
+    capabilities = [rest, grpc, db, stateful],
+    client = nodes_registry_sdk::NodesRegistryClient,
+    lifecycle(entry = "run_background", stop_timeout = "5s")
 )]
 pub struct NodesRegistry {
     service: arc_swap::ArcSwapOption<Service>,
+    config: arc_swap::ArcSwap<NodesRegistryConfig>,
 }
 
 impl Default for NodesRegistry {
     fn default() -> Self {
         Self {
             service: arc_swap::ArcSwapOption::empty(),
+            config: arc_swap::ArcSwap::from_pointee(NodesRegistryConfig::default()),
         }
     }
 }
 
+impl DatabaseCapability for NodesRegistry {
+    fn migrations(&self) -> Vec<Box<dyn sea_orm_migration::MigrationTrait>> {
+        use sea_orm_migration::MigratorTrait;
+        crate::infra::storage::migrations::Migrator::migrations()
+    }
+}
+
 #[async_trait]
 impl Module for NodesRegistry {
     async fn init(&self, ctx: &ModuleCtx) -> Result<()> {
-        // let cfg: NodesRegistryConfig = ctx.config()?; not needed for now
+        let cfg: NodesRegistryConfig = ctx.config()?;
+        cfg.validate()
+            .map_err(|e| anyhow::anyhow!("invalid nodes_registry config: {e}"))?;
+
+        // Membership is persisted only when the module has a database
+        let store = ctx.db().map(|db| {
+            Arc::new(DbMembershipStore::new(db, cfg.cluster_id)) as Arc<dyn MembershipStore>
+        });
+        let persistent = store.is_some();
+        let metrics_store = ctx
+            .db()
+            .filter(|_| cfg.metrics.rollup_interval().is_some())
+            .map(|db| Arc::new(DbMetricsStore::new(db, cfg.cluster_id)) as Arc<dyn MetricsStore>);
 
         // Create the service
-        let service = Service::new();
-        self.service.store(Some(Arc::new(service.clone())));
+        let service = Arc::new(
+            Service::with_membership(MembershipPolicy::from(&cfg), store).with_metrics(
+                cfg.metrics.retention_samples,
+                cfg.metrics.rules.clone(),
+                metrics_store,
+            ),
+        );
+        service.local_heartbeat().await?;
+        service.sync_from_store().await?;
+        self.service.store(Some(service.clone()));
+        self.config.store(Arc::new(cfg));
 
         // Expose the client to the ClientHub
-        let api: Arc<dyn NodesRegistryClient> =
-            Arc::new(NodesRegistryLocalClient::new(Arc::new(service)));
+        let api: Arc<dyn NodesRegistryClient> = Arc::new(NodesRegistryLocalClient::new(service));
 
         // Register in ClientHub directly
         ctx.client_hub().register::<dyn NodesRegistryClient>(api);
 
-        tracing::info!("Nodes registry module initialized");
+        tracing::info!(persistent, "Nodes registry module initialized");
+        Ok(())
+    }
+}
+
+impl NodesRegistry {
+    /// Background task: heartbeat the local node, refresh the fleet from the shared
+    /// store and keep the membership in a remote registry (if configured) alive.
+    /// Also runs the metrics sampler, if enabled.
+    async fn run_background(self: Arc<Self>, cancel: CancellationToken) -> Result<()> {
+        let service = self
+            .service
+            .load_full()
+            .ok_or_else(|| anyhow::anyhow!("Service not initialized"))?;
+        let cfg = self.config.load_full();
+        let sampler = cfg.metrics.enabled.then(|| {
+            let sampler = Arc::new(MetricsSampler::new(
+                service.clone(),
+                cfg.metrics.sample_interval(),
+                cfg.metrics.rollup_interval(),
+                cfg.metrics.rollup_retention(),
+            ));
+            tokio::spawn(sampler.run(cancel.child_token()))
+        });
+
+        let local_node = service.get_node(service.local_node_id())?;
+        let mut remote = cfg
+            .join_endpoint
+            .as_deref()
+            .map(|endpoint| RemoteRegistry::new(endpoint, cfg.heartbeat_interval()));
+
+        let mut ticker = tokio::time::interval(cfg.heartbeat_interval());
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                () = cancel.cancelled() => break,
+                _ = ticker.tick() => {}
+            }
+
+            heartbeat_tick(&service).await;
+            if let Some(remote) = remote.as_mut() {
+                remote.beat(&local_node).await;
+            }
+        }
+
+        if let Some(remote) = remote.as_mut() {
+            remote.leave(local_node.id).await;
+        }
+        if let Some(sampler) = sampler {
+            sampler.await??;
+        }
         Ok(())
     }
 }
 
+async fn heartbeat_tick(service: &Service) {
+    if let Err(e) = service.local_heartbeat().await {
+        tracing::warn!(error = %e, "Local node heartbeat failed");
+    }
+    if let Err(e) = service.sync_from_store().await {
+        tracing::warn!(error = %e, "Refreshing fleet membership failed");
+    }
+}
+
 impl RestApiCapability for NodesRegistry {
     fn register_rest(
         &self,
@@ -76,3 +187,22 @@ impl RestApiCapability for NodesRegistry {
         Ok(router)
     }
 }
+
+/// Export the membership gRPC service to `grpc_hub`
+#[async_trait]
+impl GrpcServiceCapability for NodesRegistry {
+    async fn get_grpc_services(&self, _ctx: &ModuleCtx) -> Result<Vec<RegisterGrpcServiceFn>> {
+        let service = self
+            .service
+            .load_full()
+            .ok_or_else(|| anyhow::anyhow!("Service not initialized"))?;
+        let grpc_svc = NodesRegistryGrpcService::new(service).into_server();
+
+        Ok(vec![RegisterGrpcServiceFn {
+            service_name: NODES_REGISTRY_SERVICE_NAME,
+            register: Box::new(move |routes| {
+                routes.add_service(grpc_svc.clone());
+            }),
+        }])
+    }
+}
@@ -0,0 +1,162 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Tests for custom capabilities set through the client API and for capability
+//! queries used for workload placement.
+
+use chrono::Utc;
+use nodes_registry::domain::error::DomainError;
+use nodes_registry::domain::local_client::NodesRegistryLocalClient;
+use nodes_registry::domain::service::Service;
+use nodes_registry::{
+    CapabilityQuery, CapabilityRequirement, NodeRegistration, NodeStatus, NodesRegistryClient,
+    SysCap,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+fn custom_cap(key: &str, amount: Option<f64>, dimension: Option<&str>) -> SysCap {
+    SysCap {
+        key: key.to_owned(),
+        category: "custom".to_owned(),
+        name: key.to_owned(),
+        display_name: key.to_owned(),
+        present: true,
+        version: None,
+        amount,
+        amount_dimension: dimension.map(str::to_owned),
+        details: None,
+        cache_ttl_secs: 0,
+        fetched_at_secs: Utc::now().timestamp(),
+    }
+}
+
+async fn register(service: &Service, hostname: &str) -> Uuid {
+    let id = Uuid::new_v4();
+    service
+        .register_node(NodeRegistration {
+            id,
+            hostname: hostname.to_owned(),
+            ip_address: None,
+        })
+        .await
+        .unwrap();
+    id
+}
+
+#[test]
+fn test_requirement_converts_byte_dimensions() {
+    let caps = vec![custom_cap("hardware:ram", Some(32.0), Some("GB"))];
+
+    assert!(
+        CapabilityRequirement::present("hardware:ram")
+            .at_least(16.0, Some("GB"))
+            .is_satisfied_by(&caps)
+    );
+    assert!(
+        CapabilityRequirement::present("hardware:ram")
+            .at_least(16_384.0, Some("MB"))
+            .is_satisfied_by(&caps)
+    );
+    assert!(
+        !CapabilityRequirement::present("hardware:ram")
+            .at_least(64.0, Some("GB"))
+            .is_satisfied_by(&caps)
+    );
+    // Incompatible dimensions never match
+    assert!(
+        !CapabilityRequirement::present("hardware:ram")
+            .at_least(1.0, Some("cores"))
+            .is_satisfied_by(&caps)
+    );
+    assert!(CapabilityRequirement::absent("gpu.cuda").is_satisfied_by(&caps));
+    assert!(!CapabilityRequirement::present("gpu.cuda").is_satisfied_by(&caps));
+}
+
+#[tokio::test]
+async fn test_custom_syscap_through_client_and_ttl() {
+    let service = Arc::new(Service::new());
+    let node_id = register(&service, "worker-1").await;
+    let client = NodesRegistryLocalClient::new(service.clone());
+
+    let mut expired = custom_cap("custom.stale", None, None);
+    expired.cache_ttl_secs = 5;
+    expired.fetched_at_secs -= 10;
+    let syscap = client
+        .set_custom_syscap(
+            node_id,
+            vec![custom_cap("custom.rack", None, None), expired],
+        )
+        .await
+        .unwrap();
+    let keys: Vec<&str> = syscap.capabilities.iter().map(|c| c.key.as_str()).collect();
+    assert_eq!(keys, vec!["custom.rack"], "expired entries are not served");
+
+    client
+        .remove_custom_syscap(node_id, vec!["custom.rack".to_owned()])
+        .await
+        .unwrap();
+    assert!(client.get_node_syscap(node_id).await.is_err());
+
+    client
+        .set_custom_syscap(node_id, vec![custom_cap("custom.zone", None, None)])
+        .await
+        .unwrap();
+    client.clear_custom_syscap(node_id).await.unwrap();
+    assert!(client.get_node_syscap(node_id).await.is_err());
+
+    assert!(matches!(
+        service.set_custom_syscap(node_id, vec![custom_cap(" ", None, None)]),
+        Err(DomainError::InvalidInput(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_find_nodes_by_capabilities() {
+    let service = Arc::new(Service::new());
+    let gpu_node = register(&service, "gpu-1").await;
+    let small_gpu_node = register(&service, "gpu-2").await;
+    let cpu_node = register(&service, "cpu-1").await;
+    service
+        .set_custom_syscap(
+            gpu_node,
+            vec![
+                custom_cap("gpu.cuda", None, None),
+                custom_cap("hardware:ram", Some(64.0), Some("GB")),
+            ],
+        )
+        .unwrap();
+    service
+        .set_custom_syscap(
+            small_gpu_node,
+            vec![
+                custom_cap("gpu.cuda", None, None),
+                custom_cap("hardware:ram", Some(8.0), Some("GB")),
+            ],
+        )
+        .unwrap();
+    service
+        .set_custom_syscap(
+            cpu_node,
+            vec![custom_cap("hardware:ram", Some(128.0), Some("GB"))],
+        )
+        .unwrap();
+
+    let query = CapabilityQuery::new()
+        .require(CapabilityRequirement::present("gpu.cuda"))
+        .require(CapabilityRequirement::present("hardware:ram").at_least(16.0, Some("GB")));
+    let client = NodesRegistryLocalClient::new(service.clone());
+    let found = client.find_nodes(&query).await.unwrap();
+    let ids: Vec<Uuid> = found.iter().map(|m| m.node.id).collect();
+    assert_eq!(ids, vec![gpu_node]);
+
+    // Matches carry the capabilities used for the decision
+    let (_, syscap) = service.find_nodes(&query).pop().unwrap();
+    assert!(syscap.is_some());
+
+    // Nodes outside the requested statuses are skipped
+    let dead_only = CapabilityQuery {
+        statuses: vec![NodeStatus::Dead],
+        ..query
+    };
+    assert!(client.find_nodes(&dead_only).await.unwrap().is_empty());
+}
@@ -22,6 +22,13 @@ fn test_error_conversion_mapping() {
             test_id.to_string(),
             "/test/nodes",
         ),
+        (
+            DomainError::NodeInfoUnavailable(test_id),
+            StatusCode::NOT_FOUND,
+            "NODE_INFO_UNAVAILABLE",
+            test_id.to_string(),
+            "/test/nodes/sysinfo",
+        ),
         (
             DomainError::SysInfoCollectionFailed("Failed to read CPU info".to_owned()),
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -0,0 +1,220 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Tests for fleet membership: registration, heartbeats, status derivation and
+//! sharing membership between replicas through the database store.
+
+use chrono::{Duration as ChronoDuration, Utc};
+use modkit_db::migration_runner::run_migrations_for_testing;
+use modkit_db::{ConnectOpts, DBProvider, DbError, connect_db};
+use nodes_registry::domain::error::DomainError;
+use nodes_registry::domain::membership::MembershipPolicy;
+use nodes_registry::domain::repo::MembershipStore;
+use nodes_registry::domain::service::Service;
+use nodes_registry::infra::storage::db_store::DbMembershipStore;
+use nodes_registry::{NodeMember, NodeRegistration, NodeStatus, SysCap};
+use nodes_registry_sdk::grpc::proto;
+use sea_orm_migration::MigratorTrait;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+fn registration(hostname: &str) -> NodeRegistration {
+    NodeRegistration {
+        id: Uuid::new_v4(),
+        hostname: hostname.to_owned(),
+        ip_address: Some("10.0.0.12".to_owned()),
+    }
+}
+
+async fn shared_db() -> DBProvider<DbError> {
+    let db = connect_db("sqlite::memory:", ConnectOpts::default())
+        .await
+        .expect("db connect");
+    run_migrations_for_testing(
+        &db,
+        nodes_registry::infra::storage::migrations::Migrator::migrations(),
+    )
+    .await
+    .expect("migrate");
+    DBProvider::new(db)
+}
+
+fn replica(db: &DBProvider<DbError>, cluster_id: Uuid) -> Service {
+    let store: Arc<dyn MembershipStore> = Arc::new(DbMembershipStore::new(db.clone(), cluster_id));
+    Service::with_membership(MembershipPolicy::default(), Some(store))
+}
+
+#[test]
+fn test_status_derived_from_heartbeat_age() {
+    let now = Utc::now();
+    let suspect = Duration::from_secs(30);
+    let dead = Duration::from_secs(120);
+    let status = |age_secs: i64| {
+        NodeStatus::from_last_seen(now - ChronoDuration::seconds(age_secs), now, suspect, dead)
+    };
+
+    assert_eq!(status(0), NodeStatus::Alive);
+    assert_eq!(status(29), NodeStatus::Alive);
+    assert_eq!(status(30), NodeStatus::Suspect);
+    assert_eq!(status(119), NodeStatus::Suspect);
+    assert_eq!(status(120), NodeStatus::Dead);
+    // Clock skew: heartbeats from the future count as fresh
+    assert_eq!(status(-60), NodeStatus::Alive);
+}
+
+#[tokio::test]
+async fn test_register_heartbeat_and_deregister() {
+    let service = Service::new();
+    let reg = registration("worker-2");
+    let node_id = reg.id;
+
+    let member = service.register_node(reg).await.unwrap();
+    assert_eq!(member.node.hostname, "worker-2");
+    assert_eq!(member.status, NodeStatus::Alive);
+
+    let members = service.list_members();
+    assert_eq!(members.len(), 2, "local node plus the registered one");
+    assert!(members.iter().all(|m| m.status == NodeStatus::Alive));
+
+    let before = member.last_seen_at;
+    let member = service.heartbeat(node_id).await.unwrap();
+    assert!(member.last_seen_at >= before);
+
+    // Re-registration keeps the original creation time
+    let mut again = registration("worker-2b");
+    again.id = node_id;
+    let updated = service.register_node(again).await.unwrap();
+    assert_eq!(updated.node.hostname, "worker-2b");
+    assert_eq!(updated.node.created_at, member.node.created_at);
+
+    service.deregister_node(node_id).await.unwrap();
+    assert_eq!(service.list_members().len(), 1);
+    assert!(matches!(
+        service.heartbeat(node_id).await,
+        Err(DomainError::NodeNotFound(id)) if id == node_id
+    ));
+}
+
+#[tokio::test]
+async fn test_registration_is_validated() {
+    let service = Service::new();
+
+    let mut nil = registration("worker");
+    nil.id = Uuid::nil();
+    let mut empty_host = registration("  ");
+    empty_host.ip_address = None;
+    let mut bad_ip = registration("worker");
+    bad_ip.ip_address = Some("not-an-ip".to_owned());
+    let mut local = registration("impostor");
+    local.id = service.local_node_id();
+
+    for reg in [nil, empty_host, bad_ip, local] {
+        assert!(matches!(
+            service.register_node(reg).await,
+            Err(DomainError::InvalidInput(_))
+        ));
+    }
+    assert!(matches!(
+        service.deregister_node(service.local_node_id()).await,
+        Err(DomainError::InvalidInput(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_remote_node_info_is_not_collected_locally() {
+    let service = Service::new();
+    let node_id = service
+        .register_node(registration("worker-3"))
+        .await
+        .unwrap()
+        .node
+        .id;
+
+    assert!(matches!(
+        service.get_node_sysinfo(node_id),
+        Err(DomainError::NodeInfoUnavailable(id)) if id == node_id
+    ));
+    assert!(matches!(
+        service.get_node_syscap(node_id, false),
+        Err(DomainError::NodeInfoUnavailable(_))
+    ));
+
+    // Custom capabilities set for a remote node are still served
+    let cap = SysCap {
+        key: "software:agent".to_owned(),
+        category: "software".to_owned(),
+        name: "agent".to_owned(),
+        display_name: "Agent".to_owned(),
+        present: true,
+        version: None,
+        amount: None,
+        amount_dimension: None,
+        details: None,
+        cache_ttl_secs: 60,
+        fetched_at_secs: Utc::now().timestamp(),
+    };
+    service.set_custom_syscap(node_id, vec![cap]).unwrap();
+    let syscap = service.get_node_syscap(node_id, false).unwrap();
+    assert_eq!(syscap.capabilities.len(), 1);
+}
+
+#[tokio::test]
+async fn test_replicas_share_membership_through_store() {
+    let db = shared_db().await;
+    let cluster = Uuid::new_v4();
+    let a = replica(&db, cluster);
+    let b = replica(&db, cluster);
+    a.local_heartbeat().await.unwrap();
+
+    let reg = registration("worker-4");
+    let node_id = reg.id;
+    a.register_node(reg).await.unwrap();
+
+    // B never saw the registration, but accepts the heartbeat via the store
+    let member = b.heartbeat(node_id).await.unwrap();
+    assert_eq!(member.node.hostname, "worker-4");
+    assert!(b.list_members().iter().any(|m| m.node.id == node_id));
+
+    // Deregistration through B is picked up by A
+    b.deregister_node(node_id).await.unwrap();
+    assert!(matches!(
+        a.heartbeat(node_id).await,
+        Err(DomainError::NodeNotFound(_))
+    ));
+    a.sync_from_store().await.unwrap();
+    assert!(a.list_members().iter().all(|m| m.node.id != node_id));
+
+    // Another cluster sharing the database does not see these nodes
+    let other = replica(&db, Uuid::new_v4());
+    other.sync_from_store().await.unwrap();
+    a.register_node(registration("worker-5")).await.unwrap();
+    other.sync_from_store().await.unwrap();
+    assert_eq!(other.list_members().len(), 1, "only its own local node");
+}
+
+#[tokio::test]
+async fn test_member_roundtrips_through_grpc_messages() {
+    let service = Service::new();
+    let member = service
+        .register_node(registration("worker-6"))
+        .await
+        .unwrap();
+
+    let msg = proto::NodeMember::from(&member);
+    assert_eq!(msg.status, proto::NodeStatus::Alive as i32);
+    let back = NodeMember::try_from(msg).unwrap();
+    // Timestamps travel as milliseconds
+    assert_eq!(back.node.id, member.node.id);
+    assert_eq!(back.node.hostname, member.node.hostname);
+    assert_eq!(back.node.ip_address, member.node.ip_address);
+    assert_eq!(
+        back.last_seen_at.timestamp_millis(),
+        member.last_seen_at.timestamp_millis()
+    );
+
+    let invalid = proto::NodeMember {
+        id: "not-a-uuid".to_owned(),
+        ..proto::NodeMember::from(&member)
+    };
+    assert!(NodeMember::try_from(invalid).is_err());
+}
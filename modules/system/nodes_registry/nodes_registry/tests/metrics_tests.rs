@@ -0,0 +1,269 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Tests for the node metrics history, downsampling, threshold alerts and
+//! persisted rollups.
+
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use modkit_db::migration_runner::run_migrations_for_testing;
+use modkit_db::{ConnectOpts, DBProvider, DbError, connect_db};
+use nodes_registry::config::MetricsConfig;
+use nodes_registry::domain::error::DomainError;
+use nodes_registry::domain::membership::MembershipPolicy;
+use nodes_registry::domain::metrics::{
+    AlertState, MetricKind, MetricSample, MetricsHistory, ThresholdRule, downsample,
+};
+use nodes_registry::domain::repo::{MembershipStore, MetricsStore};
+use nodes_registry::domain::service::Service;
+use nodes_registry::infra::storage::db_metrics_store::DbMetricsStore;
+use nodes_registry::infra::storage::db_store::DbMembershipStore;
+use sea_orm_migration::MigratorTrait;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+const GIB: u64 = 1024 * 1024 * 1024;
+
+fn base_time() -> DateTime<Utc> {
+    DateTime::from_timestamp(1_699_999_980, 0).unwrap()
+}
+
+fn sample(offset_secs: i64, cpu_percent: f64, memory_used_gib: u64) -> MetricSample {
+    MetricSample {
+        at: base_time() + ChronoDuration::seconds(offset_secs),
+        cpu_percent,
+        memory_used_bytes: memory_used_gib * GIB,
+        memory_total_bytes: 8 * GIB,
+        gpu_memory_percent: None,
+    }
+}
+
+fn cpu_rule(above: f64, for_secs: u64) -> ThresholdRule {
+    ThresholdRule {
+        name: "cpu-high".to_owned(),
+        metric: MetricKind::Cpu,
+        above,
+        for_secs,
+    }
+}
+
+async fn shared_db() -> DBProvider<DbError> {
+    let db = connect_db("sqlite::memory:", ConnectOpts::default())
+        .await
+        .expect("db connect");
+    run_migrations_for_testing(
+        &db,
+        nodes_registry::infra::storage::migrations::Migrator::migrations(),
+    )
+    .await
+    .expect("migrate");
+    DBProvider::new(db)
+}
+
+#[test]
+fn test_history_drops_oldest_samples_when_full() {
+    let history = MetricsHistory::new(3);
+    for i in 0..5 {
+        history.push(sample(i * 10, 10.0, 1));
+    }
+
+    let all = history.range(base_time(), base_time() + ChronoDuration::hours(1));
+    let offsets: Vec<i64> = all
+        .iter()
+        .map(|s| (s.at - base_time()).num_seconds())
+        .collect();
+    assert_eq!(offsets, vec![20, 30, 40]);
+    assert_eq!(
+        history.oldest(),
+        Some(base_time() + ChronoDuration::seconds(20))
+    );
+}
+
+#[test]
+fn test_downsample_aggregates_buckets() {
+    // base_time is aligned to a minute boundary
+    let samples = vec![
+        sample(0, 10.0, 2),
+        sample(20, 30.0, 4),
+        sample(40, 20.0, 2),
+        sample(60, 50.0, 6),
+        sample(130, 70.0, 8),
+    ];
+
+    let points = downsample(&samples, Duration::from_secs(60));
+
+    assert_eq!(points.len(), 3);
+    assert_eq!(points[0].at, base_time());
+    assert_eq!(points[0].samples, 3);
+    assert!((points[0].cpu_percent_avg - 20.0).abs() < 1e-9);
+    assert!((points[0].cpu_percent_max - 30.0).abs() < 1e-9);
+    assert!((points[0].memory_percent_max - 50.0).abs() < 1e-9);
+    assert_eq!(points[0].memory_used_bytes_max, 4 * GIB);
+    assert_eq!(points[1].samples, 1);
+    // Empty buckets are omitted
+    assert_eq!(points[2].at, base_time() + ChronoDuration::seconds(120));
+    assert!((points[2].memory_percent_avg - 100.0).abs() < 1e-9);
+    assert!(points[2].gpu_memory_percent_avg.is_none());
+}
+
+#[test]
+fn test_threshold_rule_fires_after_duration_and_resolves() {
+    let service = Service::new().with_metrics(100, vec![cpu_rule(80.0, 30)], None);
+    let mut events = service.subscribe_alerts();
+
+    // Exceeded, but not for 30 seconds yet
+    assert!(service.record_sample(sample(0, 90.0, 1)).is_empty());
+    assert!(service.record_sample(sample(10, 95.0, 1)).is_empty());
+    assert!(service.record_sample(sample(20, 91.0, 1)).is_empty());
+
+    let fired = service.record_sample(sample(30, 92.0, 1));
+    assert_eq!(fired.len(), 1);
+    assert_eq!(fired[0].state, AlertState::Firing);
+    assert_eq!(fired[0].rule, "cpu-high");
+    assert_eq!(fired[0].node_id, service.local_node_id());
+    assert!((fired[0].value - 92.0).abs() < 1e-9);
+
+    // Still firing: no new transition
+    assert!(service.record_sample(sample(40, 99.0, 1)).is_empty());
+
+    let resolved = service.record_sample(sample(50, 10.0, 1));
+    assert_eq!(resolved.len(), 1);
+    assert_eq!(resolved[0].state, AlertState::Resolved);
+
+    assert_eq!(events.try_recv().unwrap().state, AlertState::Firing);
+    assert_eq!(events.try_recv().unwrap().state, AlertState::Resolved);
+    assert_eq!(service.recent_alerts().len(), 2);
+}
+
+#[test]
+fn test_threshold_rule_requires_every_sample_in_window() {
+    let service = Service::new().with_metrics(100, vec![cpu_rule(80.0, 20)], None);
+
+    assert!(service.record_sample(sample(0, 90.0, 1)).is_empty());
+    // A dip below the threshold restarts the window
+    assert!(service.record_sample(sample(10, 50.0, 1)).is_empty());
+    assert!(service.record_sample(sample(20, 90.0, 1)).is_empty());
+    assert!(service.record_sample(sample(30, 90.0, 1)).is_empty());
+    assert_eq!(service.record_sample(sample(40, 90.0, 1)).len(), 1);
+}
+
+#[test]
+fn test_metric_series_is_local_only() {
+    let service = Service::new();
+    let _ = service.record_sample(sample(0, 10.0, 1));
+
+    let points = service
+        .metric_series(
+            service.local_node_id(),
+            base_time(),
+            base_time() + ChronoDuration::minutes(1),
+            Duration::from_secs(60),
+        )
+        .unwrap();
+    assert_eq!(points.len(), 1);
+
+    let err = service
+        .metric_series(
+            Uuid::new_v4(),
+            base_time(),
+            base_time(),
+            Duration::from_secs(60),
+        )
+        .unwrap_err();
+    assert!(matches!(err, DomainError::NodeNotFound(_)));
+}
+
+#[test]
+fn test_metrics_config_validation() {
+    let mut cfg = MetricsConfig::default();
+    assert!(!cfg.enabled);
+    assert!(cfg.validate().is_ok());
+
+    cfg.rollup_interval_secs = 5;
+    assert!(cfg.validate().is_err());
+    cfg.rollup_interval_secs = 0;
+    assert!(cfg.validate().is_ok());
+    assert!(cfg.rollup_interval().is_none());
+
+    cfg.rules = vec![cpu_rule(80.0, 0), cpu_rule(90.0, 0)];
+    assert!(cfg.validate().unwrap_err().contains("duplicate"));
+}
+
+#[tokio::test]
+async fn test_rollups_roundtrip_through_store() {
+    let db = shared_db().await;
+    let cluster_id = Uuid::new_v4();
+    let membership: Arc<dyn MembershipStore> =
+        Arc::new(DbMembershipStore::new(db.clone(), cluster_id));
+    let metrics: Arc<dyn MetricsStore> = Arc::new(DbMetricsStore::new(db.clone(), cluster_id));
+    let service = Service::with_membership(MembershipPolicy::default(), Some(membership))
+        .with_metrics(100, Vec::new(), Some(metrics.clone()));
+    service.local_heartbeat().await.unwrap();
+
+    let step = Duration::from_secs(60);
+    for (offset, cpu) in [(0, 10.0), (30, 30.0), (60, 50.0), (90, 70.0)] {
+        let _ = service.record_sample(sample(offset, cpu, 2));
+    }
+    assert!(service.store_rollup(base_time(), step).await.unwrap());
+    let second = base_time() + ChronoDuration::seconds(60);
+    assert!(service.store_rollup(second, step).await.unwrap());
+    // Re-storing a bucket replaces it
+    assert!(service.store_rollup(second, step).await.unwrap());
+    // Nothing sampled in this bucket
+    let empty = base_time() + ChronoDuration::seconds(600);
+    assert!(!service.store_rollup(empty, step).await.unwrap());
+
+    let node_id = service.local_node_id();
+    let until = base_time() + ChronoDuration::hours(1);
+    let per_minute = service
+        .metric_rollups(node_id, base_time(), until, step)
+        .await
+        .unwrap();
+    assert_eq!(per_minute.len(), 2);
+    assert!((per_minute[0].cpu_percent_avg - 20.0).abs() < 1e-9);
+    assert!((per_minute[1].cpu_percent_max - 70.0).abs() < 1e-9);
+
+    // Rollups are re-aggregated to coarser steps
+    let per_hour = service
+        .metric_rollups(node_id, base_time(), until, Duration::from_secs(3600))
+        .await
+        .unwrap();
+    assert_eq!(per_hour.len(), 1);
+    assert_eq!(per_hour[0].samples, 4);
+    assert!((per_hour[0].cpu_percent_avg - 40.0).abs() < 1e-9);
+    assert!((per_hour[0].cpu_percent_max - 70.0).abs() < 1e-9);
+
+    // Another cluster sharing the database does not see them
+    let other = DbMetricsStore::new(db.clone(), Uuid::new_v4());
+    assert!(
+        other
+            .rollups(node_id, base_time(), until)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    assert_eq!(service.prune_rollups(second).await.unwrap(), 1);
+    assert_eq!(
+        metrics
+            .rollups(node_id, base_time(), until)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn test_rollups_require_a_store() {
+    let service = Service::new();
+    let err = service
+        .metric_rollups(
+            service.local_node_id(),
+            base_time(),
+            base_time(),
+            Duration::from_secs(60),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DomainError::InvalidInput(_)));
+}
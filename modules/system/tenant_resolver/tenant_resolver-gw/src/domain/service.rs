@@ -16,7 +16,7 @@ use tenant_resolver_sdk::{
     TenantResolverPluginSpecV1,
 };
 use tracing::info;
-use types_registry_sdk::{GtsEntity, ListQuery, TypesRegistryClient};
+use types_registry_sdk::{EntityChangeStream, GtsEntity, ListQuery, TypesRegistryClient};
 use uuid::Uuid;
 
 use super::error::DomainError;
@@ -73,6 +73,17 @@ impl Service {
         }
     }
 
+    /// Re-resolves the plugin on the next call each time `changes` yields,
+    /// until the stream ends.
+    pub async fn invalidate_on(&self, changes: EntityChangeStream) {
+        self.selector.reset_on_changes(changes).await;
+    }
+
+    /// Re-resolves the plugin on the next call.
+    pub async fn invalidate(&self) {
+        self.selector.reset().await;
+    }
+
     /// Resolves the plugin instance from types-registry.
     #[tracing::instrument(skip_all, fields(vendor = %self.vendor))]
     async fn resolve_plugin(&self) -> Result<String, DomainError> {
@@ -83,15 +94,7 @@ impl Service {
             .get::<dyn TypesRegistryClient>()
             .map_err(|e| DomainError::TypesRegistryUnavailable(e.to_string()))?;
 
-        let plugin_type_id = TenantResolverPluginSpecV1::gts_schema_id().clone();
-
-        let instances = registry
-            .list(
-                ListQuery::new()
-                    .with_pattern(format!("{plugin_type_id}*"))
-                    .with_is_type(false),
-            )
-            .await?;
+        let instances = registry.list(plugin_instances_query()).await?;
 
         let gts_id = choose_plugin_instance(&self.vendor, &instances)?;
         info!(plugin_gts_id = %gts_id, "Selected tenant resolver plugin instance");
@@ -169,6 +172,14 @@ impl Service {
     }
 }
 
+/// Query matching the registered tenant resolver plugin instances.
+pub(crate) fn plugin_instances_query() -> ListQuery {
+    let plugin_type_id = TenantResolverPluginSpecV1::gts_schema_id().clone();
+    ListQuery::new()
+        .with_pattern(format!("{plugin_type_id}*"))
+        .with_is_type(false)
+}
+
 /// Validates that the security context has a tenant ID.
 ///
 /// Returns `Unauthorized` if the context has no tenant (nil UUID).
@@ -1,15 +1,18 @@
 //! Tenant resolver gateway module.
 
 use std::sync::{Arc, OnceLock};
+use std::time::Duration;
 
 use async_trait::async_trait;
 use modkit::Module;
 use modkit::context::ModuleCtx;
 use tenant_resolver_sdk::{TenantResolverGatewayClient, TenantResolverPluginSpecV1};
-use tracing::info;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
 use types_registry_sdk::TypesRegistryClient;
 
 use crate::config::TenantResolverGwConfig;
+use crate::domain::service::plugin_instances_query;
 use crate::domain::{Service, TenantResolverGwLocalClient};
 
 /// Tenant Resolver Gateway module.
@@ -20,20 +23,27 @@ use crate::domain::{Service, TenantResolverGwLocalClient};
 /// 3. Routes requests to the selected plugin based on vendor configuration
 ///
 /// Plugin discovery is lazy: happens on first API call after types-registry
-/// is ready.
+/// is ready, and again after plugin instances change. The background task
+/// watching for those changes runs from module start to stop.
 #[modkit::module(
     name = "tenant_resolver",
     deps = ["types_registry"],
-    capabilities = []
+    capabilities = [stateful],
+    lifecycle(entry = "watch_plugins", stop_timeout = "5s")
 )]
 pub(crate) struct TenantResolverGateway {
     service: OnceLock<Arc<Service>>,
+    registry: OnceLock<Arc<dyn TypesRegistryClient>>,
 }
 
+/// Delay before subscribing again to plugin instance changes.
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(5);
+
 impl Default for TenantResolverGateway {
     fn default() -> Self {
         Self {
             service: OnceLock::new(),
+            registry: OnceLock::new(),
         }
     }
 }
@@ -69,7 +79,47 @@ impl Module for TenantResolverGateway {
         self.service
             .set(svc)
             .map_err(|_| anyhow::anyhow!("Service already initialized"))?;
+        self.registry
+            .set(registry)
+            .map_err(|_| anyhow::anyhow!("Registry client already initialized"))?;
 
         Ok(())
     }
 }
+
+impl TenantResolverGateway {
+    /// Background task: pick up plugin instances registered or replaced after
+    /// the first resolution, subscribing again whenever the change stream
+    /// ends or cannot be opened.
+    async fn watch_plugins(self: Arc<Self>, cancel: CancellationToken) -> anyhow::Result<()> {
+        let (Some(svc), Some(registry)) = (self.service.get(), self.registry.get()) else {
+            anyhow::bail!("Service not initialized");
+        };
+
+        loop {
+            tokio::select! {
+                () = cancel.cancelled() => break,
+                () = follow_plugin_changes(svc, registry.as_ref()) => {}
+            }
+            tokio::select! {
+                () = cancel.cancelled() => break,
+                () = tokio::time::sleep(RESUBSCRIBE_DELAY) => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Resets the plugin selection on every plugin instance change, until the
+/// change stream ends or cannot be opened.
+async fn follow_plugin_changes(svc: &Service, registry: &dyn TypesRegistryClient) {
+    match registry.watch(plugin_instances_query()).await {
+        Ok(changes) => {
+            // Changes may have been missed while not subscribed
+            svc.invalidate().await;
+            svc.invalidate_on(changes).await;
+            warn!("Plugin instance change stream ended, resubscribing");
+        }
+        Err(e) => warn!(error = %e, "Failed to watch plugin instances, retrying"),
+    }
+}
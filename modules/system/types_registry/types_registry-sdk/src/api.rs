@@ -3,10 +3,16 @@
 //! This trait defines the public API for the `types-registry` module.
 //! GTS schemas and instances are global resources, so no security context is required.
 
+use std::pin::Pin;
+
 use async_trait::async_trait;
+use futures_core::Stream;
 
 use crate::error::TypesRegistryError;
-use crate::models::{GtsEntity, ListQuery, RegisterResult};
+use crate::models::{EntityChange, GtsEntity, ListQuery, RegisterResult, ResolvedSchema};
+
+/// Stream of entity changes returned by [`TypesRegistryClient::watch`].
+pub type EntityChangeStream = Pin<Box<dyn Stream<Item = EntityChange> + Send>>;
 
 /// Public API trait for the `types-registry` module.
 ///
@@ -89,4 +95,66 @@ pub trait TypesRegistryClient: Send + Sync {
     /// * `NotFound` - If no entity with the given GTS ID exists
     /// * `InvalidGtsId` - If the GTS ID format is invalid
     async fn get(&self, gts_id: &str) -> Result<GtsEntity, TypesRegistryError>;
+
+    /// Mark an entity as deprecated.
+    ///
+    /// The entity still resolves through `get` and `list`, with
+    /// `deprecated` set. Deprecating twice is a no-op.
+    ///
+    /// # Returns
+    ///
+    /// The deprecated entity.
+    ///
+    /// # Errors
+    ///
+    /// * `NotFound` - If no entity with the given GTS ID exists
+    async fn deprecate(&self, gts_id: &str) -> Result<GtsEntity, TypesRegistryError>;
+
+    /// Delete an entity.
+    ///
+    /// The GTS ID is tombstoned: it can only be registered again with the
+    /// content it had when deleted.
+    ///
+    /// # Errors
+    ///
+    /// * `NotFound` - If no entity with the given GTS ID exists
+    /// * `InUse` - If instances, derived types or other schemas (`$ref`) still
+    ///   reference the entity
+    async fn delete(&self, gts_id: &str) -> Result<(), TypesRegistryError>;
+
+    /// Resolve a type schema into a self-contained schema.
+    ///
+    /// Every `$ref` to a registered GTS schema is replaced by that schema,
+    /// recursively, so the result can be used without access to the
+    /// registry (e.g. as an LLM tool parameter schema).
+    ///
+    /// # Errors
+    ///
+    /// * `NotFound` - If no schema with the given GTS ID exists
+    /// * `InvalidGtsId` - If the GTS ID is not a type schema ID
+    /// * `CyclicReference` - If the referenced schemas form a cycle
+    async fn resolve_schema(&self, gts_id: &str) -> Result<ResolvedSchema, TypesRegistryError>;
+
+    /// Watch changes to entities matching a query.
+    ///
+    /// Delivers additions, replacements and removals that happen after the
+    /// call, in order. The stream of a consumer that falls behind by more
+    /// than the registry's buffer ends; re-read with `list` and watch again
+    /// if missing a change matters.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut changes = registry
+    ///     .watch(ListQuery::new().with_pattern("gts.x.core.modkit.plugin.v1~*"))
+    ///     .await?;
+    /// while let Some(change) = changes.next().await {
+    ///     println!("{:?}: {}", change.kind, change.gts_id());
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the registry cannot deliver changes.
+    async fn watch(&self, query: ListQuery) -> Result<EntityChangeStream, TypesRegistryError>;
 }
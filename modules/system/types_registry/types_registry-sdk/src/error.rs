@@ -23,6 +23,22 @@ pub enum TypesRegistryError {
     #[error("Validation failed: {0}")]
     ValidationFailed(String),
 
+    /// A new version of a type schema breaks compatibility with registered versions.
+    #[error("Incompatible schema: {0}")]
+    IncompatibleSchema(String),
+
+    /// The entity cannot be deleted while other entities reference it.
+    #[error("Entity in use: {0}")]
+    InUse(String),
+
+    /// The GTS ID belongs to a deleted entity and cannot be reused with different content.
+    #[error("Entity deleted: {0}")]
+    Tombstoned(String),
+
+    /// Schemas reference each other in a cycle.
+    #[error("Cyclic reference: {0}")]
+    CyclicReference(String),
+
     /// The operation requires ready mode.
     #[error("Not in ready mode")]
     NotInReadyMode,
@@ -57,6 +73,30 @@ impl TypesRegistryError {
         Self::ValidationFailed(message.into())
     }
 
+    /// Creates an `IncompatibleSchema` error.
+    #[must_use]
+    pub fn incompatible_schema(message: impl Into<String>) -> Self {
+        Self::IncompatibleSchema(message.into())
+    }
+
+    /// Creates an `InUse` error.
+    #[must_use]
+    pub fn in_use(message: impl Into<String>) -> Self {
+        Self::InUse(message.into())
+    }
+
+    /// Creates a `Tombstoned` error.
+    #[must_use]
+    pub fn tombstoned(gts_id: impl Into<String>) -> Self {
+        Self::Tombstoned(gts_id.into())
+    }
+
+    /// Creates a `CyclicReference` error.
+    #[must_use]
+    pub fn cyclic_reference(message: impl Into<String>) -> Self {
+        Self::CyclicReference(message.into())
+    }
+
     /// Creates a `NotInReadyMode` error.
     #[must_use]
     pub const fn not_in_ready_mode() -> Self {
@@ -87,6 +127,30 @@ impl TypesRegistryError {
         matches!(self, Self::ValidationFailed(_))
     }
 
+    /// Returns `true` if this is a schema compatibility error.
+    #[must_use]
+    pub const fn is_incompatible_schema(&self) -> bool {
+        matches!(self, Self::IncompatibleSchema(_))
+    }
+
+    /// Returns `true` if this is an entity in use error.
+    #[must_use]
+    pub const fn is_in_use(&self) -> bool {
+        matches!(self, Self::InUse(_))
+    }
+
+    /// Returns `true` if this is a deleted entity error.
+    #[must_use]
+    pub const fn is_tombstoned(&self) -> bool {
+        matches!(self, Self::Tombstoned(_))
+    }
+
+    /// Returns `true` if this is a cyclic reference error.
+    #[must_use]
+    pub const fn is_cyclic_reference(&self) -> bool {
+        matches!(self, Self::CyclicReference(_))
+    }
+
     /// Returns `true` if this is an invalid GTS ID error.
     #[must_use]
     pub const fn is_invalid_gts_id(&self) -> bool {
@@ -113,6 +177,18 @@ mod tests {
         let err = TypesRegistryError::validation_failed("schema invalid");
         assert!(err.is_validation_failed());
 
+        let err = TypesRegistryError::incompatible_schema("property removed");
+        assert!(err.is_incompatible_schema());
+
+        let err = TypesRegistryError::in_use("referenced by instances");
+        assert!(err.is_in_use());
+
+        let err = TypesRegistryError::tombstoned("gts.acme.core.events.test.v1~");
+        assert!(err.is_tombstoned());
+
+        let err = TypesRegistryError::cyclic_reference("a~ -> b~ -> a~");
+        assert!(err.is_cyclic_reference());
+
         let err = TypesRegistryError::not_in_ready_mode();
         assert!(matches!(err, TypesRegistryError::NotInReadyMode));
 
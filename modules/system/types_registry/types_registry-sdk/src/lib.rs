@@ -4,6 +4,8 @@
 //! - `TypesRegistryApi` trait for inter-module communication
 //! - `GtsEntity` model representing registered GTS entities
 //! - `ListQuery` for filtering entity listings
+//! - `EntityChange` notifications delivered by `watch`
+//! - `ResolvedSchema` for self-contained schemas with `$ref`s inlined
 //! - `TypesRegistryError` for error handling
 //!
 //! ## Usage
@@ -34,9 +36,11 @@ pub mod error;
 pub mod models;
 
 // Re-export main types at crate root for convenience
-pub use api::TypesRegistryClient;
+pub use api::{EntityChangeStream, TypesRegistryClient};
 pub use error::TypesRegistryError;
 pub use models::{
-    DynGtsEntity, DynRegisterResult, GtsEntity, GtsInstanceEntity, GtsTypeEntity, InstanceObject,
-    ListQuery, RegisterResult, RegisterSummary, SegmentMatchScope, TypeSchema,
+    DynGtsEntity, DynRegisterResult, EntityChange, EntityChangeKind, EntityGraph, GraphDirection,
+    GraphEdge, GraphEdgeKind, GraphNode, GtsEntity, GtsInstanceEntity, GtsTypeEntity,
+    InstanceObject, ListQuery, RegisterResult, RegisterSummary, ResolvedSchema, SegmentMatchScope,
+    TypeSchema,
 };
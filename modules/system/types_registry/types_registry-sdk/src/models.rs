@@ -3,7 +3,7 @@
 //! These are transport-agnostic data structures that define the contract
 //! between the `types-registry` module and its consumers.
 
-use gts::GtsIdSegment;
+use gts::{GtsID, GtsIdSegment, GtsWildcard};
 use uuid::Uuid;
 
 /// A registered GTS entity.
@@ -58,6 +58,10 @@ pub struct GtsEntity<C = serde_json::Value> {
 
     /// Optional description of the entity.
     pub description: Option<String>,
+
+    /// Whether the entity is deprecated. Deprecated entities still resolve
+    /// but should not be used for new data.
+    pub deprecated: bool,
 }
 
 /// Type alias for dynamic GTS entities using `serde_json::Value` as content.
@@ -412,9 +416,17 @@ impl<C> GtsEntity<C> {
             is_schema,
             content,
             description,
+            deprecated: false,
         }
     }
 
+    /// Sets the deprecation flag.
+    #[must_use]
+    pub fn with_deprecated(mut self, deprecated: bool) -> Self {
+        self.deprecated = deprecated;
+        self
+    }
+
     /// Returns `true` if this entity is a type definition (schema).
     #[must_use]
     pub const fn is_type(&self) -> bool {
@@ -610,6 +622,146 @@ impl ListQuery {
             && self.package.is_none()
             && self.namespace.is_none()
     }
+
+    /// Returns `true` if `entity` passes all filters of this query.
+    ///
+    /// An invalid pattern matches everything, as in `list`.
+    #[must_use]
+    pub fn matches<C>(&self, entity: &GtsEntity<C>) -> bool {
+        if let Some(ref pattern) = self.pattern
+            && let Ok(wildcard) = GtsWildcard::new(pattern)
+            && !GtsID::new(&entity.gts_id).is_ok_and(|id| id.wildcard_match(&wildcard))
+        {
+            return false;
+        }
+
+        if let Some(is_type) = self.is_type
+            && entity.is_type() != is_type
+        {
+            return false;
+        }
+
+        let segments_to_check: Vec<&GtsIdSegment> = match self.segment_scope {
+            SegmentMatchScope::Primary => entity.segments.first().into_iter().collect(),
+            SegmentMatchScope::Any => entity.segments.iter().collect(),
+        };
+        let segment_matches = |filter: &Option<String>, field: fn(&GtsIdSegment) -> &str| {
+            filter
+                .as_deref()
+                .is_none_or(|value| segments_to_check.iter().any(|s| field(s) == value))
+        };
+
+        segment_matches(&self.vendor, |s| &s.vendor)
+            && segment_matches(&self.package, |s| &s.package)
+            && segment_matches(&self.namespace, |s| &s.namespace)
+    }
+}
+
+/// Kind of change to a registered GTS entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntityChangeKind {
+    /// A new entity was registered.
+    Added,
+    /// A registered entity was replaced with new content.
+    Updated,
+    /// A registered entity was removed.
+    Removed,
+}
+
+/// A change to a registered GTS entity, as delivered by
+/// [`TypesRegistryClient::watch`](crate::TypesRegistryClient::watch).
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntityChange {
+    /// What happened to the entity.
+    pub kind: EntityChangeKind,
+    /// The entity after the change; for removals, the last registered version.
+    pub entity: GtsEntity,
+}
+
+impl EntityChange {
+    /// Creates a new change notification.
+    #[must_use]
+    pub fn new(kind: EntityChangeKind, entity: GtsEntity) -> Self {
+        Self { kind, entity }
+    }
+
+    /// The GTS ID of the changed entity.
+    #[must_use]
+    pub fn gts_id(&self) -> &str {
+        &self.entity.gts_id
+    }
+}
+
+/// A type schema with every GTS `$ref` replaced by the referenced schema,
+/// as returned by
+/// [`TypesRegistryClient::resolve_schema`](crate::TypesRegistryClient::resolve_schema).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedSchema {
+    /// GTS ID of the resolved schema.
+    pub gts_id: String,
+    /// The self-contained schema.
+    pub schema: serde_json::Value,
+    /// GTS IDs of the schemas that were inlined, sorted.
+    pub references: Vec<String>,
+    /// GTS IDs referenced but not registered, sorted. Their `$ref`s are
+    /// left in place.
+    pub unresolved: Vec<String>,
+}
+
+/// How one GTS entity depends on another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GraphEdgeKind {
+    /// A derived type depends on the type its chained ID extends.
+    Parent,
+    /// An instance depends on its type.
+    Type,
+    /// A schema references another schema through `$ref`.
+    Ref,
+}
+
+/// Which edges to follow from the root of a dependency graph.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GraphDirection {
+    /// Entities the root depends on.
+    Dependencies,
+    /// Entities that depend on the root.
+    Dependents,
+    /// Both.
+    #[default]
+    Both,
+}
+
+/// An entity in a dependency graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphNode {
+    /// GTS ID of the entity.
+    pub gts_id: String,
+    /// Whether the entity is a type schema.
+    pub is_schema: bool,
+    /// `false` if the entity is referenced but not registered.
+    pub registered: bool,
+}
+
+/// `from` depends on `to`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GraphEdge {
+    /// GTS ID of the dependent entity.
+    pub from: String,
+    /// GTS ID of the entity depended on.
+    pub to: String,
+    /// How `from` depends on `to`.
+    pub kind: GraphEdgeKind,
+}
+
+/// The dependencies and/or dependents of a GTS entity.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntityGraph {
+    /// GTS ID of the entity the graph was queried for.
+    pub root: String,
+    /// Entities in the graph, the root included, sorted by GTS ID.
+    pub nodes: Vec<GraphNode>,
+    /// Dependencies between the entities, sorted.
+    pub edges: Vec<GraphEdge>,
 }
 
 #[cfg(test)]
@@ -704,4 +856,35 @@ mod tests {
         assert_eq!(query.vendor, Some("acme".to_owned()));
         assert_eq!(query.segment_scope, SegmentMatchScope::Any);
     }
+
+    #[test]
+    fn test_list_query_matches() {
+        let gts_id = "gts.x.core.modkit.plugin.v1~acme.test._.plugin.v1";
+        let segments = GtsID::new(gts_id).unwrap().gts_id_segments;
+        let entity = GtsEntity::new(
+            Uuid::nil(),
+            gts_id,
+            segments,
+            false,
+            serde_json::json!({}),
+            None,
+        );
+
+        assert!(ListQuery::new().matches(&entity));
+        assert!(
+            ListQuery::new()
+                .with_pattern("gts.x.core.modkit.plugin.v1~*")
+                .with_is_type(false)
+                .matches(&entity)
+        );
+        assert!(!ListQuery::new().with_is_type(true).matches(&entity));
+        assert!(ListQuery::new().with_vendor("acme").matches(&entity));
+        assert!(
+            !ListQuery::new()
+                .with_vendor("acme")
+                .with_segment_scope(SegmentMatchScope::Primary)
+                .matches(&entity)
+        );
+        assert!(!ListQuery::new().with_pattern("gts.acme.*").matches(&entity));
+    }
 }
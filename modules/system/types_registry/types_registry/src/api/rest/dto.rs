@@ -3,7 +3,12 @@
 use uuid::Uuid;
 
 use gts::GtsIdSegment;
-use types_registry_sdk::{GtsEntity, RegisterResult, RegisterSummary, SegmentMatchScope};
+use types_registry_sdk::{
+    EntityChange, EntityChangeKind, EntityGraph, GraphDirection, GraphEdge, GraphEdgeKind,
+    GraphNode, GtsEntity, RegisterResult, RegisterSummary, ResolvedSchema, SegmentMatchScope,
+};
+
+use crate::domain::{CompatibilityMode, SchemaDiff};
 
 /// DTO for a GTS ID segment.
 #[derive(Debug, Clone)]
@@ -53,6 +58,9 @@ pub struct GtsEntityDto {
     /// Optional description of the entity.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// Whether the entity is deprecated. Deprecated entities stay resolvable.
+    #[serde(default)]
+    pub deprecated: bool,
 }
 
 impl From<GtsEntity> for GtsEntityDto {
@@ -64,6 +72,7 @@ impl From<GtsEntity> for GtsEntityDto {
             is_schema: entity.is_schema,
             content: entity.content.clone(),
             description: entity.description.clone(),
+            deprecated: entity.deprecated,
         }
     }
 }
@@ -216,6 +225,291 @@ pub struct ListEntitiesResponse {
     pub count: usize,
 }
 
+/// Kind of change to a registered GTS entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[modkit_macros::api_dto(response)]
+pub enum EntityChangeKindDto {
+    Added,
+    Updated,
+    Removed,
+}
+
+impl From<EntityChangeKind> for EntityChangeKindDto {
+    fn from(kind: EntityChangeKind) -> Self {
+        match kind {
+            EntityChangeKind::Added => Self::Added,
+            EntityChangeKind::Updated => Self::Updated,
+            EntityChangeKind::Removed => Self::Removed,
+        }
+    }
+}
+
+/// Event DTO for a change to a registered GTS entity.
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(response)]
+pub struct EntityChangeDto {
+    /// What happened to the entity.
+    pub kind: EntityChangeKindDto,
+    /// The entity after the change; for removals, the last registered version.
+    pub entity: GtsEntityDto,
+}
+
+impl From<EntityChange> for EntityChangeDto {
+    fn from(change: EntityChange) -> Self {
+        Self {
+            kind: change.kind.into(),
+            entity: change.entity.into(),
+        }
+    }
+}
+
+/// Query parameters for importing a bundle.
+#[derive(Debug, Clone, Default)]
+#[modkit_macros::api_dto(request)]
+pub struct ImportBundleQuery {
+    /// Validate the bundle against the registry without applying it.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Response DTO for a bundle import.
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(response)]
+pub struct ImportBundleResponse {
+    /// Whether the import was a dry run and nothing was applied.
+    pub dry_run: bool,
+    /// Summary of the registration of the bundle entities.
+    pub summary: RegisterSummaryDto,
+    /// Results for each entity, in bundle order.
+    pub results: Vec<RegisterResultDto>,
+}
+
+/// Which edges to follow from the root of a dependency graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[modkit_macros::api_dto(request)]
+pub enum GraphDirectionDto {
+    Dependencies,
+    Dependents,
+    Both,
+}
+
+impl From<GraphDirectionDto> for GraphDirection {
+    fn from(direction: GraphDirectionDto) -> Self {
+        match direction {
+            GraphDirectionDto::Dependencies => Self::Dependencies,
+            GraphDirectionDto::Dependents => Self::Dependents,
+            GraphDirectionDto::Both => Self::Both,
+        }
+    }
+}
+
+/// Query parameters for the dependency graph of an entity.
+#[derive(Debug, Clone, Default)]
+#[modkit_macros::api_dto(request)]
+pub struct EntityGraphQuery {
+    /// Entities the root depends on, entities depending on it, or both (default).
+    #[serde(default)]
+    pub direction: Option<GraphDirectionDto>,
+    /// Maximum number of edges from the root (default: unlimited).
+    #[serde(default)]
+    pub depth: Option<usize>,
+}
+
+/// How one GTS entity depends on another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[modkit_macros::api_dto(response)]
+pub enum GraphEdgeKindDto {
+    Parent,
+    Type,
+    Ref,
+}
+
+impl From<GraphEdgeKind> for GraphEdgeKindDto {
+    fn from(kind: GraphEdgeKind) -> Self {
+        match kind {
+            GraphEdgeKind::Parent => Self::Parent,
+            GraphEdgeKind::Type => Self::Type,
+            GraphEdgeKind::Ref => Self::Ref,
+        }
+    }
+}
+
+/// An entity in a dependency graph.
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(response)]
+pub struct GraphNodeDto {
+    /// The full GTS identifier string.
+    pub gts_id: String,
+    /// Whether the entity is a type schema.
+    pub is_schema: bool,
+    /// `false` if the entity is referenced but not registered.
+    pub registered: bool,
+}
+
+impl From<GraphNode> for GraphNodeDto {
+    fn from(node: GraphNode) -> Self {
+        Self {
+            gts_id: node.gts_id,
+            is_schema: node.is_schema,
+            registered: node.registered,
+        }
+    }
+}
+
+/// A dependency: `from` depends on `to`.
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(response)]
+pub struct GraphEdgeDto {
+    /// GTS ID of the dependent entity.
+    pub from: String,
+    /// GTS ID of the entity depended on.
+    pub to: String,
+    /// `parent` for chained IDs, `type` for instances, `ref` for `$ref`s.
+    pub kind: GraphEdgeKindDto,
+}
+
+impl From<GraphEdge> for GraphEdgeDto {
+    fn from(edge: GraphEdge) -> Self {
+        Self {
+            from: edge.from,
+            to: edge.to,
+            kind: edge.kind.into(),
+        }
+    }
+}
+
+/// Response DTO for the dependency graph of an entity.
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(response)]
+pub struct EntityGraphDto {
+    /// GTS ID of the entity the graph was queried for.
+    pub root: String,
+    /// Entities in the graph, the root included.
+    pub nodes: Vec<GraphNodeDto>,
+    /// Dependencies between the entities.
+    pub edges: Vec<GraphEdgeDto>,
+}
+
+impl From<EntityGraph> for EntityGraphDto {
+    fn from(graph: EntityGraph) -> Self {
+        Self {
+            root: graph.root,
+            nodes: graph.nodes.into_iter().map(Into::into).collect(),
+            edges: graph.edges.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Response DTO for a type schema with its `$ref`s inlined.
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(response)]
+pub struct ResolvedSchemaDto {
+    /// GTS ID of the resolved schema.
+    pub gts_id: String,
+    /// The self-contained schema.
+    pub schema: serde_json::Value,
+    /// GTS IDs of the schemas that were inlined.
+    pub references: Vec<String>,
+    /// GTS IDs referenced but not registered; their `$ref`s are left in place.
+    pub unresolved: Vec<String>,
+}
+
+impl From<ResolvedSchema> for ResolvedSchemaDto {
+    fn from(resolved: ResolvedSchema) -> Self {
+        Self {
+            gts_id: resolved.gts_id,
+            schema: resolved.schema,
+            references: resolved.references,
+            unresolved: resolved.unresolved,
+        }
+    }
+}
+
+/// Query parameters for diffing two type schemas.
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(request)]
+pub struct SchemaDiffQuery {
+    /// GTS ID of the earlier schema version.
+    pub from: String,
+    /// GTS ID of the later schema version.
+    pub to: String,
+}
+
+/// Compatibility required between minor versions of a type schema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[modkit_macros::api_dto(response)]
+pub enum CompatibilityModeDto {
+    None,
+    Backward,
+    Forward,
+    Full,
+}
+
+impl From<CompatibilityMode> for CompatibilityModeDto {
+    fn from(mode: CompatibilityMode) -> Self {
+        match mode {
+            CompatibilityMode::None => Self::None,
+            CompatibilityMode::Backward => Self::Backward,
+            CompatibilityMode::Forward => Self::Forward,
+            CompatibilityMode::Full => Self::Full,
+        }
+    }
+}
+
+/// Response DTO explaining the changes between two schema versions.
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(response)]
+pub struct SchemaDiffDto {
+    /// GTS ID of the earlier schema version.
+    pub from: String,
+    /// GTS ID of the later schema version.
+    pub to: String,
+    /// Whether instances of `from` are valid instances of `to`.
+    pub backward_compatible: bool,
+    /// Whether instances of `to` are valid instances of `from`.
+    pub forward_compatible: bool,
+    /// Compatibility mode enforced by the registry on registration.
+    pub mode: CompatibilityModeDto,
+    /// Changes that `mode` would reject.
+    pub breaking_changes: Vec<String>,
+    /// Top-level properties only defined by `to`.
+    pub added_properties: Vec<String>,
+    /// Top-level properties only defined by `from`.
+    pub removed_properties: Vec<String>,
+    /// Properties required by `to` but not by `from`.
+    pub newly_required: Vec<String>,
+    /// Properties required by `from` but not by `to`.
+    pub no_longer_required: Vec<String>,
+    /// Why instances of `from` may be rejected by `to`.
+    pub backward_errors: Vec<String>,
+    /// Why instances of `to` may be rejected by `from`.
+    pub forward_errors: Vec<String>,
+}
+
+impl SchemaDiffDto {
+    /// Builds the DTO, evaluating `diff` under `mode`.
+    #[must_use]
+    pub fn new(diff: SchemaDiff, mode: CompatibilityMode) -> Self {
+        let breaking_changes = mode.breaking_changes(&diff);
+        let (backward_compatible, forward_compatible) =
+            (diff.is_backward_compatible(), diff.is_forward_compatible());
+        Self {
+            from: diff.from,
+            to: diff.to,
+            backward_compatible,
+            forward_compatible,
+            mode: mode.into(),
+            breaking_changes,
+            added_properties: diff.added_properties,
+            removed_properties: diff.removed_properties,
+            newly_required: diff.newly_required,
+            no_longer_required: diff.no_longer_required,
+            backward_errors: diff.backward_errors,
+            forward_errors: diff.forward_errors,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
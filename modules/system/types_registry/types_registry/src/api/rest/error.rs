@@ -36,6 +36,38 @@ impl From<DomainError> for Problem {
                 "Validation failed",
                 msg.clone(),
             ),
+            DomainError::IncompatibleSchema(msg) => (
+                StatusCode::CONFLICT,
+                "TYPES_REGISTRY_INCOMPATIBLE_SCHEMA",
+                "Incompatible schema version",
+                msg.clone(),
+            ),
+            DomainError::InvalidBundle(msg) => (
+                StatusCode::BAD_REQUEST,
+                "TYPES_REGISTRY_INVALID_BUNDLE",
+                "Invalid bundle",
+                msg.clone(),
+            ),
+            DomainError::InUse(msg) => (
+                StatusCode::CONFLICT,
+                "TYPES_REGISTRY_IN_USE",
+                "Entity in use",
+                msg.clone(),
+            ),
+            DomainError::Tombstoned(id) => (
+                StatusCode::CONFLICT,
+                "TYPES_REGISTRY_TOMBSTONED",
+                "Entity deleted",
+                format!(
+                    "GTS ID belongs to a deleted entity and can only be registered again with its previous content: {id}"
+                ),
+            ),
+            DomainError::CyclicReference(path) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "TYPES_REGISTRY_CYCLIC_REFERENCE",
+                "Cyclic schema reference",
+                format!("Schemas reference each other in a cycle: {path}"),
+            ),
             DomainError::NotInReadyMode => (
                 StatusCode::SERVICE_UNAVAILABLE,
                 "TYPES_REGISTRY_NOT_READY",
@@ -1,20 +1,32 @@
 //! REST handlers for the Types Registry module.
 
+use std::convert::Infallible;
 use std::sync::Arc;
+use std::time::Duration;
 
 use axum::Json;
+use axum::body::Bytes;
 use axum::extract::{Extension, Path, Query};
+use axum::http::HeaderMap;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use futures::StreamExt;
 use modkit::api::prelude::*;
 use modkit::api::problem::Problem;
 use types_registry_sdk::RegisterSummary;
 
 use super::dto::{
-    GtsEntityDto, ListEntitiesQuery, ListEntitiesResponse, RegisterEntitiesRequest,
-    RegisterEntitiesResponse, RegisterResultDto, RegisterSummaryDto,
+    EntityChangeDto, EntityGraphDto, EntityGraphQuery, GtsEntityDto, ImportBundleQuery,
+    ImportBundleResponse, ListEntitiesQuery, ListEntitiesResponse, RegisterEntitiesRequest,
+    RegisterEntitiesResponse, RegisterResultDto, RegisterSummaryDto, ResolvedSchemaDto,
+    SchemaDiffDto, SchemaDiffQuery,
 };
 use crate::domain::error::DomainError;
 use crate::domain::service::TypesRegistryService;
 
+/// Media type of exported bundles.
+pub const BUNDLE_CONTENT_TYPE: &str = "application/x-ndjson";
+
 /// POST /api/v1/types-registry/entities
 ///
 /// Register GTS entities in batch.
@@ -29,6 +41,7 @@ pub async fn register_entities(
     }
 
     let results = service.register_validated(req.entities);
+    let results = service.persist_results(results).await;
 
     let summary = RegisterSummary::from_results(&results);
     let result_dtos: Vec<RegisterResultDto> = results.into_iter().map(Into::into).collect();
@@ -44,31 +57,57 @@ pub async fn register_entities(
 /// GET /api/v1/types-registry/entities
 ///
 /// List GTS entities with optional filtering.
+/// Responses carry an `ETag`; a matching `If-None-Match` yields `304 Not Modified`.
 pub async fn list_entities(
     Extension(service): Extension<Arc<TypesRegistryService>>,
+    headers: HeaderMap,
     Query(query): Query<ListEntitiesQuery>,
-) -> ApiResult<Json<ListEntitiesResponse>> {
+) -> ApiResult<Response> {
     if !service.is_ready() {
         return Err(DomainError::NotInReadyMode.into());
     }
 
     let list_query = query.to_list_query();
 
-    let entities = service.list(&list_query).map_err(Problem::from)?;
+    let entities = service
+        .list_refreshed(&list_query)
+        .await
+        .map_err(Problem::from)?;
 
     let entity_dtos: Vec<GtsEntityDto> = entities.into_iter().map(Into::into).collect();
     let count = entity_dtos.len();
 
-    Ok(Json(ListEntitiesResponse {
-        entities: entity_dtos,
-        count,
-    }))
+    ok_json_with_etag(
+        &headers,
+        ListEntitiesResponse {
+            entities: entity_dtos,
+            count,
+        },
+    )
 }
 
 /// GET /api/v1/types-registry/entities/{gts_id}
 ///
 /// Get a single GTS entity by its identifier.
+/// Responses carry an `ETag`; a matching `If-None-Match` yields `304 Not Modified`.
 pub async fn get_entity(
+    Extension(service): Extension<Arc<TypesRegistryService>>,
+    headers: HeaderMap,
+    Path(gts_id): Path<String>,
+) -> ApiResult<Response> {
+    if !service.is_ready() {
+        return Err(DomainError::NotInReadyMode.into());
+    }
+
+    let entity = service.get_or_load(&gts_id).await.map_err(Problem::from)?;
+
+    ok_json_with_etag(&headers, GtsEntityDto::from(entity))
+}
+
+/// POST /api/v1/types-registry/entities/{gts_id}/deprecate
+///
+/// Mark a GTS entity as deprecated. Deprecating twice is a no-op.
+pub async fn deprecate_entity(
     Extension(service): Extension<Arc<TypesRegistryService>>,
     Path(gts_id): Path<String>,
 ) -> ApiResult<Json<GtsEntityDto>> {
@@ -76,14 +115,168 @@ pub async fn get_entity(
         return Err(DomainError::NotInReadyMode.into());
     }
 
-    let entity = service.get(&gts_id).map_err(Problem::from)?;
+    let entity = service.deprecate(&gts_id).await.map_err(Problem::from)?;
+    Ok(Json(GtsEntityDto::from(entity)))
+}
+
+/// DELETE /api/v1/types-registry/entities/{gts_id}
+///
+/// Delete a GTS entity that nothing references. Its GTS ID is tombstoned.
+pub async fn delete_entity(
+    Extension(service): Extension<Arc<TypesRegistryService>>,
+    Path(gts_id): Path<String>,
+) -> ApiResult<StatusCode> {
+    if !service.is_ready() {
+        return Err(DomainError::NotInReadyMode.into());
+    }
+
+    service.delete(&gts_id).await.map_err(Problem::from)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// GET /api/v1/types-registry/entities/{gts_id}/graph
+///
+/// Get the dependencies and/or dependents of a GTS entity.
+pub async fn entity_graph(
+    Extension(service): Extension<Arc<TypesRegistryService>>,
+    Path(gts_id): Path<String>,
+    Query(query): Query<EntityGraphQuery>,
+) -> ApiResult<Json<EntityGraphDto>> {
+    if !service.is_ready() {
+        return Err(DomainError::NotInReadyMode.into());
+    }
+
+    let direction = query.direction.map(Into::into).unwrap_or_default();
+    let graph = service
+        .graph(&gts_id, direction, query.depth)
+        .await
+        .map_err(Problem::from)?;
+    Ok(Json(EntityGraphDto::from(graph)))
+}
+
+/// GET /api/v1/types-registry/entities/{gts_id}/resolved
+///
+/// Get a type schema with every `$ref` to a registered schema inlined.
+pub async fn resolve_schema(
+    Extension(service): Extension<Arc<TypesRegistryService>>,
+    Path(gts_id): Path<String>,
+) -> ApiResult<Json<ResolvedSchemaDto>> {
+    if !service.is_ready() {
+        return Err(DomainError::NotInReadyMode.into());
+    }
+
+    let resolved = service
+        .resolve_schema(&gts_id)
+        .await
+        .map_err(Problem::from)?;
+    Ok(Json(ResolvedSchemaDto::from(resolved)))
+}
+
+/// GET /api/v1/types-registry/bundle
+///
+/// Export the entities matching the list filters as a bundle (JSON lines).
+pub async fn export_bundle(
+    Extension(service): Extension<Arc<TypesRegistryService>>,
+    Query(query): Query<ListEntitiesQuery>,
+) -> ApiResult<Response> {
+    if !service.is_ready() {
+        return Err(DomainError::NotInReadyMode.into());
+    }
+
+    let bundle = service
+        .export_bundle(&query.to_list_query())
+        .await
+        .map_err(Problem::from)?;
+    Ok((
+        [(axum::http::header::CONTENT_TYPE, BUNDLE_CONTENT_TYPE)],
+        bundle,
+    )
+        .into_response())
+}
 
-    Ok(Json(entity.into()))
+/// POST /api/v1/types-registry/bundle
+///
+/// Import a bundle, or only validate it with `dry_run=true`.
+pub async fn import_bundle(
+    Extension(service): Extension<Arc<TypesRegistryService>>,
+    Query(query): Query<ImportBundleQuery>,
+    body: Bytes,
+) -> ApiResult<Json<ImportBundleResponse>> {
+    if !service.is_ready() {
+        return Err(DomainError::NotInReadyMode.into());
+    }
+
+    let bundle = std::str::from_utf8(&body)
+        .map_err(|_| Problem::from(DomainError::invalid_bundle("bundle is not UTF-8")))?;
+    let results = service
+        .import_bundle(bundle, query.dry_run)
+        .await
+        .map_err(Problem::from)?;
+
+    let summary = RegisterSummary::from_results(&results);
+    Ok(Json(ImportBundleResponse {
+        dry_run: query.dry_run,
+        summary: RegisterSummaryDto::from(summary),
+        results: results.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// GET /api/v1/types-registry/changes
+///
+/// Stream changes of entities matching the list filters as Server-Sent Events.
+pub async fn watch_entities(
+    Extension(service): Extension<Arc<TypesRegistryService>>,
+    Query(query): Query<ListEntitiesQuery>,
+) -> ApiResult<Response> {
+    if !service.is_ready() {
+        return Err(DomainError::NotInReadyMode.into());
+    }
+
+    let events = service.watch(query.to_list_query()).map(|change| {
+        let event = Event::default()
+            .event("entity_change")
+            .json_data(EntityChangeDto::from(change))
+            .unwrap_or_else(|_| {
+                Event::default()
+                    .event("entity_change")
+                    .data("serialization_error")
+            });
+        Ok::<_, Infallible>(event)
+    });
+    Ok(Sse::new(events)
+        .keep_alive(
+            KeepAlive::new()
+                .interval(Duration::from_secs(15))
+                .text("keepalive"),
+        )
+        .into_response())
+}
+
+/// GET /api/v1/types-registry/schemas/diff
+///
+/// Explain the differences between two registered type schemas.
+pub async fn diff_schemas(
+    Extension(service): Extension<Arc<TypesRegistryService>>,
+    Query(query): Query<SchemaDiffQuery>,
+) -> ApiResult<Json<SchemaDiffDto>> {
+    if !service.is_ready() {
+        return Err(DomainError::NotInReadyMode.into());
+    }
+
+    for gts_id in [&query.from, &query.to] {
+        service.get_or_load(gts_id).await.map_err(Problem::from)?;
+    }
+    let diff = service
+        .diff(&query.from, &query.to)
+        .map_err(Problem::from)?;
+
+    Ok(Json(SchemaDiffDto::new(diff, service.compatibility_mode())))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::api::rest::dto::{GraphDirectionDto, GraphEdgeKindDto};
     use crate::infra::InMemoryGtsRepository;
     use gts::GtsConfig;
     use serde_json::json;
@@ -94,6 +287,13 @@ mod tests {
         crate::config::TypesRegistryConfig::default().to_gts_config()
     }
 
+    async fn json_body<T: serde::de::DeserializeOwned>(response: Response) -> T {
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
     fn create_service() -> Arc<TypesRegistryService> {
         let repo = Arc::new(InMemoryGtsRepository::new(default_config()));
         Arc::new(TypesRegistryService::new(
@@ -125,7 +325,7 @@ mod tests {
         // Service is not ready yet
 
         let query = ListEntitiesQuery::default();
-        let result = list_entities(Extension(service), Query(query)).await;
+        let result = list_entities(Extension(service), HeaderMap::new(), Query(query)).await;
         assert!(result.is_err());
     }
 
@@ -136,6 +336,7 @@ mod tests {
 
         let result = get_entity(
             Extension(service),
+            HeaderMap::new(),
             Path("gts.acme.core.events.user_created.v1~".to_owned()),
         )
         .await;
@@ -185,11 +386,11 @@ mod tests {
         service.switch_to_ready().unwrap();
 
         let query = ListEntitiesQuery::default();
-        let result = list_entities(Extension(service), Query(query)).await;
+        let result = list_entities(Extension(service), HeaderMap::new(), Query(query)).await;
         assert!(result.is_ok());
 
-        let Json(response) = result.unwrap();
-        assert_eq!(response.count, 2);
+        let response: serde_json::Value = json_body(result.unwrap()).await;
+        assert_eq!(response["count"], 2);
     }
 
     #[tokio::test]
@@ -206,15 +407,45 @@ mod tests {
 
         let result = get_entity(
             Extension(service),
+            HeaderMap::new(),
             Path("gts.acme.core.events.user_created.v1~".to_owned()),
         )
         .await;
         assert!(result.is_ok());
 
-        let Json(entity) = result.unwrap();
+        let entity: GtsEntityDto = json_body(result.unwrap()).await;
         assert_eq!(entity.gts_id, "gts.acme.core.events.user_created.v1~");
     }
 
+    #[tokio::test]
+    async fn test_get_entity_returns_304_for_matching_etag() {
+        let service = create_service();
+        let _ = service.register(vec![json!({
+            "$id": "gts://gts.acme.core.events.user_created.v1~",
+            "$schema": JSON_SCHEMA_DRAFT_07,
+            "type": "object"
+        })]);
+        service.switch_to_ready().unwrap();
+
+        let path = || Path("gts.acme.core.events.user_created.v1~".to_owned());
+        let first = get_entity(Extension(service.clone()), HeaderMap::new(), path())
+            .await
+            .unwrap();
+        assert_eq!(first.status(), StatusCode::OK);
+        let etag = first
+            .headers()
+            .get(axum::http::header::ETAG)
+            .unwrap()
+            .clone();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(axum::http::header::IF_NONE_MATCH, etag);
+        let second = get_entity(Extension(service), headers, path())
+            .await
+            .unwrap();
+        assert_eq!(second.status(), StatusCode::NOT_MODIFIED);
+    }
+
     #[tokio::test]
     async fn test_get_entity_not_found() {
         let service = create_service();
@@ -222,9 +453,218 @@ mod tests {
 
         let result = get_entity(
             Extension(service),
+            HeaderMap::new(),
             Path("gts.unknown.pkg.ns.type.v1~".to_owned()),
         )
         .await;
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_deprecate_and_delete_entity_handlers() {
+        let service = create_service();
+        let _ = service.register(vec![
+            json!({
+                "$id": "gts://gts.acme.core.events.user_created.v1~",
+                "$schema": JSON_SCHEMA_DRAFT_07,
+                "type": "object"
+            }),
+            json!({
+                "$id": "gts://gts.acme.core.events.order_placed.v1~",
+                "$schema": JSON_SCHEMA_DRAFT_07,
+                "type": "object",
+                "properties": {
+                    "user": { "$ref": "gts://gts.acme.core.events.user_created.v1~" }
+                }
+            }),
+        ]);
+        service.switch_to_ready().unwrap();
+
+        let path = |id: &str| Path(id.to_owned());
+        let Json(entity) = deprecate_entity(
+            Extension(service.clone()),
+            path("gts.acme.core.events.user_created.v1~"),
+        )
+        .await
+        .unwrap();
+        assert!(entity.deprecated);
+
+        // Still referenced by order_placed
+        let err = delete_entity(
+            Extension(service.clone()),
+            path("gts.acme.core.events.user_created.v1~"),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::CONFLICT);
+
+        let status = delete_entity(
+            Extension(service.clone()),
+            path("gts.acme.core.events.order_placed.v1~"),
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, StatusCode::NO_CONTENT);
+
+        let err = delete_entity(
+            Extension(service),
+            path("gts.acme.core.events.order_placed.v1~"),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_export_and_import_bundle_handlers() {
+        let source = create_service();
+        let _ = source.register(vec![json!({
+            "$id": "gts://gts.acme.core.events.user_created.v1~",
+            "$schema": JSON_SCHEMA_DRAFT_07,
+            "type": "object"
+        })]);
+        source.switch_to_ready().unwrap();
+
+        let response = export_bundle(Extension(source), Query(ListEntitiesQuery::default()))
+            .await
+            .unwrap();
+        assert_eq!(
+            response.headers()[axum::http::header::CONTENT_TYPE],
+            BUNDLE_CONTENT_TYPE
+        );
+        let bundle = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+
+        let target = create_service();
+        target.switch_to_ready().unwrap();
+        let Json(dry_run) = import_bundle(
+            Extension(target.clone()),
+            Query(ImportBundleQuery { dry_run: true }),
+            bundle.clone(),
+        )
+        .await
+        .unwrap();
+        assert!(dry_run.dry_run);
+        assert_eq!(dry_run.summary.succeeded, 1);
+        assert!(target.get("gts.acme.core.events.user_created.v1~").is_err());
+
+        let Json(imported) = import_bundle(
+            Extension(target.clone()),
+            Query(ImportBundleQuery::default()),
+            bundle,
+        )
+        .await
+        .unwrap();
+        assert_eq!(imported.summary.succeeded, 1);
+        assert!(target.get("gts.acme.core.events.user_created.v1~").is_ok());
+
+        let err = import_bundle(
+            Extension(target),
+            Query(ImportBundleQuery::default()),
+            Bytes::from_static(b"not a bundle"),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_diff_schemas_handler() {
+        let service = create_service();
+        let _ = service.register(vec![json!({
+            "$id": "gts://gts.acme.core.events.user_created.v1.0~",
+            "$schema": JSON_SCHEMA_DRAFT_07,
+            "type": "object",
+            "properties": { "userId": { "type": "string" } }
+        })]);
+        service.switch_to_ready().unwrap();
+        let results = service.register_validated(vec![json!({
+            "$id": "gts://gts.acme.core.events.user_created.v1.1~",
+            "$schema": JSON_SCHEMA_DRAFT_07,
+            "type": "object",
+            "properties": {
+                "userId": { "type": "string" },
+                "source": { "type": "string" }
+            }
+        })]);
+        assert!(results[0].is_ok());
+
+        let query = SchemaDiffQuery {
+            from: "gts.acme.core.events.user_created.v1.0~".to_owned(),
+            to: "gts.acme.core.events.user_created.v1.1~".to_owned(),
+        };
+        let Json(diff) = diff_schemas(Extension(service.clone()), Query(query))
+            .await
+            .unwrap();
+        assert_eq!(diff.added_properties, vec!["source"]);
+        assert!(diff.backward_compatible);
+        assert!(diff.breaking_changes.is_empty());
+
+        let query = SchemaDiffQuery {
+            from: "gts.acme.core.events.user_created.v1.0~".to_owned(),
+            to: "gts.acme.core.events.user_created.v1.2~".to_owned(),
+        };
+        assert!(
+            diff_schemas(Extension(service), Query(query))
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_entity_graph_and_resolve_schema_handlers() {
+        let service = create_service();
+        let _ = service.register(vec![
+            json!({
+                "$id": "gts://gts.acme.core.models.address.v1~",
+                "$schema": JSON_SCHEMA_DRAFT_07,
+                "type": "object",
+                "properties": { "street": { "type": "string" } }
+            }),
+            json!({
+                "$id": "gts://gts.acme.core.models.user.v1~",
+                "$schema": JSON_SCHEMA_DRAFT_07,
+                "type": "object",
+                "properties": { "home": { "$ref": "gts://gts.acme.core.models.address.v1~" } }
+            }),
+        ]);
+        service.switch_to_ready().unwrap();
+
+        let query = EntityGraphQuery {
+            direction: Some(GraphDirectionDto::Dependents),
+            depth: None,
+        };
+        let Json(graph) = entity_graph(
+            Extension(service.clone()),
+            Path("gts.acme.core.models.address.v1~".to_owned()),
+            Query(query),
+        )
+        .await
+        .unwrap();
+        assert_eq!(graph.nodes.len(), 2);
+        assert_eq!(graph.edges[0].from, "gts.acme.core.models.user.v1~");
+        assert_eq!(graph.edges[0].kind, GraphEdgeKindDto::Ref);
+
+        let Json(resolved) = resolve_schema(
+            Extension(service.clone()),
+            Path("gts.acme.core.models.user.v1~".to_owned()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            resolved.schema["properties"]["home"]["properties"]["street"]["type"],
+            "string"
+        );
+        assert_eq!(resolved.references, ["gts.acme.core.models.address.v1~"]);
+
+        assert!(
+            resolve_schema(
+                Extension(service),
+                Path("gts.acme.core.models.user.v1~acme.core.users.alice.v1".to_owned()),
+            )
+            .await
+            .is_err()
+        );
+    }
 }
@@ -10,7 +10,8 @@ use modkit::api::operation_builder::{
 use modkit::api::prelude::StatusCode;
 
 use super::dto::{
-    GtsEntityDto, ListEntitiesResponse, RegisterEntitiesRequest, RegisterEntitiesResponse,
+    EntityChangeDto, EntityGraphDto, GtsEntityDto, ImportBundleResponse, ListEntitiesResponse,
+    RegisterEntitiesRequest, RegisterEntitiesResponse, ResolvedSchemaDto, SchemaDiffDto,
 };
 use super::handlers;
 use crate::domain::service::TypesRegistryService;
@@ -106,6 +107,7 @@ pub fn register_routes(
             StatusCode::OK,
             "List of entities",
         )
+        .with_if_none_match()
         .standard_errors(openapi)
         .register(router, openapi);
 
@@ -123,9 +125,155 @@ pub fn register_routes(
         )
         .handler(handlers::get_entity)
         .json_response_with_schema::<GtsEntityDto>(openapi, StatusCode::OK, "The requested entity")
+        .with_if_none_match()
         .problem_response(openapi, StatusCode::NOT_FOUND, "Entity not found")
         .standard_errors(openapi)
         .register(router, openapi);
 
+    // POST /types-registry/v1/entities/{gts_id}/deprecate - Deprecate GTS entity
+    router = OperationBuilder::post("/types-registry/v1/entities/{gts_id}/deprecate")
+        .operation_id("types_registry.deprecate")
+        .summary("Deprecate GTS entity")
+        .description("Mark a GTS entity as deprecated. It stays resolvable and is flagged in get and list results.")
+        .tag(TAG)
+        .require_auth(&Resource::TypesRegistry, &Action::Write)
+        .require_license_features::<License>([])
+        .path_param("gts_id", "The GTS identifier")
+        .handler(handlers::deprecate_entity)
+        .json_response_with_schema::<GtsEntityDto>(openapi, StatusCode::OK, "The deprecated entity")
+        .problem_response(openapi, StatusCode::NOT_FOUND, "Entity not found")
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    // DELETE /types-registry/v1/entities/{gts_id} - Delete GTS entity
+    router = OperationBuilder::delete("/types-registry/v1/entities/{gts_id}")
+        .operation_id("types_registry.delete")
+        .summary("Delete GTS entity")
+        .description("Delete a GTS entity. Refused while instances or other schemas derive from or `$ref` it. The GTS ID is tombstoned: it can only be registered again with the same content.")
+        .tag(TAG)
+        .require_auth(&Resource::TypesRegistry, &Action::Write)
+        .require_license_features::<License>([])
+        .path_param("gts_id", "The GTS identifier")
+        .handler(handlers::delete_entity)
+        .json_response(StatusCode::NO_CONTENT, "Entity deleted")
+        .problem_response(openapi, StatusCode::NOT_FOUND, "Entity not found")
+        .problem_response(openapi, StatusCode::CONFLICT, "Entity in use")
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    // GET /types-registry/v1/entities/{gts_id}/graph - Dependency graph of GTS entity
+    router = OperationBuilder::get("/types-registry/v1/entities/{gts_id}/graph")
+        .operation_id("types_registry.entity_graph")
+        .summary("Get dependency graph of GTS entity")
+        .description(
+            "Get the entities a GTS entity depends on (the type its chained ID extends, an instance's type, schemas it `$ref`s) and/or the entities that depend on it. Referenced entities that are not registered are included with `registered: false`.",
+        )
+        .tag(TAG)
+        .require_auth(&Resource::TypesRegistry, &Action::Read)
+        .require_license_features::<License>([])
+        .path_param("gts_id", "The GTS identifier")
+        .query_param("direction", false, "'dependencies', 'dependents' or 'both' (default)")
+        .query_param("depth", false, "Maximum number of edges from the entity (default: unlimited)")
+        .handler(handlers::entity_graph)
+        .json_response_with_schema::<EntityGraphDto>(openapi, StatusCode::OK, "Dependency graph")
+        .problem_response(openapi, StatusCode::NOT_FOUND, "Entity not found")
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    // GET /types-registry/v1/entities/{gts_id}/resolved - Self-contained type schema
+    router = OperationBuilder::get("/types-registry/v1/entities/{gts_id}/resolved")
+        .operation_id("types_registry.resolve_schema")
+        .summary("Get resolved type schema")
+        .description(
+            "Get a type schema with every `$ref` to a registered GTS schema replaced by that schema, recursively, so it can be used without access to the registry. `$ref`s to unregistered schemas are left in place and listed as unresolved.",
+        )
+        .tag(TAG)
+        .require_auth(&Resource::TypesRegistry, &Action::Read)
+        .require_license_features::<License>([])
+        .path_param("gts_id", "The GTS identifier of a type schema")
+        .handler(handlers::resolve_schema)
+        .json_response_with_schema::<ResolvedSchemaDto>(openapi, StatusCode::OK, "Resolved schema")
+        .problem_response(openapi, StatusCode::NOT_FOUND, "Schema not found")
+        .problem_response(openapi, StatusCode::UNPROCESSABLE_ENTITY, "Cyclic schema reference")
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    // GET /types-registry/v1/changes - Watch GTS entity changes
+    router = OperationBuilder::get("/types-registry/v1/changes")
+        .operation_id("types_registry.watch")
+        .summary("Watch GTS entity changes (SSE)")
+        .description(
+            "Stream entities added, updated or removed after the request as Server-Sent Events named `entity_change`. Accepts the filters of the list endpoint. Slow clients skip the oldest changes.",
+        )
+        .tag(TAG)
+        .require_auth(&Resource::TypesRegistry, &Action::Read)
+        .require_license_features::<License>([])
+        .query_param("pattern", false, "Wildcard pattern for GTS ID matching (e.g., gts.acme.*)")
+        .query_param("kind", false, "Filter by entity kind: 'type' or 'instance'")
+        .query_param("vendor", false, "Filter by vendor")
+        .query_param("package", false, "Filter by package")
+        .query_param("namespace", false, "Filter by namespace")
+        .query_param("segmentScope", false, "Segment match scope: 'primary' or 'any' (default)")
+        .handler(handlers::watch_entities)
+        .sse_json::<EntityChangeDto>(openapi, "SSE stream of entity changes")
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    // GET /types-registry/v1/bundle - Export GTS entities as a bundle
+    router = OperationBuilder::get("/types-registry/v1/bundle")
+        .operation_id("types_registry.export_bundle")
+        .summary("Export GTS entities as a bundle")
+        .description(
+            "Export the entities matching the list filters as a versioned JSON lines bundle with per-entity checksums, signed if the registry has a bundle signing key. Schemas come first, in import order.",
+        )
+        .tag(TAG)
+        .require_auth(&Resource::TypesRegistry, &Action::Read)
+        .require_license_features::<License>([])
+        .query_param("pattern", false, "Wildcard pattern for GTS ID matching (e.g., gts.acme.*)")
+        .query_param("is_schema", false, "true for types, false for instances")
+        .query_param("vendor", false, "Filter by vendor")
+        .query_param("package", false, "Filter by package")
+        .query_param("namespace", false, "Filter by namespace")
+        .handler(handlers::export_bundle)
+        .text_response(StatusCode::OK, "Bundle", handlers::BUNDLE_CONTENT_TYPE)
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    // POST /types-registry/v1/bundle - Import a bundle
+    router = OperationBuilder::post("/types-registry/v1/bundle")
+        .operation_id("types_registry.import_bundle")
+        .summary("Import a bundle")
+        .description(
+            "Register the entities of a bundle with full validation, in bundle order, and deprecate those flagged as deprecated. The bundle's checksums, and its signature if the registry has a bundle signing key, are verified first. With `dry_run=true` the per-entity results are reported without applying anything.",
+        )
+        .tag(TAG)
+        .require_auth(&Resource::TypesRegistry, &Action::Write)
+        .require_license_features::<License>([])
+        .query_param("dry_run", false, "Validate without applying")
+        .octet_stream_request(Some("Bundle exported by GET /types-registry/v1/bundle"))
+        .handler(handlers::import_bundle)
+        .json_response_with_schema::<ImportBundleResponse>(openapi, StatusCode::OK, "Per-entity import results")
+        .problem_response(openapi, StatusCode::BAD_REQUEST, "Invalid bundle")
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    // GET /types-registry/v1/schemas/diff - Diff two schema versions
+    router = OperationBuilder::get("/types-registry/v1/schemas/diff")
+        .operation_id("types_registry.diff_schemas")
+        .summary("Diff two type schemas")
+        .description(
+            "Explain the changes between two registered type schemas, e.g. two minor versions of a type: added, removed and newly required properties, and the changes that break backward or forward compatibility.",
+        )
+        .tag(TAG)
+        .require_auth(&Resource::TypesRegistry, &Action::Read)
+        .require_license_features::<License>([])
+        .query_param("from", true, "GTS ID of the earlier schema version")
+        .query_param("to", true, "GTS ID of the later schema version")
+        .handler(handlers::diff_schemas)
+        .json_response_with_schema::<SchemaDiffDto>(openapi, StatusCode::OK, "Schema differences")
+        .problem_response(openapi, StatusCode::NOT_FOUND, "Schema not found")
+        .standard_errors(openapi)
+        .register(router, openapi);
+
     router.layer(Extension(service))
 }
@@ -1,7 +1,23 @@
 //! Configuration for the Types Registry module.
 
+use std::time::Duration;
+
 use serde::Deserialize;
 
+use crate::domain::compatibility::CompatibilityMode;
+
+/// Where registered GTS entities are kept.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageBackend {
+    /// In process memory only; entities are lost on restart.
+    #[default]
+    Memory,
+    /// The module database, with the in-memory registry as a read-through cache.
+    /// Requires a `database` section for the module.
+    Database,
+}
+
 /// Configuration for the Types Registry module.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(deny_unknown_fields, default)]
@@ -13,6 +29,30 @@ pub struct TypesRegistryConfig {
     /// Fields to check for schema ID reference (in order of priority).
     /// Default: `["$schema", "gtsTid", "type"]`
     pub schema_id_fields: Vec<String>,
+
+    /// Storage backend for registered entities.
+    /// Default: `memory`
+    pub storage: StorageBackend,
+
+    /// Registry the persisted entities belong to. Deployments sharing a
+    /// database only see entities of the same registry.
+    /// Default: nil UUID
+    pub registry_id: uuid::Uuid,
+
+    /// Minimum interval between reloads of entities registered by other
+    /// replicas when listing (database storage only).
+    /// Default: `30`
+    pub refresh_interval_secs: u64,
+
+    /// Compatibility required between minor versions of a type schema
+    /// registered in ready mode: `none`, `backward`, `forward` or `full`.
+    /// Default: `backward`
+    pub compatibility: CompatibilityMode,
+
+    /// Shared secret for signing exported bundles (HMAC-SHA256). When set,
+    /// imported bundles must be signed with it.
+    /// Default: none
+    pub bundle_signing_key: Option<String>,
 }
 
 impl Default for TypesRegistryConfig {
@@ -20,6 +60,11 @@ impl Default for TypesRegistryConfig {
         Self {
             entity_id_fields: vec!["$id".to_owned(), "gtsId".to_owned(), "id".to_owned()],
             schema_id_fields: vec!["$schema".to_owned(), "gtsTid".to_owned(), "type".to_owned()],
+            storage: StorageBackend::Memory,
+            registry_id: uuid::Uuid::nil(),
+            refresh_interval_secs: 30,
+            compatibility: CompatibilityMode::Backward,
+            bundle_signing_key: None,
         }
     }
 }
@@ -33,6 +78,12 @@ impl TypesRegistryConfig {
             schema_id_fields: self.schema_id_fields.clone(),
         }
     }
+
+    /// Returns the refresh interval for database storage.
+    #[must_use]
+    pub fn refresh_interval(&self) -> Duration {
+        Duration::from_secs(self.refresh_interval_secs)
+    }
 }
 
 #[cfg(test)]
@@ -44,6 +95,8 @@ mod tests {
         let cfg = TypesRegistryConfig::default();
         assert_eq!(cfg.entity_id_fields, vec!["$id", "gtsId", "id"]);
         assert_eq!(cfg.schema_id_fields, vec!["$schema", "gtsTid", "type"]);
+        assert_eq!(cfg.storage, StorageBackend::Memory);
+        assert_eq!(cfg.compatibility, CompatibilityMode::Backward);
     }
 
     #[test]
@@ -0,0 +1,344 @@
+//! Bundles of GTS entities for moving a registry between environments.
+//!
+//! A bundle is a JSON lines document:
+//! 1. a header naming the format and its version,
+//! 2. one line per entity, carrying the SHA-256 checksum of its content,
+//! 3. a trailer with a digest over the header line and the entity checksums,
+//!    optionally signed with HMAC-SHA256.
+//!
+//! Schemas come before instances, and base schemas before derived ones, so a
+//! bundle imports in order.
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use types_registry_sdk::GtsEntity;
+
+use super::error::DomainError;
+
+/// Format name in the bundle header.
+pub const BUNDLE_FORMAT: &str = "gts-bundle";
+
+/// Latest bundle format version; bundles of later versions are rejected.
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// First line of a bundle.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleHeader {
+    pub format: String,
+    pub version: u32,
+    pub created_at: DateTime<Utc>,
+    pub entity_count: usize,
+}
+
+/// A GTS entity in a bundle.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleEntry {
+    pub gts_id: String,
+    #[serde(default)]
+    pub deprecated: bool,
+    /// Hex SHA-256 of the serialized content.
+    pub sha256: String,
+    pub content: serde_json::Value,
+}
+
+/// Last line of a bundle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BundleTrailer {
+    /// Hex SHA-256 over the header line and the entity checksums.
+    digest: String,
+    /// Hex HMAC-SHA256 of the digest.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    signature: Option<String>,
+}
+
+/// A decoded bundle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bundle {
+    pub header: BundleHeader,
+    pub entries: Vec<BundleEntry>,
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+fn content_checksum(content: &serde_json::Value) -> Result<String, DomainError> {
+    let bytes = serde_json::to_vec(content).map_err(|e| DomainError::Internal(e.into()))?;
+    Ok(sha256_hex(&bytes))
+}
+
+fn digest<'a>(header_line: &str, checksums: impl Iterator<Item = &'a str>) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(header_line.as_bytes());
+    for checksum in checksums {
+        hasher.update(b"\n");
+        hasher.update(checksum.as_bytes());
+    }
+    hex::encode(hasher.finalize())
+}
+
+fn mac(signing_key: &str, digest: &str) -> Result<Hmac<Sha256>, DomainError> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(signing_key.as_bytes())
+        .map_err(|e| DomainError::Internal(anyhow::anyhow!("bundle signing key: {e}")))?;
+    mac.update(digest.as_bytes());
+    Ok(mac)
+}
+
+fn to_line<T: Serialize>(value: &T) -> Result<String, DomainError> {
+    serde_json::to_string(value).map_err(|e| DomainError::Internal(e.into()))
+}
+
+fn parse_line<T: for<'de> Deserialize<'de>>(
+    line_no: usize,
+    line: &str,
+    what: &str,
+) -> Result<T, DomainError> {
+    serde_json::from_str(line)
+        .map_err(|e| DomainError::invalid_bundle(format!("line {line_no}: invalid {what}: {e}")))
+}
+
+impl Bundle {
+    /// Creates a bundle of `entities`, ordered for import.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Internal` if an entity cannot be serialized.
+    pub fn new(entities: Vec<GtsEntity>) -> Result<Self, DomainError> {
+        let mut entities = entities;
+        // A derived schema's ID extends its base's, so it sorts after it
+        entities.sort_by(|a, b| {
+            b.is_schema
+                .cmp(&a.is_schema)
+                .then_with(|| a.gts_id.cmp(&b.gts_id))
+        });
+
+        let entries = entities
+            .into_iter()
+            .map(|entity| {
+                let sha256 = content_checksum(&entity.content)?;
+                Ok(BundleEntry {
+                    gts_id: entity.gts_id,
+                    deprecated: entity.deprecated,
+                    sha256,
+                    content: entity.content,
+                })
+            })
+            .collect::<Result<Vec<_>, DomainError>>()?;
+
+        Ok(Self {
+            header: BundleHeader {
+                format: BUNDLE_FORMAT.to_owned(),
+                version: BUNDLE_VERSION,
+                created_at: Utc::now(),
+                entity_count: entries.len(),
+            },
+            entries,
+        })
+    }
+
+    /// Serializes the bundle, signing it if a key is given.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Internal` if the bundle cannot be serialized.
+    pub fn encode(&self, signing_key: Option<&str>) -> Result<String, DomainError> {
+        let header_line = to_line(&self.header)?;
+        let digest = digest(
+            &header_line,
+            self.entries.iter().map(|entry| entry.sha256.as_str()),
+        );
+        let signature = signing_key
+            .map(|key| mac(key, &digest).map(|mac| hex::encode(mac.finalize().into_bytes())))
+            .transpose()?;
+
+        let mut out = header_line;
+        out.push('\n');
+        for entry in &self.entries {
+            out.push_str(&to_line(entry)?);
+            out.push('\n');
+        }
+        out.push_str(&to_line(&BundleTrailer { digest, signature })?);
+        out.push('\n');
+        Ok(out)
+    }
+
+    /// Parses a bundle and verifies its checksums, and its signature if a
+    /// key is given. Unsigned bundles are rejected when a key is given.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidBundle` describing the first problem found.
+    pub fn decode(text: &str, signing_key: Option<&str>) -> Result<Self, DomainError> {
+        let lines: Vec<(usize, &str)> = text
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty())
+            .collect();
+        let [
+            (header_no, header_line),
+            body @ ..,
+            (trailer_no, trailer_line),
+        ] = lines.as_slice()
+        else {
+            return Err(DomainError::invalid_bundle(
+                "expected a header and a trailer line",
+            ));
+        };
+
+        let header: BundleHeader = parse_line(*header_no, header_line, "header")?;
+        if header.format != BUNDLE_FORMAT {
+            return Err(DomainError::invalid_bundle(format!(
+                "unknown format {:?}",
+                header.format
+            )));
+        }
+        if header.version == 0 || header.version > BUNDLE_VERSION {
+            return Err(DomainError::invalid_bundle(format!(
+                "unsupported version {} (latest supported: {BUNDLE_VERSION})",
+                header.version
+            )));
+        }
+        if header.entity_count != body.len() {
+            return Err(DomainError::invalid_bundle(format!(
+                "header announces {} entities, found {}",
+                header.entity_count,
+                body.len()
+            )));
+        }
+
+        let mut entries = Vec::with_capacity(body.len());
+        for (line_no, line) in body {
+            let entry: BundleEntry = parse_line(*line_no, line, "entity")?;
+            if content_checksum(&entry.content)? != entry.sha256 {
+                return Err(DomainError::invalid_bundle(format!(
+                    "line {line_no}: checksum mismatch for {}",
+                    entry.gts_id
+                )));
+            }
+            entries.push(entry);
+        }
+
+        let trailer: BundleTrailer = parse_line(*trailer_no, trailer_line, "trailer")?;
+        let digest = digest(
+            header_line,
+            entries.iter().map(|entry| entry.sha256.as_str()),
+        );
+        if trailer.digest != digest {
+            return Err(DomainError::invalid_bundle(
+                "digest mismatch: entities were added, removed or reordered",
+            ));
+        }
+        if let Some(key) = signing_key {
+            let signature = trailer
+                .signature
+                .as_deref()
+                .ok_or_else(|| DomainError::invalid_bundle("bundle is not signed"))?;
+            let signature = hex::decode(signature)
+                .map_err(|_| DomainError::invalid_bundle("malformed signature"))?;
+            mac(key, &digest)?
+                .verify_slice(&signature)
+                .map_err(|_| DomainError::invalid_bundle("signature mismatch"))?;
+        }
+
+        Ok(Self { header, entries })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    fn entity(gts_id: &str, is_schema: bool) -> GtsEntity {
+        GtsEntity::new(
+            Uuid::nil(),
+            gts_id.to_owned(),
+            vec![],
+            is_schema,
+            json!({ "$id": format!("gts://{gts_id}"), "type": "object" }),
+            None,
+        )
+    }
+
+    fn sample() -> Bundle {
+        Bundle::new(vec![
+            entity(
+                "gts.acme.core.models.user.v1~acme.core.instances.alice.v1",
+                false,
+            ),
+            entity(
+                "gts.acme.core.models.user.v1~acme.core.models.admin.v1~",
+                true,
+            ),
+            entity("gts.acme.core.models.user.v1~", true).with_deprecated(true),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_entries_are_ordered_for_import() {
+        let bundle = sample();
+        let ids: Vec<&str> = bundle.entries.iter().map(|e| e.gts_id.as_str()).collect();
+        assert_eq!(
+            ids,
+            [
+                "gts.acme.core.models.user.v1~",
+                "gts.acme.core.models.user.v1~acme.core.models.admin.v1~",
+                "gts.acme.core.models.user.v1~acme.core.instances.alice.v1",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_round_trip_with_and_without_signature() {
+        let bundle = sample();
+
+        let unsigned = bundle.encode(None).unwrap();
+        assert_eq!(Bundle::decode(&unsigned, None).unwrap(), bundle);
+        assert!(Bundle::decode(&unsigned, Some("secret")).is_err());
+
+        let signed = bundle.encode(Some("secret")).unwrap();
+        let decoded = Bundle::decode(&signed, Some("secret")).unwrap();
+        assert!(decoded.entries[0].deprecated);
+        assert!(Bundle::decode(&signed, Some("other")).is_err());
+        // Verification is skipped without a key
+        assert!(Bundle::decode(&signed, None).is_ok());
+    }
+
+    #[test]
+    fn test_tampering_is_detected() {
+        let text = sample().encode(Some("secret")).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+
+        // Changed content
+        let tampered = text.replace("\"type\":\"object\"", "\"type\":\"string\"");
+        assert!(matches!(
+            Bundle::decode(&tampered, None),
+            Err(DomainError::InvalidBundle(msg)) if msg.contains("checksum")
+        ));
+
+        // Dropped entity, with the count fixed up
+        let dropped = [lines[0].replace("\"entity_count\":3", "\"entity_count\":2")]
+            .into_iter()
+            .chain([lines[1], lines[2], lines[4]].map(str::to_owned))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert!(matches!(
+            Bundle::decode(&dropped, None),
+            Err(DomainError::InvalidBundle(msg)) if msg.contains("digest")
+        ));
+
+        // Newer format version
+        let newer = text.replace("\"version\":1", "\"version\":2");
+        assert!(matches!(
+            Bundle::decode(&newer, None),
+            Err(DomainError::InvalidBundle(msg)) if msg.contains("unsupported version")
+        ));
+
+        assert!(Bundle::decode("", None).is_err());
+    }
+}
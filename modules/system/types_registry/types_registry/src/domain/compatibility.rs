@@ -0,0 +1,231 @@
+//! Schema evolution rules for minor versions of GTS types.
+//!
+//! Minor versions of a type (`gts.x.pkg.ns.type.v1.0~`, `...v1.1~`) form a
+//! lineage. A new minor version is checked against its neighbours in the
+//! lineage, using the same backward/forward semantics as schema registries.
+
+use std::collections::BTreeSet;
+
+use gts::{GtsEntityCastResult, GtsID};
+use serde::Deserialize;
+
+/// Compatibility required between consecutive minor versions of a type.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompatibilityMode {
+    /// Any change is accepted.
+    None,
+    /// The new version accepts everything the previous one did, so existing
+    /// instances stay valid (no new required properties, no narrowed types).
+    #[default]
+    Backward,
+    /// The previous version accepts everything the new one does.
+    Forward,
+    /// Both backward and forward.
+    Full,
+}
+
+impl CompatibilityMode {
+    /// Changes in `diff` that violate this mode.
+    #[must_use]
+    pub fn breaking_changes(self, diff: &SchemaDiff) -> Vec<String> {
+        match self {
+            Self::None => Vec::new(),
+            Self::Backward => diff.backward_errors.clone(),
+            Self::Forward => diff.forward_errors.clone(),
+            Self::Full => diff
+                .backward_errors
+                .iter()
+                .chain(&diff.forward_errors)
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Whether instances of earlier versions must validate against a new one.
+    #[must_use]
+    pub fn revalidates_instances(self) -> bool {
+        matches!(self, Self::Backward | Self::Full)
+    }
+}
+
+/// Position of a type schema among the minor versions of its lineage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaVersion {
+    /// The GTS ID without the minor version of the last segment.
+    pub lineage: String,
+    pub major: u32,
+    /// A missing minor version counts as `0`.
+    pub minor: u32,
+}
+
+impl SchemaVersion {
+    /// Parses the version of a type schema ID; `None` for instances and invalid IDs.
+    #[must_use]
+    pub fn parse(gts_id: &str) -> Option<Self> {
+        if !gts_id.ends_with('~') {
+            return None;
+        }
+        let parsed = GtsID::new(gts_id).ok()?;
+        let last = parsed.gts_id_segments.last()?;
+        let prefix = parsed.id.get(..last.offset)?;
+        Some(Self {
+            lineage: format!(
+                "{prefix}{}.{}.{}.{}.v{}",
+                last.vendor, last.package, last.namespace, last.type_name, last.ver_major
+            ),
+            major: last.ver_major,
+            minor: last.ver_minor.unwrap_or(0),
+        })
+    }
+
+    /// Whether `other` is a different minor version of the same major version.
+    #[must_use]
+    pub fn is_sibling_of(&self, other: &Self) -> bool {
+        self.lineage == other.lineage && self.minor != other.minor
+    }
+}
+
+/// Differences between two versions of a type schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaDiff {
+    pub from: String,
+    pub to: String,
+    pub added_properties: Vec<String>,
+    pub removed_properties: Vec<String>,
+    /// Properties required by `to` but not by `from`.
+    pub newly_required: Vec<String>,
+    /// Properties required by `from` but not by `to`.
+    pub no_longer_required: Vec<String>,
+    /// Why instances of `from` may be rejected by `to`.
+    pub backward_errors: Vec<String>,
+    /// Why instances of `to` may be rejected by `from`.
+    pub forward_errors: Vec<String>,
+}
+
+impl SchemaDiff {
+    #[must_use]
+    pub fn is_backward_compatible(&self) -> bool {
+        self.backward_errors.is_empty()
+    }
+
+    #[must_use]
+    pub fn is_forward_compatible(&self) -> bool {
+        self.forward_errors.is_empty()
+    }
+}
+
+fn property_names(flat: &serde_json::Value) -> BTreeSet<String> {
+    flat.get("properties")
+        .and_then(serde_json::Value::as_object)
+        .map(|props| props.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+fn required_names(flat: &serde_json::Value) -> BTreeSet<String> {
+    flat.get("required")
+        .and_then(serde_json::Value::as_array)
+        .map(|req| {
+            req.iter()
+                .filter_map(|v| v.as_str().map(ToOwned::to_owned))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn difference(a: &BTreeSet<String>, b: &BTreeSet<String>) -> Vec<String> {
+    a.difference(b).cloned().collect()
+}
+
+/// Compares two schema documents. Property lists cover the top level, after
+/// merging `allOf`; the compatibility errors also cover nested objects.
+#[must_use]
+pub fn diff_schemas(
+    from_id: &str,
+    from: &serde_json::Value,
+    to_id: &str,
+    to: &serde_json::Value,
+) -> SchemaDiff {
+    let from_flat = GtsEntityCastResult::flatten_schema(from);
+    let to_flat = GtsEntityCastResult::flatten_schema(to);
+    let (from_props, to_props) = (property_names(&from_flat), property_names(&to_flat));
+    let (from_required, to_required) = (required_names(&from_flat), required_names(&to_flat));
+
+    let (_, backward_errors) = GtsEntityCastResult::check_backward_compatibility(from, to);
+    let (_, forward_errors) = GtsEntityCastResult::check_forward_compatibility(from, to);
+
+    SchemaDiff {
+        from: from_id.to_owned(),
+        to: to_id.to_owned(),
+        added_properties: difference(&to_props, &from_props),
+        removed_properties: difference(&from_props, &to_props),
+        newly_required: difference(&to_required, &from_required),
+        no_longer_required: difference(&from_required, &to_required),
+        backward_errors,
+        forward_errors,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_version() {
+        let v = SchemaVersion::parse("gts.acme.core.models.user.v1.2~").unwrap();
+        assert_eq!(v.lineage, "gts.acme.core.models.user.v1");
+        assert_eq!((v.major, v.minor), (1, 2));
+
+        let v =
+            SchemaVersion::parse("gts.x.core.modkit.plugin.v1~acme.app.plugins.auth.v2~").unwrap();
+        assert_eq!(
+            v.lineage,
+            "gts.x.core.modkit.plugin.v1~acme.app.plugins.auth.v2"
+        );
+        assert_eq!((v.major, v.minor), (2, 0));
+
+        assert!(
+            SchemaVersion::parse("gts.acme.core.models.user.v1~acme.core.users.alice.v1").is_none()
+        );
+    }
+
+    #[test]
+    fn test_siblings_share_major_version() {
+        let v10 = SchemaVersion::parse("gts.acme.core.models.user.v1.0~").unwrap();
+        let v11 = SchemaVersion::parse("gts.acme.core.models.user.v1.1~").unwrap();
+        let v20 = SchemaVersion::parse("gts.acme.core.models.user.v2.0~").unwrap();
+        assert!(v10.is_sibling_of(&v11));
+        assert!(!v10.is_sibling_of(&v10));
+        assert!(!v11.is_sibling_of(&v20));
+    }
+
+    #[test]
+    fn test_diff_reports_property_changes() {
+        let from = json!({
+            "type": "object",
+            "properties": { "a": { "type": "string" }, "b": { "type": "string" } },
+            "required": ["a"]
+        });
+        let to = json!({
+            "type": "object",
+            "properties": { "a": { "type": "string" }, "c": { "type": "integer" } },
+            "required": ["a", "c"]
+        });
+
+        let diff = diff_schemas("from~", &from, "to~", &to);
+        assert_eq!(diff.added_properties, vec!["c"]);
+        assert_eq!(diff.removed_properties, vec!["b"]);
+        assert_eq!(diff.newly_required, vec!["c"]);
+        assert!(diff.no_longer_required.is_empty());
+        assert!(!diff.is_backward_compatible());
+        assert!(diff.is_forward_compatible());
+        assert!(
+            CompatibilityMode::Forward
+                .breaking_changes(&diff)
+                .is_empty()
+        );
+        assert_eq!(CompatibilityMode::Full.breaking_changes(&diff).len(), 1);
+        assert!(CompatibilityMode::None.breaking_changes(&diff).is_empty());
+    }
+}
@@ -59,6 +59,28 @@ pub enum DomainError {
     #[error("Validation failed: {0}")]
     ValidationFailed(String),
 
+    /// A new version of a type schema breaks the configured compatibility
+    /// with registered versions, or with their instances.
+    #[error("Incompatible schema: {0}")]
+    IncompatibleSchema(String),
+
+    /// The entity cannot be deleted while other entities reference it.
+    #[error("Entity in use: {0}")]
+    InUse(String),
+
+    /// The GTS ID belongs to a deleted entity and the new content differs.
+    #[error("Entity deleted: {0}")]
+    Tombstoned(String),
+
+    /// A bundle is malformed, fails its checksums or is not signed with the
+    /// configured key.
+    #[error("Invalid bundle: {0}")]
+    InvalidBundle(String),
+
+    /// Schemas reference each other in a cycle.
+    #[error("Cyclic reference: {0}")]
+    CyclicReference(String),
+
     /// The operation requires ready mode but registry is in configuration mode.
     #[error("Not in ready mode")]
     NotInReadyMode,
@@ -97,6 +119,40 @@ impl DomainError {
         Self::ValidationFailed(message.into())
     }
 
+    /// Creates an `IncompatibleSchema` error listing the breaking changes.
+    #[must_use]
+    pub fn incompatible_schema(gts_id: &str, reasons: &[String]) -> Self {
+        Self::IncompatibleSchema(format!("{gts_id}: {}", reasons.join("; ")))
+    }
+
+    /// Creates an `InvalidBundle` error.
+    #[must_use]
+    pub fn invalid_bundle(message: impl Into<String>) -> Self {
+        Self::InvalidBundle(message.into())
+    }
+
+    /// Creates an `InUse` error listing the referencing entities.
+    #[must_use]
+    pub fn in_use(gts_id: &str, referenced_by: &[String]) -> Self {
+        Self::InUse(format!(
+            "{gts_id} is referenced by {}",
+            referenced_by.join(", ")
+        ))
+    }
+
+    /// Creates a `Tombstoned` error.
+    #[must_use]
+    pub fn tombstoned(gts_id: impl Into<String>) -> Self {
+        Self::Tombstoned(gts_id.into())
+    }
+
+    /// Creates a `CyclicReference` error from the path around the cycle,
+    /// which starts and ends with the same GTS ID.
+    #[must_use]
+    pub fn cyclic_reference(path: &[String]) -> Self {
+        Self::CyclicReference(path.join(" -> "))
+    }
+
     /// Returns the list of validation errors if this is a `ReadyCommitFailed` error.
     #[must_use]
     pub fn validation_errors(&self) -> Option<&[ValidationError]> {
@@ -107,13 +163,31 @@ impl DomainError {
     }
 }
 
+impl From<modkit_db::DbError> for DomainError {
+    fn from(e: modkit_db::DbError) -> Self {
+        Self::Internal(anyhow::anyhow!("database error: {e}"))
+    }
+}
+
+impl From<modkit_db::secure::ScopeError> for DomainError {
+    fn from(e: modkit_db::secure::ScopeError) -> Self {
+        Self::Internal(anyhow::anyhow!("entity store: {e}"))
+    }
+}
+
 impl From<DomainError> for TypesRegistryError {
     fn from(e: DomainError) -> Self {
         match e {
             DomainError::InvalidGtsId(msg) => TypesRegistryError::invalid_gts_id(msg),
             DomainError::NotFound(id) => TypesRegistryError::not_found(id),
             DomainError::AlreadyExists(id) => TypesRegistryError::already_exists(id),
-            DomainError::ValidationFailed(msg) => TypesRegistryError::validation_failed(msg),
+            DomainError::ValidationFailed(msg) | DomainError::InvalidBundle(msg) => {
+                TypesRegistryError::validation_failed(msg)
+            }
+            DomainError::IncompatibleSchema(msg) => TypesRegistryError::incompatible_schema(msg),
+            DomainError::InUse(msg) => TypesRegistryError::in_use(msg),
+            DomainError::Tombstoned(id) => TypesRegistryError::tombstoned(id),
+            DomainError::CyclicReference(path) => TypesRegistryError::cyclic_reference(path),
             DomainError::NotInReadyMode => TypesRegistryError::not_in_ready_mode(),
             DomainError::ReadyCommitFailed(errors) => {
                 let error_strings: Vec<String> = errors
@@ -167,6 +241,32 @@ mod tests {
         let domain_err = DomainError::invalid_gts_id("bad format");
         let sdk_err: TypesRegistryError = domain_err.into();
         assert!(sdk_err.is_invalid_gts_id());
+
+        let domain_err = DomainError::incompatible_schema(
+            "gts.x.core.events.test.v1.1~",
+            &["removed".to_owned()],
+        );
+        let sdk_err: TypesRegistryError = domain_err.into();
+        assert!(sdk_err.is_incompatible_schema());
+
+        let domain_err = DomainError::in_use(
+            "gts.x.core.events.test.v1~",
+            &["gts.x.core.events.test.v1~x.core.instances.a.v1".to_owned()],
+        );
+        let sdk_err: TypesRegistryError = domain_err.into();
+        assert!(sdk_err.is_in_use());
+
+        let domain_err = DomainError::tombstoned("gts.x.core.events.test.v1~");
+        let sdk_err: TypesRegistryError = domain_err.into();
+        assert!(sdk_err.is_tombstoned());
+
+        let domain_err = DomainError::cyclic_reference(&[
+            "gts.x.core.events.a.v1~".to_owned(),
+            "gts.x.core.events.b.v1~".to_owned(),
+            "gts.x.core.events.a.v1~".to_owned(),
+        ]);
+        let sdk_err: TypesRegistryError = domain_err.into();
+        assert!(sdk_err.is_cyclic_reference());
     }
 
     #[test]
@@ -0,0 +1,499 @@
+//! Dependencies between GTS entities.
+//!
+//! An entity depends on:
+//! - the type its chained ID extends (`gts.a~b~` on `gts.a~`),
+//! - for an instance, its type (`gts.a~x` on `gts.a~`),
+//! - for a schema, every GTS schema it `$ref`s.
+//!
+//! gts-rust inlines `$ref`s without a cycle guard, so cycles between schemas
+//! must be rejected before they reach its store.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+
+use serde_json::Value;
+use types_registry_sdk::{
+    EntityGraph, GraphDirection, GraphEdge, GraphEdgeKind, GraphNode, GtsEntity, ResolvedSchema,
+};
+
+use super::error::DomainError;
+
+const GTS_URI_PREFIX: &str = "gts://";
+
+/// Splits a `$ref` into the GTS ID it points at and its JSON pointer
+/// fragment, if it points at a GTS entity.
+fn split_ref(reference: &str) -> Option<(&str, Option<&str>)> {
+    let reference = reference.strip_prefix(GTS_URI_PREFIX).unwrap_or(reference);
+    let (target, fragment) = match reference.split_once('#') {
+        Some((target, fragment)) => (target, Some(fragment).filter(|f| !f.is_empty())),
+        None => (reference, None),
+    };
+    target.starts_with("gts.").then_some((target, fragment))
+}
+
+/// GTS IDs that a schema `$ref`s, with or without the `gts://` prefix.
+#[must_use]
+pub fn ref_targets(schema: &Value) -> BTreeSet<String> {
+    fn collect(value: &Value, targets: &mut BTreeSet<String>) {
+        match value {
+            Value::Object(map) => {
+                for (key, value) in map {
+                    match value {
+                        Value::String(reference) if key == "$ref" => {
+                            if let Some((target, _)) = split_ref(reference) {
+                                targets.insert(target.to_owned());
+                            }
+                        }
+                        _ => collect(value, targets),
+                    }
+                }
+            }
+            Value::Array(items) => items.iter().for_each(|item| collect(item, targets)),
+            _ => {}
+        }
+    }
+
+    let mut targets = BTreeSet::new();
+    collect(schema, &mut targets);
+    targets
+}
+
+/// Direct dependencies of an entity.
+#[must_use]
+pub fn dependencies(gts_id: &str, content: &Value) -> Vec<(String, GraphEdgeKind)> {
+    let mut deps = Vec::new();
+    if let Some(chained) = gts_id.strip_suffix('~') {
+        if let Some(pos) = chained.rfind('~') {
+            deps.push((gts_id[..=pos].to_owned(), GraphEdgeKind::Parent));
+        }
+        deps.extend(
+            ref_targets(content)
+                .into_iter()
+                .map(|target| (target, GraphEdgeKind::Ref)),
+        );
+    } else if let Some(pos) = gts_id.rfind('~') {
+        deps.push((gts_id[..=pos].to_owned(), GraphEdgeKind::Type));
+    }
+    deps
+}
+
+/// Finds a cycle of `$ref`s through `start`, following the targets that
+/// `refs` returns for each schema.
+///
+/// Assumes the graph without `start` is acyclic, as it is for registered
+/// schemas. The cycle starts and ends with `start`.
+pub fn find_cycle_from(
+    start: &str,
+    mut refs: impl FnMut(&str) -> BTreeSet<String>,
+) -> Option<Vec<String>> {
+    fn visit(
+        node: &str,
+        start: &str,
+        refs: &mut dyn FnMut(&str) -> BTreeSet<String>,
+        visited: &mut HashSet<String>,
+        path: &mut Vec<String>,
+    ) -> bool {
+        for target in refs(node) {
+            if target == start {
+                path.push(target);
+                return true;
+            }
+            if visited.insert(target.clone()) {
+                path.push(target.clone());
+                if visit(&target, start, refs, visited, path) {
+                    return true;
+                }
+                path.pop();
+            }
+        }
+        false
+    }
+
+    let mut path = vec![start.to_owned()];
+    visit(start, start, &mut refs, &mut HashSet::new(), &mut path).then_some(path)
+}
+
+/// Finds cycles in a graph of `$ref`s between schemas, given as the targets
+/// of each schema. Targets without an entry are ignored.
+///
+/// Reports one cycle per back edge; each starts and ends with the same ID.
+#[must_use]
+pub fn find_cycles(refs: &BTreeMap<String, BTreeSet<String>>) -> Vec<Vec<String>> {
+    fn visit<'a>(
+        node: &'a str,
+        refs: &'a BTreeMap<String, BTreeSet<String>>,
+        path: &mut Vec<&'a str>,
+        done: &mut HashSet<&'a str>,
+        cycles: &mut Vec<Vec<String>>,
+    ) {
+        if done.contains(node) {
+            return;
+        }
+        if let Some(pos) = path.iter().position(|id| *id == node) {
+            let mut cycle: Vec<String> = path[pos..].iter().map(|id| (*id).to_owned()).collect();
+            cycle.push(node.to_owned());
+            cycles.push(cycle);
+            return;
+        }
+        let Some(targets) = refs.get(node) else {
+            return;
+        };
+        path.push(node);
+        for target in targets {
+            visit(target, refs, path, done, cycles);
+        }
+        path.pop();
+        done.insert(node);
+    }
+
+    let mut done = HashSet::new();
+    let mut cycles = Vec::new();
+    for id in refs.keys() {
+        visit(id, refs, &mut Vec::new(), &mut done, &mut cycles);
+    }
+    cycles
+}
+
+/// Builds the graph of the dependencies and/or dependents of `root` among
+/// `entities`, following at most `depth` edges from the root.
+#[must_use]
+pub fn entity_graph(
+    root: &str,
+    entities: &[GtsEntity],
+    direction: GraphDirection,
+    depth: Option<usize>,
+) -> EntityGraph {
+    let mut outgoing: HashMap<&str, Vec<GraphEdge>> = HashMap::new();
+    let mut incoming: HashMap<String, Vec<GraphEdge>> = HashMap::new();
+    for entity in entities {
+        for (to, kind) in dependencies(&entity.gts_id, &entity.content) {
+            let edge = GraphEdge {
+                from: entity.gts_id.clone(),
+                to,
+                kind,
+            };
+            incoming
+                .entry(edge.to.clone())
+                .or_default()
+                .push(edge.clone());
+            outgoing.entry(&entity.gts_id).or_default().push(edge);
+        }
+    }
+
+    let mut nodes = BTreeSet::from([root.to_owned()]);
+    let mut edges = BTreeSet::new();
+    // Breadth-first from the root, along dependencies or against them
+    let mut walk = |forward: bool| {
+        let mut seen = HashSet::from([root.to_owned()]);
+        let mut queue = VecDeque::from([(root.to_owned(), 0)]);
+        while let Some((id, distance)) = queue.pop_front() {
+            if depth.is_some_and(|depth| distance >= depth) {
+                continue;
+            }
+            let next = if forward {
+                outgoing.get(id.as_str())
+            } else {
+                incoming.get(&id)
+            };
+            for edge in next.into_iter().flatten() {
+                let neighbour = if forward { &edge.to } else { &edge.from };
+                edges.insert(edge.clone());
+                if seen.insert(neighbour.clone()) {
+                    nodes.insert(neighbour.clone());
+                    queue.push_back((neighbour.clone(), distance + 1));
+                }
+            }
+        }
+    };
+    if direction != GraphDirection::Dependents {
+        walk(true);
+    }
+    if direction != GraphDirection::Dependencies {
+        walk(false);
+    }
+
+    let registered: HashSet<&str> = entities.iter().map(|e| e.gts_id.as_str()).collect();
+    EntityGraph {
+        root: root.to_owned(),
+        nodes: nodes
+            .into_iter()
+            .map(|gts_id| GraphNode {
+                is_schema: gts_id.ends_with('~'),
+                registered: registered.contains(gts_id.as_str()),
+                gts_id,
+            })
+            .collect(),
+        edges: edges.into_iter().collect(),
+    }
+}
+
+/// Replaces GTS `$ref`s with the schemas they point at, recursively.
+struct Dereferencer<F> {
+    lookup: F,
+    /// Schemas being inlined, outermost first.
+    stack: Vec<String>,
+    references: BTreeSet<String>,
+    unresolved: BTreeSet<String>,
+}
+
+impl<F: Fn(&str) -> Option<Value>> Dereferencer<F> {
+    fn inline(&mut self, value: &Value) -> Result<Value, DomainError> {
+        match value {
+            Value::Object(map) => {
+                let reference = map.get("$ref");
+                let target = reference.and_then(Value::as_str).and_then(split_ref);
+                let mut out = serde_json::Map::new();
+                if let Some((target, fragment)) = target {
+                    match self.inline_ref(target, fragment)? {
+                        Some(Value::Object(resolved)) => out = resolved,
+                        Some(resolved) if map.len() == 1 => return Ok(resolved),
+                        _ => {
+                            out.extend(reference.map(|r| ("$ref".to_owned(), r.clone())));
+                        }
+                    }
+                }
+                // Keywords next to a `$ref` refine the referenced schema
+                for (key, value) in map {
+                    if target.is_none() || key != "$ref" {
+                        out.insert(key.clone(), self.inline(value)?);
+                    }
+                }
+                Ok(Value::Object(out))
+            }
+            Value::Array(items) => items
+                .iter()
+                .map(|item| self.inline(item))
+                .collect::<Result<Vec<_>, _>>()
+                .map(Value::Array),
+            _ => Ok(value.clone()),
+        }
+    }
+
+    fn inline_ref(
+        &mut self,
+        target: &str,
+        fragment: Option<&str>,
+    ) -> Result<Option<Value>, DomainError> {
+        if let Some(pos) = self.stack.iter().position(|id| id == target) {
+            let mut path = self.stack[pos..].to_vec();
+            path.push(target.to_owned());
+            return Err(DomainError::cyclic_reference(&path));
+        }
+        let content = (self.lookup)(target).and_then(|content| match fragment {
+            Some(pointer) => content.pointer(pointer).cloned(),
+            None => Some(content),
+        });
+        let Some(content) = content else {
+            self.unresolved.insert(target.to_owned());
+            return Ok(None);
+        };
+
+        self.references.insert(target.to_owned());
+        self.stack.push(target.to_owned());
+        let mut resolved = self.inline(&content)?;
+        self.stack.pop();
+
+        // The inlined schema is no longer a document of its own
+        if let Value::Object(map) = &mut resolved {
+            map.remove("$id");
+            map.remove("$schema");
+        }
+        Ok(Some(resolved))
+    }
+}
+
+/// Resolves `schema`, registered as `gts_id`, into a self-contained schema.
+/// `lookup` returns the content of a registered schema.
+///
+/// # Errors
+///
+/// Returns `CyclicReference` if the referenced schemas form a cycle.
+pub fn resolve(
+    gts_id: &str,
+    schema: &Value,
+    lookup: impl Fn(&str) -> Option<Value>,
+) -> Result<ResolvedSchema, DomainError> {
+    let mut dereferencer = Dereferencer {
+        lookup,
+        stack: vec![gts_id.to_owned()],
+        references: BTreeSet::new(),
+        unresolved: BTreeSet::new(),
+    };
+    let schema = dereferencer.inline(schema)?;
+    Ok(ResolvedSchema {
+        gts_id: gts_id.to_owned(),
+        schema,
+        references: dereferencer.references.into_iter().collect(),
+        unresolved: dereferencer.unresolved.into_iter().collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    const BASE: &str = "gts.acme.core.models.base.v1~";
+    const ADDRESS: &str = "gts.acme.core.models.address.v1~";
+    const USER: &str = "gts.acme.core.models.base.v1~acme.core.models.user.v1~";
+
+    fn entity(gts_id: &str, content: Value) -> GtsEntity {
+        GtsEntity::new(
+            Uuid::nil(),
+            gts_id,
+            vec![],
+            gts_id.ends_with('~'),
+            content,
+            None,
+        )
+    }
+
+    fn refs(pairs: &[(&str, &[&str])]) -> BTreeMap<String, BTreeSet<String>> {
+        pairs
+            .iter()
+            .map(|(id, targets)| {
+                (
+                    (*id).to_owned(),
+                    targets.iter().map(|t| (*t).to_owned()).collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ref_targets_and_dependencies() {
+        let schema = json!({
+            "allOf": [{ "$ref": format!("gts://{BASE}") }],
+            "properties": {
+                "address": { "$ref": format!("{ADDRESS}#/properties/street") },
+                "local": { "$ref": "#/definitions/x" },
+                "external": { "$ref": "https://example.com/schema.json" }
+            }
+        });
+        assert_eq!(
+            ref_targets(&schema).into_iter().collect::<Vec<_>>(),
+            [ADDRESS, BASE]
+        );
+
+        assert_eq!(
+            dependencies(USER, &schema),
+            [
+                (BASE.to_owned(), GraphEdgeKind::Parent),
+                (ADDRESS.to_owned(), GraphEdgeKind::Ref),
+                (BASE.to_owned(), GraphEdgeKind::Ref),
+            ]
+        );
+        assert_eq!(
+            dependencies(&format!("{USER}acme.core.users.alice.v1"), &json!({})),
+            [(USER.to_owned(), GraphEdgeKind::Type)]
+        );
+    }
+
+    #[test]
+    fn test_find_cycles() {
+        assert!(find_cycles(&refs(&[("a~", &["b~"]), ("b~", &["c~"]), ("c~", &[])])).is_empty());
+
+        let cycles = find_cycles(&refs(&[
+            ("a~", &["b~"]),
+            ("b~", &["c~", "x~"]),
+            ("c~", &["a~"]),
+            ("d~", &["d~"]),
+        ]));
+        assert_eq!(cycles, [vec!["a~", "b~", "c~", "a~"], vec!["d~", "d~"],]);
+
+        let graph = refs(&[("b~", &["c~"]), ("c~", &["a~"])]);
+        let lookup = |id: &str| {
+            if id == "a~" {
+                BTreeSet::from(["b~".to_owned()])
+            } else {
+                graph.get(id).cloned().unwrap_or_default()
+            }
+        };
+        assert_eq!(
+            find_cycle_from("a~", lookup).unwrap(),
+            ["a~", "b~", "c~", "a~"]
+        );
+        assert!(find_cycle_from("c~", |_| BTreeSet::new()).is_none());
+    }
+
+    #[test]
+    fn test_entity_graph_directions_and_depth() {
+        let entities = [
+            entity(BASE, json!({})),
+            entity(ADDRESS, json!({})),
+            entity(
+                USER,
+                json!({ "properties": { "home": { "$ref": format!("gts://{ADDRESS}") } } }),
+            ),
+            entity(&format!("{USER}acme.core.users.alice.v1"), json!({})),
+        ];
+
+        let graph = entity_graph(USER, &entities, GraphDirection::Dependencies, None);
+        let ids: Vec<&str> = graph.nodes.iter().map(|n| n.gts_id.as_str()).collect();
+        assert_eq!(ids, [ADDRESS, BASE, USER]);
+        assert_eq!(graph.edges.len(), 2);
+
+        let graph = entity_graph(BASE, &entities, GraphDirection::Dependents, Some(1));
+        let ids: Vec<&str> = graph.nodes.iter().map(|n| n.gts_id.as_str()).collect();
+        assert_eq!(ids, [BASE, USER]);
+
+        let graph = entity_graph(BASE, &entities, GraphDirection::Dependents, None);
+        assert_eq!(graph.nodes.len(), 3);
+        assert!(
+            graph
+                .edges
+                .iter()
+                .any(|e| e.kind == GraphEdgeKind::Type && e.to == USER)
+        );
+    }
+
+    #[test]
+    fn test_resolve_inlines_refs_and_reports_missing_and_cycles() {
+        let schemas = BTreeMap::from([
+            (
+                ADDRESS.to_owned(),
+                json!({
+                    "$id": format!("gts://{ADDRESS}"),
+                    "$schema": "http://json-schema.org/draft-07/schema#",
+                    "type": "object",
+                    "properties": { "street": { "type": "string" } }
+                }),
+            ),
+            (BASE.to_owned(), json!({ "$ref": format!("gts://{USER}") })),
+        ]);
+        let lookup = |id: &str| schemas.get(id).cloned();
+
+        let user = json!({
+            "$id": format!("gts://{USER}"),
+            "properties": {
+                "home": { "$ref": format!("gts://{ADDRESS}"), "description": "Home" },
+                "street": { "$ref": format!("gts://{ADDRESS}#/properties/street") },
+                "missing": { "$ref": "gts://gts.acme.core.models.missing.v1~" }
+            }
+        });
+        let resolved = resolve(USER, &user, lookup).unwrap();
+        assert_eq!(
+            resolved.schema,
+            json!({
+                "$id": format!("gts://{USER}"),
+                "properties": {
+                    "home": {
+                        "type": "object",
+                        "properties": { "street": { "type": "string" } },
+                        "description": "Home"
+                    },
+                    "street": { "type": "string" },
+                    "missing": { "$ref": "gts://gts.acme.core.models.missing.v1~" }
+                }
+            })
+        );
+        assert_eq!(resolved.references, [ADDRESS]);
+        assert_eq!(resolved.unresolved, ["gts.acme.core.models.missing.v1~"]);
+
+        let cyclic = json!({ "allOf": [{ "$ref": format!("gts://{BASE}") }] });
+        assert!(matches!(
+            resolve(USER, &cyclic, lookup),
+            Err(DomainError::CyclicReference(path)) if path == format!("{USER} -> {BASE} -> {USER}")
+        ));
+    }
+}
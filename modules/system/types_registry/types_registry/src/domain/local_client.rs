@@ -4,7 +4,8 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use types_registry_sdk::{
-    GtsEntity, ListQuery, RegisterResult, TypesRegistryClient, TypesRegistryError,
+    EntityChangeStream, GtsEntity, ListQuery, RegisterResult, ResolvedSchema, TypesRegistryClient,
+    TypesRegistryError,
 };
 
 use crate::domain::service::TypesRegistryService;
@@ -32,15 +33,47 @@ impl TypesRegistryClient for TypesRegistryLocalClient {
         &self,
         entities: Vec<serde_json::Value>,
     ) -> Result<Vec<RegisterResult>, TypesRegistryError> {
-        Ok(self.service.register(entities))
+        let results = self.service.register(entities);
+        Ok(self.service.persist_results(results).await)
     }
 
     async fn list(&self, query: ListQuery) -> Result<Vec<GtsEntity>, TypesRegistryError> {
-        self.service.list(&query).map_err(TypesRegistryError::from)
+        self.service
+            .list_refreshed(&query)
+            .await
+            .map_err(TypesRegistryError::from)
     }
 
     async fn get(&self, gts_id: &str) -> Result<GtsEntity, TypesRegistryError> {
-        self.service.get(gts_id).map_err(TypesRegistryError::from)
+        self.service
+            .get_or_load(gts_id)
+            .await
+            .map_err(TypesRegistryError::from)
+    }
+
+    async fn watch(&self, query: ListQuery) -> Result<EntityChangeStream, TypesRegistryError> {
+        Ok(Box::pin(self.service.watch(query)))
+    }
+
+    async fn deprecate(&self, gts_id: &str) -> Result<GtsEntity, TypesRegistryError> {
+        self.service
+            .deprecate(gts_id)
+            .await
+            .map_err(TypesRegistryError::from)
+    }
+
+    async fn delete(&self, gts_id: &str) -> Result<(), TypesRegistryError> {
+        self.service
+            .delete(gts_id)
+            .await
+            .map_err(TypesRegistryError::from)
+    }
+
+    async fn resolve_schema(&self, gts_id: &str) -> Result<ResolvedSchema, TypesRegistryError> {
+        self.service
+            .resolve_schema(gts_id)
+            .await
+            .map_err(TypesRegistryError::from)
     }
 }
 
@@ -2,12 +2,17 @@
 //!
 //! Contains business logic, error types, and repository traits.
 
+pub mod bundle;
+pub mod compatibility;
 pub mod error;
+pub mod graph;
 pub mod repo;
 pub mod service;
 // === LOCAL CLIENT ===
 pub mod local_client;
 
+pub use bundle::Bundle;
+pub use compatibility::{CompatibilityMode, SchemaDiff};
 pub use error::DomainError;
-pub use repo::GtsRepository;
+pub use repo::{GtsRepository, GtsStore, StoredEntity};
 pub use service::TypesRegistryService;
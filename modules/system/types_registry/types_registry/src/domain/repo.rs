@@ -1,5 +1,6 @@
-//! Repository trait for GTS entity storage.
+//! Repository traits for GTS entity storage.
 
+use async_trait::async_trait;
 use types_registry_sdk::{GtsEntity, ListQuery};
 
 use super::error::DomainError;
@@ -60,4 +61,109 @@ pub trait GtsRepository: Send + Sync {
     ///
     /// Returns a list of validation errors if any entity fails validation.
     fn switch_to_ready(&self) -> Result<(), Vec<String>>;
+
+    /// Returns `schema` with `$ref`s to registered schemas inlined.
+    fn resolve_schema_refs(&self, schema: &serde_json::Value) -> serde_json::Value;
+
+    /// Validates registered instances against a type schema that is not
+    /// registered yet, as if they had been created as instances of it.
+    ///
+    /// Returns an error in the format "`gts_id`: message" for every instance
+    /// that does not validate or does not exist.
+    fn validate_instances_against(
+        &self,
+        schema: &serde_json::Value,
+        instance_ids: &[String],
+    ) -> Vec<String>;
+
+    /// Marks an entity as deprecated. Deprecating twice is a no-op.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if the entity doesn't exist.
+    fn deprecate(&self, gts_id: &str) -> Result<GtsEntity, DomainError>;
+
+    /// Removes an entity from ready mode storage and tombstones its GTS ID.
+    ///
+    /// Returns the removed entity.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if the entity doesn't exist, or `InUse` if other
+    /// entities are derived from it, are instances of it or `$ref` it.
+    fn delete(&self, gts_id: &str) -> Result<GtsEntity, DomainError>;
+
+    /// Removes an entity from ready mode storage without tombstoning it,
+    /// undoing a registration that could not be persisted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if the entity doesn't exist, or `InUse` if other
+    /// entities are derived from it, are instances of it or `$ref` it.
+    fn evict(&self, gts_id: &str) -> Result<(), DomainError>;
+
+    /// Records the content a deleted entity had, so its GTS ID can only be
+    /// registered again with that content.
+    fn tombstone(&self, gts_id: &str, content: serde_json::Value);
+
+    /// Creates an independent copy of the ready mode state, used to check
+    /// registrations without applying them.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Internal` if the entities cannot be copied.
+    fn snapshot(&self) -> Result<std::sync::Arc<dyn GtsRepository>, DomainError>;
+
+    /// Creates an independent ready mode copy holding what
+    /// [`Self::switch_to_ready`] would commit, leaving this repository as is.
+    ///
+    /// # Errors
+    ///
+    /// Returns the validation errors `switch_to_ready` would report.
+    fn staged(&self) -> Result<std::sync::Arc<dyn GtsRepository>, Vec<String>>;
+}
+
+/// An entity as kept by a [`GtsStore`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredEntity {
+    pub content: serde_json::Value,
+    pub deprecated: bool,
+    /// Deleted entities are kept as tombstones.
+    pub deleted: bool,
+}
+
+/// Durable storage for validated GTS entities.
+///
+/// The in-memory [`GtsRepository`] stays the source of validation and queries;
+/// a store persists what it accepted so the registry survives restarts and is
+/// shared between replicas.
+#[async_trait]
+pub trait GtsStore: Send + Sync {
+    /// Loads every persisted entity, including tombstones, schemas first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Internal` if the store cannot be read.
+    async fn load_all(&self) -> Result<Vec<StoredEntity>, DomainError>;
+
+    /// Loads a single entity, or its tombstone, by its GTS ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Internal` if the store cannot be read.
+    async fn load(&self, gts_id: &str) -> Result<Option<StoredEntity>, DomainError>;
+
+    /// Inserts or replaces the given entities, reviving tombstoned ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Internal` if the store cannot be written.
+    async fn save(&self, entities: &[GtsEntity]) -> Result<(), DomainError>;
+
+    /// Turns an entity into a tombstone, keeping its last content.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Internal` if the store cannot be written.
+    async fn delete(&self, gts_id: &str) -> Result<(), DomainError>;
 }
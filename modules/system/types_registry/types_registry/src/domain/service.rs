@@ -1,27 +1,75 @@
 //! Domain service for the Types Registry module.
 
+use std::collections::HashSet;
 use std::sync::Arc;
+use std::time::Instant;
 
-use types_registry_sdk::{GtsEntity, ListQuery, RegisterResult};
+use futures::Stream;
+use futures::StreamExt;
+use parking_lot::Mutex;
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+use tracing::{info, warn};
+use types_registry_sdk::{
+    EntityChange, EntityChangeKind, EntityGraph, GraphDirection, GtsEntity, ListQuery,
+    RegisterResult, ResolvedSchema,
+};
 
+use super::bundle::Bundle;
+use super::compatibility::{CompatibilityMode, SchemaDiff, SchemaVersion, diff_schemas};
 use super::error::DomainError;
-use super::repo::GtsRepository;
+use super::graph;
+use super::repo::{GtsRepository, GtsStore, StoredEntity};
 use crate::config::TypesRegistryConfig;
 
+/// Changes buffered per watcher before the oldest are skipped
+const CHANGE_BUFFER: usize = 1024;
+
 /// Domain service for GTS entity operations.
 ///
 /// This service orchestrates business logic and delegates storage
-/// operations to the repository.
+/// operations to the repository. With a durable store, the repository acts
+/// as a read-through cache of it.
 pub struct TypesRegistryService {
     repo: Arc<dyn GtsRepository>,
     config: TypesRegistryConfig,
+    store: Option<Arc<dyn GtsStore>>,
+    /// When the cache was last reloaded from the store.
+    last_refresh: Mutex<Option<Instant>>,
+    /// Changes of entities visible in ready mode.
+    changes: broadcast::Sender<EntityChange>,
+    /// GTS IDs registered in ready mode that are not persisted yet; they are
+    /// announced once the store accepts them.
+    unpersisted: Mutex<HashSet<String>>,
 }
 
 impl TypesRegistryService {
     /// Creates a new `TypesRegistryService` with the given repository and config.
     #[must_use]
     pub fn new(repo: Arc<dyn GtsRepository>, config: TypesRegistryConfig) -> Self {
-        Self { repo, config }
+        Self {
+            repo,
+            config,
+            store: None,
+            last_refresh: Mutex::new(None),
+            changes: broadcast::channel(CHANGE_BUFFER).0,
+            unpersisted: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Persists entities accepted in ready mode to `store` and reads through
+    /// to it on cache misses.
+    #[must_use]
+    pub fn with_store(mut self, store: Arc<dyn GtsStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Returns whether entities are persisted to a durable store.
+    #[must_use]
+    pub fn is_persistent(&self) -> bool {
+        self.store.is_some()
     }
 
     /// Registers GTS entities in batch.
@@ -58,8 +106,22 @@ impl TypesRegistryService {
 
         for entity in entities {
             let gts_id = self.extract_gts_id(&entity);
-            let result = match self.repo.register(&entity, validate) {
-                Ok(registered) => RegisterResult::Ok(registered),
+            let checked = match &gts_id {
+                Some(id) if validate => self.check_compatibility(id, &entity),
+                _ => Ok(()),
+            };
+            // Entities only become visible, and thus watchable, in ready mode
+            let is_new =
+                self.repo.is_ready() && gts_id.as_deref().is_some_and(|id| !self.repo.exists(id));
+            let result = match checked.and_then(|()| self.repo.register(&entity, validate)) {
+                Ok(registered) => {
+                    if is_new && self.store.is_some() {
+                        self.unpersisted.lock().insert(registered.gts_id.clone());
+                    } else if is_new {
+                        self.notify(EntityChangeKind::Added, &registered);
+                    }
+                    RegisterResult::Ok(registered)
+                }
                 Err(e) => RegisterResult::Err {
                     gts_id,
                     error: e.into(),
@@ -71,6 +133,122 @@ impl TypesRegistryService {
         results
     }
 
+    /// Checks a new minor version of a type schema against the closest
+    /// registered versions of the same major version, and re-validates the
+    /// instances of earlier versions against it, as the configured
+    /// compatibility mode requires.
+    fn check_compatibility(
+        &self,
+        gts_id: &str,
+        entity: &serde_json::Value,
+    ) -> Result<(), DomainError> {
+        if self.repo.exists(gts_id) {
+            return Ok(());
+        }
+        self.check_lineage(self.repo.as_ref(), gts_id, entity)
+    }
+
+    /// Runs the compatibility checks of `gts_id` against the versions and
+    /// instances registered in `repo`.
+    fn check_lineage(
+        &self,
+        repo: &dyn GtsRepository,
+        gts_id: &str,
+        entity: &serde_json::Value,
+    ) -> Result<(), DomainError> {
+        let mode = self.config.compatibility;
+        if mode == CompatibilityMode::None {
+            return Ok(());
+        }
+        let Some(version) = SchemaVersion::parse(gts_id) else {
+            return Ok(());
+        };
+
+        let siblings = versions_of(repo, &version)?;
+        let previous = siblings
+            .iter()
+            .filter(|(v, _)| v.minor < version.minor)
+            .max_by_key(|(v, _)| v.minor);
+        let next = siblings
+            .iter()
+            .filter(|(v, _)| v.minor > version.minor)
+            .min_by_key(|(v, _)| v.minor);
+
+        let candidate = repo.resolve_schema_refs(entity);
+        let mut breaking = Vec::new();
+        if let Some((_, prev)) = previous {
+            let prev_schema = repo.resolve_schema_refs(&prev.content);
+            let diff = diff_schemas(&prev.gts_id, &prev_schema, gts_id, &candidate);
+            breaking.extend(
+                mode.breaking_changes(&diff)
+                    .into_iter()
+                    .map(|e| format!("against {}: {e}", prev.gts_id)),
+            );
+        }
+        if let Some((_, next)) = next {
+            let next_schema = repo.resolve_schema_refs(&next.content);
+            let diff = diff_schemas(gts_id, &candidate, &next.gts_id, &next_schema);
+            breaking.extend(
+                mode.breaking_changes(&diff)
+                    .into_iter()
+                    .map(|e| format!("against {}: {e}", next.gts_id)),
+            );
+        }
+        if !breaking.is_empty() {
+            return Err(DomainError::incompatible_schema(gts_id, &breaking));
+        }
+
+        if mode.revalidates_instances() {
+            let earlier: Vec<&str> = siblings
+                .iter()
+                .filter(|(v, _)| v.minor < version.minor)
+                .map(|(_, e)| e.gts_id.as_str())
+                .collect();
+            let instance_ids: Vec<String> = repo
+                .list(&ListQuery::new().with_is_type(false))?
+                .into_iter()
+                .filter(|e| {
+                    e.gts_id
+                        .rfind('~')
+                        .is_some_and(|pos| earlier.contains(&&e.gts_id[..=pos]))
+                })
+                .map(|e| e.gts_id)
+                .collect();
+            let failures = repo.validate_instances_against(entity, &instance_ids);
+            if !failures.is_empty() {
+                return Err(DomainError::incompatible_schema(gts_id, &failures));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Explains the differences between two registered type schemas.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if either schema is not registered and
+    /// `InvalidGtsId` if either ID is not a type schema.
+    pub fn diff(&self, from: &str, to: &str) -> Result<SchemaDiff, DomainError> {
+        let load = |gts_id: &str| {
+            if !gts_id.ends_with('~') {
+                return Err(DomainError::invalid_gts_id(format!(
+                    "{gts_id} is not a type schema"
+                )));
+            }
+            let entity = self.repo.get(gts_id)?;
+            Ok(self.repo.resolve_schema_refs(&entity.content))
+        };
+        let (from_schema, to_schema) = (load(from)?, load(to)?);
+        Ok(diff_schemas(from, &from_schema, to, &to_schema))
+    }
+
+    /// Returns the compatibility mode enforced on registration.
+    #[must_use]
+    pub fn compatibility_mode(&self) -> CompatibilityMode {
+        self.config.compatibility
+    }
+
     /// Retrieves a single GTS entity by its identifier.
     pub fn get(&self, gts_id: &str) -> Result<GtsEntity, DomainError> {
         self.repo.get(gts_id)
@@ -92,15 +270,543 @@ impl TypesRegistryService {
     /// containing the GTS ID and error message for each failing entity.
     pub fn switch_to_ready(&self) -> Result<(), DomainError> {
         use crate::domain::error::ValidationError;
-        self.repo.switch_to_ready().map_err(|errors| {
+        let commit_failed = |errors: Vec<String>| {
             let typed_errors: Vec<ValidationError> = errors
                 .into_iter()
                 .map(|s| ValidationError::from_string(&s))
                 .collect();
             DomainError::ReadyCommitFailed(typed_errors)
+        };
+
+        // Types registered during configuration skipped the compatibility
+        // checks: run them against what the commit would hold.
+        if self.config.compatibility != CompatibilityMode::None {
+            let staged = self.repo.staged().map_err(commit_failed)?;
+            let errors = self.lineage_errors(staged.as_ref())?;
+            if !errors.is_empty() {
+                return Err(commit_failed(errors));
+            }
+        }
+
+        self.repo.switch_to_ready().map_err(commit_failed)?;
+
+        // Entities registered during configuration become visible now
+        if self.changes.receiver_count() > 0 {
+            for entity in self.repo.list(&ListQuery::default())? {
+                self.notify(EntityChangeKind::Added, &entity);
+            }
+        }
+        Ok(())
+    }
+
+    /// Compatibility failures, as "`gts_id`: message", of the types in
+    /// `staged` that are not committed yet.
+    fn lineage_errors(&self, staged: &dyn GtsRepository) -> Result<Vec<String>, DomainError> {
+        let mut errors = Vec::new();
+        for entity in staged.list(&ListQuery::new().with_is_type(true))? {
+            if self.repo.exists(&entity.gts_id) {
+                continue;
+            }
+            match self.check_lineage(staged, &entity.gts_id, &entity.content) {
+                Ok(()) => {}
+                Err(DomainError::IncompatibleSchema(message)) => errors.push(message),
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(errors)
+    }
+
+    /// Subscribes to changes of entities matching `query` from now on.
+    ///
+    /// A watcher falling behind by more than the change buffer has missed
+    /// changes: its stream ends, so it re-reads and subscribes again.
+    pub fn watch(&self, query: ListQuery) -> impl Stream<Item = EntityChange> + Send + use<> {
+        BroadcastStream::new(self.changes.subscribe())
+            .take_while(|change| {
+                let lagged = match change {
+                    Ok(_) => false,
+                    Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+                        warn!(skipped, "GTS entity watcher fell behind, ending its stream");
+                        true
+                    }
+                };
+                futures::future::ready(!lagged)
+            })
+            .filter_map(move |change| {
+                let change = change.ok().filter(|change| query.matches(&change.entity));
+                futures::future::ready(change)
+            })
+    }
+
+    fn notify(&self, kind: EntityChangeKind, entity: &GtsEntity) {
+        // No receivers is not an error
+        let _ = self.changes.send(EntityChange::new(kind, entity.clone()));
+    }
+
+    /// Loads persisted entities into the repository before `switch_to_ready`.
+    ///
+    /// Entities are added without validation, like any entity registered in
+    /// configuration mode, and validated together with them when switching to
+    /// ready mode. Entities already registered by modules take precedence over
+    /// persisted ones with the same GTS ID. Deleted entities are restored as
+    /// tombstones.
+    ///
+    /// Returns the number of restored entities.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Internal` if the store cannot be read.
+    pub async fn restore_from_store(&self) -> Result<usize, DomainError> {
+        let Some(store) = &self.store else {
+            return Ok(0);
+        };
+
+        let mut restored = 0;
+        for stored in store.load_all().await? {
+            if stored.deleted {
+                if let Some(gts_id) = self.extract_gts_id(&stored.content) {
+                    self.repo.tombstone(&gts_id, stored.content);
+                }
+                continue;
+            }
+            match self.repo.register(&stored.content, false) {
+                Ok(entity) => {
+                    if stored.deprecated {
+                        self.repo.deprecate(&entity.gts_id)?;
+                    }
+                    restored += 1;
+                }
+                Err(DomainError::AlreadyExists(gts_id)) => {
+                    warn!(gts_id = %gts_id, "Persisted GTS entity differs from the registered one, keeping the registered one");
+                }
+                Err(e) => warn!(error = %e, "Skipping persisted GTS entity"),
+            }
+        }
+        *self.last_refresh.lock() = Some(Instant::now());
+        Ok(restored)
+    }
+
+    /// Writes every entity of the repository to the store.
+    ///
+    /// Called after `switch_to_ready` so the types registered by modules at
+    /// startup are visible to other replicas. Returns the number of entities.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Internal` if the store cannot be written.
+    pub async fn persist_all(&self) -> Result<usize, DomainError> {
+        let Some(store) = &self.store else {
+            return Ok(0);
+        };
+        let entities = self.repo.list(&ListQuery::default())?;
+        store.save(&entities).await?;
+        Ok(entities.len())
+    }
+
+    /// Persists the successfully registered entities of `results`.
+    ///
+    /// Only applies in ready mode; entities registered during configuration
+    /// are persisted by [`Self::persist_all`] once validated. With a store,
+    /// new entities are announced to watchers only once persisted. A new
+    /// entity that cannot be persisted is removed from the cache again and
+    /// reported as failed.
+    pub async fn persist_results(&self, results: Vec<RegisterResult>) -> Vec<RegisterResult> {
+        let Some(store) = &self.store else {
+            return results;
+        };
+        if !self.repo.is_ready() {
+            return results;
+        }
+
+        let mut persisted = Vec::with_capacity(results.len());
+        let mut unsaved = Vec::new();
+        for result in results {
+            let result = match result {
+                RegisterResult::Ok(entity) => self.persist_one(store, entity, &mut unsaved).await,
+                err @ RegisterResult::Err { .. } => err,
+            };
+            persisted.push(result);
+        }
+
+        // Entities referencing others come later in a batch: evict them first
+        for gts_id in unsaved.iter().rev() {
+            if let Err(e) = self.repo.evict(gts_id) {
+                warn!(gts_id = %gts_id, error = %e, "Failed to evict unpersisted GTS entity");
+            }
+        }
+        persisted
+    }
+
+    /// Persists a registered entity, announcing it if it is new. New entities
+    /// that cannot be persisted are added to `unsaved`.
+    async fn persist_one(
+        &self,
+        store: &Arc<dyn GtsStore>,
+        entity: GtsEntity,
+        unsaved: &mut Vec<String>,
+    ) -> RegisterResult {
+        let saved = store.save(std::slice::from_ref(&entity)).await;
+        let is_new = self.unpersisted.lock().remove(&entity.gts_id);
+        match saved {
+            Ok(()) => {
+                if is_new {
+                    self.notify(EntityChangeKind::Added, &entity);
+                }
+                RegisterResult::Ok(entity)
+            }
+            Err(e) => {
+                warn!(gts_id = %entity.gts_id, error = %e, "Failed to persist GTS entity");
+                if is_new {
+                    unsaved.push(entity.gts_id.clone());
+                }
+                RegisterResult::Err {
+                    gts_id: Some(entity.gts_id),
+                    error: e.into(),
+                }
+            }
+        }
+    }
+
+    /// Retrieves an entity, falling back to the store on a cache miss.
+    ///
+    /// Entities found in the store (e.g. registered by another replica) are
+    /// validated and added to the cache.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if neither the cache nor the store has the entity,
+    /// or the validation error of a stored entity.
+    pub async fn get_or_load(&self, gts_id: &str) -> Result<GtsEntity, DomainError> {
+        match self.repo.get(gts_id) {
+            Err(DomainError::NotFound(_)) => {}
+            result => return result,
+        }
+        let Some(store) = self.store.as_ref().filter(|_| self.repo.is_ready()) else {
+            return Err(DomainError::not_found(gts_id));
+        };
+
+        let Some(stored) = store.load(gts_id).await? else {
+            return Err(DomainError::not_found(gts_id));
+        };
+        if stored.deleted {
+            self.repo.tombstone(gts_id, stored.content);
+            return Err(DomainError::not_found(gts_id));
+        }
+        match self.repo.register(&stored.content, true) {
+            Ok(entity) => {
+                let entity = if stored.deprecated {
+                    self.repo.deprecate(gts_id)?
+                } else {
+                    entity
+                };
+                self.notify(EntityChangeKind::Added, &entity);
+                Ok(entity)
+            }
+            // Registered concurrently
+            Err(DomainError::AlreadyExists(_)) => self.repo.get(gts_id),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Applies changes persisted by other replicas to the cache, at most once
+    /// per configured refresh interval: new entities are added, deprecations
+    /// applied and deleted entities removed.
+    ///
+    /// Returns the number of entities added.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Internal` if the store cannot be read.
+    pub async fn refresh_from_store(&self) -> Result<usize, DomainError> {
+        let Some(store) = self.store.as_ref().filter(|_| self.repo.is_ready()) else {
+            return Ok(0);
+        };
+        {
+            let mut last_refresh = self.last_refresh.lock();
+            if last_refresh.is_some_and(|at| at.elapsed() < self.config.refresh_interval()) {
+                return Ok(0);
+            }
+            *last_refresh = Some(Instant::now());
+        }
+
+        let (deleted, live): (Vec<StoredEntity>, Vec<StoredEntity>) =
+            store.load_all().await?.into_iter().partition(|s| s.deleted);
+
+        // Schemas come first, so instances validate against them
+        let mut added = 0;
+        for stored in live {
+            if self.apply_stored(&stored) {
+                added += 1;
+            }
+        }
+        // Instances go first, so their schemas are no longer in use
+        for stored in deleted.into_iter().rev() {
+            self.apply_deleted(stored);
+        }
+        if added > 0 {
+            info!(added, "Loaded GTS entities registered by other replicas");
+        }
+        Ok(added)
+    }
+
+    /// Adds or deprecates a live stored entity. Returns whether it was added.
+    fn apply_stored(&self, stored: &StoredEntity) -> bool {
+        let Some(gts_id) = self.extract_gts_id(&stored.content) else {
+            return false;
+        };
+        if let Ok(entity) = self.repo.get(&gts_id) {
+            if stored.deprecated && !entity.deprecated {
+                match self.repo.deprecate(&gts_id) {
+                    Ok(entity) => self.notify(EntityChangeKind::Updated, &entity),
+                    Err(e) => {
+                        warn!(gts_id = %gts_id, error = %e, "Failed to apply persisted deprecation");
+                    }
+                }
+            }
+            return false;
+        }
+        match self.repo.register(&stored.content, true) {
+            Ok(entity) => {
+                let entity = if stored.deprecated {
+                    self.repo.deprecate(&gts_id).unwrap_or(entity)
+                } else {
+                    entity
+                };
+                self.notify(EntityChangeKind::Added, &entity);
+                true
+            }
+            Err(DomainError::AlreadyExists(_)) => false,
+            Err(e) => {
+                warn!(gts_id = %gts_id, error = %e, "Skipping invalid persisted GTS entity");
+                false
+            }
+        }
+    }
+
+    /// Removes an entity deleted by another replica and records its tombstone.
+    fn apply_deleted(&self, stored: StoredEntity) {
+        let Some(gts_id) = self.extract_gts_id(&stored.content) else {
+            return;
+        };
+        if self.repo.exists(&gts_id) {
+            match self.repo.delete(&gts_id) {
+                Ok(entity) => self.notify(EntityChangeKind::Removed, &entity),
+                Err(e) => {
+                    warn!(gts_id = %gts_id, error = %e, "Failed to apply persisted deletion");
+                    return;
+                }
+            }
+        }
+        self.repo.tombstone(&gts_id, stored.content);
+    }
+
+    /// Marks an entity as deprecated. It stays resolvable and is flagged in
+    /// `get` and `list` results. Deprecating twice is a no-op.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotInReadyMode` before ready mode, `NotFound` if the entity
+    /// doesn't exist, or `Internal` if the store cannot be written.
+    pub async fn deprecate(&self, gts_id: &str) -> Result<GtsEntity, DomainError> {
+        if !self.repo.is_ready() {
+            return Err(DomainError::NotInReadyMode);
+        }
+        let entity = self.get_or_load(gts_id).await?;
+        if entity.deprecated {
+            return Ok(entity);
+        }
+
+        // Persist first: a failed write must leave the cache untouched
+        if let Some(store) = &self.store {
+            store
+                .save(std::slice::from_ref(&entity.with_deprecated(true)))
+                .await?;
+        }
+        let entity = self.repo.deprecate(gts_id)?;
+        self.notify(EntityChangeKind::Updated, &entity);
+        Ok(entity)
+    }
+
+    /// Deletes an entity, leaving a tombstone so its GTS ID can only be
+    /// registered again with the same content.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotInReadyMode` before ready mode, `NotFound` if the entity
+    /// doesn't exist, `InUse` if other entities are derived from it, are
+    /// instances of it or `$ref` it, or `Internal` if the store cannot be
+    /// written.
+    pub async fn delete(&self, gts_id: &str) -> Result<(), DomainError> {
+        if !self.repo.is_ready() {
+            return Err(DomainError::NotInReadyMode);
+        }
+        self.get_or_load(gts_id).await?;
+        // Pick up references registered by other replicas
+        if let Err(e) = self.refresh_from_store().await {
+            warn!(error = %e, "Failed to refresh GTS entities from the store before deleting");
+        }
+
+        // The cache checks that nothing references the entity, so it goes
+        // first and gets the entity back if the store rejects the deletion.
+        let removed = self.repo.delete(gts_id)?;
+        if let Some(store) = &self.store
+            && let Err(e) = store.delete(gts_id).await
+        {
+            self.restore(&removed);
+            return Err(e);
+        }
+        self.notify(EntityChangeKind::Removed, &removed);
+        Ok(())
+    }
+
+    /// Puts an entity removed from the cache back, reviving its tombstone.
+    fn restore(&self, removed: &GtsEntity) {
+        let restored = self
+            .repo
+            .register(&removed.content, true)
+            .and_then(|entity| {
+                if removed.deprecated {
+                    self.repo.deprecate(&entity.gts_id)
+                } else {
+                    Ok(entity)
+                }
+            });
+        if let Err(e) = restored {
+            warn!(gts_id = %removed.gts_id, error = %e, "Failed to restore GTS entity after a failed deletion");
+        }
+    }
+
+    /// Exports the entities matching `query` as a bundle, signed with the
+    /// configured key if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotInReadyMode` before ready mode, or `Internal` if the
+    /// bundle cannot be serialized.
+    pub async fn export_bundle(&self, query: &ListQuery) -> Result<String, DomainError> {
+        if !self.repo.is_ready() {
+            return Err(DomainError::NotInReadyMode);
+        }
+        let entities = self.list_refreshed(query).await?;
+        Bundle::new(entities)?.encode(self.config.bundle_signing_key.as_deref())
+    }
+
+    /// Imports a bundle, registering its entities with validation in bundle
+    /// order and deprecating those flagged as deprecated.
+    ///
+    /// With `dry_run`, entities are registered into a copy of the registry
+    /// and nothing is applied or persisted; the results are the same as a
+    /// real import would report.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotInReadyMode` before ready mode, or `InvalidBundle` if the
+    /// bundle is malformed, fails its checksums or is not signed with the
+    /// configured key.
+    pub async fn import_bundle(
+        &self,
+        bundle: &str,
+        dry_run: bool,
+    ) -> Result<Vec<RegisterResult>, DomainError> {
+        if !self.repo.is_ready() {
+            return Err(DomainError::NotInReadyMode);
+        }
+        let bundle = Bundle::decode(bundle, self.config.bundle_signing_key.as_deref())?;
+        let (contents, deprecated): (Vec<_>, Vec<_>) = bundle
+            .entries
+            .into_iter()
+            .map(|entry| (entry.content, entry.deprecated))
+            .unzip();
+
+        if dry_run {
+            let scratch = Self::new(self.repo.snapshot()?, self.config.clone());
+            return Ok(scratch.register_validated(contents));
+        }
+
+        let results = self.register_validated(contents);
+        let results = self.persist_results(results).await;
+        let mut imported = Vec::with_capacity(results.len());
+        for (result, deprecated) in results.into_iter().zip(deprecated) {
+            let result = match result {
+                RegisterResult::Ok(entity) if deprecated && !entity.deprecated => {
+                    match self.deprecate(&entity.gts_id).await {
+                        Ok(entity) => RegisterResult::Ok(entity),
+                        Err(e) => RegisterResult::Err {
+                            gts_id: Some(entity.gts_id),
+                            error: e.into(),
+                        },
+                    }
+                }
+                result => result,
+            };
+            imported.push(result);
+        }
+        Ok(imported)
+    }
+
+    /// Returns the entities that a registered entity depends on and/or that
+    /// depend on it, following at most `depth` edges.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if the entity is not registered.
+    pub async fn graph(
+        &self,
+        gts_id: &str,
+        direction: GraphDirection,
+        depth: Option<usize>,
+    ) -> Result<EntityGraph, DomainError> {
+        let root = self.get_or_load(gts_id).await?;
+        let entities = self.list_refreshed(&ListQuery::default()).await?;
+        Ok(graph::entity_graph(
+            &root.gts_id,
+            &entities,
+            direction,
+            depth,
+        ))
+    }
+
+    /// Resolves a registered type schema into a self-contained schema, with
+    /// every `$ref` to a registered schema inlined.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidGtsId` if the ID is not a type schema, `NotFound` if
+    /// it is not registered, or `CyclicReference` if the referenced schemas
+    /// form a cycle.
+    pub async fn resolve_schema(&self, gts_id: &str) -> Result<ResolvedSchema, DomainError> {
+        if !gts_id.ends_with('~') {
+            return Err(DomainError::invalid_gts_id(format!(
+                "{gts_id} is not a type schema"
+            )));
+        }
+        let root = self.get_or_load(gts_id).await?;
+        if let Err(e) = self.refresh_from_store().await {
+            warn!(error = %e, "Failed to refresh GTS entities from the store, resolving cached ones");
+        }
+        graph::resolve(&root.gts_id, &root.content, |id| {
+            self.repo
+                .get(id)
+                .ok()
+                .filter(|entity| entity.is_schema)
+                .map(|entity| entity.content)
         })
     }
 
+    /// Lists entities after picking up those registered by other replicas.
+    ///
+    /// If the store cannot be read, the cached entities are listed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the repository query fails.
+    pub async fn list_refreshed(&self, query: &ListQuery) -> Result<Vec<GtsEntity>, DomainError> {
+        if let Err(e) = self.refresh_from_store().await {
+            warn!(error = %e, "Failed to refresh GTS entities from the store, listing cached ones");
+        }
+        self.repo.list(query)
+    }
+
     /// Returns whether the registry is in ready mode.
     #[must_use]
     pub fn is_ready(&self) -> bool {
@@ -128,6 +834,19 @@ impl TypesRegistryService {
     }
 }
 
+/// Minor versions registered in `repo` of the same major version as `version`.
+fn versions_of(
+    repo: &dyn GtsRepository,
+    version: &SchemaVersion,
+) -> Result<Vec<(SchemaVersion, GtsEntity)>, DomainError> {
+    Ok(repo
+        .list(&ListQuery::new().with_is_type(true))?
+        .into_iter()
+        .filter_map(|e| SchemaVersion::parse(&e.gts_id).map(|v| (v, e)))
+        .filter(|(v, _)| v.is_sibling_of(version))
+        .collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -225,6 +944,48 @@ mod tests {
             self.is_ready.store(true, Ordering::SeqCst);
             Ok(())
         }
+
+        fn resolve_schema_refs(&self, schema: &serde_json::Value) -> serde_json::Value {
+            schema.clone()
+        }
+
+        fn validate_instances_against(
+            &self,
+            _schema: &serde_json::Value,
+            _instance_ids: &[String],
+        ) -> Vec<String> {
+            Vec::new()
+        }
+
+        fn deprecate(&self, gts_id: &str) -> Result<GtsEntity, DomainError> {
+            self.get(gts_id).map(|entity| entity.with_deprecated(true))
+        }
+
+        fn delete(&self, gts_id: &str) -> Result<GtsEntity, DomainError> {
+            self.get(gts_id)
+        }
+
+        fn evict(&self, _gts_id: &str) -> Result<(), DomainError> {
+            Ok(())
+        }
+
+        fn tombstone(&self, _gts_id: &str, _content: serde_json::Value) {}
+
+        fn snapshot(&self) -> Result<Arc<dyn GtsRepository>, DomainError> {
+            Ok(Arc::new(Self {
+                is_ready: AtomicBool::new(self.is_ready()),
+                fail_switch: self.fail_switch,
+            }))
+        }
+
+        fn staged(&self) -> Result<Arc<dyn GtsRepository>, Vec<String>> {
+            let copy = Self {
+                is_ready: AtomicBool::new(self.is_ready()),
+                fail_switch: self.fail_switch,
+            };
+            copy.switch_to_ready()?;
+            Ok(Arc::new(copy))
+        }
     }
 
     #[test]
@@ -4,4 +4,4 @@
 
 pub mod storage;
 
-pub use storage::InMemoryGtsRepository;
+pub use storage::{DbGtsStore, InMemoryGtsRepository};
@@ -0,0 +1,242 @@
+//! Database-backed storage for GTS entities using `modkit-db`.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use gts::GtsID;
+use modkit_db::secure::{SecureEntityExt, SecureInsertExt, SecureOnConflict, SecureUpdateExt};
+use modkit_db::{DBProvider, DbError};
+use modkit_security::AccessScope;
+use sea_orm::sea_query::Expr;
+use sea_orm::{ActiveValue, ColumnTrait, EntityTrait, QueryFilter};
+use tracing::warn;
+use types_registry_sdk::GtsEntity;
+use uuid::Uuid;
+
+use super::instance_entity::{self, Entity as InstanceEntity};
+use super::schema_entity::{self, Entity as SchemaEntity};
+use crate::domain::error::DomainError;
+use crate::domain::repo::{GtsStore, StoredEntity};
+
+/// [`GtsStore`] backed by the module database, partitioned by registry id.
+///
+/// Schemas and instances live in separate tables; content is stored as
+/// serialized JSON so the tables are portable across backends.
+pub struct DbGtsStore {
+    db: DBProvider<DbError>,
+    registry_id: Uuid,
+    scope: AccessScope,
+}
+
+impl DbGtsStore {
+    /// Creates a store for the entities of `registry_id`.
+    #[must_use]
+    pub fn new(db: DBProvider<DbError>, registry_id: Uuid) -> Self {
+        Self {
+            db,
+            registry_id,
+            scope: AccessScope::tenant(registry_id),
+        }
+    }
+
+    async fn save_schema(&self, entity: &GtsEntity, content: String) -> Result<(), DomainError> {
+        let conn = self.db.conn()?;
+        let now = Utc::now();
+        let am = schema_entity::ActiveModel {
+            registry_id: ActiveValue::Set(self.registry_id),
+            id: ActiveValue::Set(entity.id),
+            gts_id: ActiveValue::Set(entity.gts_id.clone()),
+            content: ActiveValue::Set(content),
+            description: ActiveValue::Set(entity.description.clone()),
+            deprecated: ActiveValue::Set(entity.deprecated),
+            deleted_at: ActiveValue::Set(None),
+            created_at: ActiveValue::Set(now),
+            updated_at: ActiveValue::Set(now),
+        };
+
+        // created_at is kept from the first save
+        let on_conflict = SecureOnConflict::<SchemaEntity>::columns([
+            schema_entity::Column::RegistryId,
+            schema_entity::Column::Id,
+        ])
+        .update_columns([
+            schema_entity::Column::GtsId,
+            schema_entity::Column::Content,
+            schema_entity::Column::Description,
+            schema_entity::Column::Deprecated,
+            schema_entity::Column::DeletedAt,
+            schema_entity::Column::UpdatedAt,
+        ])?;
+
+        SchemaEntity::insert(am.clone())
+            .secure()
+            .scope_with_model(&self.scope, &am)?
+            .on_conflict(on_conflict)
+            .exec(&conn)
+            .await?;
+        Ok(())
+    }
+
+    async fn save_instance(&self, entity: &GtsEntity, content: String) -> Result<(), DomainError> {
+        let conn = self.db.conn()?;
+        let now = Utc::now();
+        let am = instance_entity::ActiveModel {
+            registry_id: ActiveValue::Set(self.registry_id),
+            id: ActiveValue::Set(entity.id),
+            gts_id: ActiveValue::Set(entity.gts_id.clone()),
+            type_id: ActiveValue::Set(instance_type_id(&entity.gts_id).to_owned()),
+            content: ActiveValue::Set(content),
+            description: ActiveValue::Set(entity.description.clone()),
+            deprecated: ActiveValue::Set(entity.deprecated),
+            deleted_at: ActiveValue::Set(None),
+            created_at: ActiveValue::Set(now),
+            updated_at: ActiveValue::Set(now),
+        };
+
+        let on_conflict = SecureOnConflict::<InstanceEntity>::columns([
+            instance_entity::Column::RegistryId,
+            instance_entity::Column::Id,
+        ])
+        .update_columns([
+            instance_entity::Column::GtsId,
+            instance_entity::Column::TypeId,
+            instance_entity::Column::Content,
+            instance_entity::Column::Description,
+            instance_entity::Column::Deprecated,
+            instance_entity::Column::DeletedAt,
+            instance_entity::Column::UpdatedAt,
+        ])?;
+
+        InstanceEntity::insert(am.clone())
+            .secure()
+            .scope_with_model(&self.scope, &am)?
+            .on_conflict(on_conflict)
+            .exec(&conn)
+            .await?;
+        Ok(())
+    }
+}
+
+/// GTS ID of the schema an instance conforms to: everything up to the last `~`.
+fn instance_type_id(gts_id: &str) -> &str {
+    gts_id.rfind('~').map_or(gts_id, |pos| &gts_id[..=pos])
+}
+
+/// Parses a stored row, skipping rows whose content is not valid JSON.
+fn parse_row(
+    gts_id: &str,
+    content: &str,
+    deprecated: bool,
+    deleted_at: Option<chrono::DateTime<Utc>>,
+) -> Option<StoredEntity> {
+    match serde_json::from_str(content) {
+        Ok(content) => Some(StoredEntity {
+            content,
+            deprecated,
+            deleted: deleted_at.is_some(),
+        }),
+        Err(e) => {
+            warn!(gts_id = %gts_id, error = %e, "Skipping stored GTS entity with invalid JSON");
+            None
+        }
+    }
+}
+
+#[async_trait]
+impl GtsStore for DbGtsStore {
+    async fn load_all(&self) -> Result<Vec<StoredEntity>, DomainError> {
+        let conn = self.db.conn()?;
+        let schemas = SchemaEntity::find()
+            .secure()
+            .scope_with(&self.scope)
+            .order_by(schema_entity::Column::GtsId, sea_orm::Order::Asc)
+            .all(&conn)
+            .await?;
+        let instances = InstanceEntity::find()
+            .secure()
+            .scope_with(&self.scope)
+            .order_by(instance_entity::Column::GtsId, sea_orm::Order::Asc)
+            .all(&conn)
+            .await?;
+
+        let schemas = schemas
+            .iter()
+            .filter_map(|m| parse_row(&m.gts_id, &m.content, m.deprecated, m.deleted_at));
+        let instances = instances
+            .iter()
+            .filter_map(|m| parse_row(&m.gts_id, &m.content, m.deprecated, m.deleted_at));
+        Ok(schemas.chain(instances).collect())
+    }
+
+    async fn load(&self, gts_id: &str) -> Result<Option<StoredEntity>, DomainError> {
+        let id = GtsID::new(gts_id)
+            .map_err(|e| DomainError::invalid_gts_id(e.to_string()))?
+            .to_uuid();
+        let conn = self.db.conn()?;
+
+        let row = if gts_id.ends_with('~') {
+            SchemaEntity::find()
+                .filter(schema_entity::Column::Id.eq(id))
+                .secure()
+                .scope_with(&self.scope)
+                .one(&conn)
+                .await?
+                .map(|m| (m.content, m.deprecated, m.deleted_at))
+        } else {
+            InstanceEntity::find()
+                .filter(instance_entity::Column::Id.eq(id))
+                .secure()
+                .scope_with(&self.scope)
+                .one(&conn)
+                .await?
+                .map(|m| (m.content, m.deprecated, m.deleted_at))
+        };
+
+        Ok(row.and_then(|(content, deprecated, deleted_at)| {
+            parse_row(gts_id, &content, deprecated, deleted_at)
+        }))
+    }
+
+    async fn save(&self, entities: &[GtsEntity]) -> Result<(), DomainError> {
+        for entity in entities {
+            let content = serde_json::to_string(&entity.content)
+                .map_err(|e| DomainError::Internal(e.into()))?;
+            if entity.is_schema {
+                self.save_schema(entity, content).await?;
+            } else {
+                self.save_instance(entity, content).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn delete(&self, gts_id: &str) -> Result<(), DomainError> {
+        let id = GtsID::new(gts_id)
+            .map_err(|e| DomainError::invalid_gts_id(e.to_string()))?
+            .to_uuid();
+        let conn = self.db.conn()?;
+        let now = Utc::now();
+
+        if gts_id.ends_with('~') {
+            SchemaEntity::update_many()
+                .col_expr(schema_entity::Column::DeletedAt, Expr::value(now))
+                .col_expr(schema_entity::Column::Deprecated, Expr::value(false))
+                .col_expr(schema_entity::Column::UpdatedAt, Expr::value(now))
+                .filter(schema_entity::Column::Id.eq(id))
+                .secure()
+                .scope_with(&self.scope)
+                .exec(&conn)
+                .await?;
+        } else {
+            InstanceEntity::update_many()
+                .col_expr(instance_entity::Column::DeletedAt, Expr::value(now))
+                .col_expr(instance_entity::Column::Deprecated, Expr::value(false))
+                .col_expr(instance_entity::Column::UpdatedAt, Expr::value(now))
+                .filter(instance_entity::Column::Id.eq(id))
+                .secure()
+                .scope_with(&self.scope)
+                .exec(&conn)
+                .await?;
+        }
+        Ok(())
+    }
+}
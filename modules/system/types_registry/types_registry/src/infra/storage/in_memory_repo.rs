@@ -1,15 +1,18 @@
 //! In-memory repository implementation using gts-rust.
 
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 
-use gts::{GtsConfig, GtsID, GtsIdSegment, GtsOps, GtsWildcard};
+use gts::{GtsConfig, GtsID, GtsIdSegment, GtsOps};
 use parking_lot::Mutex;
-use types_registry_sdk::{GtsEntity, ListQuery, SegmentMatchScope};
+use types_registry_sdk::{GtsEntity, ListQuery};
 
 use super::debug_diagnostics::{
     log_instance_validation_failure, log_registration_failure, log_schema_validation_failure,
 };
 use crate::domain::error::DomainError;
+use crate::domain::graph;
 use crate::domain::repo::GtsRepository;
 
 /// In-memory repository for GTS entities using gts-rust.
@@ -25,6 +28,10 @@ pub struct InMemoryGtsRepository {
     temporary: Mutex<GtsOps>,
     /// Persistent storage after ready commit.
     persistent: Mutex<GtsOps>,
+    /// GTS IDs of deprecated entities.
+    deprecated: Mutex<HashSet<String>>,
+    /// Last content of deleted entities, keyed by GTS ID.
+    tombstones: Mutex<HashMap<String, serde_json::Value>>,
     /// Flag indicating ready mode.
     is_ready: AtomicBool,
     /// GTS configuration.
@@ -38,6 +45,8 @@ impl InMemoryGtsRepository {
         Self {
             temporary: Mutex::new(GtsOps::new(None, None, 0)),
             persistent: Mutex::new(GtsOps::new(None, None, 0)),
+            deprecated: Mutex::new(HashSet::new()),
+            tombstones: Mutex::new(HashMap::new()),
             is_ready: AtomicBool::new(false),
             config,
         }
@@ -68,6 +77,75 @@ impl InMemoryGtsRepository {
         ))
     }
 
+    /// Converts stored content to a `GtsEntity` carrying its deprecation flag.
+    fn to_entity(
+        &self,
+        gts_id: &str,
+        content: &serde_json::Value,
+    ) -> Result<GtsEntity, DomainError> {
+        let deprecated = self.deprecated.lock().contains(gts_id);
+        Self::to_gts_entity(gts_id, content).map(|entity| entity.with_deprecated(deprecated))
+    }
+
+    /// Refuses to reuse the GTS ID of a deleted entity for different content.
+    fn check_tombstone(&self, gts_id: &str, entity: &serde_json::Value) -> Result<(), DomainError> {
+        match self.tombstones.lock().get(gts_id) {
+            Some(content) if content != entity => Err(DomainError::tombstoned(gts_id)),
+            _ => Ok(()),
+        }
+    }
+
+    /// GTS IDs of the entities that would break if `gts_id` were removed:
+    /// derived schemas, instances, and schemas that `$ref` it.
+    fn referencing_ids(ops: &GtsOps, gts_id: &str) -> Vec<String> {
+        if !gts_id.ends_with('~') {
+            return Vec::new();
+        }
+        let mut ids: Vec<String> = ops
+            .store
+            .items()
+            .filter(|(id, entity)| {
+                id.as_str() != gts_id
+                    && (id.starts_with(gts_id)
+                        || entity.schema_id.as_deref() == Some(gts_id)
+                        || (entity.is_schema
+                            && graph::ref_targets(&entity.content).contains(gts_id)))
+            })
+            .map(|(id, _)| id.clone())
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    /// Removes an unreferenced entity from ready mode storage, returning it
+    /// and its content.
+    fn remove(&self, gts_id: &str) -> Result<(GtsEntity, serde_json::Value), DomainError> {
+        if !self.is_ready.load(Ordering::SeqCst) {
+            return Err(DomainError::NotInReadyMode);
+        }
+
+        let mut persistent = self.persistent.lock();
+        let content = persistent
+            .store
+            .get(gts_id)
+            .map(|entity| entity.content.clone())
+            .ok_or_else(|| DomainError::not_found(gts_id))?;
+
+        let referenced_by = Self::referencing_ids(&persistent, gts_id);
+        if !referenced_by.is_empty() {
+            return Err(DomainError::in_use(gts_id, &referenced_by));
+        }
+
+        let removed = self.to_entity(gts_id, &content)?;
+
+        // gts-rust has no removal API: rebuild the store without the entity.
+        *persistent = copy_ops(&persistent, Some(gts_id))?;
+        drop(persistent);
+
+        self.deprecated.lock().remove(gts_id);
+        Ok((removed, content))
+    }
+
     /// Extracts the GTS ID from an entity JSON value using configured fields.
     ///
     /// Strips the `gts://` URI prefix from `$id` fields for JSON Schema compatibility (gts-rust v0.7.0+).
@@ -88,50 +166,44 @@ impl InMemoryGtsRepository {
         None
     }
 
-    /// Checks if an entity matches the given query filters.
-    fn matches_query(entity: &GtsEntity, query: &ListQuery) -> bool {
-        if let Some(ref pattern) = query.pattern
-            && let Ok(wildcard) = GtsWildcard::new(pattern)
-        {
-            if let Ok(gts_id) = GtsID::new(&entity.gts_id) {
-                if !gts_id.wildcard_match(&wildcard) {
-                    return false;
+    /// Rewrites an instance so that it reads as an instance of `new_schema_id`:
+    /// its ID moves under the new type and type references to `old_schema_id`
+    /// point to the new one.
+    fn retarget_instance(
+        &self,
+        content: &serde_json::Value,
+        instance_id: &str,
+        old_schema_id: &str,
+        new_schema_id: &str,
+    ) -> (String, serde_json::Value) {
+        let new_id = format!("{new_schema_id}{}", &instance_id[old_schema_id.len()..]);
+        let mut content = content.clone();
+        if let Some(obj) = content.as_object_mut() {
+            let fields = self
+                .config
+                .entity_id_fields
+                .iter()
+                .map(|f| (f, instance_id, new_id.as_str()))
+                .chain(
+                    self.config
+                        .schema_id_fields
+                        .iter()
+                        .map(|f| (f, old_schema_id, new_schema_id)),
+                );
+            for (field, from, to) in fields {
+                if let Some(serde_json::Value::String(value)) = obj.get_mut(field.as_str()) {
+                    let prefix = if value.starts_with("gts://") {
+                        "gts://"
+                    } else {
+                        ""
+                    };
+                    if value.strip_prefix(prefix) == Some(from) {
+                        *value = format!("{prefix}{to}");
+                    }
                 }
-            } else {
-                return false;
             }
         }
-
-        if let Some(is_type) = query.is_type
-            && entity.is_type() != is_type
-        {
-            return false;
-        }
-
-        let segments_to_check: Vec<&GtsIdSegment> = match query.segment_scope {
-            SegmentMatchScope::Primary => entity.segments.first().into_iter().collect(),
-            SegmentMatchScope::Any => entity.segments.iter().collect(),
-        };
-
-        if let Some(ref vendor) = query.vendor
-            && !segments_to_check.iter().any(|s| s.vendor == *vendor)
-        {
-            return false;
-        }
-
-        if let Some(ref package) = query.package
-            && !segments_to_check.iter().any(|s| s.package == *package)
-        {
-            return false;
-        }
-
-        if let Some(ref namespace) = query.namespace
-            && !segments_to_check.iter().any(|s| s.namespace == *namespace)
-        {
-            return false;
-        }
-
-        true
+        (new_id, content)
     }
 }
 
@@ -146,17 +218,35 @@ impl GtsRepository for InMemoryGtsRepository {
             .ok_or_else(|| DomainError::invalid_gts_id("No GTS ID field found in entity"))?;
 
         GtsID::new(&gts_id).map_err(|e| DomainError::invalid_gts_id(e.to_string()))?;
+        self.check_tombstone(&gts_id, entity)?;
 
-        if self.is_ready.load(Ordering::SeqCst) {
+        let registered = if self.is_ready.load(Ordering::SeqCst) {
             let mut persistent = self.persistent.lock();
 
             if let Some(existing) = persistent.store.get(&gts_id) {
                 if existing.content == *entity {
-                    return Self::to_gts_entity(&gts_id, entity);
+                    return self.to_entity(&gts_id, entity);
                 }
                 return Err(DomainError::already_exists(&gts_id));
             }
 
+            if gts_id.ends_with('~') {
+                let refs = graph::ref_targets(entity);
+                let cycle = graph::find_cycle_from(&gts_id, |id| {
+                    if id == gts_id {
+                        return refs.clone();
+                    }
+                    persistent
+                        .store
+                        .get(id)
+                        .map(|existing| graph::ref_targets(&existing.content))
+                        .unwrap_or_default()
+                });
+                if let Some(cycle) = cycle {
+                    return Err(DomainError::cyclic_reference(&cycle));
+                }
+            }
+
             let result = persistent.add_entity(entity, validate);
             if !result.ok {
                 // Debug logging for registration failure
@@ -173,13 +263,13 @@ impl GtsRepository for InMemoryGtsRepository {
                 return Err(DomainError::validation_failed(result.error));
             }
 
-            Self::to_gts_entity(&gts_id, entity)
+            Self::to_gts_entity(&gts_id, entity)?
         } else {
             let mut temporary = self.temporary.lock();
 
             if let Some(existing) = temporary.store.get(&gts_id) {
                 if existing.content == *entity {
-                    return Self::to_gts_entity(&gts_id, entity);
+                    return self.to_entity(&gts_id, entity);
                 }
                 return Err(DomainError::already_exists(&gts_id));
             }
@@ -191,15 +281,19 @@ impl GtsRepository for InMemoryGtsRepository {
                 return Err(DomainError::validation_failed(result.error));
             }
 
-            Self::to_gts_entity(&gts_id, entity)
-        }
+            Self::to_gts_entity(&gts_id, entity)?
+        };
+
+        // Re-registering the exact content of a deleted entity revives it.
+        self.tombstones.lock().remove(&gts_id);
+        Ok(registered)
     }
 
     fn get(&self, gts_id: &str) -> Result<GtsEntity, DomainError> {
         let mut persistent = self.persistent.lock();
 
         if let Some(entity) = persistent.store.get(gts_id) {
-            return Self::to_gts_entity(gts_id, &entity.content);
+            return self.to_entity(gts_id, &entity.content);
         }
 
         Err(DomainError::not_found(gts_id))
@@ -210,8 +304,8 @@ impl GtsRepository for InMemoryGtsRepository {
         let mut results = Vec::new();
 
         for (gts_id, gts_entity) in persistent.store.items() {
-            if let Ok(entity) = Self::to_gts_entity(gts_id, &gts_entity.content)
-                && Self::matches_query(&entity, query)
+            if let Ok(entity) = self.to_entity(gts_id, &gts_entity.content)
+                && query.matches(&entity)
             {
                 results.push(entity);
             }
@@ -242,6 +336,25 @@ impl GtsRepository for InMemoryGtsRepository {
                 .partition(|id| id.ends_with('~'))
         };
 
+        // gts-rust inlines `$ref`s without a cycle guard: reject cycles
+        // before anything resolves them.
+        {
+            let temporary = self.temporary.lock();
+            let refs: BTreeMap<String, BTreeSet<String>> = temporary
+                .store
+                .items()
+                .filter(|(_, entity)| entity.is_schema)
+                .map(|(id, entity)| (id.clone(), graph::ref_targets(&entity.content)))
+                .collect();
+            for cycle in graph::find_cycles(&refs) {
+                let first = cycle.first().cloned().unwrap_or_default();
+                errors.push(format!("{first}: cyclic reference {}", cycle.join(" -> ")));
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
         // Validate all entities in temporary storage
         {
             let mut temporary = self.temporary.lock();
@@ -317,12 +430,131 @@ impl GtsRepository for InMemoryGtsRepository {
 
         Ok(())
     }
+
+    fn resolve_schema_refs(&self, schema: &serde_json::Value) -> serde_json::Value {
+        self.persistent.lock().store.resolve_schema_refs(schema)
+    }
+
+    fn validate_instances_against(
+        &self,
+        schema: &serde_json::Value,
+        instance_ids: &[String],
+    ) -> Vec<String> {
+        let Some(schema_id) = self.extract_gts_id(schema) else {
+            return vec!["unknown: No GTS ID field found in schema".to_owned()];
+        };
+
+        // Validate in a scratch store holding only the candidate schema and
+        // retargeted copies of the instances, so nothing leaks into the registry.
+        let mut scratch = GtsOps::new(None, None, 0);
+        let mut persistent = self.persistent.lock();
+        let result = scratch.add_entity(&persistent.store.resolve_schema_refs(schema), false);
+        if !result.ok {
+            return vec![format!("{schema_id}: {}", result.error)];
+        }
+
+        let mut errors = Vec::new();
+        for instance_id in instance_ids {
+            let Some(content) = persistent
+                .store
+                .get(instance_id)
+                .map(|entity| entity.content.clone())
+            else {
+                errors.push(format!("{instance_id}: instance not found"));
+                continue;
+            };
+            let Some(old_schema_id) = instance_id.rfind('~').map(|pos| &instance_id[..=pos]) else {
+                errors.push(format!("{instance_id}: not an instance of a type"));
+                continue;
+            };
+
+            let (new_id, content) =
+                self.retarget_instance(&content, instance_id, old_schema_id, &schema_id);
+            let added = scratch.add_entity(&content, false);
+            let error = if added.ok {
+                let result = scratch.validate_entity(&new_id);
+                (!result.ok).then_some(result.error)
+            } else {
+                Some(added.error)
+            };
+            if let Some(error) = error {
+                errors.push(format!("{instance_id}: {error}"));
+            }
+        }
+        errors
+    }
+
+    fn deprecate(&self, gts_id: &str) -> Result<GtsEntity, DomainError> {
+        let mut ops = if self.is_ready.load(Ordering::SeqCst) {
+            self.persistent.lock()
+        } else {
+            self.temporary.lock()
+        };
+        let content = ops
+            .store
+            .get(gts_id)
+            .map(|entity| entity.content.clone())
+            .ok_or_else(|| DomainError::not_found(gts_id))?;
+        self.deprecated.lock().insert(gts_id.to_owned());
+        Self::to_gts_entity(gts_id, &content).map(|entity| entity.with_deprecated(true))
+    }
+
+    fn delete(&self, gts_id: &str) -> Result<GtsEntity, DomainError> {
+        let (removed, content) = self.remove(gts_id)?;
+        self.tombstone(gts_id, content);
+        Ok(removed)
+    }
+
+    fn evict(&self, gts_id: &str) -> Result<(), DomainError> {
+        self.remove(gts_id).map(|_| ())
+    }
+
+    fn tombstone(&self, gts_id: &str, content: serde_json::Value) {
+        self.tombstones.lock().insert(gts_id.to_owned(), content);
+    }
+
+    fn snapshot(&self) -> Result<Arc<dyn GtsRepository>, DomainError> {
+        let copy = Self::new(self.config.clone());
+        *copy.persistent.lock() = copy_ops(&self.persistent.lock(), None)?;
+        copy.deprecated.lock().clone_from(&self.deprecated.lock());
+        copy.tombstones.lock().clone_from(&self.tombstones.lock());
+        copy.is_ready.store(self.is_ready(), Ordering::SeqCst);
+        Ok(Arc::new(copy))
+    }
+
+    fn staged(&self) -> Result<Arc<dyn GtsRepository>, Vec<String>> {
+        let copy = Self::new(self.config.clone());
+        *copy.temporary.lock() =
+            copy_ops(&self.temporary.lock(), None).map_err(|e| vec![e.to_string()])?;
+        *copy.persistent.lock() =
+            copy_ops(&self.persistent.lock(), None).map_err(|e| vec![e.to_string()])?;
+        copy.deprecated.lock().clone_from(&self.deprecated.lock());
+        copy.tombstones.lock().clone_from(&self.tombstones.lock());
+        copy.switch_to_ready()?;
+        Ok(Arc::new(copy))
+    }
+}
+
+/// Copies the entities of `ops`, except `skip`, into a new store.
+fn copy_ops(ops: &GtsOps, skip: Option<&str>) -> Result<GtsOps, DomainError> {
+    let mut copy = GtsOps::new(None, None, 0);
+    for (id, entity) in ops.store.items() {
+        if Some(id.as_str()) != skip
+            && let Err(e) = copy.store.register(entity.clone())
+        {
+            return Err(DomainError::Internal(anyhow::anyhow!(
+                "Failed to copy GTS entity {id}: {e}"
+            )));
+        }
+    }
+    Ok(copy)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use serde_json::json;
+    use types_registry_sdk::SegmentMatchScope;
 
     const JSON_SCHEMA_DRAFT_07: &str = "http://json-schema.org/draft-07/schema#";
 
@@ -722,4 +954,44 @@ mod tests {
         let result = repo.register(&entity, false);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_delete_keeps_other_entities_and_tombstones_the_id() {
+        let repo = InMemoryGtsRepository::new(default_config());
+        let user = json!({
+            "$id": "gts://gts.acme.core.events.user_created.v1~",
+            "$schema": JSON_SCHEMA_DRAFT_07,
+            "type": "object"
+        });
+        let order = json!({
+            "$id": "gts://gts.acme.core.events.order_placed.v1~",
+            "$schema": JSON_SCHEMA_DRAFT_07,
+            "type": "object"
+        });
+        repo.register(&user, false).unwrap();
+        repo.register(&order, false).unwrap();
+        assert!(matches!(
+            repo.delete("gts.acme.core.events.user_created.v1~"),
+            Err(DomainError::NotInReadyMode)
+        ));
+        repo.switch_to_ready().unwrap();
+
+        repo.deprecate("gts.acme.core.events.user_created.v1~")
+            .unwrap();
+        let removed = repo
+            .delete("gts.acme.core.events.user_created.v1~")
+            .unwrap();
+        assert!(removed.deprecated);
+        assert!(!repo.exists("gts.acme.core.events.user_created.v1~"));
+        assert!(repo.exists("gts.acme.core.events.order_placed.v1~"));
+
+        let mut reshaped = user.clone();
+        reshaped["description"] = json!("Different content");
+        assert!(matches!(
+            repo.register(&reshaped, true),
+            Err(DomainError::Tombstoned(_))
+        ));
+        let revived = repo.register(&user, true).unwrap();
+        assert!(!revived.deprecated);
+    }
 }
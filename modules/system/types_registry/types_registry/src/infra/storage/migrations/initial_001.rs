@@ -0,0 +1,111 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let backend = manager.get_database_backend();
+        let conn = manager.get_connection();
+
+        let statements: &[&str] = match backend {
+            sea_orm::DatabaseBackend::Postgres => &[
+                r"
+CREATE TABLE IF NOT EXISTS types_registry_schemas (
+    registry_id UUID NOT NULL,
+    id UUID NOT NULL,
+    gts_id TEXT NOT NULL,
+    content TEXT NOT NULL,
+    description TEXT,
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (registry_id, id)
+);
+                ",
+                r"
+CREATE TABLE IF NOT EXISTS types_registry_instances (
+    registry_id UUID NOT NULL,
+    id UUID NOT NULL,
+    gts_id TEXT NOT NULL,
+    type_id TEXT NOT NULL,
+    content TEXT NOT NULL,
+    description TEXT,
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (registry_id, id)
+);
+                ",
+            ],
+            sea_orm::DatabaseBackend::MySql => &[
+                r"
+CREATE TABLE IF NOT EXISTS types_registry_schemas (
+    registry_id VARCHAR(36) NOT NULL,
+    id VARCHAR(36) NOT NULL,
+    gts_id TEXT NOT NULL,
+    content LONGTEXT NOT NULL,
+    description TEXT,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL,
+    PRIMARY KEY (registry_id, id)
+);
+                ",
+                r"
+CREATE TABLE IF NOT EXISTS types_registry_instances (
+    registry_id VARCHAR(36) NOT NULL,
+    id VARCHAR(36) NOT NULL,
+    gts_id TEXT NOT NULL,
+    type_id TEXT NOT NULL,
+    content LONGTEXT NOT NULL,
+    description TEXT,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL,
+    PRIMARY KEY (registry_id, id)
+);
+                ",
+            ],
+            sea_orm::DatabaseBackend::Sqlite => &[
+                r"
+CREATE TABLE IF NOT EXISTS types_registry_schemas (
+    registry_id TEXT NOT NULL,
+    id TEXT NOT NULL,
+    gts_id TEXT NOT NULL,
+    content TEXT NOT NULL,
+    description TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (registry_id, id)
+);
+                ",
+                r"
+CREATE TABLE IF NOT EXISTS types_registry_instances (
+    registry_id TEXT NOT NULL,
+    id TEXT NOT NULL,
+    gts_id TEXT NOT NULL,
+    type_id TEXT NOT NULL,
+    content TEXT NOT NULL,
+    description TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (registry_id, id)
+);
+                ",
+            ],
+        };
+
+        for sql in statements {
+            conn.execute_unprepared(sql).await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+        conn.execute_unprepared("DROP TABLE IF EXISTS types_registry_instances;")
+            .await?;
+        conn.execute_unprepared("DROP TABLE IF EXISTS types_registry_schemas;")
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+/// Adds the deprecation flag and the soft-delete marker that turns a row into
+/// a tombstone.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const TABLES: [&str; 2] = ["types_registry_schemas", "types_registry_instances"];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let backend = manager.get_database_backend();
+        let conn = manager.get_connection();
+
+        let (deprecated, deleted_at) = match backend {
+            sea_orm::DatabaseBackend::Postgres => {
+                ("BOOLEAN NOT NULL DEFAULT FALSE", "TIMESTAMPTZ NULL")
+            }
+            sea_orm::DatabaseBackend::MySql => ("BOOLEAN NOT NULL DEFAULT FALSE", "TIMESTAMP NULL"),
+            sea_orm::DatabaseBackend::Sqlite => ("BOOLEAN NOT NULL DEFAULT 0", "TEXT NULL"),
+        };
+
+        for table in TABLES {
+            conn.execute_unprepared(&format!(
+                "ALTER TABLE {table} ADD COLUMN deprecated {deprecated};"
+            ))
+            .await?;
+            conn.execute_unprepared(&format!(
+                "ALTER TABLE {table} ADD COLUMN deleted_at {deleted_at};"
+            ))
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+        for table in TABLES {
+            conn.execute_unprepared(&format!("ALTER TABLE {table} DROP COLUMN deleted_at;"))
+                .await?;
+            conn.execute_unprepared(&format!("ALTER TABLE {table} DROP COLUMN deprecated;"))
+                .await?;
+        }
+        Ok(())
+    }
+}
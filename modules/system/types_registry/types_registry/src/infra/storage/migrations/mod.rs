@@ -0,0 +1,19 @@
+// `MigrationTrait` signatures elide the `SchemaManager` lifetime
+#![allow(elided_lifetimes_in_paths)]
+
+use sea_orm_migration::prelude::*;
+
+pub mod initial_001;
+pub mod lifecycle_002;
+
+pub struct Migrator;
+
+#[async_trait::async_trait]
+impl MigratorTrait for Migrator {
+    fn migrations() -> Vec<Box<dyn MigrationTrait>> {
+        vec![
+            Box::new(initial_001::Migration),
+            Box::new(lifecycle_002::Migration),
+        ]
+    }
+}
@@ -1,6 +1,11 @@
 //! Storage implementations for the Types Registry module.
 
+pub mod db_store;
 mod debug_diagnostics;
 mod in_memory_repo;
+pub mod instance_entity;
+pub mod migrations;
+pub mod schema_entity;
 
+pub use db_store::DbGtsStore;
 pub use in_memory_repo::InMemoryGtsRepository;
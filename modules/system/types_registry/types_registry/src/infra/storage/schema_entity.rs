@@ -0,0 +1,30 @@
+use modkit_db_macros::Scopable;
+use sea_orm::entity::prelude::*;
+use uuid::Uuid;
+
+/// Persisted GTS type schema. `registry_id` partitions the table between
+/// deployments sharing a database and is used as the scoping tenant.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Scopable)]
+#[sea_orm(table_name = "types_registry_schemas")]
+#[secure(tenant_col = "registry_id", resource_col = "id", no_owner, no_type)]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub registry_id: Uuid,
+    /// UUID derived from the GTS ID
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub gts_id: String,
+    /// JSON Schema document, serialized
+    pub content: String,
+    pub description: Option<String>,
+    pub deprecated: bool,
+    /// Set when the entity is deleted; the row is kept as a tombstone
+    pub deleted_at: Option<DateTimeUtc>,
+    pub created_at: DateTimeUtc,
+    pub updated_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -4,15 +4,15 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use modkit::api::OpenApiRegistry;
-use modkit::contracts::SystemCapability;
+use modkit::contracts::{DatabaseCapability, SystemCapability};
 use modkit::{Module, ModuleCtx, RestApiCapability};
 use tracing::{debug, info};
 use types_registry_sdk::TypesRegistryClient;
 
-use crate::config::TypesRegistryConfig;
+use crate::config::{StorageBackend, TypesRegistryConfig};
 use crate::domain::local_client::TypesRegistryLocalClient;
 use crate::domain::service::TypesRegistryService;
-use crate::infra::InMemoryGtsRepository;
+use crate::infra::{DbGtsStore, InMemoryGtsRepository};
 
 /// Types Registry module.
 ///
@@ -22,6 +22,7 @@ use crate::infra::InMemoryGtsRepository;
 ///
 /// - `system` — Core infrastructure module, initialized early in startup
 /// - `rest` — Exposes REST API endpoints
+/// - `db` — Persists entities when `storage: database` is configured
 ///
 /// ## Note
 ///
@@ -30,7 +31,7 @@ use crate::infra::InMemoryGtsRepository;
 /// separation of concerns and avoids circular dependencies.
 #[modkit::module(
     name = "types_registry",
-    capabilities = [system, rest]
+    capabilities = [system, rest, db]
 )]
 pub struct TypesRegistryModule {
     service: arc_swap::ArcSwapOption<TypesRegistryService>,
@@ -52,6 +53,13 @@ impl Clone for TypesRegistryModule {
     }
 }
 
+impl DatabaseCapability for TypesRegistryModule {
+    fn migrations(&self) -> Vec<Box<dyn sea_orm_migration::MigrationTrait>> {
+        use sea_orm_migration::MigratorTrait;
+        crate::infra::storage::migrations::Migrator::migrations()
+    }
+}
+
 #[async_trait]
 impl Module for TypesRegistryModule {
     async fn init(&self, ctx: &ModuleCtx) -> anyhow::Result<()> {
@@ -65,7 +73,16 @@ impl Module for TypesRegistryModule {
 
         let gts_config = cfg.to_gts_config();
         let repo = Arc::new(InMemoryGtsRepository::new(gts_config));
-        let service = Arc::new(TypesRegistryService::new(repo, cfg));
+        let mut service = TypesRegistryService::new(repo, cfg.clone());
+        if cfg.storage == StorageBackend::Database {
+            let db = ctx.db().ok_or_else(|| {
+                anyhow::anyhow!(
+                    "types_registry storage is `database` but no database is configured"
+                )
+            })?;
+            service = service.with_store(Arc::new(DbGtsStore::new(db, cfg.registry_id)));
+        }
+        let service = Arc::new(service);
 
         self.service.store(Some(service.clone()));
 
@@ -73,7 +90,7 @@ impl Module for TypesRegistryModule {
 
         ctx.client_hub().register::<dyn TypesRegistryClient>(api);
 
-        info!("Types registry module initialized");
+        info!(storage = ?cfg.storage, "Types registry module initialized");
         Ok(())
     }
 }
@@ -95,6 +112,15 @@ impl SystemCapability for TypesRegistryModule {
             .ok_or_else(|| anyhow::anyhow!("Service not initialized"))?
             .clone();
 
+        // Persisted entities are validated together with the module-registered ones
+        let restored = service
+            .restore_from_store()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to restore persisted GTS entities: {e}"))?;
+        if service.is_persistent() {
+            info!(restored, "types_registry restored persisted entities");
+        }
+
         service.switch_to_ready().map_err(|e| {
             if let Some(errors) = e.validation_errors() {
                 for err in errors {
@@ -116,6 +142,14 @@ impl SystemCapability for TypesRegistryModule {
             anyhow::anyhow!("Failed to switch to ready mode: {e}")
         })?;
 
+        let persisted = service
+            .persist_all()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to persist GTS entities: {e}"))?;
+        if service.is_persistent() {
+            info!(persisted, "types_registry persisted entities");
+        }
+
         info!("types_registry switched to ready mode successfully");
         Ok(())
     }
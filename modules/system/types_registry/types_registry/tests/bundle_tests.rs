@@ -0,0 +1,173 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Integration tests for bundle export and import
+
+use std::sync::Arc;
+
+use serde_json::{Value, json};
+use types_registry::config::TypesRegistryConfig;
+use types_registry::domain::error::DomainError;
+use types_registry::domain::service::TypesRegistryService;
+use types_registry::infra::InMemoryGtsRepository;
+use types_registry_sdk::ListQuery;
+
+const USER_TYPE: &str = "gts.acme.core.models.user.v1~";
+const ORDER_TYPE: &str = "gts.acme.core.models.order.v1~";
+const ALICE: &str = "gts.acme.core.models.user.v1~acme.core.instances.alice.v1";
+
+fn user_type() -> Value {
+    json!({
+        "$id": format!("gts://{USER_TYPE}"),
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "type": "object",
+        "properties": { "userId": { "type": "string" } },
+        "required": ["userId"]
+    })
+}
+
+fn order_type() -> Value {
+    json!({
+        "$id": format!("gts://{ORDER_TYPE}"),
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "type": "object",
+        "properties": {
+            "buyer": { "$ref": format!("gts://{USER_TYPE}") }
+        }
+    })
+}
+
+fn alice() -> Value {
+    json!({ "id": ALICE, "userId": "alice" })
+}
+
+fn registry(signing_key: Option<&str>) -> TypesRegistryService {
+    let config = TypesRegistryConfig {
+        bundle_signing_key: signing_key.map(ToOwned::to_owned),
+        ..TypesRegistryConfig::default()
+    };
+    let repo = Arc::new(InMemoryGtsRepository::new(config.to_gts_config()));
+    TypesRegistryService::new(repo, config)
+}
+
+async fn source(signing_key: Option<&str>) -> TypesRegistryService {
+    let service = registry(signing_key);
+    let results = service.register(vec![user_type(), order_type(), alice()]);
+    assert!(results.iter().all(types_registry::RegisterResult::is_ok));
+    service.switch_to_ready().unwrap();
+    service.deprecate(ORDER_TYPE).await.unwrap();
+    service
+}
+
+fn ready(signing_key: Option<&str>) -> TypesRegistryService {
+    let service = registry(signing_key);
+    service.switch_to_ready().unwrap();
+    service
+}
+
+#[tokio::test]
+async fn test_export_import_round_trip() {
+    let bundle = source(None)
+        .await
+        .export_bundle(&ListQuery::default())
+        .await
+        .unwrap();
+
+    let target = ready(None);
+    let results = target.import_bundle(&bundle, false).await.unwrap();
+    assert_eq!(results.len(), 3);
+    assert!(
+        results.iter().all(types_registry::RegisterResult::is_ok),
+        "{results:?}"
+    );
+
+    assert_eq!(target.get(ALICE).unwrap().content, alice());
+    assert!(target.get(ORDER_TYPE).unwrap().deprecated);
+    assert!(!target.get(USER_TYPE).unwrap().deprecated);
+
+    // Importing the same bundle again is idempotent
+    let results = target.import_bundle(&bundle, false).await.unwrap();
+    assert!(results.iter().all(types_registry::RegisterResult::is_ok));
+}
+
+#[tokio::test]
+async fn test_export_is_filtered_by_query() {
+    let bundle = source(None)
+        .await
+        .export_bundle(&ListQuery::default().with_is_type(true))
+        .await
+        .unwrap();
+
+    let target = ready(None);
+    let results = target.import_bundle(&bundle, false).await.unwrap();
+    assert_eq!(results.len(), 2);
+    assert!(target.get(ALICE).is_err());
+}
+
+#[tokio::test]
+async fn test_dry_run_reports_results_without_applying() {
+    let bundle = source(None)
+        .await
+        .export_bundle(&ListQuery::default())
+        .await
+        .unwrap();
+
+    // The target already has a different user type
+    let target = registry(None);
+    let mut other_user = user_type();
+    other_user["description"] = json!("A different user type");
+    let _ = target.register(vec![other_user]);
+    target.switch_to_ready().unwrap();
+
+    let results = target.import_bundle(&bundle, true).await.unwrap();
+    let failed: Vec<_> = results.iter().filter_map(|r| r.as_result().err()).collect();
+    assert_eq!(failed.len(), 1);
+    assert!(failed[0].is_already_exists());
+
+    // Nothing was applied
+    assert!(target.get(ORDER_TYPE).is_err());
+    assert!(target.get(ALICE).is_err());
+    assert_eq!(target.list(&ListQuery::default()).unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_signed_bundles_are_verified() {
+    let bundle = source(Some("secret"))
+        .await
+        .export_bundle(&ListQuery::default())
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        ready(Some("other")).import_bundle(&bundle, true).await,
+        Err(DomainError::InvalidBundle(_))
+    ));
+    let results = ready(Some("secret"))
+        .import_bundle(&bundle, false)
+        .await
+        .unwrap();
+    assert!(results.iter().all(types_registry::RegisterResult::is_ok));
+
+    // An unsigned bundle is refused when a key is configured
+    let unsigned = source(None)
+        .await
+        .export_bundle(&ListQuery::default())
+        .await
+        .unwrap();
+    assert!(matches!(
+        ready(Some("secret")).import_bundle(&unsigned, false).await,
+        Err(DomainError::InvalidBundle(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_bundles_require_ready_mode() {
+    let service = registry(None);
+    assert!(matches!(
+        service.export_bundle(&ListQuery::default()).await,
+        Err(DomainError::NotInReadyMode)
+    ));
+    assert!(matches!(
+        service.import_bundle("", true).await,
+        Err(DomainError::NotInReadyMode)
+    ));
+}
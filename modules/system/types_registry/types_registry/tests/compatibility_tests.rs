@@ -0,0 +1,205 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Integration tests for schema compatibility between minor versions
+
+use std::sync::Arc;
+
+use serde_json::{Value, json};
+use types_registry::config::TypesRegistryConfig;
+use types_registry::domain::CompatibilityMode;
+use types_registry::domain::error::DomainError;
+use types_registry::domain::service::TypesRegistryService;
+use types_registry::infra::InMemoryGtsRepository;
+use types_registry_sdk::RegisterResult;
+
+const USER_V1_0: &str = "gts.acme.core.models.user.v1.0~";
+const USER_V1_1: &str = "gts.acme.core.models.user.v1.1~";
+const USER_V1_2: &str = "gts.acme.core.models.user.v1.2~";
+const ALICE: &str = "gts.acme.core.models.user.v1.0~acme.core.instances.alice.v1";
+
+fn user_type(gts_id: &str, properties: &Value, required: &[&str]) -> Value {
+    json!({
+        "$id": format!("gts://{gts_id}"),
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "type": "object",
+        "properties": properties,
+        "required": required
+    })
+}
+
+fn base_properties() -> Value {
+    json!({
+        "userId": { "type": "string" },
+        "email": { "type": "string" }
+    })
+}
+
+/// A ready registry holding `v1.0` of the user type and one instance of it
+fn ready_service(mode: CompatibilityMode) -> TypesRegistryService {
+    let config = TypesRegistryConfig {
+        compatibility: mode,
+        ..TypesRegistryConfig::default()
+    };
+    let repo = Arc::new(InMemoryGtsRepository::new(config.to_gts_config()));
+    let service = TypesRegistryService::new(repo, config);
+    let results = service.register(vec![
+        user_type(USER_V1_0, &base_properties(), &["userId"]),
+        json!({ "id": ALICE, "userId": "user-001", "email": "alice@example.com" }),
+    ]);
+    assert!(results.iter().all(RegisterResult::is_ok));
+    service.switch_to_ready().unwrap();
+    service
+}
+
+fn register_one(service: &TypesRegistryService, entity: Value) -> RegisterResult {
+    service.register_validated(vec![entity]).pop().unwrap()
+}
+
+#[test]
+fn test_compatible_minor_version_is_accepted() {
+    let service = ready_service(CompatibilityMode::Backward);
+    let mut properties = base_properties();
+    properties["name"] = json!({ "type": "string" });
+
+    let result = register_one(&service, user_type(USER_V1_1, &properties, &["userId"]));
+    assert!(result.is_ok(), "{result:?}");
+}
+
+#[test]
+fn test_new_required_property_breaks_backward_compatibility() {
+    let service = ready_service(CompatibilityMode::Backward);
+    let mut properties = base_properties();
+    properties["name"] = json!({ "type": "string" });
+
+    let result = register_one(
+        &service,
+        user_type(USER_V1_1, &properties, &["userId", "name"]),
+    );
+    let error = result.err().unwrap();
+    assert!(error.is_incompatible_schema(), "{error}");
+    assert!(error.to_string().contains(USER_V1_0));
+    assert!(service.get(USER_V1_1).is_err());
+}
+
+#[test]
+fn test_existing_instances_are_revalidated() {
+    let service = ready_service(CompatibilityMode::Backward);
+    let mut properties = base_properties();
+    properties["email"] = json!({ "type": "string", "pattern": "@corp\\.example$" });
+
+    let result = register_one(&service, user_type(USER_V1_1, &properties, &["userId"]));
+    let error = result.err().unwrap();
+    assert!(error.is_incompatible_schema(), "{error}");
+    assert!(error.to_string().contains(ALICE));
+
+    // The instance was only validated in a scratch store
+    assert!(service.get(ALICE).is_ok());
+    assert!(service.get(USER_V1_1).is_err());
+}
+
+#[test]
+fn test_forward_mode_skips_instance_revalidation() {
+    let service = ready_service(CompatibilityMode::Forward);
+    let mut properties = base_properties();
+    properties["email"] = json!({ "type": "string", "pattern": "@corp\\.example$" });
+
+    let result = register_one(&service, user_type(USER_V1_1, &properties, &["userId"]));
+    assert!(result.is_ok(), "{result:?}");
+}
+
+#[test]
+fn test_full_mode_checks_both_directions() {
+    let service = ready_service(CompatibilityMode::Full);
+    let mut properties = base_properties();
+    properties["name"] = json!({ "type": "string" });
+
+    let backward_only = user_type(USER_V1_1, &properties, &["userId", "name"]);
+    let result = register_one(&service, backward_only);
+    assert!(result.err().unwrap().is_incompatible_schema());
+
+    let result = register_one(&service, user_type(USER_V1_1, &base_properties(), &[]));
+    assert!(result.err().unwrap().is_incompatible_schema());
+}
+
+#[test]
+fn test_mode_none_accepts_any_change() {
+    let service = ready_service(CompatibilityMode::None);
+    let result = register_one(
+        &service,
+        user_type(
+            USER_V1_1,
+            &json!({ "userId": { "type": "integer" } }),
+            &["userId"],
+        ),
+    );
+    assert!(result.is_ok(), "{result:?}");
+}
+
+#[test]
+fn test_version_is_checked_against_later_minor() {
+    let service = ready_service(CompatibilityMode::Backward);
+    let mut properties = base_properties();
+    properties["name"] = json!({ "type": "string" });
+    let result = register_one(&service, user_type(USER_V1_2, &properties, &["userId"]));
+    assert!(result.is_ok(), "{result:?}");
+
+    // Fine after v1.0, but v1.2 cannot read v1.1 data
+    properties["name"] = json!({ "type": "integer" });
+    let result = register_one(&service, user_type(USER_V1_1, &properties, &["userId"]));
+    let error = result.err().unwrap();
+    assert!(error.is_incompatible_schema());
+    assert!(error.to_string().contains(USER_V1_2));
+}
+
+#[test]
+fn test_diff_explains_changes() {
+    let service = ready_service(CompatibilityMode::None);
+    let mut properties = base_properties();
+    properties["name"] = json!({ "type": "string" });
+    properties.as_object_mut().unwrap().remove("email");
+    let result = register_one(
+        &service,
+        user_type(USER_V1_1, &properties, &["userId", "name"]),
+    );
+    assert!(result.is_ok(), "{result:?}");
+
+    let diff = service.diff(USER_V1_0, USER_V1_1).unwrap();
+    assert_eq!(diff.added_properties, vec!["name"]);
+    assert_eq!(diff.removed_properties, vec!["email"]);
+    assert_eq!(diff.newly_required, vec!["name"]);
+    assert!(!diff.is_backward_compatible());
+    assert!(
+        !CompatibilityMode::Backward
+            .breaking_changes(&diff)
+            .is_empty()
+    );
+
+    assert!(matches!(
+        service.diff(USER_V1_0, USER_V1_2),
+        Err(DomainError::NotFound(_))
+    ));
+    assert!(matches!(
+        service.diff(USER_V1_0, ALICE),
+        Err(DomainError::InvalidGtsId(_))
+    ));
+}
+
+#[test]
+fn test_ready_commit_checks_minor_versions_registered_during_configuration() {
+    let config = TypesRegistryConfig::default();
+    let repo = Arc::new(InMemoryGtsRepository::new(config.to_gts_config()));
+    let service = TypesRegistryService::new(repo, config);
+    let mut properties = base_properties();
+    properties["name"] = json!({ "type": "string" });
+    let results = service.register(vec![
+        user_type(USER_V1_0, &base_properties(), &["userId"]),
+        user_type(USER_V1_1, &properties, &["userId", "name"]),
+    ]);
+    assert!(results.iter().all(RegisterResult::is_ok));
+
+    let error = service.switch_to_ready().unwrap_err();
+    let errors = error.validation_errors().unwrap();
+    let v1_1 = errors.iter().find(|e| e.gts_id == USER_V1_1).unwrap();
+    assert!(v1_1.message.contains(USER_V1_0), "{v1_1}");
+    assert!(!service.is_ready());
+}
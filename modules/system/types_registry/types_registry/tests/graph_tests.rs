@@ -0,0 +1,249 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Integration tests for dependency graphs, schema resolution and cycle detection
+
+mod common;
+
+use common::create_service;
+use serde_json::{Value, json};
+use types_registry::domain::error::DomainError;
+use types_registry::domain::local_client::TypesRegistryLocalClient;
+use types_registry_sdk::{GraphDirection, GraphEdgeKind, TypesRegistryClient};
+
+const ADDRESS_TYPE: &str = "gts.acme.core.models.address.v1~";
+const USER_TYPE: &str = "gts.acme.core.models.user.v1~";
+const ADMIN_TYPE: &str = "gts.acme.core.models.user.v1~acme.core.models.admin.v1~";
+const ROOT: &str =
+    "gts.acme.core.models.user.v1~acme.core.models.admin.v1~acme.core.instances.root.v1";
+const NODE_A: &str = "gts.acme.core.models.node_a.v1~";
+const NODE_B: &str = "gts.acme.core.models.node_b.v1~";
+
+fn schema(gts_id: &str, body: Value) -> Value {
+    let mut schema = json!({
+        "$id": format!("gts://{gts_id}"),
+        "$schema": "http://json-schema.org/draft-07/schema#",
+    });
+    if let (Some(schema), Value::Object(body)) = (schema.as_object_mut(), body) {
+        schema.extend(body);
+    }
+    schema
+}
+
+fn address_type() -> Value {
+    schema(
+        ADDRESS_TYPE,
+        json!({ "type": "object", "properties": { "street": { "type": "string" } } }),
+    )
+}
+
+fn user_type() -> Value {
+    schema(
+        USER_TYPE,
+        json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string" },
+                "home": { "$ref": format!("gts://{ADDRESS_TYPE}") }
+            }
+        }),
+    )
+}
+
+fn admin_type() -> Value {
+    schema(
+        ADMIN_TYPE,
+        json!({
+            "allOf": [
+                { "$ref": format!("gts://{USER_TYPE}") },
+                { "type": "object", "properties": { "level": { "type": "integer" } } }
+            ]
+        }),
+    )
+}
+
+fn node(gts_id: &str, next: &str) -> Value {
+    schema(
+        gts_id,
+        json!({ "type": "object", "properties": { "next": { "$ref": format!("gts://{next}") } } }),
+    )
+}
+
+fn ready_client(entities: Vec<Value>) -> TypesRegistryLocalClient {
+    let service = create_service();
+    let results = service.register(entities);
+    assert!(
+        results
+            .iter()
+            .all(types_registry_sdk::RegisterResult::is_ok)
+    );
+    service.switch_to_ready().unwrap();
+    TypesRegistryLocalClient::new(service)
+}
+
+#[tokio::test]
+async fn test_resolve_schema_inlines_refs_recursively() {
+    let client = ready_client(vec![address_type(), user_type(), admin_type()]);
+
+    let resolved = client.resolve_schema(ADMIN_TYPE).await.unwrap();
+    assert_eq!(resolved.gts_id, ADMIN_TYPE);
+    assert_eq!(resolved.references, [ADDRESS_TYPE, USER_TYPE]);
+    assert!(resolved.unresolved.is_empty());
+    assert_eq!(resolved.schema["$id"], json!(format!("gts://{ADMIN_TYPE}")));
+
+    let user = &resolved.schema["allOf"][0];
+    assert!(user.get("$id").is_none());
+    assert!(user.get("$ref").is_none());
+    assert_eq!(
+        user["properties"]["home"]["properties"]["street"]["type"],
+        "string"
+    );
+    assert!(!resolved.schema.to_string().contains("\"$ref\""));
+}
+
+#[tokio::test]
+async fn test_resolve_schema_errors() {
+    let client = ready_client(vec![address_type()]);
+
+    assert!(
+        client
+            .resolve_schema("gts.acme.core.models.missing.v1~")
+            .await
+            .unwrap_err()
+            .is_not_found()
+    );
+    assert!(
+        client
+            .resolve_schema("gts.acme.core.models.address.v1~acme.core.instances.home.v1")
+            .await
+            .unwrap_err()
+            .is_invalid_gts_id()
+    );
+}
+
+#[tokio::test]
+async fn test_graph_dependencies_and_dependents() {
+    let service = create_service();
+    let results = service.register(vec![
+        address_type(),
+        user_type(),
+        admin_type(),
+        json!({ "id": ROOT, "name": "root", "level": 1 }),
+    ]);
+    assert!(
+        results
+            .iter()
+            .all(types_registry_sdk::RegisterResult::is_ok)
+    );
+    service.switch_to_ready().unwrap();
+
+    let graph = service
+        .graph(ADMIN_TYPE, GraphDirection::Dependencies, None)
+        .await
+        .unwrap();
+    let ids: Vec<&str> = graph.nodes.iter().map(|n| n.gts_id.as_str()).collect();
+    assert_eq!(ids, [ADDRESS_TYPE, USER_TYPE, ADMIN_TYPE]);
+    let kinds: Vec<(&str, GraphEdgeKind)> = graph
+        .edges
+        .iter()
+        .map(|e| (e.to.as_str(), e.kind))
+        .collect();
+    assert!(kinds.contains(&(USER_TYPE, GraphEdgeKind::Parent)));
+    assert!(kinds.contains(&(USER_TYPE, GraphEdgeKind::Ref)));
+    assert!(kinds.contains(&(ADDRESS_TYPE, GraphEdgeKind::Ref)));
+
+    let graph = service
+        .graph(ADDRESS_TYPE, GraphDirection::Dependents, None)
+        .await
+        .unwrap();
+    let ids: Vec<&str> = graph.nodes.iter().map(|n| n.gts_id.as_str()).collect();
+    assert_eq!(ids, [ADDRESS_TYPE, USER_TYPE, ADMIN_TYPE, ROOT]);
+
+    let graph = service
+        .graph(ADDRESS_TYPE, GraphDirection::Dependents, Some(1))
+        .await
+        .unwrap();
+    assert_eq!(graph.nodes.len(), 2);
+
+    let graph = service
+        .graph(USER_TYPE, GraphDirection::Both, Some(1))
+        .await
+        .unwrap();
+    let ids: Vec<&str> = graph.nodes.iter().map(|n| n.gts_id.as_str()).collect();
+    assert_eq!(ids, [ADDRESS_TYPE, USER_TYPE, ADMIN_TYPE]);
+
+    assert!(matches!(
+        service
+            .graph(
+                "gts.acme.core.models.missing.v1~",
+                GraphDirection::Both,
+                None
+            )
+            .await,
+        Err(DomainError::NotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_unregistered_references_are_reported() {
+    let service = create_service();
+    let _ = service.register(vec![node(NODE_A, NODE_B)]);
+    service.switch_to_ready().unwrap();
+
+    let graph = service
+        .graph(NODE_A, GraphDirection::Dependencies, None)
+        .await
+        .unwrap();
+    let missing = graph.nodes.iter().find(|n| n.gts_id == NODE_B).unwrap();
+    assert!(!missing.registered);
+
+    let client = TypesRegistryLocalClient::new(service);
+    let resolved = client.resolve_schema(NODE_A).await.unwrap();
+    assert_eq!(resolved.unresolved, [NODE_B]);
+    assert_eq!(
+        resolved.schema["properties"]["next"]["$ref"],
+        json!(format!("gts://{NODE_B}"))
+    );
+}
+
+#[tokio::test]
+async fn test_switch_to_ready_rejects_cycles() {
+    let service = create_service();
+    let _ = service.register(vec![
+        address_type(),
+        node(NODE_A, NODE_B),
+        node(NODE_B, NODE_A),
+    ]);
+
+    let err = service.switch_to_ready().unwrap_err();
+    let errors = err.validation_errors().unwrap();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].gts_id, NODE_A);
+    assert!(
+        errors[0]
+            .message
+            .contains(&format!("{NODE_A} -> {NODE_B} -> {NODE_A}")),
+        "{}",
+        errors[0].message
+    );
+    assert!(!service.is_ready());
+}
+
+#[tokio::test]
+async fn test_registration_rejects_closing_a_cycle() {
+    let client = ready_client(vec![node(NODE_A, NODE_B)]);
+
+    let results = client.register(vec![node(NODE_B, NODE_A)]).await.unwrap();
+    let err = results[0].as_result().unwrap_err();
+    assert!(err.is_cyclic_reference(), "{err}");
+
+    // A self-reference is a cycle too
+    let results = client
+        .register(vec![node(USER_TYPE, USER_TYPE)])
+        .await
+        .unwrap();
+    assert!(results[0].as_result().unwrap_err().is_cyclic_reference());
+
+    // Nothing was registered, so the schema still resolves
+    let resolved = client.resolve_schema(NODE_A).await.unwrap();
+    assert_eq!(resolved.unresolved, [NODE_B]);
+}
@@ -0,0 +1,146 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Integration tests for deprecating and deleting GTS entities
+
+mod common;
+
+use common::create_service;
+use serde_json::{Value, json};
+use types_registry::domain::local_client::TypesRegistryLocalClient;
+use types_registry_sdk::{ListQuery, TypesRegistryClient};
+
+const USER_TYPE: &str = "gts.acme.core.models.user.v1~";
+const ADMIN_TYPE: &str = "gts.acme.core.models.user.v1~acme.core.models.admin.v1~";
+const ORDER_TYPE: &str = "gts.acme.core.models.order.v1~";
+const ALICE: &str = "gts.acme.core.models.user.v1~acme.core.instances.alice.v1";
+
+fn user_type() -> Value {
+    json!({
+        "$id": format!("gts://{USER_TYPE}"),
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "type": "object",
+        "properties": { "userId": { "type": "string" } }
+    })
+}
+
+fn admin_type() -> Value {
+    json!({
+        "$id": format!("gts://{ADMIN_TYPE}"),
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "allOf": [
+            { "$ref": format!("gts://{USER_TYPE}") },
+            { "type": "object", "properties": { "level": { "type": "integer" } } }
+        ]
+    })
+}
+
+fn order_type() -> Value {
+    json!({
+        "$id": format!("gts://{ORDER_TYPE}"),
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "type": "object",
+        "properties": {
+            "buyer": { "$ref": format!("gts://{USER_TYPE}") }
+        }
+    })
+}
+
+fn alice() -> Value {
+    json!({ "id": ALICE, "userId": "alice" })
+}
+
+fn ready_client(entities: Vec<Value>) -> TypesRegistryLocalClient {
+    let service = create_service();
+    let results = service.register(entities);
+    assert!(
+        results
+            .iter()
+            .all(types_registry_sdk::RegisterResult::is_ok)
+    );
+    service.switch_to_ready().unwrap();
+    TypesRegistryLocalClient::new(service)
+}
+
+#[tokio::test]
+async fn test_deprecated_entities_stay_resolvable_and_are_flagged() {
+    let client = ready_client(vec![user_type(), alice()]);
+
+    let deprecated = client.deprecate(USER_TYPE).await.unwrap();
+    assert!(deprecated.deprecated);
+    // Idempotent
+    assert!(client.deprecate(USER_TYPE).await.unwrap().deprecated);
+
+    assert!(client.get(USER_TYPE).await.unwrap().deprecated);
+    assert!(!client.get(ALICE).await.unwrap().deprecated);
+
+    let types = client
+        .list(ListQuery::default().with_is_type(true))
+        .await
+        .unwrap();
+    assert_eq!(types.len(), 1);
+    assert!(types[0].deprecated);
+
+    // Instances of a deprecated type can still be registered
+    let bob = "gts.acme.core.models.user.v1~acme.core.instances.bob.v1";
+    let results = client
+        .register(vec![json!({ "id": bob, "userId": "bob" })])
+        .await
+        .unwrap();
+    assert!(results[0].is_ok());
+
+    let err = client
+        .deprecate("gts.acme.core.models.missing.v1~")
+        .await
+        .unwrap_err();
+    assert!(err.is_not_found());
+}
+
+#[tokio::test]
+async fn test_delete_refuses_referenced_entities() {
+    let client = ready_client(vec![user_type(), admin_type(), order_type(), alice()]);
+
+    let err = client.delete(USER_TYPE).await.unwrap_err();
+    assert!(err.is_in_use());
+    let message = err.to_string();
+    assert!(message.contains(ADMIN_TYPE), "{message}");
+    assert!(message.contains(ORDER_TYPE), "{message}");
+    assert!(message.contains(ALICE), "{message}");
+
+    client.delete(ALICE).await.unwrap();
+    client.delete(ADMIN_TYPE).await.unwrap();
+    assert!(client.delete(USER_TYPE).await.unwrap_err().is_in_use());
+    client.delete(ORDER_TYPE).await.unwrap();
+    client.delete(USER_TYPE).await.unwrap();
+
+    assert!(client.get(USER_TYPE).await.unwrap_err().is_not_found());
+    assert!(client.list(ListQuery::default()).await.unwrap().is_empty());
+    assert!(client.delete(USER_TYPE).await.unwrap_err().is_not_found());
+}
+
+#[tokio::test]
+async fn test_tombstones_prevent_reuse_with_a_different_shape() {
+    let client = ready_client(vec![user_type()]);
+    client.deprecate(USER_TYPE).await.unwrap();
+    client.delete(USER_TYPE).await.unwrap();
+
+    let mut reshaped = user_type();
+    reshaped["properties"]["userId"]["type"] = json!("integer");
+    let results = client.register(vec![reshaped]).await.unwrap();
+    let err = results[0].as_result().unwrap_err();
+    assert!(err.is_tombstoned());
+
+    // The same shape revives the entity, without its deprecation
+    let results = client.register(vec![user_type()]).await.unwrap();
+    assert!(results[0].is_ok());
+    assert!(!client.get(USER_TYPE).await.unwrap().deprecated);
+}
+
+#[tokio::test]
+async fn test_lifecycle_requires_ready_mode() {
+    let service = create_service();
+    let _ = service.register(vec![user_type()]);
+    let client = TypesRegistryLocalClient::new(service);
+
+    assert!(client.deprecate(USER_TYPE).await.is_err());
+    assert!(client.delete(USER_TYPE).await.is_err());
+}
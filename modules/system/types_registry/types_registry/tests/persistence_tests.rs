@@ -0,0 +1,333 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Integration tests for the database-backed entity store
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures::{FutureExt, StreamExt};
+use modkit_db::migration_runner::run_migrations_for_testing;
+use modkit_db::{ConnectOpts, DBProvider, DbError, connect_db};
+use sea_orm_migration::MigratorTrait;
+use serde_json::{Value, json};
+use types_registry::config::TypesRegistryConfig;
+use types_registry::domain::error::DomainError;
+use types_registry::domain::repo::{GtsStore, StoredEntity};
+use types_registry::domain::service::TypesRegistryService;
+use types_registry::infra::{DbGtsStore, InMemoryGtsRepository};
+use types_registry_sdk::{GtsEntity, ListQuery};
+use uuid::Uuid;
+
+const USER_TYPE: &str = "gts.acme.core.models.user.v1~";
+const ALICE: &str = "gts.acme.core.models.user.v1~acme.core.instances.alice.v1";
+
+fn user_type() -> Value {
+    json!({
+        "$id": format!("gts://{USER_TYPE}"),
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "type": "object",
+        "properties": {
+            "userId": { "type": "string" },
+            "email": { "type": "string" }
+        },
+        "required": ["userId", "email"]
+    })
+}
+
+fn user(gts_id: &str) -> Value {
+    json!({
+        "id": gts_id,
+        "userId": "user-001",
+        "email": "alice@example.com"
+    })
+}
+
+async fn shared_db() -> DBProvider<DbError> {
+    let db = connect_db("sqlite::memory:", ConnectOpts::default())
+        .await
+        .expect("db connect");
+    run_migrations_for_testing(
+        &db,
+        types_registry::infra::storage::migrations::Migrator::migrations(),
+    )
+    .await
+    .expect("migrate");
+    DBProvider::new(db)
+}
+
+/// A store whose writes always fail
+struct UnwritableStore;
+
+#[async_trait]
+impl GtsStore for UnwritableStore {
+    async fn load_all(&self) -> Result<Vec<StoredEntity>, DomainError> {
+        Ok(Vec::new())
+    }
+
+    async fn load(&self, _gts_id: &str) -> Result<Option<StoredEntity>, DomainError> {
+        Ok(None)
+    }
+
+    async fn save(&self, _entities: &[GtsEntity]) -> Result<(), DomainError> {
+        Err(DomainError::Internal(anyhow::anyhow!("store is read-only")))
+    }
+
+    async fn delete(&self, _gts_id: &str) -> Result<(), DomainError> {
+        Err(DomainError::Internal(anyhow::anyhow!("store is read-only")))
+    }
+}
+
+/// A ready registry holding the user type and `ALICE`, backed by a store
+/// that rejects every write
+fn unwritable() -> TypesRegistryService {
+    let config = TypesRegistryConfig::default();
+    let repo = Arc::new(InMemoryGtsRepository::new(config.to_gts_config()));
+    let service = TypesRegistryService::new(repo, config).with_store(Arc::new(UnwritableStore));
+    let _ = service.register(vec![user_type(), user(ALICE)]);
+    service.switch_to_ready().unwrap();
+    service
+}
+
+/// A replica of the registry sharing `db`, still in configuration mode
+fn replica(db: &DBProvider<DbError>, registry_id: Uuid) -> TypesRegistryService {
+    let config = TypesRegistryConfig {
+        registry_id,
+        refresh_interval_secs: 0,
+        ..TypesRegistryConfig::default()
+    };
+    let repo = Arc::new(InMemoryGtsRepository::new(config.to_gts_config()));
+    TypesRegistryService::new(repo, config)
+        .with_store(Arc::new(DbGtsStore::new(db.clone(), registry_id)))
+}
+
+/// Runs the startup sequence of the module: restore, validate, persist
+async fn start(service: &TypesRegistryService) {
+    service.restore_from_store().await.unwrap();
+    service.switch_to_ready().unwrap();
+    service.persist_all().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_entities_survive_restart() {
+    let db = shared_db().await;
+    let registry_id = Uuid::new_v4();
+
+    let first = replica(&db, registry_id);
+    let results = first.register(vec![user_type()]);
+    assert!(results[0].is_ok());
+    start(&first).await;
+
+    // Registered in ready mode, e.g. over REST
+    let results = first.register_validated(vec![user(ALICE)]);
+    let results = first.persist_results(results).await;
+    assert!(results[0].is_ok(), "{:?}", results[0]);
+
+    let restarted = replica(&db, registry_id);
+    start(&restarted).await;
+
+    let all = restarted.list(&ListQuery::default()).unwrap();
+    assert_eq!(all.len(), 2);
+    let alice = restarted.get(ALICE).unwrap();
+    assert!(alice.is_instance());
+    assert_eq!(alice.content["email"], "alice@example.com");
+}
+
+#[tokio::test]
+async fn test_registered_types_take_precedence_over_persisted() {
+    let db = shared_db().await;
+    let registry_id = Uuid::new_v4();
+
+    let first = replica(&db, registry_id);
+    let _ = first.register(vec![user_type()]);
+    start(&first).await;
+
+    // The module now ships a changed schema
+    let mut changed = user_type();
+    changed["description"] = json!("User v1, documented");
+    let restarted = replica(&db, registry_id);
+    assert!(restarted.register(vec![changed])[0].is_ok());
+    start(&restarted).await;
+
+    assert_eq!(
+        restarted.get(USER_TYPE).unwrap().description.as_deref(),
+        Some("User v1, documented")
+    );
+
+    // ... and the persisted copy is updated
+    let again = replica(&db, registry_id);
+    start(&again).await;
+    assert_eq!(
+        again.get(USER_TYPE).unwrap().description.as_deref(),
+        Some("User v1, documented")
+    );
+}
+
+#[tokio::test]
+async fn test_persisted_entities_are_validated_on_ready() {
+    let db = shared_db().await;
+    let registry_id = Uuid::new_v4();
+    let store = DbGtsStore::new(db.clone(), registry_id);
+
+    // Stored instance that does not satisfy the schema (no email)
+    let invalid = json!({ "id": ALICE, "userId": "user-001" });
+    let entity = GtsEntity::new(
+        Uuid::new_v4(),
+        ALICE.to_owned(),
+        vec![],
+        false,
+        invalid,
+        None,
+    );
+    store.save(&[entity]).await.unwrap();
+
+    let service = replica(&db, registry_id);
+    let _ = service.register(vec![user_type()]);
+    service.restore_from_store().await.unwrap();
+
+    let err = service.switch_to_ready().unwrap_err();
+    let errors = err.validation_errors().unwrap();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].gts_id, ALICE);
+}
+
+#[tokio::test]
+async fn test_replicas_read_through_the_store() {
+    let db = shared_db().await;
+    let registry_id = Uuid::new_v4();
+
+    let first = replica(&db, registry_id);
+    let _ = first.register(vec![user_type()]);
+    start(&first).await;
+    let second = replica(&db, registry_id);
+    start(&second).await;
+
+    let bob = "gts.acme.core.models.user.v1~acme.core.instances.bob.v1";
+    let results = first.register_validated(vec![user(ALICE), user(bob)]);
+    assert!(
+        first
+            .persist_results(results)
+            .await
+            .iter()
+            .all(types_registry::RegisterResult::is_ok)
+    );
+
+    // Cache miss on the second replica loads the entity from the store
+    assert!(matches!(second.get(ALICE), Err(DomainError::NotFound(_))));
+    let alice = second.get_or_load(ALICE).await.unwrap();
+    assert_eq!(alice.gts_id, ALICE);
+    assert!(second.get(ALICE).is_ok());
+
+    // Listing picks up the rest
+    let instances = second
+        .list_refreshed(&ListQuery::default().with_is_type(false))
+        .await
+        .unwrap();
+    assert_eq!(instances.len(), 2);
+
+    let missing = "gts.acme.core.models.user.v1~acme.core.instances.carol.v1";
+    assert!(matches!(
+        second.get_or_load(missing).await,
+        Err(DomainError::NotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_registries_sharing_a_database_are_isolated() {
+    let db = shared_db().await;
+
+    let first = replica(&db, Uuid::new_v4());
+    let _ = first.register(vec![user_type()]);
+    start(&first).await;
+
+    let other = replica(&db, Uuid::new_v4());
+    start(&other).await;
+    assert!(other.list(&ListQuery::default()).unwrap().is_empty());
+    assert!(other.get_or_load(USER_TYPE).await.is_err());
+}
+
+#[tokio::test]
+async fn test_deprecation_and_deletion_survive_restart_and_reach_replicas() {
+    let db = shared_db().await;
+    let registry_id = Uuid::new_v4();
+    let bob = "gts.acme.core.models.user.v1~acme.core.instances.bob.v1";
+
+    let first = replica(&db, registry_id);
+    let _ = first.register(vec![user_type(), user(ALICE), user(bob)]);
+    start(&first).await;
+    let second = replica(&db, registry_id);
+    start(&second).await;
+
+    first.deprecate(ALICE).await.unwrap();
+    first.delete(bob).await.unwrap();
+
+    // The second replica applies both on its next refresh
+    second.refresh_from_store().await.unwrap();
+    assert!(second.get(ALICE).unwrap().deprecated);
+    assert!(matches!(second.get(bob), Err(DomainError::NotFound(_))));
+
+    // The deleted row is kept as a tombstone
+    let store = DbGtsStore::new(db.clone(), registry_id);
+    let stored = store.load(bob).await.unwrap().unwrap();
+    assert!(stored.deleted);
+    assert!(!stored.deprecated);
+
+    let restarted = replica(&db, registry_id);
+    start(&restarted).await;
+    assert!(restarted.get(ALICE).unwrap().deprecated);
+    assert!(matches!(
+        restarted.get_or_load(bob).await,
+        Err(DomainError::NotFound(_))
+    ));
+
+    // The tombstone survives the restart
+    let mut reshaped = user(bob);
+    reshaped["email"] = json!("bob@example.com");
+    let results = restarted.register_validated(vec![reshaped]);
+    assert!(matches!(
+        &results[0],
+        types_registry::RegisterResult::Err { error, .. } if error.is_tombstoned()
+    ));
+
+    // Registering the previous content again revives the entity
+    let results = restarted.register_validated(vec![user(bob)]);
+    assert!(
+        restarted
+            .persist_results(results)
+            .await
+            .iter()
+            .all(types_registry::RegisterResult::is_ok)
+    );
+    assert!(!store.load(bob).await.unwrap().unwrap().deleted);
+}
+
+#[tokio::test]
+async fn test_unpersisted_registrations_are_rolled_back() {
+    let service = unwritable();
+    let mut changes = Box::pin(service.watch(ListQuery::default()));
+    let bob = "gts.acme.core.models.user.v1~acme.core.instances.bob.v1";
+
+    let results = service.register_validated(vec![user(bob)]);
+    let results = service.persist_results(results).await;
+    assert!(results[0].is_err());
+    assert!(matches!(service.get(bob), Err(DomainError::NotFound(_))));
+    assert!(changes.next().now_or_never().is_none());
+
+    // No tombstone is left behind
+    let mut reshaped = user(bob);
+    reshaped["email"] = json!("bob@example.com");
+    let results = service.register_validated(vec![reshaped]);
+    assert!(results[0].is_ok(), "{results:?}");
+}
+
+#[tokio::test]
+async fn test_unpersisted_deprecation_and_deletion_leave_the_cache_untouched() {
+    let service = unwritable();
+    let mut changes = Box::pin(service.watch(ListQuery::default()));
+
+    assert!(service.deprecate(ALICE).await.is_err());
+    assert!(!service.get(ALICE).unwrap().deprecated);
+
+    assert!(service.delete(ALICE).await.is_err());
+    assert_eq!(service.get(ALICE).unwrap().gts_id, ALICE);
+    assert!(changes.next().now_or_never().is_none());
+}
@@ -4,7 +4,7 @@
 
 mod common;
 
-use axum::extract::Json;
+use axum::response::Response;
 use common::create_service;
 use serde_json::json;
 use types_registry::api::rest::dto::ListEntitiesQuery;
@@ -258,6 +258,7 @@ async fn test_combined_vendor_package_namespace_filter() {
 #[tokio::test]
 async fn test_rest_list_handler_integration() {
     use axum::extract::{Extension, Query};
+    use axum::http::HeaderMap;
     use types_registry::api::rest::handlers::list_entities;
 
     let service = create_service();
@@ -275,16 +276,17 @@ async fn test_rest_list_handler_integration() {
         ..Default::default()
     };
 
-    let result = list_entities(Extension(service), Query(query)).await;
+    let result = list_entities(Extension(service), HeaderMap::new(), Query(query)).await;
     assert!(result.is_ok());
 
-    let Json(response) = result.unwrap();
-    assert_eq!(response.count, 2);
+    let response = json_body(result.unwrap()).await;
+    assert_eq!(response["count"], 2);
 }
 
 #[tokio::test]
 async fn test_rest_list_empty_results() {
     use axum::extract::{Extension, Query};
+    use axum::http::HeaderMap;
     use types_registry::api::rest::handlers::list_entities;
 
     let service = create_service();
@@ -296,12 +298,12 @@ async fn test_rest_list_empty_results() {
         ..Default::default()
     };
 
-    let result = list_entities(Extension(service), Query(query)).await;
+    let result = list_entities(Extension(service), HeaderMap::new(), Query(query)).await;
     assert!(result.is_ok());
 
-    let Json(response) = result.unwrap();
-    assert_eq!(response.count, 0);
-    assert!(response.entities.is_empty());
+    let response = json_body(result.unwrap()).await;
+    assert_eq!(response["count"], 0);
+    assert!(response["entities"].as_array().unwrap().is_empty());
 }
 
 // =============================================================================
@@ -311,6 +313,7 @@ async fn test_rest_list_empty_results() {
 #[tokio::test]
 async fn test_rest_get_handler_integration() {
     use axum::extract::{Extension, Path};
+    use axum::http::HeaderMap;
     use types_registry::api::rest::handlers::get_entity;
 
     let service = create_service();
@@ -327,22 +330,24 @@ async fn test_rest_get_handler_integration() {
     // Test get handler (now service is ready)
     let result = get_entity(
         Extension(service),
+        HeaderMap::new(),
         Path("gts.acme.core.events.get_test.v1~".to_owned()),
     )
     .await;
     assert!(result.is_ok());
 
-    let Json(entity) = result.unwrap();
-    assert_eq!(entity.gts_id, "gts.acme.core.events.get_test.v1~");
-    assert_eq!(
-        entity.description,
-        Some("Test entity for GET handler".to_owned())
-    );
+    let response = result.unwrap();
+    assert!(response.headers().contains_key(axum::http::header::ETAG));
+
+    let entity = json_body(response).await;
+    assert_eq!(entity["gts_id"], "gts.acme.core.events.get_test.v1~");
+    assert_eq!(entity["description"], "Test entity for GET handler");
 }
 
 #[tokio::test]
 async fn test_rest_get_handler_not_found() {
     use axum::extract::{Extension, Path};
+    use axum::http::HeaderMap;
     use types_registry::api::rest::handlers::get_entity;
 
     let service = create_service();
@@ -350,9 +355,17 @@ async fn test_rest_get_handler_not_found() {
 
     let result = get_entity(
         Extension(service),
+        HeaderMap::new(),
         Path("gts.nonexistent.pkg.ns.type.v1~".to_owned()),
     )
     .await;
 
     assert!(result.is_err());
 }
+
+async fn json_body(response: Response) -> serde_json::Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
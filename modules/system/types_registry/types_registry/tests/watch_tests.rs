@@ -0,0 +1,123 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Integration tests for watching entity changes
+
+mod common;
+
+use std::time::Duration;
+
+use common::create_service;
+use futures::{Stream, StreamExt};
+use serde_json::{Value, json};
+use types_registry::domain::local_client::TypesRegistryLocalClient;
+use types_registry_sdk::{EntityChange, EntityChangeKind, ListQuery, TypesRegistryClient};
+
+const PLUGIN_TYPE: &str = "gts.x.core.modkit.plugin.v1~";
+const PLUGIN_A: &str = "gts.x.core.modkit.plugin.v1~acme.test._.plugin_a.v1";
+const OTHER_TYPE: &str = "gts.acme.core.events.user_created.v1~";
+
+fn plugin_type() -> Value {
+    json!({
+        "$id": format!("gts://{PLUGIN_TYPE}"),
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "type": "object",
+        "properties": { "id": { "type": "string" } }
+    })
+}
+
+fn other_type() -> Value {
+    json!({
+        "$id": format!("gts://{OTHER_TYPE}"),
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "type": "object"
+    })
+}
+
+fn plugin_instances() -> ListQuery {
+    ListQuery::new()
+        .with_pattern(format!("{PLUGIN_TYPE}*"))
+        .with_is_type(false)
+}
+
+async fn next_change(changes: &mut (impl Stream<Item = EntityChange> + Unpin)) -> EntityChange {
+    tokio::time::timeout(Duration::from_secs(1), changes.next())
+        .await
+        .expect("change within timeout")
+        .expect("open stream")
+}
+
+async fn no_change(changes: &mut (impl Stream<Item = EntityChange> + Unpin)) {
+    let next = tokio::time::timeout(Duration::from_millis(50), changes.next()).await;
+    assert!(next.is_err(), "unexpected change: {next:?}");
+}
+
+#[tokio::test]
+async fn test_watch_delivers_matching_additions() {
+    let service = create_service();
+    let _ = service.register(vec![plugin_type()]);
+    service.switch_to_ready().unwrap();
+
+    let mut changes = Box::pin(service.watch(plugin_instances()));
+    let results = service.register_validated(vec![other_type(), json!({ "id": PLUGIN_A })]);
+    assert!(results.iter().all(types_registry::RegisterResult::is_ok));
+
+    let change = next_change(&mut changes).await;
+    assert_eq!(change.kind, EntityChangeKind::Added);
+    assert_eq!(change.gts_id(), PLUGIN_A);
+    no_change(&mut changes).await;
+
+    // Registering identical content again is not a change
+    let _ = service.register_validated(vec![json!({ "id": PLUGIN_A })]);
+    no_change(&mut changes).await;
+}
+
+#[tokio::test]
+async fn test_watch_reports_entities_becoming_visible_on_ready() {
+    let service = create_service();
+    let mut changes = Box::pin(service.watch(ListQuery::new().with_is_type(true)));
+
+    let _ = service.register(vec![plugin_type(), json!({ "id": PLUGIN_A })]);
+    no_change(&mut changes).await;
+
+    service.switch_to_ready().unwrap();
+    let change = next_change(&mut changes).await;
+    assert_eq!(change.kind, EntityChangeKind::Added);
+    assert_eq!(change.gts_id(), PLUGIN_TYPE);
+    no_change(&mut changes).await;
+}
+
+#[tokio::test]
+async fn test_client_watch_and_failed_registrations() {
+    let service = create_service();
+    let _ = service.register(vec![plugin_type()]);
+    service.switch_to_ready().unwrap();
+    let client = TypesRegistryLocalClient::new(service);
+
+    let mut changes = client.watch(ListQuery::default()).await.unwrap();
+    let results = client
+        .register(vec![json!({ "$id": "not-a-gts-id" }), other_type()])
+        .await
+        .unwrap();
+    assert!(results[0].is_err());
+
+    let change = next_change(&mut changes).await;
+    assert_eq!(change.gts_id(), OTHER_TYPE);
+    no_change(&mut changes).await;
+}
+
+#[tokio::test]
+async fn test_watch_ends_when_falling_behind() {
+    let service = create_service();
+    let _ = service.register(vec![plugin_type()]);
+    service.switch_to_ready().unwrap();
+
+    let mut changes = Box::pin(service.watch(plugin_instances()));
+    let plugins: Vec<Value> = (0..1100)
+        .map(|n| json!({ "id": format!("{PLUGIN_TYPE}acme.test._.plugin_{n}.v1") }))
+        .collect();
+    let results = service.register_validated(plugins);
+    assert!(results.iter().all(types_registry::RegisterResult::is_ok));
+
+    let next = tokio::time::timeout(Duration::from_secs(1), changes.next()).await;
+    assert!(matches!(next, Ok(None)), "stream should end: {next:?}");
+}